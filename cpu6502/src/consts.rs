@@ -4,4 +4,6 @@ pub type Word = u16;
 pub const STACK_PAGE_HI: Word = 0x0100;
 
 pub const BRK_INTERRUPT_VECTOR: Word = 0xFFFE;
+pub const IRQ_INTERRUPT_VECTOR: Word = 0xFFFE;
+pub const NMI_INTERRUPT_VECTOR: Word = 0xFFFA;
 pub const RESET_VECTOR: Word = 0xFFFC;
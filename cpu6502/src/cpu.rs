@@ -1,17 +1,48 @@
-use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::HashSet;
+use std::ops::Range;
+#[cfg(any(
+    feature = "memory-watch",
+    feature = "uninitialized-memory",
+    feature = "watchpoints"
+))]
+use std::ops::RangeInclusive;
 
 use super::consts::{Byte, Word};
-use crate::consts::RESET_VECTOR;
-use crate::{consts::STACK_PAGE_HI, memory::Memory};
-
+use crate::consts::{IRQ_INTERRUPT_VECTOR, NMI_INTERRUPT_VECTOR, RESET_VECTOR};
+#[cfg(feature = "watchpoints")]
+use crate::memory::watchpoints;
+#[cfg(feature = "call-trace")]
+use crate::symbols::SymbolTable;
+use crate::{consts::STACK_PAGE_HI, memory::Bus};
+
+pub mod access_log;
+pub mod assembler;
+pub mod bus_activity;
+pub mod call_trace;
+pub mod data_breakpoints;
+pub mod disassembler;
+pub mod emulation_event;
+pub mod heatmap;
+pub mod instruction_trace;
 mod instructions;
+pub mod memory_watch;
+pub mod monitor;
+pub mod opcode_coverage;
+pub mod opcode_policy;
 mod opcodes;
-mod processor_status;
+pub mod open_bus;
+pub mod processor_status;
+pub mod program_generator;
+pub mod state_diff;
+pub mod statistics;
+pub mod undocumented_opcodes;
+pub mod uninitialized_memory;
+pub mod vice_snapshot;
+pub mod watchdog;
 
 type Instruction = Byte;
 
-#[derive(Copy, Clone, PartialEq)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 enum AddressingMode {
     Accumulator,
     Immediate,
@@ -26,13 +57,43 @@ enum AddressingMode {
     AbsoluteY,
     IndexIndirectX,
     IndirectIndexY,
+    // 65C02 (zp)
+    ZeroPageIndirect,
+    // 65C02 (abs,X), used only by JMP
+    AbsoluteIndexIndirectX,
+    // 65CE02 (d,SP),Y, a pointer on the stack indexed with Y
+    StackIndirectIndexY,
+    // Rockwell zp,rel of BBR and BBS: a zero page address followed by a branch offset
+    ZeroPageRelative,
+    // 65CE02 branches with a 16-bit offset
+    RelativeWord,
+    // 65CE02 #nnnn, used only by PHW
+    ImmediateWord,
 }
 
-#[derive(Copy, Clone, PartialEq)]
-enum ChipVariant {
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum ChipVariant {
     NMOS,
+    // NES CPU: an NMOS core whose decimal mode was disconnected
+    Ricoh2A03,
+    // original 65C02, without the Rockwell bit instructions or WDC additions
+    CMOS,
     RockwellCMOS,
     WDCCMOS,
+    // CSG 65CE02, the core of the 4510 in the C65 and MEGA65: a Rockwell 65C02 with a Z
+    // register, a relocatable base page and stack, word branches and 16-bit operations
+    CSG65CE02,
+}
+
+impl ChipVariant {
+    // NMOS derived chips share the undocumented opcodes and the addressing quirks
+    fn is_nmos(&self) -> bool {
+        return matches!(self, ChipVariant::NMOS | ChipVariant::Ricoh2A03);
+    }
+
+    fn has_decimal_mode(&self) -> bool {
+        return *self != ChipVariant::Ricoh2A03;
+    }
 }
 
 #[derive(Copy, Clone, PartialEq)]
@@ -42,11 +103,14 @@ enum Registers {
     Accumulator,
     IndexX,
     IndexY,
+    IndexZ,
+    BasePage,
+    StackPointerHi,
 }
 
-type OpcodeHandler = fn(&mut CPU) -> ();
+type OpcodeHandler<M> = fn(&mut CPU<M>) -> ();
 
-pub struct CPU<'a> {
+pub struct CPU<M: Bus> {
     cycle: u64,
     chip_variant: ChipVariant,
     program_counter: Word,
@@ -54,14 +118,72 @@ pub struct CPU<'a> {
     accumulator: Byte,
     index_register_x: Byte,
     index_register_y: Byte,
+    // 65CE02 registers; they keep their reset values on every other chip, which makes zero
+    // page, page one stack and STZ behave as usual
+    index_register_z: Byte,
+    base_page: Byte,
+    stack_pointer_hi: Byte,
+    // E flag cleared, the stack pointer counts through all 16 bits
+    extended_stack: bool,
     processor_status: processor_status::ProcessorStatus,
-    memory: &'a RefCell<dyn Memory>,
-    opcode_handlers: HashMap<Byte, OpcodeHandler>,
+    memory: M,
+    opcode_handlers: instructions::OpcodeTable<M>,
+    instruction_lengths: instructions::LengthTable,
+    #[cfg(feature = "call-trace")]
+    call_trace: Option<call_trace::CallTrace>,
+    #[cfg(feature = "instruction-trace")]
+    instruction_trace: Option<instruction_trace::InstructionTrace>,
+    #[cfg(feature = "statistics")]
+    statistics: Option<statistics::Statistics>,
+    #[cfg(feature = "opcode-coverage")]
+    opcode_coverage: Option<opcode_coverage::OpcodeCoverage>,
+    #[cfg(feature = "bus-activity")]
+    bus_activity: Option<bus_activity::BusActivityCallback>,
+    #[cfg(feature = "access-log")]
+    access_log: Option<access_log::AccessLog>,
+    #[cfg(feature = "heatmap")]
+    heatmap: Option<heatmap::Heatmap>,
+    instruction_address: Word,
+    #[cfg(feature = "memory-watch")]
+    memory_watches: memory_watch::MemoryWatches,
+    #[cfg(feature = "data-breakpoints")]
+    data_breakpoints: data_breakpoints::DataBreakpoints,
+    #[cfg(feature = "watchpoints")]
+    watchpoints: watchpoints::Watchpoints,
+    breakpoints: HashSet<Word>,
+    // report every serviced NMI and IRQ as an event
+    stop_on_interrupt_entry: bool,
+    watchdog: Option<watchdog::Watchdog>,
+    undocumented_opcodes: undocumented_opcodes::UndocumentedOpcodes,
+    magic_constant: Byte,
+    jammed: bool,
+    waiting: bool,
+    stopped: bool,
+    // cycle from which the IRQ line is seen asserted
+    irq_line: Option<u64>,
+    nmi_line: bool,
+    // cycle at which an NMI edge becomes visible to the CPU, kept until the NMI is serviced
+    nmi_edge: Option<u64>,
+    so_line: bool,
+    // RDY input as set between instructions, low holds the next opcode fetch
+    ready: bool,
+    // cycles during which RDY is low, holding any read cycle that falls in them
+    rdy_low_cycles: Range<u64>,
+    // last cycle whose interrupt line state the CPU acts on at the next instruction boundary
+    interrupt_poll_cycle: u64,
+    // set by a taken branch that stays on its page, its last cycle does not poll interrupts
+    skip_interrupt_poll: bool,
+    unknown_opcode_policy: opcode_policy::UnknownOpcodePolicy,
+    open_bus: open_bus::OpenBus,
+    // last value read or written, what an unmapped address reads back as
+    data_bus: Byte,
+    #[cfg(feature = "uninitialized-memory")]
+    uninitialized_memory: Option<uninitialized_memory::UninitializedMemoryTracker>,
 }
 
-impl<'a> CPU<'a> {
-    fn new(memory: &'a RefCell<dyn Memory>, chip_variant: ChipVariant) -> Self {
-        return CPU {
+impl<M: Bus> CPU<M> {
+    fn new(memory: M, chip_variant: ChipVariant) -> Self {
+        let mut cpu = CPU {
             cycle: 0,
             chip_variant: chip_variant,
             program_counter: RESET_VECTOR,
@@ -69,44 +191,974 @@ impl<'a> CPU<'a> {
             accumulator: 0,
             index_register_x: 0,
             index_register_y: 0,
+            index_register_z: 0,
+            base_page: 0,
+            stack_pointer_hi: STACK_PAGE_HI.to_be_bytes()[0],
+            extended_stack: false,
             processor_status: processor_status::ProcessorStatus::default(),
             memory: memory,
-            opcode_handlers: instructions::get_instructions(),
+            opcode_handlers: instructions::get_instructions(chip_variant),
+            instruction_lengths: instructions::get_instruction_lengths(chip_variant),
+            #[cfg(feature = "call-trace")]
+            call_trace: None,
+            #[cfg(feature = "instruction-trace")]
+            instruction_trace: None,
+            #[cfg(feature = "statistics")]
+            statistics: None,
+            #[cfg(feature = "opcode-coverage")]
+            opcode_coverage: None,
+            #[cfg(feature = "bus-activity")]
+            bus_activity: None,
+            #[cfg(feature = "access-log")]
+            access_log: None,
+            #[cfg(feature = "heatmap")]
+            heatmap: None,
+            instruction_address: RESET_VECTOR,
+            #[cfg(feature = "memory-watch")]
+            memory_watches: memory_watch::MemoryWatches::default(),
+            #[cfg(feature = "data-breakpoints")]
+            data_breakpoints: data_breakpoints::DataBreakpoints::default(),
+            #[cfg(feature = "watchpoints")]
+            watchpoints: watchpoints::Watchpoints::default(),
+            breakpoints: HashSet::new(),
+            stop_on_interrupt_entry: false,
+            watchdog: None,
+            // undocumented opcodes are NMOS behaviour, the 65C02 reassigned them
+            undocumented_opcodes: if chip_variant.is_nmos() {
+                undocumented_opcodes::UndocumentedOpcodes::default()
+            } else {
+                undocumented_opcodes::UndocumentedOpcodes::all_disabled()
+            },
+            magic_constant: undocumented_opcodes::DEFAULT_MAGIC_CONSTANT,
+            jammed: false,
+            waiting: false,
+            stopped: false,
+            irq_line: None,
+            nmi_line: false,
+            nmi_edge: None,
+            so_line: false,
+            ready: true,
+            rdy_low_cycles: 0..0,
+            interrupt_poll_cycle: 0,
+            skip_interrupt_poll: false,
+            unknown_opcode_policy: opcode_policy::UnknownOpcodePolicy::default(),
+            open_bus: open_bus::OpenBus::default(),
+            data_bus: 0,
+            #[cfg(feature = "uninitialized-memory")]
+            uninitialized_memory: None,
         };
+        // the chip comes out of power-on through the regular reset sequence
+        cpu.reset();
+
+        return cpu;
     }
 
-    pub fn new_nmos(memory: &'a RefCell<dyn Memory>) -> Self {
+    pub fn new_nmos(memory: M) -> Self {
         return CPU::new(memory, ChipVariant::NMOS);
     }
 
-    pub fn new_rockwell_cmos(memory: &'a RefCell<dyn Memory>) -> Self {
+    pub fn new_ricoh_2a03(memory: M) -> Self {
+        return CPU::new(memory, ChipVariant::Ricoh2A03);
+    }
+
+    pub fn new_cmos(memory: M) -> Self {
+        return CPU::new(memory, ChipVariant::CMOS);
+    }
+
+    pub fn new_rockwell_cmos(memory: M) -> Self {
         return CPU::new(memory, ChipVariant::RockwellCMOS);
     }
 
-    pub fn new_wdc_cmos(memory: &'a RefCell<dyn Memory>) -> Self {
+    pub fn new_wdc_cmos(memory: M) -> Self {
         return CPU::new(memory, ChipVariant::WDCCMOS);
     }
 
+    pub fn new_65ce02(memory: M) -> Self {
+        return CPU::new(memory, ChipVariant::CSG65CE02);
+    }
+
+    // an NMOS 6502 that owns its memory outright, so it carries no borrow and can be kept in
+    // other structs; every `new_*` constructor takes owned memory the same way
+    pub fn with_memory(memory: M) -> Self {
+        return CPU::new_nmos(memory);
+    }
+
+    pub fn into_memory(self) -> M {
+        return self.memory;
+    }
+
+    // RESET runs the interrupt sequence with writes suppressed: two dummy reads at PC, three
+    // stack reads that still move SP down, and the vector fetch - seven cycles in total. The
+    // clock keeps running and A, X and Y keep their values, as on the chip
     pub fn reset(&mut self) -> () {
-        self.program_counter = self.fetch_address_from(RESET_VECTOR);
-        self.cycle = 0;
-        self.stack_pointer = 0x00;
+        self.index_register_z = 0;
+        self.base_page = 0;
+        self.stack_pointer_hi = STACK_PAGE_HI.to_be_bytes()[0];
+        self.extended_stack = false;
+        self.access_memory(self.program_counter);
+        self.tick();
+        self.access_memory(self.program_counter);
+        self.tick();
+        for _ in 0..3 {
+            self.access_memory(self.get_stack_address());
+            self.stack_pointer = self.stack_pointer.wrapping_sub(1);
+            self.tick();
+        }
+        self.processor_status.change_interrupt_disable_flag(true);
         self.processor_status.change_decimal_mode_flag(false);
-        self.accumulator = 0;
-        self.index_register_x = 0;
-        self.index_register_y = 0;
+        self.program_counter = self.fetch_address_from(RESET_VECTOR);
+        self.jammed = false;
+        self.waiting = false;
+        self.stopped = false;
+        self.poll_interrupts();
+    }
+
+    pub fn is_jammed(&self) -> bool {
+        return self.jammed;
+    }
+
+    pub fn is_waiting(&self) -> bool {
+        return self.waiting;
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        return self.stopped;
+    }
+
+    // SO is edge triggered like NMI, asserting the line sets the overflow flag right away
+    // without involving any instruction
+    pub fn set_so_line(&mut self, asserted: bool) {
+        if asserted && !self.so_line {
+            self.processor_status.change_overflow_flag(true);
+        }
+        self.so_line = asserted;
+    }
+
+    pub fn get_so_line(&self) -> bool {
+        return self.so_line;
+    }
+
+    // `false` pulls RDY low so a DMA device can take over the bus. The line is only seen
+    // between instructions, so the CPU stalls on the opcode fetch, one cycle per call to
+    // `execute_next_instruction`; `schedule_rdy_low` halts an instruction in flight.
+    pub fn set_rdy(&mut self, ready: bool) {
+        self.ready = ready;
+    }
+
+    // RDY low for `cycles`, e.g. for a DMA device that knows ahead when it takes the bus. A
+    // read cycle falling in them is repeated until RDY rises again, writes complete anyway.
+    pub fn schedule_rdy_low(&mut self, cycles: Range<u64>) {
+        self.rdy_low_cycles = cycles;
+    }
+
+    pub fn get_rdy(&self) -> bool {
+        return self.ready;
+    }
+
+    // level triggered, the line has to stay asserted until the handler acknowledges the
+    // device; `true` means the (active low) IRQ pin is pulled down. A change made between
+    // two instructions counts as made before the previous one polled the lines
+    pub fn set_irq_line(&mut self, asserted: bool) {
+        if !asserted {
+            self.irq_line = None;
+        } else if self.irq_line.is_none() {
+            self.schedule_irq(self.interrupt_poll_cycle);
+        }
+    }
+
+    pub fn get_irq_line(&self) -> bool {
+        return self.irq_line.is_some();
+    }
+
+    // asserts the IRQ line from `cycle` on, which lets a device raise it in the middle of an
+    // instruction
+    pub fn schedule_irq(&mut self, cycle: u64) {
+        self.irq_line = Some(cycle);
+    }
+
+    // NMI is edge triggered, only asserting the line latches an interrupt; keeping it
+    // asserted does not trigger another one
+    pub fn set_nmi_line(&mut self, asserted: bool) {
+        if asserted && !self.nmi_line {
+            self.schedule_nmi(self.interrupt_poll_cycle);
+        }
+        self.nmi_line = asserted;
+    }
+
+    pub fn get_nmi_line(&self) -> bool {
+        return self.nmi_line;
+    }
+
+    // latches an NMI edge that the CPU notices once its cycle counter reaches `cycle`, which
+    // lets a device interrupt in the middle of an instruction or interrupt sequence
+    pub fn schedule_nmi(&mut self, cycle: u64) {
+        self.nmi_edge = Some(cycle);
+    }
+
+    fn is_nmi_detected(&self) -> bool {
+        return self.nmi_edge.is_some_and(|cycle| cycle <= self.cycle);
+    }
+
+    // an asserted IRQ or a latched NMI wakes up a CPU waiting after WAI, even when interrupts
+    // are disabled
+    fn has_pending_interrupt(&self) -> bool {
+        return self.irq_line.is_some_and(|cycle| cycle <= self.cycle) || self.is_nmi_detected();
+    }
+
+    // the lines are sampled on the second-to-last cycle of an instruction, whatever changes
+    // on the last one waits until the next instruction finishes
+    fn poll_interrupts(&mut self) {
+        let polled_cycles_back = if self.skip_interrupt_poll { 2 } else { 1 };
+        self.interrupt_poll_cycle = self.cycle.saturating_sub(polled_cycles_back);
+        self.skip_interrupt_poll = false;
+    }
+
+    fn is_nmi_polled(&self) -> bool {
+        return self
+            .nmi_edge
+            .is_some_and(|cycle| cycle <= self.interrupt_poll_cycle);
+    }
+
+    fn should_service_irq(&self) -> bool {
+        return self
+            .irq_line
+            .is_some_and(|cycle| cycle <= self.interrupt_poll_cycle)
+            && !self.processor_status.get_interrupt_disable_flag();
+    }
+
+    // hardware interrupt sequence: two dummy reads of the next opcode, then the same pushes
+    // and vector fetch as BRK
+    fn service_interrupt(&mut self, vector: Word, source: statistics::InterruptSource) {
+        let return_address = self.program_counter;
+        self.access_memory(return_address);
+        self.tick();
+        self.access_memory(return_address);
+        self.tick();
+
+        self.enter_interrupt(return_address, vector, source);
+    }
+
+    // the break flag is not a real register bit, it only exists in the pushed status so that
+    // a handler shared by BRK and IRQ can tell them apart
+    fn enter_interrupt(
+        &mut self,
+        from: Word,
+        mut vector: Word,
+        mut source: statistics::InterruptSource,
+    ) {
+        self.push_word_to_stack(self.program_counter);
+        let mut pushed_status = self.processor_status;
+        pushed_status.change_break_flag(source == statistics::InterruptSource::Break);
+        self.push_byte_to_stack(pushed_status.into());
+        self.processor_status.change_interrupt_disable_flag(true);
+        if !self.chip_variant.is_nmos() {
+            self.processor_status.change_decimal_mode_flag(false);
+        }
+        // NMOS chips pick the vector only now, so an NMI that arrived while BRK or IRQ were
+        // pushing hijacks the sequence; the BRK itself is lost apart from the pushed B flag
+        if self.chip_variant.is_nmos() && self.is_nmi_detected() {
+            vector = NMI_INTERRUPT_VECTOR;
+            source = statistics::InterruptSource::Nmi;
+        }
+        if source == statistics::InterruptSource::Nmi {
+            self.nmi_edge = None;
+        }
+        self.program_counter = self.fetch_address_from(vector);
+        self.trace_call(call_trace::CallKind::Interrupt, from, self.program_counter);
+        self.record_interrupt_entry(source);
     }
 
     pub fn get_processor_status(&self) -> Byte {
         return self.processor_status.into();
     }
 
+    pub fn get_flags(&self) -> processor_status::ProcessorStatus {
+        return self.processor_status;
+    }
+
+    pub fn get_program_counter(&self) -> Word {
+        return self.program_counter;
+    }
+
+    pub fn set_program_counter(&mut self, addr: Word) {
+        self.program_counter = addr;
+    }
+
+    pub fn set_processor_status(&mut self, status: Byte) {
+        self.processor_status.set(status);
+    }
+
+    pub fn set_flag(&mut self, flag: processor_status::Flags, value_set: bool) {
+        self.processor_status.change_flag(flag, value_set);
+    }
+
+    pub fn get_accumulator(&self) -> Byte {
+        return self.accumulator;
+    }
+
+    pub fn set_accumulator(&mut self, value: Byte) {
+        self.accumulator = value;
+    }
+
+    pub fn get_index_register_x(&self) -> Byte {
+        return self.index_register_x;
+    }
+
+    pub fn set_index_register_x(&mut self, value: Byte) {
+        self.index_register_x = value;
+    }
+
+    pub fn get_index_register_y(&self) -> Byte {
+        return self.index_register_y;
+    }
+
+    pub fn set_index_register_y(&mut self, value: Byte) {
+        self.index_register_y = value;
+    }
+
+    pub fn get_stack_pointer(&self) -> Byte {
+        return self.stack_pointer;
+    }
+
+    pub fn set_stack_pointer(&mut self, value: Byte) {
+        self.stack_pointer = value;
+    }
+
+    pub fn get_index_register_z(&self) -> Byte {
+        return self.index_register_z;
+    }
+
+    pub fn set_index_register_z(&mut self, value: Byte) {
+        self.index_register_z = value;
+    }
+
+    pub fn get_base_page(&self) -> Byte {
+        return self.base_page;
+    }
+
+    pub fn set_base_page(&mut self, value: Byte) {
+        self.base_page = value;
+    }
+
+    pub fn get_stack_pointer_hi(&self) -> Byte {
+        return self.stack_pointer_hi;
+    }
+
+    pub fn set_stack_pointer_hi(&mut self, value: Byte) {
+        self.stack_pointer_hi = value;
+    }
+
+    pub fn is_stack_extended(&self) -> bool {
+        return self.extended_stack;
+    }
+
+    pub fn get_cycle(&self) -> u64 {
+        return self.cycle;
+    }
+
+    pub fn get_chip_variant(&self) -> ChipVariant {
+        return self.chip_variant;
+    }
+
+    pub fn get_memory(&self) -> &M {
+        return &self.memory;
+    }
+
+    // accesses made through this reference bypass the CPU: no cycles, bus callbacks or watches
+    pub fn get_memory_mut(&mut self) -> &mut M {
+        return &mut self.memory;
+    }
+
+    // moves past the instruction at PC without executing it or spending cycles; unknown opcodes
+    // are skipped as single bytes. Returns the new PC.
+    pub fn skip_instruction(&mut self) -> Word {
+        let opcode = self.memory.peek(self.program_counter);
+        let length = self.instruction_lengths[opcode as usize];
+        self.program_counter = self.program_counter.wrapping_add(length as Word);
+
+        return self.program_counter;
+    }
+
+    // FNV-1a over registers, cycle counter and the whole address space; stable between runs
+    // and builds so digests can be recorded and compared later
+    pub fn state_digest(&mut self) -> u64 {
+        const FNV_OFFSET_BASIS: u64 = 0xCBF29CE484222325;
+        const FNV_PRIME: u64 = 0x100000001B3;
+
+        let status: Byte = self.processor_status.into();
+        let mut digest = FNV_OFFSET_BASIS;
+        let mut feed = |byte: Byte| {
+            digest ^= byte as u64;
+            digest = digest.wrapping_mul(FNV_PRIME);
+        };
+        self.cycle.to_le_bytes().into_iter().for_each(&mut feed);
+        self.program_counter
+            .to_le_bytes()
+            .into_iter()
+            .for_each(&mut feed);
+        [
+            self.stack_pointer,
+            self.accumulator,
+            self.index_register_x,
+            self.index_register_y,
+            status,
+            self.index_register_z,
+            self.base_page,
+            self.stack_pointer_hi,
+            self.extended_stack as Byte,
+        ]
+        .into_iter()
+        .for_each(&mut feed);
+        (0..=Word::MAX).for_each(|addr| feed(self.memory.peek(addr)));
+
+        return digest;
+    }
+
+    pub fn snapshot(&mut self) -> state_diff::CpuSnapshot {
+        return state_diff::CpuSnapshot::capture(self);
+    }
+
+    pub fn add_breakpoint(&mut self, addr: Word) -> bool {
+        return self.breakpoints.insert(addr);
+    }
+
+    pub fn remove_breakpoint(&mut self, addr: Word) -> bool {
+        return self.breakpoints.remove(&addr);
+    }
+
+    // `true` makes entering an NMI or IRQ handler pause execution like a breakpoint would
+    pub fn set_stop_on_interrupt_entry(&mut self, stop: bool) {
+        self.stop_on_interrupt_entry = stop;
+    }
+
+    pub fn set_watchdog(&mut self, watchdog: watchdog::Watchdog) {
+        self.watchdog = Some(watchdog);
+    }
+
+    pub fn clear_watchdog(&mut self) -> Option<watchdog::Watchdog> {
+        return self.watchdog.take();
+    }
+
+    pub fn get_watchdog(&self) -> Option<&watchdog::Watchdog> {
+        return self.watchdog.as_ref();
+    }
+
+    pub fn set_undocumented_opcodes(
+        &mut self,
+        undocumented_opcodes: undocumented_opcodes::UndocumentedOpcodes,
+    ) {
+        self.undocumented_opcodes = undocumented_opcodes;
+    }
+
+    pub fn get_undocumented_opcodes(&self) -> &undocumented_opcodes::UndocumentedOpcodes {
+        return &self.undocumented_opcodes;
+    }
+
+    // CMOS chips reuse the NMOS undocumented opcodes for their own instructions, so the
+    // matrix only applies to NMOS
+    fn is_undocumented_opcode_enabled(&self, opcode: Byte) -> bool {
+        return !self.chip_variant.is_nmos() || self.undocumented_opcodes.is_enabled(opcode);
+    }
+
+    // the 2A03 keeps the D flag in the status register, but its adder ignores it
+    fn is_decimal_mode_active(&self) -> bool {
+        return self.chip_variant.has_decimal_mode()
+            && self.processor_status.get_decimal_mode_flag();
+    }
+
+    // value ORed into the accumulator by ANE and LXA; it varies between chips and even with
+    // temperature, so it has to match the machine being emulated
+    pub fn set_magic_constant(&mut self, magic_constant: Byte) {
+        self.magic_constant = magic_constant;
+    }
+
+    pub fn get_magic_constant(&self) -> Byte {
+        return self.magic_constant;
+    }
+
+    pub fn set_unknown_opcode_policy(&mut self, policy: opcode_policy::UnknownOpcodePolicy) {
+        self.unknown_opcode_policy = policy;
+    }
+
+    pub fn get_unknown_opcode_policy(&self) -> &opcode_policy::UnknownOpcodePolicy {
+        return &self.unknown_opcode_policy;
+    }
+
+    pub fn set_open_bus(&mut self, open_bus: open_bus::OpenBus) {
+        self.open_bus = open_bus;
+    }
+
+    pub fn get_open_bus(&self) -> open_bus::OpenBus {
+        return self.open_bus;
+    }
+
+    #[cfg(feature = "instruction-trace")]
+    pub fn enable_instruction_trace(&mut self, filter: instruction_trace::TraceFilter) {
+        self.instruction_trace = Some(instruction_trace::InstructionTrace::new(
+            self.chip_variant,
+            filter,
+        ));
+    }
+
+    #[cfg(feature = "instruction-trace")]
+    pub fn disable_instruction_trace(&mut self) -> Option<instruction_trace::InstructionTrace> {
+        return self.instruction_trace.take();
+    }
+
+    #[cfg(feature = "instruction-trace")]
+    pub fn get_instruction_trace(&self) -> Option<&instruction_trace::InstructionTrace> {
+        return self.instruction_trace.as_ref();
+    }
+
+    #[cfg(feature = "call-trace")]
+    pub fn enable_call_trace(&mut self, symbols: SymbolTable) {
+        self.call_trace = Some(call_trace::CallTrace::new(symbols));
+    }
+
+    #[cfg(feature = "call-trace")]
+    pub fn disable_call_trace(&mut self) -> Option<call_trace::CallTrace> {
+        return self.call_trace.take();
+    }
+
+    #[cfg(feature = "call-trace")]
+    pub fn get_call_trace(&self) -> Option<&call_trace::CallTrace> {
+        return self.call_trace.as_ref();
+    }
+
+    #[cfg(feature = "statistics")]
+    pub fn enable_statistics(&mut self) {
+        self.statistics = Some(statistics::Statistics::new());
+    }
+
+    #[cfg(feature = "statistics")]
+    pub fn disable_statistics(&mut self) -> Option<statistics::Statistics> {
+        return self.statistics.take();
+    }
+
+    #[cfg(feature = "statistics")]
+    pub fn get_statistics(&self) -> Option<&statistics::Statistics> {
+        return self.statistics.as_ref();
+    }
+
+    // the callback sees every access the CPU makes to memory, dummy ones included, in the
+    // order they happen
+    #[cfg(feature = "bus-activity")]
+    pub fn set_bus_activity_callback(&mut self, callback: bus_activity::BusActivityCallback) {
+        self.bus_activity = Some(callback);
+    }
+
+    #[cfg(feature = "bus-activity")]
+    pub fn clear_bus_activity_callback(&mut self) {
+        self.bus_activity = None;
+    }
+
+    #[cfg(feature = "access-log")]
+    pub fn enable_access_log(&mut self, capacity: usize, filter: access_log::AccessFilter) {
+        self.access_log = Some(access_log::AccessLog::new(capacity, filter));
+    }
+
+    #[cfg(feature = "access-log")]
+    pub fn disable_access_log(&mut self) -> Option<access_log::AccessLog> {
+        return self.access_log.take();
+    }
+
+    #[cfg(feature = "access-log")]
+    pub fn get_access_log(&self) -> Option<&access_log::AccessLog> {
+        return self.access_log.as_ref();
+    }
+
+    #[cfg(feature = "heatmap")]
+    pub fn enable_heatmap(&mut self, granularity: heatmap::HeatmapGranularity) {
+        self.heatmap = Some(heatmap::Heatmap::new(granularity));
+    }
+
+    #[cfg(feature = "heatmap")]
+    pub fn disable_heatmap(&mut self) -> Option<heatmap::Heatmap> {
+        return self.heatmap.take();
+    }
+
+    #[cfg(feature = "heatmap")]
+    pub fn get_heatmap(&self) -> Option<&heatmap::Heatmap> {
+        return self.heatmap.as_ref();
+    }
+
+    // only opcodes that are handled and not disabled at this point are accounted for
+    #[cfg(feature = "opcode-coverage")]
+    pub fn enable_opcode_coverage(&mut self) {
+        let supported: Vec<Byte> = (0..=Byte::MAX)
+            .filter(|opcode| self.opcode_handlers[*opcode as usize].is_some())
+            .filter(|opcode| self.is_undocumented_opcode_enabled(*opcode))
+            .collect();
+        self.opcode_coverage = Some(opcode_coverage::OpcodeCoverage::new(
+            self.chip_variant,
+            supported,
+        ));
+    }
+
+    #[cfg(feature = "opcode-coverage")]
+    pub fn disable_opcode_coverage(&mut self) -> Option<opcode_coverage::OpcodeCoverage> {
+        return self.opcode_coverage.take();
+    }
+
+    #[cfg(feature = "opcode-coverage")]
+    pub fn get_opcode_coverage(&self) -> Option<&opcode_coverage::OpcodeCoverage> {
+        return self.opcode_coverage.as_ref();
+    }
+
+    #[cfg(feature = "uninitialized-memory")]
+    pub fn enable_uninitialized_read_detection(&mut self, stop_on_read: bool) {
+        self.uninitialized_memory = Some(uninitialized_memory::UninitializedMemoryTracker::new(
+            stop_on_read,
+        ));
+    }
+
+    #[cfg(feature = "uninitialized-memory")]
+    pub fn disable_uninitialized_read_detection(
+        &mut self,
+    ) -> Option<uninitialized_memory::UninitializedMemoryTracker> {
+        return self.uninitialized_memory.take();
+    }
+
+    #[cfg(feature = "uninitialized-memory")]
+    pub fn get_uninitialized_read_detection(
+        &self,
+    ) -> Option<&uninitialized_memory::UninitializedMemoryTracker> {
+        return self.uninitialized_memory.as_ref();
+    }
+
+    #[cfg(feature = "uninitialized-memory")]
+    pub fn mark_memory_initialized(&mut self, region: RangeInclusive<Word>) {
+        if let Some(tracker) = self.uninitialized_memory.as_mut() {
+            tracker.mark_initialized(region);
+        }
+    }
+
+    #[cfg(feature = "memory-watch")]
+    pub fn watch_region(
+        &mut self,
+        region: RangeInclusive<Word>,
+        target: memory_watch::DumpTarget,
+    ) -> usize {
+        return self.memory_watches.add(region, target);
+    }
+
+    #[cfg(feature = "memory-watch")]
+    pub fn unwatch_region(&mut self, id: usize) -> bool {
+        return self.memory_watches.remove(id);
+    }
+
+    #[cfg(feature = "memory-watch")]
+    pub fn get_region_dumps(&self, id: usize) -> Option<&[memory_watch::RegionDump]> {
+        return self.memory_watches.get_log(id);
+    }
+
+    #[cfg(feature = "data-breakpoints")]
+    pub fn add_data_breakpoint(&mut self, condition: data_breakpoints::DataCondition) -> usize {
+        return self.data_breakpoints.add(condition, &mut self.memory);
+    }
+
+    #[cfg(feature = "data-breakpoints")]
+    pub fn remove_data_breakpoint(&mut self, id: usize) -> bool {
+        return self.data_breakpoints.remove(id);
+    }
+
+    #[cfg(feature = "watchpoints")]
+    pub fn watch_read(&mut self, range: RangeInclusive<Word>) -> usize {
+        return self.watchpoints.watch_read(range);
+    }
+
+    #[cfg(feature = "watchpoints")]
+    pub fn watch_write(&mut self, range: RangeInclusive<Word>) -> usize {
+        return self.watchpoints.watch_write(range);
+    }
+
+    #[cfg(feature = "watchpoints")]
+    pub fn remove_watchpoint(&mut self, id: usize) -> bool {
+        return self.watchpoints.remove(id);
+    }
+
+    #[cfg(feature = "watchpoints")]
+    fn check_watchpoints(&mut self, access: watchpoints::Access, addr: Word) {
+        if !self.watchpoints.is_empty() {
+            self.watchpoints.notify(
+                access,
+                addr,
+                self.data_bus,
+                self.instruction_address,
+                self.cycle,
+            );
+        }
+    }
+
+    // hits pause execution once the instruction or interrupt sequence making them completes
+    #[cfg(feature = "watchpoints")]
+    fn take_watchpoint_event(&mut self) -> Option<emulation_event::EmulationEvent> {
+        let hits = self.watchpoints.take_hits();
+        if hits.is_empty() {
+            return None;
+        }
+
+        return Some(emulation_event::EmulationEvent::Watchpoint(hits));
+    }
+
+    #[cfg(not(feature = "watchpoints"))]
+    #[inline(always)]
+    fn take_watchpoint_event(&mut self) -> Option<emulation_event::EmulationEvent> {
+        return None;
+    }
+
+    // a watchpoint hit by the pushes takes precedence over the entry itself
+    fn interrupt_entry_event(
+        &mut self,
+        source: statistics::InterruptSource,
+    ) -> Option<emulation_event::EmulationEvent> {
+        let watchpoint_event = self.take_watchpoint_event();
+        if watchpoint_event.is_some() || !self.stop_on_interrupt_entry {
+            return watchpoint_event;
+        }
+
+        return Some(emulation_event::EmulationEvent::InterruptEntry(source));
+    }
+
+    #[cfg(feature = "memory-watch")]
+    fn dump_watched_regions(&mut self, addr: Word, value: Byte) {
+        for (id, region) in self.memory_watches.regions_containing(addr) {
+            let contents: Vec<Byte> = {
+                region
+                    .clone()
+                    .map(|region_addr| self.memory.peek(region_addr))
+                    .collect()
+            };
+            let dump = memory_watch::RegionDump {
+                region_start: *region.start(),
+                address: addr,
+                value,
+                program_counter: self.instruction_address,
+                cycle: self.cycle,
+                contents,
+            };
+            self.memory_watches.notify(id, dump);
+        }
+    }
+
+    #[cfg(feature = "statistics")]
+    fn record_branch(&mut self, site: Word, taken: bool, page_crossed: bool) {
+        if let Some(statistics) = self.statistics.as_mut() {
+            statistics.record_branch(site, taken, page_crossed);
+        }
+    }
+
+    #[cfg(not(feature = "statistics"))]
+    #[inline(always)]
+    fn record_branch(&mut self, _site: Word, _taken: bool, _page_crossed: bool) {}
+
+    #[cfg(feature = "statistics")]
+    fn record_interrupt_entry(&mut self, source: statistics::InterruptSource) {
+        if let Some(statistics) = self.statistics.as_mut() {
+            statistics.record_interrupt_entry(source, self.cycle);
+        }
+    }
+
+    #[cfg(not(feature = "statistics"))]
+    #[inline(always)]
+    fn record_interrupt_entry(&mut self, _source: statistics::InterruptSource) {}
+
+    #[cfg(feature = "statistics")]
+    fn record_interrupt_return(&mut self) {
+        if let Some(statistics) = self.statistics.as_mut() {
+            statistics.record_interrupt_return(self.cycle);
+        }
+    }
+
+    #[cfg(not(feature = "statistics"))]
+    #[inline(always)]
+    fn record_interrupt_return(&mut self) {}
+
+    #[cfg(feature = "instruction-trace")]
+    fn trace_instruction(&mut self) {
+        if self.instruction_trace.is_none() {
+            return;
+        }
+
+        let state = program_generator::TraceEntry::capture(self);
+        if let Some(trace) = self.instruction_trace.as_mut() {
+            trace.record(&mut self.memory, state);
+        }
+    }
+
+    #[cfg(not(feature = "instruction-trace"))]
+    #[inline(always)]
+    fn trace_instruction(&mut self) {}
+
+    #[cfg(feature = "call-trace")]
+    fn trace_call(&mut self, kind: call_trace::CallKind, from: Word, target: Word) {
+        if let Some(trace) = self.call_trace.as_mut() {
+            trace.record_call(self.cycle, kind, from, target);
+        }
+    }
+
+    #[cfg(not(feature = "call-trace"))]
+    #[inline(always)]
+    fn trace_call(&mut self, _kind: call_trace::CallKind, _from: Word, _target: Word) {}
+
+    #[cfg(feature = "call-trace")]
+    fn trace_return(&mut self, kind: call_trace::CallKind) {
+        if let Some(trace) = self.call_trace.as_mut() {
+            trace.record_return(self.cycle, kind, self.program_counter);
+        }
+    }
+
+    #[cfg(not(feature = "call-trace"))]
+    #[inline(always)]
+    fn trace_return(&mut self, _kind: call_trace::CallKind) {}
+
+    #[cfg(feature = "bus-activity")]
+    fn observe_bus(&mut self, address: Word, direction: bus_activity::BusDirection, sync: bool) {
+        if let Some(callback) = self.bus_activity.as_mut() {
+            callback(&bus_activity::BusCycle {
+                cycle: self.cycle,
+                address,
+                data: self.data_bus,
+                direction,
+                sync,
+            });
+        }
+    }
+
+    #[cfg(not(feature = "bus-activity"))]
+    #[inline(always)]
+    fn observe_bus(&mut self, _address: Word, _direction: bus_activity::BusDirection, _sync: bool) {
+    }
+
+    #[cfg(feature = "access-log")]
+    fn log_access(&mut self, address: Word, direction: bus_activity::BusDirection) {
+        if let Some(log) = self.access_log.as_mut() {
+            log.record(access_log::MemoryAccess {
+                cycle: self.cycle,
+                program_counter: self.instruction_address,
+                address,
+                value: self.data_bus,
+                direction,
+            });
+        }
+    }
+
+    #[cfg(not(feature = "access-log"))]
+    #[inline(always)]
+    fn log_access(&mut self, _address: Word, _direction: bus_activity::BusDirection) {}
+
+    #[cfg(feature = "heatmap")]
+    fn record_heat(&mut self, address: Word, direction: bus_activity::BusDirection, sync: bool) {
+        if let Some(heatmap) = self.heatmap.as_mut() {
+            heatmap.record(address, direction, sync);
+        }
+    }
+
+    #[cfg(not(feature = "heatmap"))]
+    #[inline(always)]
+    fn record_heat(&mut self, _address: Word, _direction: bus_activity::BusDirection, _sync: bool) {
+    }
+
+    #[cfg(feature = "uninitialized-memory")]
+    fn track_read(&mut self, addr: Word) {
+        if let Some(tracker) = self.uninitialized_memory.as_mut() {
+            tracker.record_read(addr, self.instruction_address, self.cycle);
+        }
+    }
+
+    #[cfg(not(feature = "uninitialized-memory"))]
+    #[inline(always)]
+    fn track_read(&mut self, _addr: Word) {}
+
     fn access_memory(&mut self, addr: Word) -> Byte {
-        return self.memory.borrow()[addr];
+        self.track_read(addr);
+        return self.read_bus(addr, false);
+    }
+
+    // dummy reads reach the memory but their value is never used, so they are not tracked as
+    // reads of uninitialized memory
+    fn dummy_read(&mut self, addr: Word) {
+        self.read_bus(addr, false);
+    }
+
+    // every read goes through here; `sync` marks opcode fetches like the SYNC pin does
+    fn read_bus(&mut self, addr: Word, sync: bool) -> Byte {
+        while self.rdy_low_cycles.contains(&self.cycle) {
+            self.read_bus_cycle(addr, sync);
+            self.tick();
+        }
+
+        return self.read_bus_cycle(addr, sync);
+    }
+
+    fn read_bus_cycle(&mut self, addr: Word, sync: bool) -> Byte {
+        {
+            self.data_bus = if self.memory.is_mapped(addr) {
+                self.memory.read(addr)
+            } else {
+                match self.open_bus {
+                    open_bus::OpenBus::LastValue => self.data_bus,
+                    open_bus::OpenBus::Constant(value) => value,
+                }
+            };
+        }
+        self.observe_bus(addr, bus_activity::BusDirection::Read, sync);
+        self.log_access(addr, bus_activity::BusDirection::Read);
+        self.record_heat(addr, bus_activity::BusDirection::Read, sync);
+        #[cfg(feature = "watchpoints")]
+        self.check_watchpoints(watchpoints::Access::Read, addr);
+
+        return self.data_bus;
     }
 
     fn put_into_memory(&mut self, addr: Word, value: Byte) {
-        self.memory.borrow_mut()[addr] = value;
+        self.data_bus = value;
+        self.observe_bus(addr, bus_activity::BusDirection::Write, false);
+        self.log_access(addr, bus_activity::BusDirection::Write);
+        self.record_heat(addr, bus_activity::BusDirection::Write, false);
+        #[cfg(feature = "watchpoints")]
+        self.check_watchpoints(watchpoints::Access::Write, addr);
+        #[cfg(feature = "uninitialized-memory")]
+        if let Some(tracker) = self.uninitialized_memory.as_mut() {
+            tracker.record_write(addr);
+        }
+        #[cfg(any(feature = "memory-watch", feature = "data-breakpoints"))]
+        if self.has_write_observers() {
+            self.observe_write(addr, value);
+            return;
+        }
+
+        self.memory.write(addr, value);
+    }
+
+    #[cfg(any(feature = "memory-watch", feature = "data-breakpoints"))]
+    fn has_write_observers(&self) -> bool {
+        let mut observed = false;
+        #[cfg(feature = "memory-watch")]
+        {
+            observed |= !self.memory_watches.is_empty();
+        }
+        #[cfg(feature = "data-breakpoints")]
+        {
+            observed |= !self.data_breakpoints.is_empty();
+        }
+
+        return observed;
+    }
+
+    #[cfg(any(feature = "memory-watch", feature = "data-breakpoints"))]
+    fn observe_write(&mut self, addr: Word, value: Byte) {
+        #[cfg(feature = "memory-watch")]
+        let previous_value = self.memory.peek(addr);
+        self.memory.write(addr, value);
+        #[cfg(feature = "memory-watch")]
+        if previous_value != value {
+            self.dump_watched_regions(addr, value);
+        }
+        #[cfg(feature = "data-breakpoints")]
+        if self.data_breakpoints.is_watched(addr) {
+            self.data_breakpoints.notify_write(addr, &mut self.memory);
+        }
     }
 
     fn increment_program_counter(&mut self) {
@@ -116,12 +1168,12 @@ impl<'a> CPU<'a> {
 
     fn increment_register(&mut self, register: Registers) {
         self.set_register(register, self.get_register(register).wrapping_add(1));
-        self.cycle += 1;
+        self.idle_cycle();
     }
 
     fn decrement_register(&mut self, register: Registers) {
         self.set_register(register, self.get_register(register).wrapping_sub(1));
-        self.cycle += 1;
+        self.idle_cycle();
     }
 
     fn set_register(&mut self, register: Registers, value: Byte) {
@@ -129,10 +1181,19 @@ impl<'a> CPU<'a> {
             Registers::Accumulator => self.accumulator = value,
             Registers::IndexX => self.index_register_x = value,
             Registers::IndexY => self.index_register_y = value,
+            Registers::IndexZ => self.index_register_z = value,
             Registers::ProcessorStatus => self.processor_status.set(value),
             Registers::StackPointer => self.stack_pointer = value,
+            Registers::BasePage => self.base_page = value,
+            Registers::StackPointerHi => self.stack_pointer_hi = value,
         };
-        if register == Registers::ProcessorStatus || register == Registers::StackPointer {
+        if matches!(
+            register,
+            Registers::ProcessorStatus
+                | Registers::StackPointer
+                | Registers::BasePage
+                | Registers::StackPointerHi
+        ) {
             return;
         };
 
@@ -144,30 +1205,40 @@ impl<'a> CPU<'a> {
             Registers::Accumulator => self.accumulator,
             Registers::IndexX => self.index_register_x,
             Registers::IndexY => self.index_register_y,
+            Registers::IndexZ => self.index_register_z,
             Registers::ProcessorStatus => self.processor_status.into(),
             Registers::StackPointer => self.stack_pointer,
+            Registers::BasePage => self.base_page,
+            Registers::StackPointerHi => self.stack_pointer_hi,
         };
     }
 
+    // the low byte is indexed while the operand is still being read; only a carry into the
+    // high byte costs a cycle of its own
     fn offset_addr(&mut self, addr: Word, offset: Byte) -> Word {
-        let [lo, mut hi] = addr.to_le_bytes();
+        let [lo, hi] = addr.to_le_bytes();
         let (new_lo, carry) = lo.overflowing_add(offset);
-        let mut address = Word::from_le_bytes([new_lo, hi]);
-        self.cycle += 1;
-
+        let address = Word::from_le_bytes([new_lo, hi]);
         if !carry {
             return address;
         };
 
-        hi = hi.wrapping_add(1);
-        address = Word::from_le_bytes([new_lo, hi]);
+        // the carry reaches the high byte a cycle late; NMOS reads the address that is not
+        // fixed yet, CMOS rereads the last operand byte instead
+        let dummy_address = if self.chip_variant.is_nmos() {
+            address
+        } else {
+            self.program_counter.wrapping_sub(1)
+        };
+        self.dummy_read(dummy_address);
         self.cycle += 1;
 
-        return address;
+        return Word::from_le_bytes([new_lo, hi.wrapping_add(1)]);
     }
 
     fn fetch_instruction(&mut self) -> Instruction {
-        let opcode = self.access_memory(self.program_counter);
+        self.track_read(self.program_counter);
+        let opcode = self.read_bus(self.program_counter, true);
         self.increment_program_counter();
 
         return opcode;
@@ -191,11 +1262,27 @@ impl<'a> CPU<'a> {
         return Word::from_le_bytes([lo, hi]);
     }
 
+    // the pointer wraps around within zero page
+    fn fetch_zero_page_pointer(&mut self, addr: Word) -> Word {
+        let [addr_lo, addr_hi] = addr.to_le_bytes();
+        let lo = self.access_memory(addr);
+        self.cycle += 1;
+        let hi = self.access_memory(Word::from_le_bytes([addr_lo.wrapping_add(1), addr_hi]));
+        self.cycle += 1;
+
+        return Word::from_le_bytes([lo, hi]);
+    }
+
+    // zero page is the page selected by the base page register, which only the 65CE02 moves
+    fn zero_page_address(&self, lsb: Byte) -> Word {
+        return Word::from_le_bytes([lsb, self.base_page]);
+    }
+
     fn fetch_zero_page_address(&mut self) -> Word {
-        let address: Word = self.access_memory(self.program_counter).into();
+        let lsb = self.access_memory(self.program_counter);
         self.increment_program_counter();
 
-        return address;
+        return self.zero_page_address(lsb);
     }
 
     fn fetch_zero_page_address_lsb(&mut self) -> Byte {
@@ -207,7 +1294,8 @@ impl<'a> CPU<'a> {
 
     fn fetch_zero_page_address_with_idx_register_offset(&mut self, register: Registers) -> Word {
         let zero_page_addr = self.fetch_zero_page_address_lsb();
-        return self.sum_with_idx_register(zero_page_addr, register).into();
+        let lsb = self.sum_with_idx_register(zero_page_addr, register);
+        return self.zero_page_address(lsb);
     }
 
     fn set_status_of_register(&mut self, register: Registers) {
@@ -249,16 +1337,42 @@ impl<'a> CPU<'a> {
             _ => panic!("cannot sum with non-idx register"),
         };
 
+        // the base address is read while the index is added; CMOS rereads the operand instead
+        let dummy_address = if self.chip_variant.is_nmos() {
+            self.zero_page_address(val)
+        } else {
+            self.program_counter.wrapping_sub(1)
+        };
+        self.dummy_read(dummy_address);
         let res = val.wrapping_add(register_value);
         self.cycle += 1;
 
         return res;
     }
 
+    fn get_stack_address(&self) -> Word {
+        return Word::from_le_bytes([self.stack_pointer, self.stack_pointer_hi]);
+    }
+
+    // with the extended stack the high byte follows the low one, otherwise the stack wraps
+    // within its page
+    fn move_stack_pointer(&mut self, delta: i16) {
+        if self.extended_stack {
+            let address = self.get_stack_address().wrapping_add_signed(delta);
+            [self.stack_pointer, self.stack_pointer_hi] = address.to_le_bytes();
+        } else {
+            self.stack_pointer = self.stack_pointer.wrapping_add_signed(delta as i8);
+        }
+    }
+
     fn push_byte_to_stack(&mut self, val: Byte) {
-        let stack_addr: Word = STACK_PAGE_HI | (self.stack_pointer as u16);
-        self.put_into_memory(stack_addr, val);
-        self.decrement_register(Registers::StackPointer);
+        self.put_into_memory(self.get_stack_address(), val);
+        self.move_stack_pointer(-1);
+        self.cycle += 1;
+        #[cfg(feature = "statistics")]
+        if let Some(statistics) = self.statistics.as_mut() {
+            statistics.record_stack_pointer(self.stack_pointer, self.instruction_address);
+        }
     }
 
     fn push_word_to_stack(&mut self, val: Word) {
@@ -267,10 +1381,22 @@ impl<'a> CPU<'a> {
         self.push_byte_to_stack(lo);
     }
 
+    // the stack pointer is incremented during a read of the current top of the stack, which
+    // happens before every pull
+    fn dummy_stack_read(&mut self) {
+        self.dummy_read(self.get_stack_address());
+        self.tick();
+    }
+
     fn pop_byte_from_stack(&mut self) -> Byte {
-        self.increment_register(Registers::StackPointer);
-        let stack_addr: Word = STACK_PAGE_HI | (self.stack_pointer as u16);
+        let stack_addr = if self.extended_stack {
+            self.get_stack_address().wrapping_add(1)
+        } else {
+            Word::from_le_bytes([self.stack_pointer.wrapping_add(1), self.stack_pointer_hi])
+        };
         let val = self.access_memory(stack_addr);
+        self.move_stack_pointer(1);
+        self.cycle += 1;
 
         return val;
     }
@@ -301,11 +1427,15 @@ impl<'a> CPU<'a> {
                 program_counter_lo.overflowing_add(directionless_offset);
         }
 
+        // the next opcode is read while the low byte is added, and the target with the high
+        // byte not fixed yet while it is
+        self.dummy_read(self.program_counter);
         self.program_counter = Word::from_le_bytes([offset_program_counter_lo, program_counter_hi]);
         self.tick();
         if !carry {
             return;
         }
+        self.dummy_read(self.program_counter);
 
         let offset_program_counter_hi: Byte;
         if negative_offset_direction {
@@ -325,13 +1455,21 @@ impl<'a> CPU<'a> {
         };
 
         let value = self.access_memory(address);
-        if !access_cycle_has_been_done_during_address_fixing(addr_mode) {
-            self.tick();
-        }
+        self.tick();
 
         return Some(value);
     }
 
+    fn indexing_crossed_page(&self, addr_mode: AddressingMode, address: Word) -> bool {
+        return match addr_mode {
+            AddressingMode::AbsoluteX => crosses_page(address, self.index_register_x),
+            AddressingMode::AbsoluteY | AddressingMode::IndirectIndexY => {
+                crosses_page(address, self.index_register_y)
+            }
+            _ => false,
+        };
+    }
+
     fn modify_memory(
         &mut self,
         addr_mode: AddressingMode,
@@ -342,11 +1480,29 @@ impl<'a> CPU<'a> {
             None => return None,
         };
 
+        // indexed modes always spend the cycle to fix the address, reading the already right
+        // address when indexing stayed on the page; CMOS skips it for abs,X in that case
+        let page_crossed = self.indexing_crossed_page(addr_mode, address);
+        let fixes_address = match addr_mode {
+            AddressingMode::AbsoluteX => self.chip_variant.is_nmos() && !page_crossed,
+            AddressingMode::AbsoluteY | AddressingMode::IndirectIndexY => !page_crossed,
+            _ => false,
+        };
+        if fixes_address {
+            self.dummy_read(address);
+            self.tick();
+        }
         let value = self.access_memory(address);
-        // extra cycle to fix address
         self.tick();
 
+        // while modifying the value NMOS writes the unmodified one back, which devices see
+        // as a second write; CMOS reads the address again instead
         let modified_value = cb(&value);
+        if self.chip_variant.is_nmos() {
+            self.put_into_memory(address, value);
+        } else {
+            self.dummy_read(address);
+        }
         self.tick();
 
         self.put_into_memory(address, modified_value);
@@ -360,10 +1516,17 @@ impl<'a> CPU<'a> {
             Some(address) => address,
             None => return None,
         };
-        // extra cycle to fix address
-        self.tick();
+        // indexed stores always spend the cycle to fix the address, reading the target before
+        // writing it; indexing that crossed a page already did
+        if is_indexed_with_address_fixing(addr_mode)
+            && !self.indexing_crossed_page(addr_mode, address)
+        {
+            self.dummy_read(address);
+            self.tick();
+        }
 
         self.put_into_memory(address, value);
+        self.tick();
 
         return Some(());
     }
@@ -371,7 +1534,7 @@ impl<'a> CPU<'a> {
     fn transfer_registers(&mut self, src: Registers, tgt: Registers) {
         let value = self.get_register(src);
         self.set_register(tgt, value);
-        self.tick();
+        self.idle_cycle();
     }
 
     fn dummy_fetch(&mut self) {
@@ -379,6 +1542,15 @@ impl<'a> CPU<'a> {
         self.tick();
     }
 
+    // a cycle spent inside the CPU, with the byte after the opcode read and discarded; the
+    // bus is never idle
+    fn idle_cycle(&mut self) {
+        self.dummy_read(self.program_counter);
+        self.tick();
+    }
+
+    // handlers tick while they execute, so conditional extra cycles (page crossings, taken
+    // branches) simply extend the instruction in flight; there is no precomputed cycle queue
     fn tick(&mut self) {
         self.cycle += 1;
     }
@@ -391,13 +1563,39 @@ impl<'a> CPU<'a> {
             AddressingMode::IndexIndirectX => {
                 let address =
                     self.fetch_zero_page_address_with_idx_register_offset(Registers::IndexX);
-                let effective_address = self.fetch_address_from(address);
+                let effective_address = self.fetch_zero_page_pointer(address);
 
                 return Some(effective_address);
             }
+            AddressingMode::AbsoluteIndexIndirectX => {
+                let partial = self.fetch_address();
+                let address = partial.wrapping_add(self.index_register_x as Word);
+                self.dummy_read(self.program_counter.wrapping_sub(1));
+                self.tick();
+                return Some(self.fetch_address_from(address));
+            }
+            AddressingMode::ZeroPageIndirect => {
+                let address = self.fetch_zero_page_address();
+                let pointer = self.fetch_zero_page_pointer(address);
+                // the 65CE02 indexes (zp) with Z, which stays zero unless a program loads it
+                if self.chip_variant == ChipVariant::CSG65CE02 {
+                    return Some(self.offset_addr(pointer, self.index_register_z));
+                }
+
+                return Some(pointer);
+            }
+            AddressingMode::StackIndirectIndexY => {
+                let offset = self.fetch_zero_page_address_lsb();
+                self.dummy_read(self.program_counter.wrapping_sub(1));
+                self.tick();
+                let address = self.get_stack_address().wrapping_add(offset as Word);
+                let pointer = self.fetch_address_from(address);
+
+                return Some(self.offset_addr(pointer, self.index_register_y));
+            }
             AddressingMode::IndirectIndexY => {
                 let address = self.fetch_zero_page_address();
-                let partial = self.fetch_address_from(address);
+                let partial = self.fetch_zero_page_pointer(address);
                 let effective_address = self.offset_addr(partial, self.index_register_y);
 
                 return Some(effective_address);
@@ -427,7 +1625,8 @@ impl<'a> CPU<'a> {
             }
             AddressingMode::Indirect => {
                 let address = self.fetch_address();
-                if self.chip_variant != ChipVariant::NMOS {
+                if !self.chip_variant.is_nmos() {
+                    self.dummy_read(self.program_counter.wrapping_sub(1));
                     self.tick();
                     return Some(self.fetch_address_from(address));
                 }
@@ -437,9 +1636,13 @@ impl<'a> CPU<'a> {
                     return Some(self.fetch_address_from(address));
                 };
 
-                let hi = self.access_memory(address);
-                let lo = self.access_memory(address & 0xFF00);
-                let incorrect_jmp_address = Word::from_le_bytes([hi, lo]);
+                // the pointer increment does not carry into the high byte, so the high byte of
+                // the target is read from the start of the same page
+                let lo = self.access_memory(address);
+                self.cycle += 1;
+                let hi = self.access_memory(address & 0xFF00);
+                self.cycle += 1;
+                let incorrect_jmp_address = Word::from_le_bytes([lo, hi]);
 
                 return Some(incorrect_jmp_address);
             }
@@ -452,25 +1655,185 @@ impl<'a> CPU<'a> {
         }
     }
 
-    pub fn execute_next_instruction(&mut self) {
+    pub fn execute_next_instruction(&mut self) -> Option<emulation_event::EmulationEvent> {
+        // a jammed CPU does nothing until reset, it only keeps reporting the jam
+        if self.jammed {
+            return Some(emulation_event::EmulationEvent::Jam {
+                address: self.instruction_address,
+                opcode: self.memory.peek(self.instruction_address),
+            });
+        }
+        if self.stopped {
+            return Some(emulation_event::EmulationEvent::Stop(
+                self.instruction_address,
+            ));
+        }
+        // a halted CPU holds the opcode fetch while the clock keeps running
+        if !self.ready {
+            self.dummy_read(self.program_counter);
+            self.tick();
+            return None;
+        }
+        // the clock keeps running while waiting for an interrupt, with the lines sampled on
+        // every cycle
+        if self.waiting {
+            self.interrupt_poll_cycle = self.cycle;
+            if !self.has_pending_interrupt() {
+                self.dummy_read(self.program_counter);
+                self.tick();
+                return None;
+            }
+        }
+        self.waiting = false;
+        // interrupts are taken between instructions, NMI first
+        if self.is_nmi_polled() {
+            self.service_interrupt(NMI_INTERRUPT_VECTOR, statistics::InterruptSource::Nmi);
+            return self.interrupt_entry_event(statistics::InterruptSource::Nmi);
+        }
+        if self.should_service_irq() {
+            self.service_interrupt(IRQ_INTERRUPT_VECTOR, statistics::InterruptSource::Irq);
+            return self.interrupt_entry_event(statistics::InterruptSource::Irq);
+        }
+        self.instruction_address = self.program_counter;
+        self.trace_instruction();
         let opcode = self.fetch_instruction();
-        let handler = self.opcode_handlers.get(&opcode);
-        match handler {
-            Some(cb) => cb(self),
-            None => panic!("illegal opcode found: {opcode}"),
+        let handler = match self.opcode_handlers[opcode as usize] {
+            Some(cb) if self.is_undocumented_opcode_enabled(opcode) => cb,
+            _ => return self.handle_unknown_opcode(opcode),
+        };
+        #[cfg(feature = "opcode-coverage")]
+        if let Some(coverage) = self.opcode_coverage.as_mut() {
+            coverage.record(opcode);
+        }
+        handler(self);
+        self.poll_interrupts();
+
+        #[cfg(feature = "data-breakpoints")]
+        {
+            let triggered = self.data_breakpoints.take_triggered();
+            if !triggered.is_empty() {
+                return Some(emulation_event::EmulationEvent::DataBreakpoint(triggered));
+            }
+        }
+        if let Some(event) = self.take_watchpoint_event() {
+            return Some(event);
         }
+        #[cfg(feature = "uninitialized-memory")]
+        if let Some(read) = self
+            .uninitialized_memory
+            .as_mut()
+            .and_then(|tracker| tracker.take_unhandled_read())
+        {
+            return Some(emulation_event::EmulationEvent::UninitializedRead(read));
+        }
+        if self.jammed {
+            return Some(emulation_event::EmulationEvent::Jam {
+                address: self.instruction_address,
+                opcode,
+            });
+        }
+        if self.stopped {
+            return Some(emulation_event::EmulationEvent::Stop(
+                self.instruction_address,
+            ));
+        }
+        if opcode == opcodes::BRK {
+            return Some(emulation_event::EmulationEvent::Trap(
+                self.instruction_address,
+            ));
+        }
+
+        return None;
     }
 
-    pub fn execute_until_break(&mut self) -> u64 {
-        while !self.processor_status.get_break_flag() {
-            self.execute_next_instruction();
-        }
+    fn handle_unknown_opcode(&mut self, opcode: Byte) -> Option<emulation_event::EmulationEvent> {
+        let address = self.instruction_address;
+        match &mut self.unknown_opcode_policy {
+            opcode_policy::UnknownOpcodePolicy::Fault => (),
+            opcode_policy::UnknownOpcodePolicy::Panic => {
+                panic!("unsupported opcode ${opcode:02X} at ${address:04X}")
+            }
+            opcode_policy::UnknownOpcodePolicy::TreatAsNop => {
+                self.program_counter = address;
+                self.skip_instruction();
+                self.dummy_read(address.wrapping_add(1));
+                self.tick();
+                return None;
+            }
+            opcode_policy::UnknownOpcodePolicy::Jam => {
+                self.jammed = true;
+                return Some(emulation_event::EmulationEvent::Jam { address, opcode });
+            }
+            opcode_policy::UnknownOpcodePolicy::Callback(callback) => {
+                if let Some(resume_address) = callback(opcode, address) {
+                    self.program_counter = resume_address;
+                    return None;
+                }
+            }
+        };
 
-        return self.cycle;
+        return Some(emulation_event::EmulationEvent::Fault { address, opcode });
+    }
+
+    pub fn execute_until_break(&mut self) -> emulation_event::EmulationEvent {
+        return self.run(None, None);
+    }
+
+    pub fn execute_until(&mut self, exit_address: Word) -> emulation_event::EmulationEvent {
+        return self.run(Some(exit_address), None);
+    }
+
+    pub fn execute_cycles(&mut self, budget: u64) -> emulation_event::EmulationEvent {
+        return self.run(None, Some(budget));
     }
+
+    // a breakpoint at the starting address is skipped so that a stopped run can be resumed
+    fn run(
+        &mut self,
+        exit_address: Option<Word>,
+        cycle_budget: Option<u64>,
+    ) -> emulation_event::EmulationEvent {
+        let last_cycle = cycle_budget.map(|budget| self.cycle.saturating_add(budget));
+        let mut first_instruction = true;
+        let mut watchdog_armed_at = self.cycle;
+        loop {
+            if !first_instruction && self.breakpoints.contains(&self.program_counter) {
+                return emulation_event::EmulationEvent::Breakpoint(self.program_counter);
+            }
+            if last_cycle.is_some_and(|last_cycle| self.cycle >= last_cycle) {
+                return emulation_event::EmulationEvent::CycleBudgetExhausted;
+            }
+            first_instruction = false;
+
+            if let Some(event) = self.execute_next_instruction() {
+                return event;
+            }
+            if exit_address == Some(self.program_counter) {
+                return emulation_event::EmulationEvent::ExitAddress(self.program_counter);
+            }
+            if let Some(watchdog) = self.watchdog.as_mut() {
+                if watchdog.is_expired(watchdog_armed_at, self.cycle) {
+                    let elapsed = self.cycle - watchdog_armed_at;
+                    if watchdog.expire(self.program_counter, elapsed) {
+                        return emulation_event::EmulationEvent::WatchdogExpired {
+                            address: self.program_counter,
+                            cycles: elapsed,
+                        };
+                    }
+                    watchdog_armed_at = self.cycle;
+                }
+            }
+        }
+    }
+}
+
+// whether an indexed address ended up on a different page than its base
+fn crosses_page(effective_addr: Word, offset: Byte) -> bool {
+    let base_addr = effective_addr.wrapping_sub(offset as Word);
+    return base_addr & 0xFF00 != effective_addr & 0xFF00;
 }
 
-fn access_cycle_has_been_done_during_address_fixing(addr_mode: AddressingMode) -> bool {
+fn is_indexed_with_address_fixing(addr_mode: AddressingMode) -> bool {
     return addr_mode == AddressingMode::AbsoluteX
         || addr_mode == AddressingMode::AbsoluteY
         || addr_mode == AddressingMode::IndirectIndexY;
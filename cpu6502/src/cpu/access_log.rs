@@ -0,0 +1,132 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use super::bus_activity::BusDirection;
+use crate::consts::{Byte, Word};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MemoryAccess {
+    pub cycle: u64,
+    // first byte of the instruction making the access
+    pub program_counter: Word,
+    pub address: Word,
+    pub value: Byte,
+    pub direction: BusDirection,
+}
+
+impl fmt::Display for MemoryAccess {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let direction = match self.direction {
+            BusDirection::Read => 'R',
+            BusDirection::Write => 'W',
+        };
+
+        return write!(
+            f,
+            "{:>10} ${:04X} {direction} ${:04X} ${:02X}",
+            self.cycle, self.program_counter, self.address, self.value
+        );
+    }
+}
+
+// an empty filter accepts everything; an access is logged when it falls into any of the
+// ranges and goes in the selected direction
+#[derive(Default)]
+pub struct AccessFilter {
+    address_ranges: Vec<RangeInclusive<Word>>,
+    direction: Option<BusDirection>,
+}
+
+impl AccessFilter {
+    pub fn new() -> Self {
+        return AccessFilter::default();
+    }
+
+    pub fn add_address_range(&mut self, range: RangeInclusive<Word>) {
+        self.address_ranges.push(range);
+    }
+
+    pub fn set_direction(&mut self, direction: BusDirection) {
+        self.direction = Some(direction);
+    }
+
+    pub fn matches(&self, address: Word, direction: BusDirection) -> bool {
+        let in_range = self.address_ranges.is_empty()
+            || self
+                .address_ranges
+                .iter()
+                .any(|range| range.contains(&address));
+
+        return in_range && self.direction.is_none_or(|selected| selected == direction);
+    }
+}
+
+// keeps the most recent `capacity` accesses passing the filter, older ones are dropped
+pub struct AccessLog {
+    filter: AccessFilter,
+    entries: VecDeque<MemoryAccess>,
+    capacity: usize,
+    dropped: u64,
+}
+
+impl AccessLog {
+    pub fn new(capacity: usize, filter: AccessFilter) -> Self {
+        assert!(capacity > 0, "access log cannot be empty");
+
+        return AccessLog {
+            filter,
+            entries: VecDeque::with_capacity(capacity),
+            capacity,
+            dropped: 0,
+        };
+    }
+
+    pub fn get_filter(&mut self) -> &mut AccessFilter {
+        return &mut self.filter;
+    }
+
+    // oldest first
+    pub fn entries(&self) -> impl Iterator<Item = &MemoryAccess> {
+        return self.entries.iter();
+    }
+
+    pub fn get_capacity(&self) -> usize {
+        return self.capacity;
+    }
+
+    // accesses that passed the filter but no longer fit in the buffer
+    pub fn get_dropped_count(&self) -> u64 {
+        return self.dropped;
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.dropped = 0;
+    }
+
+    pub fn record(&mut self, access: MemoryAccess) {
+        if !self.filter.matches(access.address, access.direction) {
+            return;
+        }
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+            self.dropped += 1;
+        }
+
+        self.entries.push_back(access);
+    }
+}
+
+impl fmt::Display for AccessLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entry in &self.entries {
+            writeln!(f, "{entry}")?;
+        }
+
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,144 @@
+#[cfg(test)]
+mod record {
+    use crate::cpu::{
+        access_log::{AccessFilter, AccessLog, MemoryAccess},
+        bus_activity::BusDirection,
+    };
+
+    fn access(cycle: u64, address: u16, direction: BusDirection) -> MemoryAccess {
+        return MemoryAccess {
+            cycle,
+            program_counter: 0x0600,
+            address,
+            value: 0x00,
+            direction,
+        };
+    }
+
+    #[test]
+    fn should_keep_most_recent_accesses_within_capacity() {
+        let mut log = AccessLog::new(2, AccessFilter::new());
+
+        (0..5).for_each(|cycle| log.record(access(cycle, 0x0200, BusDirection::Read)));
+
+        let cycles: Vec<u64> = log.entries().map(|entry| entry.cycle).collect();
+        assert_eq!(cycles, vec![3, 4]);
+        assert_eq!(log.get_dropped_count(), 3);
+    }
+
+    #[test]
+    fn should_only_record_accesses_inside_address_ranges() {
+        let mut filter = AccessFilter::new();
+        filter.add_address_range(0xD000..=0xD02E);
+        filter.add_address_range(0xDC00..=0xDC0F);
+        let mut log = AccessLog::new(16, filter);
+
+        log.record(access(0, 0xD020, BusDirection::Write));
+        log.record(access(1, 0xD400, BusDirection::Write));
+        log.record(access(2, 0xDC0D, BusDirection::Read));
+
+        let addresses: Vec<u16> = log.entries().map(|entry| entry.address).collect();
+        assert_eq!(addresses, vec![0xD020, 0xDC0D]);
+        assert_eq!(log.get_dropped_count(), 0);
+    }
+
+    #[test]
+    fn should_only_record_accesses_in_selected_direction() {
+        let mut filter = AccessFilter::new();
+        filter.set_direction(BusDirection::Write);
+        let mut log = AccessLog::new(16, filter);
+
+        log.record(access(0, 0xD020, BusDirection::Read));
+        log.record(access(1, 0xD020, BusDirection::Write));
+
+        let cycles: Vec<u64> = log.entries().map(|entry| entry.cycle).collect();
+        assert_eq!(cycles, vec![1]);
+    }
+
+    #[test]
+    fn should_format_one_access_per_line() {
+        let mut log = AccessLog::new(16, AccessFilter::new());
+        log.record(MemoryAccess {
+            cycle: 12,
+            program_counter: 0x0602,
+            address: 0xD020,
+            value: 0x0E,
+            direction: BusDirection::Write,
+        });
+
+        assert_eq!(log.to_string(), "        12 $0602 W $D020 $0E\n");
+    }
+}
+
+#[cfg(all(test, feature = "access-log"))]
+mod cpu_access_log {
+    use crate::{
+        cpu::{
+            access_log::{AccessFilter, MemoryAccess},
+            bus_activity::BusDirection,
+            CPU,
+        },
+        memory::VecMemory,
+    };
+
+    #[test]
+    fn should_log_accesses_of_instruction_to_io_registers() {
+        let mut memory = VecMemory::new();
+        memory.insert(
+            0x0600,
+            &[
+                0xA9, 0x0E, // LDA #$0E
+                0x8D, 0x20, 0xD0, // STA $D020
+                0xAD, 0x12, 0xD0, // LDA $D012
+            ],
+        );
+        memory[0xD012] = 0x7F;
+        let mut cpu = CPU::new_nmos(memory);
+        cpu.set_program_counter(0x0600);
+        let mut filter = AccessFilter::new();
+        filter.add_address_range(0xD000..=0xD3FF);
+        cpu.enable_access_log(64, filter);
+        let start = cpu.get_cycle();
+
+        cpu.execute_next_instruction();
+        cpu.execute_next_instruction();
+        cpu.execute_next_instruction();
+
+        let log: Vec<MemoryAccess> = cpu.get_access_log().unwrap().entries().copied().collect();
+        assert_eq!(
+            log,
+            vec![
+                MemoryAccess {
+                    cycle: start + 5,
+                    program_counter: 0x0602,
+                    address: 0xD020,
+                    value: 0x0E,
+                    direction: BusDirection::Write,
+                },
+                MemoryAccess {
+                    cycle: start + 9,
+                    program_counter: 0x0605,
+                    address: 0xD012,
+                    value: 0x7F,
+                    direction: BusDirection::Read,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn should_stop_logging_once_disabled() {
+        let mut memory = VecMemory::new();
+        memory.insert(0x0600, &[0xEA, 0xEA]); // NOP; NOP
+        let mut cpu = CPU::new_nmos(memory);
+        cpu.set_program_counter(0x0600);
+        cpu.enable_access_log(64, AccessFilter::new());
+
+        cpu.execute_next_instruction();
+        let log = cpu.disable_access_log().unwrap();
+        cpu.execute_next_instruction();
+
+        assert_eq!(log.entries().count(), 2);
+        assert!(cpu.get_access_log().is_none());
+    }
+}
@@ -0,0 +1,313 @@
+use std::collections::HashMap;
+
+use super::instructions::{get_opcode_metadata, LengthTable};
+use super::undocumented_opcodes::UndocumentedClass;
+use super::{AddressingMode, ChipVariant};
+use crate::consts::{Byte, Word};
+
+fn preferred_encoding(opcode: Byte, chosen: Byte) -> bool {
+    let documented = UndocumentedClass::classify(opcode).is_none();
+    let chosen_documented = UndocumentedClass::classify(chosen).is_none();
+    if documented != chosen_documented {
+        return documented;
+    }
+
+    return opcode < chosen;
+}
+
+struct Operand {
+    value: u32,
+    is_word: bool,
+}
+
+pub struct Assembler {
+    opcodes: HashMap<(&'static str, AddressingMode), Byte>,
+    lengths: LengthTable,
+}
+
+impl Assembler {
+    // only instructions the given chip variant executes can be assembled
+    pub fn new(chip_variant: ChipVariant) -> Self {
+        let mut opcodes = HashMap::new();
+        let mut lengths: LengthTable = [1; 256];
+        for (opcode, metadata) in get_opcode_metadata(chip_variant).into_iter().enumerate() {
+            let (opcode, metadata) = match metadata {
+                Some(metadata) => (opcode as Byte, metadata),
+                None => continue,
+            };
+            lengths[opcode as usize] = metadata.length();
+            // some undocumented instructions are encoded by several opcodes; the documented
+            // encoding wins, otherwise the lowest one does
+            opcodes
+                .entry((metadata.mnemonic, metadata.addressing_mode))
+                .and_modify(|chosen: &mut Byte| {
+                    if preferred_encoding(opcode, *chosen) {
+                        *chosen = opcode;
+                    }
+                })
+                .or_insert(opcode);
+        }
+
+        return Assembler { opcodes, lengths };
+    }
+
+    fn supports(&self, mnemonic: &str, addressing_mode: AddressingMode) -> bool {
+        return self.opcodes.contains_key(&(mnemonic, addressing_mode));
+    }
+
+    // bare numbers are hexadecimal, as in machine-language monitors; "$" and "0x" prefixes
+    // are accepted as well as "%" for binary. Two-digit values select zero page addressing.
+    pub fn assemble_instruction(&self, line: &str, address: Word) -> Result<Vec<Byte>, String> {
+        let line = line.trim();
+        let (mnemonic, operand) = match line.split_once(char::is_whitespace) {
+            Some((mnemonic, operand)) => (mnemonic, operand),
+            None => (line, ""),
+        };
+        let mnemonic = mnemonic.to_uppercase();
+        let operand: String = operand
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>()
+            .to_uppercase();
+
+        if !self.opcodes.keys().any(|(known, _)| *known == mnemonic) {
+            return Err(format!("unknown mnemonic: {mnemonic}"));
+        }
+
+        if self.supports(&mnemonic, AddressingMode::Relative)
+            || self.supports(&mnemonic, AddressingMode::RelativeWord)
+        {
+            return self.assemble_branch(&mnemonic, &operand, address);
+        }
+        if self.supports(&mnemonic, AddressingMode::ZeroPageRelative) {
+            return self.assemble_bit_branch(&mnemonic, &operand, address);
+        }
+        if self.supports(&mnemonic, AddressingMode::ImmediateWord) && operand.starts_with('#') {
+            let opcode = self.opcodes[&(mnemonic.as_str(), AddressingMode::ImmediateWord)];
+            let value = parse_word(&operand[1..], &operand)?;
+            return Ok(self.encode(opcode, value));
+        }
+
+        let (candidates, value) = parse_operand(&operand)?;
+        for addressing_mode in candidates {
+            let opcode = match self.opcodes.get(&(mnemonic.as_str(), addressing_mode)) {
+                Some(opcode) => *opcode,
+                None => continue,
+            };
+
+            return Ok(self.encode(opcode, value));
+        }
+
+        return Err(format!("{mnemonic} does not support operand \"{operand}\""));
+    }
+
+    // a 65CE02 branch takes the word offset form when the target is out of reach of a byte
+    fn assemble_branch(
+        &self,
+        mnemonic: &str,
+        operand: &str,
+        address: Word,
+    ) -> Result<Vec<Byte>, String> {
+        let target = parse_branch_target(operand)?;
+        let next_instruction = address.wrapping_add(2) as i32;
+        let offset = target as i32 - next_instruction;
+        let short_opcode = self.opcodes.get(&(mnemonic, AddressingMode::Relative));
+        if let Some(opcode) = short_opcode.filter(|_| (-128..=127).contains(&offset)) {
+            return Ok(vec![*opcode, offset as i8 as Byte]);
+        }
+
+        return match self.opcodes.get(&(mnemonic, AddressingMode::RelativeWord)) {
+            // the word offset is relative to the last byte of the instruction
+            Some(opcode) => {
+                let offset = target.wrapping_sub(address.wrapping_add(2));
+                Ok(self.encode(*opcode, offset as u32))
+            }
+            None => Err(format!("branch target out of range: {operand}")),
+        };
+    }
+
+    // BBR and BBS take a zero page address and a branch target: "BBR0 $12,$1000"
+    fn assemble_bit_branch(
+        &self,
+        mnemonic: &str,
+        operand: &str,
+        address: Word,
+    ) -> Result<Vec<Byte>, String> {
+        let opcode = self.opcodes[&(mnemonic, AddressingMode::ZeroPageRelative)];
+        let (zero_page, target) = match operand.split_once(',') {
+            Some(operands) => operands,
+            None => {
+                return Err(format!(
+                    "zero page address and branch target expected: {operand}"
+                ))
+            }
+        };
+        let zero_page = parse_value(zero_page)?;
+        if zero_page.value > Byte::MAX as u32 {
+            return Err(format!("zero page address expected: {operand}"));
+        }
+        let target = parse_branch_target(target)?;
+
+        let next_instruction = address.wrapping_add(3) as i32;
+        let offset = target as i32 - next_instruction;
+        if !(-128..=127).contains(&offset) {
+            return Err(format!("branch target out of range: {operand}"));
+        }
+
+        return Ok(vec![opcode, zero_page.value as Byte, offset as i8 as Byte]);
+    }
+
+    // operand bytes follow the opcode little endian, as many as the instruction is long
+    fn encode(&self, opcode: Byte, value: u32) -> Vec<Byte> {
+        let operand_length = self.lengths[opcode as usize] as usize - 1;
+        let mut bytes = vec![opcode];
+        bytes.extend_from_slice(&value.to_le_bytes()[..operand_length]);
+
+        return bytes;
+    }
+}
+
+impl Default for Assembler {
+    fn default() -> Self {
+        return Assembler::new(ChipVariant::NMOS);
+    }
+}
+
+fn parse_branch_target(operand: &str) -> Result<Word, String> {
+    let target = parse_value(operand)?;
+    if target.value > Word::MAX as u32 {
+        return Err(format!("branch target out of address space: {operand}"));
+    }
+
+    return Ok(target.value as Word);
+}
+
+fn parse_operand(operand: &str) -> Result<(Vec<AddressingMode>, u32), String> {
+    if operand.is_empty() {
+        return Ok((
+            vec![AddressingMode::Implicit, AddressingMode::Accumulator],
+            0,
+        ));
+    }
+    if operand == "A" {
+        return Ok((vec![AddressingMode::Accumulator], 0));
+    }
+    if let Some(value) = operand.strip_prefix('#') {
+        let value = parse_value(value)?;
+        if value.value > Byte::MAX as u32 {
+            return Err(format!("immediate value too big: {operand}"));
+        }
+
+        return Ok((vec![AddressingMode::Immediate], value.value));
+    }
+    if let Some(value) = operand
+        .strip_prefix('(')
+        .and_then(|operand| operand.strip_suffix(",X)"))
+    {
+        return zero_page_or_absolute(
+            AddressingMode::IndexIndirectX,
+            AddressingMode::AbsoluteIndexIndirectX,
+            value,
+            operand,
+        );
+    }
+    if let Some(value) = operand
+        .strip_prefix('(')
+        .and_then(|operand| operand.strip_suffix(",SP),Y"))
+    {
+        return zero_page_only(
+            AddressingMode::StackIndirectIndexY,
+            parse_value(value)?,
+            operand,
+        );
+    }
+    if let Some(value) = operand
+        .strip_prefix('(')
+        .and_then(|operand| operand.strip_suffix("),Y"))
+    {
+        return zero_page_only(AddressingMode::IndirectIndexY, parse_value(value)?, operand);
+    }
+    if let Some(value) = operand
+        .strip_prefix('(')
+        .and_then(|operand| operand.strip_suffix(')'))
+    {
+        return zero_page_or_absolute(
+            AddressingMode::ZeroPageIndirect,
+            AddressingMode::Indirect,
+            value,
+            operand,
+        );
+    }
+
+    let (value, zero_page_mode, absolute_mode) = if let Some(value) = operand.strip_suffix(",X") {
+        (value, AddressingMode::ZeroPageX, AddressingMode::AbsoluteX)
+    } else if let Some(value) = operand.strip_suffix(",Y") {
+        (value, AddressingMode::ZeroPageY, AddressingMode::AbsoluteY)
+    } else {
+        (operand, AddressingMode::ZeroPage, AddressingMode::Absolute)
+    };
+    return zero_page_or_absolute(zero_page_mode, absolute_mode, value, operand);
+}
+
+// two-digit operands try the zero page form first and fall back to the absolute one
+fn zero_page_or_absolute(
+    zero_page_mode: AddressingMode,
+    absolute_mode: AddressingMode,
+    value: &str,
+    operand: &str,
+) -> Result<(Vec<AddressingMode>, u32), String> {
+    let parsed = parse_value(value)?;
+    let value = parse_word(value, operand)?;
+    if parsed.is_word {
+        return Ok((vec![absolute_mode], value));
+    }
+
+    return Ok((vec![zero_page_mode, absolute_mode], value));
+}
+
+fn zero_page_only(
+    addressing_mode: AddressingMode,
+    value: Operand,
+    operand: &str,
+) -> Result<(Vec<AddressingMode>, u32), String> {
+    if value.value > Byte::MAX as u32 {
+        return Err(format!("zero page address expected: {operand}"));
+    }
+
+    return Ok((vec![addressing_mode], value.value));
+}
+
+fn parse_word(value: &str, operand: &str) -> Result<u32, String> {
+    let value = parse_value(value)?;
+    if value.value > Word::MAX as u32 {
+        return Err(format!("address out of address space: {operand}"));
+    }
+
+    return Ok(value.value);
+}
+
+fn parse_value(text: &str) -> Result<Operand, String> {
+    let (digits, radix) = if let Some(digits) = text.strip_prefix('$') {
+        (digits, 16)
+    } else if let Some(digits) = text.strip_prefix("0X") {
+        (digits, 16)
+    } else if let Some(digits) = text.strip_prefix('%') {
+        (digits, 2)
+    } else {
+        (text, 16)
+    };
+
+    let value = match u32::from_str_radix(digits, radix) {
+        Ok(value) => value,
+        Err(_) => return Err(format!("incorrect number: {text}")),
+    };
+    let is_word = match radix {
+        16 => digits.len() > 2,
+        _ => value > Byte::MAX as u32,
+    };
+
+    return Ok(Operand { value, is_word });
+}
+
+#[cfg(test)]
+mod tests;
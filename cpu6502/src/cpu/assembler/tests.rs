@@ -0,0 +1,230 @@
+#[cfg(test)]
+mod assemble_instruction {
+    use crate::cpu::{assembler::Assembler, ChipVariant};
+
+    #[test]
+    fn should_assemble_implicit_and_accumulator_instructions() {
+        let assembler = Assembler::new(ChipVariant::NMOS);
+
+        assert_eq!(
+            assembler.assemble_instruction("NOP", 0x1000),
+            Ok(vec![0xEA])
+        );
+        assert_eq!(
+            assembler.assemble_instruction("asl", 0x1000),
+            Ok(vec![0x0A])
+        );
+        assert_eq!(
+            assembler.assemble_instruction("ASL A", 0x1000),
+            Ok(vec![0x0A])
+        );
+    }
+
+    #[test]
+    fn should_pick_lowest_encoding_of_undocumented_instruction() {
+        let assembler = Assembler::new(ChipVariant::NMOS);
+
+        assert_eq!(
+            assembler.assemble_instruction("ANC #$01", 0x1000),
+            Ok(vec![0x0B, 0x01])
+        );
+    }
+
+    #[test]
+    fn should_assemble_immediate_and_zero_page_operands() {
+        let assembler = Assembler::new(ChipVariant::NMOS);
+
+        assert_eq!(
+            assembler.assemble_instruction("LDA #$42", 0x1000),
+            Ok(vec![0xA9, 0x42])
+        );
+        assert_eq!(
+            assembler.assemble_instruction("lda $42", 0x1000),
+            Ok(vec![0xA5, 0x42])
+        );
+        assert_eq!(
+            assembler.assemble_instruction("LDA 42,X", 0x1000),
+            Ok(vec![0xB5, 0x42])
+        );
+        assert_eq!(
+            assembler.assemble_instruction("LDX $42,Y", 0x1000),
+            Ok(vec![0xB6, 0x42])
+        );
+    }
+
+    #[test]
+    fn should_assemble_absolute_operands_written_with_four_digits() {
+        let assembler = Assembler::new(ChipVariant::NMOS);
+
+        assert_eq!(
+            assembler.assemble_instruction("LDA $0042", 0x1000),
+            Ok(vec![0xAD, 0x42, 0x00])
+        );
+        assert_eq!(
+            assembler.assemble_instruction("STA $1234,X", 0x1000),
+            Ok(vec![0x9D, 0x34, 0x12])
+        );
+        assert_eq!(
+            assembler.assemble_instruction("STA 0x1234, Y", 0x1000),
+            Ok(vec![0x99, 0x34, 0x12])
+        );
+    }
+
+    #[test]
+    fn should_fall_back_to_absolute_when_zero_page_variant_does_not_exist() {
+        let assembler = Assembler::new(ChipVariant::NMOS);
+
+        assert_eq!(
+            assembler.assemble_instruction("LDA $42,Y", 0x1000),
+            Ok(vec![0xB9, 0x42, 0x00])
+        );
+        assert_eq!(
+            assembler.assemble_instruction("JMP $42", 0x1000),
+            Ok(vec![0x4C, 0x42, 0x00])
+        );
+    }
+
+    #[test]
+    fn should_assemble_indirect_operands() {
+        let assembler = Assembler::new(ChipVariant::NMOS);
+
+        assert_eq!(
+            assembler.assemble_instruction("LDA ($80,X)", 0x1000),
+            Ok(vec![0xA1, 0x80])
+        );
+        assert_eq!(
+            assembler.assemble_instruction("sta ($80),y", 0x1000),
+            Ok(vec![0x91, 0x80])
+        );
+        assert_eq!(
+            assembler.assemble_instruction("JMP ($FFFC)", 0x1000),
+            Ok(vec![0x6C, 0xFC, 0xFF])
+        );
+    }
+
+    #[test]
+    fn should_assemble_branches_relative_to_instruction_address() {
+        let assembler = Assembler::new(ChipVariant::NMOS);
+
+        assert_eq!(
+            assembler.assemble_instruction("BNE $1010", 0x1000),
+            Ok(vec![0xD0, 0x0E])
+        );
+        assert_eq!(
+            assembler.assemble_instruction("BNE $1000", 0x1000),
+            Ok(vec![0xD0, 0xFE])
+        );
+    }
+
+    #[test]
+    fn should_reject_branch_targets_out_of_range() {
+        let assembler = Assembler::new(ChipVariant::NMOS);
+
+        assert_eq!(
+            assembler.assemble_instruction("BEQ $1100", 0x1000),
+            Err(String::from("branch target out of range: $1100"))
+        );
+    }
+
+    #[test]
+    fn should_reject_unknown_mnemonics_and_unsupported_operands() {
+        let assembler = Assembler::new(ChipVariant::NMOS);
+
+        assert_eq!(
+            assembler.assemble_instruction("FOO #$00", 0x1000),
+            Err(String::from("unknown mnemonic: FOO"))
+        );
+        assert_eq!(
+            assembler.assemble_instruction("STA #$00", 0x1000),
+            Err(String::from("STA does not support operand \"#$00\""))
+        );
+        assert_eq!(
+            assembler.assemble_instruction("LDA #$100", 0x1000),
+            Err(String::from("immediate value too big: #$100"))
+        );
+    }
+}
+
+#[cfg(test)]
+mod chip_variants {
+    use crate::cpu::{assembler::Assembler, ChipVariant};
+
+    #[test]
+    fn should_assemble_cmos_instructions_only_for_cmos_chips() {
+        let nmos = Assembler::new(ChipVariant::NMOS);
+        let cmos = Assembler::new(ChipVariant::CMOS);
+
+        assert_eq!(
+            nmos.assemble_instruction("STZ $10", 0x1000),
+            Err(String::from("unknown mnemonic: STZ"))
+        );
+        assert_eq!(
+            cmos.assemble_instruction("STZ $10", 0x1000),
+            Ok(vec![0x64, 0x10])
+        );
+        assert_eq!(
+            cmos.assemble_instruction("BRA $1010", 0x1000),
+            Ok(vec![0x80, 0x0E])
+        );
+        assert_eq!(cmos.assemble_instruction("DEC A", 0x1000), Ok(vec![0x3A]));
+        assert_eq!(cmos.assemble_instruction("NOP", 0x1000), Ok(vec![0xEA]));
+    }
+
+    #[test]
+    fn should_assemble_cmos_indirect_operands() {
+        let cmos = Assembler::new(ChipVariant::CMOS);
+
+        assert_eq!(
+            cmos.assemble_instruction("LDA ($10)", 0x1000),
+            Ok(vec![0xB2, 0x10])
+        );
+        assert_eq!(
+            cmos.assemble_instruction("JMP ($10)", 0x1000),
+            Ok(vec![0x6C, 0x10, 0x00])
+        );
+        assert_eq!(
+            cmos.assemble_instruction("JMP ($1234,X)", 0x1000),
+            Ok(vec![0x7C, 0x34, 0x12])
+        );
+    }
+
+    #[test]
+    fn should_assemble_rockwell_bit_branches() {
+        let rockwell = Assembler::new(ChipVariant::RockwellCMOS);
+
+        assert_eq!(
+            rockwell.assemble_instruction("BBR0 $10,$1013", 0x1000),
+            Ok(vec![0x0F, 0x10, 0x10])
+        );
+        assert_eq!(
+            rockwell.assemble_instruction("BBS7 $10,$1000", 0x1000),
+            Ok(vec![0xFF, 0x10, 0xFD])
+        );
+        assert_eq!(
+            rockwell.assemble_instruction("BBS7 $10,$2000", 0x1000),
+            Err(String::from("branch target out of range: $10,$2000"))
+        );
+    }
+
+    #[test]
+    fn should_assemble_65ce02_operands() {
+        let csg = Assembler::new(ChipVariant::CSG65CE02);
+
+        assert_eq!(
+            csg.assemble_instruction("BRA $1010", 0x1000),
+            Ok(vec![0x80, 0x0E])
+        );
+        assert_eq!(
+            csg.assemble_instruction("BRA $2000", 0x1000),
+            Ok(vec![0x83, 0xFE, 0x0F])
+        );
+        assert_eq!(
+            csg.assemble_instruction("PHW #$1234", 0x1000),
+            Ok(vec![0xF4, 0x34, 0x12])
+        );
+        assert_eq!(
+            csg.assemble_instruction("LDA ($05,SP),Y", 0x1000),
+            Ok(vec![0xE2, 0x05])
+        );
+    }
+}
@@ -0,0 +1,23 @@
+use crate::consts::{Byte, Word};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BusDirection {
+    Read,
+    Write,
+}
+
+// a single access as a logic analyzer hooked to the pins would see it; `sync` is high on the
+// cycle fetching an opcode
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BusCycle {
+    pub cycle: u64,
+    pub address: Word,
+    pub data: Byte,
+    pub direction: BusDirection,
+    pub sync: bool,
+}
+
+pub type BusActivityCallback = Box<dyn FnMut(&BusCycle) + Send>;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,181 @@
+#[cfg(all(test, feature = "bus-activity"))]
+mod cpu_bus_activity {
+    use std::{
+        cell::RefCell,
+        sync::{Arc, Mutex},
+    };
+
+    use crate::cpu::{
+        bus_activity::{BusCycle, BusDirection},
+        tests::MemoryMock,
+        CPU,
+    };
+
+    fn record_bus(cpu: &mut CPU<&RefCell<MemoryMock>>) -> Arc<Mutex<Vec<BusCycle>>> {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&log);
+        cpu.set_bus_activity_callback(Box::new(move |bus_cycle| {
+            sink.lock().unwrap().push(*bus_cycle);
+        }));
+
+        return log;
+    }
+
+    fn read(cycle: u64, address: u16, data: u8, sync: bool) -> BusCycle {
+        return BusCycle {
+            cycle,
+            address,
+            data,
+            direction: BusDirection::Read,
+            sync,
+        };
+    }
+
+    #[test]
+    fn should_report_every_access_with_sync_on_opcode_fetch() {
+        let memory = RefCell::new(MemoryMock::new(&[0xA5, 0x03, 0x00, 0x42])); // LDA $03
+        let mut cpu = CPU::new_nmos(&memory);
+        cpu.program_counter = 0x0000;
+        cpu.cycle = 0;
+        let log = record_bus(&mut cpu);
+
+        cpu.execute_next_instruction();
+
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec![
+                read(0, 0x0000, 0xA5, true),
+                read(1, 0x0001, 0x03, false),
+                read(2, 0x0003, 0x42, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_report_writes() {
+        let memory = RefCell::new(MemoryMock::new(&[0x85, 0x10])); // STA $10
+        let mut cpu = CPU::new_nmos(&memory);
+        cpu.program_counter = 0x0000;
+        cpu.cycle = 0;
+        cpu.accumulator = 0x42;
+        let log = record_bus(&mut cpu);
+
+        cpu.execute_next_instruction();
+
+        assert_eq!(
+            log.lock().unwrap().last(),
+            Some(&BusCycle {
+                cycle: 2,
+                address: 0x0010,
+                data: 0x42,
+                direction: BusDirection::Write,
+                sync: false,
+            })
+        );
+    }
+
+    #[test]
+    fn should_stop_reporting_once_cleared() {
+        let memory = RefCell::new(MemoryMock::new(&[0xEA])); // NOP
+        let mut cpu = CPU::new_nmos(&memory);
+        cpu.program_counter = 0x0000;
+        let log = record_bus(&mut cpu);
+        cpu.clear_bus_activity_callback();
+
+        cpu.execute_next_instruction();
+
+        assert!(log.lock().unwrap().is_empty());
+    }
+
+    // runs one instruction and checks the probe saw exactly one access on every cycle it took
+    fn assert_one_access_per_cycle(cpu: &mut CPU<&RefCell<MemoryMock>>) -> Vec<BusCycle> {
+        cpu.cycle = 0;
+        let log = record_bus(cpu);
+
+        cpu.execute_next_instruction();
+
+        let reported = log.lock().unwrap().clone();
+        let cycles: Vec<u64> = reported.iter().map(|bus_cycle| bus_cycle.cycle).collect();
+        assert_eq!(cycles, (0..cpu.cycle).collect::<Vec<u64>>());
+        return reported;
+    }
+
+    #[test]
+    fn should_report_dummy_read_of_next_byte_for_implied_instruction() {
+        let memory = RefCell::new(MemoryMock::new(&[0xE8, 0x55])); // INX
+        let mut cpu = CPU::new_nmos(&memory);
+        cpu.program_counter = 0x0000;
+
+        let reported = assert_one_access_per_cycle(&mut cpu);
+
+        assert_eq!(
+            reported,
+            vec![read(0, 0x0000, 0xE8, true), read(1, 0x0001, 0x55, false)]
+        );
+    }
+
+    #[test]
+    fn should_report_every_cycle_of_taken_branch() {
+        let memory = RefCell::new(MemoryMock::new(&[0xD0, 0x02, 0x55])); // BNE +2
+        let mut cpu = CPU::new_nmos(&memory);
+        cpu.program_counter = 0x0000;
+
+        let reported = assert_one_access_per_cycle(&mut cpu);
+
+        assert_eq!(
+            reported,
+            vec![
+                read(0, 0x0000, 0xD0, true),
+                read(1, 0x0001, 0x02, false),
+                read(2, 0x0002, 0x55, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_report_read_of_unfixed_target_for_branch_crossing_page() {
+        let memory = RefCell::new(MemoryMock::new(&[0xD0, 0xFC])); // BNE -4
+        let mut cpu = CPU::new_nmos(&memory);
+        cpu.program_counter = 0x0000;
+
+        let reported = assert_one_access_per_cycle(&mut cpu);
+
+        assert_eq!(cpu.program_counter, 0xFFFE);
+        assert_eq!(reported[2].address, 0x0002);
+        assert_eq!(reported[3].address, 0x00FE);
+    }
+
+    #[test]
+    fn should_report_dummy_read_of_zero_page_base_when_indexing() {
+        let memory = RefCell::new(MemoryMock::new(&[0xB5, 0x10])); // LDA $10,X
+        let mut cpu = CPU::new_nmos(&memory);
+        cpu.program_counter = 0x0000;
+        cpu.index_register_x = 0x05;
+        cpu.memory.borrow_mut()[0x0010] = 0x11;
+        cpu.memory.borrow_mut()[0x0015] = 0x42;
+
+        let reported = assert_one_access_per_cycle(&mut cpu);
+
+        assert_eq!(
+            reported,
+            vec![
+                read(0, 0x0000, 0xB5, true),
+                read(1, 0x0001, 0x10, false),
+                read(2, 0x0010, 0x11, false),
+                read(3, 0x0015, 0x42, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_report_halted_cycles_while_rdy_is_low() {
+        let memory = RefCell::new(MemoryMock::new(&[0xEA])); // NOP
+        let mut cpu = CPU::new_nmos(&memory);
+        cpu.program_counter = 0x0000;
+        cpu.set_rdy(false);
+
+        let reported = assert_one_access_per_cycle(&mut cpu);
+
+        assert_eq!(reported, vec![read(0, 0x0000, 0xEA, false)]);
+    }
+}
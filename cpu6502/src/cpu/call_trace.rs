@@ -0,0 +1,144 @@
+use std::fmt;
+
+use crate::{consts::Word, symbols::SymbolTable};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum CallKind {
+    Subroutine,
+    Interrupt,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum CallEvent {
+    Call {
+        kind: CallKind,
+        from: Word,
+        target: Word,
+    },
+    Return {
+        kind: CallKind,
+        to: Word,
+        callee: Option<Word>,
+        elapsed_cycles: Option<u64>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CallTraceEntry {
+    pub cycle: u64,
+    pub depth: usize,
+    pub event: CallEvent,
+}
+
+struct Frame {
+    target: Word,
+    cycle: u64,
+}
+
+pub struct CallTrace {
+    symbols: SymbolTable,
+    entries: Vec<CallTraceEntry>,
+    frames: Vec<Frame>,
+}
+
+impl CallTrace {
+    pub fn new(symbols: SymbolTable) -> Self {
+        return CallTrace {
+            symbols,
+            entries: Vec::new(),
+            frames: Vec::new(),
+        };
+    }
+
+    pub fn entries(&self) -> &[CallTraceEntry] {
+        return &self.entries;
+    }
+
+    pub fn depth(&self) -> usize {
+        return self.frames.len();
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.frames.clear();
+    }
+
+    pub fn record_call(&mut self, cycle: u64, kind: CallKind, from: Word, target: Word) {
+        self.entries.push(CallTraceEntry {
+            cycle,
+            depth: self.frames.len(),
+            event: CallEvent::Call { kind, from, target },
+        });
+        self.frames.push(Frame { target, cycle });
+    }
+
+    pub fn record_return(&mut self, cycle: u64, kind: CallKind, to: Word) {
+        // unbalanced returns (e.g. RTS used as a computed jump) are logged at the outermost level
+        let frame = self.frames.pop();
+        self.entries.push(CallTraceEntry {
+            cycle,
+            depth: self.frames.len(),
+            event: CallEvent::Return {
+                kind,
+                to,
+                callee: frame.as_ref().map(|frame| frame.target),
+                elapsed_cycles: frame.map(|frame| cycle - frame.cycle),
+            },
+        });
+    }
+
+    fn format_entry(&self, entry: &CallTraceEntry) -> String {
+        let indent = "  ".repeat(entry.depth);
+        return match entry.event {
+            CallEvent::Call { kind, from, target } => {
+                let mnemonic = match kind {
+                    CallKind::Subroutine => "JSR",
+                    CallKind::Interrupt => "INT",
+                };
+                format!(
+                    "{:>10} {indent}{mnemonic} {} from {}",
+                    entry.cycle,
+                    self.symbols.resolve(target),
+                    self.symbols.resolve(from)
+                )
+            }
+            CallEvent::Return {
+                kind,
+                to,
+                callee,
+                elapsed_cycles,
+            } => {
+                let mnemonic = match kind {
+                    CallKind::Subroutine => "RTS",
+                    CallKind::Interrupt => "RTI",
+                };
+                let callee = match callee {
+                    Some(addr) => self.symbols.resolve(addr),
+                    None => String::from("?"),
+                };
+                let elapsed = match elapsed_cycles {
+                    Some(cycles) => format!(" ({cycles} cycles)"),
+                    None => String::new(),
+                };
+                format!(
+                    "{:>10} {indent}{mnemonic} {callee} to {}{elapsed}",
+                    entry.cycle,
+                    self.symbols.resolve(to)
+                )
+            }
+        };
+    }
+}
+
+impl fmt::Display for CallTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entry in &self.entries {
+            writeln!(f, "{}", self.format_entry(entry))?;
+        }
+
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests;
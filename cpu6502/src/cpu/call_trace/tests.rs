@@ -0,0 +1,143 @@
+#[cfg(test)]
+mod record {
+    use crate::cpu::call_trace::{CallEvent, CallKind, CallTrace};
+    use crate::symbols::SymbolTable;
+
+    #[test]
+    fn should_increase_depth_on_call_and_decrease_it_on_return() {
+        let mut trace = CallTrace::new(SymbolTable::new());
+
+        trace.record_call(6, CallKind::Subroutine, 0x0600, 0x0300);
+        trace.record_call(12, CallKind::Subroutine, 0x0302, 0x0400);
+        trace.record_return(20, CallKind::Subroutine, 0x0305);
+
+        let depths: Vec<usize> = trace.entries().iter().map(|entry| entry.depth).collect();
+        assert_eq!(depths, vec![0, 1, 1]);
+        assert_eq!(trace.depth(), 1);
+    }
+
+    #[test]
+    fn should_match_return_with_its_call_and_report_elapsed_cycles() {
+        let mut trace = CallTrace::new(SymbolTable::new());
+
+        trace.record_call(6, CallKind::Subroutine, 0x0600, 0x0300);
+        trace.record_return(20, CallKind::Subroutine, 0x0603);
+
+        assert_eq!(
+            trace.entries()[1].event,
+            CallEvent::Return {
+                kind: CallKind::Subroutine,
+                to: 0x0603,
+                callee: Some(0x0300),
+                elapsed_cycles: Some(14),
+            }
+        );
+    }
+
+    #[test]
+    fn should_record_unbalanced_return_at_outermost_level() {
+        let mut trace = CallTrace::new(SymbolTable::new());
+
+        trace.record_return(20, CallKind::Subroutine, 0x0603);
+
+        assert_eq!(trace.entries()[0].depth, 0);
+        assert_eq!(
+            trace.entries()[0].event,
+            CallEvent::Return {
+                kind: CallKind::Subroutine,
+                to: 0x0603,
+                callee: None,
+                elapsed_cycles: None,
+            }
+        );
+    }
+}
+
+#[cfg(test)]
+mod display {
+    use crate::cpu::call_trace::{CallKind, CallTrace};
+    use crate::symbols::SymbolTable;
+
+    #[test]
+    fn should_produce_indented_log_with_resolved_symbols() {
+        let mut symbols = SymbolTable::new();
+        symbols.insert(0x0300, "print");
+        symbols.insert(0x0400, "putc");
+        let mut trace = CallTrace::new(symbols);
+
+        trace.record_call(6, CallKind::Subroutine, 0x0600, 0x0300);
+        trace.record_call(12, CallKind::Subroutine, 0x0302, 0x0400);
+        trace.record_return(20, CallKind::Subroutine, 0x0305);
+        trace.record_return(30, CallKind::Subroutine, 0x0603);
+
+        assert_eq!(
+            trace.to_string(),
+            concat!(
+                "         6 JSR print from $0600\n",
+                "        12   JSR putc from $0302\n",
+                "        20   RTS putc to $0305 (8 cycles)\n",
+                "        30 RTS print to $0603 (24 cycles)\n",
+            )
+        );
+    }
+}
+
+#[cfg(all(test, feature = "call-trace"))]
+mod cpu_integration {
+    use std::cell::RefCell;
+
+    use crate::cpu::{
+        call_trace::{CallEvent, CallKind},
+        tests::MemoryMock,
+        CPU,
+    };
+    use crate::symbols::SymbolTable;
+
+    #[test]
+    fn should_record_jsr_and_rts_executed_by_cpu() {
+        let memory = &RefCell::new(MemoryMock::new(&[
+            0x20, 0x04, 0x00, 0xEA, // JSR $0004; NOP
+            0x60, // RTS
+        ]));
+        let mut cpu = CPU::new_nmos(memory);
+        cpu.program_counter = 0x0000;
+        cpu.stack_pointer = 0xFF;
+        cpu.cycle = 0;
+        cpu.enable_call_trace(SymbolTable::new());
+
+        cpu.execute_next_instruction();
+        cpu.execute_next_instruction();
+
+        let entries = cpu.get_call_trace().unwrap().entries();
+        assert_eq!(
+            entries[0].event,
+            CallEvent::Call {
+                kind: CallKind::Subroutine,
+                from: 0x0000,
+                target: 0x0004,
+            }
+        );
+        assert_eq!(entries[0].cycle, 6);
+        assert_eq!(
+            entries[1].event,
+            CallEvent::Return {
+                kind: CallKind::Subroutine,
+                to: 0x0003,
+                callee: Some(0x0004),
+                elapsed_cycles: Some(6),
+            }
+        );
+    }
+
+    #[test]
+    fn should_not_record_anything_when_call_trace_is_disabled() {
+        let memory = &RefCell::new(MemoryMock::new(&[0x20, 0x04, 0x00, 0xEA, 0x60]));
+        let mut cpu = CPU::new_nmos(memory);
+        cpu.program_counter = 0x0000;
+        cpu.stack_pointer = 0xFF;
+
+        cpu.execute_next_instruction();
+
+        assert!(cpu.get_call_trace().is_none());
+    }
+}
@@ -0,0 +1,259 @@
+use std::collections::HashMap;
+
+use crate::{consts::Word, memory::Bus};
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum DataOperand {
+    Byte(Word),
+    Word(Word),
+    Constant(Word),
+}
+
+impl DataOperand {
+    fn addresses(&self) -> Vec<Word> {
+        return match self {
+            DataOperand::Byte(addr) => vec![*addr],
+            DataOperand::Word(addr) => vec![*addr, addr.wrapping_add(1)],
+            DataOperand::Constant(_) => vec![],
+        };
+    }
+
+    fn evaluate(&self, memory: &mut dyn Bus) -> Word {
+        return match self {
+            DataOperand::Byte(addr) => memory.peek(*addr) as Word,
+            DataOperand::Word(addr) => {
+                Word::from_le_bytes([memory.peek(*addr), memory.peek(addr.wrapping_add(1))])
+            }
+            DataOperand::Constant(value) => *value,
+        };
+    }
+
+    // "[$00FB]" reads a byte, "[$00FB].w" a little-endian word, anything else is a constant
+    fn parse(text: &str) -> Result<Self, String> {
+        if let Some(addr) = text.strip_prefix('[') {
+            let (addr, is_word) = match addr.strip_suffix("].w") {
+                Some(addr) => (addr, true),
+                None => match addr.strip_suffix(']') {
+                    Some(addr) => (addr, false),
+                    None => return Err(format!("unterminated memory reference: {text}")),
+                },
+            };
+            let addr = parse_number(addr)?;
+
+            return Ok(match is_word {
+                true => DataOperand::Word(addr),
+                false => DataOperand::Byte(addr),
+            });
+        }
+
+        return Ok(DataOperand::Constant(parse_number(text)?));
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Comparison {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+impl Comparison {
+    fn apply(&self, lhs: Word, rhs: Word) -> bool {
+        return match self {
+            Comparison::Equal => lhs == rhs,
+            Comparison::NotEqual => lhs != rhs,
+            Comparison::Less => lhs < rhs,
+            Comparison::LessOrEqual => lhs <= rhs,
+            Comparison::Greater => lhs > rhs,
+            Comparison::GreaterOrEqual => lhs >= rhs,
+        };
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum DataCondition {
+    Compare {
+        lhs: DataOperand,
+        comparison: Comparison,
+        rhs: DataOperand,
+    },
+    Changed(DataOperand),
+}
+
+impl DataCondition {
+    // accepts "<operand> <op> <operand>" with ==, !=, <, <=, > or >=, and "changed <operand>"
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let tokens: Vec<&str> = source.split_whitespace().collect();
+        return match tokens.as_slice() {
+            ["changed", operand] => Ok(DataCondition::Changed(DataOperand::parse(operand)?)),
+            [lhs, comparison, rhs] => {
+                let comparison = match *comparison {
+                    "==" => Comparison::Equal,
+                    "!=" => Comparison::NotEqual,
+                    "<" => Comparison::Less,
+                    "<=" => Comparison::LessOrEqual,
+                    ">" => Comparison::Greater,
+                    ">=" => Comparison::GreaterOrEqual,
+                    _ => return Err(format!("unknown comparison: {comparison}")),
+                };
+
+                Ok(DataCondition::Compare {
+                    lhs: DataOperand::parse(lhs)?,
+                    comparison,
+                    rhs: DataOperand::parse(rhs)?,
+                })
+            }
+            _ => Err(format!("unrecognized condition: {source}")),
+        };
+    }
+
+    pub fn addresses(&self) -> Vec<Word> {
+        return match self {
+            DataCondition::Compare { lhs, rhs, .. } => {
+                let mut addresses = lhs.addresses();
+                addresses.extend(rhs.addresses());
+                addresses
+            }
+            DataCondition::Changed(operand) => operand.addresses(),
+        };
+    }
+}
+
+struct DataBreakpoint {
+    condition: DataCondition,
+    last_value: Word,
+    last_result: bool,
+}
+
+impl DataBreakpoint {
+    fn new(condition: DataCondition, memory: &mut dyn Bus) -> Self {
+        let mut breakpoint = DataBreakpoint {
+            condition,
+            last_value: 0,
+            last_result: false,
+        };
+        breakpoint.evaluate(memory);
+
+        return breakpoint;
+    }
+
+    // returns true only on the transition from false to true, or on every change for "changed"
+    fn evaluate(&mut self, memory: &mut dyn Bus) -> bool {
+        return match self.condition {
+            DataCondition::Compare {
+                lhs,
+                comparison,
+                rhs,
+            } => {
+                let result = comparison.apply(lhs.evaluate(memory), rhs.evaluate(memory));
+                let became_true = result && !self.last_result;
+                self.last_result = result;
+                became_true
+            }
+            DataCondition::Changed(operand) => {
+                let value = operand.evaluate(memory);
+                let changed = value != self.last_value;
+                self.last_value = value;
+                changed
+            }
+        };
+    }
+}
+
+// conditions on memory contents, re-evaluated after writes to the addresses they refer to,
+// that pause execution when they become true; watchpoints pause on the accesses themselves
+#[derive(Default)]
+pub struct DataBreakpoints {
+    breakpoints: HashMap<usize, DataBreakpoint>,
+    watched_addresses: HashMap<Word, Vec<usize>>,
+    triggered: Vec<usize>,
+    next_id: usize,
+}
+
+impl DataBreakpoints {
+    pub fn is_empty(&self) -> bool {
+        return self.breakpoints.is_empty();
+    }
+
+    pub fn add(&mut self, condition: DataCondition, memory: &mut dyn Bus) -> usize {
+        let id = self.next_id;
+        self.next_id += 1;
+        for addr in condition.addresses() {
+            self.watched_addresses.entry(addr).or_default().push(id);
+        }
+        self.breakpoints
+            .insert(id, DataBreakpoint::new(condition, memory));
+
+        return id;
+    }
+
+    pub fn remove(&mut self, id: usize) -> bool {
+        let breakpoint = match self.breakpoints.remove(&id) {
+            Some(breakpoint) => breakpoint,
+            None => return false,
+        };
+        for addr in breakpoint.condition.addresses() {
+            if let Some(ids) = self.watched_addresses.get_mut(&addr) {
+                ids.retain(|watched_id| *watched_id != id);
+                if ids.is_empty() {
+                    self.watched_addresses.remove(&addr);
+                }
+            }
+        }
+
+        return true;
+    }
+
+    pub fn is_watched(&self, addr: Word) -> bool {
+        return self.watched_addresses.contains_key(&addr);
+    }
+
+    // only conditions referencing the written address are evaluated
+    pub fn notify_write(&mut self, addr: Word, memory: &mut dyn Bus) {
+        let ids = match self.watched_addresses.get(&addr) {
+            Some(ids) => ids,
+            None => return,
+        };
+
+        for id in ids {
+            let breakpoint = match self.breakpoints.get_mut(id) {
+                Some(breakpoint) => breakpoint,
+                None => continue,
+            };
+            if breakpoint.evaluate(memory) && !self.triggered.contains(id) {
+                self.triggered.push(*id);
+            }
+        }
+    }
+
+    pub fn get_triggered(&self) -> &[usize] {
+        return &self.triggered;
+    }
+
+    pub fn take_triggered(&mut self) -> Vec<usize> {
+        return std::mem::take(&mut self.triggered);
+    }
+}
+
+fn parse_number(text: &str) -> Result<Word, String> {
+    let (digits, radix) = if let Some(digits) = text.strip_prefix('$') {
+        (digits, 16)
+    } else if let Some(digits) = text.strip_prefix("0x") {
+        (digits, 16)
+    } else if let Some(digits) = text.strip_prefix('%') {
+        (digits, 2)
+    } else {
+        (text, 10)
+    };
+
+    return match Word::from_str_radix(digits, radix) {
+        Ok(value) => Ok(value),
+        Err(_) => Err(format!("incorrect number: {text}")),
+    };
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,128 @@
+#[cfg(test)]
+mod parse {
+    use crate::cpu::data_breakpoints::{Comparison, DataCondition, DataOperand};
+
+    #[test]
+    fn should_parse_comparison_of_memory_byte_with_constant() {
+        let condition = DataCondition::parse("[$00FB] >= $80");
+
+        assert_eq!(
+            condition,
+            Ok(DataCondition::Compare {
+                lhs: DataOperand::Byte(0x00FB),
+                comparison: Comparison::GreaterOrEqual,
+                rhs: DataOperand::Constant(0x80),
+            })
+        );
+    }
+
+    #[test]
+    fn should_parse_change_of_word_pointer() {
+        let condition = DataCondition::parse("changed [$FB].w");
+
+        assert_eq!(
+            condition,
+            Ok(DataCondition::Changed(DataOperand::Word(0x00FB)))
+        );
+        assert_eq!(condition.unwrap().addresses(), vec![0x00FB, 0x00FC]);
+    }
+
+    #[test]
+    fn should_reject_unknown_comparison() {
+        assert_eq!(
+            DataCondition::parse("[$FB] => 1"),
+            Err(String::from("unknown comparison: =>"))
+        );
+        assert_eq!(
+            DataCondition::parse("[$FB >= 1"),
+            Err(String::from("unterminated memory reference: [$FB"))
+        );
+    }
+}
+
+#[cfg(test)]
+mod notify_write {
+    use crate::cpu::{
+        data_breakpoints::{DataBreakpoints, DataCondition},
+        tests::MemoryMock,
+    };
+    use crate::memory::Bus;
+
+    fn write(breakpoints: &mut DataBreakpoints, memory: &mut MemoryMock, addr: u16, value: u8) {
+        memory[addr] = value;
+        breakpoints.notify_write(addr, memory as &mut dyn Bus);
+    }
+
+    #[test]
+    fn should_trigger_only_when_condition_becomes_true() {
+        let mut memory = MemoryMock::new(&[]);
+        let mut breakpoints = DataBreakpoints::default();
+        let id = breakpoints.add(DataCondition::parse("[$FB] >= $80").unwrap(), &mut memory);
+
+        write(&mut breakpoints, &mut memory, 0x00FB, 0x7F);
+        assert!(breakpoints.take_triggered().is_empty());
+
+        write(&mut breakpoints, &mut memory, 0x00FB, 0x80);
+        assert_eq!(breakpoints.take_triggered(), vec![id]);
+
+        write(&mut breakpoints, &mut memory, 0x00FB, 0x90);
+        assert!(breakpoints.take_triggered().is_empty());
+    }
+
+    #[test]
+    fn should_trigger_when_either_byte_of_word_pointer_changes() {
+        let mut memory = MemoryMock::new(&[]);
+        let mut breakpoints = DataBreakpoints::default();
+        let id = breakpoints.add(
+            DataCondition::parse("changed [$FB].w").unwrap(),
+            &mut memory,
+        );
+
+        write(&mut breakpoints, &mut memory, 0x00FC, 0x12);
+        assert_eq!(breakpoints.take_triggered(), vec![id]);
+
+        write(&mut breakpoints, &mut memory, 0x00FC, 0x12);
+        assert!(breakpoints.take_triggered().is_empty());
+    }
+
+    #[test]
+    fn should_not_evaluate_after_removal() {
+        let mut memory = MemoryMock::new(&[]);
+        let mut breakpoints = DataBreakpoints::default();
+        let id = breakpoints.add(DataCondition::parse("[$10] == 1").unwrap(), &mut memory);
+
+        assert!(breakpoints.remove(id));
+        write(&mut breakpoints, &mut memory, 0x0010, 0x01);
+
+        assert!(breakpoints.take_triggered().is_empty());
+        assert!(!breakpoints.is_watched(0x0010));
+    }
+}
+
+#[cfg(all(test, feature = "data-breakpoints"))]
+mod cpu_integration {
+    use std::cell::RefCell;
+
+    use crate::cpu::{
+        data_breakpoints::DataCondition, emulation_event::EmulationEvent, tests::MemoryMock, CPU,
+    };
+
+    #[test]
+    fn should_stop_execution_after_instruction_that_triggered_breakpoint() {
+        let memory = &RefCell::new(MemoryMock::new(&[
+            0xE6, 0xFB, // INC $FB
+            0xE6, 0xFB, // INC $FB
+            0xE6, 0xFB, // INC $FB
+            0x00, // BRK
+        ]));
+        memory.borrow_mut()[0x00FB] = 0x7E;
+        let mut cpu = CPU::new_nmos(memory);
+        cpu.program_counter = 0x0000;
+        let id = cpu.add_data_breakpoint(DataCondition::parse("[$FB] >= $80").unwrap());
+
+        let event = cpu.execute_until_break();
+
+        assert_eq!(event, EmulationEvent::DataBreakpoint(vec![id]));
+        assert_eq!(cpu.program_counter, 0x0004);
+    }
+}
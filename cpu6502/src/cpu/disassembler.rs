@@ -0,0 +1,449 @@
+use std::collections::HashMap;
+
+use super::instructions::{get_opcode_metadata, MetadataTable};
+use super::opcodes::OpcodeMetadata;
+use super::{AddressingMode, ChipVariant};
+use crate::{
+    consts::{Byte, Word},
+    memory::Bus,
+    symbols::SymbolTable,
+};
+
+const INSTRUCTION_COLUMN_WIDTH: usize = 24;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum MnemonicCase {
+    Upper,
+    Lower,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum HexPrefix {
+    Dollar,
+    ZeroX,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum AssemblerSyntax {
+    Ca65,
+    Acme,
+    Vasm,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct DisassemblyStyle {
+    pub mnemonic_case: MnemonicCase,
+    pub hex_prefix: HexPrefix,
+    pub syntax: AssemblerSyntax,
+    pub byte_column_width: usize,
+    pub show_addresses: bool,
+}
+
+impl Default for DisassemblyStyle {
+    fn default() -> Self {
+        return DisassemblyStyle {
+            mnemonic_case: MnemonicCase::Upper,
+            hex_prefix: HexPrefix::Dollar,
+            syntax: AssemblerSyntax::Ca65,
+            byte_column_width: 3,
+            show_addresses: true,
+        };
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct DisassembledInstruction {
+    pub address: Word,
+    pub bytes: Vec<Byte>,
+    metadata: Option<OpcodeMetadata>,
+}
+
+impl DisassembledInstruction {
+    pub fn mnemonic(&self) -> Option<&'static str> {
+        return self.metadata.map(|metadata| metadata.mnemonic);
+    }
+
+    pub fn length(&self) -> usize {
+        return self.bytes.len();
+    }
+
+    pub fn next_address(&self) -> Word {
+        return self.address.wrapping_add(self.bytes.len() as Word);
+    }
+
+    fn operand_byte(&self) -> Byte {
+        return self.bytes[1];
+    }
+
+    fn operand_word(&self) -> Word {
+        return Word::from_le_bytes([self.bytes[1], self.bytes[2]]);
+    }
+
+    fn branch_target(&self) -> Word {
+        let offset = self.operand_byte() as i8;
+        return self.next_address().wrapping_add(offset as Word);
+    }
+
+    // the offset of a BBR or BBS branch follows the zero page address
+    fn bit_branch_target(&self) -> Word {
+        let offset = self.bytes[2] as i8;
+        return self.next_address().wrapping_add(offset as Word);
+    }
+
+    // 65CE02 word branches are relative to the last byte of the instruction
+    fn word_branch_target(&self) -> Word {
+        return self
+            .next_address()
+            .wrapping_sub(1)
+            .wrapping_add(self.operand_word());
+    }
+
+    pub fn jump_target(&self) -> Option<Word> {
+        let metadata = self.metadata?;
+        return match metadata.addressing_mode {
+            AddressingMode::Relative => Some(self.branch_target()),
+            AddressingMode::ZeroPageRelative => Some(self.bit_branch_target()),
+            AddressingMode::RelativeWord => Some(self.word_branch_target()),
+            AddressingMode::Absolute
+                if metadata.mnemonic == "JMP" || metadata.mnemonic == "JSR" =>
+            {
+                Some(self.operand_word())
+            }
+            _ => None,
+        };
+    }
+}
+
+pub struct Disassembler {
+    style: DisassemblyStyle,
+    metadata: MetadataTable,
+    symbols: SymbolTable,
+}
+
+impl Disassembler {
+    // opcodes are decoded the way the given chip variant executes them
+    pub fn new(chip_variant: ChipVariant, style: DisassemblyStyle) -> Self {
+        return Disassembler {
+            style,
+            metadata: get_opcode_metadata(chip_variant),
+            symbols: SymbolTable::new(),
+        };
+    }
+
+    pub fn get_style(&self) -> DisassemblyStyle {
+        return self.style;
+    }
+
+    pub fn set_style(&mut self, style: DisassemblyStyle) {
+        self.style = style;
+    }
+
+    pub fn set_symbols(&mut self, symbols: SymbolTable) {
+        self.symbols = symbols;
+    }
+
+    pub fn get_symbols(&self) -> &SymbolTable {
+        return &self.symbols;
+    }
+
+    pub fn decode(&self, memory: &mut dyn Bus, address: Word) -> DisassembledInstruction {
+        let opcode = memory.peek(address);
+        let metadata = self.metadata[opcode as usize];
+        let length = match metadata {
+            Some(metadata) => metadata.length(),
+            None => 1,
+        };
+        let bytes = (0..length)
+            .map(|idx| memory.peek(address.wrapping_add(idx as Word)))
+            .collect();
+
+        return DisassembledInstruction {
+            address,
+            bytes,
+            metadata,
+        };
+    }
+
+    pub fn disassemble(
+        &self,
+        memory: &mut dyn Bus,
+        start: Word,
+        count: usize,
+    ) -> Vec<DisassembledInstruction> {
+        let mut instructions = Vec::with_capacity(count);
+        let mut address = start;
+        for _ in 0..count {
+            let instruction = self.decode(memory, address);
+            address = instruction.next_address();
+            instructions.push(instruction);
+        }
+
+        return instructions;
+    }
+
+    pub fn listing(&self, memory: &mut dyn Bus, start: Word, count: usize) -> String {
+        let instructions = self.disassemble(memory, start, count);
+        let local_labels = self.generate_local_labels(&instructions);
+
+        let mut lines: Vec<String> = Vec::new();
+        for instruction in &instructions {
+            let label = match self.symbols.get(instruction.address) {
+                Some(name) => Some(name.to_owned()),
+                None => local_labels.get(&instruction.address).cloned(),
+            };
+            if let Some(label) = label {
+                lines.push(format!("{label}:"));
+            }
+            lines.push(self.render_line(instruction, &local_labels));
+        }
+
+        return lines.join("\n");
+    }
+
+    // jump targets inside of the listed range without a symbol get an L_XXXX label
+    fn generate_local_labels(
+        &self,
+        instructions: &[DisassembledInstruction],
+    ) -> HashMap<Word, String> {
+        let mut labels = HashMap::new();
+        for instruction in instructions {
+            let target = match instruction.jump_target() {
+                Some(target) => target,
+                None => continue,
+            };
+            let in_range = instructions
+                .iter()
+                .any(|candidate| candidate.address == target);
+            if in_range && self.symbols.get(target).is_none() {
+                labels.insert(target, format!("L_{target:04X}"));
+            }
+        }
+
+        return labels;
+    }
+
+    // address and bytes columns go into a trailing comment so the listing stays re-assemblable
+    pub fn format_line(&self, instruction: &DisassembledInstruction) -> String {
+        return self.render_line(instruction, &HashMap::new());
+    }
+
+    fn render_line(
+        &self,
+        instruction: &DisassembledInstruction,
+        local_labels: &HashMap<Word, String>,
+    ) -> String {
+        let source = self.render_instruction(instruction, local_labels);
+        let mut columns: Vec<String> = Vec::new();
+        if self.style.show_addresses {
+            columns.push(format!("{}:", self.hex_word(instruction.address)));
+        }
+        if self.style.byte_column_width > 0 {
+            let bytes: Vec<String> = instruction
+                .bytes
+                .iter()
+                .take(self.style.byte_column_width)
+                .map(|byte| format!("{byte:02X}"))
+                .collect();
+            columns.push(bytes.join(" "));
+        }
+        if columns.is_empty() {
+            return source;
+        }
+
+        return format!(
+            "{source:<width$}; {}",
+            columns.join(" "),
+            width = INSTRUCTION_COLUMN_WIDTH
+        )
+        .trim_end()
+        .to_owned();
+    }
+
+    pub fn format_instruction(&self, instruction: &DisassembledInstruction) -> String {
+        return self.render_instruction(instruction, &HashMap::new());
+    }
+
+    fn render_instruction(
+        &self,
+        instruction: &DisassembledInstruction,
+        local_labels: &HashMap<Word, String>,
+    ) -> String {
+        let metadata = match instruction.metadata {
+            Some(metadata) => metadata,
+            None => return self.format_data_bytes(&instruction.bytes),
+        };
+
+        let forced_absolute = self.requires_forced_absolute(instruction, metadata);
+        if forced_absolute && self.style.syntax == AssemblerSyntax::Vasm {
+            return self.format_data_bytes(&instruction.bytes);
+        }
+
+        let mut mnemonic = self.apply_case(metadata.mnemonic);
+        if forced_absolute && self.style.syntax == AssemblerSyntax::Acme {
+            mnemonic.push_str("+2");
+        }
+        let operand = self.format_operand(
+            instruction,
+            metadata.addressing_mode,
+            forced_absolute,
+            local_labels,
+        );
+        if operand.is_empty() {
+            return mnemonic;
+        }
+
+        return format!("{mnemonic} {operand}");
+    }
+
+    fn format_operand(
+        &self,
+        instruction: &DisassembledInstruction,
+        addressing_mode: AddressingMode,
+        forced_absolute: bool,
+        local_labels: &HashMap<Word, String>,
+    ) -> String {
+        let zero_page = |addr: Byte| -> String {
+            return match self.lookup_label(addr as Word, local_labels) {
+                Some(label) => label,
+                None => self.hex_byte(addr),
+            };
+        };
+        let absolute = |addr: Word| -> String {
+            return match self.lookup_label(addr, local_labels) {
+                Some(label) => label,
+                None => self.hex_word(addr),
+            };
+        };
+        let x = self.apply_case("X");
+        let y = self.apply_case("Y");
+        let absolute_prefix = if forced_absolute && self.style.syntax == AssemblerSyntax::Ca65 {
+            "a:"
+        } else {
+            ""
+        };
+
+        return match addressing_mode {
+            AddressingMode::Implicit => String::new(),
+            AddressingMode::Accumulator => match self.style.syntax {
+                AssemblerSyntax::Ca65 => self.apply_case("A"),
+                AssemblerSyntax::Acme | AssemblerSyntax::Vasm => String::new(),
+            },
+            AddressingMode::Immediate => format!("#{}", self.hex_byte(instruction.operand_byte())),
+            AddressingMode::ImmediateWord => {
+                format!("#{}", self.hex_word(instruction.operand_word()))
+            }
+            AddressingMode::Relative => absolute(instruction.branch_target()),
+            AddressingMode::RelativeWord => absolute(instruction.word_branch_target()),
+            AddressingMode::ZeroPageRelative => format!(
+                "{},{}",
+                zero_page(instruction.operand_byte()),
+                absolute(instruction.bit_branch_target())
+            ),
+            AddressingMode::ZeroPage => zero_page(instruction.operand_byte()),
+            AddressingMode::ZeroPageX => {
+                format!("{},{x}", zero_page(instruction.operand_byte()))
+            }
+            AddressingMode::ZeroPageY => {
+                format!("{},{y}", zero_page(instruction.operand_byte()))
+            }
+            AddressingMode::Absolute => {
+                format!("{absolute_prefix}{}", absolute(instruction.operand_word()))
+            }
+            AddressingMode::AbsoluteX => format!(
+                "{absolute_prefix}{},{x}",
+                absolute(instruction.operand_word())
+            ),
+            AddressingMode::AbsoluteY => format!(
+                "{absolute_prefix}{},{y}",
+                absolute(instruction.operand_word())
+            ),
+            AddressingMode::Indirect => format!("({})", absolute(instruction.operand_word())),
+            AddressingMode::AbsoluteIndexIndirectX => {
+                format!("({},{x})", absolute(instruction.operand_word()))
+            }
+            AddressingMode::IndexIndirectX => {
+                format!("({},{x})", zero_page(instruction.operand_byte()))
+            }
+            AddressingMode::IndirectIndexY => {
+                format!("({}),{y}", zero_page(instruction.operand_byte()))
+            }
+            AddressingMode::ZeroPageIndirect => {
+                format!("({})", zero_page(instruction.operand_byte()))
+            }
+            AddressingMode::StackIndirectIndexY => format!(
+                "({},{}),{y}",
+                self.hex_byte(instruction.operand_byte()),
+                self.apply_case("SP")
+            ),
+        };
+    }
+
+    fn lookup_label(&self, addr: Word, local_labels: &HashMap<Word, String>) -> Option<String> {
+        if let Some(name) = self.symbols.get(addr) {
+            return Some(name.to_owned());
+        }
+
+        return local_labels.get(&addr).cloned();
+    }
+
+    // assemblers pick zero page encoding for operands below $0100 unless told otherwise
+    fn requires_forced_absolute(
+        &self,
+        instruction: &DisassembledInstruction,
+        metadata: OpcodeMetadata,
+    ) -> bool {
+        let zero_page_mode = match metadata.addressing_mode {
+            AddressingMode::Absolute => AddressingMode::ZeroPage,
+            AddressingMode::AbsoluteX => AddressingMode::ZeroPageX,
+            AddressingMode::AbsoluteY => AddressingMode::ZeroPageY,
+            _ => return false,
+        };
+        if instruction.operand_word() > 0x00FF {
+            return false;
+        }
+
+        return self.metadata.iter().flatten().any(|candidate| {
+            candidate.mnemonic == metadata.mnemonic && candidate.addressing_mode == zero_page_mode
+        });
+    }
+
+    fn format_data_bytes(&self, bytes: &[Byte]) -> String {
+        let directive = match self.style.syntax {
+            AssemblerSyntax::Ca65 | AssemblerSyntax::Vasm => ".byte",
+            AssemblerSyntax::Acme => "!byte",
+        };
+        let values: Vec<String> = bytes.iter().map(|byte| self.hex_byte(*byte)).collect();
+
+        return format!("{directive} {}", values.join(","));
+    }
+
+    fn apply_case(&self, text: &str) -> String {
+        return match self.style.mnemonic_case {
+            MnemonicCase::Upper => text.to_uppercase(),
+            MnemonicCase::Lower => text.to_lowercase(),
+        };
+    }
+
+    fn hex_byte(&self, value: Byte) -> String {
+        return match self.style.hex_prefix {
+            HexPrefix::Dollar => format!("${value:02X}"),
+            HexPrefix::ZeroX => format!("0x{value:02X}"),
+        };
+    }
+
+    fn hex_word(&self, value: Word) -> String {
+        return match self.style.hex_prefix {
+            HexPrefix::Dollar => format!("${value:04X}"),
+            HexPrefix::ZeroX => format!("0x{value:04X}"),
+        };
+    }
+}
+
+impl Default for Disassembler {
+    fn default() -> Self {
+        return Disassembler::new(ChipVariant::NMOS, DisassemblyStyle::default());
+    }
+}
+
+#[cfg(test)]
+mod tests;
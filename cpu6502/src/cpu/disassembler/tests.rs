@@ -0,0 +1,334 @@
+#[cfg(test)]
+mod decode {
+    use crate::cpu::{
+        disassembler::{Disassembler, DisassemblyStyle},
+        tests::MemoryMock,
+        ChipVariant,
+    };
+
+    fn format_on(chip_variant: ChipVariant, bytes: &[u8]) -> String {
+        let mut memory = MemoryMock::new(bytes);
+        let disassembler = Disassembler::new(chip_variant, DisassemblyStyle::default());
+        let instruction = disassembler.decode(&mut memory, 0x0000);
+
+        return disassembler.format_instruction(&instruction);
+    }
+
+    #[test]
+    fn should_read_as_many_bytes_as_addressing_mode_requires() {
+        let mut memory = MemoryMock::new(&[0xAD, 0x34, 0x12, 0xEA]); // LDA $1234; NOP
+        let disassembler = Disassembler::default();
+
+        let instructions = disassembler.disassemble(&mut memory, 0x0000, 2);
+
+        assert_eq!(instructions[0].bytes, vec![0xAD, 0x34, 0x12]);
+        assert_eq!(instructions[0].mnemonic(), Some("LDA"));
+        assert_eq!(instructions[1].address, 0x0003);
+        assert_eq!(instructions[1].mnemonic(), Some("NOP"));
+    }
+
+    #[test]
+    fn should_treat_unknown_opcode_as_single_byte() {
+        let mut memory = MemoryMock::new(&[0xEB, 0xEA]);
+        let disassembler = Disassembler::default();
+
+        let instruction = disassembler.decode(&mut memory, 0x0000);
+
+        assert_eq!(instruction.mnemonic(), None);
+        assert_eq!(instruction.length(), 1);
+    }
+
+    #[test]
+    fn should_decode_opcodes_as_chip_variant_executes_them() {
+        assert_eq!(format_on(ChipVariant::NMOS, &[0x80, 0x02]), "NOP #$02");
+        assert_eq!(format_on(ChipVariant::CMOS, &[0x80, 0x02]), "BRA $0004");
+        assert_eq!(format_on(ChipVariant::CMOS, &[0x64, 0x10]), "STZ $10");
+        assert_eq!(format_on(ChipVariant::CMOS, &[0xDA]), "PHX");
+        assert_eq!(format_on(ChipVariant::CMOS, &[0xB2, 0x10]), "LDA ($10)");
+        assert_eq!(format_on(ChipVariant::CMOS, &[0x87, 0x10]), "NOP");
+        assert_eq!(
+            format_on(ChipVariant::RockwellCMOS, &[0x87, 0x10]),
+            "SMB0 $10"
+        );
+        assert_eq!(
+            format_on(ChipVariant::RockwellCMOS, &[0x0F, 0x10, 0xFD]),
+            "BBR0 $10,$0000"
+        );
+        assert_eq!(format_on(ChipVariant::WDCCMOS, &[0xCB]), "WAI");
+    }
+
+    #[test]
+    fn should_decode_65ce02_operands() {
+        assert_eq!(
+            format_on(ChipVariant::CSG65CE02, &[0x83, 0x10, 0x00]),
+            "BRA $0012"
+        );
+        assert_eq!(
+            format_on(ChipVariant::CSG65CE02, &[0xF4, 0x34, 0x12]),
+            "PHW #$1234"
+        );
+        assert_eq!(
+            format_on(ChipVariant::CSG65CE02, &[0xE2, 0x05]),
+            "LDA ($05,SP),Y"
+        );
+        assert_eq!(format_on(ChipVariant::CSG65CE02, &[0x5C, 0, 0, 0]), "AUG");
+    }
+}
+
+#[cfg(test)]
+mod format_instruction {
+    use crate::cpu::{
+        disassembler::{AssemblerSyntax, Disassembler, DisassemblyStyle, HexPrefix, MnemonicCase},
+        tests::MemoryMock,
+        ChipVariant,
+    };
+
+    fn format(bytes: &[u8], style: DisassemblyStyle) -> String {
+        let mut memory = MemoryMock::new(bytes);
+        let disassembler = Disassembler::new(ChipVariant::NMOS, style);
+        let instruction = disassembler.decode(&mut memory, 0x0000);
+
+        return disassembler.format_instruction(&instruction);
+    }
+
+    #[test]
+    fn should_format_every_addressing_mode_in_default_style() {
+        let style = DisassemblyStyle::default();
+
+        assert_eq!(format(&[0xA9, 0x42], style), "LDA #$42");
+        assert_eq!(format(&[0xA5, 0x42], style), "LDA $42");
+        assert_eq!(format(&[0xB5, 0x42], style), "LDA $42,X");
+        assert_eq!(format(&[0xB6, 0x42], style), "LDX $42,Y");
+        assert_eq!(format(&[0xAD, 0x34, 0x12], style), "LDA $1234");
+        assert_eq!(format(&[0xBD, 0x34, 0x12], style), "LDA $1234,X");
+        assert_eq!(format(&[0xB9, 0x34, 0x12], style), "LDA $1234,Y");
+        assert_eq!(format(&[0xA1, 0x42], style), "LDA ($42,X)");
+        assert_eq!(format(&[0xB1, 0x42], style), "LDA ($42),Y");
+        assert_eq!(format(&[0x6C, 0x34, 0x12], style), "JMP ($1234)");
+        assert_eq!(format(&[0x0A], style), "ASL A");
+        assert_eq!(format(&[0xEA], style), "NOP");
+    }
+
+    #[test]
+    fn should_resolve_relative_branch_targets() {
+        let style = DisassemblyStyle::default();
+
+        assert_eq!(format(&[0xD0, 0x10], style), "BNE $0012");
+        assert_eq!(format(&[0xD0, 0xFE], style), "BNE $0000");
+    }
+
+    #[test]
+    fn should_use_lowercase_mnemonics_and_registers() {
+        let style = DisassemblyStyle {
+            mnemonic_case: MnemonicCase::Lower,
+            ..DisassemblyStyle::default()
+        };
+
+        assert_eq!(format(&[0xB1, 0x42], style), "lda ($42),y");
+        assert_eq!(format(&[0x0A], style), "asl a");
+    }
+
+    #[test]
+    fn should_use_0x_prefix() {
+        let style = DisassemblyStyle {
+            hex_prefix: HexPrefix::ZeroX,
+            ..DisassemblyStyle::default()
+        };
+
+        assert_eq!(format(&[0xBD, 0x34, 0x12], style), "LDA 0x1234,X");
+    }
+
+    #[test]
+    fn should_omit_accumulator_operand_for_acme_and_vasm() {
+        for syntax in [AssemblerSyntax::Acme, AssemblerSyntax::Vasm] {
+            let style = DisassemblyStyle {
+                syntax,
+                ..DisassemblyStyle::default()
+            };
+
+            assert_eq!(format(&[0x0A], style), "ASL");
+        }
+    }
+
+    #[test]
+    fn should_force_absolute_addressing_for_zero_page_operands() {
+        let ca65 = DisassemblyStyle::default();
+        let acme = DisassemblyStyle {
+            syntax: AssemblerSyntax::Acme,
+            ..DisassemblyStyle::default()
+        };
+        let vasm = DisassemblyStyle {
+            syntax: AssemblerSyntax::Vasm,
+            ..DisassemblyStyle::default()
+        };
+
+        assert_eq!(format(&[0xAD, 0x12, 0x00], ca65), "LDA a:$0012");
+        assert_eq!(format(&[0xBD, 0x12, 0x00], acme), "LDA+2 $0012,X");
+        assert_eq!(format(&[0xAD, 0x12, 0x00], vasm), ".byte $AD,$12,$00");
+    }
+
+    #[test]
+    fn should_not_force_absolute_addressing_when_there_is_no_zero_page_variant() {
+        let style = DisassemblyStyle::default();
+
+        assert_eq!(format(&[0xB9, 0x12, 0x00], style), "LDA $0012,Y");
+        assert_eq!(format(&[0x4C, 0x12, 0x00], style), "JMP $0012");
+    }
+
+    #[test]
+    fn should_emit_unknown_opcodes_as_data_bytes() {
+        let acme = DisassemblyStyle {
+            syntax: AssemblerSyntax::Acme,
+            ..DisassemblyStyle::default()
+        };
+
+        assert_eq!(format(&[0xEB], DisassemblyStyle::default()), ".byte $EB");
+        assert_eq!(format(&[0xEB], acme), "!byte $EB");
+    }
+}
+
+#[cfg(test)]
+mod listing {
+    use crate::cpu::{
+        disassembler::{Disassembler, DisassemblyStyle},
+        tests::MemoryMock,
+        ChipVariant,
+    };
+
+    #[test]
+    fn should_put_address_and_bytes_columns_in_trailing_comment() {
+        let mut memory = MemoryMock::new(&[0xA9, 0xFF, 0x8D, 0x00, 0x02]);
+        let disassembler = Disassembler::default();
+
+        assert_eq!(
+            disassembler.listing(&mut memory, 0x0000, 2),
+            concat!(
+                "LDA #$FF                ; $0000: A9 FF\n",
+                "STA $0200               ; $0002: 8D 00 02"
+            )
+        );
+    }
+
+    #[test]
+    fn should_limit_bytes_column_to_configured_width() {
+        let mut memory = MemoryMock::new(&[0x8D, 0x00, 0x02]);
+        let disassembler = Disassembler::new(
+            ChipVariant::NMOS,
+            DisassemblyStyle {
+                byte_column_width: 1,
+                show_addresses: false,
+                ..DisassemblyStyle::default()
+            },
+        );
+
+        assert_eq!(
+            disassembler.listing(&mut memory, 0x0000, 1),
+            "STA $0200               ; 8D"
+        );
+    }
+
+    #[test]
+    fn should_produce_plain_source_when_columns_are_disabled() {
+        let mut memory = MemoryMock::new(&[0x8D, 0x00, 0x02]);
+        let disassembler = Disassembler::new(
+            ChipVariant::NMOS,
+            DisassemblyStyle {
+                byte_column_width: 0,
+                show_addresses: false,
+                ..DisassemblyStyle::default()
+            },
+        );
+
+        assert_eq!(disassembler.listing(&mut memory, 0x0000, 1), "STA $0200");
+    }
+}
+
+#[cfg(test)]
+mod labels {
+    use crate::cpu::{
+        disassembler::{Disassembler, DisassemblyStyle},
+        tests::MemoryMock,
+        ChipVariant,
+    };
+    use crate::symbols::SymbolTable;
+
+    fn plain_disassembler() -> Disassembler {
+        return Disassembler::new(
+            ChipVariant::NMOS,
+            DisassemblyStyle {
+                byte_column_width: 0,
+                show_addresses: false,
+                ..DisassemblyStyle::default()
+            },
+        );
+    }
+
+    #[test]
+    fn should_substitute_symbols_for_operands() {
+        let mut memory = MemoryMock::new(&[0x20, 0x00, 0x03, 0xA5, 0xFB, 0xAD, 0x00, 0xD0]);
+        let mut disassembler = plain_disassembler();
+        let mut symbols = SymbolTable::new();
+        symbols.insert(0x0300, "print");
+        symbols.insert(0x00FB, "ptr");
+        symbols.insert(0xD000, "VIC_SPR0_X");
+        disassembler.set_symbols(symbols);
+
+        let listing = disassembler.listing(&mut memory, 0x0000, 3);
+
+        assert_eq!(listing, "JSR print\nLDA ptr\nLDA VIC_SPR0_X");
+    }
+
+    #[test]
+    fn should_emit_symbol_definitions_for_listed_addresses() {
+        let mut memory = MemoryMock::new(&[0xEA, 0x60]);
+        let mut disassembler = plain_disassembler();
+        let mut symbols = SymbolTable::new();
+        symbols.insert(0x0001, "done");
+        disassembler.set_symbols(symbols);
+
+        assert_eq!(
+            disassembler.listing(&mut memory, 0x0000, 2),
+            "NOP\ndone:\nRTS"
+        );
+    }
+
+    #[test]
+    fn should_generate_local_labels_for_in_range_targets_without_symbols() {
+        let mut memory = MemoryMock::new(&[
+            0xCA, // DEX
+            0xD0, 0xFD, // BNE $0000
+            0x4C, 0x00, 0x10, // JMP $1000
+        ]);
+        let disassembler = plain_disassembler();
+
+        assert_eq!(
+            disassembler.listing(&mut memory, 0x0000, 3),
+            "L_0000:\nDEX\nBNE L_0000\nJMP $1000"
+        );
+    }
+
+    #[test]
+    fn should_prefer_symbols_over_local_labels() {
+        let mut memory = MemoryMock::new(&[0xCA, 0xD0, 0xFD]);
+        let mut disassembler = plain_disassembler();
+        let mut symbols = SymbolTable::new();
+        symbols.insert(0x0000, "loop");
+        disassembler.set_symbols(symbols);
+
+        assert_eq!(
+            disassembler.listing(&mut memory, 0x0000, 2),
+            "loop:\nDEX\nBNE loop"
+        );
+    }
+
+    #[test]
+    fn should_keep_forced_absolute_addressing_for_zero_page_symbols() {
+        let mut memory = MemoryMock::new(&[0xAD, 0xFB, 0x00]);
+        let mut disassembler = plain_disassembler();
+        let mut symbols = SymbolTable::new();
+        symbols.insert(0x00FB, "ptr");
+        disassembler.set_symbols(symbols);
+
+        assert_eq!(disassembler.listing(&mut memory, 0x0000, 1), "LDA a:ptr");
+    }
+}
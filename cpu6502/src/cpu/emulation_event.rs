@@ -0,0 +1,69 @@
+use std::fmt;
+
+use super::statistics::InterruptSource;
+use super::uninitialized_memory::UninitializedRead;
+use crate::consts::{Byte, Word};
+use crate::memory::watchpoints::WatchpointHit;
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum EmulationEvent {
+    Breakpoint(Word),
+    // ids of the data breakpoints whose condition on memory contents became true
+    DataBreakpoint(Vec<usize>),
+    // every read or write of a watched address range made by the last instruction
+    Watchpoint(Vec<WatchpointHit>),
+    UninitializedRead(UninitializedRead),
+    Trap(Word),
+    Jam { address: Word, opcode: Byte },
+    Stop(Word),
+    Wait(Word),
+    Fault { address: Word, opcode: Byte },
+    InterruptEntry(InterruptSource),
+    CycleBudgetExhausted,
+    WatchdogExpired { address: Word, cycles: u64 },
+    ExitAddress(Word),
+    // a device asked to end the emulation, e.g. the program called exit()
+    Exit(i32),
+}
+
+impl fmt::Display for EmulationEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            EmulationEvent::Breakpoint(addr) => write!(f, "breakpoint at ${addr:04X}"),
+            EmulationEvent::DataBreakpoint(ids) => {
+                write!(f, "data breakpoints {ids:?} triggered")
+            }
+            EmulationEvent::Watchpoint(hits) => {
+                let hits: Vec<String> = hits.iter().map(|hit| hit.to_string()).collect();
+                write!(f, "watched {}", hits.join(", "))
+            }
+            EmulationEvent::UninitializedRead(read) => write!(
+                f,
+                "read of uninitialized ${:04X} by ${:04X}",
+                read.address, read.program_counter
+            ),
+            EmulationEvent::Trap(addr) => write!(f, "BRK at ${addr:04X}"),
+            EmulationEvent::Jam { address, opcode } => {
+                write!(f, "CPU jammed by ${opcode:02X} at ${address:04X}")
+            }
+            EmulationEvent::Stop(addr) => write!(f, "STP at ${addr:04X}"),
+            EmulationEvent::Wait(addr) => write!(f, "WAI at ${addr:04X}"),
+            EmulationEvent::Fault { address, opcode } => {
+                write!(f, "unsupported opcode ${opcode:02X} at ${address:04X}")
+            }
+            EmulationEvent::InterruptEntry(source) => write!(f, "{source:?} handler entered"),
+            EmulationEvent::CycleBudgetExhausted => write!(f, "cycle budget exhausted"),
+            EmulationEvent::WatchdogExpired { address, cycles } => {
+                write!(
+                    f,
+                    "watchdog expired at ${address:04X} after {cycles} cycles"
+                )
+            }
+            EmulationEvent::ExitAddress(addr) => write!(f, "exit address ${addr:04X} reached"),
+            EmulationEvent::Exit(code) => write!(f, "exited with code {code}"),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,153 @@
+#[cfg(test)]
+mod display {
+    use crate::cpu::emulation_event::EmulationEvent;
+
+    #[test]
+    fn should_describe_stop_condition() {
+        assert_eq!(EmulationEvent::Trap(0x0600).to_string(), "BRK at $0600");
+        assert_eq!(EmulationEvent::Wait(0x0600).to_string(), "WAI at $0600");
+        assert_eq!(
+            EmulationEvent::Fault {
+                address: 0x0600,
+                opcode: 0x02
+            }
+            .to_string(),
+            "unsupported opcode $02 at $0600"
+        );
+        assert_eq!(
+            EmulationEvent::CycleBudgetExhausted.to_string(),
+            "cycle budget exhausted"
+        );
+    }
+}
+
+#[cfg(test)]
+mod execute_next_instruction {
+    use std::cell::RefCell;
+
+    use crate::cpu::{
+        emulation_event::EmulationEvent, statistics::InterruptSource, tests::MemoryMock, CPU,
+    };
+
+    #[test]
+    fn should_return_no_event_for_regular_instruction() {
+        let memory = &RefCell::new(MemoryMock::new(&[0xEA])); // NOP
+        let mut cpu = CPU::new_nmos(memory);
+        cpu.program_counter = 0x0000;
+
+        assert_eq!(cpu.execute_next_instruction(), None);
+    }
+
+    #[test]
+    fn should_report_fault_instead_of_panicking_on_unsupported_opcode() {
+        let memory = &RefCell::new(MemoryMock::new(&[0xEA, 0xEB]));
+        let mut cpu = CPU::new_nmos(memory);
+        cpu.program_counter = 0x0001;
+
+        assert_eq!(
+            cpu.execute_next_instruction(),
+            Some(EmulationEvent::Fault {
+                address: 0x0001,
+                opcode: 0xEB
+            })
+        );
+    }
+
+    fn cpu_with_pending_irq(memory: &RefCell<MemoryMock>) -> CPU<&RefCell<MemoryMock>> {
+        memory.borrow_mut()[0xFFFE] = 0x00;
+        memory.borrow_mut()[0xFFFF] = 0x80;
+        let mut cpu = CPU::new_nmos(memory);
+        cpu.program_counter = 0x0000;
+        cpu.processor_status.change_interrupt_disable_flag(false);
+        cpu.set_irq_line(true);
+
+        return cpu;
+    }
+
+    #[test]
+    fn should_report_interrupt_entry_when_asked_to() {
+        let memory = &RefCell::new(MemoryMock::new(&[0xEA])); // NOP
+        let mut cpu = cpu_with_pending_irq(memory);
+        cpu.set_stop_on_interrupt_entry(true);
+
+        assert_eq!(
+            cpu.execute_next_instruction(),
+            Some(EmulationEvent::InterruptEntry(InterruptSource::Irq))
+        );
+        assert_eq!(cpu.program_counter, 0x8000);
+    }
+
+    #[test]
+    fn should_enter_interrupt_silently_by_default() {
+        let memory = &RefCell::new(MemoryMock::new(&[0xEA])); // NOP
+        let mut cpu = cpu_with_pending_irq(memory);
+
+        assert_eq!(cpu.execute_next_instruction(), None);
+        assert_eq!(cpu.program_counter, 0x8000);
+    }
+}
+
+#[cfg(test)]
+mod run {
+    use std::cell::RefCell;
+
+    use crate::cpu::{emulation_event::EmulationEvent, tests::MemoryMock, CPU};
+
+    const PROGRAM: [u8; 5] = [
+        0xE8, // INX
+        0xE8, // INX
+        0xE8, // INX
+        0xE8, // INX
+        0x00, // BRK
+    ];
+
+    #[test]
+    fn should_stop_on_brk_with_trap() {
+        let memory = &RefCell::new(MemoryMock::new(&PROGRAM));
+        let mut cpu = CPU::new_nmos(memory);
+        cpu.program_counter = 0x0000;
+
+        assert_eq!(cpu.execute_until_break(), EmulationEvent::Trap(0x0004));
+        assert_eq!(cpu.index_register_x, 4);
+    }
+
+    #[test]
+    fn should_stop_before_instruction_at_breakpoint_and_resume_past_it() {
+        let memory = &RefCell::new(MemoryMock::new(&PROGRAM));
+        let mut cpu = CPU::new_nmos(memory);
+        cpu.program_counter = 0x0000;
+        cpu.add_breakpoint(0x0002);
+
+        assert_eq!(
+            cpu.execute_until_break(),
+            EmulationEvent::Breakpoint(0x0002)
+        );
+        assert_eq!(cpu.index_register_x, 2);
+        assert_eq!(cpu.execute_until_break(), EmulationEvent::Trap(0x0004));
+    }
+
+    #[test]
+    fn should_stop_when_exit_address_is_reached() {
+        let memory = &RefCell::new(MemoryMock::new(&PROGRAM));
+        let mut cpu = CPU::new_nmos(memory);
+        cpu.program_counter = 0x0000;
+
+        assert_eq!(
+            cpu.execute_until(0x0003),
+            EmulationEvent::ExitAddress(0x0003)
+        );
+        assert_eq!(cpu.index_register_x, 3);
+    }
+
+    #[test]
+    fn should_stop_when_cycle_budget_is_exhausted() {
+        let memory = &RefCell::new(MemoryMock::new(&PROGRAM));
+        let mut cpu = CPU::new_nmos(memory);
+        cpu.cycle = 0;
+        cpu.program_counter = 0x0000;
+
+        assert_eq!(cpu.execute_cycles(4), EmulationEvent::CycleBudgetExhausted);
+        assert_eq!(cpu.index_register_x, 2);
+        assert_eq!(cpu.get_cycle(), 4);
+    }
+}
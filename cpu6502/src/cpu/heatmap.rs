@@ -0,0 +1,135 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use super::bus_activity::BusDirection;
+use crate::consts::Word;
+
+const PAGE_SIZE: usize = 0x100;
+
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct AccessCounts {
+    pub reads: u64,
+    pub writes: u64,
+    // opcode fetches, which are not counted as reads
+    pub executes: u64,
+}
+
+impl AccessCounts {
+    pub fn total(&self) -> u64 {
+        return self.reads + self.writes + self.executes;
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum HeatmapGranularity {
+    Address,
+    Page,
+}
+
+// counts every bus cycle the CPU makes, dummy accesses included, per address or per 256-byte
+// page; buckets are identified by the first address they cover
+#[derive(Debug, Clone)]
+pub struct Heatmap {
+    granularity: HeatmapGranularity,
+    counts: Vec<AccessCounts>,
+}
+
+impl Heatmap {
+    pub fn new(granularity: HeatmapGranularity) -> Self {
+        let bucket_count = match granularity {
+            HeatmapGranularity::Address => Word::MAX as usize + 1,
+            HeatmapGranularity::Page => (Word::MAX as usize + 1) / PAGE_SIZE,
+        };
+
+        return Heatmap {
+            granularity,
+            counts: vec![AccessCounts::default(); bucket_count],
+        };
+    }
+
+    pub fn get_granularity(&self) -> HeatmapGranularity {
+        return self.granularity;
+    }
+
+    // counts of the bucket covering `addr`
+    pub fn get_counts(&self, addr: Word) -> AccessCounts {
+        return self.counts[self.bucket(addr)];
+    }
+
+    // touched buckets sorted by total accesses, hottest first
+    pub fn get_hottest(&self, count: usize) -> Vec<(Word, AccessCounts)> {
+        let mut buckets = self.touched_buckets();
+        buckets
+            .sort_by(|(addr_a, a), (addr_b, b)| b.total().cmp(&a.total()).then(addr_a.cmp(addr_b)));
+        buckets.truncate(count);
+
+        return buckets;
+    }
+
+    // buckets inside `range` that were written, e.g. to catch a program storing into its ROM
+    pub fn get_written(&self, range: RangeInclusive<Word>) -> Vec<(Word, u64)> {
+        return self
+            .touched_buckets()
+            .into_iter()
+            .filter(|(addr, counts)| counts.writes > 0 && range.contains(addr))
+            .map(|(addr, counts)| (addr, counts.writes))
+            .collect();
+    }
+
+    pub fn clear(&mut self) {
+        self.counts.fill(AccessCounts::default());
+    }
+
+    pub fn record(&mut self, addr: Word, direction: BusDirection, sync: bool) {
+        let bucket = self.bucket(addr);
+        let counts = &mut self.counts[bucket];
+        match direction {
+            BusDirection::Read if sync => counts.executes += 1,
+            BusDirection::Read => counts.reads += 1,
+            BusDirection::Write => counts.writes += 1,
+        };
+    }
+
+    fn bucket(&self, addr: Word) -> usize {
+        return match self.granularity {
+            HeatmapGranularity::Address => addr as usize,
+            HeatmapGranularity::Page => addr as usize / PAGE_SIZE,
+        };
+    }
+
+    fn bucket_start(&self, bucket: usize) -> Word {
+        return match self.granularity {
+            HeatmapGranularity::Address => bucket as Word,
+            HeatmapGranularity::Page => (bucket * PAGE_SIZE) as Word,
+        };
+    }
+
+    fn touched_buckets(&self) -> Vec<(Word, AccessCounts)> {
+        return self
+            .counts
+            .iter()
+            .enumerate()
+            .filter(|(_, counts)| counts.total() > 0)
+            .map(|(bucket, counts)| (self.bucket_start(bucket), *counts))
+            .collect();
+    }
+}
+
+// the report lists every touched bucket in address order
+impl fmt::Display for Heatmap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "address     reads    writes  executes")?;
+        for (addr, counts) in self.touched_buckets() {
+            writeln!(
+                f,
+                "${addr:04X} {:>10} {:>9} {:>9}",
+                counts.reads, counts.writes, counts.executes
+            )?;
+        }
+
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,116 @@
+#[cfg(test)]
+mod record {
+    use crate::cpu::{
+        bus_activity::BusDirection,
+        heatmap::{AccessCounts, Heatmap, HeatmapGranularity},
+    };
+
+    #[test]
+    fn should_count_opcode_fetches_as_executes() {
+        let mut heatmap = Heatmap::new(HeatmapGranularity::Address);
+
+        heatmap.record(0x0600, BusDirection::Read, true);
+        heatmap.record(0x0600, BusDirection::Read, false);
+        heatmap.record(0x0600, BusDirection::Write, false);
+
+        assert_eq!(
+            heatmap.get_counts(0x0600),
+            AccessCounts {
+                reads: 1,
+                writes: 1,
+                executes: 1,
+            }
+        );
+        assert_eq!(heatmap.get_counts(0x0601).total(), 0);
+    }
+
+    #[test]
+    fn should_sum_accesses_per_page() {
+        let mut heatmap = Heatmap::new(HeatmapGranularity::Page);
+
+        heatmap.record(0xD020, BusDirection::Write, false);
+        heatmap.record(0xD0FF, BusDirection::Write, false);
+        heatmap.record(0xD100, BusDirection::Write, false);
+
+        assert_eq!(heatmap.get_counts(0xD000).writes, 2);
+        assert_eq!(
+            heatmap.get_hottest(1),
+            vec![(0xD000, heatmap.get_counts(0xD000))]
+        );
+    }
+
+    #[test]
+    fn should_sort_hottest_buckets_by_total_then_address() {
+        let mut heatmap = Heatmap::new(HeatmapGranularity::Address);
+        heatmap.record(0x0010, BusDirection::Read, false);
+        heatmap.record(0x0020, BusDirection::Read, false);
+        heatmap.record(0x0030, BusDirection::Read, false);
+        heatmap.record(0x0030, BusDirection::Read, false);
+
+        let hottest: Vec<u16> = heatmap
+            .get_hottest(3)
+            .iter()
+            .map(|(addr, _)| *addr)
+            .collect();
+
+        assert_eq!(hottest, vec![0x0030, 0x0010, 0x0020]);
+    }
+
+    #[test]
+    fn should_list_writes_inside_range() {
+        let mut heatmap = Heatmap::new(HeatmapGranularity::Address);
+        heatmap.record(0x0200, BusDirection::Write, false);
+        heatmap.record(0x8004, BusDirection::Write, false);
+        heatmap.record(0x8004, BusDirection::Write, false);
+        heatmap.record(0x8010, BusDirection::Read, false);
+
+        assert_eq!(heatmap.get_written(0x8000..=0xFFFF), vec![(0x8004, 2)]);
+    }
+
+    #[test]
+    fn should_report_touched_buckets_in_address_order() {
+        let mut heatmap = Heatmap::new(HeatmapGranularity::Page);
+        heatmap.record(0x0300, BusDirection::Write, false);
+        heatmap.record(0x0200, BusDirection::Read, true);
+
+        assert_eq!(
+            heatmap.to_string(),
+            "address     reads    writes  executes\n\
+             $0200          0         0         1\n\
+             $0300          0         1         0\n"
+        );
+    }
+}
+
+#[cfg(all(test, feature = "heatmap"))]
+mod cpu_heatmap {
+    use crate::{
+        cpu::{heatmap::HeatmapGranularity, CPU},
+        memory::VecMemory,
+    };
+
+    #[test]
+    fn should_show_hot_loop_after_run() {
+        let mut memory = VecMemory::new();
+        memory.insert(
+            0x0600,
+            &[
+                0xA2, 0x08, // LDX #$08
+                0xCA, // loop: DEX
+                0xD0, 0xFD, // BNE loop
+                0x00, // BRK
+            ],
+        );
+        let mut cpu = CPU::new_nmos(memory);
+        cpu.set_program_counter(0x0600);
+        cpu.enable_heatmap(HeatmapGranularity::Address);
+
+        cpu.execute_until_break();
+
+        let heatmap = cpu.get_heatmap().unwrap();
+        assert_eq!(heatmap.get_counts(0x0600).executes, 1);
+        assert_eq!(heatmap.get_counts(0x0602).executes, 8);
+        assert_eq!(heatmap.get_counts(0x0603).executes, 8);
+        assert!(heatmap.get_written(0x0600..=0x06FF).is_empty());
+    }
+}
@@ -0,0 +1,154 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use super::disassembler::{DisassembledInstruction, Disassembler, DisassemblyStyle};
+use super::processor_status::ProcessorStatus;
+use super::program_generator::TraceEntry;
+use super::ChipVariant;
+use crate::{
+    consts::{Byte, Word},
+    memory::Bus,
+};
+
+// tells which bank is visible at an address, e.g. by reading a bank register latch
+pub type BankResolver = Box<dyn Fn(&mut dyn Bus, Word) -> usize + Send>;
+
+// every criterion left empty accepts everything; opcodes and mnemonics together form one
+// criterion, so an instruction is traced when it matches either of them
+#[derive(Default)]
+pub struct TraceFilter {
+    address_ranges: Vec<RangeInclusive<Word>>,
+    opcodes: HashSet<Byte>,
+    mnemonics: HashSet<String>,
+    banks: HashSet<usize>,
+    bank_resolver: Option<BankResolver>,
+}
+
+impl TraceFilter {
+    pub fn new() -> Self {
+        return TraceFilter::default();
+    }
+
+    pub fn add_address_range(&mut self, range: RangeInclusive<Word>) {
+        self.address_ranges.push(range);
+    }
+
+    pub fn add_opcode(&mut self, opcode: Byte) {
+        self.opcodes.insert(opcode);
+    }
+
+    pub fn add_mnemonic(&mut self, mnemonic: &str) {
+        self.mnemonics.insert(mnemonic.to_ascii_uppercase());
+    }
+
+    pub fn set_banks(&mut self, resolver: BankResolver, banks: impl IntoIterator<Item = usize>) {
+        self.bank_resolver = Some(resolver);
+        self.banks = banks.into_iter().collect();
+    }
+
+    pub fn matches(&self, memory: &mut dyn Bus, instruction: &DisassembledInstruction) -> bool {
+        let in_range = self.address_ranges.is_empty()
+            || self
+                .address_ranges
+                .iter()
+                .any(|range| range.contains(&instruction.address));
+        if !in_range {
+            return false;
+        }
+
+        let selected_instruction = (self.opcodes.is_empty() && self.mnemonics.is_empty())
+            || self.opcodes.contains(&instruction.bytes[0])
+            || instruction
+                .mnemonic()
+                .is_some_and(|mnemonic| self.mnemonics.contains(mnemonic));
+        if !selected_instruction {
+            return false;
+        }
+
+        return match &self.bank_resolver {
+            Some(resolver) => self.banks.contains(&resolver(memory, instruction.address)),
+            None => true,
+        };
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct InstructionTraceEntry {
+    // registers and cycle before the instruction executed
+    pub state: TraceEntry,
+    pub instruction: DisassembledInstruction,
+}
+
+pub struct InstructionTrace {
+    disassembler: Disassembler,
+    filter: TraceFilter,
+    entries: Vec<InstructionTraceEntry>,
+    filtered_out: u64,
+}
+
+impl InstructionTrace {
+    pub fn new(chip_variant: ChipVariant, filter: TraceFilter) -> Self {
+        return InstructionTrace {
+            disassembler: Disassembler::new(chip_variant, DisassemblyStyle::default()),
+            filter,
+            entries: Vec::new(),
+            filtered_out: 0,
+        };
+    }
+
+    pub fn get_disassembler(&mut self) -> &mut Disassembler {
+        return &mut self.disassembler;
+    }
+
+    pub fn get_filter(&mut self) -> &mut TraceFilter {
+        return &mut self.filter;
+    }
+
+    pub fn entries(&self) -> &[InstructionTraceEntry] {
+        return &self.entries;
+    }
+
+    pub fn get_filtered_out_count(&self) -> u64 {
+        return self.filtered_out;
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.filtered_out = 0;
+    }
+
+    pub fn record(&mut self, memory: &mut dyn Bus, state: TraceEntry) {
+        let instruction = self.disassembler.decode(memory, state.program_counter);
+        if !self.filter.matches(memory, &instruction) {
+            self.filtered_out += 1;
+            return;
+        }
+
+        self.entries
+            .push(InstructionTraceEntry { state, instruction });
+    }
+}
+
+impl fmt::Display for InstructionTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entry in &self.entries {
+            writeln!(
+                f,
+                "{:>10} {} A={:02X} X={:02X} Y={:02X} SP={:02X} P={}",
+                entry.state.cycle,
+                self.disassembler.format_line(&entry.instruction),
+                entry.state.accumulator,
+                entry.state.index_register_x,
+                entry.state.index_register_y,
+                entry.state.stack_pointer,
+                ProcessorStatus::from(entry.state.processor_status)
+            )?;
+        }
+
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests;
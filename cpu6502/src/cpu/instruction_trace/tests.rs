@@ -0,0 +1,117 @@
+#[cfg(test)]
+mod matches {
+    use crate::{
+        cpu::{disassembler::Disassembler, instruction_trace::TraceFilter},
+        memory::VecMemory,
+    };
+
+    fn memory() -> VecMemory {
+        let mut memory = VecMemory::new();
+        memory.insert(0x0200, &[0xA9, 0x01, 0x8D, 0x00, 0x04, 0xE8]); // LDA #$01; STA $0400; INX
+        memory.insert(0x8000, &[0xE8]); // INX
+        return memory;
+    }
+
+    #[test]
+    fn should_accept_everything_without_criteria() {
+        let mut memory = memory();
+        let disassembler = Disassembler::default();
+        let filter = TraceFilter::new();
+
+        let instruction = disassembler.decode(&mut memory, 0x0200);
+        assert!(filter.matches(&mut memory, &instruction));
+        let instruction = disassembler.decode(&mut memory, 0x8000);
+        assert!(filter.matches(&mut memory, &instruction));
+    }
+
+    #[test]
+    fn should_restrict_to_address_ranges() {
+        let mut memory = memory();
+        let disassembler = Disassembler::default();
+        let mut filter = TraceFilter::new();
+        filter.add_address_range(0x8000..=0x8FFF);
+
+        let instruction = disassembler.decode(&mut memory, 0x0205);
+        assert!(!filter.matches(&mut memory, &instruction));
+        let instruction = disassembler.decode(&mut memory, 0x8000);
+        assert!(filter.matches(&mut memory, &instruction));
+    }
+
+    #[test]
+    fn should_select_instructions_by_opcode_or_mnemonic() {
+        let mut memory = memory();
+        let disassembler = Disassembler::default();
+        let mut filter = TraceFilter::new();
+        filter.add_opcode(0xA9);
+        filter.add_mnemonic("sta");
+
+        let instruction = disassembler.decode(&mut memory, 0x0200);
+        assert!(filter.matches(&mut memory, &instruction));
+        let instruction = disassembler.decode(&mut memory, 0x0202);
+        assert!(filter.matches(&mut memory, &instruction));
+        let instruction = disassembler.decode(&mut memory, 0x0205);
+        assert!(!filter.matches(&mut memory, &instruction));
+    }
+
+    #[test]
+    fn should_restrict_to_banks_reported_by_resolver() {
+        let mut memory = memory();
+        let disassembler = Disassembler::default();
+        let mut filter = TraceFilter::new();
+        filter.set_banks(Box::new(|memory, _| memory.read(0xDE00) as usize), [2]);
+
+        let instruction = disassembler.decode(&mut memory, 0x8000);
+        assert!(!filter.matches(&mut memory, &instruction));
+        memory[0xDE00] = 2;
+        let instruction = disassembler.decode(&mut memory, 0x8000);
+        assert!(filter.matches(&mut memory, &instruction));
+    }
+}
+
+#[cfg(all(test, feature = "instruction-trace"))]
+mod cpu_integration {
+    use std::cell::RefCell;
+
+    use crate::cpu::{instruction_trace::TraceFilter, tests::MemoryMock, CPU};
+
+    #[test]
+    fn should_record_only_instructions_passing_filter() {
+        let memory = &RefCell::new(MemoryMock::new(&[
+            0xA9, 0x01, // LDA #$01
+            0xE8, // INX
+            0xE8, // INX
+        ]));
+        let mut cpu = CPU::new_nmos(memory);
+        cpu.program_counter = 0x0000;
+        cpu.cycle = 0;
+        let mut filter = TraceFilter::new();
+        filter.add_mnemonic("INX");
+        cpu.enable_instruction_trace(filter);
+
+        cpu.execute_next_instruction();
+        cpu.execute_next_instruction();
+        cpu.execute_next_instruction();
+
+        let trace = cpu.get_instruction_trace().unwrap();
+        assert_eq!(trace.entries().len(), 2);
+        assert_eq!(trace.get_filtered_out_count(), 1);
+        assert_eq!(
+            trace.to_string(),
+            "         2 INX                     ; $0002: E8 A=01 X=00 Y=00 SP=FD P=nv-bdIzc\n         \
+             4 INX                     ; $0003: E8 A=01 X=01 Y=00 SP=FD P=nv-bdIzc\n"
+        );
+    }
+
+    #[test]
+    fn should_decode_instructions_as_the_traced_chip_variant() {
+        let memory = &RefCell::new(MemoryMock::new(&[0xDA])); // PHX
+        let mut cpu = CPU::new_cmos(memory);
+        cpu.program_counter = 0x0000;
+        cpu.enable_instruction_trace(TraceFilter::new());
+
+        cpu.execute_next_instruction();
+
+        let trace = cpu.get_instruction_trace().unwrap();
+        assert_eq!(trace.entries()[0].instruction.mnemonic(), Some("PHX"));
+    }
+}
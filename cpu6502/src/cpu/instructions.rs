@@ -1,25 +1,555 @@
 use std::collections::HashMap;
 
 use crate::consts::Byte;
+use crate::memory::Bus;
 
 use self::arithmetic::*;
+use self::bit_operations::*;
 use self::branches::*;
+use self::immediate_combos::*;
 use self::inc_and_decrements::*;
 use self::jumps_and_calls::*;
 use self::load_and_store_ops::*;
 use self::logical::*;
 use self::register_transfers::*;
+use self::rmw_combos::*;
 use self::shifts::*;
 use self::stack_operations::*;
 use self::status_flag_changes::*;
 use self::system_functions::*;
+use self::unstable_opcodes::*;
 
-use super::OpcodeHandler;
+use super::undocumented_opcodes::UndocumentedClass;
+use super::{AddressingMode, ChipVariant, OpcodeHandler};
 use crate::cpu::opcodes::*;
 
-pub fn get_instructions() -> HashMap<Byte, OpcodeHandler> {
+pub type OpcodeTable<M> = [Option<OpcodeHandler<M>>; 256];
+
+pub type MetadataTable = [Option<OpcodeMetadata>; 256];
+
+pub type LengthTable = [u8; 256];
+
+// every chip variant decodes through its own table; opcodes left empty are handled by the
+// unknown opcode policy
+pub fn get_instructions<M: Bus>(chip_variant: ChipVariant) -> OpcodeTable<M> {
+    return match chip_variant {
+        ChipVariant::NMOS => get_nmos_table(),
+        ChipVariant::Ricoh2A03 => get_ricoh_2a03_table(),
+        ChipVariant::CMOS => get_cmos_table(vec![]),
+        ChipVariant::RockwellCMOS => get_cmos_table(vec![get_rockwell_instructions()]),
+        // WDC chips include the Rockwell bit instructions as well
+        ChipVariant::WDCCMOS => {
+            get_cmos_table(vec![get_rockwell_instructions(), get_wdc_instructions()])
+        }
+        ChipVariant::CSG65CE02 => {
+            get_cmos_table(vec![get_rockwell_instructions(), get_65ce02_instructions()])
+        }
+    };
+}
+
+// mnemonic, addressing mode and length of every opcode as the variant decodes it, composed
+// the same way as the dispatch table of the variant
+pub fn get_opcode_metadata(chip_variant: ChipVariant) -> MetadataTable {
+    return match chip_variant {
+        ChipVariant::NMOS | ChipVariant::Ricoh2A03 => build_table(get_nmos_metadata()),
+        ChipVariant::CMOS => get_cmos_metadata_table(vec![]),
+        ChipVariant::RockwellCMOS => get_cmos_metadata_table(vec![get_rockwell_metadata()]),
+        ChipVariant::WDCCMOS => {
+            get_cmos_metadata_table(vec![get_rockwell_metadata(), get_wdc_metadata()])
+        }
+        ChipVariant::CSG65CE02 => {
+            get_cmos_metadata_table(vec![get_rockwell_metadata(), get_65ce02_metadata()])
+        }
+    };
+}
+
+// instruction lengths in bytes as the variant decodes them, for stepping over an instruction
+// without executing it; opcodes of no instruction count as a single byte
+pub fn get_instruction_lengths(chip_variant: ChipVariant) -> LengthTable {
+    let metadata = get_opcode_metadata(chip_variant);
+    return std::array::from_fn(|opcode| match metadata[opcode] {
+        Some(metadata) => metadata.length(),
+        None => 1,
+    });
+}
+
+fn build_table<T: Copy>(entries: HashMap<Byte, T>) -> [Option<T>; 256] {
+    let mut table: [Option<T>; 256] = [None; 256];
+    for (opcode, entry) in entries {
+        table[opcode as usize] = Some(entry);
+    }
+
+    return table;
+}
+
+fn get_nmos_table<M: Bus>() -> OpcodeTable<M> {
+    return build_table(get_nmos_instructions());
+}
+
+// the 2A03 decodes exactly like the NMOS 6502, including the undocumented opcodes; the
+// missing decimal mode is handled by the adder
+fn get_ricoh_2a03_table<M: Bus>() -> OpcodeTable<M> {
+    return build_table(get_nmos_instructions());
+}
+
+fn get_cmos_table<M: Bus>(additions: Vec<HashMap<Byte, OpcodeHandler<M>>>) -> OpcodeTable<M> {
+    return build_cmos_table(
+        get_nmos_instructions(),
+        get_cmos_instructions(),
+        additions,
+        get_cmos_nop,
+    );
+}
+
+fn get_cmos_metadata_table(additions: Vec<HashMap<Byte, OpcodeMetadata>>) -> MetadataTable {
+    return build_cmos_table(
+        get_nmos_metadata(),
+        get_cmos_metadata(),
+        additions,
+        get_cmos_nop_metadata,
+    );
+}
+
+// undocumented NMOS opcodes do not exist on CMOS chips, some of them became new instructions
+// and every other one is a NOP of defined length
+fn build_cmos_table<T: Copy>(
+    mut entries: HashMap<Byte, T>,
+    cmos: HashMap<Byte, T>,
+    additions: Vec<HashMap<Byte, T>>,
+    nop: fn(Byte) -> T,
+) -> [Option<T>; 256] {
+    entries.retain(|opcode, _| UndocumentedClass::classify(*opcode).is_none());
+    entries.extend(cmos);
+    for addition in additions {
+        entries.extend(addition);
+    }
+
+    let mut table = build_table(entries);
+    for opcode in 0..=Byte::MAX {
+        if table[opcode as usize].is_none() {
+            table[opcode as usize] = Some(nop(opcode));
+        }
+    }
+
+    return table;
+}
+
+fn get_cmos_nop<M: Bus>(opcode: Byte) -> OpcodeHandler<M> {
+    return match opcode {
+        0x02 | 0x22 | 0x42 | 0x62 | 0x82 | 0xC2 | 0xE2 => nop_im,
+        0x44 => nop_zp,
+        0x54 | 0xD4 | 0xF4 => nop_zpx,
+        0xDC | 0xFC => nop_a,
+        0x5C => nop_a_slow,
+        _ => nop_single_cycle,
+    };
+}
+
+fn get_cmos_nop_metadata(opcode: Byte) -> OpcodeMetadata {
+    let addressing_mode = match opcode {
+        0x02 | 0x22 | 0x42 | 0x62 | 0x82 | 0xC2 | 0xE2 => AddressingMode::Immediate,
+        0x44 => AddressingMode::ZeroPage,
+        0x54 | 0xD4 | 0xF4 => AddressingMode::ZeroPageX,
+        0x5C | 0xDC | 0xFC => AddressingMode::Absolute,
+        _ => AddressingMode::Implicit,
+    };
+
+    return OpcodeMetadata::new("NOP", addressing_mode);
+}
+
+fn get_wdc_instructions<M: Bus>() -> HashMap<Byte, OpcodeHandler<M>> {
+    return HashMap::from([(STP, stp as OpcodeHandler<M>), (WAI, wai)]);
+}
+
+fn get_wdc_metadata() -> HashMap<Byte, OpcodeMetadata> {
     return HashMap::from([
-        (ADC_IM, adc_im as OpcodeHandler),
+        (STP, OpcodeMetadata::new("STP", AddressingMode::Implicit)),
+        (WAI, OpcodeMetadata::new("WAI", AddressingMode::Implicit)),
+    ]);
+}
+
+fn get_65ce02_instructions<M: Bus>() -> HashMap<Byte, OpcodeHandler<M>> {
+    return HashMap::from([
+        (ASR_ACC, asr_acc as OpcodeHandler<M>),
+        (ASR_ZP, asr_zp),
+        (ASR_ZPX, asr_zpx),
+        (ASW_A, asw_a),
+        (AUG, aug),
+        (BCC_W, bcc_w),
+        (BCS_W, bcs_w),
+        (BEQ_W, beq_w),
+        (BMI_W, bmi_w),
+        (BNE_W, bne_w),
+        (BPL_W, bpl_w),
+        (BRA_W, bra_w),
+        (BSR_W, bsr_w),
+        (BVC_W, bvc_w),
+        (BVS_W, bvs_w),
+        (CLE, cle),
+        (CPZ_IM, cpz_im),
+        (CPZ_ZP, cpz_zp),
+        (CPZ_A, cpz_a),
+        (DEW_ZP, dew_zp),
+        (DEZ, dez),
+        (INW_ZP, inw_zp),
+        (INZ, inz),
+        (JSR_IN, jsr_in),
+        (JSR_AINX, jsr_ainx),
+        (LDA_ISY, lda_isy),
+        (LDZ_IM, ldz_im),
+        (LDZ_A, ldz_a),
+        (LDZ_AX, ldz_ax),
+        (NEG_ACC, neg_acc),
+        (PHW_IM, phw_im),
+        (PHW_A, phw_a),
+        (PHZ, phz),
+        (PLZ, plz),
+        (ROW_A, row_a),
+        (RTS_IM, rts_im),
+        (SEE, see),
+        (STA_ISY, sta_isy),
+        (STX_AY, stx_ay),
+        (STY_AX, sty_ax),
+        (TAB, tab),
+        (TAZ, taz),
+        (TBA, tba),
+        (TSY, tsy),
+        (TYS, tys),
+        (TZA, tza),
+    ]);
+}
+
+fn get_65ce02_metadata() -> HashMap<Byte, OpcodeMetadata> {
+    return HashMap::from([
+        (
+            ASR_ACC,
+            OpcodeMetadata::new("ASR", AddressingMode::Accumulator),
+        ),
+        (ASR_ZP, OpcodeMetadata::new("ASR", AddressingMode::ZeroPage)),
+        (
+            ASR_ZPX,
+            OpcodeMetadata::new("ASR", AddressingMode::ZeroPageX),
+        ),
+        (ASW_A, OpcodeMetadata::new("ASW", AddressingMode::Absolute)),
+        // AUG reserves three operand bytes for a future extension and ignores them
+        (
+            AUG,
+            OpcodeMetadata::with_length("AUG", AddressingMode::Implicit, 4),
+        ),
+        (
+            BCC_W,
+            OpcodeMetadata::new("BCC", AddressingMode::RelativeWord),
+        ),
+        (
+            BCS_W,
+            OpcodeMetadata::new("BCS", AddressingMode::RelativeWord),
+        ),
+        (
+            BEQ_W,
+            OpcodeMetadata::new("BEQ", AddressingMode::RelativeWord),
+        ),
+        (
+            BMI_W,
+            OpcodeMetadata::new("BMI", AddressingMode::RelativeWord),
+        ),
+        (
+            BNE_W,
+            OpcodeMetadata::new("BNE", AddressingMode::RelativeWord),
+        ),
+        (
+            BPL_W,
+            OpcodeMetadata::new("BPL", AddressingMode::RelativeWord),
+        ),
+        (
+            BRA_W,
+            OpcodeMetadata::new("BRA", AddressingMode::RelativeWord),
+        ),
+        (
+            BSR_W,
+            OpcodeMetadata::new("BSR", AddressingMode::RelativeWord),
+        ),
+        (
+            BVC_W,
+            OpcodeMetadata::new("BVC", AddressingMode::RelativeWord),
+        ),
+        (
+            BVS_W,
+            OpcodeMetadata::new("BVS", AddressingMode::RelativeWord),
+        ),
+        (CLE, OpcodeMetadata::new("CLE", AddressingMode::Implicit)),
+        (
+            CPZ_IM,
+            OpcodeMetadata::new("CPZ", AddressingMode::Immediate),
+        ),
+        (CPZ_ZP, OpcodeMetadata::new("CPZ", AddressingMode::ZeroPage)),
+        (CPZ_A, OpcodeMetadata::new("CPZ", AddressingMode::Absolute)),
+        (DEW_ZP, OpcodeMetadata::new("DEW", AddressingMode::ZeroPage)),
+        (DEZ, OpcodeMetadata::new("DEZ", AddressingMode::Implicit)),
+        (INW_ZP, OpcodeMetadata::new("INW", AddressingMode::ZeroPage)),
+        (INZ, OpcodeMetadata::new("INZ", AddressingMode::Implicit)),
+        (JSR_IN, OpcodeMetadata::new("JSR", AddressingMode::Indirect)),
+        (
+            JSR_AINX,
+            OpcodeMetadata::new("JSR", AddressingMode::AbsoluteIndexIndirectX),
+        ),
+        (
+            LDA_ISY,
+            OpcodeMetadata::new("LDA", AddressingMode::StackIndirectIndexY),
+        ),
+        (
+            LDZ_IM,
+            OpcodeMetadata::new("LDZ", AddressingMode::Immediate),
+        ),
+        (LDZ_A, OpcodeMetadata::new("LDZ", AddressingMode::Absolute)),
+        (
+            LDZ_AX,
+            OpcodeMetadata::new("LDZ", AddressingMode::AbsoluteX),
+        ),
+        (
+            NEG_ACC,
+            OpcodeMetadata::new("NEG", AddressingMode::Accumulator),
+        ),
+        (
+            PHW_IM,
+            OpcodeMetadata::new("PHW", AddressingMode::ImmediateWord),
+        ),
+        (PHW_A, OpcodeMetadata::new("PHW", AddressingMode::Absolute)),
+        (PHZ, OpcodeMetadata::new("PHZ", AddressingMode::Implicit)),
+        (PLZ, OpcodeMetadata::new("PLZ", AddressingMode::Implicit)),
+        (ROW_A, OpcodeMetadata::new("ROW", AddressingMode::Absolute)),
+        (
+            RTS_IM,
+            OpcodeMetadata::new("RTS", AddressingMode::Immediate),
+        ),
+        (SEE, OpcodeMetadata::new("SEE", AddressingMode::Implicit)),
+        (
+            STA_ISY,
+            OpcodeMetadata::new("STA", AddressingMode::StackIndirectIndexY),
+        ),
+        (
+            STX_AY,
+            OpcodeMetadata::new("STX", AddressingMode::AbsoluteY),
+        ),
+        (
+            STY_AX,
+            OpcodeMetadata::new("STY", AddressingMode::AbsoluteX),
+        ),
+        (TAB, OpcodeMetadata::new("TAB", AddressingMode::Implicit)),
+        (TAZ, OpcodeMetadata::new("TAZ", AddressingMode::Implicit)),
+        (TBA, OpcodeMetadata::new("TBA", AddressingMode::Implicit)),
+        (TSY, OpcodeMetadata::new("TSY", AddressingMode::Implicit)),
+        (TYS, OpcodeMetadata::new("TYS", AddressingMode::Implicit)),
+        (TZA, OpcodeMetadata::new("TZA", AddressingMode::Implicit)),
+    ]);
+}
+
+fn get_rockwell_instructions<M: Bus>() -> HashMap<Byte, OpcodeHandler<M>> {
+    return HashMap::from([
+        (BBR0, bbr::<M, 0> as OpcodeHandler<M>),
+        (BBR1, bbr::<M, 1>),
+        (BBR2, bbr::<M, 2>),
+        (BBR3, bbr::<M, 3>),
+        (BBR4, bbr::<M, 4>),
+        (BBR5, bbr::<M, 5>),
+        (BBR6, bbr::<M, 6>),
+        (BBR7, bbr::<M, 7>),
+        (BBS0, bbs::<M, 0>),
+        (BBS1, bbs::<M, 1>),
+        (BBS2, bbs::<M, 2>),
+        (BBS3, bbs::<M, 3>),
+        (BBS4, bbs::<M, 4>),
+        (BBS5, bbs::<M, 5>),
+        (BBS6, bbs::<M, 6>),
+        (BBS7, bbs::<M, 7>),
+        (RMB0, rmb::<M, 0>),
+        (RMB1, rmb::<M, 1>),
+        (RMB2, rmb::<M, 2>),
+        (RMB3, rmb::<M, 3>),
+        (RMB4, rmb::<M, 4>),
+        (RMB5, rmb::<M, 5>),
+        (RMB6, rmb::<M, 6>),
+        (RMB7, rmb::<M, 7>),
+        (SMB0, smb::<M, 0>),
+        (SMB1, smb::<M, 1>),
+        (SMB2, smb::<M, 2>),
+        (SMB3, smb::<M, 3>),
+        (SMB4, smb::<M, 4>),
+        (SMB5, smb::<M, 5>),
+        (SMB6, smb::<M, 6>),
+        (SMB7, smb::<M, 7>),
+    ]);
+}
+
+const BBR_MNEMONICS: [&str; 8] = [
+    "BBR0", "BBR1", "BBR2", "BBR3", "BBR4", "BBR5", "BBR6", "BBR7",
+];
+const BBS_MNEMONICS: [&str; 8] = [
+    "BBS0", "BBS1", "BBS2", "BBS3", "BBS4", "BBS5", "BBS6", "BBS7",
+];
+const RMB_MNEMONICS: [&str; 8] = [
+    "RMB0", "RMB1", "RMB2", "RMB3", "RMB4", "RMB5", "RMB6", "RMB7",
+];
+const SMB_MNEMONICS: [&str; 8] = [
+    "SMB0", "SMB1", "SMB2", "SMB3", "SMB4", "SMB5", "SMB6", "SMB7",
+];
+
+// the tested or modified bit is part of the mnemonic and selects the column of the opcode
+fn get_rockwell_metadata() -> HashMap<Byte, OpcodeMetadata> {
+    let mut metadata = HashMap::new();
+    for bit in 0..8 {
+        let column = (bit as Byte) << 4;
+        metadata.insert(
+            BBR0 | column,
+            OpcodeMetadata::new(BBR_MNEMONICS[bit], AddressingMode::ZeroPageRelative),
+        );
+        metadata.insert(
+            BBS0 | column,
+            OpcodeMetadata::new(BBS_MNEMONICS[bit], AddressingMode::ZeroPageRelative),
+        );
+        metadata.insert(
+            RMB0 | column,
+            OpcodeMetadata::new(RMB_MNEMONICS[bit], AddressingMode::ZeroPage),
+        );
+        metadata.insert(
+            SMB0 | column,
+            OpcodeMetadata::new(SMB_MNEMONICS[bit], AddressingMode::ZeroPage),
+        );
+    }
+
+    return metadata;
+}
+
+fn get_cmos_instructions<M: Bus>() -> HashMap<Byte, OpcodeHandler<M>> {
+    return HashMap::from([
+        (ADC_IM, adc_im_cmos as OpcodeHandler<M>),
+        (ADC_ZP, adc_zp_cmos),
+        (ADC_ZPX, adc_zpx_cmos),
+        (ADC_A, adc_a_cmos),
+        (ADC_AX, adc_ax_cmos),
+        (ADC_AY, adc_ay_cmos),
+        (ADC_INX, adc_inx_cmos),
+        (ADC_INY, adc_iny_cmos),
+        (ADC_IZP, adc_izp),
+        (SBC_IM, sbc_im_cmos),
+        (SBC_ZP, sbc_zp_cmos),
+        (SBC_ZPX, sbc_zpx_cmos),
+        (SBC_A, sbc_a_cmos),
+        (SBC_AX, sbc_ax_cmos),
+        (SBC_AY, sbc_ay_cmos),
+        (SBC_INX, sbc_inx_cmos),
+        (SBC_INY, sbc_iny_cmos),
+        (SBC_IZP, sbc_izp),
+        (AND_IZP, and_izp),
+        (BIT_IM, bit_im),
+        (BIT_ZPX, bit_zpx),
+        (BIT_AX, bit_ax),
+        (BRA, bra),
+        (CMP_IZP, cmp_izp),
+        (DEC_ACC, dec_acc),
+        (EOR_IZP, eor_izp),
+        (INC_ACC, inc_acc),
+        (JMP_AINX, jmp_ainx),
+        (LDA_IZP, lda_izp),
+        (ORA_IZP, ora_izp),
+        (PHX, phx),
+        (PHY, phy),
+        (PLX, plx),
+        (PLY, ply),
+        (STA_IZP, sta_izp),
+        (STZ_ZP, stz_zp),
+        (STZ_ZPX, stz_zpx),
+        (STZ_A, stz_a),
+        (STZ_AX, stz_ax),
+        (TRB_ZP, trb_zp),
+        (TRB_A, trb_a),
+        (TSB_ZP, tsb_zp),
+        (TSB_A, tsb_a),
+    ]);
+}
+
+fn get_cmos_metadata() -> HashMap<Byte, OpcodeMetadata> {
+    return HashMap::from([
+        (
+            ADC_IZP,
+            OpcodeMetadata::new("ADC", AddressingMode::ZeroPageIndirect),
+        ),
+        (
+            AND_IZP,
+            OpcodeMetadata::new("AND", AddressingMode::ZeroPageIndirect),
+        ),
+        (
+            BIT_IM,
+            OpcodeMetadata::new("BIT", AddressingMode::Immediate),
+        ),
+        (
+            BIT_ZPX,
+            OpcodeMetadata::new("BIT", AddressingMode::ZeroPageX),
+        ),
+        (
+            BIT_AX,
+            OpcodeMetadata::new("BIT", AddressingMode::AbsoluteX),
+        ),
+        (BRA, OpcodeMetadata::new("BRA", AddressingMode::Relative)),
+        (
+            CMP_IZP,
+            OpcodeMetadata::new("CMP", AddressingMode::ZeroPageIndirect),
+        ),
+        (
+            DEC_ACC,
+            OpcodeMetadata::new("DEC", AddressingMode::Accumulator),
+        ),
+        (
+            EOR_IZP,
+            OpcodeMetadata::new("EOR", AddressingMode::ZeroPageIndirect),
+        ),
+        (
+            INC_ACC,
+            OpcodeMetadata::new("INC", AddressingMode::Accumulator),
+        ),
+        (
+            JMP_AINX,
+            OpcodeMetadata::new("JMP", AddressingMode::AbsoluteIndexIndirectX),
+        ),
+        (
+            LDA_IZP,
+            OpcodeMetadata::new("LDA", AddressingMode::ZeroPageIndirect),
+        ),
+        (
+            ORA_IZP,
+            OpcodeMetadata::new("ORA", AddressingMode::ZeroPageIndirect),
+        ),
+        (PHX, OpcodeMetadata::new("PHX", AddressingMode::Implicit)),
+        (PHY, OpcodeMetadata::new("PHY", AddressingMode::Implicit)),
+        (PLX, OpcodeMetadata::new("PLX", AddressingMode::Implicit)),
+        (PLY, OpcodeMetadata::new("PLY", AddressingMode::Implicit)),
+        (
+            SBC_IZP,
+            OpcodeMetadata::new("SBC", AddressingMode::ZeroPageIndirect),
+        ),
+        (
+            STA_IZP,
+            OpcodeMetadata::new("STA", AddressingMode::ZeroPageIndirect),
+        ),
+        (STZ_ZP, OpcodeMetadata::new("STZ", AddressingMode::ZeroPage)),
+        (
+            STZ_ZPX,
+            OpcodeMetadata::new("STZ", AddressingMode::ZeroPageX),
+        ),
+        (STZ_A, OpcodeMetadata::new("STZ", AddressingMode::Absolute)),
+        (
+            STZ_AX,
+            OpcodeMetadata::new("STZ", AddressingMode::AbsoluteX),
+        ),
+        (TRB_ZP, OpcodeMetadata::new("TRB", AddressingMode::ZeroPage)),
+        (TRB_A, OpcodeMetadata::new("TRB", AddressingMode::Absolute)),
+        (TSB_ZP, OpcodeMetadata::new("TSB", AddressingMode::ZeroPage)),
+        (TSB_A, OpcodeMetadata::new("TSB", AddressingMode::Absolute)),
+    ]);
+}
+
+fn get_nmos_instructions<M: Bus>() -> HashMap<Byte, OpcodeHandler<M>> {
+    return HashMap::from([
+        (ADC_IM, adc_im as OpcodeHandler<M>),
         (ADC_ZP, adc_zp),
         (ADC_ZPX, adc_zpx),
         (ADC_A, adc_a),
@@ -48,6 +578,12 @@ pub fn get_instructions() -> HashMap<Byte, OpcodeHandler> {
         (EOR_AY, eor_ay),
         (EOR_INX, eor_inx),
         (EOR_INY, eor_iny),
+        (LAX_ZP, lax_zp),
+        (LAX_ZPY, lax_zpy),
+        (LAX_A, lax_a),
+        (LAX_AY, lax_ay),
+        (LAX_INX, lax_inx),
+        (LAX_INY, lax_iny),
         (LDA_IM, lda_im),
         (LDA_ZP, lda_zp),
         (LDA_ZPX, lda_zpx),
@@ -117,6 +653,10 @@ pub fn get_instructions() -> HashMap<Byte, OpcodeHandler> {
         (STA_AY, sta_ay),
         (STA_INX, sta_inx),
         (STA_INY, sta_iny),
+        (SAX_ZP, sax_zp),
+        (SAX_ZPY, sax_zpy),
+        (SAX_A, sax_a),
+        (SAX_INX, sax_inx),
         (STX_ZP, stx_zp),
         (STX_ZPY, stx_zpy),
         (STX_A, stx_a),
@@ -170,17 +710,804 @@ pub fn get_instructions() -> HashMap<Byte, OpcodeHandler> {
         (TXA, txa),
         (TAY, tay),
         (TYA, tya),
+        (SLO_ZP, slo_zp),
+        (SLO_ZPX, slo_zpx),
+        (SLO_A, slo_a),
+        (SLO_AX, slo_ax),
+        (SLO_AY, slo_ay),
+        (SLO_INX, slo_inx),
+        (SLO_INY, slo_iny),
+        (RLA_ZP, rla_zp),
+        (RLA_ZPX, rla_zpx),
+        (RLA_A, rla_a),
+        (RLA_AX, rla_ax),
+        (RLA_AY, rla_ay),
+        (RLA_INX, rla_inx),
+        (RLA_INY, rla_iny),
+        (SRE_ZP, sre_zp),
+        (SRE_ZPX, sre_zpx),
+        (SRE_A, sre_a),
+        (SRE_AX, sre_ax),
+        (SRE_AY, sre_ay),
+        (SRE_INX, sre_inx),
+        (SRE_INY, sre_iny),
+        (RRA_ZP, rra_zp),
+        (RRA_ZPX, rra_zpx),
+        (RRA_A, rra_a),
+        (RRA_AX, rra_ax),
+        (RRA_AY, rra_ay),
+        (RRA_INX, rra_inx),
+        (RRA_INY, rra_iny),
+        (DCP_ZP, dcp_zp),
+        (DCP_ZPX, dcp_zpx),
+        (DCP_A, dcp_a),
+        (DCP_AX, dcp_ax),
+        (DCP_AY, dcp_ay),
+        (DCP_INX, dcp_inx),
+        (DCP_INY, dcp_iny),
+        (ISC_ZP, isc_zp),
+        (ISC_ZPX, isc_zpx),
+        (ISC_A, isc_a),
+        (ISC_AX, isc_ax),
+        (ISC_AY, isc_ay),
+        (ISC_INX, isc_inx),
+        (ISC_INY, isc_iny),
+        (ANC_IM, anc_im),
+        (ANC_IM_ALT, anc_im),
+        (ALR_IM, alr_im),
+        (ARR_IM, arr_im),
+        (SBX_IM, sbx_im),
+        (ANE_IM, ane_im),
+        (LXA_IM, lxa_im),
+        (SHA_AY, sha_ay),
+        (SHA_INY, sha_iny),
+        (TAS_AY, tas_ay),
+        (LAS_AY, las_ay),
+        (SHY_AX, shy_ax),
+        (SHX_AY, shx_ay),
+        (JAM_02, jam),
+        (JAM_12, jam),
+        (JAM_22, jam),
+        (JAM_32, jam),
+        (JAM_42, jam),
+        (JAM_52, jam),
+        (JAM_62, jam),
+        (JAM_72, jam),
+        (JAM_92, jam),
+        (JAM_B2, jam),
+        (JAM_D2, jam),
+        (JAM_F2, jam),
+        (NOP_1A, nop),
+        (NOP_3A, nop),
+        (NOP_5A, nop),
+        (NOP_7A, nop),
+        (NOP_DA, nop),
+        (NOP_FA, nop),
+        (NOP_IM_80, nop_im),
+        (NOP_IM_82, nop_im),
+        (NOP_IM_89, nop_im),
+        (NOP_IM_C2, nop_im),
+        (NOP_IM_E2, nop_im),
+        (NOP_ZP_04, nop_zp),
+        (NOP_ZP_44, nop_zp),
+        (NOP_ZP_64, nop_zp),
+        (NOP_ZPX_14, nop_zpx),
+        (NOP_ZPX_34, nop_zpx),
+        (NOP_ZPX_54, nop_zpx),
+        (NOP_ZPX_74, nop_zpx),
+        (NOP_ZPX_D4, nop_zpx),
+        (NOP_ZPX_F4, nop_zpx),
+        (NOP_A_0C, nop_a),
+        (NOP_AX_1C, nop_ax),
+        (NOP_AX_3C, nop_ax),
+        (NOP_AX_5C, nop_ax),
+        (NOP_AX_7C, nop_ax),
+        (NOP_AX_DC, nop_ax),
+        (NOP_AX_FC, nop_ax),
+    ]);
+}
+
+fn get_nmos_metadata() -> HashMap<Byte, OpcodeMetadata> {
+    return HashMap::from([
+        (
+            ADC_IM,
+            OpcodeMetadata::new("ADC", AddressingMode::Immediate),
+        ),
+        (ADC_ZP, OpcodeMetadata::new("ADC", AddressingMode::ZeroPage)),
+        (
+            ADC_ZPX,
+            OpcodeMetadata::new("ADC", AddressingMode::ZeroPageX),
+        ),
+        (ADC_A, OpcodeMetadata::new("ADC", AddressingMode::Absolute)),
+        (
+            ADC_AX,
+            OpcodeMetadata::new("ADC", AddressingMode::AbsoluteX),
+        ),
+        (
+            ADC_AY,
+            OpcodeMetadata::new("ADC", AddressingMode::AbsoluteY),
+        ),
+        (
+            ADC_INX,
+            OpcodeMetadata::new("ADC", AddressingMode::IndexIndirectX),
+        ),
+        (
+            ADC_INY,
+            OpcodeMetadata::new("ADC", AddressingMode::IndirectIndexY),
+        ),
+        (
+            AND_IM,
+            OpcodeMetadata::new("AND", AddressingMode::Immediate),
+        ),
+        (AND_ZP, OpcodeMetadata::new("AND", AddressingMode::ZeroPage)),
+        (
+            AND_ZPX,
+            OpcodeMetadata::new("AND", AddressingMode::ZeroPageX),
+        ),
+        (AND_A, OpcodeMetadata::new("AND", AddressingMode::Absolute)),
+        (
+            AND_AX,
+            OpcodeMetadata::new("AND", AddressingMode::AbsoluteX),
+        ),
+        (
+            AND_AY,
+            OpcodeMetadata::new("AND", AddressingMode::AbsoluteY),
+        ),
+        (
+            AND_INX,
+            OpcodeMetadata::new("AND", AddressingMode::IndexIndirectX),
+        ),
+        (
+            AND_INY,
+            OpcodeMetadata::new("AND", AddressingMode::IndirectIndexY),
+        ),
+        (
+            ASL_ACC,
+            OpcodeMetadata::new("ASL", AddressingMode::Accumulator),
+        ),
+        (ASL_ZP, OpcodeMetadata::new("ASL", AddressingMode::ZeroPage)),
+        (
+            ASL_ZPX,
+            OpcodeMetadata::new("ASL", AddressingMode::ZeroPageX),
+        ),
+        (ASL_A, OpcodeMetadata::new("ASL", AddressingMode::Absolute)),
+        (
+            ASL_AX,
+            OpcodeMetadata::new("ASL", AddressingMode::AbsoluteX),
+        ),
+        (BCC, OpcodeMetadata::new("BCC", AddressingMode::Relative)),
+        (BCS, OpcodeMetadata::new("BCS", AddressingMode::Relative)),
+        (BEQ, OpcodeMetadata::new("BEQ", AddressingMode::Relative)),
+        (BIT_ZP, OpcodeMetadata::new("BIT", AddressingMode::ZeroPage)),
+        (BIT_A, OpcodeMetadata::new("BIT", AddressingMode::Absolute)),
+        (BMI, OpcodeMetadata::new("BMI", AddressingMode::Relative)),
+        (BNE, OpcodeMetadata::new("BNE", AddressingMode::Relative)),
+        (BPL, OpcodeMetadata::new("BPL", AddressingMode::Relative)),
+        (BRK, OpcodeMetadata::new("BRK", AddressingMode::Implicit)),
+        (BVC, OpcodeMetadata::new("BVC", AddressingMode::Relative)),
+        (BVS, OpcodeMetadata::new("BVS", AddressingMode::Relative)),
+        (CLC, OpcodeMetadata::new("CLC", AddressingMode::Implicit)),
+        (CLD, OpcodeMetadata::new("CLD", AddressingMode::Implicit)),
+        (CLI, OpcodeMetadata::new("CLI", AddressingMode::Implicit)),
+        (CLV, OpcodeMetadata::new("CLV", AddressingMode::Implicit)),
+        (
+            CMP_IM,
+            OpcodeMetadata::new("CMP", AddressingMode::Immediate),
+        ),
+        (CMP_ZP, OpcodeMetadata::new("CMP", AddressingMode::ZeroPage)),
+        (
+            CMP_ZPX,
+            OpcodeMetadata::new("CMP", AddressingMode::ZeroPageX),
+        ),
+        (CMP_A, OpcodeMetadata::new("CMP", AddressingMode::Absolute)),
+        (
+            CMP_AX,
+            OpcodeMetadata::new("CMP", AddressingMode::AbsoluteX),
+        ),
+        (
+            CMP_AY,
+            OpcodeMetadata::new("CMP", AddressingMode::AbsoluteY),
+        ),
+        (
+            CMP_INX,
+            OpcodeMetadata::new("CMP", AddressingMode::IndexIndirectX),
+        ),
+        (
+            CMP_INY,
+            OpcodeMetadata::new("CMP", AddressingMode::IndirectIndexY),
+        ),
+        (
+            CPX_IM,
+            OpcodeMetadata::new("CPX", AddressingMode::Immediate),
+        ),
+        (CPX_ZP, OpcodeMetadata::new("CPX", AddressingMode::ZeroPage)),
+        (CPX_A, OpcodeMetadata::new("CPX", AddressingMode::Absolute)),
+        (
+            CPY_IM,
+            OpcodeMetadata::new("CPY", AddressingMode::Immediate),
+        ),
+        (CPY_ZP, OpcodeMetadata::new("CPY", AddressingMode::ZeroPage)),
+        (CPY_A, OpcodeMetadata::new("CPY", AddressingMode::Absolute)),
+        (DEC_A, OpcodeMetadata::new("DEC", AddressingMode::Absolute)),
+        (
+            DEC_AX,
+            OpcodeMetadata::new("DEC", AddressingMode::AbsoluteX),
+        ),
+        (DEC_ZP, OpcodeMetadata::new("DEC", AddressingMode::ZeroPage)),
+        (
+            DEC_ZPX,
+            OpcodeMetadata::new("DEC", AddressingMode::ZeroPageX),
+        ),
+        (DEX_IM, OpcodeMetadata::new("DEX", AddressingMode::Implicit)),
+        (DEY_IM, OpcodeMetadata::new("DEY", AddressingMode::Implicit)),
+        (
+            EOR_IM,
+            OpcodeMetadata::new("EOR", AddressingMode::Immediate),
+        ),
+        (EOR_ZP, OpcodeMetadata::new("EOR", AddressingMode::ZeroPage)),
+        (
+            EOR_ZPX,
+            OpcodeMetadata::new("EOR", AddressingMode::ZeroPageX),
+        ),
+        (EOR_A, OpcodeMetadata::new("EOR", AddressingMode::Absolute)),
+        (
+            EOR_AX,
+            OpcodeMetadata::new("EOR", AddressingMode::AbsoluteX),
+        ),
+        (
+            EOR_AY,
+            OpcodeMetadata::new("EOR", AddressingMode::AbsoluteY),
+        ),
+        (
+            EOR_INX,
+            OpcodeMetadata::new("EOR", AddressingMode::IndexIndirectX),
+        ),
+        (
+            EOR_INY,
+            OpcodeMetadata::new("EOR", AddressingMode::IndirectIndexY),
+        ),
+        (INC_ZP, OpcodeMetadata::new("INC", AddressingMode::ZeroPage)),
+        (
+            INC_ZPX,
+            OpcodeMetadata::new("INC", AddressingMode::ZeroPageX),
+        ),
+        (INC_A, OpcodeMetadata::new("INC", AddressingMode::Absolute)),
+        (
+            INC_AX,
+            OpcodeMetadata::new("INC", AddressingMode::AbsoluteX),
+        ),
+        (INX_IM, OpcodeMetadata::new("INX", AddressingMode::Implicit)),
+        (INY_IM, OpcodeMetadata::new("INY", AddressingMode::Implicit)),
+        (JMP_A, OpcodeMetadata::new("JMP", AddressingMode::Absolute)),
+        (JMP_IN, OpcodeMetadata::new("JMP", AddressingMode::Indirect)),
+        (JSR_A, OpcodeMetadata::new("JSR", AddressingMode::Absolute)),
+        (LAX_ZP, OpcodeMetadata::new("LAX", AddressingMode::ZeroPage)),
+        (
+            LAX_ZPY,
+            OpcodeMetadata::new("LAX", AddressingMode::ZeroPageY),
+        ),
+        (LAX_A, OpcodeMetadata::new("LAX", AddressingMode::Absolute)),
+        (
+            LAX_AY,
+            OpcodeMetadata::new("LAX", AddressingMode::AbsoluteY),
+        ),
+        (
+            LAX_INX,
+            OpcodeMetadata::new("LAX", AddressingMode::IndexIndirectX),
+        ),
+        (
+            LAX_INY,
+            OpcodeMetadata::new("LAX", AddressingMode::IndirectIndexY),
+        ),
+        (SAX_ZP, OpcodeMetadata::new("SAX", AddressingMode::ZeroPage)),
+        (
+            SAX_ZPY,
+            OpcodeMetadata::new("SAX", AddressingMode::ZeroPageY),
+        ),
+        (SAX_A, OpcodeMetadata::new("SAX", AddressingMode::Absolute)),
+        (
+            SAX_INX,
+            OpcodeMetadata::new("SAX", AddressingMode::IndexIndirectX),
+        ),
+        (
+            LDA_IM,
+            OpcodeMetadata::new("LDA", AddressingMode::Immediate),
+        ),
+        (LDA_ZP, OpcodeMetadata::new("LDA", AddressingMode::ZeroPage)),
+        (
+            LDA_ZPX,
+            OpcodeMetadata::new("LDA", AddressingMode::ZeroPageX),
+        ),
+        (LDA_A, OpcodeMetadata::new("LDA", AddressingMode::Absolute)),
+        (
+            LDA_AX,
+            OpcodeMetadata::new("LDA", AddressingMode::AbsoluteX),
+        ),
+        (
+            LDA_AY,
+            OpcodeMetadata::new("LDA", AddressingMode::AbsoluteY),
+        ),
+        (
+            LDA_INX,
+            OpcodeMetadata::new("LDA", AddressingMode::IndexIndirectX),
+        ),
+        (
+            LDA_INY,
+            OpcodeMetadata::new("LDA", AddressingMode::IndirectIndexY),
+        ),
+        (
+            LDY_IM,
+            OpcodeMetadata::new("LDY", AddressingMode::Immediate),
+        ),
+        (LDY_ZP, OpcodeMetadata::new("LDY", AddressingMode::ZeroPage)),
+        (
+            LDY_ZPX,
+            OpcodeMetadata::new("LDY", AddressingMode::ZeroPageX),
+        ),
+        (LDY_A, OpcodeMetadata::new("LDY", AddressingMode::Absolute)),
+        (
+            LDY_AX,
+            OpcodeMetadata::new("LDY", AddressingMode::AbsoluteX),
+        ),
+        (
+            LDX_IM,
+            OpcodeMetadata::new("LDX", AddressingMode::Immediate),
+        ),
+        (LDX_ZP, OpcodeMetadata::new("LDX", AddressingMode::ZeroPage)),
+        (
+            LDX_ZPY,
+            OpcodeMetadata::new("LDX", AddressingMode::ZeroPageY),
+        ),
+        (LDX_A, OpcodeMetadata::new("LDX", AddressingMode::Absolute)),
+        (
+            LDX_AY,
+            OpcodeMetadata::new("LDX", AddressingMode::AbsoluteY),
+        ),
+        (
+            LSR_ACC,
+            OpcodeMetadata::new("LSR", AddressingMode::Accumulator),
+        ),
+        (LSR_ZP, OpcodeMetadata::new("LSR", AddressingMode::ZeroPage)),
+        (
+            LSR_ZPX,
+            OpcodeMetadata::new("LSR", AddressingMode::ZeroPageX),
+        ),
+        (LSR_A, OpcodeMetadata::new("LSR", AddressingMode::Absolute)),
+        (
+            LSR_AX,
+            OpcodeMetadata::new("LSR", AddressingMode::AbsoluteX),
+        ),
+        (NOP, OpcodeMetadata::new("NOP", AddressingMode::Implicit)),
+        (
+            ORA_IM,
+            OpcodeMetadata::new("ORA", AddressingMode::Immediate),
+        ),
+        (ORA_ZP, OpcodeMetadata::new("ORA", AddressingMode::ZeroPage)),
+        (
+            ORA_ZPX,
+            OpcodeMetadata::new("ORA", AddressingMode::ZeroPageX),
+        ),
+        (ORA_A, OpcodeMetadata::new("ORA", AddressingMode::Absolute)),
+        (
+            ORA_AX,
+            OpcodeMetadata::new("ORA", AddressingMode::AbsoluteX),
+        ),
+        (
+            ORA_AY,
+            OpcodeMetadata::new("ORA", AddressingMode::AbsoluteY),
+        ),
+        (
+            ORA_INX,
+            OpcodeMetadata::new("ORA", AddressingMode::IndexIndirectX),
+        ),
+        (
+            ORA_INY,
+            OpcodeMetadata::new("ORA", AddressingMode::IndirectIndexY),
+        ),
+        (PHA, OpcodeMetadata::new("PHA", AddressingMode::Implicit)),
+        (PHP, OpcodeMetadata::new("PHP", AddressingMode::Implicit)),
+        (PLA, OpcodeMetadata::new("PLA", AddressingMode::Implicit)),
+        (PLP, OpcodeMetadata::new("PLP", AddressingMode::Implicit)),
+        (
+            ROL_ACC,
+            OpcodeMetadata::new("ROL", AddressingMode::Accumulator),
+        ),
+        (ROL_ZP, OpcodeMetadata::new("ROL", AddressingMode::ZeroPage)),
+        (
+            ROL_ZPX,
+            OpcodeMetadata::new("ROL", AddressingMode::ZeroPageX),
+        ),
+        (ROL_A, OpcodeMetadata::new("ROL", AddressingMode::Absolute)),
+        (
+            ROL_AX,
+            OpcodeMetadata::new("ROL", AddressingMode::AbsoluteX),
+        ),
+        (
+            ROR_ACC,
+            OpcodeMetadata::new("ROR", AddressingMode::Accumulator),
+        ),
+        (ROR_ZP, OpcodeMetadata::new("ROR", AddressingMode::ZeroPage)),
+        (
+            ROR_ZPX,
+            OpcodeMetadata::new("ROR", AddressingMode::ZeroPageX),
+        ),
+        (ROR_A, OpcodeMetadata::new("ROR", AddressingMode::Absolute)),
+        (
+            ROR_AX,
+            OpcodeMetadata::new("ROR", AddressingMode::AbsoluteX),
+        ),
+        (RTI, OpcodeMetadata::new("RTI", AddressingMode::Implicit)),
+        (RTS, OpcodeMetadata::new("RTS", AddressingMode::Implicit)),
+        (STA_ZP, OpcodeMetadata::new("STA", AddressingMode::ZeroPage)),
+        (
+            STA_ZPX,
+            OpcodeMetadata::new("STA", AddressingMode::ZeroPageX),
+        ),
+        (STA_A, OpcodeMetadata::new("STA", AddressingMode::Absolute)),
+        (
+            STA_AX,
+            OpcodeMetadata::new("STA", AddressingMode::AbsoluteX),
+        ),
+        (
+            STA_AY,
+            OpcodeMetadata::new("STA", AddressingMode::AbsoluteY),
+        ),
+        (
+            STA_INX,
+            OpcodeMetadata::new("STA", AddressingMode::IndexIndirectX),
+        ),
+        (
+            STA_INY,
+            OpcodeMetadata::new("STA", AddressingMode::IndirectIndexY),
+        ),
+        (STX_ZP, OpcodeMetadata::new("STX", AddressingMode::ZeroPage)),
+        (
+            STX_ZPY,
+            OpcodeMetadata::new("STX", AddressingMode::ZeroPageY),
+        ),
+        (STX_A, OpcodeMetadata::new("STX", AddressingMode::Absolute)),
+        (STY_ZP, OpcodeMetadata::new("STY", AddressingMode::ZeroPage)),
+        (
+            STY_ZPX,
+            OpcodeMetadata::new("STY", AddressingMode::ZeroPageX),
+        ),
+        (STY_A, OpcodeMetadata::new("STY", AddressingMode::Absolute)),
+        (SEC, OpcodeMetadata::new("SEC", AddressingMode::Implicit)),
+        (SED, OpcodeMetadata::new("SED", AddressingMode::Implicit)),
+        (SEI, OpcodeMetadata::new("SEI", AddressingMode::Implicit)),
+        (
+            SBC_IM,
+            OpcodeMetadata::new("SBC", AddressingMode::Immediate),
+        ),
+        (SBC_ZP, OpcodeMetadata::new("SBC", AddressingMode::ZeroPage)),
+        (
+            SBC_ZPX,
+            OpcodeMetadata::new("SBC", AddressingMode::ZeroPageX),
+        ),
+        (SBC_A, OpcodeMetadata::new("SBC", AddressingMode::Absolute)),
+        (
+            SBC_AX,
+            OpcodeMetadata::new("SBC", AddressingMode::AbsoluteX),
+        ),
+        (
+            SBC_AY,
+            OpcodeMetadata::new("SBC", AddressingMode::AbsoluteY),
+        ),
+        (
+            SBC_INX,
+            OpcodeMetadata::new("SBC", AddressingMode::IndexIndirectX),
+        ),
+        (
+            SBC_INY,
+            OpcodeMetadata::new("SBC", AddressingMode::IndirectIndexY),
+        ),
+        (TAX, OpcodeMetadata::new("TAX", AddressingMode::Implicit)),
+        (TAY, OpcodeMetadata::new("TAY", AddressingMode::Implicit)),
+        (TSX, OpcodeMetadata::new("TSX", AddressingMode::Implicit)),
+        (TXA, OpcodeMetadata::new("TXA", AddressingMode::Implicit)),
+        (TXS, OpcodeMetadata::new("TXS", AddressingMode::Implicit)),
+        (TYA, OpcodeMetadata::new("TYA", AddressingMode::Implicit)),
+        (SLO_ZP, OpcodeMetadata::new("SLO", AddressingMode::ZeroPage)),
+        (
+            SLO_ZPX,
+            OpcodeMetadata::new("SLO", AddressingMode::ZeroPageX),
+        ),
+        (SLO_A, OpcodeMetadata::new("SLO", AddressingMode::Absolute)),
+        (
+            SLO_AX,
+            OpcodeMetadata::new("SLO", AddressingMode::AbsoluteX),
+        ),
+        (
+            SLO_AY,
+            OpcodeMetadata::new("SLO", AddressingMode::AbsoluteY),
+        ),
+        (
+            SLO_INX,
+            OpcodeMetadata::new("SLO", AddressingMode::IndexIndirectX),
+        ),
+        (
+            SLO_INY,
+            OpcodeMetadata::new("SLO", AddressingMode::IndirectIndexY),
+        ),
+        (RLA_ZP, OpcodeMetadata::new("RLA", AddressingMode::ZeroPage)),
+        (
+            RLA_ZPX,
+            OpcodeMetadata::new("RLA", AddressingMode::ZeroPageX),
+        ),
+        (RLA_A, OpcodeMetadata::new("RLA", AddressingMode::Absolute)),
+        (
+            RLA_AX,
+            OpcodeMetadata::new("RLA", AddressingMode::AbsoluteX),
+        ),
+        (
+            RLA_AY,
+            OpcodeMetadata::new("RLA", AddressingMode::AbsoluteY),
+        ),
+        (
+            RLA_INX,
+            OpcodeMetadata::new("RLA", AddressingMode::IndexIndirectX),
+        ),
+        (
+            RLA_INY,
+            OpcodeMetadata::new("RLA", AddressingMode::IndirectIndexY),
+        ),
+        (SRE_ZP, OpcodeMetadata::new("SRE", AddressingMode::ZeroPage)),
+        (
+            SRE_ZPX,
+            OpcodeMetadata::new("SRE", AddressingMode::ZeroPageX),
+        ),
+        (SRE_A, OpcodeMetadata::new("SRE", AddressingMode::Absolute)),
+        (
+            SRE_AX,
+            OpcodeMetadata::new("SRE", AddressingMode::AbsoluteX),
+        ),
+        (
+            SRE_AY,
+            OpcodeMetadata::new("SRE", AddressingMode::AbsoluteY),
+        ),
+        (
+            SRE_INX,
+            OpcodeMetadata::new("SRE", AddressingMode::IndexIndirectX),
+        ),
+        (
+            SRE_INY,
+            OpcodeMetadata::new("SRE", AddressingMode::IndirectIndexY),
+        ),
+        (RRA_ZP, OpcodeMetadata::new("RRA", AddressingMode::ZeroPage)),
+        (
+            RRA_ZPX,
+            OpcodeMetadata::new("RRA", AddressingMode::ZeroPageX),
+        ),
+        (RRA_A, OpcodeMetadata::new("RRA", AddressingMode::Absolute)),
+        (
+            RRA_AX,
+            OpcodeMetadata::new("RRA", AddressingMode::AbsoluteX),
+        ),
+        (
+            RRA_AY,
+            OpcodeMetadata::new("RRA", AddressingMode::AbsoluteY),
+        ),
+        (
+            RRA_INX,
+            OpcodeMetadata::new("RRA", AddressingMode::IndexIndirectX),
+        ),
+        (
+            RRA_INY,
+            OpcodeMetadata::new("RRA", AddressingMode::IndirectIndexY),
+        ),
+        (DCP_ZP, OpcodeMetadata::new("DCP", AddressingMode::ZeroPage)),
+        (
+            DCP_ZPX,
+            OpcodeMetadata::new("DCP", AddressingMode::ZeroPageX),
+        ),
+        (DCP_A, OpcodeMetadata::new("DCP", AddressingMode::Absolute)),
+        (
+            DCP_AX,
+            OpcodeMetadata::new("DCP", AddressingMode::AbsoluteX),
+        ),
+        (
+            DCP_AY,
+            OpcodeMetadata::new("DCP", AddressingMode::AbsoluteY),
+        ),
+        (
+            DCP_INX,
+            OpcodeMetadata::new("DCP", AddressingMode::IndexIndirectX),
+        ),
+        (
+            DCP_INY,
+            OpcodeMetadata::new("DCP", AddressingMode::IndirectIndexY),
+        ),
+        (ISC_ZP, OpcodeMetadata::new("ISC", AddressingMode::ZeroPage)),
+        (
+            ISC_ZPX,
+            OpcodeMetadata::new("ISC", AddressingMode::ZeroPageX),
+        ),
+        (ISC_A, OpcodeMetadata::new("ISC", AddressingMode::Absolute)),
+        (
+            ISC_AX,
+            OpcodeMetadata::new("ISC", AddressingMode::AbsoluteX),
+        ),
+        (
+            ISC_AY,
+            OpcodeMetadata::new("ISC", AddressingMode::AbsoluteY),
+        ),
+        (
+            ISC_INX,
+            OpcodeMetadata::new("ISC", AddressingMode::IndexIndirectX),
+        ),
+        (
+            ISC_INY,
+            OpcodeMetadata::new("ISC", AddressingMode::IndirectIndexY),
+        ),
+        (
+            ANC_IM,
+            OpcodeMetadata::new("ANC", AddressingMode::Immediate),
+        ),
+        (
+            ANC_IM_ALT,
+            OpcodeMetadata::new("ANC", AddressingMode::Immediate),
+        ),
+        (
+            ALR_IM,
+            OpcodeMetadata::new("ALR", AddressingMode::Immediate),
+        ),
+        (
+            ARR_IM,
+            OpcodeMetadata::new("ARR", AddressingMode::Immediate),
+        ),
+        (
+            SBX_IM,
+            OpcodeMetadata::new("SBX", AddressingMode::Immediate),
+        ),
+        (
+            ANE_IM,
+            OpcodeMetadata::new("ANE", AddressingMode::Immediate),
+        ),
+        (
+            LXA_IM,
+            OpcodeMetadata::new("LXA", AddressingMode::Immediate),
+        ),
+        (
+            SHA_AY,
+            OpcodeMetadata::new("SHA", AddressingMode::AbsoluteY),
+        ),
+        (
+            SHA_INY,
+            OpcodeMetadata::new("SHA", AddressingMode::IndirectIndexY),
+        ),
+        (
+            TAS_AY,
+            OpcodeMetadata::new("TAS", AddressingMode::AbsoluteY),
+        ),
+        (
+            LAS_AY,
+            OpcodeMetadata::new("LAS", AddressingMode::AbsoluteY),
+        ),
+        (
+            SHY_AX,
+            OpcodeMetadata::new("SHY", AddressingMode::AbsoluteX),
+        ),
+        (
+            SHX_AY,
+            OpcodeMetadata::new("SHX", AddressingMode::AbsoluteY),
+        ),
+        (JAM_02, OpcodeMetadata::new("JAM", AddressingMode::Implicit)),
+        (JAM_12, OpcodeMetadata::new("JAM", AddressingMode::Implicit)),
+        (JAM_22, OpcodeMetadata::new("JAM", AddressingMode::Implicit)),
+        (JAM_32, OpcodeMetadata::new("JAM", AddressingMode::Implicit)),
+        (JAM_42, OpcodeMetadata::new("JAM", AddressingMode::Implicit)),
+        (JAM_52, OpcodeMetadata::new("JAM", AddressingMode::Implicit)),
+        (JAM_62, OpcodeMetadata::new("JAM", AddressingMode::Implicit)),
+        (JAM_72, OpcodeMetadata::new("JAM", AddressingMode::Implicit)),
+        (JAM_92, OpcodeMetadata::new("JAM", AddressingMode::Implicit)),
+        (JAM_B2, OpcodeMetadata::new("JAM", AddressingMode::Implicit)),
+        (JAM_D2, OpcodeMetadata::new("JAM", AddressingMode::Implicit)),
+        (JAM_F2, OpcodeMetadata::new("JAM", AddressingMode::Implicit)),
+        (NOP_1A, OpcodeMetadata::new("NOP", AddressingMode::Implicit)),
+        (NOP_3A, OpcodeMetadata::new("NOP", AddressingMode::Implicit)),
+        (NOP_5A, OpcodeMetadata::new("NOP", AddressingMode::Implicit)),
+        (NOP_7A, OpcodeMetadata::new("NOP", AddressingMode::Implicit)),
+        (NOP_DA, OpcodeMetadata::new("NOP", AddressingMode::Implicit)),
+        (NOP_FA, OpcodeMetadata::new("NOP", AddressingMode::Implicit)),
+        (
+            NOP_IM_80,
+            OpcodeMetadata::new("NOP", AddressingMode::Immediate),
+        ),
+        (
+            NOP_IM_82,
+            OpcodeMetadata::new("NOP", AddressingMode::Immediate),
+        ),
+        (
+            NOP_IM_89,
+            OpcodeMetadata::new("NOP", AddressingMode::Immediate),
+        ),
+        (
+            NOP_IM_C2,
+            OpcodeMetadata::new("NOP", AddressingMode::Immediate),
+        ),
+        (
+            NOP_IM_E2,
+            OpcodeMetadata::new("NOP", AddressingMode::Immediate),
+        ),
+        (
+            NOP_ZP_04,
+            OpcodeMetadata::new("NOP", AddressingMode::ZeroPage),
+        ),
+        (
+            NOP_ZP_44,
+            OpcodeMetadata::new("NOP", AddressingMode::ZeroPage),
+        ),
+        (
+            NOP_ZP_64,
+            OpcodeMetadata::new("NOP", AddressingMode::ZeroPage),
+        ),
+        (
+            NOP_ZPX_14,
+            OpcodeMetadata::new("NOP", AddressingMode::ZeroPageX),
+        ),
+        (
+            NOP_ZPX_34,
+            OpcodeMetadata::new("NOP", AddressingMode::ZeroPageX),
+        ),
+        (
+            NOP_ZPX_54,
+            OpcodeMetadata::new("NOP", AddressingMode::ZeroPageX),
+        ),
+        (
+            NOP_ZPX_74,
+            OpcodeMetadata::new("NOP", AddressingMode::ZeroPageX),
+        ),
+        (
+            NOP_ZPX_D4,
+            OpcodeMetadata::new("NOP", AddressingMode::ZeroPageX),
+        ),
+        (
+            NOP_ZPX_F4,
+            OpcodeMetadata::new("NOP", AddressingMode::ZeroPageX),
+        ),
+        (
+            NOP_A_0C,
+            OpcodeMetadata::new("NOP", AddressingMode::Absolute),
+        ),
+        (
+            NOP_AX_1C,
+            OpcodeMetadata::new("NOP", AddressingMode::AbsoluteX),
+        ),
+        (
+            NOP_AX_3C,
+            OpcodeMetadata::new("NOP", AddressingMode::AbsoluteX),
+        ),
+        (
+            NOP_AX_5C,
+            OpcodeMetadata::new("NOP", AddressingMode::AbsoluteX),
+        ),
+        (
+            NOP_AX_7C,
+            OpcodeMetadata::new("NOP", AddressingMode::AbsoluteX),
+        ),
+        (
+            NOP_AX_DC,
+            OpcodeMetadata::new("NOP", AddressingMode::AbsoluteX),
+        ),
+        (
+            NOP_AX_FC,
+            OpcodeMetadata::new("NOP", AddressingMode::AbsoluteX),
+        ),
     ]);
 }
 
 mod arithmetic;
+mod bit_operations;
 mod branches;
+mod immediate_combos;
 mod inc_and_decrements;
 mod jumps_and_calls;
 mod load_and_store_ops;
 mod logical;
 mod register_transfers;
+mod rmw_combos;
 mod shifts;
 mod stack_operations;
 mod status_flag_changes;
 mod system_functions;
+mod unstable_opcodes;
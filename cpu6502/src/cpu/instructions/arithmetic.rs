@@ -1,9 +1,10 @@
 use crate::{
     consts::Byte,
     cpu::{AddressingMode, Registers, CPU},
+    memory::Bus,
 };
 
-fn compare(cpu: &mut CPU, addr_mode: AddressingMode, register: Registers) {
+fn compare<M: Bus>(cpu: &mut CPU<M>, addr_mode: AddressingMode, register: Registers) {
     let value = match cpu.read_memory(addr_mode) {
         Some(value) => value,
         None => panic!("compare used with incorrect address mode"),
@@ -12,62 +13,88 @@ fn compare(cpu: &mut CPU, addr_mode: AddressingMode, register: Registers) {
     cpu.set_cmp_status(register, value);
 }
 
-pub fn cmp_im(cpu: &mut CPU) {
+pub fn cmp_im<M: Bus>(cpu: &mut CPU<M>) {
     compare(cpu, AddressingMode::Immediate, Registers::Accumulator);
 }
 
-pub fn cmp_zp(cpu: &mut CPU) {
+pub fn cmp_zp<M: Bus>(cpu: &mut CPU<M>) {
     compare(cpu, AddressingMode::ZeroPage, Registers::Accumulator);
 }
 
-pub fn cmp_zpx(cpu: &mut CPU) {
+pub fn cmp_zpx<M: Bus>(cpu: &mut CPU<M>) {
     compare(cpu, AddressingMode::ZeroPageX, Registers::Accumulator);
 }
 
-pub fn cmp_a(cpu: &mut CPU) {
+pub fn cmp_a<M: Bus>(cpu: &mut CPU<M>) {
     compare(cpu, AddressingMode::Absolute, Registers::Accumulator);
 }
 
-pub fn cmp_ax(cpu: &mut CPU) {
+pub fn cmp_ax<M: Bus>(cpu: &mut CPU<M>) {
     compare(cpu, AddressingMode::AbsoluteX, Registers::Accumulator);
 }
 
-pub fn cmp_ay(cpu: &mut CPU) {
+pub fn cmp_ay<M: Bus>(cpu: &mut CPU<M>) {
     compare(cpu, AddressingMode::AbsoluteY, Registers::Accumulator);
 }
 
-pub fn cmp_inx(cpu: &mut CPU) {
+pub fn cmp_inx<M: Bus>(cpu: &mut CPU<M>) {
     compare(cpu, AddressingMode::IndexIndirectX, Registers::Accumulator);
 }
 
-pub fn cmp_iny(cpu: &mut CPU) {
+pub fn cmp_iny<M: Bus>(cpu: &mut CPU<M>) {
     compare(cpu, AddressingMode::IndirectIndexY, Registers::Accumulator);
 }
 
-pub fn cpx_im(cpu: &mut CPU) {
+pub fn cmp_izp<M: Bus>(cpu: &mut CPU<M>) {
+    compare(
+        cpu,
+        AddressingMode::ZeroPageIndirect,
+        Registers::Accumulator,
+    );
+}
+
+pub fn cpx_im<M: Bus>(cpu: &mut CPU<M>) {
     compare(cpu, AddressingMode::Immediate, Registers::IndexX);
 }
 
-pub fn cpx_zp(cpu: &mut CPU) {
+pub fn cpx_zp<M: Bus>(cpu: &mut CPU<M>) {
     compare(cpu, AddressingMode::ZeroPage, Registers::IndexX);
 }
 
-pub fn cpx_a(cpu: &mut CPU) {
+pub fn cpx_a<M: Bus>(cpu: &mut CPU<M>) {
     compare(cpu, AddressingMode::Absolute, Registers::IndexX);
 }
 
-pub fn cpy_im(cpu: &mut CPU) {
+pub fn cpy_im<M: Bus>(cpu: &mut CPU<M>) {
     compare(cpu, AddressingMode::Immediate, Registers::IndexY);
 }
 
-pub fn cpy_zp(cpu: &mut CPU) {
+pub fn cpy_zp<M: Bus>(cpu: &mut CPU<M>) {
     compare(cpu, AddressingMode::ZeroPage, Registers::IndexY);
 }
 
-pub fn cpy_a(cpu: &mut CPU) {
+pub fn cpy_a<M: Bus>(cpu: &mut CPU<M>) {
     compare(cpu, AddressingMode::Absolute, Registers::IndexY);
 }
 
+pub fn cpz_im<M: Bus>(cpu: &mut CPU<M>) {
+    compare(cpu, AddressingMode::Immediate, Registers::IndexZ);
+}
+
+pub fn cpz_zp<M: Bus>(cpu: &mut CPU<M>) {
+    compare(cpu, AddressingMode::ZeroPage, Registers::IndexZ);
+}
+
+pub fn cpz_a<M: Bus>(cpu: &mut CPU<M>) {
+    compare(cpu, AddressingMode::Absolute, Registers::IndexZ);
+}
+
+// 65CE02 two's complement negation of the accumulator
+pub fn neg_acc<M: Bus>(cpu: &mut CPU<M>) {
+    cpu.set_register(Registers::Accumulator, cpu.accumulator.wrapping_neg());
+    cpu.idle_cycle();
+}
+
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub enum FlagOp {
     Unchanged,
@@ -75,7 +102,7 @@ pub enum FlagOp {
     Clear,
 }
 
-fn adc(val: Byte, acc: Byte, carry: bool) -> (Byte, FlagOp, FlagOp) {
+pub fn adc(val: Byte, acc: Byte, carry: bool) -> (Byte, FlagOp, FlagOp) {
     let (result, carry) = acc.overflowing_add(val);
     // if a sign (0x80) of a result differs from signs of both inputs
     let overflow = (acc ^ result) & (val ^ result) & 0x80 > 0;
@@ -93,7 +120,7 @@ fn adc(val: Byte, acc: Byte, carry: bool) -> (Byte, FlagOp, FlagOp) {
     return (result, carry_op, overflow_op);
 }
 
-fn sbc(val: Byte, acc: Byte, carry: bool) -> (Byte, FlagOp, FlagOp) {
+pub fn sbc(val: Byte, acc: Byte, carry: bool) -> (Byte, FlagOp, FlagOp) {
     let (result, carry) = acc.overflowing_add(0xFF - val + (carry as u8));
     // if a sign (0x80) of a result differs from sign of accumulator
     // and ones-complement of value sign differs from sign of result
@@ -112,16 +139,57 @@ fn sbc(val: Byte, acc: Byte, carry: bool) -> (Byte, FlagOp, FlagOp) {
     return (result, carry_op, overflow_op);
 }
 
-pub fn operations_with_carry(
-    cpu: &mut CPU,
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum CarryOperation {
+    Addition,
+    Subtraction,
+}
+
+pub fn operations_with_carry<M: Bus>(
+    cpu: &mut CPU<M>,
     addr_mode: AddressingMode,
-    op: fn(val: Byte, acc: Byte, carry: bool) -> (Byte, FlagOp, FlagOp),
+    operation: CarryOperation,
 ) {
     let value = match cpu.read_memory(addr_mode) {
         Some(value) => value,
         None => panic!("arithmetic operation with carry used with incorrect address mode"),
     };
 
+    apply_with_carry(cpu, value, operation);
+}
+
+// 65C02 decimal arithmetic corrects the result differently and takes an extra cycle, the
+// CMOS dispatch tables register these in place of the NMOS handlers
+fn cmos_operations_with_carry<M: Bus>(
+    cpu: &mut CPU<M>,
+    addr_mode: AddressingMode,
+    operation: CarryOperation,
+) {
+    let value = match cpu.read_memory(addr_mode) {
+        Some(value) => value,
+        None => panic!("arithmetic operation with carry used with incorrect address mode"),
+    };
+
+    if cpu.is_decimal_mode_active() {
+        decimal_cmos(cpu, value, operation);
+        return;
+    }
+    apply_binary_with_carry(cpu, value, operation);
+}
+
+pub fn apply_with_carry<M: Bus>(cpu: &mut CPU<M>, value: Byte, operation: CarryOperation) {
+    if cpu.is_decimal_mode_active() {
+        decimal_nmos(cpu, value, operation);
+        return;
+    }
+    apply_binary_with_carry(cpu, value, operation);
+}
+
+fn apply_binary_with_carry<M: Bus>(cpu: &mut CPU<M>, value: Byte, operation: CarryOperation) {
+    let op = match operation {
+        CarryOperation::Addition => adc,
+        CarryOperation::Subtraction => sbc,
+    };
     let accumulator = cpu.get_register(Registers::Accumulator);
     let (value, carry, overflow) = op(value, accumulator, cpu.processor_status.get_carry_flag());
 
@@ -136,68 +204,326 @@ pub fn operations_with_carry(
     }
 }
 
-pub fn adc_im(cpu: &mut CPU) {
-    operations_with_carry(cpu, AddressingMode::Immediate, adc);
+// addition (or subtraction of the ones-complement) straight through the adder, taking the carry
+// flag in and leaving C and V as the adder produces them; used by the undocumented combos
+// which feed the modified value to the ALU of the NMOS chip
+pub fn apply_with_carry_through_adder<M: Bus>(
+    cpu: &mut CPU<M>,
+    value: Byte,
+    operation: CarryOperation,
+) {
+    if cpu.is_decimal_mode_active() {
+        decimal_nmos(cpu, value, operation);
+        return;
+    }
+
+    let operand = match operation {
+        CarryOperation::Addition => value,
+        CarryOperation::Subtraction => !value,
+    };
+    let accumulator = cpu.get_register(Registers::Accumulator);
+    let sum = accumulator as u16 + operand as u16 + cpu.processor_status.get_carry_flag() as u16;
+    let result = sum as Byte;
+    let overflow = (accumulator ^ result) & (operand ^ result) & 0x80 > 0;
+
+    cpu.set_register(Registers::Accumulator, result);
+    cpu.processor_status.change_carry_flag(sum > 0xFF);
+    cpu.processor_status.change_overflow_flag(overflow);
+}
+
+struct DecimalResult {
+    result: Byte,
+    carry: bool,
+    overflow: bool,
+    // N and Z as seen by the NMOS adder, before the result is BCD corrected
+    binary_negative: bool,
+    binary_zero: bool,
+}
+
+// BCD addition as done by the NMOS adder: nibbles are corrected one after another, N and V
+// are taken before the high nibble correction and Z from the plain binary sum
+fn decimal_adc(val: Byte, acc: Byte, carry: bool) -> DecimalResult {
+    let binary = acc.wrapping_add(val).wrapping_add(carry as Byte);
+    let mut low = (acc & 0x0F) as u16 + (val & 0x0F) as u16 + carry as u16;
+    if low >= 0x0A {
+        low = ((low + 0x06) & 0x0F) + 0x10;
+    }
+    let mut sum = (acc & 0xF0) as u16 + (val & 0xF0) as u16 + low;
+    let overflow = (acc as u16 ^ sum) & (val as u16 ^ sum) & 0x80 > 0;
+    let negative = sum & 0x80 > 0;
+    if sum >= 0xA0 {
+        sum += 0x60;
+    }
+
+    return DecimalResult {
+        result: sum as Byte,
+        carry: sum >= 0x100,
+        overflow,
+        binary_negative: negative,
+        binary_zero: binary == 0,
+    };
+}
+
+// BCD subtraction; all flags are the same as in binary subtraction
+fn decimal_sbc(val: Byte, acc: Byte, carry: bool) -> DecimalResult {
+    let borrow = !carry as i16;
+    let binary = acc as i16 - val as i16 - borrow;
+    let overflow = (acc as i16 ^ binary) & (acc as i16 ^ val as i16) & 0x80 > 0;
+
+    let mut low = (acc & 0x0F) as i16 - (val & 0x0F) as i16 - borrow;
+    if low < 0 {
+        low = ((low - 0x06) & 0x0F) - 0x10;
+    }
+    let mut difference = (acc & 0xF0) as i16 - (val & 0xF0) as i16 + low;
+    if difference < 0 {
+        difference -= 0x60;
+    }
+
+    return DecimalResult {
+        result: difference as Byte,
+        carry: binary >= 0,
+        overflow,
+        binary_negative: binary & 0x80 > 0,
+        binary_zero: binary as Byte == 0,
+    };
+}
+
+// BCD subtraction as done by the 65C02: the binary difference is corrected by $60 on a borrow
+// out of the high nibble and by $06 on one out of the low nibble, which differs from the NMOS
+// result for operands that are not valid BCD; carry and overflow are those of binary
+// subtraction
+fn decimal_sbc_cmos(val: Byte, acc: Byte, carry: bool) -> DecimalResult {
+    let borrow = !carry as i16;
+    let binary = acc as i16 - val as i16 - borrow;
+    let overflow = (acc as i16 ^ binary) & (acc as i16 ^ val as i16) & 0x80 > 0;
+
+    let low = (acc & 0x0F) as i16 - (val & 0x0F) as i16 - borrow;
+    let mut difference = binary;
+    if difference < 0 {
+        difference -= 0x60;
+    }
+    if low < 0 {
+        difference -= 0x06;
+    }
+
+    return DecimalResult {
+        result: difference as Byte,
+        carry: binary >= 0,
+        overflow,
+        binary_negative: binary & 0x80 > 0,
+        binary_zero: binary as Byte == 0,
+    };
+}
+
+fn decimal_result(value: Byte, acc: Byte, carry: bool, operation: CarryOperation) -> DecimalResult {
+    return match operation {
+        CarryOperation::Addition => decimal_adc(value, acc, carry),
+        CarryOperation::Subtraction => decimal_sbc(value, acc, carry),
+    };
+}
+
+// the NMOS chip does not derive N and Z from the corrected result, programs relying on them
+// in decimal mode see the values of the binary operation
+fn decimal_nmos<M: Bus>(cpu: &mut CPU<M>, value: Byte, operation: CarryOperation) {
+    let carry = cpu.processor_status.get_carry_flag();
+    let decimal = decimal_result(value, cpu.accumulator, carry, operation);
+
+    cpu.accumulator = decimal.result;
+    cpu.processor_status
+        .change_negative_flag(decimal.binary_negative);
+    cpu.processor_status.change_zero_flag(decimal.binary_zero);
+    cpu.processor_status.change_carry_flag(decimal.carry);
+    cpu.processor_status.change_overflow_flag(decimal.overflow);
+}
+
+// the 65C02 spends an extra cycle to get valid N and Z flags from the corrected result
+fn decimal_cmos<M: Bus>(cpu: &mut CPU<M>, value: Byte, operation: CarryOperation) {
+    let carry = cpu.processor_status.get_carry_flag();
+    let decimal = match operation {
+        CarryOperation::Addition => decimal_adc(value, cpu.accumulator, carry),
+        CarryOperation::Subtraction => decimal_sbc_cmos(value, cpu.accumulator, carry),
+    };
+
+    cpu.set_register(Registers::Accumulator, decimal.result);
+    cpu.processor_status.change_carry_flag(decimal.carry);
+    cpu.processor_status.change_overflow_flag(decimal.overflow);
+    cpu.idle_cycle();
+}
+
+pub fn adc_im<M: Bus>(cpu: &mut CPU<M>) {
+    operations_with_carry(cpu, AddressingMode::Immediate, CarryOperation::Addition);
+}
+
+pub fn adc_zp<M: Bus>(cpu: &mut CPU<M>) {
+    operations_with_carry(cpu, AddressingMode::ZeroPage, CarryOperation::Addition);
+}
+
+pub fn adc_zpx<M: Bus>(cpu: &mut CPU<M>) {
+    operations_with_carry(cpu, AddressingMode::ZeroPageX, CarryOperation::Addition);
+}
+
+pub fn adc_a<M: Bus>(cpu: &mut CPU<M>) {
+    operations_with_carry(cpu, AddressingMode::Absolute, CarryOperation::Addition);
+}
+
+pub fn adc_ax<M: Bus>(cpu: &mut CPU<M>) {
+    operations_with_carry(cpu, AddressingMode::AbsoluteX, CarryOperation::Addition);
+}
+
+pub fn adc_ay<M: Bus>(cpu: &mut CPU<M>) {
+    operations_with_carry(cpu, AddressingMode::AbsoluteY, CarryOperation::Addition);
+}
+
+pub fn adc_inx<M: Bus>(cpu: &mut CPU<M>) {
+    operations_with_carry(
+        cpu,
+        AddressingMode::IndexIndirectX,
+        CarryOperation::Addition,
+    );
+}
+
+pub fn adc_iny<M: Bus>(cpu: &mut CPU<M>) {
+    operations_with_carry(
+        cpu,
+        AddressingMode::IndirectIndexY,
+        CarryOperation::Addition,
+    );
+}
+
+pub fn sbc_im<M: Bus>(cpu: &mut CPU<M>) {
+    operations_with_carry(cpu, AddressingMode::Immediate, CarryOperation::Subtraction);
+}
+
+pub fn sbc_zp<M: Bus>(cpu: &mut CPU<M>) {
+    operations_with_carry(cpu, AddressingMode::ZeroPage, CarryOperation::Subtraction);
+}
+
+pub fn sbc_zpx<M: Bus>(cpu: &mut CPU<M>) {
+    operations_with_carry(cpu, AddressingMode::ZeroPageX, CarryOperation::Subtraction);
+}
+
+pub fn sbc_a<M: Bus>(cpu: &mut CPU<M>) {
+    operations_with_carry(cpu, AddressingMode::Absolute, CarryOperation::Subtraction);
+}
+
+pub fn sbc_ax<M: Bus>(cpu: &mut CPU<M>) {
+    operations_with_carry(cpu, AddressingMode::AbsoluteX, CarryOperation::Subtraction);
+}
+
+pub fn sbc_ay<M: Bus>(cpu: &mut CPU<M>) {
+    operations_with_carry(cpu, AddressingMode::AbsoluteY, CarryOperation::Subtraction);
+}
+
+pub fn sbc_inx<M: Bus>(cpu: &mut CPU<M>) {
+    operations_with_carry(
+        cpu,
+        AddressingMode::IndexIndirectX,
+        CarryOperation::Subtraction,
+    );
+}
+
+pub fn sbc_iny<M: Bus>(cpu: &mut CPU<M>) {
+    operations_with_carry(
+        cpu,
+        AddressingMode::IndirectIndexY,
+        CarryOperation::Subtraction,
+    );
+}
+
+pub fn adc_im_cmos<M: Bus>(cpu: &mut CPU<M>) {
+    cmos_operations_with_carry(cpu, AddressingMode::Immediate, CarryOperation::Addition);
+}
+
+pub fn adc_zp_cmos<M: Bus>(cpu: &mut CPU<M>) {
+    cmos_operations_with_carry(cpu, AddressingMode::ZeroPage, CarryOperation::Addition);
+}
+
+pub fn adc_zpx_cmos<M: Bus>(cpu: &mut CPU<M>) {
+    cmos_operations_with_carry(cpu, AddressingMode::ZeroPageX, CarryOperation::Addition);
 }
 
-pub fn adc_zp(cpu: &mut CPU) {
-    operations_with_carry(cpu, AddressingMode::ZeroPage, adc);
+pub fn adc_a_cmos<M: Bus>(cpu: &mut CPU<M>) {
+    cmos_operations_with_carry(cpu, AddressingMode::Absolute, CarryOperation::Addition);
 }
 
-pub fn adc_zpx(cpu: &mut CPU) {
-    operations_with_carry(cpu, AddressingMode::ZeroPageX, adc);
+pub fn adc_ax_cmos<M: Bus>(cpu: &mut CPU<M>) {
+    cmos_operations_with_carry(cpu, AddressingMode::AbsoluteX, CarryOperation::Addition);
 }
 
-pub fn adc_a(cpu: &mut CPU) {
-    operations_with_carry(cpu, AddressingMode::Absolute, adc);
+pub fn adc_ay_cmos<M: Bus>(cpu: &mut CPU<M>) {
+    cmos_operations_with_carry(cpu, AddressingMode::AbsoluteY, CarryOperation::Addition);
 }
 
-pub fn adc_ax(cpu: &mut CPU) {
-    operations_with_carry(cpu, AddressingMode::AbsoluteX, adc);
+pub fn adc_inx_cmos<M: Bus>(cpu: &mut CPU<M>) {
+    cmos_operations_with_carry(
+        cpu,
+        AddressingMode::IndexIndirectX,
+        CarryOperation::Addition,
+    );
 }
 
-pub fn adc_ay(cpu: &mut CPU) {
-    operations_with_carry(cpu, AddressingMode::AbsoluteY, adc);
+pub fn adc_iny_cmos<M: Bus>(cpu: &mut CPU<M>) {
+    cmos_operations_with_carry(
+        cpu,
+        AddressingMode::IndirectIndexY,
+        CarryOperation::Addition,
+    );
 }
 
-pub fn adc_inx(cpu: &mut CPU) {
-    operations_with_carry(cpu, AddressingMode::IndexIndirectX, adc);
+pub fn sbc_im_cmos<M: Bus>(cpu: &mut CPU<M>) {
+    cmos_operations_with_carry(cpu, AddressingMode::Immediate, CarryOperation::Subtraction);
 }
 
-pub fn adc_iny(cpu: &mut CPU) {
-    operations_with_carry(cpu, AddressingMode::IndirectIndexY, adc);
+pub fn sbc_zp_cmos<M: Bus>(cpu: &mut CPU<M>) {
+    cmos_operations_with_carry(cpu, AddressingMode::ZeroPage, CarryOperation::Subtraction);
 }
 
-pub fn sbc_im(cpu: &mut CPU) {
-    operations_with_carry(cpu, AddressingMode::Immediate, sbc);
+pub fn sbc_zpx_cmos<M: Bus>(cpu: &mut CPU<M>) {
+    cmos_operations_with_carry(cpu, AddressingMode::ZeroPageX, CarryOperation::Subtraction);
 }
 
-pub fn sbc_zp(cpu: &mut CPU) {
-    operations_with_carry(cpu, AddressingMode::ZeroPage, sbc);
+pub fn sbc_a_cmos<M: Bus>(cpu: &mut CPU<M>) {
+    cmos_operations_with_carry(cpu, AddressingMode::Absolute, CarryOperation::Subtraction);
 }
 
-pub fn sbc_zpx(cpu: &mut CPU) {
-    operations_with_carry(cpu, AddressingMode::ZeroPageX, sbc);
+pub fn sbc_ax_cmos<M: Bus>(cpu: &mut CPU<M>) {
+    cmos_operations_with_carry(cpu, AddressingMode::AbsoluteX, CarryOperation::Subtraction);
 }
 
-pub fn sbc_a(cpu: &mut CPU) {
-    operations_with_carry(cpu, AddressingMode::Absolute, sbc);
+pub fn sbc_ay_cmos<M: Bus>(cpu: &mut CPU<M>) {
+    cmos_operations_with_carry(cpu, AddressingMode::AbsoluteY, CarryOperation::Subtraction);
 }
 
-pub fn sbc_ax(cpu: &mut CPU) {
-    operations_with_carry(cpu, AddressingMode::AbsoluteX, sbc);
+pub fn sbc_inx_cmos<M: Bus>(cpu: &mut CPU<M>) {
+    cmos_operations_with_carry(
+        cpu,
+        AddressingMode::IndexIndirectX,
+        CarryOperation::Subtraction,
+    );
 }
 
-pub fn sbc_ay(cpu: &mut CPU) {
-    operations_with_carry(cpu, AddressingMode::AbsoluteY, sbc);
+pub fn sbc_iny_cmos<M: Bus>(cpu: &mut CPU<M>) {
+    cmos_operations_with_carry(
+        cpu,
+        AddressingMode::IndirectIndexY,
+        CarryOperation::Subtraction,
+    );
 }
 
-pub fn sbc_inx(cpu: &mut CPU) {
-    operations_with_carry(cpu, AddressingMode::IndexIndirectX, sbc);
+pub fn adc_izp<M: Bus>(cpu: &mut CPU<M>) {
+    cmos_operations_with_carry(
+        cpu,
+        AddressingMode::ZeroPageIndirect,
+        CarryOperation::Addition,
+    );
 }
 
-pub fn sbc_iny(cpu: &mut CPU) {
-    operations_with_carry(cpu, AddressingMode::IndirectIndexY, sbc);
+pub fn sbc_izp<M: Bus>(cpu: &mut CPU<M>) {
+    cmos_operations_with_carry(
+        cpu,
+        AddressingMode::ZeroPageIndirect,
+        CarryOperation::Subtraction,
+    );
 }
 
 #[cfg(test)]
@@ -10,6 +10,7 @@ mod cmp {
         fn should_compare_accumulator_with_next_byte_from_memory() {
             let memory = &RefCell::new(MemoryMock::new(&[0x03, 0xFF]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.accumulator = 0x02;
             cpu.program_counter = 0x00;
             assert_eq!(cpu.processor_status, 0b00000000);
@@ -43,6 +44,7 @@ mod cmp {
         fn should_compare_accumulator_with_a_value_from_a_zero_page_address() {
             let memory = &RefCell::new(MemoryMock::new(&[0x03, 0xFF, 0x00, 0x04]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.accumulator = 0x02;
             cpu.program_counter = 0x00;
             assert_eq!(cpu.processor_status, 0b00000000);
@@ -76,6 +78,7 @@ mod cmp {
         fn should_compare_accumulator_with_a_value_from_a_zero_page_summed_with_index_register_x() {
             let memory = &RefCell::new(MemoryMock::new(&[0x01, 0x00, 0x00, 0x03]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.accumulator = 0x02;
             cpu.index_register_x = 0x02;
             cpu.program_counter = 0x00;
@@ -111,6 +114,7 @@ mod cmp {
         fn should_compare_accumulator_with_a_value_from_an_address() {
             let memory = &RefCell::new(MemoryMock::new(&[0x03, 0x00, 0x00, 0x03]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.accumulator = 0x02;
             cpu.program_counter = 0x00;
             assert_eq!(cpu.processor_status, 0b00000000);
@@ -154,6 +158,7 @@ mod cmp {
                 ADDRESS_LO, ADDRESS_HI, 0x45, 0xAF, 0xDD, VALUE,
             ]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.accumulator = 0x02;
             cpu.program_counter = 0x00;
             cpu.index_register_x = 0x02;
@@ -222,6 +227,7 @@ mod cmp {
                 ADDRESS_LO, ADDRESS_HI, 0x45, 0xAF, 0xDD, VALUE,
             ]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.accumulator = 0x02;
             cpu.program_counter = 0x00;
             cpu.index_register_y = 0x02;
@@ -297,6 +303,7 @@ mod cmp {
                 VALUE,
             ]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.accumulator = 0x02;
             cpu.index_register_y = 0x02;
             cpu.program_counter = 0x00;
@@ -363,6 +370,7 @@ mod cpy {
         fn should_compare_y_register_with_next_byte_from_memory() {
             let memory = &RefCell::new(MemoryMock::new(&[0x03, 0xFF]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.index_register_y = 0x02;
             cpu.program_counter = 0x00;
             assert_eq!(cpu.processor_status, 0b00000000);
@@ -396,6 +404,7 @@ mod cpy {
         fn should_compare_y_register_with_a_value_from_a_zero_page_address() {
             let memory = &RefCell::new(MemoryMock::new(&[0x03, 0xFF, 0x00, 0x04]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.index_register_y = 0x02;
             cpu.program_counter = 0x00;
             assert_eq!(cpu.processor_status, 0b00000000);
@@ -429,6 +438,7 @@ mod cpy {
         fn should_compare_y_register_with_a_value_from_an_address() {
             let memory = &RefCell::new(MemoryMock::new(&[0x03, 0x00, 0x00, 0x03]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.index_register_y = 0x02;
             cpu.program_counter = 0x00;
             assert_eq!(cpu.processor_status, 0b00000000);
@@ -465,6 +475,7 @@ mod cpx {
         fn should_compare_x_register_with_next_byte_from_memory() {
             let memory = &RefCell::new(MemoryMock::new(&[0x03, 0xFF]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.index_register_x = 0x02;
             cpu.program_counter = 0x00;
             assert_eq!(cpu.processor_status, 0b00000000);
@@ -498,6 +509,7 @@ mod cpx {
         fn should_compare_x_register_with_a_value_from_a_zero_page_address() {
             let memory = &RefCell::new(MemoryMock::new(&[0x03, 0xFF, 0x00, 0x04]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.index_register_x = 0x02;
             cpu.program_counter = 0x00;
             assert_eq!(cpu.processor_status, 0b00000000);
@@ -531,6 +543,7 @@ mod cpx {
         fn should_compare_x_register_with_a_value_from_an_address() {
             let memory = &RefCell::new(MemoryMock::new(&[0x03, 0x00, 0x00, 0x03]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.index_register_x = 0x02;
             cpu.program_counter = 0x00;
             assert_eq!(cpu.processor_status, 0b00000000);
@@ -1892,3 +1905,173 @@ mod sbc {
         }
     }
 }
+
+#[cfg(test)]
+mod decimal_mode {
+    use std::cell::RefCell;
+
+    use crate::cpu::{
+        instructions::arithmetic::{decimal_adc, decimal_sbc},
+        tests::MemoryMock,
+        CPU,
+    };
+
+    #[test]
+    fn should_add_binary_coded_decimals() {
+        let result = decimal_adc(0x01, 0x09, false);
+        assert_eq!((result.result, result.carry), (0x10, false));
+
+        let result = decimal_adc(0x01, 0x99, false);
+        assert_eq!((result.result, result.carry), (0x00, true));
+
+        let result = decimal_adc(0x46, 0x58, true);
+        assert_eq!((result.result, result.carry), (0x05, true));
+    }
+
+    #[test]
+    fn should_subtract_binary_coded_decimals() {
+        let result = decimal_sbc(0x12, 0x46, true);
+        assert_eq!((result.result, result.carry), (0x34, true));
+
+        let result = decimal_sbc(0x13, 0x40, true);
+        assert_eq!((result.result, result.carry), (0x27, true));
+
+        let result = decimal_sbc(0x01, 0x00, true);
+        assert_eq!((result.result, result.carry), (0x99, false));
+    }
+
+    fn add_in_decimal_mode(mut cpu: CPU<&RefCell<MemoryMock>>) -> CPU<&RefCell<MemoryMock>> {
+        cpu.program_counter = 0x0000;
+        cpu.cycle = 0;
+        cpu.accumulator = 0x99;
+        cpu.processor_status.change_decimal_mode_flag(true);
+
+        cpu.execute_next_instruction();
+
+        return cpu;
+    }
+
+    #[test]
+    fn should_take_extra_cycle_and_set_valid_flags_on_cmos() {
+        let memory = RefCell::new(MemoryMock::new(&[0x69, 0x01])); // ADC #$01
+        let cpu = add_in_decimal_mode(CPU::new_cmos(&memory));
+
+        assert_eq!(cpu.accumulator, 0x00);
+        assert_eq!(cpu.cycle, 3);
+        assert!(cpu.processor_status.get_zero_flag());
+        assert!(cpu.processor_status.get_carry_flag());
+    }
+
+    #[test]
+    fn should_not_take_extra_cycle_on_nmos() {
+        let memory = RefCell::new(MemoryMock::new(&[0x69, 0x01])); // ADC #$01
+        let cpu = add_in_decimal_mode(CPU::new_nmos(&memory));
+
+        assert_eq!(cpu.accumulator, 0x00);
+        assert_eq!(cpu.cycle, 2);
+    }
+
+    #[test]
+    fn should_set_zero_from_binary_sum_and_negative_before_high_nibble_correction_on_nmos() {
+        let memory = RefCell::new(MemoryMock::new(&[0x69, 0x01])); // ADC #$01
+        let cpu = add_in_decimal_mode(CPU::new_nmos(&memory));
+
+        // binary 0x99 + 0x01 = 0x9A, 0xA0 before the high nibble is corrected
+        assert!(!cpu.processor_status.get_zero_flag());
+        assert!(cpu.processor_status.get_negative_flag());
+        assert!(cpu.processor_status.get_carry_flag());
+    }
+
+    #[test]
+    fn should_set_flags_from_binary_difference_on_nmos() {
+        let memory = RefCell::new(MemoryMock::new(&[0xE9, 0x01])); // SBC #$01
+        let mut cpu = CPU::new_nmos(&memory);
+        cpu.program_counter = 0x0000;
+        cpu.accumulator = 0x00;
+        cpu.processor_status.change_decimal_mode_flag(true);
+        cpu.processor_status.change_carry_flag(true);
+
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.accumulator, 0x99);
+        assert!(cpu.processor_status.get_negative_flag());
+        assert!(!cpu.processor_status.get_zero_flag());
+        assert!(!cpu.processor_status.get_carry_flag());
+    }
+
+    #[test]
+    fn should_correct_difference_of_invalid_bcd_like_cmos() {
+        let memory = RefCell::new(MemoryMock::new(&[0xE9, 0x0A])); // SBC #$0A
+        let mut cpu = CPU::new_cmos(&memory);
+        cpu.program_counter = 0x0000;
+        cpu.accumulator = 0x00;
+        cpu.processor_status.change_decimal_mode_flag(true);
+        cpu.processor_status.change_carry_flag(false);
+
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.accumulator, 0x8F);
+    }
+
+    // the 65C02 sequences of Bruce Clark's "Decimal Mode" tutorial, appendix B
+    fn cmos_reference(acc: u8, val: u8, carry: bool, subtract: bool) -> (u8, bool, bool) {
+        let signed = |value: i16| value as u8 as i8 as i16;
+        let in_range = |value: i16| (-128..=127).contains(&value);
+        let (acc, val, carry) = (acc as i16, val as i16, carry as i16);
+        if subtract {
+            let low = (acc & 0x0F) - (val & 0x0F) + carry - 1;
+            let binary = acc - val + carry - 1;
+            let mut result = binary;
+            if result < 0 {
+                result -= 0x60;
+            }
+            if low < 0 {
+                result -= 0x06;
+            }
+            let overflow = !in_range(signed(acc) - signed(val) + carry - 1);
+            return (result as u8, binary >= 0, overflow);
+        }
+
+        let mut low = (acc & 0x0F) + (val & 0x0F) + carry;
+        if low >= 0x0A {
+            low = ((low + 0x06) & 0x0F) + 0x10;
+        }
+        let mut result = (acc & 0xF0) + (val & 0xF0) + low;
+        let overflow = !in_range(signed(acc & 0xF0) + signed(val & 0xF0) + low);
+        if result >= 0xA0 {
+            result += 0x60;
+        }
+        return (result as u8, result >= 0x100, overflow);
+    }
+
+    #[test]
+    fn should_match_cmos_decimal_reference_for_every_operand() {
+        for (opcode, subtract) in [(0x69, false), (0xE9, true)] {
+            let memory = RefCell::new(MemoryMock::new(&[opcode, 0x00]));
+            let mut cpu = CPU::new_cmos(&memory);
+            for acc in 0..=0xFF {
+                for val in 0..=0xFF {
+                    for carry in [false, true] {
+                        cpu.memory.borrow_mut()[0x0001] = val;
+                        cpu.program_counter = 0x0000;
+                        cpu.accumulator = acc;
+                        cpu.processor_status.change_decimal_mode_flag(true);
+                        cpu.processor_status.change_carry_flag(carry);
+
+                        cpu.execute_next_instruction();
+
+                        let (result, carry_out, overflow) =
+                            cmos_reference(acc, val, carry, subtract);
+                        let status = &cpu.processor_status;
+                        let case = format!("{opcode:#04X} A={acc:#04X} M={val:#04X} C={carry}");
+                        assert_eq!(cpu.accumulator, result, "{case}");
+                        assert_eq!(status.get_carry_flag(), carry_out, "carry of {case}");
+                        assert_eq!(status.get_overflow_flag(), overflow, "overflow of {case}");
+                        assert_eq!(status.get_negative_flag(), result & 0x80 != 0, "{case}");
+                        assert_eq!(status.get_zero_flag(), result == 0, "{case}");
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use crate::cpu::{AddressingMode, CPU};
+use crate::memory::Bus;
+
+// Rockwell bit instructions, also present on WDC chips; the bit number is part of the opcode
+
+fn modify_bit<M: Bus>(cpu: &mut CPU<M>, bit: u8, set: bool) {
+    let mask = 1 << bit;
+    let modify = move |value: &u8| {
+        if set {
+            return value | mask;
+        }
+        return value & !mask;
+    };
+    if cpu
+        .modify_memory(AddressingMode::ZeroPage, &modify)
+        .is_none()
+    {
+        panic!("modify_bit could not address zero page");
+    }
+}
+
+pub fn rmb<M: Bus, const BIT: u8>(cpu: &mut CPU<M>) {
+    modify_bit(cpu, BIT, false);
+}
+
+pub fn smb<M: Bus, const BIT: u8>(cpu: &mut CPU<M>) {
+    modify_bit(cpu, BIT, true);
+}
+
+// zero page operand is read first, then the relative offset; taking the branch costs the
+// same extra cycles as regular branches
+fn branch_on_bit<M: Bus>(cpu: &mut CPU<M>, bit: u8, set: bool) {
+    let branch_addr = cpu.program_counter.wrapping_sub(1);
+    let value = match cpu.read_memory(AddressingMode::ZeroPage) {
+        Some(value) => value,
+        None => panic!("branch_on_bit could not address zero page"),
+    };
+    let operand = cpu.access_memory(cpu.program_counter);
+    cpu.increment_program_counter();
+    cpu.idle_cycle();
+    if (value & 1 << bit > 0) != set {
+        cpu.record_branch(branch_addr, false, false);
+        return;
+    }
+
+    let [_, program_counter_hi] = cpu.program_counter.to_le_bytes();
+    cpu.offset_program_counter(operand);
+    let [_, offset_program_counter_hi] = cpu.program_counter.to_le_bytes();
+    cpu.record_branch(
+        branch_addr,
+        true,
+        program_counter_hi != offset_program_counter_hi,
+    );
+}
+
+pub fn bbr<M: Bus, const BIT: u8>(cpu: &mut CPU<M>) {
+    branch_on_bit(cpu, BIT, false);
+}
+
+pub fn bbs<M: Bus, const BIT: u8>(cpu: &mut CPU<M>) {
+    branch_on_bit(cpu, BIT, true);
+}
+
+#[cfg(test)]
+mod tests;
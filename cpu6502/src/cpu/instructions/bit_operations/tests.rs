@@ -0,0 +1,74 @@
+#[cfg(test)]
+mod reset_and_set_memory_bit {
+    use std::cell::RefCell;
+
+    use crate::cpu::{tests::MemoryMock, CPU};
+
+    #[test]
+    fn should_reset_and_set_single_bit_in_zero_page() {
+        let memory = &RefCell::new(MemoryMock::new(&[0x37, 0x10, 0xC7, 0x10])); // RMB3 $10; SMB4 $10
+        memory.borrow_mut()[0x0010] = 0b00001000;
+        let mut cpu = CPU::new_rockwell_cmos(memory);
+        cpu.cycle = 0;
+        cpu.program_counter = 0x0000;
+
+        cpu.execute_next_instruction();
+        assert_eq!(memory.borrow()[0x0010], 0b00000000);
+        assert_eq!(cpu.cycle, 5);
+
+        cpu.execute_next_instruction();
+        assert_eq!(memory.borrow()[0x0010], 0b00010000);
+    }
+
+    #[test]
+    fn should_be_single_cycle_nop_on_plain_cmos() {
+        let memory = &RefCell::new(MemoryMock::new(&[0x87, 0x10])); // SMB0 $10
+        let mut cpu = CPU::new_cmos(memory);
+        cpu.cycle = 0;
+        cpu.program_counter = 0x0000;
+
+        assert!(cpu.execute_next_instruction().is_none());
+        assert_eq!(memory.borrow()[0x0010], 0x00);
+        assert_eq!(cpu.program_counter, 0x0001);
+        assert_eq!(cpu.cycle, 1);
+    }
+}
+
+#[cfg(test)]
+mod branch_on_bit {
+    use std::cell::RefCell;
+
+    use crate::cpu::{tests::MemoryMock, CPU};
+
+    fn execute_on_wdc(program: &[u8], value: u8) -> (u16, u64) {
+        let memory = RefCell::new(MemoryMock::default());
+        memory.borrow_mut()[0x0010] = value;
+        for (offset, byte) in program.iter().enumerate() {
+            memory.borrow_mut()[0x02F0 + offset as u16] = *byte;
+        }
+        let mut cpu = CPU::new_wdc_cmos(&memory);
+        cpu.cycle = 0;
+        cpu.program_counter = 0x02F0;
+
+        cpu.execute_next_instruction();
+
+        return (cpu.program_counter, cpu.cycle);
+    }
+
+    #[test]
+    fn should_fall_through_in_five_cycles_when_condition_fails() {
+        assert_eq!(execute_on_wdc(&[0x8F, 0x10, 0x04], 0x00), (0x02F3, 5)); // BBS0 $10,+4
+        assert_eq!(execute_on_wdc(&[0x0F, 0x10, 0x04], 0x01), (0x02F3, 5)); // BBR0 $10,+4
+    }
+
+    #[test]
+    fn should_branch_on_tested_bit() {
+        assert_eq!(execute_on_wdc(&[0xFF, 0x10, 0x04], 0x80), (0x02F7, 6)); // BBS7 $10,+4
+        assert_eq!(execute_on_wdc(&[0x7F, 0x10, 0xFD], 0x7F), (0x02F0, 6)); // BBR7 $10,-3
+    }
+
+    #[test]
+    fn should_take_extra_cycle_when_branch_crosses_page() {
+        assert_eq!(execute_on_wdc(&[0x1F, 0x10, 0x10], 0x00), (0x0303, 7)); // BBR1 $10,+16
+    }
+}
@@ -1,59 +1,145 @@
 use crate::cpu::CPU;
+use crate::memory::Bus;
 
-fn branch(cpu: &mut CPU, condition: fn(&CPU) -> bool) {
+fn branch<M: Bus>(cpu: &mut CPU<M>, condition: fn(&CPU<M>) -> bool) {
+    let branch_addr = cpu.program_counter.wrapping_sub(1);
     let operand = cpu.access_memory(cpu.program_counter);
     cpu.increment_program_counter();
     if !condition(cpu) {
+        cpu.record_branch(branch_addr, false, false);
         return;
     }
 
-    cpu.offset_program_counter(operand)
+    let [_, program_counter_hi] = cpu.program_counter.to_le_bytes();
+    cpu.offset_program_counter(operand);
+    let [_, offset_program_counter_hi] = cpu.program_counter.to_le_bytes();
+    let page_crossed = program_counter_hi != offset_program_counter_hi;
+    // the extra cycle of a branch staying on its page does not poll interrupts, so one
+    // arriving during it is taken only after the next instruction
+    cpu.skip_interrupt_poll = !page_crossed;
+    cpu.record_branch(branch_addr, true, page_crossed);
 }
 
-pub fn bcc(cpu: &mut CPU) {
-    branch(cpu, |cpu: &CPU| -> bool {
+// 65CE02 word branches take a 16-bit offset, counted from the last byte of the instruction
+fn branch_word<M: Bus>(cpu: &mut CPU<M>, condition: fn(&CPU<M>) -> bool) {
+    let branch_addr = cpu.program_counter.wrapping_sub(1);
+    let offset = cpu.fetch_address();
+    if !condition(cpu) {
+        cpu.record_branch(branch_addr, false, false);
+        return;
+    }
+
+    let target = cpu.program_counter.wrapping_sub(1).wrapping_add(offset);
+    let page_crossed = (cpu.program_counter ^ target) & 0xFF00 != 0;
+    cpu.idle_cycle();
+    cpu.program_counter = target;
+    cpu.record_branch(branch_addr, true, page_crossed);
+}
+
+pub fn bra<M: Bus>(cpu: &mut CPU<M>) {
+    branch(cpu, |_: &CPU<M>| -> bool {
+        return true;
+    });
+}
+
+pub fn bcc<M: Bus>(cpu: &mut CPU<M>) {
+    branch(cpu, |cpu: &CPU<M>| -> bool {
+        return !cpu.processor_status.get_carry_flag();
+    });
+}
+
+pub fn bcs<M: Bus>(cpu: &mut CPU<M>) {
+    branch(cpu, |cpu: &CPU<M>| -> bool {
+        return cpu.processor_status.get_carry_flag();
+    });
+}
+
+pub fn beq<M: Bus>(cpu: &mut CPU<M>) {
+    branch(cpu, |cpu: &CPU<M>| -> bool {
+        return cpu.processor_status.get_zero_flag();
+    });
+}
+
+pub fn bmi<M: Bus>(cpu: &mut CPU<M>) {
+    branch(cpu, |cpu: &CPU<M>| -> bool {
+        return cpu.processor_status.get_negative_flag();
+    });
+}
+
+pub fn bne<M: Bus>(cpu: &mut CPU<M>) {
+    branch(cpu, |cpu: &CPU<M>| -> bool {
+        return !cpu.processor_status.get_zero_flag();
+    });
+}
+
+pub fn bpl<M: Bus>(cpu: &mut CPU<M>) {
+    branch(cpu, |cpu: &CPU<M>| -> bool {
+        return !cpu.processor_status.get_negative_flag();
+    });
+}
+
+pub fn bvs<M: Bus>(cpu: &mut CPU<M>) {
+    branch(cpu, |cpu: &CPU<M>| -> bool {
+        return cpu.processor_status.get_overflow_flag();
+    });
+}
+
+pub fn bvc<M: Bus>(cpu: &mut CPU<M>) {
+    branch(cpu, |cpu: &CPU<M>| -> bool {
+        return !cpu.processor_status.get_overflow_flag();
+    });
+}
+
+pub fn bra_w<M: Bus>(cpu: &mut CPU<M>) {
+    branch_word(cpu, |_: &CPU<M>| -> bool {
+        return true;
+    });
+}
+
+pub fn bcc_w<M: Bus>(cpu: &mut CPU<M>) {
+    branch_word(cpu, |cpu: &CPU<M>| -> bool {
         return !cpu.processor_status.get_carry_flag();
     });
 }
 
-pub fn bcs(cpu: &mut CPU) {
-    branch(cpu, |cpu: &CPU| -> bool {
+pub fn bcs_w<M: Bus>(cpu: &mut CPU<M>) {
+    branch_word(cpu, |cpu: &CPU<M>| -> bool {
         return cpu.processor_status.get_carry_flag();
     });
 }
 
-pub fn beq(cpu: &mut CPU) {
-    branch(cpu, |cpu: &CPU| -> bool {
+pub fn beq_w<M: Bus>(cpu: &mut CPU<M>) {
+    branch_word(cpu, |cpu: &CPU<M>| -> bool {
         return cpu.processor_status.get_zero_flag();
     });
 }
 
-pub fn bmi(cpu: &mut CPU) {
-    branch(cpu, |cpu: &CPU| -> bool {
+pub fn bmi_w<M: Bus>(cpu: &mut CPU<M>) {
+    branch_word(cpu, |cpu: &CPU<M>| -> bool {
         return cpu.processor_status.get_negative_flag();
     });
 }
 
-pub fn bne(cpu: &mut CPU) {
-    branch(cpu, |cpu: &CPU| -> bool {
+pub fn bne_w<M: Bus>(cpu: &mut CPU<M>) {
+    branch_word(cpu, |cpu: &CPU<M>| -> bool {
         return !cpu.processor_status.get_zero_flag();
     });
 }
 
-pub fn bpl(cpu: &mut CPU) {
-    branch(cpu, |cpu: &CPU| -> bool {
+pub fn bpl_w<M: Bus>(cpu: &mut CPU<M>) {
+    branch_word(cpu, |cpu: &CPU<M>| -> bool {
         return !cpu.processor_status.get_negative_flag();
     });
 }
 
-pub fn bvs(cpu: &mut CPU) {
-    branch(cpu, |cpu: &CPU| -> bool {
+pub fn bvs_w<M: Bus>(cpu: &mut CPU<M>) {
+    branch_word(cpu, |cpu: &CPU<M>| -> bool {
         return cpu.processor_status.get_overflow_flag();
     });
 }
 
-pub fn bvc(cpu: &mut CPU) {
-    branch(cpu, |cpu: &CPU| -> bool {
+pub fn bvc_w<M: Bus>(cpu: &mut CPU<M>) {
+    branch_word(cpu, |cpu: &CPU<M>| -> bool {
         return !cpu.processor_status.get_overflow_flag();
     });
 }
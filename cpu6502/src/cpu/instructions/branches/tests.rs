@@ -13,7 +13,7 @@ mod common_branch {
         let mut cpu = CPU::new_nmos(memory);
         cpu.program_counter = 0x0000;
 
-        let condition: fn(&CPU) -> bool = |_| false;
+        let condition: fn(&CPU<_>) -> bool = |_| false;
         branch(&mut cpu, condition);
 
         assert_eq!(cpu.program_counter, 0x0001);
@@ -26,7 +26,7 @@ mod common_branch {
         let mut cpu = CPU::new_nmos(memory);
         cpu.program_counter = 0x00;
 
-        let condition: fn(&CPU) -> bool = |_| true;
+        let condition: fn(&CPU<_>) -> bool = |_| true;
         branch(&mut cpu, condition);
 
         assert_eq!(cpu.program_counter, 0x0004);
@@ -46,7 +46,7 @@ mod common_branch {
         let mut cpu = CPU::new_nmos(memory);
         cpu.program_counter = 0x02;
 
-        let condition: fn(&CPU) -> bool = |_| true;
+        let condition: fn(&CPU<_>) -> bool = |_| true;
         branch(&mut cpu, condition);
 
         assert_eq!(cpu.program_counter, 0x00);
@@ -61,7 +61,7 @@ mod common_branch {
         let mut cpu = CPU::new_nmos(memory);
         cpu.program_counter = 0x00FE;
 
-        let condition: fn(&CPU) -> bool = |_| true;
+        let condition: fn(&CPU<_>) -> bool = |_| true;
         branch(&mut cpu, condition);
 
         assert_eq!(cpu.program_counter, 0x0103);
@@ -81,7 +81,7 @@ mod common_branch {
         let mut cpu = CPU::new_nmos(memory);
         cpu.program_counter = 0x00;
 
-        let condition: fn(&CPU) -> bool = |_| true;
+        let condition: fn(&CPU<_>) -> bool = |_| true;
         branch(&mut cpu, condition);
 
         assert_eq!(cpu.program_counter, 0xFFFE);
@@ -94,7 +94,7 @@ mod common_branch {
         cpu.program_counter = 0x00;
         cpu.cycle = 0;
 
-        let condition: fn(&CPU) -> bool = |_| false;
+        let condition: fn(&CPU<_>) -> bool = |_| false;
         branch(&mut cpu, condition);
 
         assert_eq!(cpu.cycle, 1);
@@ -108,7 +108,7 @@ mod common_branch {
         cpu.program_counter = 0x00;
         cpu.cycle = 0;
 
-        let condition: fn(&CPU) -> bool = |_| true;
+        let condition: fn(&CPU<_>) -> bool = |_| true;
         branch(&mut cpu, condition);
 
         assert_eq!(cpu.cycle, 2);
@@ -124,7 +124,7 @@ mod common_branch {
         cpu.program_counter = 0x00FE;
         cpu.cycle = 0;
 
-        let condition: fn(&CPU) -> bool = |_| true;
+        let condition: fn(&CPU<_>) -> bool = |_| true;
         branch(&mut cpu, condition);
 
         assert_eq!(cpu.cycle, 3);
@@ -410,3 +410,34 @@ mod bvs {
         assert_eq!(cpu.program_counter, 0x0004);
     }
 }
+
+#[cfg(test)]
+mod bra {
+    use std::cell::RefCell;
+
+    use crate::cpu::{tests::MemoryMock, CPU};
+
+    fn branch_on_cmos(program_counter: u16, offset: u8) -> (u16, u64) {
+        let memory = RefCell::new(MemoryMock::default());
+        memory.borrow_mut()[program_counter] = 0x80;
+        memory.borrow_mut()[program_counter + 1] = offset;
+        let mut cpu = CPU::new_cmos(&memory);
+        cpu.cycle = 0;
+        cpu.program_counter = program_counter;
+
+        cpu.execute_next_instruction();
+
+        return (cpu.program_counter, cpu.cycle);
+    }
+
+    #[test]
+    fn should_always_branch() {
+        assert_eq!(branch_on_cmos(0x0200, 0x10), (0x0212, 3));
+        assert_eq!(branch_on_cmos(0x0200, 0xFC), (0x01FE, 4));
+    }
+
+    #[test]
+    fn should_take_extra_cycle_on_page_crossing() {
+        assert_eq!(branch_on_cmos(0x02F0, 0x20), (0x0312, 4));
+    }
+}
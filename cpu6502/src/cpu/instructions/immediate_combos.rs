@@ -0,0 +1,75 @@
+use crate::{
+    consts::Byte,
+    cpu::{AddressingMode, Registers, CPU},
+    memory::Bus,
+};
+
+// undocumented NMOS opcodes that AND the immediate operand with a register and then run
+// part of another instruction on the result
+
+fn read_operand<M: Bus>(cpu: &mut CPU<M>) -> Byte {
+    return match cpu.read_memory(AddressingMode::Immediate) {
+        Some(value) => value,
+        None => panic!("immediate combo could not read its operand"),
+    };
+}
+
+// AND with carry copied from the negative flag
+pub fn anc_im<M: Bus>(cpu: &mut CPU<M>) {
+    let value = read_operand(cpu);
+    cpu.set_register(Registers::Accumulator, cpu.accumulator & value);
+    cpu.processor_status
+        .change_carry_flag(cpu.processor_status.get_negative_flag());
+}
+
+// AND + LSR A
+pub fn alr_im<M: Bus>(cpu: &mut CPU<M>) {
+    let value = cpu.accumulator & read_operand(cpu);
+    cpu.processor_status
+        .change_carry_flag(value & 0b00000001 > 0);
+    cpu.set_register(Registers::Accumulator, value >> 1);
+}
+
+// AND + ROR A, with carry and overflow taken from the adder rather than the rotation; in
+// decimal mode the adder also applies BCD correction to the rotated value
+pub fn arr_im<M: Bus>(cpu: &mut CPU<M>) {
+    let value = cpu.accumulator & read_operand(cpu);
+    let carry = cpu.processor_status.get_carry_flag();
+    let rotated = value >> 1 | (carry as u8) << 7;
+
+    if !cpu.is_decimal_mode_active() {
+        cpu.set_register(Registers::Accumulator, rotated);
+        cpu.processor_status
+            .change_carry_flag(rotated & 0b01000000 > 0);
+        cpu.processor_status
+            .change_overflow_flag((rotated ^ rotated << 1) & 0b01000000 > 0);
+        return;
+    }
+
+    cpu.processor_status.change_negative_flag(carry);
+    cpu.processor_status.change_zero_flag(rotated == 0);
+    cpu.processor_status
+        .change_overflow_flag((rotated ^ value) & 0b01000000 > 0);
+
+    let mut result = rotated;
+    if (value & 0x0F) + (value & 0x01) > 0x05 {
+        result = (result & 0xF0) | (result.wrapping_add(0x06) & 0x0F);
+    }
+    let high_nibble_fix = (value & 0xF0) as u16 + (value & 0x10) as u16 > 0x50;
+    if high_nibble_fix {
+        result = result.wrapping_add(0x60);
+    }
+    cpu.processor_status.change_carry_flag(high_nibble_fix);
+    cpu.accumulator = result;
+}
+
+// (A AND X) - operand into X, flags set like CMP and decimal mode ignored
+pub fn sbx_im<M: Bus>(cpu: &mut CPU<M>) {
+    let value = read_operand(cpu);
+    let masked = cpu.accumulator & cpu.index_register_x;
+    cpu.processor_status.change_carry_flag(masked >= value);
+    cpu.set_register(Registers::IndexX, masked.wrapping_sub(value));
+}
+
+#[cfg(test)]
+mod tests;
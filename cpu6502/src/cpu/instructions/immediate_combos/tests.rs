@@ -0,0 +1,123 @@
+#[cfg(test)]
+mod anc {
+    use crate::cpu::tests::assert_instruction;
+
+    #[test]
+    fn should_and_accumulator_and_copy_negative_flag_into_carry() {
+        assert_instruction! {
+            program: [0x0B, 0x80], // ANC #$80
+            setup: |cpu| cpu.accumulator = 0xF0,
+            cycles: 2,
+            registers: { accumulator: 0x80 },
+            status: 0b10000001,
+        };
+    }
+
+    #[test]
+    fn should_clear_carry_for_positive_result_with_alternative_opcode() {
+        assert_instruction! {
+            program: [0x2B, 0x0F], // ANC #$0F
+            setup: |cpu| {
+                cpu.accumulator = 0x3F;
+                cpu.processor_status.change_carry_flag(true);
+            },
+            cycles: 2,
+            registers: { accumulator: 0x0F },
+            status: 0b00000000,
+        };
+    }
+}
+
+#[cfg(test)]
+mod alr {
+    use crate::cpu::tests::assert_instruction;
+
+    #[test]
+    fn should_and_accumulator_and_shift_it_right() {
+        assert_instruction! {
+            program: [0x4B, 0x03], // ALR #$03
+            setup: |cpu| cpu.accumulator = 0xFF,
+            cycles: 2,
+            registers: { accumulator: 0x01 },
+            status: 0b00000001,
+        };
+    }
+}
+
+#[cfg(test)]
+mod arr {
+    use crate::cpu::tests::assert_instruction;
+
+    #[test]
+    fn should_take_carry_from_bit_six_and_overflow_from_bits_six_and_five() {
+        assert_instruction! {
+            program: [0x6B, 0xC0], // ARR #$C0
+            setup: |cpu| cpu.accumulator = 0xFF,
+            cycles: 2,
+            registers: { accumulator: 0x60 },
+            status: 0b00000001,
+        };
+    }
+
+    #[test]
+    fn should_rotate_carry_into_bit_seven() {
+        assert_instruction! {
+            program: [0x6B, 0x80], // ARR #$80
+            setup: |cpu| {
+                cpu.accumulator = 0xFF;
+                cpu.processor_status.change_carry_flag(true);
+            },
+            cycles: 2,
+            registers: { accumulator: 0xC0 },
+            status: 0b11000001,
+        };
+    }
+
+    #[test]
+    fn should_apply_bcd_correction_in_decimal_mode() {
+        assert_instruction! {
+            program: [0x6B, 0x5F], // ARR #$5F
+            setup: |cpu| {
+                cpu.accumulator = 0xFF;
+                cpu.processor_status.change_decimal_mode_flag(true);
+            },
+            cycles: 2,
+            registers: { accumulator: 0x85 },
+            status: 0b01001001,
+        };
+    }
+}
+
+#[cfg(test)]
+mod sbx {
+    use crate::cpu::tests::assert_instruction;
+
+    #[test]
+    fn should_subtract_operand_from_accumulator_and_x_into_x() {
+        assert_instruction! {
+            program: [0xCB, 0x10], // SBX #$10
+            setup: |cpu| {
+                cpu.accumulator = 0xF0;
+                cpu.index_register_x = 0x3C;
+            },
+            cycles: 2,
+            registers: { accumulator: 0xF0, index_register_x: 0x20 },
+            status: 0b00000001,
+        };
+    }
+
+    #[test]
+    fn should_clear_carry_on_borrow_and_ignore_decimal_mode() {
+        assert_instruction! {
+            program: [0xCB, 0x40], // SBX #$40
+            setup: |cpu| {
+                cpu.accumulator = 0xF0;
+                cpu.index_register_x = 0x3C;
+                cpu.processor_status.change_decimal_mode_flag(true);
+            },
+            cycles: 2,
+            registers: { index_register_x: 0xF0 },
+            status: 0b10001000,
+        };
+    }
+}
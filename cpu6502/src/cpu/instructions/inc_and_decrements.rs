@@ -1,4 +1,8 @@
-use crate::cpu::{AddressingMode, Registers, CPU};
+use crate::{
+    consts::Word,
+    cpu::{AddressingMode, Registers, CPU},
+    memory::Bus,
+};
 
 fn decrement_cb(value: &u8) -> u8 {
     return value.wrapping_sub(1);
@@ -8,7 +12,7 @@ fn increment_cb(value: &u8) -> u8 {
     return value.wrapping_add(1);
 }
 
-fn decrement_memory(cpu: &mut CPU, addr_mode: AddressingMode) {
+fn decrement_memory<M: Bus>(cpu: &mut CPU<M>, addr_mode: AddressingMode) {
     match cpu.modify_memory(addr_mode, &decrement_cb) {
         Some((_, modified_value)) => {
             cpu.set_status_of_value(modified_value);
@@ -17,40 +21,48 @@ fn decrement_memory(cpu: &mut CPU, addr_mode: AddressingMode) {
     };
 }
 
-fn decrement_register(cpu: &mut CPU, register: Registers) {
+fn decrement_register<M: Bus>(cpu: &mut CPU<M>, register: Registers) {
     match register {
-        Registers::IndexX | Registers::IndexY => {
+        Registers::Accumulator | Registers::IndexX | Registers::IndexY | Registers::IndexZ => {
             cpu.decrement_register(register);
         }
         _ => panic!("decrement_register used with incorrect register"),
     }
 }
 
-pub fn dec_zp(cpu: &mut CPU) {
+pub fn dec_zp<M: Bus>(cpu: &mut CPU<M>) {
     decrement_memory(cpu, AddressingMode::ZeroPage);
 }
 
-pub fn dec_zpx(cpu: &mut CPU) {
+pub fn dec_zpx<M: Bus>(cpu: &mut CPU<M>) {
     decrement_memory(cpu, AddressingMode::ZeroPageX);
 }
 
-pub fn dec_a(cpu: &mut CPU) {
+pub fn dec_a<M: Bus>(cpu: &mut CPU<M>) {
     decrement_memory(cpu, AddressingMode::Absolute);
 }
 
-pub fn dec_ax(cpu: &mut CPU) {
+pub fn dec_ax<M: Bus>(cpu: &mut CPU<M>) {
     decrement_memory(cpu, AddressingMode::AbsoluteX);
 }
 
-pub fn dex_im(cpu: &mut CPU) {
+pub fn dec_acc<M: Bus>(cpu: &mut CPU<M>) {
+    decrement_register(cpu, Registers::Accumulator);
+}
+
+pub fn dex_im<M: Bus>(cpu: &mut CPU<M>) {
     decrement_register(cpu, Registers::IndexX);
 }
 
-pub fn dey_im(cpu: &mut CPU) {
+pub fn dey_im<M: Bus>(cpu: &mut CPU<M>) {
     decrement_register(cpu, Registers::IndexY);
 }
 
-fn increment_memory(cpu: &mut CPU, addr_mode: AddressingMode) {
+pub fn dez<M: Bus>(cpu: &mut CPU<M>) {
+    decrement_register(cpu, Registers::IndexZ);
+}
+
+fn increment_memory<M: Bus>(cpu: &mut CPU<M>, addr_mode: AddressingMode) {
     match cpu.modify_memory(addr_mode, &increment_cb) {
         Some((_, modified_value)) => {
             cpu.set_status_of_value(modified_value);
@@ -59,38 +71,73 @@ fn increment_memory(cpu: &mut CPU, addr_mode: AddressingMode) {
     };
 }
 
-fn increment_register(cpu: &mut CPU, register: Registers) {
+fn increment_register<M: Bus>(cpu: &mut CPU<M>, register: Registers) {
     match register {
-        Registers::IndexX | Registers::IndexY => {
+        Registers::Accumulator | Registers::IndexX | Registers::IndexY | Registers::IndexZ => {
             cpu.increment_register(register);
         }
         _ => panic!("increment_register used with incorrect register"),
     }
 }
 
-pub fn inc_zp(cpu: &mut CPU) {
+pub fn inc_zp<M: Bus>(cpu: &mut CPU<M>) {
     increment_memory(cpu, AddressingMode::ZeroPage);
 }
 
-pub fn inc_zpx(cpu: &mut CPU) {
+pub fn inc_zpx<M: Bus>(cpu: &mut CPU<M>) {
     increment_memory(cpu, AddressingMode::ZeroPageX);
 }
 
-pub fn inc_a(cpu: &mut CPU) {
+pub fn inc_a<M: Bus>(cpu: &mut CPU<M>) {
     increment_memory(cpu, AddressingMode::Absolute);
 }
 
-pub fn inc_ax(cpu: &mut CPU) {
+pub fn inc_ax<M: Bus>(cpu: &mut CPU<M>) {
     increment_memory(cpu, AddressingMode::AbsoluteX);
 }
 
-pub fn inx_im(cpu: &mut CPU) {
+pub fn inc_acc<M: Bus>(cpu: &mut CPU<M>) {
+    increment_register(cpu, Registers::Accumulator);
+}
+
+pub fn inx_im<M: Bus>(cpu: &mut CPU<M>) {
     increment_register(cpu, Registers::IndexX);
 }
 
-pub fn iny_im(cpu: &mut CPU) {
+pub fn iny_im<M: Bus>(cpu: &mut CPU<M>) {
     increment_register(cpu, Registers::IndexY);
 }
 
+pub fn inz<M: Bus>(cpu: &mut CPU<M>) {
+    increment_register(cpu, Registers::IndexZ);
+}
+
+// INW and DEW step a little endian word in zero page; N and Z describe the whole word
+fn step_zero_page_word<M: Bus>(cpu: &mut CPU<M>, step: fn(Word) -> Word) {
+    let address = cpu.fetch_zero_page_address();
+    let value = cpu.fetch_zero_page_pointer(address);
+    let [lo, hi] = step(value).to_le_bytes();
+    let [address_lo, address_hi] = address.to_le_bytes();
+
+    cpu.put_into_memory(address, lo);
+    cpu.tick();
+    cpu.put_into_memory(
+        Word::from_le_bytes([address_lo.wrapping_add(1), address_hi]),
+        hi,
+    );
+    cpu.tick();
+    cpu.processor_status.change_zero_flag(lo == 0 && hi == 0);
+    cpu.processor_status
+        .change_negative_flag(hi & 0b10000000 > 0);
+}
+
+pub fn inw_zp<M: Bus>(cpu: &mut CPU<M>) {
+    step_zero_page_word(cpu, |value| value.wrapping_add(1));
+}
+
+pub fn dew_zp<M: Bus>(cpu: &mut CPU<M>) {
+    step_zero_page_word(cpu, |value| value.wrapping_sub(1));
+}
+
 #[cfg(test)]
 mod tests;
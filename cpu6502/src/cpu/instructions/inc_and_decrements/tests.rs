@@ -1,547 +1,342 @@
 #[cfg(test)]
 mod inx_im {
-    use std::cell::RefCell;
-
-    use crate::cpu::{instructions::inx_im, tests::MemoryMock, CPU};
-
-    #[test]
-    fn should_increment_x_register() {
-        let memory = &RefCell::new(MemoryMock::default());
-        let mut cpu = CPU::new_nmos(memory);
-        cpu.index_register_x = 0x02;
-
-        inx_im(&mut cpu);
-
-        assert_eq!(cpu.index_register_x, 0x03);
-    }
+    use crate::cpu::tests::assert_instruction;
 
     #[test]
-    fn should_take_one_cycle() {
-        let memory = &RefCell::new(MemoryMock::default());
-        let mut cpu = CPU::new_nmos(memory);
-        cpu.index_register_x = 0x02;
-        cpu.cycle = 0;
-
-        inx_im(&mut cpu);
-
-        assert_eq!(cpu.cycle, 1);
+    fn should_increment_x_register_in_two_cycles() {
+        assert_instruction! {
+            program: [0xE8],
+            setup: |cpu| cpu.index_register_x = 0x02,
+            cycles: 2,
+            registers: { index_register_x: 0x03 },
+            status: 0b00000000,
+        };
     }
 
     #[test]
     fn should_set_processor_status_of_x_register_after_increment() {
-        let memory = &RefCell::new(MemoryMock::default());
-        let mut cpu = CPU::new_nmos(memory);
-        cpu.index_register_x = 0xFF;
-
-        inx_im(&mut cpu);
-
-        assert_eq!(cpu.processor_status, 0b00000010);
+        assert_instruction! {
+            program: [0xE8],
+            setup: |cpu| cpu.index_register_x = 0xFF,
+            status: 0b00000010,
+        };
     }
 }
 
 #[cfg(test)]
 mod iny_im {
-    use std::cell::RefCell;
-
-    use crate::cpu::{instructions::iny_im, tests::MemoryMock, CPU};
-
-    #[test]
-    fn should_increment_y_register() {
-        let memory = &RefCell::new(MemoryMock::default());
-        let mut cpu = CPU::new_nmos(memory);
-        cpu.index_register_y = 0x02;
-
-        iny_im(&mut cpu);
-
-        assert_eq!(cpu.index_register_y, 0x03);
-    }
+    use crate::cpu::tests::assert_instruction;
 
     #[test]
-    fn should_take_one_cycle() {
-        let memory = &RefCell::new(MemoryMock::default());
-        let mut cpu = CPU::new_nmos(memory);
-        cpu.index_register_y = 0x02;
-        cpu.cycle = 0;
-
-        iny_im(&mut cpu);
-
-        assert_eq!(cpu.cycle, 1);
+    fn should_increment_y_register_in_two_cycles() {
+        assert_instruction! {
+            program: [0xC8],
+            setup: |cpu| cpu.index_register_y = 0x02,
+            cycles: 2,
+            registers: { index_register_y: 0x03 },
+            status: 0b00000000,
+        };
     }
 
     #[test]
-    fn should_set_processor_status_of_x_register_after_increment() {
-        let memory = &RefCell::new(MemoryMock::default());
-        let mut cpu = CPU::new_nmos(memory);
-        cpu.index_register_y = 0xFF;
-
-        iny_im(&mut cpu);
-
-        assert_eq!(cpu.processor_status, 0b00000010);
+    fn should_set_processor_status_of_y_register_after_increment() {
+        assert_instruction! {
+            program: [0xC8],
+            setup: |cpu| cpu.index_register_y = 0xFF,
+            status: 0b00000010,
+        };
     }
 }
 
 #[cfg(test)]
 mod inc_zp {
-    use std::cell::RefCell;
-
-    use crate::cpu::{instructions::inc_zp, tests::MemoryMock, Byte, Word, CPU};
-
-    const VALUE: Byte = 0x02;
-    const ZERO_PAGE_ADDR: Byte = 0x03;
+    use crate::cpu::tests::assert_instruction;
 
     #[test]
-    fn should_increment_value_stored_in_memory_at_zero_page_address() {
-        let memory = &RefCell::new(MemoryMock::new(&[ZERO_PAGE_ADDR, 0xFF, 0x00, VALUE]));
-        let mut cpu = CPU::new_nmos(memory);
-        cpu.program_counter = 0x00;
-
-        inc_zp(&mut cpu);
-
-        assert_eq!(memory.borrow()[ZERO_PAGE_ADDR as Word], 0x03);
-    }
-
-    #[test]
-    fn should_take_four_cycles() {
-        let memory = &RefCell::new(MemoryMock::new(&[ZERO_PAGE_ADDR, 0xFF, 0x00, VALUE]));
-        let mut cpu = CPU::new_nmos(memory);
-        cpu.program_counter = 0x00;
-        cpu.cycle = 0;
-
-        inc_zp(&mut cpu);
-
-        assert_eq!(cpu.cycle, 4);
+    fn should_increment_value_stored_in_memory_at_zero_page_address_in_five_cycles() {
+        assert_instruction! {
+            program: [0xE6, 0x03, 0x00, 0x02],
+            cycles: 5,
+            memory: { 0x0003 => 0x03 },
+            status: 0b00000000,
+        };
     }
 
     #[test]
     fn should_set_processor_status_of_value_in_memory() {
-        const VALUE: Byte = 0xFF;
-        let memory = &RefCell::new(MemoryMock::new(&[ZERO_PAGE_ADDR, 0xFF, 0x00, VALUE]));
-        let mut cpu = CPU::new_nmos(memory);
-        cpu.program_counter = 0x00;
-
-        inc_zp(&mut cpu);
-
-        assert_eq!(cpu.processor_status, 0b00000010);
+        assert_instruction! {
+            program: [0xE6, 0x03, 0x00, 0xFF],
+            memory: { 0x0003 => 0x00 },
+            status: 0b00000010,
+        };
     }
 }
 
 #[cfg(test)]
 mod inc_zpx {
-    use std::cell::RefCell;
-
-    use crate::cpu::{instructions::inc_zpx, tests::MemoryMock, Byte, Word, CPU};
-
-    const VALUE: Byte = 0x09;
-    const ZERO_PAGE_ADDR: Byte = 0x01;
-    const ZERO_PAGE_ADDR_SUM_X: Word = 0x03;
+    use crate::cpu::tests::assert_instruction;
 
     #[test]
-    fn should_increment_value_stored_in_memory_at_zero_page_address_summed_with_index_register_x() {
-        let memory = &RefCell::new(MemoryMock::new(&[ZERO_PAGE_ADDR, 0xFF, 0x00, VALUE]));
-        let mut cpu = CPU::new_nmos(memory);
-        cpu.program_counter = 0x00;
-        cpu.index_register_x = 0x02;
-
-        inc_zpx(&mut cpu);
-
-        assert_eq!(memory.borrow()[ZERO_PAGE_ADDR_SUM_X as Word], 0x0A);
-    }
-
-    #[test]
-    fn should_take_five_cycles() {
-        let memory = &RefCell::new(MemoryMock::new(&[ZERO_PAGE_ADDR, 0xFF, 0x00, VALUE]));
-        let mut cpu = CPU::new_nmos(memory);
-        cpu.program_counter = 0x00;
-        cpu.index_register_x = 0x02;
-        cpu.cycle = 0;
-
-        inc_zpx(&mut cpu);
-
-        assert_eq!(cpu.cycle, 5);
+    fn should_increment_value_stored_in_memory_at_zero_page_address_summed_with_index_register_x_in_six_cycles(
+    ) {
+        assert_instruction! {
+            program: [0xF6, 0x01, 0x00, 0x09],
+            setup: |cpu| cpu.index_register_x = 0x02,
+            cycles: 6,
+            memory: { 0x0003 => 0x0A },
+            status: 0b00000000,
+        };
     }
 
     #[test]
     fn should_set_processor_status_of_value_in_memory() {
-        const VALUE: Byte = 0xFF;
-        let memory = &RefCell::new(MemoryMock::new(&[ZERO_PAGE_ADDR, 0xFF, 0x00, VALUE]));
-        let mut cpu = CPU::new_nmos(memory);
-        cpu.program_counter = 0x00;
-        cpu.index_register_x = 0x02;
-
-        inc_zpx(&mut cpu);
-
-        assert_eq!(cpu.processor_status, 0b00000010);
+        assert_instruction! {
+            program: [0xF6, 0x01, 0x00, 0xFF],
+            setup: |cpu| cpu.index_register_x = 0x02,
+            memory: { 0x0003 => 0x00 },
+            status: 0b00000010,
+        };
     }
 }
 
 #[cfg(test)]
 mod inc_a {
-    use std::cell::RefCell;
-
-    use crate::cpu::{instructions::inc_a, tests::MemoryMock, Byte, Word, CPU};
-
-    const VALUE: Byte = 0x09;
-    const ADDR_LO: Byte = 0x04;
-    const ADDR_HI: Byte = 0x00;
-    const ADDR: Word = 0x0004;
+    use crate::cpu::tests::assert_instruction;
 
     #[test]
-    fn should_increment_value_stored_in_memory_at_absolute_address() {
-        let memory = &RefCell::new(MemoryMock::new(&[ADDR_LO, ADDR_HI, 0x00, 0x00, VALUE]));
-        let mut cpu = CPU::new_nmos(memory);
-        cpu.program_counter = 0x00;
-
-        inc_a(&mut cpu);
-
-        assert_eq!(memory.borrow()[ADDR as Word], 0x0A);
-    }
-
-    #[test]
-    fn should_take_five_cycles() {
-        let memory = &RefCell::new(MemoryMock::new(&[ADDR_LO, ADDR_HI, 0x00, 0x00, VALUE]));
-        let mut cpu = CPU::new_nmos(memory);
-        cpu.program_counter = 0x00;
-        cpu.cycle = 0;
-
-        inc_a(&mut cpu);
-
-        assert_eq!(cpu.cycle, 5);
+    fn should_increment_value_stored_in_memory_at_absolute_address_in_six_cycles() {
+        assert_instruction! {
+            program: [0xEE, 0x04, 0x00, 0x00, 0x09],
+            cycles: 6,
+            memory: { 0x0004 => 0x0A },
+            status: 0b00000000,
+        };
     }
 
     #[test]
     fn should_set_processor_status_of_value_in_memory() {
-        const VALUE: Byte = 0xFF;
-        let memory = &RefCell::new(MemoryMock::new(&[ADDR_LO, ADDR_HI, 0x00, 0x00, VALUE]));
-        let mut cpu = CPU::new_nmos(memory);
-        cpu.program_counter = 0x00;
-
-        inc_a(&mut cpu);
-
-        assert_eq!(cpu.processor_status, 0b00000010);
+        assert_instruction! {
+            program: [0xEE, 0x04, 0x00, 0x00, 0xFF],
+            memory: { 0x0004 => 0x00 },
+            status: 0b00000010,
+        };
     }
 }
 
 #[cfg(test)]
 mod inc_ax {
-    use std::cell::RefCell;
-
-    use crate::cpu::{instructions::inc_ax, tests::MemoryMock, Byte, Word, CPU};
-
-    const VALUE: Byte = 0x09;
-    const ADDR_LO: Byte = 0x02;
-    const ADDR_HI: Byte = 0x00;
-    const OFFSET: Byte = 0x02;
-    const ADDR_OFFSET_BY_X: Word = 0x0004;
+    use crate::cpu::tests::assert_instruction;
 
     #[test]
-    fn should_increment_value_stored_in_memory_at_absolute_address_offset_by_index_register_x() {
-        let memory = &RefCell::new(MemoryMock::new(&[ADDR_LO, ADDR_HI, 0x00, 0x00, VALUE]));
-        let mut cpu = CPU::new_nmos(memory);
-        cpu.program_counter = 0x00;
-        cpu.index_register_x = OFFSET;
-
-        inc_ax(&mut cpu);
-
-        assert_eq!(memory.borrow()[ADDR_OFFSET_BY_X], 0x0A);
-    }
-
-    #[test]
-    fn should_take_six_cycles() {
-        let memory = &RefCell::new(MemoryMock::new(&[ADDR_LO, ADDR_HI, 0x00, 0x00, VALUE]));
-        let mut cpu = CPU::new_nmos(memory);
-        cpu.program_counter = 0x00;
-        cpu.index_register_x = OFFSET;
-        cpu.cycle = 0;
-
-        inc_ax(&mut cpu);
-
-        assert_eq!(cpu.cycle, 6);
+    fn should_increment_value_stored_in_memory_at_absolute_address_offset_by_index_register_x_in_seven_cycles(
+    ) {
+        assert_instruction! {
+            program: [0xFE, 0x02, 0x00, 0x00, 0x09],
+            setup: |cpu| cpu.index_register_x = 0x02,
+            cycles: 7,
+            memory: { 0x0004 => 0x0A },
+            status: 0b00000000,
+        };
     }
 
     #[test]
     fn should_set_processor_status_of_value_in_memory() {
-        const VALUE: Byte = 0xFF;
-        let memory = &RefCell::new(MemoryMock::new(&[ADDR_LO, ADDR_HI, 0x00, 0x00, VALUE]));
-        let mut cpu = CPU::new_nmos(memory);
-        cpu.program_counter = 0x00;
-        cpu.index_register_x = OFFSET;
-
-        inc_ax(&mut cpu);
-
-        assert_eq!(cpu.processor_status, 0b00000010);
+        assert_instruction! {
+            program: [0xFE, 0x02, 0x00, 0x00, 0xFF],
+            setup: |cpu| cpu.index_register_x = 0x02,
+            memory: { 0x0004 => 0x00 },
+            status: 0b00000010,
+        };
     }
 }
 
 #[cfg(test)]
 mod dex_im {
-    use std::cell::RefCell;
-
-    use crate::cpu::{instructions::dex_im, tests::MemoryMock, CPU};
+    use crate::cpu::tests::assert_instruction;
 
     #[test]
-    fn should_decrement_x_register() {
-        let memory = &RefCell::new(MemoryMock::default());
-        let mut cpu = CPU::new_nmos(memory);
-        cpu.index_register_x = 0x02;
-
-        dex_im(&mut cpu);
-
-        assert_eq!(cpu.index_register_x, 0x01);
-    }
-
-    #[test]
-    fn should_take_one_cycle() {
-        let memory = &RefCell::new(MemoryMock::default());
-        let mut cpu = CPU::new_nmos(memory);
-        cpu.index_register_x = 0x02;
-        cpu.cycle = 0;
-
-        dex_im(&mut cpu);
-
-        assert_eq!(cpu.cycle, 1);
+    fn should_decrement_x_register_in_two_cycles() {
+        assert_instruction! {
+            program: [0xCA],
+            setup: |cpu| cpu.index_register_x = 0x02,
+            cycles: 2,
+            registers: { index_register_x: 0x01 },
+            status: 0b00000000,
+        };
     }
 
     #[test]
     fn should_set_processor_status_of_x_register_after_decrement() {
-        let memory = &RefCell::new(MemoryMock::default());
-        let mut cpu = CPU::new_nmos(memory);
-        cpu.index_register_x = 0x01;
-
-        dex_im(&mut cpu);
-
-        assert_eq!(cpu.processor_status, 0b00000010);
+        assert_instruction! {
+            program: [0xCA],
+            setup: |cpu| cpu.index_register_x = 0x01,
+            status: 0b00000010,
+        };
     }
 }
 
 #[cfg(test)]
 mod dey_im {
-    use std::cell::RefCell;
-
-    use crate::cpu::{instructions::dey_im, tests::MemoryMock, CPU};
-
-    #[test]
-    fn should_decrement_y_register() {
-        let memory = &RefCell::new(MemoryMock::default());
-        let mut cpu = CPU::new_nmos(memory);
-        cpu.index_register_y = 0x02;
-
-        dey_im(&mut cpu);
-
-        assert_eq!(cpu.index_register_y, 0x01);
-    }
+    use crate::cpu::tests::assert_instruction;
 
     #[test]
-    fn should_take_one_cycle() {
-        let memory = &RefCell::new(MemoryMock::default());
-        let mut cpu = CPU::new_nmos(memory);
-        cpu.index_register_y = 0x02;
-        cpu.cycle = 0;
-
-        dey_im(&mut cpu);
-
-        assert_eq!(cpu.cycle, 1);
+    fn should_decrement_y_register_in_two_cycles() {
+        assert_instruction! {
+            program: [0x88],
+            setup: |cpu| cpu.index_register_y = 0x02,
+            cycles: 2,
+            registers: { index_register_y: 0x01 },
+            status: 0b00000000,
+        };
     }
 
     #[test]
     fn should_set_processor_status_of_y_register_after_decrement() {
-        let memory = &RefCell::new(MemoryMock::default());
-        let mut cpu = CPU::new_nmos(memory);
-        cpu.index_register_y = 0x01;
-
-        dey_im(&mut cpu);
-
-        assert_eq!(cpu.processor_status, 0b00000010);
+        assert_instruction! {
+            program: [0x88],
+            setup: |cpu| cpu.index_register_y = 0x01,
+            status: 0b00000010,
+        };
     }
 }
 
 #[cfg(test)]
 mod dec_zp {
-    use std::cell::RefCell;
-
-    use crate::cpu::{instructions::dec_zp, tests::MemoryMock, Byte, Word, CPU};
-
-    const VALUE: Byte = 0x02;
-    const ZERO_PAGE_ADDR: Byte = 0x03;
+    use crate::cpu::tests::assert_instruction;
 
     #[test]
-    fn should_decrement_value_stored_in_memory_at_zero_page_address() {
-        let memory = &RefCell::new(MemoryMock::new(&[ZERO_PAGE_ADDR, 0xFF, 0x00, VALUE]));
-        let mut cpu = CPU::new_nmos(memory);
-        cpu.program_counter = 0x00;
-
-        dec_zp(&mut cpu);
-
-        assert_eq!(memory.borrow()[ZERO_PAGE_ADDR as Word], 0x01);
-    }
-
-    #[test]
-    fn should_take_four_cycles() {
-        let memory = &RefCell::new(MemoryMock::new(&[ZERO_PAGE_ADDR, 0xFF, 0x00, VALUE]));
-        let mut cpu = CPU::new_nmos(memory);
-        cpu.program_counter = 0x00;
-        cpu.cycle = 0;
-
-        dec_zp(&mut cpu);
-
-        assert_eq!(cpu.cycle, 4);
+    fn should_decrement_value_stored_in_memory_at_zero_page_address_in_five_cycles() {
+        assert_instruction! {
+            program: [0xC6, 0x03, 0x00, 0x02],
+            cycles: 5,
+            memory: { 0x0003 => 0x01 },
+            status: 0b00000000,
+        };
     }
 
     #[test]
     fn should_set_processor_status_of_value_in_memory() {
-        const VALUE: Byte = 0x01;
-        let memory = &RefCell::new(MemoryMock::new(&[ZERO_PAGE_ADDR, 0xFF, 0x00, VALUE]));
-        let mut cpu = CPU::new_nmos(memory);
-        cpu.program_counter = 0x00;
-
-        dec_zp(&mut cpu);
-
-        assert_eq!(cpu.processor_status, 0b00000010);
+        assert_instruction! {
+            program: [0xC6, 0x03, 0x00, 0x01],
+            memory: { 0x0003 => 0x00 },
+            status: 0b00000010,
+        };
     }
 }
 
 #[cfg(test)]
 mod dec_zpx {
-    use std::cell::RefCell;
-
-    use crate::cpu::{instructions::dec_zpx, tests::MemoryMock, Byte, Word, CPU};
-
-    const VALUE: Byte = 0x09;
-    const ZERO_PAGE_ADDR: Byte = 0x01;
-    const ZERO_PAGE_ADDR_SUM_X: Word = 0x03;
+    use crate::cpu::tests::assert_instruction;
 
     #[test]
-    fn should_decrement_value_stored_in_memory_at_zero_page_address_summed_with_index_register_x() {
-        let memory = &RefCell::new(MemoryMock::new(&[ZERO_PAGE_ADDR, 0xFF, 0x00, VALUE]));
-        let mut cpu = CPU::new_nmos(memory);
-        cpu.program_counter = 0x00;
-        cpu.index_register_x = 0x02;
-
-        dec_zpx(&mut cpu);
-
-        assert_eq!(memory.borrow()[ZERO_PAGE_ADDR_SUM_X as Word], 0x08);
-    }
-
-    #[test]
-    fn should_take_five_cycles() {
-        let memory = &RefCell::new(MemoryMock::new(&[ZERO_PAGE_ADDR, 0xFF, 0x00, VALUE]));
-        let mut cpu = CPU::new_nmos(memory);
-        cpu.program_counter = 0x00;
-        cpu.index_register_x = 0x02;
-        cpu.cycle = 0;
-
-        dec_zpx(&mut cpu);
-
-        assert_eq!(cpu.cycle, 5);
+    fn should_decrement_value_stored_in_memory_at_zero_page_address_summed_with_index_register_x_in_six_cycles(
+    ) {
+        assert_instruction! {
+            program: [0xD6, 0x01, 0x00, 0x09],
+            setup: |cpu| cpu.index_register_x = 0x02,
+            cycles: 6,
+            memory: { 0x0003 => 0x08 },
+            status: 0b00000000,
+        };
     }
 
     #[test]
     fn should_set_processor_status_of_value_in_memory() {
-        const VALUE: Byte = 0x01;
-        let memory = &RefCell::new(MemoryMock::new(&[ZERO_PAGE_ADDR, 0xFF, 0x00, VALUE]));
-        let mut cpu = CPU::new_nmos(memory);
-        cpu.program_counter = 0x00;
-        cpu.index_register_x = 0x02;
-
-        dec_zpx(&mut cpu);
-
-        assert_eq!(cpu.processor_status, 0b00000010);
+        assert_instruction! {
+            program: [0xD6, 0x01, 0x00, 0x01],
+            setup: |cpu| cpu.index_register_x = 0x02,
+            memory: { 0x0003 => 0x00 },
+            status: 0b00000010,
+        };
     }
 }
 
 #[cfg(test)]
 mod dec_a {
-    use std::cell::RefCell;
-
-    use crate::cpu::{instructions::dec_a, tests::MemoryMock, Byte, Word, CPU};
-
-    const VALUE: Byte = 0x09;
-    const ADDR_LO: Byte = 0x04;
-    const ADDR_HI: Byte = 0x00;
-    const ADDR: Word = 0x0004;
+    use crate::cpu::tests::assert_instruction;
 
     #[test]
-    fn should_decrement_value_stored_in_memory_at_absolute_address() {
-        let memory = &RefCell::new(MemoryMock::new(&[ADDR_LO, ADDR_HI, 0x00, 0x00, VALUE]));
-        let mut cpu = CPU::new_nmos(memory);
-        cpu.program_counter = 0x00;
-
-        dec_a(&mut cpu);
-
-        assert_eq!(memory.borrow()[ADDR as Word], 0x08);
+    fn should_decrement_value_stored_in_memory_at_absolute_address_in_six_cycles() {
+        assert_instruction! {
+            program: [0xCE, 0x04, 0x00, 0x00, 0x09],
+            cycles: 6,
+            memory: { 0x0004 => 0x08 },
+            status: 0b00000000,
+        };
     }
 
     #[test]
-    fn should_take_five_cycles() {
-        let memory = &RefCell::new(MemoryMock::new(&[ADDR_LO, ADDR_HI, 0x00, 0x00, VALUE]));
-        let mut cpu = CPU::new_nmos(memory);
-        cpu.program_counter = 0x00;
-        cpu.cycle = 0;
+    fn should_set_processor_status_of_value_in_memory() {
+        assert_instruction! {
+            program: [0xCE, 0x04, 0x00, 0x00, 0x01],
+            memory: { 0x0004 => 0x00 },
+            status: 0b00000010,
+        };
+    }
+}
 
-        dec_a(&mut cpu);
+#[cfg(test)]
+mod dec_ax {
+    use crate::cpu::tests::assert_instruction;
 
-        assert_eq!(cpu.cycle, 5);
+    #[test]
+    fn should_decrement_value_stored_in_memory_at_absolute_address_offset_by_index_register_x_in_seven_cycles(
+    ) {
+        assert_instruction! {
+            program: [0xDE, 0x02, 0x00, 0x00, 0x09],
+            setup: |cpu| cpu.index_register_x = 0x02,
+            cycles: 7,
+            memory: { 0x0004 => 0x08 },
+            status: 0b00000000,
+        };
     }
 
     #[test]
     fn should_set_processor_status_of_value_in_memory() {
-        const VALUE: Byte = 0x01;
-        let memory = &RefCell::new(MemoryMock::new(&[ADDR_LO, ADDR_HI, 0x00, 0x00, VALUE]));
-        let mut cpu = CPU::new_nmos(memory);
-        cpu.program_counter = 0x00;
-
-        dec_a(&mut cpu);
-
-        assert_eq!(cpu.processor_status, 0b00000010);
+        assert_instruction! {
+            program: [0xDE, 0x02, 0x00, 0x00, 0x01],
+            setup: |cpu| cpu.index_register_x = 0x02,
+            memory: { 0x0004 => 0x00 },
+            status: 0b00000010,
+        };
     }
 }
 
 #[cfg(test)]
-mod dec_ax {
+mod accumulator {
     use std::cell::RefCell;
 
-    use crate::cpu::{instructions::dec_ax, tests::MemoryMock, Byte, Word, CPU};
-
-    const VALUE: Byte = 0x09;
-    const ADDR_LO: Byte = 0x02;
-    const ADDR_HI: Byte = 0x00;
-    const OFFSET: Byte = 0x02;
-    const ADDR_OFFSET_BY_X: Word = 0x0004;
+    use crate::cpu::{tests::MemoryMock, CPU};
 
     #[test]
-    fn should_decrement_value_stored_in_memory_at_absolute_address_offset_by_index_register_x() {
-        let memory = &RefCell::new(MemoryMock::new(&[ADDR_LO, ADDR_HI, 0x00, 0x00, VALUE]));
-        let mut cpu = CPU::new_nmos(memory);
-        cpu.program_counter = 0x00;
-        cpu.index_register_x = OFFSET;
-
-        dec_ax(&mut cpu);
-
-        assert_eq!(memory.borrow()[ADDR_OFFSET_BY_X], 0x08);
-    }
-
-    #[test]
-    fn should_take_six_cycles() {
-        let memory = &RefCell::new(MemoryMock::new(&[ADDR_LO, ADDR_HI, 0x00, 0x00, VALUE]));
-        let mut cpu = CPU::new_nmos(memory);
-        cpu.program_counter = 0x00;
-        cpu.index_register_x = OFFSET;
+    fn should_increment_accumulator_in_two_cycles() {
+        let memory = RefCell::new(MemoryMock::new(&[0x1A])); // INC A
+        let mut cpu = CPU::new_cmos(&memory);
         cpu.cycle = 0;
+        cpu.processor_status.set(0);
+        cpu.program_counter = 0x0000;
+        cpu.accumulator = 0x7F;
 
-        dec_ax(&mut cpu);
+        cpu.execute_next_instruction();
 
-        assert_eq!(cpu.cycle, 6);
+        assert_eq!(cpu.cycle, 2);
+        assert_eq!(cpu.accumulator, 0x80);
+        assert!(cpu.processor_status.get_negative_flag());
     }
 
     #[test]
-    fn should_set_processor_status_of_value_in_memory() {
-        const VALUE: Byte = 0x01;
-        let memory = &RefCell::new(MemoryMock::new(&[ADDR_LO, ADDR_HI, 0x00, 0x00, VALUE]));
-        let mut cpu = CPU::new_nmos(memory);
-        cpu.program_counter = 0x00;
-        cpu.index_register_x = OFFSET;
+    fn should_decrement_accumulator_in_two_cycles() {
+        let memory = RefCell::new(MemoryMock::new(&[0x3A])); // DEC A
+        let mut cpu = CPU::new_cmos(&memory);
+        cpu.cycle = 0;
+        cpu.processor_status.set(0);
+        cpu.program_counter = 0x0000;
+        cpu.accumulator = 0x01;
 
-        dec_ax(&mut cpu);
+        cpu.execute_next_instruction();
 
-        assert_eq!(cpu.processor_status, 0b00000010);
+        assert_eq!(cpu.cycle, 2);
+        assert_eq!(cpu.accumulator, 0x00);
+        assert!(cpu.processor_status.get_zero_flag());
     }
 }
@@ -1,37 +1,98 @@
-use crate::cpu::{AddressingMode, CPU};
+use crate::{
+    consts::Word,
+    cpu::{call_trace::CallKind, AddressingMode, CPU},
+    memory::Bus,
+};
 
-pub fn jsr_a(cpu: &mut CPU) {
-    let jump_addr = match cpu.get_address(AddressingMode::Absolute) {
-        Some(address) => address,
-        None => panic!("couldn't fetch address during a jsr"),
-    };
+// the high byte of the target is fetched only after the return address, which points at that
+// very byte, has been pushed
+pub fn jsr_a<M: Bus>(cpu: &mut CPU<M>) {
+    let jump_addr_lo = cpu.access_memory(cpu.program_counter);
+    cpu.increment_program_counter();
+    cpu.dummy_stack_read();
 
-    cpu.push_word_to_stack(cpu.program_counter - 1);
-    cpu.program_counter = jump_addr;
+    let return_addr = cpu.program_counter;
+    cpu.push_word_to_stack(return_addr);
+    let jump_addr_hi = cpu.access_memory(cpu.program_counter);
     cpu.tick();
+
+    let jump_addr = Word::from_le_bytes([jump_addr_lo, jump_addr_hi]);
+    cpu.program_counter = jump_addr;
+    cpu.trace_call(CallKind::Subroutine, return_addr.wrapping_sub(2), jump_addr);
 }
 
-pub fn rts(cpu: &mut CPU) {
+pub fn rts<M: Bus>(cpu: &mut CPU<M>) {
     cpu.dummy_fetch();
+    cpu.dummy_stack_read();
     cpu.program_counter = cpu.pop_word_from_stack();
+    cpu.dummy_read(cpu.program_counter);
+    cpu.increment_program_counter();
+    cpu.trace_return(CallKind::Subroutine);
+}
+
+// 65CE02 indirect calls resolve the target first, then push the address of their last byte
+fn jsr_indirect<M: Bus>(cpu: &mut CPU<M>, addr_mode: AddressingMode) {
+    let call_addr = cpu.program_counter.wrapping_sub(1);
+    let target = match cpu.get_address(addr_mode) {
+        Some(address) => address,
+        None => panic!("jsr used with incorrect addressing mode"),
+    };
+    cpu.push_word_to_stack(cpu.program_counter.wrapping_sub(1));
+    cpu.program_counter = target;
+    cpu.trace_call(CallKind::Subroutine, call_addr, target);
+}
+
+pub fn jsr_in<M: Bus>(cpu: &mut CPU<M>) {
+    jsr_indirect(cpu, AddressingMode::Indirect);
+}
+
+pub fn jsr_ainx<M: Bus>(cpu: &mut CPU<M>) {
+    jsr_indirect(cpu, AddressingMode::AbsoluteIndexIndirectX);
+}
+
+// BSR calls with a word offset counted like the word branches, returning through RTS
+pub fn bsr_w<M: Bus>(cpu: &mut CPU<M>) {
+    let call_addr = cpu.program_counter.wrapping_sub(1);
+    let offset = cpu.fetch_address();
+    let return_addr = cpu.program_counter.wrapping_sub(1);
+    cpu.push_word_to_stack(return_addr);
+
+    let target = return_addr.wrapping_add(offset);
+    cpu.idle_cycle();
+    cpu.program_counter = target;
+    cpu.trace_call(CallKind::Subroutine, call_addr, target);
+}
+
+// RTS # also drops the given number of argument bytes from the stack
+pub fn rts_im<M: Bus>(cpu: &mut CPU<M>) {
+    let argument_bytes = cpu.access_memory(cpu.program_counter);
     cpu.tick();
+    cpu.dummy_stack_read();
+    cpu.program_counter = cpu.pop_word_from_stack();
+    cpu.move_stack_pointer(argument_bytes as i16);
+    cpu.dummy_read(cpu.program_counter);
     cpu.increment_program_counter();
+    cpu.trace_return(CallKind::Subroutine);
 }
 
-fn jmp(cpu: &mut CPU, addr_mode: AddressingMode) {
+fn jmp<M: Bus>(cpu: &mut CPU<M>, addr_mode: AddressingMode) {
     match cpu.get_address(addr_mode) {
         Some(address) => cpu.program_counter = address,
         None => panic!("jmp used with incorrect addressing mode"),
     }
 }
 
-pub fn jmp_a(cpu: &mut CPU) {
+pub fn jmp_a<M: Bus>(cpu: &mut CPU<M>) {
     jmp(cpu, AddressingMode::Absolute);
 }
 
-pub fn jmp_in(cpu: &mut CPU) {
+pub fn jmp_in<M: Bus>(cpu: &mut CPU<M>) {
     jmp(cpu, AddressingMode::Indirect);
 }
 
+pub fn jmp_ainx<M: Bus>(cpu: &mut CPU<M>) {
+    jmp(cpu, AddressingMode::AbsoluteIndexIndirectX);
+}
+
 #[cfg(test)]
 mod tests;
@@ -171,6 +171,25 @@ mod jmp_in {
 
             assert_eq!(cpu.cycle, 4);
         }
+
+        #[test]
+        fn should_read_target_high_byte_from_start_of_the_same_page_when_pointer_is_at_page_end() {
+            let mut program = [0x00; 512];
+            program[0x0000] = 0x09;
+            program[0x0001] = 0xFF;
+            program[0x0002] = 0x00;
+            program[0x00FF] = 0xA5;
+            program[0x0100] = 0xCC;
+            let memory = &RefCell::new(MemoryMock::new(&program));
+            let mut cpu = CPU::new_nmos(memory);
+            cpu.program_counter = 0x01;
+            cpu.cycle = 0;
+
+            jmp_in(&mut cpu);
+
+            assert_eq!(cpu.program_counter, 0x09A5);
+            assert_eq!(cpu.cycle, 4);
+        }
     }
 
     #[cfg(test)]
@@ -190,5 +209,47 @@ mod jmp_in {
 
             assert_eq!(cpu.cycle, 5);
         }
+
+        #[test]
+        fn should_read_target_high_byte_from_next_page_when_pointer_is_at_page_end() {
+            let mut program = [0x00; 512];
+            program[0x0000] = 0x09;
+            program[0x0001] = 0xFF;
+            program[0x0002] = 0x00;
+            program[0x00FF] = 0xA5;
+            program[0x0100] = 0xCC;
+            let memory = &RefCell::new(MemoryMock::new(&program));
+            let mut cpu = CPU::new_cmos(memory);
+            cpu.program_counter = 0x01;
+            cpu.cycle = 0;
+
+            jmp_in(&mut cpu);
+
+            assert_eq!(cpu.program_counter, 0xCCA5);
+            assert_eq!(cpu.cycle, 5);
+        }
+    }
+}
+
+#[cfg(test)]
+mod jmp_ainx {
+    use std::cell::RefCell;
+
+    use crate::cpu::{tests::MemoryMock, CPU};
+
+    #[test]
+    fn should_jump_through_indexed_pointer_in_six_cycles() {
+        let memory = &RefCell::new(MemoryMock::new(&[0x7C, 0xFE, 0x01])); // JMP ($01FE,X)
+        memory.borrow_mut()[0x0200] = 0x34;
+        memory.borrow_mut()[0x0201] = 0x12;
+        let mut cpu = CPU::new_cmos(memory);
+        cpu.cycle = 0;
+        cpu.program_counter = 0x0000;
+        cpu.index_register_x = 0x02;
+
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.program_counter, 0x1234);
+        assert_eq!(cpu.cycle, 6);
     }
 }
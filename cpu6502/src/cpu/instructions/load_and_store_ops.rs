@@ -1,6 +1,7 @@
 use crate::cpu::{AddressingMode, Registers, CPU};
+use crate::memory::Bus;
 
-fn ld(cpu: &mut CPU, addr_mode: AddressingMode, register: Registers) {
+fn ld<M: Bus>(cpu: &mut CPU<M>, addr_mode: AddressingMode, register: Registers) {
     let value = match cpu.read_memory(addr_mode) {
         Some(value) => value,
         None => panic!("ld used with incorrect address mode"),
@@ -9,79 +10,142 @@ fn ld(cpu: &mut CPU, addr_mode: AddressingMode, register: Registers) {
     cpu.set_register(register, value);
 }
 
-pub fn lda_im(cpu: &mut CPU) {
+pub fn lda_im<M: Bus>(cpu: &mut CPU<M>) {
     ld(cpu, AddressingMode::Immediate, Registers::Accumulator);
 }
 
-pub fn lda_zp(cpu: &mut CPU) {
+pub fn lda_zp<M: Bus>(cpu: &mut CPU<M>) {
     ld(cpu, AddressingMode::ZeroPage, Registers::Accumulator);
 }
 
-pub fn lda_zpx(cpu: &mut CPU) {
+pub fn lda_zpx<M: Bus>(cpu: &mut CPU<M>) {
     ld(cpu, AddressingMode::ZeroPageX, Registers::Accumulator);
 }
 
-pub fn lda_a(cpu: &mut CPU) {
+pub fn lda_a<M: Bus>(cpu: &mut CPU<M>) {
     ld(cpu, AddressingMode::Absolute, Registers::Accumulator);
 }
 
-pub fn lda_ax(cpu: &mut CPU) {
+pub fn lda_ax<M: Bus>(cpu: &mut CPU<M>) {
     ld(cpu, AddressingMode::AbsoluteX, Registers::Accumulator);
 }
 
-pub fn lda_ay(cpu: &mut CPU) {
+pub fn lda_ay<M: Bus>(cpu: &mut CPU<M>) {
     ld(cpu, AddressingMode::AbsoluteY, Registers::Accumulator);
 }
 
-pub fn lda_inx(cpu: &mut CPU) {
+pub fn lda_inx<M: Bus>(cpu: &mut CPU<M>) {
     ld(cpu, AddressingMode::IndexIndirectX, Registers::Accumulator);
 }
 
-pub fn lda_iny(cpu: &mut CPU) {
+pub fn lda_iny<M: Bus>(cpu: &mut CPU<M>) {
     ld(cpu, AddressingMode::IndirectIndexY, Registers::Accumulator);
 }
 
-pub fn ldy_im(cpu: &mut CPU) {
+pub fn lda_izp<M: Bus>(cpu: &mut CPU<M>) {
+    ld(
+        cpu,
+        AddressingMode::ZeroPageIndirect,
+        Registers::Accumulator,
+    );
+}
+
+pub fn ldy_im<M: Bus>(cpu: &mut CPU<M>) {
     ld(cpu, AddressingMode::Immediate, Registers::IndexY);
 }
 
-pub fn ldy_zp(cpu: &mut CPU) {
+pub fn ldy_zp<M: Bus>(cpu: &mut CPU<M>) {
     ld(cpu, AddressingMode::ZeroPage, Registers::IndexY);
 }
 
-pub fn ldy_zpx(cpu: &mut CPU) {
+pub fn ldy_zpx<M: Bus>(cpu: &mut CPU<M>) {
     ld(cpu, AddressingMode::ZeroPageX, Registers::IndexY);
 }
 
-pub fn ldy_a(cpu: &mut CPU) {
+pub fn ldy_a<M: Bus>(cpu: &mut CPU<M>) {
     ld(cpu, AddressingMode::Absolute, Registers::IndexY);
 }
 
-pub fn ldy_ax(cpu: &mut CPU) {
+pub fn ldy_ax<M: Bus>(cpu: &mut CPU<M>) {
     ld(cpu, AddressingMode::AbsoluteX, Registers::IndexY);
 }
 
-pub fn ldx_im(cpu: &mut CPU) {
+pub fn ldx_im<M: Bus>(cpu: &mut CPU<M>) {
     ld(cpu, AddressingMode::Immediate, Registers::IndexX);
 }
 
-pub fn ldx_zp(cpu: &mut CPU) {
+pub fn ldx_zp<M: Bus>(cpu: &mut CPU<M>) {
     ld(cpu, AddressingMode::ZeroPage, Registers::IndexX);
 }
 
-pub fn ldx_zpy(cpu: &mut CPU) {
+pub fn ldx_zpy<M: Bus>(cpu: &mut CPU<M>) {
     ld(cpu, AddressingMode::ZeroPageY, Registers::IndexX);
 }
 
-pub fn ldx_a(cpu: &mut CPU) {
+pub fn ldx_a<M: Bus>(cpu: &mut CPU<M>) {
     ld(cpu, AddressingMode::Absolute, Registers::IndexX);
 }
 
-pub fn ldx_ay(cpu: &mut CPU) {
+pub fn ldx_ay<M: Bus>(cpu: &mut CPU<M>) {
     ld(cpu, AddressingMode::AbsoluteY, Registers::IndexX);
 }
 
-pub fn store(cpu: &mut CPU, addr_mode: AddressingMode, register: Registers) {
+pub fn ldz_im<M: Bus>(cpu: &mut CPU<M>) {
+    ld(cpu, AddressingMode::Immediate, Registers::IndexZ);
+}
+
+pub fn ldz_a<M: Bus>(cpu: &mut CPU<M>) {
+    ld(cpu, AddressingMode::Absolute, Registers::IndexZ);
+}
+
+pub fn ldz_ax<M: Bus>(cpu: &mut CPU<M>) {
+    ld(cpu, AddressingMode::AbsoluteX, Registers::IndexZ);
+}
+
+pub fn lda_isy<M: Bus>(cpu: &mut CPU<M>) {
+    ld(
+        cpu,
+        AddressingMode::StackIndirectIndexY,
+        Registers::Accumulator,
+    );
+}
+
+// undocumented, loads the same value into A and X
+fn lax<M: Bus>(cpu: &mut CPU<M>, addr_mode: AddressingMode) {
+    let value = match cpu.read_memory(addr_mode) {
+        Some(value) => value,
+        None => panic!("lax used with incorrect address mode"),
+    };
+
+    cpu.set_register(Registers::Accumulator, value);
+    cpu.set_register(Registers::IndexX, value);
+}
+
+pub fn lax_zp<M: Bus>(cpu: &mut CPU<M>) {
+    lax(cpu, AddressingMode::ZeroPage);
+}
+
+pub fn lax_zpy<M: Bus>(cpu: &mut CPU<M>) {
+    lax(cpu, AddressingMode::ZeroPageY);
+}
+
+pub fn lax_a<M: Bus>(cpu: &mut CPU<M>) {
+    lax(cpu, AddressingMode::Absolute);
+}
+
+pub fn lax_ay<M: Bus>(cpu: &mut CPU<M>) {
+    lax(cpu, AddressingMode::AbsoluteY);
+}
+
+pub fn lax_inx<M: Bus>(cpu: &mut CPU<M>) {
+    lax(cpu, AddressingMode::IndexIndirectX);
+}
+
+pub fn lax_iny<M: Bus>(cpu: &mut CPU<M>) {
+    lax(cpu, AddressingMode::IndirectIndexY);
+}
+
+pub fn store<M: Bus>(cpu: &mut CPU<M>, addr_mode: AddressingMode, register: Registers) {
     let value = cpu.get_register(register);
     match cpu.write_memory(addr_mode, value) {
         Some(()) => (),
@@ -89,57 +153,130 @@ pub fn store(cpu: &mut CPU, addr_mode: AddressingMode, register: Registers) {
     }
 }
 
-pub fn sta_zp(cpu: &mut CPU) {
+pub fn sta_zp<M: Bus>(cpu: &mut CPU<M>) {
     store(cpu, AddressingMode::ZeroPage, Registers::Accumulator);
 }
 
-pub fn sta_zpx(cpu: &mut CPU) {
+pub fn sta_zpx<M: Bus>(cpu: &mut CPU<M>) {
     store(cpu, AddressingMode::ZeroPageX, Registers::Accumulator);
 }
 
-pub fn sta_a(cpu: &mut CPU) {
+pub fn sta_a<M: Bus>(cpu: &mut CPU<M>) {
     store(cpu, AddressingMode::Absolute, Registers::Accumulator);
 }
 
-pub fn sta_ax(cpu: &mut CPU) {
+pub fn sta_ax<M: Bus>(cpu: &mut CPU<M>) {
     store(cpu, AddressingMode::AbsoluteX, Registers::Accumulator);
 }
 
-pub fn sta_ay(cpu: &mut CPU) {
+pub fn sta_ay<M: Bus>(cpu: &mut CPU<M>) {
     store(cpu, AddressingMode::AbsoluteY, Registers::Accumulator);
 }
 
-pub fn sta_inx(cpu: &mut CPU) {
+pub fn sta_inx<M: Bus>(cpu: &mut CPU<M>) {
     store(cpu, AddressingMode::IndexIndirectX, Registers::Accumulator);
 }
 
-pub fn sta_iny(cpu: &mut CPU) {
+pub fn sta_iny<M: Bus>(cpu: &mut CPU<M>) {
     store(cpu, AddressingMode::IndirectIndexY, Registers::Accumulator);
 }
 
-pub fn stx_zp(cpu: &mut CPU) {
+pub fn sta_izp<M: Bus>(cpu: &mut CPU<M>) {
+    store(
+        cpu,
+        AddressingMode::ZeroPageIndirect,
+        Registers::Accumulator,
+    );
+}
+
+pub fn sta_isy<M: Bus>(cpu: &mut CPU<M>) {
+    store(
+        cpu,
+        AddressingMode::StackIndirectIndexY,
+        Registers::Accumulator,
+    );
+}
+
+pub fn stx_zp<M: Bus>(cpu: &mut CPU<M>) {
     store(cpu, AddressingMode::ZeroPage, Registers::IndexX);
 }
 
-pub fn stx_zpy(cpu: &mut CPU) {
+pub fn stx_zpy<M: Bus>(cpu: &mut CPU<M>) {
     store(cpu, AddressingMode::ZeroPageY, Registers::IndexX);
 }
 
-pub fn stx_a(cpu: &mut CPU) {
+pub fn stx_a<M: Bus>(cpu: &mut CPU<M>) {
     store(cpu, AddressingMode::Absolute, Registers::IndexX);
 }
 
-pub fn sty_zp(cpu: &mut CPU) {
+pub fn stx_ay<M: Bus>(cpu: &mut CPU<M>) {
+    store(cpu, AddressingMode::AbsoluteY, Registers::IndexX);
+}
+
+pub fn sty_zp<M: Bus>(cpu: &mut CPU<M>) {
     store(cpu, AddressingMode::ZeroPage, Registers::IndexY);
 }
 
-pub fn sty_zpx(cpu: &mut CPU) {
+pub fn sty_zpx<M: Bus>(cpu: &mut CPU<M>) {
     store(cpu, AddressingMode::ZeroPageX, Registers::IndexY);
 }
 
-pub fn sty_a(cpu: &mut CPU) {
+pub fn sty_a<M: Bus>(cpu: &mut CPU<M>) {
     store(cpu, AddressingMode::Absolute, Registers::IndexY);
 }
 
+pub fn sty_ax<M: Bus>(cpu: &mut CPU<M>) {
+    store(cpu, AddressingMode::AbsoluteX, Registers::IndexY);
+}
+
+// undocumented, stores A AND X without affecting flags
+// the 65CE02 stores its Z register here, which is zero on every other chip
+fn stz<M: Bus>(cpu: &mut CPU<M>, addr_mode: AddressingMode) {
+    match cpu.write_memory(addr_mode, cpu.index_register_z) {
+        Some(()) => (),
+        None => panic!("stz used with incorrect address mode"),
+    }
+}
+
+pub fn stz_zp<M: Bus>(cpu: &mut CPU<M>) {
+    stz(cpu, AddressingMode::ZeroPage);
+}
+
+pub fn stz_zpx<M: Bus>(cpu: &mut CPU<M>) {
+    stz(cpu, AddressingMode::ZeroPageX);
+}
+
+pub fn stz_a<M: Bus>(cpu: &mut CPU<M>) {
+    stz(cpu, AddressingMode::Absolute);
+}
+
+pub fn stz_ax<M: Bus>(cpu: &mut CPU<M>) {
+    stz(cpu, AddressingMode::AbsoluteX);
+}
+
+fn sax<M: Bus>(cpu: &mut CPU<M>, addr_mode: AddressingMode) {
+    let value = cpu.get_register(Registers::Accumulator) & cpu.get_register(Registers::IndexX);
+    match cpu.write_memory(addr_mode, value) {
+        Some(()) => (),
+        None => panic!("sax used with incorrect address mode"),
+    }
+}
+
+pub fn sax_zp<M: Bus>(cpu: &mut CPU<M>) {
+    sax(cpu, AddressingMode::ZeroPage);
+}
+
+pub fn sax_zpy<M: Bus>(cpu: &mut CPU<M>) {
+    sax(cpu, AddressingMode::ZeroPageY);
+}
+
+pub fn sax_a<M: Bus>(cpu: &mut CPU<M>) {
+    sax(cpu, AddressingMode::Absolute);
+}
+
+pub fn sax_inx<M: Bus>(cpu: &mut CPU<M>) {
+    sax(cpu, AddressingMode::IndexIndirectX);
+}
+
 #[cfg(test)]
 mod tests;
@@ -22,6 +22,7 @@ mod lda {
         fn should_set_load_accumulator_processor_status() {
             let memory = &RefCell::new(MemoryMock::default());
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.program_counter = 0x04;
 
             lda_im(&mut cpu);
@@ -65,6 +66,7 @@ mod lda {
         fn should_set_load_accumulator_processor_status() {
             let memory = &RefCell::new(MemoryMock::new(&[0x03, 0xFF, 0x00, 0xFF]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.program_counter = 0x00;
 
             lda_zp(&mut cpu);
@@ -121,6 +123,7 @@ mod lda {
         fn should_set_load_accumulator_processor_status() {
             let memory = &RefCell::new(MemoryMock::new(&[0x01, 0x00, 0x00, 0xFF]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.index_register_x = 0x02;
             cpu.program_counter = 0x00;
 
@@ -166,6 +169,7 @@ mod lda {
         fn should_set_load_accumulator_processor_status() {
             let memory = &RefCell::new(MemoryMock::new(&[0x03, 0x00, 0x00, 0xFF]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.program_counter = 0x00;
 
             lda_a(&mut cpu);
@@ -222,6 +226,7 @@ mod lda {
                 ADDRESS_LO, ADDRESS_HI, 0x45, 0xAF, 0xDD, VALUE,
             ]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.program_counter = 0x00;
             cpu.index_register_x = 0x02;
 
@@ -302,6 +307,7 @@ mod lda {
                 ADDRESS_LO, ADDRESS_HI, 0x45, 0xAF, 0xDD, VALUE,
             ]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.program_counter = 0x00;
             cpu.index_register_y = 0x02;
 
@@ -392,6 +398,7 @@ mod lda {
                 VALUE,
             ]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.index_register_y = 0x02;
             cpu.program_counter = 0x00;
 
@@ -466,6 +473,7 @@ mod ldx {
         fn should_set_load_index_register_x_processor_status() {
             let memory = &RefCell::new(MemoryMock::default());
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.program_counter = 0x04;
 
             ldx_im(&mut cpu);
@@ -509,6 +517,7 @@ mod ldx {
         fn should_set_load_index_register_x_processor_status() {
             let memory = &RefCell::new(MemoryMock::new(&[0x03, 0xFF, 0x00, 0xFF]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.program_counter = 0x00;
 
             ldx_zp(&mut cpu);
@@ -565,6 +574,7 @@ mod ldx {
         fn should_set_load_index_register_x_processor_status() {
             let memory = &RefCell::new(MemoryMock::new(&[0x01, 0x00, 0x00, 0xFF]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.index_register_y = 0x02;
             cpu.program_counter = 0x00;
 
@@ -610,6 +620,7 @@ mod ldx {
         fn should_set_load_index_register_x_processor_status() {
             let memory = &RefCell::new(MemoryMock::new(&[0x03, 0x00, 0x00, 0xFF]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.program_counter = 0x00;
 
             ldx_a(&mut cpu);
@@ -666,6 +677,7 @@ mod ldx {
                 ADDRESS_LO, ADDRESS_HI, 0x45, 0xAF, 0xDD, VALUE,
             ]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.program_counter = 0x00;
             cpu.index_register_y = 0x02;
 
@@ -735,6 +747,7 @@ mod ldy {
         fn should_set_load_index_register_y_processor_status() {
             let memory = &RefCell::new(MemoryMock::default());
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.program_counter = 0x04;
 
             ldy_im(&mut cpu);
@@ -778,6 +791,7 @@ mod ldy {
         fn should_set_load_index_register_y_processor_status() {
             let memory = &RefCell::new(MemoryMock::new(&[0x03, 0xFF, 0x00, 0xFF]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.program_counter = 0x00;
 
             ldy_zp(&mut cpu);
@@ -834,6 +848,7 @@ mod ldy {
         fn should_set_load_index_register_y_processor_status() {
             let memory = &RefCell::new(MemoryMock::new(&[0x01, 0x00, 0x00, 0xFF]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.index_register_x = 0x02;
             cpu.program_counter = 0x00;
 
@@ -879,6 +894,7 @@ mod ldy {
         fn should_set_load_index_register_y_processor_status() {
             let memory = &RefCell::new(MemoryMock::new(&[0x03, 0x00, 0x00, 0xFF]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.program_counter = 0x00;
 
             ldy_a(&mut cpu);
@@ -935,6 +951,7 @@ mod ldy {
                 ADDRESS_LO, ADDRESS_HI, 0x45, 0xAF, 0xDD, VALUE,
             ]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.program_counter = 0x00;
             cpu.index_register_x = 0x02;
 
@@ -1482,3 +1499,157 @@ mod sty_a {
         assert_eq!(cpu.cycle, 3);
     }
 }
+
+#[cfg(test)]
+mod lax {
+    use crate::cpu::tests::{assert_cycles, assert_instruction};
+
+    #[test]
+    fn should_load_value_into_accumulator_and_x_register() {
+        assert_instruction! {
+            program: [0xA7, 0x02, 0x85], // LAX $02
+            cycles: 3,
+            registers: { accumulator: 0x85, index_register_x: 0x85 },
+            status: 0b10000000,
+        };
+    }
+
+    #[test]
+    fn should_set_zero_flag_for_zero_value() {
+        assert_instruction! {
+            program: [0xAF, 0x03, 0x00, 0x00], // LAX $0003
+            setup: |cpu| cpu.accumulator = 0x01,
+            cycles: 4,
+            registers: { accumulator: 0x00, index_register_x: 0x00 },
+            status: 0b00000010,
+        };
+    }
+
+    #[test]
+    fn should_take_datasheet_cycles_in_every_addressing_mode() {
+        assert_cycles!([0xB7, 0x02], |cpu| cpu.index_register_y = 0x01, 4); // LAX $02,Y
+        assert_cycles!([0xBF, 0x00, 0x01], |cpu| cpu.index_register_y = 0x01, 4); // LAX $0100,Y
+        assert_cycles!([0xBF, 0xFF, 0x00], |cpu| cpu.index_register_y = 0x01, 5); // LAX $00FF,Y
+        assert_cycles!([0xA3, 0x02], 6); // LAX ($02,X)
+        assert_cycles!([0xB3, 0x02], |cpu| cpu.index_register_y = 0x01, 5); // LAX ($02),Y
+    }
+
+    #[test]
+    fn should_load_through_indirect_indexed_address() {
+        assert_instruction! {
+            program: [0xB3, 0x02, 0x04, 0x00, 0x00, 0x7F], // LAX ($02),Y
+            setup: |cpu| cpu.index_register_y = 0x01,
+            cycles: 5,
+            registers: { accumulator: 0x7F, index_register_x: 0x7F },
+            status: 0b00000000,
+        };
+    }
+}
+
+#[cfg(test)]
+mod sax {
+    use crate::cpu::tests::{assert_cycles, assert_instruction};
+
+    #[test]
+    fn should_store_accumulator_and_x_register_without_changing_flags() {
+        assert_instruction! {
+            program: [0x87, 0x10], // SAX $10
+            setup: |cpu| {
+                cpu.accumulator = 0xF0;
+                cpu.index_register_x = 0x3C;
+            },
+            cycles: 3,
+            memory: { 0x0010 => 0x30 },
+            status: 0b00000000,
+        };
+    }
+
+    #[test]
+    fn should_store_at_zero_page_address_offset_by_y_register() {
+        assert_instruction! {
+            program: [0x97, 0x10], // SAX $10,Y
+            setup: |cpu| {
+                cpu.accumulator = 0xFF;
+                cpu.index_register_x = 0x81;
+                cpu.index_register_y = 0x02;
+            },
+            cycles: 4,
+            memory: { 0x0012 => 0x81 },
+        };
+    }
+
+    #[test]
+    fn should_take_datasheet_cycles_in_every_addressing_mode() {
+        assert_cycles!([0x8F, 0x00, 0x02], 4); // SAX $0200
+        assert_cycles!([0x83, 0x02], 6); // SAX ($02,X)
+    }
+}
+
+#[cfg(test)]
+mod stz {
+    use crate::cpu::tests::assert_instruction;
+
+    #[test]
+    fn should_store_zero_in_every_addressing_mode() {
+        assert_instruction! {
+            variant: CMOS,
+            program: [0x64, 0x11], // STZ $11
+            setup: |cpu| cpu.get_memory().borrow_mut()[0x0011] = 0xFF,
+            cycles: 3,
+            memory: { 0x0011 => 0x00 },
+        }
+        assert_instruction! {
+            variant: CMOS,
+            program: [0x74, 0x10], // STZ $10,X
+            setup: |cpu| {
+                cpu.index_register_x = 0x01;
+                cpu.get_memory().borrow_mut()[0x0011] = 0xFF;
+            },
+            cycles: 4,
+            memory: { 0x0011 => 0x00 },
+        }
+        assert_instruction! {
+            variant: CMOS,
+            program: [0x9C, 0x10, 0x02], // STZ $0210
+            setup: |cpu| cpu.get_memory().borrow_mut()[0x0210] = 0xFF,
+            cycles: 4,
+            memory: { 0x0210 => 0x00 },
+        }
+        assert_instruction! {
+            variant: CMOS,
+            program: [0x9E, 0x00, 0x02], // STZ $0200,X
+            setup: |cpu| {
+                cpu.index_register_x = 0x10;
+                cpu.get_memory().borrow_mut()[0x0210] = 0xFF;
+            },
+            cycles: 5,
+            memory: { 0x0210 => 0x00 },
+        }
+        assert_instruction! {
+            variant: CMOS,
+            program: [0x9E, 0xF1, 0x01], // STZ $01F1,X
+            setup: |cpu| {
+                cpu.index_register_x = 0x1F;
+                cpu.get_memory().borrow_mut()[0x0210] = 0xFF;
+            },
+            cycles: 5,
+            memory: { 0x0210 => 0x00 },
+        }
+    }
+}
+
+#[cfg(test)]
+mod indexed_store_timing {
+    use crate::cpu::tests::assert_cycles;
+
+    #[test]
+    fn should_not_spend_extra_cycle_on_page_crossing() {
+        assert_cycles!([0x9D, 0xFF, 0x00], |cpu| cpu.index_register_x = 0x01, 5); // STA $00FF,X
+        assert_cycles!([0x99, 0xFF, 0x00], |cpu| cpu.index_register_y = 0x01, 5); // STA $00FF,Y
+        assert_cycles!(
+            [0x91, 0x02, 0xFF, 0x00],
+            |cpu| cpu.index_register_y = 0x01,
+            6
+        ); // STA ($02),Y
+    }
+}
@@ -1,6 +1,7 @@
 use crate::cpu::{AddressingMode, Registers, CPU};
+use crate::memory::Bus;
 
-pub fn and(cpu: &mut CPU, addr_mode: AddressingMode) {
+pub fn and<M: Bus>(cpu: &mut CPU<M>, addr_mode: AddressingMode) {
     let value = match cpu.read_memory(addr_mode) {
         Some(value) => value,
         None => panic!("and used with incorrect addressing mode"),
@@ -11,39 +12,43 @@ pub fn and(cpu: &mut CPU, addr_mode: AddressingMode) {
     cpu.set_register(Registers::Accumulator, result_value);
 }
 
-pub fn and_im(cpu: &mut CPU) {
+pub fn and_im<M: Bus>(cpu: &mut CPU<M>) {
     and(cpu, AddressingMode::Immediate);
 }
 
-pub fn and_zp(cpu: &mut CPU) {
+pub fn and_zp<M: Bus>(cpu: &mut CPU<M>) {
     and(cpu, AddressingMode::ZeroPage);
 }
 
-pub fn and_zpx(cpu: &mut CPU) {
+pub fn and_zpx<M: Bus>(cpu: &mut CPU<M>) {
     and(cpu, AddressingMode::ZeroPageX);
 }
 
-pub fn and_a(cpu: &mut CPU) {
+pub fn and_a<M: Bus>(cpu: &mut CPU<M>) {
     and(cpu, AddressingMode::Absolute);
 }
 
-pub fn and_ax(cpu: &mut CPU) {
+pub fn and_ax<M: Bus>(cpu: &mut CPU<M>) {
     and(cpu, AddressingMode::AbsoluteX);
 }
 
-pub fn and_ay(cpu: &mut CPU) {
+pub fn and_ay<M: Bus>(cpu: &mut CPU<M>) {
     and(cpu, AddressingMode::AbsoluteY);
 }
 
-pub fn and_inx(cpu: &mut CPU) {
+pub fn and_inx<M: Bus>(cpu: &mut CPU<M>) {
     and(cpu, AddressingMode::IndexIndirectX);
 }
 
-pub fn and_iny(cpu: &mut CPU) {
+pub fn and_iny<M: Bus>(cpu: &mut CPU<M>) {
     and(cpu, AddressingMode::IndirectIndexY);
 }
 
-pub fn eor(cpu: &mut CPU, addr_mode: AddressingMode) {
+pub fn and_izp<M: Bus>(cpu: &mut CPU<M>) {
+    and(cpu, AddressingMode::ZeroPageIndirect);
+}
+
+pub fn eor<M: Bus>(cpu: &mut CPU<M>, addr_mode: AddressingMode) {
     let value = match cpu.read_memory(addr_mode) {
         Some(value) => value,
         None => panic!("eor used with incorrect addressing mode"),
@@ -54,39 +59,43 @@ pub fn eor(cpu: &mut CPU, addr_mode: AddressingMode) {
     cpu.set_register(Registers::Accumulator, result_value);
 }
 
-pub fn eor_im(cpu: &mut CPU) {
+pub fn eor_im<M: Bus>(cpu: &mut CPU<M>) {
     eor(cpu, AddressingMode::Immediate);
 }
 
-pub fn eor_zp(cpu: &mut CPU) {
+pub fn eor_zp<M: Bus>(cpu: &mut CPU<M>) {
     eor(cpu, AddressingMode::ZeroPage);
 }
 
-pub fn eor_zpx(cpu: &mut CPU) {
+pub fn eor_zpx<M: Bus>(cpu: &mut CPU<M>) {
     eor(cpu, AddressingMode::ZeroPageX);
 }
 
-pub fn eor_a(cpu: &mut CPU) {
+pub fn eor_a<M: Bus>(cpu: &mut CPU<M>) {
     eor(cpu, AddressingMode::Absolute);
 }
 
-pub fn eor_ax(cpu: &mut CPU) {
+pub fn eor_ax<M: Bus>(cpu: &mut CPU<M>) {
     eor(cpu, AddressingMode::AbsoluteX);
 }
 
-pub fn eor_ay(cpu: &mut CPU) {
+pub fn eor_ay<M: Bus>(cpu: &mut CPU<M>) {
     eor(cpu, AddressingMode::AbsoluteY);
 }
 
-pub fn eor_inx(cpu: &mut CPU) {
+pub fn eor_inx<M: Bus>(cpu: &mut CPU<M>) {
     eor(cpu, AddressingMode::IndexIndirectX);
 }
 
-pub fn eor_iny(cpu: &mut CPU) {
+pub fn eor_iny<M: Bus>(cpu: &mut CPU<M>) {
     eor(cpu, AddressingMode::IndirectIndexY);
 }
 
-pub fn ora(cpu: &mut CPU, addr_mode: AddressingMode) {
+pub fn eor_izp<M: Bus>(cpu: &mut CPU<M>) {
+    eor(cpu, AddressingMode::ZeroPageIndirect);
+}
+
+pub fn ora<M: Bus>(cpu: &mut CPU<M>, addr_mode: AddressingMode) {
     let value = match cpu.read_memory(addr_mode) {
         Some(value) => value,
         None => panic!("ora used with incorrect addressing mode"),
@@ -97,39 +106,43 @@ pub fn ora(cpu: &mut CPU, addr_mode: AddressingMode) {
     cpu.set_register(Registers::Accumulator, result_value);
 }
 
-pub fn ora_im(cpu: &mut CPU) {
+pub fn ora_im<M: Bus>(cpu: &mut CPU<M>) {
     ora(cpu, AddressingMode::Immediate);
 }
 
-pub fn ora_zp(cpu: &mut CPU) {
+pub fn ora_zp<M: Bus>(cpu: &mut CPU<M>) {
     ora(cpu, AddressingMode::ZeroPage);
 }
 
-pub fn ora_zpx(cpu: &mut CPU) {
+pub fn ora_zpx<M: Bus>(cpu: &mut CPU<M>) {
     ora(cpu, AddressingMode::ZeroPageX);
 }
 
-pub fn ora_a(cpu: &mut CPU) {
+pub fn ora_a<M: Bus>(cpu: &mut CPU<M>) {
     ora(cpu, AddressingMode::Absolute);
 }
 
-pub fn ora_ax(cpu: &mut CPU) {
+pub fn ora_ax<M: Bus>(cpu: &mut CPU<M>) {
     ora(cpu, AddressingMode::AbsoluteX);
 }
 
-pub fn ora_ay(cpu: &mut CPU) {
+pub fn ora_ay<M: Bus>(cpu: &mut CPU<M>) {
     ora(cpu, AddressingMode::AbsoluteY);
 }
 
-pub fn ora_inx(cpu: &mut CPU) {
+pub fn ora_inx<M: Bus>(cpu: &mut CPU<M>) {
     ora(cpu, AddressingMode::IndexIndirectX);
 }
 
-pub fn ora_iny(cpu: &mut CPU) {
+pub fn ora_iny<M: Bus>(cpu: &mut CPU<M>) {
     ora(cpu, AddressingMode::IndirectIndexY);
 }
 
-pub fn bit(cpu: &mut CPU, addr_mode: AddressingMode) {
+pub fn ora_izp<M: Bus>(cpu: &mut CPU<M>) {
+    ora(cpu, AddressingMode::ZeroPageIndirect);
+}
+
+pub fn bit<M: Bus>(cpu: &mut CPU<M>, addr_mode: AddressingMode) {
     let value = match cpu.read_memory(addr_mode) {
         Some(value) => value,
         None => panic!("bit used with incorrect addressing mode"),
@@ -138,13 +151,65 @@ pub fn bit(cpu: &mut CPU, addr_mode: AddressingMode) {
     cpu.set_bit_status(cpu.accumulator & value);
 }
 
-pub fn bit_zp(cpu: &mut CPU) {
+// Z is set from A AND memory like BIT, then the bits set in A are set or cleared in memory
+fn test_and_modify_bits<M: Bus>(cpu: &mut CPU<M>, addr_mode: AddressingMode, set: bool) {
+    let accumulator = cpu.accumulator;
+    let modify = move |value: &u8| {
+        if set {
+            return value | accumulator;
+        }
+        return value & !accumulator;
+    };
+    match cpu.modify_memory(addr_mode, &modify) {
+        Some((value, _)) => cpu
+            .processor_status
+            .change_zero_flag(accumulator & value == 0),
+        None => panic!("test_and_modify_bits used with incorrect addressing mode"),
+    };
+}
+
+pub fn trb_zp<M: Bus>(cpu: &mut CPU<M>) {
+    test_and_modify_bits(cpu, AddressingMode::ZeroPage, false);
+}
+
+pub fn trb_a<M: Bus>(cpu: &mut CPU<M>) {
+    test_and_modify_bits(cpu, AddressingMode::Absolute, false);
+}
+
+pub fn tsb_zp<M: Bus>(cpu: &mut CPU<M>) {
+    test_and_modify_bits(cpu, AddressingMode::ZeroPage, true);
+}
+
+pub fn tsb_a<M: Bus>(cpu: &mut CPU<M>) {
+    test_and_modify_bits(cpu, AddressingMode::Absolute, true);
+}
+
+pub fn bit_zp<M: Bus>(cpu: &mut CPU<M>) {
     bit(cpu, AddressingMode::ZeroPage);
 }
 
-pub fn bit_a(cpu: &mut CPU) {
+pub fn bit_a<M: Bus>(cpu: &mut CPU<M>) {
     bit(cpu, AddressingMode::Absolute);
 }
 
+// 65C02 immediate BIT has no memory operand to take N and V from, only Z is affected
+pub fn bit_im<M: Bus>(cpu: &mut CPU<M>) {
+    let value = match cpu.read_memory(AddressingMode::Immediate) {
+        Some(value) => value,
+        None => panic!("bit used with incorrect addressing mode"),
+    };
+
+    cpu.processor_status
+        .change_zero_flag(cpu.accumulator & value == 0);
+}
+
+pub fn bit_zpx<M: Bus>(cpu: &mut CPU<M>) {
+    bit(cpu, AddressingMode::ZeroPageX);
+}
+
+pub fn bit_ax<M: Bus>(cpu: &mut CPU<M>) {
+    bit(cpu, AddressingMode::AbsoluteX);
+}
+
 #[cfg(test)]
 mod tests;
@@ -22,6 +22,7 @@ mod ora {
         fn should_set_processor_status() {
             let memory = &RefCell::new(MemoryMock::new(&[0x22, 0x00]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.program_counter = 0x00;
             cpu.accumulator = 0x86;
 
@@ -69,6 +70,7 @@ mod ora {
         fn should_set_processor_status() {
             let memory = &RefCell::new(MemoryMock::new(&[ZERO_PAGE_ADDR, 0xFF, 0x00, VALUE]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.accumulator = 0x86;
             cpu.program_counter = 0x00;
 
@@ -118,6 +120,7 @@ mod ora {
         fn should_set_processor_status() {
             let memory = &RefCell::new(MemoryMock::new(&[ZERO_PAGE_ADDR, 0xFF, 0x00, VALUE]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.accumulator = 0x86;
             cpu.index_register_x = 0x02;
             cpu.program_counter = 0x00;
@@ -168,6 +171,7 @@ mod ora {
         fn should_set_processor_status() {
             let memory = &RefCell::new(MemoryMock::new(&[ADDR_LO, ADDR_HI, 0x00, 0x00, VALUE]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.accumulator = 0x86;
             cpu.program_counter = 0x00;
 
@@ -220,6 +224,7 @@ mod ora {
         fn should_set_processor_status() {
             let memory = &RefCell::new(MemoryMock::new(&[ADDR_LO, ADDR_HI, 0x00, 0x00, VALUE]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.accumulator = 0x86;
             cpu.program_counter = 0x00;
             cpu.index_register_x = OFFSET;
@@ -295,6 +300,7 @@ mod ora {
         fn should_set_processor_status() {
             let memory = &RefCell::new(MemoryMock::new(&[ADDR_LO, ADDR_HI, 0x00, 0x00, VALUE]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.accumulator = 0x86;
             cpu.program_counter = 0x00;
             cpu.index_register_y = OFFSET;
@@ -384,6 +390,7 @@ mod ora {
                 VALUE,
             ]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.program_counter = 0x00;
             cpu.accumulator = 0x86;
             cpu.index_register_x = OFFSET;
@@ -462,6 +469,7 @@ mod ora {
                 VALUE,
             ]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.accumulator = 0x86;
             cpu.index_register_y = 0x02;
             cpu.program_counter = 0x00;
@@ -532,6 +540,7 @@ mod bit {
         fn should_set_zero_flag_when_logic_and_on_accumulator_and_value_from_zero_page_is_zero() {
             let memory = &RefCell::new(MemoryMock::new(&[ZERO_PAGE_ADDR_LO, 0x0F]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.program_counter = 0x00;
             cpu.accumulator = 0xF0;
 
@@ -545,6 +554,7 @@ mod bit {
         ) {
             let memory = &RefCell::new(MemoryMock::new(&[ZERO_PAGE_ADDR_LO, 0b01000000]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.program_counter = 0x00;
             cpu.accumulator = 0xF0;
 
@@ -558,6 +568,7 @@ mod bit {
         ) {
             let memory = &RefCell::new(MemoryMock::new(&[ZERO_PAGE_ADDR_LO, 0b10000000]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.program_counter = 0x00;
             cpu.accumulator = 0xF0;
 
@@ -570,6 +581,7 @@ mod bit {
         fn should_take_two_cycles() {
             let memory = &RefCell::new(MemoryMock::new(&[ZERO_PAGE_ADDR_LO, 0x0F]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.cycle = 0;
             cpu.program_counter = 0x00;
             cpu.accumulator = 0xF0;
 
@@ -601,6 +613,7 @@ mod bit {
                 0x0F,
             ]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.program_counter = 0x00;
             cpu.accumulator = 0xF0;
 
@@ -619,6 +632,7 @@ mod bit {
                 0b01000000,
             ]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.program_counter = 0x00;
             cpu.accumulator = 0xF0;
 
@@ -637,6 +651,7 @@ mod bit {
                 0b10000000,
             ]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.program_counter = 0x00;
             cpu.accumulator = 0xF0;
 
@@ -654,6 +669,7 @@ mod bit {
                 0x0F,
             ]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.cycle = 0;
             cpu.program_counter = 0x00;
             cpu.accumulator = 0xF0;
 
@@ -693,6 +709,7 @@ mod and {
         fn should_set_processor_status() {
             let memory = &RefCell::new(MemoryMock::new(&[VALUE, 0x00]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.program_counter = 0x00;
             cpu.accumulator = 0x86;
 
@@ -740,6 +757,7 @@ mod and {
         fn should_set_processor_status() {
             let memory = &RefCell::new(MemoryMock::new(&[ZERO_PAGE_ADDR, 0xFF, 0x00, VALUE]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.accumulator = 0x86;
             cpu.program_counter = 0x00;
 
@@ -789,6 +807,7 @@ mod and {
         fn should_set_processor_status() {
             let memory = &RefCell::new(MemoryMock::new(&[ZERO_PAGE_ADDR, 0xFF, 0x00, VALUE]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.accumulator = 0x86;
             cpu.index_register_x = 0x02;
             cpu.program_counter = 0x00;
@@ -839,6 +858,7 @@ mod and {
         fn should_set_processor_status() {
             let memory = &RefCell::new(MemoryMock::new(&[ADDR_LO, ADDR_HI, 0x00, 0x00, VALUE]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.accumulator = 0x86;
             cpu.program_counter = 0x00;
 
@@ -891,6 +911,7 @@ mod and {
         fn should_set_processor_status() {
             let memory = &RefCell::new(MemoryMock::new(&[ADDR_LO, ADDR_HI, 0x00, 0x00, VALUE]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.accumulator = 0x86;
             cpu.program_counter = 0x00;
             cpu.index_register_x = OFFSET;
@@ -966,6 +987,7 @@ mod and {
         fn should_set_processor_status() {
             let memory = &RefCell::new(MemoryMock::new(&[ADDR_LO, ADDR_HI, 0x00, 0x00, VALUE]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.accumulator = 0x86;
             cpu.program_counter = 0x00;
             cpu.index_register_y = OFFSET;
@@ -1055,6 +1077,7 @@ mod and {
                 VALUE,
             ]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.program_counter = 0x00;
             cpu.accumulator = 0x86;
             cpu.index_register_x = OFFSET;
@@ -1133,6 +1156,7 @@ mod and {
                 VALUE,
             ]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.accumulator = 0x86;
             cpu.index_register_y = 0x02;
             cpu.program_counter = 0x00;
@@ -1215,6 +1239,7 @@ mod eor {
         fn should_set_processor_status() {
             let memory = &RefCell::new(MemoryMock::new(&[VALUE, 0x00]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.program_counter = 0x00;
             cpu.accumulator = 0x07;
 
@@ -1262,6 +1287,7 @@ mod eor {
         fn should_set_processor_status() {
             let memory = &RefCell::new(MemoryMock::new(&[ZERO_PAGE_ADDR, 0xFF, 0x00, VALUE]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.accumulator = 0x07;
             cpu.program_counter = 0x00;
 
@@ -1311,6 +1337,7 @@ mod eor {
         fn should_set_processor_status() {
             let memory = &RefCell::new(MemoryMock::new(&[ZERO_PAGE_ADDR, 0xFF, 0x00, VALUE]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.accumulator = 0x07;
             cpu.index_register_x = 0x02;
             cpu.program_counter = 0x00;
@@ -1361,6 +1388,7 @@ mod eor {
         fn should_set_processor_status() {
             let memory = &RefCell::new(MemoryMock::new(&[ADDR_LO, ADDR_HI, 0x00, 0x00, VALUE]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.accumulator = 0x07;
             cpu.program_counter = 0x00;
 
@@ -1413,6 +1441,7 @@ mod eor {
         fn should_set_processor_status() {
             let memory = &RefCell::new(MemoryMock::new(&[ADDR_LO, ADDR_HI, 0x00, 0x00, VALUE]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.accumulator = 0x07;
             cpu.program_counter = 0x00;
             cpu.index_register_x = OFFSET;
@@ -1488,6 +1517,7 @@ mod eor {
         fn should_set_processor_status() {
             let memory = &RefCell::new(MemoryMock::new(&[ADDR_LO, ADDR_HI, 0x00, 0x00, VALUE]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.accumulator = 0x07;
             cpu.program_counter = 0x00;
             cpu.index_register_y = OFFSET;
@@ -1577,6 +1607,7 @@ mod eor {
                 VALUE,
             ]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.program_counter = 0x00;
             cpu.accumulator = 0x07;
             cpu.index_register_x = OFFSET;
@@ -1655,6 +1686,7 @@ mod eor {
                 VALUE,
             ]));
             let mut cpu = CPU::new_nmos(memory);
+            cpu.processor_status.set(0);
             cpu.accumulator = 0x07;
             cpu.index_register_y = 0x02;
             cpu.program_counter = 0x00;
@@ -1707,3 +1739,135 @@ mod eor {
         }
     }
 }
+
+#[cfg(test)]
+mod test_and_modify_bits {
+    use std::cell::RefCell;
+
+    use crate::cpu::{
+        tests::{assert_instruction, MemoryMock},
+        CPU,
+    };
+
+    #[test]
+    fn should_set_accumulator_bits_in_memory() {
+        assert_instruction! {
+            variant: CMOS,
+            program: [0x0C, 0x10, 0x02], // TSB $0210
+            setup: |cpu| {
+                cpu.accumulator = 0b00001111;
+                cpu.get_memory().borrow_mut()[0x0210] = 0b10100101;
+            },
+            cycles: 6,
+            memory: { 0x0210 => 0b10101111 },
+            status: 0b00000000,
+        }
+        assert_instruction! {
+            variant: CMOS,
+            program: [0x0C, 0x10, 0x02], // TSB $0210
+            setup: |cpu| {
+                cpu.accumulator = 0b01010000;
+                cpu.get_memory().borrow_mut()[0x0210] = 0b10100101;
+            },
+            cycles: 6,
+            memory: { 0x0210 => 0b11110101 },
+            status: 0b00000010,
+        }
+    }
+
+    #[test]
+    fn should_reset_accumulator_bits_in_memory() {
+        assert_instruction! {
+            variant: CMOS,
+            program: [0x1C, 0x10, 0x02], // TRB $0210
+            setup: |cpu| {
+                cpu.accumulator = 0b00001111;
+                cpu.get_memory().borrow_mut()[0x0210] = 0b10100101;
+            },
+            cycles: 6,
+            memory: { 0x0210 => 0b10100000 },
+            status: 0b00000000,
+        }
+        assert_instruction! {
+            variant: CMOS,
+            program: [0x1C, 0x10, 0x02], // TRB $0210
+            setup: |cpu| {
+                cpu.accumulator = 0b01010000;
+                cpu.get_memory().borrow_mut()[0x0210] = 0b10100101;
+            },
+            cycles: 6,
+            memory: { 0x0210 => 0b10100101 },
+            status: 0b00000010,
+        }
+    }
+
+    #[test]
+    fn should_take_five_cycles_in_zero_page() {
+        let memory = RefCell::new(MemoryMock::new(&[0x04, 0x02, 0x01])); // TSB $02
+        let mut cpu = CPU::new_cmos(&memory);
+        cpu.cycle = 0;
+        cpu.processor_status.set(0);
+        cpu.program_counter = 0x0000;
+        cpu.accumulator = 0x80;
+
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.cycle, 5);
+        assert_eq!(memory.borrow()[0x0002], 0x81);
+        assert!(cpu.processor_status.get_zero_flag());
+    }
+}
+
+#[cfg(test)]
+mod cmos_bit {
+    use std::cell::RefCell;
+
+    use crate::cpu::{tests::MemoryMock, CPU};
+
+    #[test]
+    fn should_only_change_zero_flag_in_immediate_mode() {
+        let memory = RefCell::new(MemoryMock::new(&[0x89, 0xC0])); // BIT #$C0
+        let mut cpu = CPU::new_cmos(&memory);
+        cpu.cycle = 0;
+        cpu.processor_status.set(0);
+        cpu.program_counter = 0x0000;
+        cpu.accumulator = 0x01;
+
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.cycle, 2);
+        assert!(cpu.processor_status.get_zero_flag());
+        assert!(!cpu.processor_status.get_negative_flag());
+        assert!(!cpu.processor_status.get_overflow_flag());
+    }
+
+    #[test]
+    fn should_test_bits_in_indexed_modes() {
+        let memory = RefCell::new(MemoryMock::new(&[0x34, 0x02, 0x00, 0x00])); // BIT $02,X
+        memory.borrow_mut()[0x0003] = 0x01;
+        let mut cpu = CPU::new_cmos(&memory);
+        cpu.cycle = 0;
+        cpu.processor_status.set(0);
+        cpu.program_counter = 0x0000;
+        cpu.accumulator = 0x01;
+        cpu.index_register_x = 0x01;
+
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.cycle, 4);
+        assert!(!cpu.processor_status.get_zero_flag());
+    }
+
+    #[test]
+    fn should_take_extra_cycle_on_page_crossing_in_absolute_x_mode() {
+        let memory = RefCell::new(MemoryMock::new(&[0x3C, 0xFF, 0x00])); // BIT $00FF,X
+        let mut cpu = CPU::new_cmos(&memory);
+        cpu.cycle = 0;
+        cpu.program_counter = 0x0000;
+        cpu.index_register_x = 0x01;
+
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.cycle, 5);
+    }
+}
@@ -1,20 +1,38 @@
 use crate::cpu::{Registers, CPU};
+use crate::memory::Bus;
 
-pub fn tax(cpu: &mut CPU) {
+pub fn tax<M: Bus>(cpu: &mut CPU<M>) {
     cpu.transfer_registers(Registers::Accumulator, Registers::IndexX);
 }
 
-pub fn txa(cpu: &mut CPU) {
+pub fn txa<M: Bus>(cpu: &mut CPU<M>) {
     cpu.transfer_registers(Registers::IndexX, Registers::Accumulator);
 }
 
-pub fn tay(cpu: &mut CPU) {
+pub fn tay<M: Bus>(cpu: &mut CPU<M>) {
     cpu.transfer_registers(Registers::Accumulator, Registers::IndexY);
 }
 
-pub fn tya(cpu: &mut CPU) {
+pub fn tya<M: Bus>(cpu: &mut CPU<M>) {
     cpu.transfer_registers(Registers::IndexY, Registers::Accumulator);
 }
 
+pub fn taz<M: Bus>(cpu: &mut CPU<M>) {
+    cpu.transfer_registers(Registers::Accumulator, Registers::IndexZ);
+}
+
+pub fn tza<M: Bus>(cpu: &mut CPU<M>) {
+    cpu.transfer_registers(Registers::IndexZ, Registers::Accumulator);
+}
+
+// the base page register is set without touching flags, reading it back sets N and Z
+pub fn tab<M: Bus>(cpu: &mut CPU<M>) {
+    cpu.transfer_registers(Registers::Accumulator, Registers::BasePage);
+}
+
+pub fn tba<M: Bus>(cpu: &mut CPU<M>) {
+    cpu.transfer_registers(Registers::BasePage, Registers::Accumulator);
+}
+
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,239 @@
+use crate::{
+    consts::Byte,
+    cpu::{
+        instructions::arithmetic::{apply_with_carry_through_adder, CarryOperation},
+        AddressingMode, Registers, CPU,
+    },
+    memory::Bus,
+};
+
+// undocumented NMOS opcodes combining a read-modify-write instruction with an accumulator
+// operation on the modified value; they take the cycles of the read-modify-write part
+
+fn modify<M: Bus>(
+    cpu: &mut CPU<M>,
+    addr_mode: AddressingMode,
+    cb: &dyn Fn(&u8) -> u8,
+) -> (Byte, Byte) {
+    return match cpu.modify_memory(addr_mode, cb) {
+        Some(result) => result,
+        None => panic!("read-modify-write combo used with incorrect address mode"),
+    };
+}
+
+// ASL + ORA
+fn slo<M: Bus>(cpu: &mut CPU<M>, addr_mode: AddressingMode) {
+    let (previous, modified) = modify(cpu, addr_mode, &|value| value << 1);
+    cpu.processor_status
+        .change_carry_flag(previous & 0b10000000 > 0);
+    cpu.set_register(Registers::Accumulator, cpu.accumulator | modified);
+}
+
+// ROL + AND
+fn rla<M: Bus>(cpu: &mut CPU<M>, addr_mode: AddressingMode) {
+    let carry = cpu.processor_status.get_carry_flag() as u8;
+    let (previous, modified) = modify(cpu, addr_mode, &|value| value << 1 | carry);
+    cpu.processor_status
+        .change_carry_flag(previous & 0b10000000 > 0);
+    cpu.set_register(Registers::Accumulator, cpu.accumulator & modified);
+}
+
+// LSR + EOR
+fn sre<M: Bus>(cpu: &mut CPU<M>, addr_mode: AddressingMode) {
+    let (previous, modified) = modify(cpu, addr_mode, &|value| value >> 1);
+    cpu.processor_status
+        .change_carry_flag(previous & 0b00000001 > 0);
+    cpu.set_register(Registers::Accumulator, cpu.accumulator ^ modified);
+}
+
+// ROR + ADC, the addition uses the carry shifted out
+fn rra<M: Bus>(cpu: &mut CPU<M>, addr_mode: AddressingMode) {
+    let carry = (cpu.processor_status.get_carry_flag() as u8) << 7;
+    let (previous, modified) = modify(cpu, addr_mode, &|value| value >> 1 | carry);
+    cpu.processor_status
+        .change_carry_flag(previous & 0b00000001 > 0);
+    apply_with_carry_through_adder(cpu, modified, CarryOperation::Addition);
+}
+
+// DEC + CMP
+fn dcp<M: Bus>(cpu: &mut CPU<M>, addr_mode: AddressingMode) {
+    let (_, modified) = modify(cpu, addr_mode, &|value| value.wrapping_sub(1));
+    cpu.set_cmp_status(Registers::Accumulator, modified);
+}
+
+// INC + SBC
+fn isc<M: Bus>(cpu: &mut CPU<M>, addr_mode: AddressingMode) {
+    let (_, modified) = modify(cpu, addr_mode, &|value| value.wrapping_add(1));
+    apply_with_carry_through_adder(cpu, modified, CarryOperation::Subtraction);
+}
+
+pub fn slo_zp<M: Bus>(cpu: &mut CPU<M>) {
+    slo(cpu, AddressingMode::ZeroPage);
+}
+
+pub fn slo_zpx<M: Bus>(cpu: &mut CPU<M>) {
+    slo(cpu, AddressingMode::ZeroPageX);
+}
+
+pub fn slo_a<M: Bus>(cpu: &mut CPU<M>) {
+    slo(cpu, AddressingMode::Absolute);
+}
+
+pub fn slo_ax<M: Bus>(cpu: &mut CPU<M>) {
+    slo(cpu, AddressingMode::AbsoluteX);
+}
+
+pub fn slo_ay<M: Bus>(cpu: &mut CPU<M>) {
+    slo(cpu, AddressingMode::AbsoluteY);
+}
+
+pub fn slo_inx<M: Bus>(cpu: &mut CPU<M>) {
+    slo(cpu, AddressingMode::IndexIndirectX);
+}
+
+pub fn slo_iny<M: Bus>(cpu: &mut CPU<M>) {
+    slo(cpu, AddressingMode::IndirectIndexY);
+}
+
+pub fn rla_zp<M: Bus>(cpu: &mut CPU<M>) {
+    rla(cpu, AddressingMode::ZeroPage);
+}
+
+pub fn rla_zpx<M: Bus>(cpu: &mut CPU<M>) {
+    rla(cpu, AddressingMode::ZeroPageX);
+}
+
+pub fn rla_a<M: Bus>(cpu: &mut CPU<M>) {
+    rla(cpu, AddressingMode::Absolute);
+}
+
+pub fn rla_ax<M: Bus>(cpu: &mut CPU<M>) {
+    rla(cpu, AddressingMode::AbsoluteX);
+}
+
+pub fn rla_ay<M: Bus>(cpu: &mut CPU<M>) {
+    rla(cpu, AddressingMode::AbsoluteY);
+}
+
+pub fn rla_inx<M: Bus>(cpu: &mut CPU<M>) {
+    rla(cpu, AddressingMode::IndexIndirectX);
+}
+
+pub fn rla_iny<M: Bus>(cpu: &mut CPU<M>) {
+    rla(cpu, AddressingMode::IndirectIndexY);
+}
+
+pub fn sre_zp<M: Bus>(cpu: &mut CPU<M>) {
+    sre(cpu, AddressingMode::ZeroPage);
+}
+
+pub fn sre_zpx<M: Bus>(cpu: &mut CPU<M>) {
+    sre(cpu, AddressingMode::ZeroPageX);
+}
+
+pub fn sre_a<M: Bus>(cpu: &mut CPU<M>) {
+    sre(cpu, AddressingMode::Absolute);
+}
+
+pub fn sre_ax<M: Bus>(cpu: &mut CPU<M>) {
+    sre(cpu, AddressingMode::AbsoluteX);
+}
+
+pub fn sre_ay<M: Bus>(cpu: &mut CPU<M>) {
+    sre(cpu, AddressingMode::AbsoluteY);
+}
+
+pub fn sre_inx<M: Bus>(cpu: &mut CPU<M>) {
+    sre(cpu, AddressingMode::IndexIndirectX);
+}
+
+pub fn sre_iny<M: Bus>(cpu: &mut CPU<M>) {
+    sre(cpu, AddressingMode::IndirectIndexY);
+}
+
+pub fn rra_zp<M: Bus>(cpu: &mut CPU<M>) {
+    rra(cpu, AddressingMode::ZeroPage);
+}
+
+pub fn rra_zpx<M: Bus>(cpu: &mut CPU<M>) {
+    rra(cpu, AddressingMode::ZeroPageX);
+}
+
+pub fn rra_a<M: Bus>(cpu: &mut CPU<M>) {
+    rra(cpu, AddressingMode::Absolute);
+}
+
+pub fn rra_ax<M: Bus>(cpu: &mut CPU<M>) {
+    rra(cpu, AddressingMode::AbsoluteX);
+}
+
+pub fn rra_ay<M: Bus>(cpu: &mut CPU<M>) {
+    rra(cpu, AddressingMode::AbsoluteY);
+}
+
+pub fn rra_inx<M: Bus>(cpu: &mut CPU<M>) {
+    rra(cpu, AddressingMode::IndexIndirectX);
+}
+
+pub fn rra_iny<M: Bus>(cpu: &mut CPU<M>) {
+    rra(cpu, AddressingMode::IndirectIndexY);
+}
+
+pub fn dcp_zp<M: Bus>(cpu: &mut CPU<M>) {
+    dcp(cpu, AddressingMode::ZeroPage);
+}
+
+pub fn dcp_zpx<M: Bus>(cpu: &mut CPU<M>) {
+    dcp(cpu, AddressingMode::ZeroPageX);
+}
+
+pub fn dcp_a<M: Bus>(cpu: &mut CPU<M>) {
+    dcp(cpu, AddressingMode::Absolute);
+}
+
+pub fn dcp_ax<M: Bus>(cpu: &mut CPU<M>) {
+    dcp(cpu, AddressingMode::AbsoluteX);
+}
+
+pub fn dcp_ay<M: Bus>(cpu: &mut CPU<M>) {
+    dcp(cpu, AddressingMode::AbsoluteY);
+}
+
+pub fn dcp_inx<M: Bus>(cpu: &mut CPU<M>) {
+    dcp(cpu, AddressingMode::IndexIndirectX);
+}
+
+pub fn dcp_iny<M: Bus>(cpu: &mut CPU<M>) {
+    dcp(cpu, AddressingMode::IndirectIndexY);
+}
+
+pub fn isc_zp<M: Bus>(cpu: &mut CPU<M>) {
+    isc(cpu, AddressingMode::ZeroPage);
+}
+
+pub fn isc_zpx<M: Bus>(cpu: &mut CPU<M>) {
+    isc(cpu, AddressingMode::ZeroPageX);
+}
+
+pub fn isc_a<M: Bus>(cpu: &mut CPU<M>) {
+    isc(cpu, AddressingMode::Absolute);
+}
+
+pub fn isc_ax<M: Bus>(cpu: &mut CPU<M>) {
+    isc(cpu, AddressingMode::AbsoluteX);
+}
+
+pub fn isc_ay<M: Bus>(cpu: &mut CPU<M>) {
+    isc(cpu, AddressingMode::AbsoluteY);
+}
+
+pub fn isc_inx<M: Bus>(cpu: &mut CPU<M>) {
+    isc(cpu, AddressingMode::IndexIndirectX);
+}
+
+pub fn isc_iny<M: Bus>(cpu: &mut CPU<M>) {
+    isc(cpu, AddressingMode::IndirectIndexY);
+}
+
+#[cfg(test)]
+mod tests;
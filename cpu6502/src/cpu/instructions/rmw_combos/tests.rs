@@ -0,0 +1,195 @@
+#[cfg(test)]
+mod slo {
+    use crate::cpu::tests::{assert_cycles, assert_instruction};
+
+    #[test]
+    fn should_shift_memory_left_and_or_it_into_accumulator() {
+        assert_instruction! {
+            program: [0x07, 0x02, 0x81], // SLO $02
+            setup: |cpu| cpu.accumulator = 0x01,
+            cycles: 5,
+            registers: { accumulator: 0x03 },
+            memory: { 0x0002 => 0x02 },
+            status: 0b00000001,
+        };
+    }
+
+    #[test]
+    fn should_take_read_modify_write_cycles_in_every_addressing_mode() {
+        assert_cycles!([0x17, 0x02], |cpu| cpu.index_register_x = 0x01, 6); // SLO $02,X
+        assert_cycles!([0x0F, 0x02, 0x00], 6); // SLO $0002
+        assert_cycles!([0x1F, 0xFF, 0x00], |cpu| cpu.index_register_x = 0x01, 7); // SLO $00FF,X
+        assert_cycles!([0x1B, 0x02, 0x00], |cpu| cpu.index_register_y = 0x01, 7); // SLO $0002,Y
+        assert_cycles!([0x1B, 0xFF, 0x00], |cpu| cpu.index_register_y = 0x01, 7); // SLO $00FF,Y
+        assert_cycles!([0x03, 0x02], 8); // SLO ($02,X)
+        assert_cycles!([0x13, 0x02], |cpu| cpu.index_register_y = 0x01, 8); // SLO ($02),Y
+    }
+}
+
+#[cfg(test)]
+mod rla {
+    use crate::cpu::tests::assert_instruction;
+
+    #[test]
+    fn should_rotate_memory_left_and_and_it_with_accumulator() {
+        assert_instruction! {
+            program: [0x27, 0x02, 0x81], // RLA $02
+            setup: |cpu| {
+                cpu.accumulator = 0xFE;
+                cpu.processor_status.change_carry_flag(true);
+            },
+            cycles: 5,
+            registers: { accumulator: 0x02 },
+            memory: { 0x0002 => 0x03 },
+            status: 0b00000001,
+        };
+    }
+}
+
+#[cfg(test)]
+mod sre {
+    use crate::cpu::tests::assert_instruction;
+
+    #[test]
+    fn should_shift_memory_right_and_xor_it_with_accumulator() {
+        assert_instruction! {
+            program: [0x47, 0x02, 0x03], // SRE $02
+            setup: |cpu| cpu.accumulator = 0x01,
+            cycles: 5,
+            registers: { accumulator: 0x00 },
+            memory: { 0x0002 => 0x01 },
+            status: 0b00000011,
+        };
+    }
+}
+
+#[cfg(test)]
+mod rra {
+    use crate::cpu::tests::assert_instruction;
+
+    #[test]
+    fn should_rotate_memory_right_and_add_it_to_accumulator() {
+        assert_instruction! {
+            program: [0x6F, 0x03, 0x00, 0x02], // RRA $0003
+            setup: |cpu| cpu.accumulator = 0x10,
+            cycles: 6,
+            registers: { accumulator: 0x11 },
+            memory: { 0x0003 => 0x01 },
+            status: 0b00000000,
+        };
+    }
+
+    #[test]
+    fn should_add_carry_shifted_out_and_clear_carry_when_sum_fits_in_a_byte() {
+        assert_instruction! {
+            program: [0x6F, 0x03, 0x00, 0x03], // RRA $0003
+            setup: |cpu| {
+                cpu.accumulator = 0x10;
+                cpu.processor_status.change_carry_flag(true);
+            },
+            cycles: 6,
+            registers: { accumulator: 0x92 },
+            memory: { 0x0003 => 0x81 },
+            status: 0b10000000,
+        };
+    }
+
+    #[test]
+    fn should_set_overflow_and_carry_from_the_addition() {
+        assert_instruction! {
+            program: [0x6F, 0x03, 0x00, 0x01], // RRA $0003
+            setup: |cpu| {
+                cpu.accumulator = 0x80;
+                cpu.processor_status.change_carry_flag(true);
+            },
+            cycles: 6,
+            registers: { accumulator: 0x01 },
+            memory: { 0x0003 => 0x80 },
+            status: 0b01000001,
+        };
+    }
+}
+
+#[cfg(test)]
+mod dcp {
+    use crate::cpu::tests::assert_instruction;
+
+    #[test]
+    fn should_decrement_memory_and_compare_it_with_accumulator() {
+        assert_instruction! {
+            program: [0xD7, 0x01, 0x43], // DCP $01,X
+            setup: |cpu| {
+                cpu.accumulator = 0x42;
+                cpu.index_register_x = 0x01;
+            },
+            cycles: 6,
+            registers: { accumulator: 0x42 },
+            memory: { 0x0002 => 0x42 },
+            status: 0b00000011,
+        };
+    }
+}
+
+#[cfg(test)]
+mod isc {
+    use crate::cpu::tests::assert_instruction;
+
+    #[test]
+    fn should_increment_memory_and_subtract_it_from_accumulator() {
+        assert_instruction! {
+            program: [0xE7, 0x02, 0x0F], // ISC $02
+            setup: |cpu| {
+                cpu.accumulator = 0x20;
+                cpu.processor_status.change_carry_flag(true);
+            },
+            cycles: 5,
+            registers: { accumulator: 0x10 },
+            memory: { 0x0002 => 0x10 },
+            status: 0b00000001,
+        };
+    }
+
+    #[test]
+    fn should_set_overflow_and_keep_carry_when_subtraction_does_not_borrow() {
+        assert_instruction! {
+            program: [0xE7, 0x02, 0x00], // ISC $02
+            setup: |cpu| {
+                cpu.accumulator = 0x80;
+                cpu.processor_status.change_carry_flag(true);
+            },
+            cycles: 5,
+            registers: { accumulator: 0x7F },
+            memory: { 0x0002 => 0x01 },
+            status: 0b01000001,
+        };
+    }
+
+    #[test]
+    fn should_clear_carry_and_overflow_when_subtraction_borrows() {
+        assert_instruction! {
+            program: [0xE7, 0x02, 0x1F], // ISC $02
+            setup: |cpu| {
+                cpu.accumulator = 0x10;
+                cpu.processor_status.change_carry_flag(true);
+                cpu.processor_status.change_overflow_flag(true);
+            },
+            cycles: 5,
+            registers: { accumulator: 0xF0 },
+            memory: { 0x0002 => 0x20 },
+            status: 0b10000000,
+        };
+    }
+
+    #[test]
+    fn should_write_through_indirect_indexed_address() {
+        assert_instruction! {
+            program: [0xF3, 0x02, 0x04, 0x00, 0x00, 0xFE], // ISC ($02),Y
+            setup: |cpu| {
+                cpu.index_register_y = 0x01;
+                cpu.processor_status.change_carry_flag(true);
+            },
+            cycles: 8,
+            memory: { 0x0005 => 0xFF },
+        };
+    }
+}
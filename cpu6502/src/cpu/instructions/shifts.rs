@@ -1,6 +1,7 @@
 use crate::{
-    consts::Byte,
+    consts::{Byte, Word},
     cpu::{AddressingMode, Registers, CPU},
+    memory::Bus,
 };
 
 #[derive(PartialEq, Eq)]
@@ -39,7 +40,7 @@ fn shift_right_cb(value: &u8) -> u8 {
     return value >> 1;
 }
 
-fn shift(cpu: &mut CPU, addr_mode: AddressingMode, dir: Directions) {
+fn shift<M: Bus>(cpu: &mut CPU<M>, addr_mode: AddressingMode, dir: Directions) {
     let previous_value: Byte;
     let modified_value: Byte;
 
@@ -61,7 +62,7 @@ fn shift(cpu: &mut CPU, addr_mode: AddressingMode, dir: Directions) {
         previous_value = cpu.get_register(Registers::Accumulator);
         modified_value = cb(&previous_value);
         cpu.accumulator = modified_value;
-        cpu.cycle += 1;
+        cpu.idle_cycle();
     }
 
     let carry = match dir {
@@ -72,55 +73,96 @@ fn shift(cpu: &mut CPU, addr_mode: AddressingMode, dir: Directions) {
     cpu.set_status_of_value(modified_value);
 }
 
-fn asl(cpu: &mut CPU, addr_mode: AddressingMode) {
+fn asl<M: Bus>(cpu: &mut CPU<M>, addr_mode: AddressingMode) {
     shift(cpu, addr_mode, Directions::Left);
 }
 
-pub fn asl_acc(cpu: &mut CPU) {
+pub fn asl_acc<M: Bus>(cpu: &mut CPU<M>) {
     asl(cpu, AddressingMode::Accumulator);
 }
 
-pub fn asl_zp(cpu: &mut CPU) {
+pub fn asl_zp<M: Bus>(cpu: &mut CPU<M>) {
     asl(cpu, AddressingMode::ZeroPage);
 }
 
-pub fn asl_zpx(cpu: &mut CPU) {
+pub fn asl_zpx<M: Bus>(cpu: &mut CPU<M>) {
     asl(cpu, AddressingMode::ZeroPageX);
 }
 
-pub fn asl_a(cpu: &mut CPU) {
+pub fn asl_a<M: Bus>(cpu: &mut CPU<M>) {
     asl(cpu, AddressingMode::Absolute);
 }
 
-pub fn asl_ax(cpu: &mut CPU) {
+pub fn asl_ax<M: Bus>(cpu: &mut CPU<M>) {
     asl(cpu, AddressingMode::AbsoluteX);
 }
 
-fn lsr(cpu: &mut CPU, addr_mode: AddressingMode) {
+fn lsr<M: Bus>(cpu: &mut CPU<M>, addr_mode: AddressingMode) {
     shift(cpu, addr_mode, Directions::Right);
 }
 
-pub fn lsr_acc(cpu: &mut CPU) {
+pub fn lsr_acc<M: Bus>(cpu: &mut CPU<M>) {
     lsr(cpu, AddressingMode::Accumulator);
 }
 
-pub fn lsr_zp(cpu: &mut CPU) {
+pub fn lsr_zp<M: Bus>(cpu: &mut CPU<M>) {
     lsr(cpu, AddressingMode::ZeroPage);
 }
 
-pub fn lsr_zpx(cpu: &mut CPU) {
+pub fn lsr_zpx<M: Bus>(cpu: &mut CPU<M>) {
     lsr(cpu, AddressingMode::ZeroPageX);
 }
 
-pub fn lsr_a(cpu: &mut CPU) {
+pub fn lsr_a<M: Bus>(cpu: &mut CPU<M>) {
     lsr(cpu, AddressingMode::Absolute);
 }
 
-pub fn lsr_ax(cpu: &mut CPU) {
+pub fn lsr_ax<M: Bus>(cpu: &mut CPU<M>) {
     lsr(cpu, AddressingMode::AbsoluteX);
 }
 
-fn rotate(cpu: &mut CPU, addr_mode: AddressingMode, dir: Directions) {
+fn arithmetic_shift_right_cb(value: &u8) -> u8 {
+    return (*value as i8 >> 1) as u8;
+}
+
+// 65CE02 ASR keeps the sign bit while shifting right
+fn asr<M: Bus>(cpu: &mut CPU<M>, addr_mode: AddressingMode) {
+    let previous_value: Byte;
+    let modified_value: Byte;
+
+    if addr_mode != AddressingMode::Accumulator {
+        match cpu.modify_memory(addr_mode, &arithmetic_shift_right_cb) {
+            Some((previous, modified)) => {
+                previous_value = previous;
+                modified_value = modified;
+            }
+            None => panic!("could not shift value in memory"),
+        };
+    } else {
+        previous_value = cpu.get_register(Registers::Accumulator);
+        modified_value = arithmetic_shift_right_cb(&previous_value);
+        cpu.accumulator = modified_value;
+        cpu.idle_cycle();
+    }
+
+    cpu.processor_status
+        .change_carry_flag(previous_value & 0b00000001 > 0);
+    cpu.set_status_of_value(modified_value);
+}
+
+pub fn asr_acc<M: Bus>(cpu: &mut CPU<M>) {
+    asr(cpu, AddressingMode::Accumulator);
+}
+
+pub fn asr_zp<M: Bus>(cpu: &mut CPU<M>) {
+    asr(cpu, AddressingMode::ZeroPage);
+}
+
+pub fn asr_zpx<M: Bus>(cpu: &mut CPU<M>) {
+    asr(cpu, AddressingMode::ZeroPageX);
+}
+
+fn rotate<M: Bus>(cpu: &mut CPU<M>, addr_mode: AddressingMode, dir: Directions) {
     let previous_value: Byte;
     let modified_value: Byte;
     let current_carry = cpu.processor_status.get_carry_flag();
@@ -142,7 +184,7 @@ fn rotate(cpu: &mut CPU, addr_mode: AddressingMode, dir: Directions) {
         previous_value = cpu.get_register(Registers::Accumulator);
         modified_value = cb(&previous_value);
         cpu.accumulator = modified_value;
-        cpu.cycle += 1;
+        cpu.idle_cycle();
     }
 
     let new_carry = match dir {
@@ -153,53 +195,83 @@ fn rotate(cpu: &mut CPU, addr_mode: AddressingMode, dir: Directions) {
     cpu.set_status_of_value(modified_value);
 }
 
-fn rol(cpu: &mut CPU, addr_mode: AddressingMode) {
+fn rol<M: Bus>(cpu: &mut CPU<M>, addr_mode: AddressingMode) {
     rotate(cpu, addr_mode, Directions::Left);
 }
 
-pub fn rol_acc(cpu: &mut CPU) {
+pub fn rol_acc<M: Bus>(cpu: &mut CPU<M>) {
     rol(cpu, AddressingMode::Accumulator);
 }
 
-pub fn rol_zp(cpu: &mut CPU) {
+pub fn rol_zp<M: Bus>(cpu: &mut CPU<M>) {
     rol(cpu, AddressingMode::ZeroPage);
 }
 
-pub fn rol_zpx(cpu: &mut CPU) {
+pub fn rol_zpx<M: Bus>(cpu: &mut CPU<M>) {
     rol(cpu, AddressingMode::ZeroPageX);
 }
 
-pub fn rol_a(cpu: &mut CPU) {
+pub fn rol_a<M: Bus>(cpu: &mut CPU<M>) {
     rol(cpu, AddressingMode::Absolute);
 }
 
-pub fn rol_ax(cpu: &mut CPU) {
+pub fn rol_ax<M: Bus>(cpu: &mut CPU<M>) {
     rol(cpu, AddressingMode::AbsoluteX);
 }
 
-fn ror(cpu: &mut CPU, addr_mode: AddressingMode) {
+fn ror<M: Bus>(cpu: &mut CPU<M>, addr_mode: AddressingMode) {
     rotate(cpu, addr_mode, Directions::Right);
 }
 
-pub fn ror_acc(cpu: &mut CPU) {
+pub fn ror_acc<M: Bus>(cpu: &mut CPU<M>) {
     ror(cpu, AddressingMode::Accumulator);
 }
 
-pub fn ror_zp(cpu: &mut CPU) {
+pub fn ror_zp<M: Bus>(cpu: &mut CPU<M>) {
     ror(cpu, AddressingMode::ZeroPage);
 }
 
-pub fn ror_zpx(cpu: &mut CPU) {
+pub fn ror_zpx<M: Bus>(cpu: &mut CPU<M>) {
     ror(cpu, AddressingMode::ZeroPageX);
 }
 
-pub fn ror_a(cpu: &mut CPU) {
+pub fn ror_a<M: Bus>(cpu: &mut CPU<M>) {
     ror(cpu, AddressingMode::Absolute);
 }
 
-pub fn ror_ax(cpu: &mut CPU) {
+pub fn ror_ax<M: Bus>(cpu: &mut CPU<M>) {
     ror(cpu, AddressingMode::AbsoluteX);
 }
 
+// 65CE02 ASW and ROW shift a little endian word in place, carry comes out of bit 15 and N
+// and Z describe the whole word
+fn shift_word_left<M: Bus>(cpu: &mut CPU<M>, carry_in: bool) {
+    let address = match cpu.get_address(AddressingMode::Absolute) {
+        Some(address) => address,
+        None => panic!("could not address word in memory"),
+    };
+    let value = cpu.fetch_address_from(address);
+    let result = value << 1 | carry_in as Word;
+    let [lo, hi] = result.to_le_bytes();
+
+    cpu.put_into_memory(address, lo);
+    cpu.tick();
+    cpu.put_into_memory(address.wrapping_add(1), hi);
+    cpu.tick();
+    cpu.processor_status.change_carry_flag(value & 0x8000 > 0);
+    cpu.processor_status.change_zero_flag(result == 0);
+    cpu.processor_status
+        .change_negative_flag(result & 0x8000 > 0);
+}
+
+pub fn asw_a<M: Bus>(cpu: &mut CPU<M>) {
+    shift_word_left(cpu, false);
+}
+
+pub fn row_a<M: Bus>(cpu: &mut CPU<M>) {
+    let carry = cpu.processor_status.get_carry_flag();
+    shift_word_left(cpu, carry);
+}
+
 #[cfg(test)]
 mod tests;
@@ -1,40 +1,95 @@
-use crate::cpu::{Registers, CPU};
+use crate::cpu::{AddressingMode, Registers, CPU};
+use crate::memory::Bus;
 
-fn push_register(cpu: &mut CPU, register: Registers) {
+fn push_register<M: Bus>(cpu: &mut CPU<M>, register: Registers) {
     cpu.dummy_fetch();
     cpu.push_byte_to_stack(cpu.get_register(register));
 }
 
-pub fn pha(cpu: &mut CPU) {
+pub fn pha<M: Bus>(cpu: &mut CPU<M>) {
     push_register(cpu, Registers::Accumulator);
 }
 
-pub fn php(cpu: &mut CPU) {
+pub fn php<M: Bus>(cpu: &mut CPU<M>) {
     push_register(cpu, Registers::ProcessorStatus);
 }
 
-fn pull_register(cpu: &mut CPU, register: Registers) {
+pub fn phx<M: Bus>(cpu: &mut CPU<M>) {
+    push_register(cpu, Registers::IndexX);
+}
+
+pub fn phy<M: Bus>(cpu: &mut CPU<M>) {
+    push_register(cpu, Registers::IndexY);
+}
+
+pub fn phz<M: Bus>(cpu: &mut CPU<M>) {
+    push_register(cpu, Registers::IndexZ);
+}
+
+// PHW pushes a word high byte first, so it lands on the stack in little endian order
+fn push_word<M: Bus>(cpu: &mut CPU<M>, addr_mode: AddressingMode) {
+    let value = match addr_mode {
+        AddressingMode::Immediate => cpu.fetch_address(),
+        AddressingMode::Absolute => {
+            let address = cpu.fetch_address();
+            cpu.fetch_address_from(address)
+        }
+        _ => panic!("push_word used with incorrect address mode"),
+    };
+    cpu.push_word_to_stack(value);
+}
+
+pub fn phw_im<M: Bus>(cpu: &mut CPU<M>) {
+    push_word(cpu, AddressingMode::Immediate);
+}
+
+pub fn phw_a<M: Bus>(cpu: &mut CPU<M>) {
+    push_word(cpu, AddressingMode::Absolute);
+}
+
+fn pull_register<M: Bus>(cpu: &mut CPU<M>, register: Registers) {
     cpu.dummy_fetch();
+    cpu.dummy_stack_read();
     let value = cpu.pop_byte_from_stack();
-    cpu.tick();
     cpu.set_register(register, value);
 }
 
-pub fn pla(cpu: &mut CPU) {
+pub fn pla<M: Bus>(cpu: &mut CPU<M>) {
     pull_register(cpu, Registers::Accumulator);
 }
 
-pub fn plp(cpu: &mut CPU) {
+pub fn plp<M: Bus>(cpu: &mut CPU<M>) {
     pull_register(cpu, Registers::ProcessorStatus);
 }
 
-pub fn tsx(cpu: &mut CPU) {
+pub fn plx<M: Bus>(cpu: &mut CPU<M>) {
+    pull_register(cpu, Registers::IndexX);
+}
+
+pub fn ply<M: Bus>(cpu: &mut CPU<M>) {
+    pull_register(cpu, Registers::IndexY);
+}
+
+pub fn plz<M: Bus>(cpu: &mut CPU<M>) {
+    pull_register(cpu, Registers::IndexZ);
+}
+
+pub fn tsx<M: Bus>(cpu: &mut CPU<M>) {
     cpu.transfer_registers(Registers::StackPointer, Registers::IndexX);
 }
 
-pub fn txs(cpu: &mut CPU) {
+pub fn txs<M: Bus>(cpu: &mut CPU<M>) {
     cpu.transfer_registers(Registers::IndexX, Registers::StackPointer);
 }
 
+// the stack pointer high byte is reached through Y
+pub fn tsy<M: Bus>(cpu: &mut CPU<M>) {
+    cpu.transfer_registers(Registers::StackPointerHi, Registers::IndexY);
+}
+
+pub fn tys<M: Bus>(cpu: &mut CPU<M>) {
+    cpu.transfer_registers(Registers::IndexY, Registers::StackPointerHi);
+}
+
 #[cfg(test)]
 mod tests;
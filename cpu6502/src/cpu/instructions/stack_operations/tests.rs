@@ -213,3 +213,59 @@ mod tsx {
         assert_eq!(cpu.processor_status, 0b10000000);
     }
 }
+
+#[cfg(test)]
+mod index_register_stack_operations {
+    use std::cell::RefCell;
+
+    use crate::cpu::{tests::MemoryMock, CPU};
+
+    #[test]
+    fn should_push_and_pull_index_registers_on_cmos() {
+        let memory = &RefCell::new(MemoryMock::new(&[0xDA, 0x5A, 0xFA, 0x7A])); // PHX; PHY; PLX; PLY
+        let mut cpu = CPU::new_cmos(memory);
+        cpu.cycle = 0;
+        cpu.processor_status.set(0);
+        cpu.program_counter = 0x0000;
+        cpu.stack_pointer = 0xFF;
+        cpu.index_register_x = 0x80;
+        cpu.index_register_y = 0x00;
+
+        for _ in 0..4 {
+            assert_eq!(cpu.execute_next_instruction(), None);
+        }
+
+        assert_eq!(cpu.index_register_x, 0x00);
+        assert_eq!(cpu.index_register_y, 0x80);
+        assert_eq!(cpu.stack_pointer, 0xFF);
+        assert_eq!(cpu.cycle, 14);
+        assert_eq!(cpu.processor_status, 0b10000000);
+    }
+
+    #[test]
+    fn should_set_zero_flag_on_pull_like_pla() {
+        let memory = &RefCell::new(MemoryMock::new(&[0xFA])); // PLX
+        let mut cpu = CPU::new_wdc_cmos(memory);
+        cpu.processor_status.set(0);
+        cpu.program_counter = 0x0000;
+        cpu.stack_pointer = 0xFE;
+        cpu.index_register_x = 0x42;
+
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.index_register_x, 0x00);
+        assert_eq!(cpu.processor_status, 0b00000010);
+    }
+
+    #[test]
+    fn should_not_touch_stack_on_nmos() {
+        let memory = &RefCell::new(MemoryMock::new(&[0xDA])); // NOP on NMOS
+        let mut cpu = CPU::new_nmos(memory);
+        cpu.program_counter = 0x0000;
+        cpu.stack_pointer = 0xFF;
+
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.stack_pointer, 0xFF);
+    }
+}
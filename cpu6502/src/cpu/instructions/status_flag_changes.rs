@@ -1,37 +1,49 @@
 use crate::cpu::{processor_status::Flags, CPU};
+use crate::memory::Bus;
 
-fn change_flag_value(cpu: &mut CPU, flag: Flags, value: bool) {
+fn change_flag_value<M: Bus>(cpu: &mut CPU<M>, flag: Flags, value: bool) {
     cpu.processor_status.change_flag(flag, value);
-    cpu.cycle += 1;
+    cpu.idle_cycle();
 }
 
-pub fn clc(cpu: &mut CPU) {
+pub fn clc<M: Bus>(cpu: &mut CPU<M>) {
     change_flag_value(cpu, Flags::Carry, false);
 }
 
-pub fn cld(cpu: &mut CPU) {
+pub fn cld<M: Bus>(cpu: &mut CPU<M>) {
     change_flag_value(cpu, Flags::DecimalMode, false);
 }
 
-pub fn cli(cpu: &mut CPU) {
+pub fn cli<M: Bus>(cpu: &mut CPU<M>) {
     change_flag_value(cpu, Flags::InterruptDisable, false);
 }
 
-pub fn clv(cpu: &mut CPU) {
+pub fn clv<M: Bus>(cpu: &mut CPU<M>) {
     change_flag_value(cpu, Flags::Overflow, false);
 }
 
-pub fn sec(cpu: &mut CPU) {
+pub fn sec<M: Bus>(cpu: &mut CPU<M>) {
     change_flag_value(cpu, Flags::Carry, true);
 }
 
-pub fn sed(cpu: &mut CPU) {
+pub fn sed<M: Bus>(cpu: &mut CPU<M>) {
     change_flag_value(cpu, Flags::DecimalMode, true);
 }
 
-pub fn sei(cpu: &mut CPU) {
+pub fn sei<M: Bus>(cpu: &mut CPU<M>) {
     change_flag_value(cpu, Flags::InterruptDisable, true);
 }
 
+// 65CE02 E flag; it is kept outside of P, so PLP and RTI do not change the stack mode
+pub fn cle<M: Bus>(cpu: &mut CPU<M>) {
+    cpu.extended_stack = true;
+    cpu.idle_cycle();
+}
+
+pub fn see<M: Bus>(cpu: &mut CPU<M>) {
+    cpu.extended_stack = false;
+    cpu.idle_cycle();
+}
+
 #[cfg(test)]
 mod tests;
@@ -1,33 +1,99 @@
 use crate::{
     consts::BRK_INTERRUPT_VECTOR,
-    cpu::{ChipVariant, CPU},
+    cpu::{call_trace::CallKind, statistics::InterruptSource, AddressingMode, CPU},
+    memory::Bus,
 };
 
-pub fn nop(cpu: &mut CPU) {
-    cpu.increment_program_counter();
+pub fn nop<M: Bus>(cpu: &mut CPU<M>) {
+    cpu.dummy_fetch();
 }
 
-pub fn brk(cpu: &mut CPU) {
-    cpu.access_memory(cpu.program_counter); // fetch and discard
-    cpu.increment_program_counter();
+// undocumented multi-byte NOPs read their operand like a load would, including the page
+// crossing penalty, and discard it
+fn nop_read<M: Bus>(cpu: &mut CPU<M>, addr_mode: AddressingMode) {
+    if cpu.read_memory(addr_mode).is_none() {
+        panic!("nop used with incorrect addressing mode");
+    }
+}
+
+pub fn nop_im<M: Bus>(cpu: &mut CPU<M>) {
+    nop_read(cpu, AddressingMode::Immediate);
+}
+
+pub fn nop_zp<M: Bus>(cpu: &mut CPU<M>) {
+    nop_read(cpu, AddressingMode::ZeroPage);
+}
+
+pub fn nop_zpx<M: Bus>(cpu: &mut CPU<M>) {
+    nop_read(cpu, AddressingMode::ZeroPageX);
+}
+
+pub fn nop_a<M: Bus>(cpu: &mut CPU<M>) {
+    nop_read(cpu, AddressingMode::Absolute);
+}
+
+pub fn nop_ax<M: Bus>(cpu: &mut CPU<M>) {
+    nop_read(cpu, AddressingMode::AbsoluteX);
+}
 
-    cpu.push_word_to_stack(cpu.program_counter);
-    cpu.push_byte_to_stack(cpu.processor_status.into());
-    cpu.program_counter = cpu.fetch_address_from(BRK_INTERRUPT_VECTOR);
+// undefined 65C02 opcodes in columns 3 and B (and 7 and F without the Rockwell bit
+// instructions) finish within the opcode fetch
+pub fn nop_single_cycle<M: Bus>(_cpu: &mut CPU<M>) {}
 
-    cpu.processor_status.change_break_flag(true);
-    if cpu.chip_variant == ChipVariant::NMOS {
-        return;
+// 65C02 $5C reads its absolute operand and keeps it on the bus for another four cycles
+pub fn nop_a_slow<M: Bus>(cpu: &mut CPU<M>) {
+    let address = match cpu.get_address(AddressingMode::Absolute) {
+        Some(address) => address,
+        None => panic!("nop used with incorrect addressing mode"),
+    };
+    for _ in 0..5 {
+        cpu.dummy_read(address);
+        cpu.tick();
     }
+}
+
+// 65CE02 AUG reserves three operand bytes for future extensions and skips them
+pub fn aug<M: Bus>(cpu: &mut CPU<M>) {
+    for _ in 0..3 {
+        cpu.access_memory(cpu.program_counter);
+        cpu.increment_program_counter();
+    }
+}
+
+// the NMOS decoder locks up on these and only RESET brings the chip back
+pub fn jam<M: Bus>(cpu: &mut CPU<M>) {
+    cpu.jammed = true;
+}
+
+// WDC additions: WAI sleeps until an interrupt arrives, STP stops the clock until RESET
+pub fn wai<M: Bus>(cpu: &mut CPU<M>) {
+    cpu.dummy_fetch();
+    cpu.idle_cycle();
+    cpu.waiting = true;
+}
+
+pub fn stp<M: Bus>(cpu: &mut CPU<M>) {
+    cpu.dummy_fetch();
+    cpu.idle_cycle();
+    cpu.stopped = true;
+}
+
+// software interrupt, the byte after BRK is skipped so the return address is BRK + 2
+pub fn brk<M: Bus>(cpu: &mut CPU<M>) {
+    let brk_addr = cpu.program_counter.wrapping_sub(1);
+    cpu.access_memory(cpu.program_counter); // fetch and discard
+    cpu.increment_program_counter();
 
-    cpu.processor_status.change_decimal_mode_flag(false);
+    cpu.enter_interrupt(brk_addr, BRK_INTERRUPT_VECTOR, InterruptSource::Break);
 }
 
-pub fn rti(cpu: &mut CPU) {
+pub fn rti<M: Bus>(cpu: &mut CPU<M>) {
     cpu.dummy_fetch();
+    cpu.dummy_stack_read();
     cpu.processor_status = cpu.pop_byte_from_stack().into();
     cpu.program_counter = cpu.pop_word_from_stack();
-    cpu.tick();
+    cpu.trace_return(CallKind::Interrupt);
+    cpu.record_interrupt_return();
 }
 
 #[cfg(test)]
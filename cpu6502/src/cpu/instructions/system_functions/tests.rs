@@ -6,7 +6,7 @@ mod brk {
 
         use crate::{
             consts::Byte,
-            cpu::{instructions::brk, tests::MemoryMock, CPU},
+            cpu::{instructions::brk, tests::assert_instruction, tests::MemoryMock, CPU},
         };
 
         #[test]
@@ -41,15 +41,29 @@ mod brk {
         }
 
         #[test]
-        fn should_set_break_processor_status_flag() {
+        fn should_push_processor_status_with_break_flag_set() {
             let memory = &RefCell::new(MemoryMock::default());
             let mut cpu = CPU::new_nmos(memory);
             cpu.program_counter = 0x00;
-            cpu.processor_status.change_break_flag(false);
+            cpu.stack_pointer = 0xFF;
+            cpu.processor_status.set(0b00000001);
 
             brk(&mut cpu);
 
-            assert_eq!(cpu.processor_status.get_break_flag(), true);
+            assert_eq!(memory.borrow()[0x01FD], 0b00010001);
+            assert_eq!(cpu.processor_status.get_break_flag(), false);
+        }
+
+        #[test]
+        fn should_set_interrupt_disable_flag() {
+            let memory = &RefCell::new(MemoryMock::default());
+            let mut cpu = CPU::new_nmos(memory);
+            cpu.program_counter = 0x00;
+            cpu.processor_status.set(0);
+
+            brk(&mut cpu);
+
+            assert!(cpu.processor_status.get_interrupt_disable_flag());
         }
 
         #[test]
@@ -63,6 +77,16 @@ mod brk {
 
             assert_eq!(cpu.cycle, 6);
         }
+
+        #[test]
+        fn should_take_seven_cycles_including_opcode_fetch_and_return_past_signature_byte() {
+            assert_instruction! {
+                program: [0x00, 0x42],
+                setup: |cpu| cpu.stack_pointer = 0xFF,
+                cycles: 7,
+                memory: { 0x01FF => 0x00, 0x01FE => 0x02 },
+            };
+        }
     }
 
     #[cfg(test)]
@@ -176,14 +200,14 @@ mod nop {
     use crate::cpu::{instructions::nop, tests::MemoryMock, CPU};
 
     #[test]
-    fn should_increment_program_counter() {
+    fn should_leave_program_counter_at_next_instruction() {
         let memory = &RefCell::new(MemoryMock::default());
         let mut cpu = CPU::new_nmos(memory);
         cpu.program_counter = 0x05;
 
         nop(&mut cpu);
 
-        assert_eq!(cpu.program_counter, 0x06);
+        assert_eq!(cpu.program_counter, 0x05);
     }
 
     #[test]
@@ -198,3 +222,154 @@ mod nop {
         assert_eq!(cpu.cycle, 1);
     }
 }
+
+#[cfg(test)]
+mod jam {
+    use std::cell::RefCell;
+
+    use crate::cpu::{emulation_event::EmulationEvent, tests::MemoryMock, CPU};
+
+    #[test]
+    fn should_halt_until_reset() {
+        let memory = &RefCell::new(MemoryMock::new(&[0xE8, 0x02, 0xE8])); // INX; JAM; INX
+        let mut cpu = CPU::new_nmos(memory);
+        cpu.program_counter = 0x0000;
+        let jam = Some(EmulationEvent::Jam {
+            address: 0x0001,
+            opcode: 0x02,
+        });
+
+        cpu.execute_next_instruction();
+        assert_eq!(cpu.execute_next_instruction(), jam);
+        let cycle = cpu.cycle;
+        assert_eq!(cpu.execute_next_instruction(), jam);
+
+        assert!(cpu.is_jammed());
+        assert_eq!(cpu.index_register_x, 0x01);
+        assert_eq!(cpu.program_counter, 0x0002);
+        assert_eq!(cpu.cycle, cycle);
+
+        cpu.reset();
+
+        assert!(!cpu.is_jammed());
+    }
+
+    #[test]
+    fn should_stop_run_with_jam_event() {
+        let memory = &RefCell::new(MemoryMock::new(&[0xE8, 0xE8, 0xF2])); // INX; INX; JAM
+        let mut cpu = CPU::new_nmos(memory);
+        cpu.program_counter = 0x0000;
+
+        assert_eq!(
+            cpu.execute_until_break(),
+            EmulationEvent::Jam {
+                address: 0x0002,
+                opcode: 0xF2
+            }
+        );
+    }
+}
+
+#[cfg(test)]
+mod undocumented_nop {
+    use crate::cpu::tests::{assert_cycles, assert_instruction};
+
+    #[test]
+    fn should_advance_program_counter_past_operands_without_side_effects() {
+        assert_instruction! {
+            program: [0x80, 0x42], // NOP #$42
+            cycles: 2,
+            registers: { program_counter: 0x0002, accumulator: 0x00 },
+            status: 0b00000000,
+        };
+        assert_instruction! {
+            program: [0x0C, 0x00, 0x02], // NOP $0200
+            cycles: 4,
+            registers: { program_counter: 0x0003 },
+        };
+        assert_instruction! {
+            program: [0x1A], // NOP
+            cycles: 2,
+            registers: { program_counter: 0x0001 },
+        };
+    }
+
+    #[test]
+    fn should_take_load_cycles_in_every_addressing_mode() {
+        assert_cycles!([0x04, 0x02], 3); // NOP $02
+        assert_cycles!([0x14, 0x02], |cpu| cpu.index_register_x = 0x01, 4); // NOP $02,X
+        assert_cycles!([0x1C, 0x00, 0x02], |cpu| cpu.index_register_x = 0x01, 4); // NOP $0200,X
+        assert_cycles!([0xFC, 0xFF, 0x02], |cpu| cpu.index_register_x = 0x01, 5);
+        // NOP $02FF,X
+    }
+}
+
+#[cfg(test)]
+mod wai_and_stp {
+    use std::cell::RefCell;
+
+    use crate::cpu::{emulation_event::EmulationEvent, tests::MemoryMock, CPU};
+
+    #[test]
+    fn should_keep_clock_running_while_waiting_for_interrupt() {
+        let memory = &RefCell::new(MemoryMock::new(&[0xCB, 0xE8])); // WAI; INX
+        let mut cpu = CPU::new_wdc_cmos(memory);
+        cpu.cycle = 0;
+        cpu.program_counter = 0x0000;
+
+        assert_eq!(cpu.execute_next_instruction(), None);
+        assert_eq!(cpu.cycle, 3);
+        assert!(cpu.is_waiting());
+
+        assert_eq!(cpu.execute_next_instruction(), None);
+        assert_eq!(cpu.cycle, 4);
+        assert_eq!(cpu.program_counter, 0x0001);
+        assert_eq!(cpu.index_register_x, 0x00);
+    }
+
+    #[test]
+    fn should_resume_after_wai_without_servicing_masked_irq() {
+        let memory = &RefCell::new(MemoryMock::new(&[0xCB, 0xE8])); // WAI; INX
+        let mut cpu = CPU::new_wdc_cmos(memory);
+        cpu.program_counter = 0x0000;
+        cpu.processor_status.change_interrupt_disable_flag(true);
+
+        cpu.execute_next_instruction();
+        cpu.set_irq_line(true);
+        cpu.execute_next_instruction();
+
+        assert!(!cpu.is_waiting());
+        assert_eq!(cpu.program_counter, 0x0002);
+        assert_eq!(cpu.index_register_x, 0x01);
+    }
+
+    #[test]
+    fn should_stop_until_reset() {
+        let memory = &RefCell::new(MemoryMock::new(&[0xE8, 0xDB, 0xE8])); // INX; STP; INX
+        let mut cpu = CPU::new_wdc_cmos(memory);
+        cpu.program_counter = 0x0000;
+
+        assert_eq!(cpu.execute_until_break(), EmulationEvent::Stop(0x0001));
+        assert_eq!(
+            cpu.execute_next_instruction(),
+            Some(EmulationEvent::Stop(0x0001))
+        );
+        assert!(cpu.is_stopped());
+        assert_eq!(cpu.index_register_x, 0x01);
+
+        cpu.reset();
+
+        assert!(!cpu.is_stopped());
+    }
+
+    #[test]
+    fn should_exist_only_on_wdc_chips() {
+        let memory = &RefCell::new(MemoryMock::new(&[0xDB])); // STP
+        let mut cpu = CPU::new_rockwell_cmos(memory);
+        cpu.program_counter = 0x0000;
+
+        cpu.execute_next_instruction();
+
+        assert!(!cpu.is_stopped());
+    }
+}
@@ -0,0 +1,113 @@
+use crate::{
+    consts::{Byte, Word},
+    cpu::{crosses_page, AddressingMode, Registers, CPU},
+    memory::Bus,
+};
+
+// undocumented NMOS opcodes whose results depend on analog effects inside the chip; they
+// are emulated the way most chips behave, with the unpredictable part of ANE and LXA
+// supplied by the configurable magic constant
+
+fn read_operand<M: Bus>(cpu: &mut CPU<M>, addr_mode: AddressingMode) -> Byte {
+    return match cpu.read_memory(addr_mode) {
+        Some(value) => value,
+        None => panic!("unstable opcode used with incorrect address mode"),
+    };
+}
+
+// the value is ANDed with the high byte of the base address plus one; when indexing crosses
+// a page the stored value also replaces the high byte of the target address
+fn store_and_high_byte<M: Bus>(
+    cpu: &mut CPU<M>,
+    addr_mode: AddressingMode,
+    index: Byte,
+    value: Byte,
+) {
+    let address = match cpu.get_address(addr_mode) {
+        Some(address) => address,
+        None => panic!("unstable store used with incorrect address mode"),
+    };
+    let page_crossed = crosses_page(address, index);
+    // extra cycle to fix address, already spent on page crossing
+    if !page_crossed {
+        cpu.dummy_read(address);
+        cpu.tick();
+    }
+
+    let [_, base_hi] = address.wrapping_sub(index as Word).to_le_bytes();
+    let value = value & base_hi.wrapping_add(1);
+    let target = if page_crossed {
+        Word::from_le_bytes([address.to_le_bytes()[0], value])
+    } else {
+        address
+    };
+
+    cpu.put_into_memory(target, value);
+    cpu.tick();
+}
+
+pub fn ane_im<M: Bus>(cpu: &mut CPU<M>) {
+    let value = read_operand(cpu, AddressingMode::Immediate);
+    let result = (cpu.accumulator | cpu.magic_constant) & cpu.index_register_x & value;
+    cpu.set_register(Registers::Accumulator, result);
+}
+
+pub fn lxa_im<M: Bus>(cpu: &mut CPU<M>) {
+    let value = read_operand(cpu, AddressingMode::Immediate);
+    let result = (cpu.accumulator | cpu.magic_constant) & value;
+    cpu.set_register(Registers::Accumulator, result);
+    cpu.set_register(Registers::IndexX, result);
+}
+
+pub fn sha_ay<M: Bus>(cpu: &mut CPU<M>) {
+    let value = cpu.accumulator & cpu.index_register_x;
+    store_and_high_byte(cpu, AddressingMode::AbsoluteY, cpu.index_register_y, value);
+}
+
+pub fn sha_iny<M: Bus>(cpu: &mut CPU<M>) {
+    let value = cpu.accumulator & cpu.index_register_x;
+    store_and_high_byte(
+        cpu,
+        AddressingMode::IndirectIndexY,
+        cpu.index_register_y,
+        value,
+    );
+}
+
+pub fn tas_ay<M: Bus>(cpu: &mut CPU<M>) {
+    cpu.stack_pointer = cpu.accumulator & cpu.index_register_x;
+    store_and_high_byte(
+        cpu,
+        AddressingMode::AbsoluteY,
+        cpu.index_register_y,
+        cpu.stack_pointer,
+    );
+}
+
+pub fn las_ay<M: Bus>(cpu: &mut CPU<M>) {
+    let value = read_operand(cpu, AddressingMode::AbsoluteY) & cpu.stack_pointer;
+    cpu.stack_pointer = value;
+    cpu.set_register(Registers::Accumulator, value);
+    cpu.set_register(Registers::IndexX, value);
+}
+
+pub fn shy_ax<M: Bus>(cpu: &mut CPU<M>) {
+    store_and_high_byte(
+        cpu,
+        AddressingMode::AbsoluteX,
+        cpu.index_register_x,
+        cpu.index_register_y,
+    );
+}
+
+pub fn shx_ay<M: Bus>(cpu: &mut CPU<M>) {
+    store_and_high_byte(
+        cpu,
+        AddressingMode::AbsoluteY,
+        cpu.index_register_y,
+        cpu.index_register_x,
+    );
+}
+
+#[cfg(test)]
+mod tests;
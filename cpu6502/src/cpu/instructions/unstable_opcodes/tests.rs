@@ -0,0 +1,140 @@
+#[cfg(test)]
+mod ane_and_lxa {
+    use crate::cpu::tests::assert_instruction;
+
+    #[test]
+    fn should_mix_default_magic_constant_into_accumulator() {
+        assert_instruction! {
+            program: [0x8B, 0xFF], // ANE #$FF
+            setup: |cpu| cpu.index_register_x = 0xFF,
+            cycles: 2,
+            registers: { accumulator: 0xEE },
+            status: 0b10000000,
+        };
+    }
+
+    #[test]
+    fn should_use_configured_magic_constant() {
+        assert_instruction! {
+            program: [0x8B, 0xFF], // ANE #$FF
+            setup: |cpu| {
+                cpu.set_magic_constant(0xFF);
+                cpu.index_register_x = 0x0F;
+            },
+            cycles: 2,
+            registers: { accumulator: 0x0F },
+            status: 0b00000000,
+        };
+    }
+
+    #[test]
+    fn should_load_accumulator_and_x_register_through_magic_constant() {
+        assert_instruction! {
+            program: [0xAB, 0x13], // LXA #$13
+            setup: |cpu| cpu.accumulator = 0x01,
+            cycles: 2,
+            registers: { accumulator: 0x03, index_register_x: 0x03 },
+            status: 0b00000000,
+        };
+    }
+}
+
+#[cfg(test)]
+mod high_byte_stores {
+    use crate::cpu::tests::assert_instruction;
+
+    #[test]
+    fn should_store_accumulator_and_x_and_incremented_high_byte() {
+        assert_instruction! {
+            program: [0x9F, 0x00, 0x02], // SHA $0200,Y
+            setup: |cpu| {
+                cpu.accumulator = 0xFF;
+                cpu.index_register_x = 0xFF;
+                cpu.index_register_y = 0x10;
+            },
+            cycles: 5,
+            memory: { 0x0210 => 0x03 },
+        };
+    }
+
+    #[test]
+    fn should_replace_high_byte_of_target_with_stored_value_on_page_crossing() {
+        assert_instruction! {
+            program: [0x9F, 0xF0, 0x02], // SHA $02F0,Y
+            setup: |cpu| {
+                cpu.accumulator = 0x01;
+                cpu.index_register_x = 0xFF;
+                cpu.index_register_y = 0x20;
+            },
+            cycles: 5,
+            memory: { 0x0110 => 0x01, 0x0310 => 0x00 },
+        };
+    }
+
+    #[test]
+    fn should_store_through_indirect_indexed_address() {
+        assert_instruction! {
+            program: [0x93, 0x02, 0x00, 0x02], // SHA ($02),Y
+            setup: |cpu| {
+                cpu.accumulator = 0xFF;
+                cpu.index_register_x = 0xFF;
+                cpu.index_register_y = 0x05;
+            },
+            cycles: 6,
+            memory: { 0x0205 => 0x03 },
+        };
+    }
+
+    #[test]
+    fn should_transfer_accumulator_and_x_to_stack_pointer_before_storing() {
+        assert_instruction! {
+            program: [0x9B, 0x00, 0x0F], // TAS $0F00,Y
+            setup: |cpu| {
+                cpu.accumulator = 0xF0;
+                cpu.index_register_x = 0x3F;
+                cpu.index_register_y = 0x01;
+            },
+            cycles: 5,
+            registers: { stack_pointer: 0x30 },
+            memory: { 0x0F01 => 0x10 },
+        };
+    }
+
+    #[test]
+    fn should_store_index_registers_and_incremented_high_byte() {
+        assert_instruction! {
+            program: [0x9C, 0x00, 0x0F], // SHY $0F00,X
+            setup: |cpu| {
+                cpu.index_register_x = 0x01;
+                cpu.index_register_y = 0xFF;
+            },
+            cycles: 5,
+            memory: { 0x0F01 => 0x10 },
+        };
+        assert_instruction! {
+            program: [0x9E, 0x00, 0x0F], // SHX $0F00,Y
+            setup: |cpu| {
+                cpu.index_register_x = 0x3F;
+                cpu.index_register_y = 0x02;
+            },
+            cycles: 5,
+            memory: { 0x0F02 => 0x10 },
+        };
+    }
+}
+
+#[cfg(test)]
+mod las {
+    use crate::cpu::tests::assert_instruction;
+
+    #[test]
+    fn should_and_memory_with_stack_pointer_into_accumulator_x_and_stack_pointer() {
+        assert_instruction! {
+            program: [0xBB, 0x03, 0x00, 0xF3], // LAS $0003,Y
+            setup: |cpu| cpu.stack_pointer = 0x3F,
+            cycles: 4,
+            registers: { accumulator: 0x33, index_register_x: 0x33, stack_pointer: 0x33 },
+            status: 0b00000000,
+        };
+    }
+}
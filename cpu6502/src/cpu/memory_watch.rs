@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use crate::consts::{Byte, Word};
+
+const HEXDUMP_LINE_LENGTH: usize = 16;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegionDump {
+    pub region_start: Word,
+    pub address: Word,
+    pub value: Byte,
+    pub program_counter: Word,
+    pub cycle: u64,
+    pub contents: Vec<Byte>,
+}
+
+impl RegionDump {
+    pub fn hexdump(&self) -> String {
+        let mut lines: Vec<String> = Vec::new();
+        for (idx, chunk) in self.contents.chunks(HEXDUMP_LINE_LENGTH).enumerate() {
+            let line_addr = self
+                .region_start
+                .wrapping_add((idx * HEXDUMP_LINE_LENGTH) as Word);
+            let bytes: Vec<String> = chunk.iter().map(|byte| format!("{byte:02X}")).collect();
+            lines.push(format!("${line_addr:04X}: {}", bytes.join(" ")));
+        }
+
+        return lines.join("\n");
+    }
+}
+
+impl fmt::Display for RegionDump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "${:04X} <- ${:02X} by ${:04X} at cycle {}",
+            self.address, self.value, self.program_counter, self.cycle
+        )?;
+        return write!(f, "{}", self.hexdump());
+    }
+}
+
+pub enum DumpTarget {
+    Log,
+    Callback(Box<dyn FnMut(&RegionDump) + Send>),
+}
+
+struct RegionWatch {
+    region: RangeInclusive<Word>,
+    target: DumpTarget,
+    log: Vec<RegionDump>,
+}
+
+// regions dumped on every write into them, to a log or a callback, while execution goes on;
+// watchpoints are the ones that pause it
+#[derive(Default)]
+pub struct MemoryWatches {
+    watches: HashMap<usize, RegionWatch>,
+    next_id: usize,
+}
+
+impl MemoryWatches {
+    pub fn is_empty(&self) -> bool {
+        return self.watches.is_empty();
+    }
+
+    pub fn add(&mut self, region: RangeInclusive<Word>, target: DumpTarget) -> usize {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.watches.insert(
+            id,
+            RegionWatch {
+                region,
+                target,
+                log: Vec::new(),
+            },
+        );
+
+        return id;
+    }
+
+    pub fn remove(&mut self, id: usize) -> bool {
+        return self.watches.remove(&id).is_some();
+    }
+
+    pub fn get_log(&self, id: usize) -> Option<&[RegionDump]> {
+        return self.watches.get(&id).map(|watch| watch.log.as_slice());
+    }
+
+    pub fn regions_containing(&self, addr: Word) -> Vec<(usize, RangeInclusive<Word>)> {
+        return self
+            .watches
+            .iter()
+            .filter(|(_, watch)| watch.region.contains(&addr))
+            .map(|(id, watch)| (*id, watch.region.clone()))
+            .collect();
+    }
+
+    pub fn notify(&mut self, id: usize, dump: RegionDump) {
+        let watch = match self.watches.get_mut(&id) {
+            Some(watch) => watch,
+            None => return,
+        };
+
+        match &mut watch.target {
+            DumpTarget::Log => watch.log.push(dump),
+            DumpTarget::Callback(cb) => cb(&dump),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
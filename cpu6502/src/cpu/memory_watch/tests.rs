@@ -0,0 +1,133 @@
+#[cfg(test)]
+mod hexdump {
+    use crate::cpu::memory_watch::RegionDump;
+
+    #[test]
+    fn should_dump_sixteen_bytes_per_line_prefixed_with_line_address() {
+        let dump = RegionDump {
+            region_start: 0x0300,
+            address: 0x0301,
+            value: 0xAB,
+            program_counter: 0x0600,
+            cycle: 12,
+            contents: (0..18).collect(),
+        };
+
+        assert_eq!(
+            dump.hexdump(),
+            concat!(
+                "$0300: 00 01 02 03 04 05 06 07 08 09 0A 0B 0C 0D 0E 0F\n",
+                "$0310: 10 11"
+            )
+        );
+    }
+
+    #[test]
+    fn should_display_writing_program_counter_and_cycle_before_hexdump() {
+        let dump = RegionDump {
+            region_start: 0x0300,
+            address: 0x0301,
+            value: 0xAB,
+            program_counter: 0x0600,
+            cycle: 12,
+            contents: vec![0x00, 0xAB],
+        };
+
+        assert_eq!(
+            dump.to_string(),
+            "$0301 <- $AB by $0600 at cycle 12\n$0300: 00 AB"
+        );
+    }
+}
+
+#[cfg(all(test, feature = "memory-watch"))]
+mod cpu_integration {
+    use std::cell::RefCell;
+    use std::sync::{Arc, Mutex};
+
+    use crate::cpu::{
+        memory_watch::{DumpTarget, RegionDump},
+        tests::MemoryMock,
+        CPU,
+    };
+
+    #[test]
+    fn should_log_dump_of_region_when_byte_in_it_changes() {
+        let memory = &RefCell::new(MemoryMock::new(&[0x85, 0x11])); // STA $11
+        let mut cpu = CPU::new_nmos(memory);
+        cpu.program_counter = 0x0000;
+        cpu.cycle = 0;
+        cpu.accumulator = 0x42;
+        let id = cpu.watch_region(0x0010..=0x0013, DumpTarget::Log);
+
+        cpu.execute_next_instruction();
+
+        let dumps = cpu.get_region_dumps(id).unwrap();
+        assert_eq!(dumps.len(), 1);
+        assert_eq!(dumps[0].address, 0x0011);
+        assert_eq!(dumps[0].program_counter, 0x0000);
+        assert_eq!(dumps[0].cycle, 2);
+        assert_eq!(dumps[0].contents, vec![0x00, 0x42, 0x00, 0x00]);
+    }
+
+    #[test]
+    fn should_not_dump_region_when_written_value_is_unchanged() {
+        let memory = &RefCell::new(MemoryMock::new(&[0x85, 0x11])); // STA $11
+        let mut cpu = CPU::new_nmos(memory);
+        cpu.program_counter = 0x0000;
+        cpu.accumulator = 0x00;
+        let id = cpu.watch_region(0x0010..=0x0013, DumpTarget::Log);
+
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.get_region_dumps(id).unwrap().len(), 0);
+    }
+
+    #[test]
+    fn should_not_dump_region_when_write_lands_outside_of_it() {
+        let memory = &RefCell::new(MemoryMock::new(&[0x85, 0x20])); // STA $20
+        let mut cpu = CPU::new_nmos(memory);
+        cpu.program_counter = 0x0000;
+        cpu.accumulator = 0x42;
+        let id = cpu.watch_region(0x0010..=0x0013, DumpTarget::Log);
+
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.get_region_dumps(id).unwrap().len(), 0);
+    }
+
+    #[test]
+    fn should_pass_dump_to_callback() {
+        let memory = &RefCell::new(MemoryMock::new(&[0x85, 0x11])); // STA $11
+        let mut cpu = CPU::new_nmos(memory);
+        cpu.program_counter = 0x0000;
+        cpu.accumulator = 0x42;
+        let received: Arc<Mutex<Vec<RegionDump>>> = Arc::new(Mutex::new(Vec::new()));
+        let sink = received.clone();
+        cpu.watch_region(
+            0x0011..=0x0011,
+            DumpTarget::Callback(Box::new(move |dump| {
+                sink.lock().unwrap().push(dump.clone())
+            })),
+        );
+
+        cpu.execute_next_instruction();
+
+        assert_eq!(received.lock().unwrap().len(), 1);
+        assert_eq!(received.lock().unwrap()[0].contents, vec![0x42]);
+    }
+
+    #[test]
+    fn should_stop_dumping_after_region_is_unwatched() {
+        let memory = &RefCell::new(MemoryMock::new(&[0x85, 0x11])); // STA $11
+        let mut cpu = CPU::new_nmos(memory);
+        cpu.program_counter = 0x0000;
+        cpu.accumulator = 0x42;
+        let id = cpu.watch_region(0x0010..=0x0013, DumpTarget::Log);
+
+        assert!(cpu.unwatch_region(id));
+        cpu.execute_next_instruction();
+
+        assert!(cpu.get_region_dumps(id).is_none());
+    }
+}
@@ -0,0 +1,271 @@
+use std::io::{self, BufRead, Write};
+
+use super::assembler::Assembler;
+use super::disassembler::{Disassembler, DisassemblyStyle};
+use super::processor_status::{Flags, ProcessorStatus};
+use super::{ChipVariant, CPU};
+use crate::consts::{Byte, Word};
+use crate::memory::Bus;
+
+const DEFAULT_DISASSEMBLY_LENGTH: usize = 10;
+const DEFAULT_MEMORY_DUMP_LENGTH: usize = 64;
+const MEMORY_DUMP_LINE_LENGTH: usize = 16;
+
+pub struct Monitor {
+    assembler: Assembler,
+    disassembler: Disassembler,
+    assembly_cursor: Option<Word>,
+}
+
+impl Monitor {
+    // assembles and disassembles for the given chip variant, usually that of the monitored CPU
+    pub fn new(chip_variant: ChipVariant) -> Self {
+        return Monitor {
+            assembler: Assembler::new(chip_variant),
+            disassembler: Disassembler::new(chip_variant, DisassemblyStyle::default()),
+            assembly_cursor: None,
+        };
+    }
+
+    pub fn get_disassembler(&mut self) -> &mut Disassembler {
+        return &mut self.disassembler;
+    }
+
+    pub fn is_assembling(&self) -> bool {
+        return self.assembly_cursor.is_some();
+    }
+
+    pub fn prompt(&self) -> String {
+        return match self.assembly_cursor {
+            Some(addr) => format!("a {addr:04X} "),
+            None => String::from(". "),
+        };
+    }
+
+    pub fn execute<M: Bus>(&mut self, cpu: &mut CPU<M>, line: &str) -> Result<String, String> {
+        let line = line.trim();
+        if self.is_assembling() {
+            if line.is_empty() || line == "." {
+                self.assembly_cursor = None;
+                return Ok(String::new());
+            }
+
+            return self.assemble(cpu, line);
+        }
+
+        let (command, args) = match line.split_once(char::is_whitespace) {
+            Some((command, args)) => (command, args.trim()),
+            None => (line, ""),
+        };
+        return match command {
+            "" => Ok(String::new()),
+            "a" => self.start_assembly(cpu, args),
+            "d" => self.disassemble(cpu, args),
+            "f" => set_flag(cpu, args),
+            "m" => self.dump_memory(cpu, args),
+            "r" => set_registers(cpu, args),
+            "s" => self.skip_instruction(cpu),
+            _ => Err(format!("unknown command: {command}")),
+        };
+    }
+
+    pub fn run<M: Bus>(
+        &mut self,
+        cpu: &mut CPU<M>,
+        input: &mut dyn BufRead,
+        output: &mut dyn Write,
+    ) -> io::Result<()> {
+        loop {
+            write!(output, "{}", self.prompt())?;
+            output.flush()?;
+
+            let mut line = String::new();
+            if input.read_line(&mut line)? == 0 {
+                return Ok(());
+            }
+            if !self.is_assembling() && line.trim() == "x" {
+                return Ok(());
+            }
+
+            match self.execute(cpu, &line) {
+                Ok(result) if result.is_empty() => (),
+                Ok(result) => writeln!(output, "{result}")?,
+                Err(err) => writeln!(output, "error: {err}")?,
+            }
+        }
+    }
+
+    // "a <addr>" enters assembly mode, an optional instruction after the address is assembled
+    // right away; the cursor then moves past every assembled instruction until an empty line
+    fn start_assembly<M: Bus>(&mut self, cpu: &mut CPU<M>, args: &str) -> Result<String, String> {
+        let (addr, instruction) = match args.split_once(char::is_whitespace) {
+            Some((addr, instruction)) => (addr, instruction.trim()),
+            None => (args, ""),
+        };
+        self.assembly_cursor = Some(parse_address(addr)?);
+        if instruction.is_empty() {
+            return Ok(String::new());
+        }
+
+        return self.assemble(cpu, instruction);
+    }
+
+    fn assemble<M: Bus>(&mut self, cpu: &mut CPU<M>, instruction: &str) -> Result<String, String> {
+        let addr = match self.assembly_cursor {
+            Some(addr) => addr,
+            None => return Err(String::from("not in assembly mode")),
+        };
+        let bytes = self.assembler.assemble_instruction(instruction, addr)?;
+        {
+            let memory = &mut cpu.memory;
+            for (idx, byte) in bytes.iter().enumerate() {
+                memory.poke(addr.wrapping_add(idx as Word), *byte);
+            }
+        }
+        self.assembly_cursor = Some(addr.wrapping_add(bytes.len() as Word));
+
+        let memory = &mut cpu.memory;
+        let decoded = self.disassembler.decode(memory, addr);
+        return Ok(self.disassembler.format_line(&decoded));
+    }
+
+    fn disassemble<M: Bus>(&self, cpu: &mut CPU<M>, args: &str) -> Result<String, String> {
+        let (start, count) =
+            parse_range_args(args, cpu.program_counter, DEFAULT_DISASSEMBLY_LENGTH)?;
+        let memory = &mut cpu.memory;
+
+        return Ok(self.disassembler.listing(memory, start, count));
+    }
+
+    fn skip_instruction<M: Bus>(&self, cpu: &mut CPU<M>) -> Result<String, String> {
+        let skipped = {
+            let memory = &mut cpu.memory;
+            let decoded = self.disassembler.decode(memory, cpu.program_counter);
+            self.disassembler.format_line(&decoded)
+        };
+        cpu.skip_instruction();
+
+        return Ok(format!("skipped {skipped}\n{}", format_registers(cpu)));
+    }
+
+    fn dump_memory<M: Bus>(&self, cpu: &mut CPU<M>, args: &str) -> Result<String, String> {
+        let (start, length) = parse_range_args(args, 0x0000, DEFAULT_MEMORY_DUMP_LENGTH)?;
+        let memory = &mut cpu.memory;
+
+        let mut lines: Vec<String> = Vec::new();
+        for line_start in (0..length).step_by(MEMORY_DUMP_LINE_LENGTH) {
+            let line_addr = start.wrapping_add(line_start as Word);
+            let line_length = MEMORY_DUMP_LINE_LENGTH.min(length - line_start);
+            let bytes: Vec<String> = (0..line_length)
+                .map(|idx| format!("{:02X}", memory.peek(line_addr.wrapping_add(idx as Word))))
+                .collect();
+            lines.push(format!("${line_addr:04X}: {}", bytes.join(" ")));
+        }
+
+        return Ok(lines.join("\n"));
+    }
+}
+
+fn format_registers<M: Bus>(cpu: &CPU<M>) -> String {
+    return format!(
+        "PC={:04X} A={:02X} X={:02X} Y={:02X} SP={:02X} P={}",
+        cpu.program_counter,
+        cpu.accumulator,
+        cpu.index_register_x,
+        cpu.index_register_y,
+        cpu.stack_pointer,
+        cpu.processor_status
+    );
+}
+
+// "r" alone prints registers, "r a 42 pc 0600 p Nv-bdizc" sets each named register first
+fn set_registers<M: Bus>(cpu: &mut CPU<M>, args: &str) -> Result<String, String> {
+    let args: Vec<&str> = args.split_whitespace().collect();
+    for assignment in args.chunks(2) {
+        let (register, value) = match assignment {
+            [register, value] => (register.to_ascii_lowercase(), *value),
+            [register] => return Err(format!("missing value for register: {register}")),
+            _ => unreachable!(),
+        };
+        match register.as_str() {
+            "pc" => cpu.program_counter = parse_address(value)?,
+            "a" => cpu.accumulator = parse_byte(value)?,
+            "x" => cpu.index_register_x = parse_byte(value)?,
+            "y" => cpu.index_register_y = parse_byte(value)?,
+            "sp" => cpu.stack_pointer = parse_byte(value)?,
+            "p" => match value.parse::<ProcessorStatus>() {
+                Ok(status) => cpu.processor_status = status,
+                Err(_) => cpu.processor_status.set(parse_byte(value)?),
+            },
+            _ => return Err(format!("unknown register: {register}")),
+        }
+    }
+
+    return Ok(format_registers(cpu));
+}
+
+// "f <letter> <0|1>" with letters as in NV-BDIZC
+fn set_flag<M: Bus>(cpu: &mut CPU<M>, args: &str) -> Result<String, String> {
+    let (letter, value) = match args.split_once(char::is_whitespace) {
+        Some((letter, value)) => (letter.to_ascii_uppercase(), value.trim()),
+        None => return Err(String::from("expected flag and value")),
+    };
+    let flag = match letter.as_str() {
+        "N" => Flags::Negative,
+        "V" => Flags::Overflow,
+        "B" => Flags::Break,
+        "D" => Flags::DecimalMode,
+        "I" => Flags::InterruptDisable,
+        "Z" => Flags::Zero,
+        "C" => Flags::Carry,
+        _ => return Err(format!("unknown flag: {letter}")),
+    };
+    let value_set = match value {
+        "0" => false,
+        "1" => true,
+        _ => return Err(format!("incorrect flag value: {value}")),
+    };
+    cpu.processor_status.change_flag(flag, value_set);
+
+    return Ok(format_registers(cpu));
+}
+
+fn parse_byte(text: &str) -> Result<Byte, String> {
+    let digits = text.trim_start_matches('$');
+    return match Byte::from_str_radix(digits, 16) {
+        Ok(value) => Ok(value),
+        Err(_) => Err(format!("incorrect value: {text}")),
+    };
+}
+
+fn parse_address(text: &str) -> Result<Word, String> {
+    let digits = text.trim_start_matches('$');
+    return match Word::from_str_radix(digits, 16) {
+        Ok(addr) => Ok(addr),
+        Err(_) => Err(format!("incorrect address: {text}")),
+    };
+}
+
+fn parse_range_args(
+    args: &str,
+    default_start: Word,
+    default_length: usize,
+) -> Result<(Word, usize), String> {
+    let mut args = args.split_whitespace();
+    let start = match args.next() {
+        Some(addr) => parse_address(addr)?,
+        None => default_start,
+    };
+    let length = match args.next() {
+        Some(length) => match usize::from_str_radix(length.trim_start_matches('$'), 16) {
+            Ok(length) => length,
+            Err(_) => return Err(format!("incorrect length: {length}")),
+        },
+        None => default_length,
+    };
+
+    return Ok((start, length));
+}
+
+#[cfg(test)]
+mod tests;
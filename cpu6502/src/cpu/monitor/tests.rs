@@ -0,0 +1,262 @@
+#[cfg(test)]
+mod assemble_command {
+    use std::cell::RefCell;
+
+    use crate::cpu::{monitor::Monitor, tests::MemoryMock, CPU};
+
+    #[test]
+    fn should_assemble_instructions_at_moving_cursor() {
+        let memory = &RefCell::new(MemoryMock::default());
+        let mut cpu = CPU::new_nmos(memory);
+        let mut monitor = Monitor::new(cpu.get_chip_variant());
+
+        monitor.execute(&mut cpu, "a 1000").unwrap();
+        monitor.execute(&mut cpu, "LDA #$01").unwrap();
+        monitor.execute(&mut cpu, "STA $0200").unwrap();
+        assert_eq!(monitor.prompt(), "a 1005 ");
+        monitor.execute(&mut cpu, "").unwrap();
+
+        let memory = memory.borrow();
+        assert_eq!(
+            [
+                memory[0x1000],
+                memory[0x1001],
+                memory[0x1002],
+                memory[0x1003],
+                memory[0x1004]
+            ],
+            [0xA9, 0x01, 0x8D, 0x00, 0x02]
+        );
+        assert!(!monitor.is_assembling());
+    }
+
+    #[test]
+    fn should_assemble_instruction_given_together_with_address() {
+        let memory = &RefCell::new(MemoryMock::default());
+        let mut cpu = CPU::new_nmos(memory);
+        let mut monitor = Monitor::new(cpu.get_chip_variant());
+
+        let result = monitor.execute(&mut cpu, "a 1000 INX");
+
+        assert_eq!(
+            result,
+            Ok(String::from("INX                     ; $1000: E8"))
+        );
+        assert_eq!(memory.borrow()[0x1000], 0xE8);
+        assert_eq!(monitor.prompt(), "a 1001 ");
+    }
+
+    #[test]
+    fn should_assemble_instructions_of_monitored_chip_variant() {
+        let memory = &RefCell::new(MemoryMock::default());
+        let mut cpu = CPU::new_rockwell_cmos(memory);
+        let mut monitor = Monitor::new(cpu.get_chip_variant());
+
+        monitor.execute(&mut cpu, "a 1000").unwrap();
+        let results = [
+            monitor.execute(&mut cpu, "STZ $10"),
+            monitor.execute(&mut cpu, "PHX"),
+            monitor.execute(&mut cpu, "SMB0 $10"),
+        ];
+
+        assert_eq!(
+            results,
+            [
+                Ok(String::from("STZ $10                 ; $1000: 64 10")),
+                Ok(String::from("PHX                     ; $1002: DA")),
+                Ok(String::from("SMB0 $10                ; $1003: 87 10")),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_keep_cursor_in_place_when_instruction_is_incorrect() {
+        let memory = &RefCell::new(MemoryMock::default());
+        let mut cpu = CPU::new_nmos(memory);
+        let mut monitor = Monitor::new(cpu.get_chip_variant());
+
+        monitor.execute(&mut cpu, "a 1000").unwrap();
+        let result = monitor.execute(&mut cpu, "LDA (");
+
+        assert!(result.is_err());
+        assert_eq!(monitor.prompt(), "a 1000 ");
+    }
+}
+
+#[cfg(test)]
+mod other_commands {
+    use std::cell::RefCell;
+
+    use crate::cpu::{monitor::Monitor, tests::MemoryMock, CPU};
+
+    #[test]
+    fn should_disassemble_memory() {
+        let memory = &RefCell::new(MemoryMock::new(&[0xA9, 0x01, 0xEA]));
+        let mut cpu = CPU::new_nmos(memory);
+        let mut monitor = Monitor::new(cpu.get_chip_variant());
+
+        let result = monitor.execute(&mut cpu, "d 0000 2");
+
+        assert_eq!(
+            result,
+            Ok(String::from(
+                "LDA #$01                ; $0000: A9 01\nNOP                     ; $0002: EA"
+            ))
+        );
+    }
+
+    #[test]
+    fn should_disassemble_memory_as_monitored_chip_variant() {
+        let memory = &RefCell::new(MemoryMock::new(&[0x80, 0x10, 0x64, 0x10, 0xDA]));
+        let mut cpu = CPU::new_cmos(memory);
+        let mut monitor = Monitor::new(cpu.get_chip_variant());
+
+        let result = monitor.execute(&mut cpu, "d 0000 3");
+
+        assert_eq!(
+            result,
+            Ok(String::from(concat!(
+                "BRA $0012               ; $0000: 80 10\n",
+                "STZ $10                 ; $0002: 64 10\n",
+                "PHX                     ; $0004: DA"
+            )))
+        );
+    }
+
+    #[test]
+    fn should_dump_memory() {
+        let memory = &RefCell::new(MemoryMock::default());
+        let mut cpu = CPU::new_nmos(memory);
+        let mut monitor = Monitor::new(cpu.get_chip_variant());
+
+        let result = monitor.execute(&mut cpu, "m $0000 5");
+
+        assert_eq!(result, Ok(String::from("$0000: 44 51 88 42 99")));
+    }
+
+    #[test]
+    fn should_print_registers() {
+        let memory = &RefCell::new(MemoryMock::default());
+        let mut cpu = CPU::new_nmos(memory);
+        cpu.program_counter = 0x0600;
+        cpu.accumulator = 0x42;
+        let mut monitor = Monitor::new(cpu.get_chip_variant());
+
+        let result = monitor.execute(&mut cpu, "r");
+
+        assert_eq!(
+            result,
+            Ok(String::from("PC=0600 A=42 X=00 Y=00 SP=FD P=nv-bdIzc"))
+        );
+    }
+
+    #[test]
+    fn should_reject_unknown_command() {
+        let memory = &RefCell::new(MemoryMock::default());
+        let mut cpu = CPU::new_nmos(memory);
+        let mut monitor = Monitor::new(cpu.get_chip_variant());
+
+        assert_eq!(
+            monitor.execute(&mut cpu, "q"),
+            Err(String::from("unknown command: q"))
+        );
+    }
+}
+
+#[cfg(test)]
+mod state_editing_commands {
+    use std::cell::RefCell;
+
+    use crate::cpu::{monitor::Monitor, tests::MemoryMock, CPU};
+
+    #[test]
+    fn should_set_named_registers() {
+        let memory = &RefCell::new(MemoryMock::default());
+        let mut cpu = CPU::new_nmos(memory);
+        let mut monitor = Monitor::new(cpu.get_chip_variant());
+
+        let result = monitor.execute(&mut cpu, "r a 42 X $10 pc 0600 sp fd p Nv-bdiZc");
+
+        assert_eq!(
+            result,
+            Ok(String::from("PC=0600 A=42 X=10 Y=00 SP=FD P=Nv-bdiZc"))
+        );
+        assert_eq!(cpu.get_processor_status(), 0b10000010);
+    }
+
+    #[test]
+    fn should_reject_unknown_register_or_missing_value() {
+        let memory = &RefCell::new(MemoryMock::default());
+        let mut cpu = CPU::new_nmos(memory);
+        let mut monitor = Monitor::new(cpu.get_chip_variant());
+
+        assert_eq!(
+            monitor.execute(&mut cpu, "r q 01"),
+            Err(String::from("unknown register: q"))
+        );
+        assert_eq!(
+            monitor.execute(&mut cpu, "r a"),
+            Err(String::from("missing value for register: a"))
+        );
+    }
+
+    #[test]
+    fn should_set_single_flag() {
+        let memory = &RefCell::new(MemoryMock::default());
+        let mut cpu = CPU::new_nmos(memory);
+        let mut monitor = Monitor::new(cpu.get_chip_variant());
+
+        monitor.execute(&mut cpu, "f c 1").unwrap();
+        monitor.execute(&mut cpu, "f V 1").unwrap();
+        monitor.execute(&mut cpu, "f c 0").unwrap();
+
+        assert_eq!(cpu.get_flags().to_string(), "nV-bdIzc");
+        assert_eq!(
+            monitor.execute(&mut cpu, "f c 2"),
+            Err(String::from("incorrect flag value: 2"))
+        );
+    }
+
+    #[test]
+    fn should_skip_instruction_without_executing_it() {
+        let memory = &RefCell::new(MemoryMock::new(&[0xA9, 0x01, 0xE8]));
+        let mut cpu = CPU::new_nmos(memory);
+        cpu.program_counter = 0x0000;
+        cpu.cycle = 0;
+        let mut monitor = Monitor::new(cpu.get_chip_variant());
+
+        let result = monitor.execute(&mut cpu, "s");
+
+        assert_eq!(
+            result,
+            Ok(String::from(
+                "skipped LDA #$01                ; $0000: A9 01\nPC=0002 A=00 X=00 Y=00 SP=FD P=nv-bdIzc"
+            ))
+        );
+        assert_eq!(cpu.get_cycle(), 0);
+    }
+}
+
+#[cfg(test)]
+mod run {
+    use std::cell::RefCell;
+
+    use crate::cpu::{monitor::Monitor, tests::MemoryMock, CPU};
+
+    #[test]
+    fn should_read_commands_until_exit_and_print_prompts() {
+        let memory = &RefCell::new(MemoryMock::default());
+        let mut cpu = CPU::new_nmos(memory);
+        let mut monitor = Monitor::new(cpu.get_chip_variant());
+        let mut input = "a 2000\nNOP\n\nx\n".as_bytes();
+        let mut output: Vec<u8> = Vec::new();
+
+        monitor.run(&mut cpu, &mut input, &mut output).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            ". a 2000 NOP                     ; $2000: EA\na 2001 . "
+        );
+        assert_eq!(memory.borrow()[0x2000], 0xEA);
+    }
+}
@@ -0,0 +1,91 @@
+use std::fmt;
+
+use super::instructions::{get_opcode_metadata, MetadataTable};
+use super::ChipVariant;
+use crate::consts::Byte;
+
+// counts executions of every opcode byte, so each addressing mode variant of an instruction
+// is tracked on its own
+pub struct OpcodeCoverage {
+    executions: [u64; 256],
+    supported: Vec<Byte>,
+    metadata: MetadataTable,
+}
+
+impl OpcodeCoverage {
+    // `supported` lists the opcodes the report should account for, they are described the way
+    // the given chip variant decodes them
+    pub fn new(chip_variant: ChipVariant, supported: impl IntoIterator<Item = Byte>) -> Self {
+        let mut supported: Vec<Byte> = supported.into_iter().collect();
+        supported.sort();
+        supported.dedup();
+
+        return OpcodeCoverage {
+            executions: [0; 256],
+            supported,
+            metadata: get_opcode_metadata(chip_variant),
+        };
+    }
+
+    pub fn record(&mut self, opcode: Byte) {
+        self.executions[opcode as usize] += 1;
+    }
+
+    pub fn get_execution_count(&self, opcode: Byte) -> u64 {
+        return self.executions[opcode as usize];
+    }
+
+    pub fn is_executed(&self, opcode: Byte) -> bool {
+        return self.get_execution_count(opcode) > 0;
+    }
+
+    pub fn executed(&self) -> Vec<Byte> {
+        return self
+            .supported
+            .iter()
+            .copied()
+            .filter(|opcode| self.is_executed(*opcode))
+            .collect();
+    }
+
+    pub fn never_executed(&self) -> Vec<Byte> {
+        return self
+            .supported
+            .iter()
+            .copied()
+            .filter(|opcode| !self.is_executed(*opcode))
+            .collect();
+    }
+
+    pub fn reset(&mut self) {
+        self.executions = [0; 256];
+    }
+}
+
+// summary line followed by every never executed opcode with its mnemonic and addressing mode
+impl fmt::Display for OpcodeCoverage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let never_executed = self.never_executed();
+        writeln!(
+            f,
+            "{}/{} opcodes executed",
+            self.supported.len() - never_executed.len(),
+            self.supported.len()
+        )?;
+        for opcode in never_executed {
+            match self.metadata[opcode as usize] {
+                Some(metadata) => writeln!(
+                    f,
+                    "${opcode:02X} {} {:?}",
+                    metadata.mnemonic, metadata.addressing_mode
+                )?,
+                None => writeln!(f, "${opcode:02X}")?,
+            }
+        }
+
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,109 @@
+#[cfg(test)]
+mod never_executed {
+    use crate::cpu::{opcode_coverage::OpcodeCoverage, ChipVariant};
+
+    #[test]
+    fn should_list_supported_opcodes_that_were_not_recorded() {
+        let mut coverage = OpcodeCoverage::new(ChipVariant::NMOS, [0xA9, 0xAD, 0xE8]);
+
+        coverage.record(0xAD);
+        coverage.record(0xAD);
+
+        assert_eq!(coverage.never_executed(), vec![0xA9, 0xE8]);
+        assert_eq!(coverage.executed(), vec![0xAD]);
+        assert_eq!(coverage.get_execution_count(0xAD), 2);
+    }
+
+    #[test]
+    fn should_forget_executions_on_reset() {
+        let mut coverage = OpcodeCoverage::new(ChipVariant::NMOS, [0xE8]);
+        coverage.record(0xE8);
+
+        coverage.reset();
+
+        assert_eq!(coverage.never_executed(), vec![0xE8]);
+    }
+}
+
+#[cfg(test)]
+mod display {
+    use crate::cpu::{opcode_coverage::OpcodeCoverage, ChipVariant};
+
+    #[test]
+    fn should_summarize_and_describe_never_executed_opcodes() {
+        let mut coverage = OpcodeCoverage::new(ChipVariant::NMOS, [0xA9, 0xBD, 0xE8]);
+        coverage.record(0xE8);
+
+        assert_eq!(
+            coverage.to_string(),
+            "1/3 opcodes executed\n$A9 LDA Immediate\n$BD LDA AbsoluteX\n"
+        );
+    }
+
+    #[test]
+    fn should_describe_opcodes_as_chip_variant_decodes_them() {
+        let nmos = OpcodeCoverage::new(ChipVariant::NMOS, [0x80, 0xDA]);
+        let cmos = OpcodeCoverage::new(ChipVariant::CMOS, [0x80, 0xDA]);
+
+        assert_eq!(
+            nmos.to_string(),
+            "0/2 opcodes executed\n$80 NOP Immediate\n$DA NOP Implicit\n"
+        );
+        assert_eq!(
+            cmos.to_string(),
+            "0/2 opcodes executed\n$80 BRA Relative\n$DA PHX Implicit\n"
+        );
+    }
+}
+
+#[cfg(all(test, feature = "opcode-coverage"))]
+mod cpu_integration {
+    use std::cell::RefCell;
+
+    use crate::cpu::{tests::MemoryMock, undocumented_opcodes::UndocumentedOpcodes, CPU};
+
+    #[test]
+    fn should_record_opcodes_executed_by_cpu() {
+        let memory = &RefCell::new(MemoryMock::new(&[
+            0xA9, 0x01, // LDA #$01
+            0xE8, // INX
+            0xE8, // INX
+        ]));
+        let mut cpu = CPU::new_nmos(memory);
+        cpu.program_counter = 0x0000;
+        cpu.enable_opcode_coverage();
+
+        cpu.execute_next_instruction();
+        cpu.execute_next_instruction();
+        cpu.execute_next_instruction();
+
+        let coverage = cpu.get_opcode_coverage().unwrap();
+        assert_eq!(coverage.get_execution_count(0xA9), 1);
+        assert_eq!(coverage.get_execution_count(0xE8), 2);
+        assert!(coverage.never_executed().contains(&0xAD));
+        assert!(!coverage.never_executed().contains(&0xE8));
+    }
+
+    #[test]
+    fn should_account_for_opcodes_of_cpu_chip_variant() {
+        let memory = &RefCell::new(MemoryMock::default());
+        let mut cpu = CPU::new_wdc_cmos(memory);
+        cpu.enable_opcode_coverage();
+
+        let coverage = cpu.get_opcode_coverage().unwrap();
+        assert_eq!(coverage.never_executed().len(), 256);
+        assert!(coverage.to_string().contains("$CB WAI Implicit\n"));
+    }
+
+    #[test]
+    fn should_account_only_for_supported_opcodes() {
+        let memory = &RefCell::new(MemoryMock::default());
+        let mut cpu = CPU::new_nmos(memory);
+        cpu.set_undocumented_opcodes(UndocumentedOpcodes::all_disabled());
+        cpu.enable_opcode_coverage();
+
+        let never_executed = cpu.get_opcode_coverage().unwrap().never_executed();
+        assert!(never_executed.contains(&0xEA)); // NOP
+        assert!(!never_executed.contains(&0x02)); // JAM
+    }
+}
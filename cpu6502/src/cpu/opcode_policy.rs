@@ -0,0 +1,19 @@
+use crate::consts::{Byte, Word};
+
+// what the CPU does when it fetches an opcode it has no handler for, or one that is switched
+// off in the undocumented opcode matrix
+#[derive(Default)]
+pub enum UnknownOpcodePolicy {
+    #[default]
+    Fault,
+    Panic,
+    // operands known from opcode metadata are skipped so the next instruction stays in sync
+    TreatAsNop,
+    Jam,
+    // receives the opcode and its address; returning an address resumes execution there,
+    // None stops with a fault
+    Callback(Box<dyn FnMut(Byte, Word) -> Option<Word> + Send>),
+}
+
+#[cfg(test)]
+mod tests;
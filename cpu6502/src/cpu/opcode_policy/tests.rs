@@ -0,0 +1,98 @@
+#[cfg(test)]
+mod cpu_integration {
+    use std::cell::RefCell;
+
+    use crate::cpu::{
+        emulation_event::EmulationEvent, opcode_policy::UnknownOpcodePolicy, tests::MemoryMock,
+        undocumented_opcodes::UndocumentedOpcodes, CPU,
+    };
+
+    // LAX $02 is unsupported once undocumented opcodes are disabled
+    const PROGRAM: [u8; 3] = [0xA7, 0x02, 0xE8];
+
+    fn new_cpu(memory: &RefCell<MemoryMock>) -> CPU<&RefCell<MemoryMock>> {
+        let mut cpu = CPU::new_nmos(memory);
+        cpu.cycle = 0;
+        cpu.program_counter = 0x0000;
+        cpu.set_undocumented_opcodes(UndocumentedOpcodes::all_disabled());
+
+        return cpu;
+    }
+
+    #[test]
+    fn should_fault_by_default() {
+        let memory = &RefCell::new(MemoryMock::new(&PROGRAM));
+        let mut cpu = new_cpu(memory);
+
+        assert_eq!(
+            cpu.execute_next_instruction(),
+            Some(EmulationEvent::Fault {
+                address: 0x0000,
+                opcode: 0xA7
+            })
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "unsupported opcode $A7 at $0000")]
+    fn should_panic_when_configured() {
+        let memory = &RefCell::new(MemoryMock::new(&PROGRAM));
+        let mut cpu = new_cpu(memory);
+        cpu.set_unknown_opcode_policy(UnknownOpcodePolicy::Panic);
+
+        cpu.execute_next_instruction();
+    }
+
+    #[test]
+    fn should_skip_operands_when_treated_as_nop() {
+        let memory = &RefCell::new(MemoryMock::new(&PROGRAM));
+        let mut cpu = new_cpu(memory);
+        cpu.set_unknown_opcode_policy(UnknownOpcodePolicy::TreatAsNop);
+
+        assert_eq!(cpu.execute_next_instruction(), None);
+        assert_eq!(cpu.program_counter, 0x0002);
+        assert_eq!(cpu.cycle, 2);
+        assert_eq!(cpu.execute_next_instruction(), None);
+        assert_eq!(cpu.index_register_x, 0x01);
+    }
+
+    #[test]
+    fn should_jam_when_configured() {
+        let memory = &RefCell::new(MemoryMock::new(&PROGRAM));
+        let mut cpu = new_cpu(memory);
+        cpu.set_unknown_opcode_policy(UnknownOpcodePolicy::Jam);
+
+        cpu.execute_next_instruction();
+
+        assert!(cpu.is_jammed());
+    }
+
+    #[test]
+    fn should_resume_at_address_returned_by_callback() {
+        let memory = &RefCell::new(MemoryMock::new(&PROGRAM));
+        let mut cpu = new_cpu(memory);
+        cpu.set_unknown_opcode_policy(UnknownOpcodePolicy::Callback(Box::new(
+            |opcode, address| {
+                if opcode == 0xA7 {
+                    return Some(address + 2);
+                }
+                return None;
+            },
+        )));
+
+        assert_eq!(cpu.execute_next_instruction(), None);
+        assert_eq!(cpu.program_counter, 0x0002);
+    }
+
+    #[test]
+    fn should_fault_when_callback_declines() {
+        let memory = &RefCell::new(MemoryMock::new(&PROGRAM));
+        let mut cpu = new_cpu(memory);
+        cpu.set_unknown_opcode_policy(UnknownOpcodePolicy::Callback(Box::new(|_, _| None)));
+
+        assert!(matches!(
+            cpu.execute_next_instruction(),
+            Some(EmulationEvent::Fault { .. })
+        ));
+    }
+}
@@ -1,3 +1,4 @@
+use super::AddressingMode;
 use crate::consts::Byte;
 
 pub const ADC_IM: Byte = 0x69;
@@ -8,14 +9,16 @@ pub const ADC_AX: Byte = 0x7D;
 pub const ADC_AY: Byte = 0x79;
 pub const ADC_INX: Byte = 0x61;
 pub const ADC_INY: Byte = 0x71;
-pub const AND_IM: Byte = 0x49;
-pub const AND_ZP: Byte = 0x45;
-pub const AND_ZPX: Byte = 0x55;
-pub const AND_A: Byte = 0x4D;
-pub const AND_AX: Byte = 0x5D;
-pub const AND_AY: Byte = 0x59;
-pub const AND_INX: Byte = 0x41;
-pub const AND_INY: Byte = 0x51;
+pub const ADC_IZP: Byte = 0x72;
+pub const AND_IM: Byte = 0x29;
+pub const AND_ZP: Byte = 0x25;
+pub const AND_ZPX: Byte = 0x35;
+pub const AND_A: Byte = 0x2D;
+pub const AND_AX: Byte = 0x3D;
+pub const AND_AY: Byte = 0x39;
+pub const AND_INX: Byte = 0x21;
+pub const AND_INY: Byte = 0x31;
+pub const AND_IZP: Byte = 0x32;
 pub const ASL_ACC: Byte = 0x0A;
 pub const ASL_ZP: Byte = 0x06;
 pub const ASL_ZPX: Byte = 0x16;
@@ -26,9 +29,45 @@ pub const BCS: Byte = 0xB0;
 pub const BEQ: Byte = 0xF0;
 pub const BIT_ZP: Byte = 0x24;
 pub const BIT_A: Byte = 0x2C;
+pub const BIT_IM: Byte = 0x89;
+pub const BIT_ZPX: Byte = 0x34;
+pub const BIT_AX: Byte = 0x3C;
 pub const BMI: Byte = 0x30;
 pub const BNE: Byte = 0xD0;
 pub const BPL: Byte = 0x10;
+pub const BRA: Byte = 0x80;
+pub const BBR0: Byte = 0x0F;
+pub const BBR1: Byte = 0x1F;
+pub const BBR2: Byte = 0x2F;
+pub const BBR3: Byte = 0x3F;
+pub const BBR4: Byte = 0x4F;
+pub const BBR5: Byte = 0x5F;
+pub const BBR6: Byte = 0x6F;
+pub const BBR7: Byte = 0x7F;
+pub const BBS0: Byte = 0x8F;
+pub const BBS1: Byte = 0x9F;
+pub const BBS2: Byte = 0xAF;
+pub const BBS3: Byte = 0xBF;
+pub const BBS4: Byte = 0xCF;
+pub const BBS5: Byte = 0xDF;
+pub const BBS6: Byte = 0xEF;
+pub const BBS7: Byte = 0xFF;
+pub const RMB0: Byte = 0x07;
+pub const RMB1: Byte = 0x17;
+pub const RMB2: Byte = 0x27;
+pub const RMB3: Byte = 0x37;
+pub const RMB4: Byte = 0x47;
+pub const RMB5: Byte = 0x57;
+pub const RMB6: Byte = 0x67;
+pub const RMB7: Byte = 0x77;
+pub const SMB0: Byte = 0x87;
+pub const SMB1: Byte = 0x97;
+pub const SMB2: Byte = 0xA7;
+pub const SMB3: Byte = 0xB7;
+pub const SMB4: Byte = 0xC7;
+pub const SMB5: Byte = 0xD7;
+pub const SMB6: Byte = 0xE7;
+pub const SMB7: Byte = 0xF7;
 pub const BRK: Byte = 0x00;
 pub const BVC: Byte = 0x50;
 pub const BVS: Byte = 0x70;
@@ -44,6 +83,7 @@ pub const CMP_AX: Byte = 0xDD;
 pub const CMP_AY: Byte = 0xD9;
 pub const CMP_INX: Byte = 0xC1;
 pub const CMP_INY: Byte = 0xD1;
+pub const CMP_IZP: Byte = 0xD2;
 pub const CPX_IM: Byte = 0xE0;
 pub const CPX_ZP: Byte = 0xE4;
 pub const CPX_A: Byte = 0xEC;
@@ -54,25 +94,35 @@ pub const DEC_A: Byte = 0xCE;
 pub const DEC_AX: Byte = 0xDE;
 pub const DEC_ZP: Byte = 0xC6;
 pub const DEC_ZPX: Byte = 0xD6;
+pub const DEC_ACC: Byte = 0x3A;
 pub const DEX_IM: Byte = 0xCA;
 pub const DEY_IM: Byte = 0x88;
-pub const EOR_IM: Byte = 0x29;
-pub const EOR_ZP: Byte = 0x25;
-pub const EOR_ZPX: Byte = 0x35;
-pub const EOR_A: Byte = 0x2D;
-pub const EOR_AX: Byte = 0x3D;
-pub const EOR_AY: Byte = 0x39;
-pub const EOR_INX: Byte = 0x21;
-pub const EOR_INY: Byte = 0x31;
+pub const EOR_IM: Byte = 0x49;
+pub const EOR_ZP: Byte = 0x45;
+pub const EOR_ZPX: Byte = 0x55;
+pub const EOR_A: Byte = 0x4D;
+pub const EOR_AX: Byte = 0x5D;
+pub const EOR_AY: Byte = 0x59;
+pub const EOR_INX: Byte = 0x41;
+pub const EOR_INY: Byte = 0x51;
+pub const EOR_IZP: Byte = 0x52;
 pub const INC_ZP: Byte = 0xE6;
 pub const INC_ZPX: Byte = 0xF6;
 pub const INC_A: Byte = 0xEE;
 pub const INC_AX: Byte = 0xFE;
+pub const INC_ACC: Byte = 0x1A;
 pub const INX_IM: Byte = 0xE8;
 pub const INY_IM: Byte = 0xC8;
 pub const JMP_A: Byte = 0x4C;
 pub const JMP_IN: Byte = 0x6C;
+pub const JMP_AINX: Byte = 0x7C;
 pub const JSR_A: Byte = 0x20;
+pub const LAX_ZP: Byte = 0xA7;
+pub const LAX_ZPY: Byte = 0xB7;
+pub const LAX_A: Byte = 0xAF;
+pub const LAX_AY: Byte = 0xBF;
+pub const LAX_INX: Byte = 0xA3;
+pub const LAX_INY: Byte = 0xB3;
 pub const LDA_IM: Byte = 0xA9;
 pub const LDA_ZP: Byte = 0xA5;
 pub const LDA_ZPX: Byte = 0xB5;
@@ -81,6 +131,7 @@ pub const LDA_AX: Byte = 0xBD;
 pub const LDA_AY: Byte = 0xB9;
 pub const LDA_INX: Byte = 0xA1;
 pub const LDA_INY: Byte = 0xB1;
+pub const LDA_IZP: Byte = 0xB2;
 pub const LDY_IM: Byte = 0xA0;
 pub const LDY_ZP: Byte = 0xA4;
 pub const LDY_ZPX: Byte = 0xB4;
@@ -105,10 +156,15 @@ pub const ORA_AX: Byte = 0x1D;
 pub const ORA_AY: Byte = 0x19;
 pub const ORA_INX: Byte = 0x01;
 pub const ORA_INY: Byte = 0x11;
+pub const ORA_IZP: Byte = 0x12;
 pub const PHA: Byte = 0x48;
 pub const PHP: Byte = 0x08;
+pub const PHX: Byte = 0xDA;
+pub const PHY: Byte = 0x5A;
 pub const PLA: Byte = 0x68;
 pub const PLP: Byte = 0x28;
+pub const PLX: Byte = 0xFA;
+pub const PLY: Byte = 0x7A;
 pub const ROL_ACC: Byte = 0x2A;
 pub const ROL_ZP: Byte = 0x26;
 pub const ROL_ZPX: Byte = 0x36;
@@ -121,6 +177,10 @@ pub const ROR_A: Byte = 0x6E;
 pub const ROR_AX: Byte = 0x7E;
 pub const RTI: Byte = 0x40;
 pub const RTS: Byte = 0x60;
+pub const SAX_ZP: Byte = 0x87;
+pub const SAX_ZPY: Byte = 0x97;
+pub const SAX_A: Byte = 0x8F;
+pub const SAX_INX: Byte = 0x83;
 pub const STA_ZP: Byte = 0x85;
 pub const STA_ZPX: Byte = 0x95;
 pub const STA_A: Byte = 0x8D;
@@ -128,12 +188,18 @@ pub const STA_AX: Byte = 0x9D;
 pub const STA_AY: Byte = 0x99;
 pub const STA_INX: Byte = 0x81;
 pub const STA_INY: Byte = 0x91;
+pub const STA_IZP: Byte = 0x92;
 pub const STX_ZP: Byte = 0x86;
 pub const STX_ZPY: Byte = 0x96;
 pub const STX_A: Byte = 0x8E;
 pub const STY_ZP: Byte = 0x84;
 pub const STY_ZPX: Byte = 0x94;
 pub const STY_A: Byte = 0x8C;
+pub const STP: Byte = 0xDB;
+pub const STZ_ZP: Byte = 0x64;
+pub const STZ_ZPX: Byte = 0x74;
+pub const STZ_A: Byte = 0x9C;
+pub const STZ_AX: Byte = 0x9E;
 pub const SEC: Byte = 0x38;
 pub const SED: Byte = 0xF8;
 pub const SEI: Byte = 0x78;
@@ -145,9 +211,213 @@ pub const SBC_AX: Byte = 0xFD;
 pub const SBC_AY: Byte = 0xF9;
 pub const SBC_INX: Byte = 0xE1;
 pub const SBC_INY: Byte = 0xF1;
+pub const SBC_IZP: Byte = 0xF2;
 pub const TAX: Byte = 0xAA;
 pub const TAY: Byte = 0xA8;
+pub const TRB_ZP: Byte = 0x14;
+pub const TRB_A: Byte = 0x1C;
+pub const TSB_ZP: Byte = 0x04;
+pub const TSB_A: Byte = 0x0C;
 pub const TSX: Byte = 0xBA;
 pub const TXA: Byte = 0x8A;
 pub const TXS: Byte = 0x9A;
 pub const TYA: Byte = 0x98;
+pub const WAI: Byte = 0xCB;
+// 65CE02 additions, most of them reuse opcodes that are NOPs on the 65C02
+pub const ASR_ACC: Byte = 0x43;
+pub const ASR_ZP: Byte = 0x44;
+pub const ASR_ZPX: Byte = 0x54;
+pub const ASW_A: Byte = 0xCB;
+pub const AUG: Byte = 0x5C;
+pub const BCC_W: Byte = 0x93;
+pub const BCS_W: Byte = 0xB3;
+pub const BEQ_W: Byte = 0xF3;
+pub const BMI_W: Byte = 0x33;
+pub const BNE_W: Byte = 0xD3;
+pub const BPL_W: Byte = 0x13;
+pub const BRA_W: Byte = 0x83;
+pub const BSR_W: Byte = 0x63;
+pub const BVC_W: Byte = 0x53;
+pub const BVS_W: Byte = 0x73;
+pub const CLE: Byte = 0x02;
+pub const CPZ_IM: Byte = 0xC2;
+pub const CPZ_ZP: Byte = 0xD4;
+pub const CPZ_A: Byte = 0xDC;
+pub const DEW_ZP: Byte = 0xC3;
+pub const DEZ: Byte = 0x3B;
+pub const INW_ZP: Byte = 0xE3;
+pub const INZ: Byte = 0x1B;
+pub const JSR_IN: Byte = 0x22;
+pub const JSR_AINX: Byte = 0x23;
+pub const LDA_ISY: Byte = 0xE2;
+pub const LDZ_IM: Byte = 0xA3;
+pub const LDZ_A: Byte = 0xAB;
+pub const LDZ_AX: Byte = 0xBB;
+pub const NEG_ACC: Byte = 0x42;
+pub const PHW_IM: Byte = 0xF4;
+pub const PHW_A: Byte = 0xFC;
+pub const PHZ: Byte = 0xDB;
+pub const PLZ: Byte = 0xFB;
+pub const ROW_A: Byte = 0xEB;
+pub const RTS_IM: Byte = 0x62;
+pub const SEE: Byte = 0x03;
+pub const STA_ISY: Byte = 0x82;
+pub const STX_AY: Byte = 0x9B;
+pub const STY_AX: Byte = 0x8B;
+pub const TAB: Byte = 0x5B;
+pub const TAZ: Byte = 0x4B;
+pub const TBA: Byte = 0x7B;
+pub const TSY: Byte = 0x0B;
+pub const TYS: Byte = 0x2B;
+pub const TZA: Byte = 0x6B;
+pub const SLO_ZP: Byte = 0x07;
+pub const SLO_ZPX: Byte = 0x17;
+pub const SLO_A: Byte = 0x0F;
+pub const SLO_AX: Byte = 0x1F;
+pub const SLO_AY: Byte = 0x1B;
+pub const SLO_INX: Byte = 0x03;
+pub const SLO_INY: Byte = 0x13;
+pub const RLA_ZP: Byte = 0x27;
+pub const RLA_ZPX: Byte = 0x37;
+pub const RLA_A: Byte = 0x2F;
+pub const RLA_AX: Byte = 0x3F;
+pub const RLA_AY: Byte = 0x3B;
+pub const RLA_INX: Byte = 0x23;
+pub const RLA_INY: Byte = 0x33;
+pub const SRE_ZP: Byte = 0x47;
+pub const SRE_ZPX: Byte = 0x57;
+pub const SRE_A: Byte = 0x4F;
+pub const SRE_AX: Byte = 0x5F;
+pub const SRE_AY: Byte = 0x5B;
+pub const SRE_INX: Byte = 0x43;
+pub const SRE_INY: Byte = 0x53;
+pub const RRA_ZP: Byte = 0x67;
+pub const RRA_ZPX: Byte = 0x77;
+pub const RRA_A: Byte = 0x6F;
+pub const RRA_AX: Byte = 0x7F;
+pub const RRA_AY: Byte = 0x7B;
+pub const RRA_INX: Byte = 0x63;
+pub const RRA_INY: Byte = 0x73;
+pub const DCP_ZP: Byte = 0xC7;
+pub const DCP_ZPX: Byte = 0xD7;
+pub const DCP_A: Byte = 0xCF;
+pub const DCP_AX: Byte = 0xDF;
+pub const DCP_AY: Byte = 0xDB;
+pub const DCP_INX: Byte = 0xC3;
+pub const DCP_INY: Byte = 0xD3;
+pub const ISC_ZP: Byte = 0xE7;
+pub const ISC_ZPX: Byte = 0xF7;
+pub const ISC_A: Byte = 0xEF;
+pub const ISC_AX: Byte = 0xFF;
+pub const ISC_AY: Byte = 0xFB;
+pub const ISC_INX: Byte = 0xE3;
+pub const ISC_INY: Byte = 0xF3;
+pub const ANC_IM: Byte = 0x0B;
+pub const ANC_IM_ALT: Byte = 0x2B;
+pub const ALR_IM: Byte = 0x4B;
+pub const ARR_IM: Byte = 0x6B;
+pub const SBX_IM: Byte = 0xCB;
+pub const ANE_IM: Byte = 0x8B;
+pub const LXA_IM: Byte = 0xAB;
+pub const SHA_AY: Byte = 0x9F;
+pub const SHA_INY: Byte = 0x93;
+pub const TAS_AY: Byte = 0x9B;
+pub const LAS_AY: Byte = 0xBB;
+pub const SHY_AX: Byte = 0x9C;
+pub const SHX_AY: Byte = 0x9E;
+pub const JAM_02: Byte = 0x02;
+pub const JAM_12: Byte = 0x12;
+pub const JAM_22: Byte = 0x22;
+pub const JAM_32: Byte = 0x32;
+pub const JAM_42: Byte = 0x42;
+pub const JAM_52: Byte = 0x52;
+pub const JAM_62: Byte = 0x62;
+pub const JAM_72: Byte = 0x72;
+pub const JAM_92: Byte = 0x92;
+pub const JAM_B2: Byte = 0xB2;
+pub const JAM_D2: Byte = 0xD2;
+pub const JAM_F2: Byte = 0xF2;
+pub const NOP_1A: Byte = 0x1A;
+pub const NOP_3A: Byte = 0x3A;
+pub const NOP_5A: Byte = 0x5A;
+pub const NOP_7A: Byte = 0x7A;
+pub const NOP_DA: Byte = 0xDA;
+pub const NOP_FA: Byte = 0xFA;
+pub const NOP_IM_80: Byte = 0x80;
+pub const NOP_IM_82: Byte = 0x82;
+pub const NOP_IM_89: Byte = 0x89;
+pub const NOP_IM_C2: Byte = 0xC2;
+pub const NOP_IM_E2: Byte = 0xE2;
+pub const NOP_ZP_04: Byte = 0x04;
+pub const NOP_ZP_44: Byte = 0x44;
+pub const NOP_ZP_64: Byte = 0x64;
+pub const NOP_ZPX_14: Byte = 0x14;
+pub const NOP_ZPX_34: Byte = 0x34;
+pub const NOP_ZPX_54: Byte = 0x54;
+pub const NOP_ZPX_74: Byte = 0x74;
+pub const NOP_ZPX_D4: Byte = 0xD4;
+pub const NOP_ZPX_F4: Byte = 0xF4;
+pub const NOP_A_0C: Byte = 0x0C;
+pub const NOP_AX_1C: Byte = 0x1C;
+pub const NOP_AX_3C: Byte = 0x3C;
+pub const NOP_AX_5C: Byte = 0x5C;
+pub const NOP_AX_7C: Byte = 0x7C;
+pub const NOP_AX_DC: Byte = 0xDC;
+pub const NOP_AX_FC: Byte = 0xFC;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct OpcodeMetadata {
+    pub mnemonic: &'static str,
+    pub addressing_mode: AddressingMode,
+    length: u8,
+}
+
+impl OpcodeMetadata {
+    pub const fn new(mnemonic: &'static str, addressing_mode: AddressingMode) -> Self {
+        return OpcodeMetadata {
+            mnemonic,
+            addressing_mode,
+            length: operand_length(addressing_mode) + 1,
+        };
+    }
+
+    // for instructions whose length does not follow from the addressing mode
+    pub const fn with_length(
+        mnemonic: &'static str,
+        addressing_mode: AddressingMode,
+        length: u8,
+    ) -> Self {
+        return OpcodeMetadata {
+            mnemonic,
+            addressing_mode,
+            length,
+        };
+    }
+
+    pub fn length(&self) -> u8 {
+        return self.length;
+    }
+}
+
+const fn operand_length(addressing_mode: AddressingMode) -> u8 {
+    return match addressing_mode {
+        AddressingMode::Accumulator | AddressingMode::Implicit => 0,
+        AddressingMode::Immediate
+        | AddressingMode::Relative
+        | AddressingMode::ZeroPage
+        | AddressingMode::ZeroPageX
+        | AddressingMode::ZeroPageY
+        | AddressingMode::IndexIndirectX
+        | AddressingMode::IndirectIndexY
+        | AddressingMode::ZeroPageIndirect
+        | AddressingMode::StackIndirectIndexY => 1,
+        AddressingMode::Absolute
+        | AddressingMode::AbsoluteX
+        | AddressingMode::AbsoluteY
+        | AddressingMode::Indirect
+        | AddressingMode::AbsoluteIndexIndirectX
+        | AddressingMode::ZeroPageRelative
+        | AddressingMode::RelativeWord
+        | AddressingMode::ImmediateWord => 2,
+    };
+}
@@ -0,0 +1,13 @@
+use crate::consts::Byte;
+
+// what reading an address that `Bus::is_mapped` reports as unmapped returns; nothing drives
+// the data bus then, so it usually still holds the last value that was on it
+#[derive(Default, Clone, Copy, PartialEq, Debug)]
+pub enum OpenBus {
+    #[default]
+    LastValue,
+    Constant(Byte),
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,57 @@
+#[cfg(test)]
+mod cpu_integration {
+    use std::cell::RefCell;
+
+    use crate::{
+        cpu::{open_bus::OpenBus, CPU},
+        memory::{Bus, PartiallyMappedMemory, VecMemory},
+    };
+
+    // RAM in the first page only, $4000 is left unmapped
+    fn memory_with_program(program: &[u8]) -> RefCell<PartiallyMappedMemory<VecMemory>> {
+        let mut ram = VecMemory::new();
+        ram.insert(0x0000, program);
+        ram.data[0x4000] = 0x99;
+
+        return RefCell::new(PartiallyMappedMemory::new(ram, vec![0x0000..=0x00FF]));
+    }
+
+    #[test]
+    fn should_read_last_value_on_bus_from_unmapped_address_by_default() {
+        let memory = memory_with_program(&[0xAD, 0x00, 0x40]); // LDA $4000
+        let mut cpu = CPU::new_nmos(&memory);
+        cpu.program_counter = 0x0000;
+
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.get_open_bus(), OpenBus::LastValue);
+        assert_eq!(cpu.accumulator, 0x40);
+    }
+
+    #[test]
+    fn should_read_configured_constant_from_unmapped_address() {
+        let memory = memory_with_program(&[0xAD, 0x00, 0x40]); // LDA $4000
+        let mut cpu = CPU::new_nmos(&memory);
+        cpu.program_counter = 0x0000;
+        cpu.set_open_bus(OpenBus::Constant(0xFF));
+
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.accumulator, 0xFF);
+    }
+
+    #[test]
+    fn should_read_last_written_value_from_unmapped_address() {
+        // STX $10; LDA $4000
+        let memory = memory_with_program(&[0x86, 0x10, 0xAD, 0x00, 0x40]);
+        let mut cpu = CPU::new_nmos(&memory);
+        cpu.program_counter = 0x0000;
+        cpu.index_register_x = 0x42;
+
+        cpu.execute_next_instruction();
+        cpu.execute_next_instruction();
+
+        assert_eq!(memory.borrow_mut().read(0x0010), 0x42);
+        assert_eq!(cpu.accumulator, 0x40);
+    }
+}
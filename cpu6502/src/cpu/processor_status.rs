@@ -1,5 +1,10 @@
+use std::fmt;
+use std::str::FromStr;
+
 use crate::consts::Byte;
 
+pub(super) const FLAG_LETTERS: [char; 8] = ['N', 'V', '-', 'B', 'D', 'I', 'Z', 'C'];
+
 pub enum Flags {
     Carry = 0,
     Zero = 1,
@@ -122,3 +127,59 @@ impl ProcessorStatus {
         return (self.flags & (1 << shift)) > 0;
     }
 }
+
+// "NV-BDIZC" from bit 7 down to bit 0, set flags uppercase and clear flags lowercase
+impl fmt::Display for ProcessorStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text: String = FLAG_LETTERS
+            .iter()
+            .enumerate()
+            .map(|(idx, letter)| {
+                let is_set = self.flags & (0b10000000 >> idx) > 0;
+                match (letter, is_set) {
+                    ('-', _) => '-',
+                    (letter, true) => *letter,
+                    (letter, false) => letter.to_ascii_lowercase(),
+                }
+            })
+            .collect();
+
+        return write!(f, "{text}");
+    }
+}
+
+impl FromStr for ProcessorStatus {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let chars: Vec<char> = text.chars().collect();
+        if chars.len() != FLAG_LETTERS.len() {
+            return Err(format!("expected 8 flags in NV-BDIZC order: {text}"));
+        }
+
+        let mut flags: Byte = 0;
+        for (idx, (letter, expected)) in chars.iter().zip(FLAG_LETTERS).enumerate() {
+            if expected == '-' {
+                if *letter != '-' {
+                    return Err(format!(
+                        "unexpected {letter} in place of unused bit: {text}"
+                    ));
+                }
+                continue;
+            }
+            if letter.to_ascii_uppercase() != expected {
+                return Err(format!(
+                    "unexpected {letter} in place of {expected}: {text}"
+                ));
+            }
+            if letter.is_ascii_uppercase() {
+                flags |= 0b10000000 >> idx;
+            }
+        }
+
+        return Ok(ProcessorStatus { flags });
+    }
+}
+
+#[cfg(test)]
+mod tests;
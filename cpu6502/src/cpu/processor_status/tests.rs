@@ -0,0 +1,51 @@
+#[cfg(test)]
+mod display {
+    use crate::cpu::processor_status::ProcessorStatus;
+
+    #[test]
+    fn should_print_set_flags_uppercase_and_clear_flags_lowercase() {
+        assert_eq!(ProcessorStatus::from(0b10000011).to_string(), "Nv-bdiZC");
+        assert_eq!(ProcessorStatus::from(0b00000000).to_string(), "nv-bdizc");
+        assert_eq!(ProcessorStatus::from(0b11111111).to_string(), "NV-BDIZC");
+    }
+}
+
+#[cfg(test)]
+mod from_str {
+    use crate::cpu::processor_status::ProcessorStatus;
+
+    #[test]
+    fn should_parse_flags_from_letters() {
+        let status: ProcessorStatus = "Nv-bdiZC".parse().unwrap();
+
+        assert_eq!(status, 0b10000011);
+    }
+
+    #[test]
+    fn should_round_trip_every_value_except_unused_bit() {
+        for value in 0..=0xFF {
+            let status = ProcessorStatus::from(value);
+            let parsed: ProcessorStatus = status.to_string().parse().unwrap();
+
+            assert_eq!(parsed, value & 0b11011111);
+        }
+    }
+
+    #[test]
+    fn should_reject_incorrect_flag_strings() {
+        assert_eq!(
+            "NV-BDIZ".parse::<ProcessorStatus>(),
+            Err(String::from("expected 8 flags in NV-BDIZC order: NV-BDIZ"))
+        );
+        assert_eq!(
+            "NVXBDIZC".parse::<ProcessorStatus>(),
+            Err(String::from(
+                "unexpected X in place of unused bit: NVXBDIZC"
+            ))
+        );
+        assert_eq!(
+            "VN-BDIZC".parse::<ProcessorStatus>(),
+            Err(String::from("unexpected V in place of N: VN-BDIZC"))
+        );
+    }
+}
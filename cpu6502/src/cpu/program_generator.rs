@@ -0,0 +1,200 @@
+use super::instructions::get_opcode_metadata;
+use super::opcodes::OpcodeMetadata;
+use super::undocumented_opcodes::UndocumentedClass;
+use super::{AddressingMode, ChipVariant, CPU};
+use crate::{
+    consts::{Byte, Word},
+    memory::Bus,
+};
+
+// instructions leaving the straight-line stream would make the program length unbounded,
+// those stopping the clock would end it early
+const CONTROL_FLOW_MNEMONICS: [&str; 8] = ["BRK", "BSR", "JMP", "JSR", "RTI", "RTS", "STP", "WAI"];
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct GeneratorConfig {
+    pub seed: u64,
+    pub origin: Word,
+    pub instruction_count: usize,
+    // programs use only the instructions of this variant
+    pub chip_variant: ChipVariant,
+    pub include_undocumented: bool,
+    pub include_jams: bool,
+}
+
+impl Default for GeneratorConfig {
+    fn default() -> Self {
+        return GeneratorConfig {
+            seed: 0x6502,
+            origin: 0x8000,
+            instruction_count: 64,
+            chip_variant: ChipVariant::NMOS,
+            include_undocumented: false,
+            include_jams: false,
+        };
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct TraceEntry {
+    pub program_counter: Word,
+    pub accumulator: Byte,
+    pub index_register_x: Byte,
+    pub index_register_y: Byte,
+    pub stack_pointer: Byte,
+    pub processor_status: Byte,
+    pub cycle: u64,
+}
+
+impl TraceEntry {
+    pub fn capture<M: Bus>(cpu: &CPU<M>) -> Self {
+        return TraceEntry {
+            program_counter: cpu.program_counter,
+            accumulator: cpu.accumulator,
+            index_register_x: cpu.index_register_x,
+            index_register_y: cpu.index_register_y,
+            stack_pointer: cpu.stack_pointer,
+            processor_status: cpu.processor_status.into(),
+            cycle: cpu.cycle,
+        };
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct GeneratedProgram {
+    pub origin: Word,
+    pub code: Vec<Byte>,
+    pub instruction_count: usize,
+}
+
+impl GeneratedProgram {
+    pub fn load_into(&self, memory: &mut dyn Bus) {
+        for (idx, byte) in self.code.iter().enumerate() {
+            memory.poke(self.origin.wrapping_add(idx as Word), *byte);
+        }
+    }
+
+    // runs the program on an independent reference core
+    pub fn expected_trace(&self, reference: &mut dyn ReferenceCore) -> Vec<TraceEntry> {
+        reference.load(self.origin, &self.code);
+
+        return (0..self.instruction_count)
+            .map(|_| reference.step())
+            .collect();
+    }
+}
+
+// a second implementation the core under test is checked against, e.g. another emulator
+// wrapped to report its state after every instruction
+pub trait ReferenceCore {
+    // places the code at the origin and points the program counter at it
+    fn load(&mut self, origin: Word, code: &[Byte]);
+    fn step(&mut self) -> TraceEntry;
+}
+
+// index of the first instruction after which the traces differ; the expected trace may come
+// from a reference core as well as from a recording made on real hardware
+pub fn first_divergence(trace: &[TraceEntry], expected: &[TraceEntry]) -> Option<usize> {
+    let divergence = trace
+        .iter()
+        .zip(expected)
+        .position(|(entry, expected)| entry != expected);
+    if divergence.is_some() || trace.len() == expected.len() {
+        return divergence;
+    }
+
+    return Some(trace.len().min(expected.len()));
+}
+
+// records the state after each instruction, stopping early on any emulation event
+pub fn record_trace<M: Bus>(cpu: &mut CPU<M>, steps: usize) -> Vec<TraceEntry> {
+    let mut trace = Vec::with_capacity(steps);
+    for _ in 0..steps {
+        let event = cpu.execute_next_instruction();
+        trace.push(TraceEntry::capture(cpu));
+        if event.is_some() {
+            break;
+        }
+    }
+
+    return trace;
+}
+
+pub struct ProgramGenerator {
+    config: GeneratorConfig,
+    opcodes: Vec<(Byte, OpcodeMetadata)>,
+    state: u64,
+}
+
+impl ProgramGenerator {
+    pub fn new(config: GeneratorConfig) -> Self {
+        let chip_variant = config.chip_variant;
+        let opcodes: Vec<(Byte, OpcodeMetadata)> = get_opcode_metadata(chip_variant)
+            .into_iter()
+            .enumerate()
+            .filter_map(|(opcode, metadata)| Some((opcode as Byte, metadata?)))
+            .filter(|(_, metadata)| !CONTROL_FLOW_MNEMONICS.contains(&metadata.mnemonic))
+            .filter(
+                |(opcode, metadata)| match UndocumentedClass::classify(*opcode) {
+                    None => true,
+                    // CMOS chips turned some of these into instructions and the rest into NOPs
+                    Some(_) if !chip_variant.is_nmos() => {
+                        metadata.mnemonic != "NOP" || config.include_undocumented
+                    }
+                    Some(UndocumentedClass::Jam) => config.include_jams,
+                    Some(_) => config.include_undocumented,
+                },
+            )
+            .collect();
+
+        return ProgramGenerator {
+            config,
+            opcodes,
+            state: config.seed.max(1),
+        };
+    }
+
+    pub fn generate(&mut self) -> GeneratedProgram {
+        let mut code = Vec::new();
+        for _ in 0..self.config.instruction_count {
+            let idx = self.next_below(self.opcodes.len());
+            let (opcode, metadata) = self.opcodes[idx];
+            code.push(opcode);
+            let operand_length = metadata.length() as usize - 1;
+            match metadata.addressing_mode {
+                // branch to the next instruction either way, so both outcomes stay in the stream
+                AddressingMode::Relative => code.push(0x00),
+                AddressingMode::ZeroPageRelative => {
+                    code.extend_from_slice(&[self.next_u64() as Byte, 0x00])
+                }
+                // word branches are relative to the last byte of the instruction
+                AddressingMode::RelativeWord => code.extend_from_slice(&[0x01, 0x00]),
+                _ if operand_length > 0 => {
+                    code.extend_from_slice(&self.next_u64().to_le_bytes()[..operand_length])
+                }
+                _ => (),
+            }
+        }
+
+        return GeneratedProgram {
+            origin: self.config.origin,
+            code,
+            instruction_count: self.config.instruction_count,
+        };
+    }
+
+    // xorshift64, good enough for test streams and free of external dependencies
+    fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        return self.state;
+    }
+
+    fn next_below(&mut self, bound: usize) -> usize {
+        return (self.next_u64() % bound as u64) as usize;
+    }
+}
+
+#[cfg(test)]
+mod tests;
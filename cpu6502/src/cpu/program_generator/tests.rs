@@ -0,0 +1,197 @@
+#[cfg(test)]
+mod generate {
+    use crate::{
+        cpu::{
+            disassembler::{Disassembler, DisassemblyStyle},
+            program_generator::{GeneratorConfig, ProgramGenerator},
+            ChipVariant,
+        },
+        memory::VecMemory,
+    };
+
+    #[test]
+    fn should_generate_same_program_for_same_seed() {
+        let config = GeneratorConfig::default();
+
+        let first = ProgramGenerator::new(config).generate();
+        let second = ProgramGenerator::new(config).generate();
+
+        assert_eq!(first, second);
+        assert!(!first.code.is_empty());
+    }
+
+    #[test]
+    fn should_generate_different_programs_for_different_seeds() {
+        let first = ProgramGenerator::new(GeneratorConfig::default()).generate();
+        let second = ProgramGenerator::new(GeneratorConfig {
+            seed: 0x2A03,
+            ..GeneratorConfig::default()
+        })
+        .generate();
+
+        assert_ne!(first.code, second.code);
+    }
+
+    #[test]
+    fn should_never_emit_control_flow_or_unknown_opcodes() {
+        let program = ProgramGenerator::new(GeneratorConfig {
+            instruction_count: 2000,
+            ..GeneratorConfig::default()
+        })
+        .generate();
+        let mut memory = VecMemory::new();
+        program.load_into(&mut memory);
+
+        let instructions = Disassembler::default().disassemble(
+            &mut memory,
+            program.origin,
+            program.instruction_count,
+        );
+
+        assert_eq!(
+            instructions.last().unwrap().next_address(),
+            program.origin + program.code.len() as u16
+        );
+        for instruction in instructions {
+            let mnemonic = instruction.mnemonic().unwrap();
+            assert!(!["BRK", "JMP", "JSR", "RTI", "RTS"].contains(&mnemonic));
+        }
+    }
+
+    #[test]
+    fn should_draw_opcodes_from_chip_variant() {
+        let program = ProgramGenerator::new(GeneratorConfig {
+            instruction_count: 2000,
+            chip_variant: ChipVariant::WDCCMOS,
+            ..GeneratorConfig::default()
+        })
+        .generate();
+        let mut memory = VecMemory::new();
+        program.load_into(&mut memory);
+
+        let instructions = Disassembler::new(ChipVariant::WDCCMOS, DisassemblyStyle::default())
+            .disassemble(&mut memory, program.origin, program.instruction_count);
+
+        assert_eq!(
+            instructions.last().unwrap().next_address(),
+            program.origin + program.code.len() as u16
+        );
+        let mnemonics: Vec<&str> = instructions
+            .iter()
+            .map(|instruction| instruction.mnemonic().unwrap())
+            .collect();
+        assert!(mnemonics.contains(&"STZ"));
+        assert!(mnemonics.iter().any(|mnemonic| mnemonic.starts_with("BB")));
+        assert!(!mnemonics.contains(&"WAI"));
+        assert!(!mnemonics.contains(&"STP"));
+    }
+}
+
+#[cfg(test)]
+mod expected_trace {
+    use std::cell::RefCell;
+
+    use crate::{
+        consts::{Byte, Word},
+        cpu::{
+            program_generator::{
+                first_divergence, record_trace, GeneratorConfig, ProgramGenerator, ReferenceCore,
+                TraceEntry,
+            },
+            CPU,
+        },
+        memory::VecMemory,
+    };
+
+    // replays a recording, standing in for a second emulator
+    struct RecordedCore {
+        origin: Word,
+        code: Vec<Byte>,
+        entries: Vec<TraceEntry>,
+    }
+
+    impl ReferenceCore for RecordedCore {
+        fn load(&mut self, origin: Word, code: &[Byte]) {
+            self.origin = origin;
+            self.code = code.to_vec();
+        }
+
+        fn step(&mut self) -> TraceEntry {
+            return self.entries.remove(0);
+        }
+    }
+
+    fn entry(program_counter: Word, accumulator: Byte) -> TraceEntry {
+        return TraceEntry {
+            program_counter,
+            accumulator,
+            index_register_x: 0,
+            index_register_y: 0,
+            stack_pointer: 0xFD,
+            processor_status: 0x24,
+            cycle: 0,
+        };
+    }
+
+    #[test]
+    fn should_load_program_into_reference_core_and_step_it() {
+        let program = ProgramGenerator::new(GeneratorConfig {
+            instruction_count: 2,
+            ..GeneratorConfig::default()
+        })
+        .generate();
+        let mut reference = RecordedCore {
+            origin: 0,
+            code: vec![],
+            entries: vec![entry(0x8001, 0x01), entry(0x8002, 0x02)],
+        };
+
+        let expected = program.expected_trace(&mut reference);
+
+        assert_eq!(expected, vec![entry(0x8001, 0x01), entry(0x8002, 0x02)]);
+        assert_eq!(reference.origin, program.origin);
+        assert_eq!(reference.code, program.code);
+    }
+
+    #[test]
+    fn should_find_no_divergence_in_equal_traces() {
+        let program = ProgramGenerator::new(GeneratorConfig {
+            seed: 42,
+            ..GeneratorConfig::default()
+        })
+        .generate();
+        let mut memory = VecMemory::new();
+        program.load_into(&mut memory);
+        let memory = RefCell::new(memory);
+        let mut cpu = CPU::new_nmos(&memory);
+        cpu.set_program_counter(program.origin);
+        let trace = record_trace(&mut cpu, program.instruction_count);
+
+        assert_eq!(first_divergence(&trace, &trace.clone()), None);
+    }
+
+    #[test]
+    fn should_report_first_differing_entry() {
+        let trace = vec![
+            entry(0x8001, 0x01),
+            entry(0x8002, 0x02),
+            entry(0x8003, 0x03),
+        ];
+        let expected = vec![
+            entry(0x8001, 0x01),
+            entry(0x8002, 0x12),
+            entry(0x8003, 0x13),
+        ];
+
+        assert_eq!(first_divergence(&trace, &expected), Some(1));
+    }
+
+    #[test]
+    fn should_report_end_of_shorter_trace() {
+        let trace = vec![entry(0x8001, 0x01)];
+        let expected = vec![entry(0x8001, 0x01), entry(0x8002, 0x02)];
+
+        assert_eq!(first_divergence(&trace, &expected), Some(1));
+        assert_eq!(first_divergence(&expected, &trace), Some(1));
+    }
+}
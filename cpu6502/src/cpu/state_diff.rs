@@ -0,0 +1,225 @@
+use std::fmt;
+
+use super::processor_status::{ProcessorStatus, FLAG_LETTERS};
+use super::CPU;
+use crate::consts::{Byte, Word};
+use crate::memory::Bus;
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct CpuSnapshot {
+    pub cycle: u64,
+    pub program_counter: Word,
+    pub stack_pointer: Byte,
+    pub accumulator: Byte,
+    pub index_register_x: Byte,
+    pub index_register_y: Byte,
+    pub processor_status: ProcessorStatus,
+    // 65CE02 state, at reset values on the other chips
+    pub index_register_z: Byte,
+    pub base_page: Byte,
+    pub stack_pointer_hi: Byte,
+    pub extended_stack: bool,
+    pub memory: Vec<Byte>,
+}
+
+impl CpuSnapshot {
+    pub fn capture<M: Bus>(cpu: &mut CPU<M>) -> Self {
+        let memory = &mut cpu.memory;
+        return CpuSnapshot {
+            cycle: cpu.cycle,
+            program_counter: cpu.program_counter,
+            stack_pointer: cpu.stack_pointer,
+            accumulator: cpu.accumulator,
+            index_register_x: cpu.index_register_x,
+            index_register_y: cpu.index_register_y,
+            processor_status: cpu.processor_status,
+            index_register_z: cpu.index_register_z,
+            base_page: cpu.base_page,
+            stack_pointer_hi: cpu.stack_pointer_hi,
+            extended_stack: cpu.extended_stack,
+            memory: (0..=Word::MAX).map(|addr| memory.peek(addr)).collect(),
+        };
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Register {
+    ProgramCounter,
+    StackPointer,
+    Accumulator,
+    IndexRegisterX,
+    IndexRegisterY,
+    IndexRegisterZ,
+    BasePage,
+    StackPointerHi,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct RegisterDifference {
+    pub register: Register,
+    pub expected: Word,
+    pub actual: Word,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct FlagDifference {
+    // letter of the flag as printed in "NV-BDIZC"; 'E' is the 65CE02 extended stack
+    pub flag: char,
+    pub expected: bool,
+    pub actual: bool,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct MemoryDifference {
+    pub address: Word,
+    pub expected: Byte,
+    pub actual: Byte,
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct StateDiff {
+    pub registers: Vec<RegisterDifference>,
+    pub flags: Vec<FlagDifference>,
+    pub cycles: Option<(u64, u64)>,
+    pub memory: Vec<MemoryDifference>,
+}
+
+impl StateDiff {
+    pub fn is_empty(&self) -> bool {
+        return self.registers.is_empty()
+            && self.flags.is_empty()
+            && self.cycles.is_none()
+            && self.memory.is_empty();
+    }
+}
+
+// `expected` is treated as the reference, e.g. the state of a reference core or of a recorded run
+pub fn diff(expected: &CpuSnapshot, actual: &CpuSnapshot) -> StateDiff {
+    let mut report = StateDiff::default();
+
+    let registers = [
+        (
+            Register::ProgramCounter,
+            expected.program_counter,
+            actual.program_counter,
+        ),
+        (
+            Register::StackPointer,
+            expected.stack_pointer as Word,
+            actual.stack_pointer as Word,
+        ),
+        (
+            Register::Accumulator,
+            expected.accumulator as Word,
+            actual.accumulator as Word,
+        ),
+        (
+            Register::IndexRegisterX,
+            expected.index_register_x as Word,
+            actual.index_register_x as Word,
+        ),
+        (
+            Register::IndexRegisterY,
+            expected.index_register_y as Word,
+            actual.index_register_y as Word,
+        ),
+        (
+            Register::IndexRegisterZ,
+            expected.index_register_z as Word,
+            actual.index_register_z as Word,
+        ),
+        (
+            Register::BasePage,
+            expected.base_page as Word,
+            actual.base_page as Word,
+        ),
+        (
+            Register::StackPointerHi,
+            expected.stack_pointer_hi as Word,
+            actual.stack_pointer_hi as Word,
+        ),
+    ];
+    for (register, expected, actual) in registers {
+        if expected != actual {
+            report.registers.push(RegisterDifference {
+                register,
+                expected,
+                actual,
+            });
+        }
+    }
+
+    let expected_status: Byte = expected.processor_status.into();
+    let actual_status: Byte = actual.processor_status.into();
+    for (idx, flag) in FLAG_LETTERS.into_iter().enumerate() {
+        let mask = 0b10000000 >> idx;
+        if flag != '-' && (expected_status ^ actual_status) & mask > 0 {
+            report.flags.push(FlagDifference {
+                flag,
+                expected: expected_status & mask > 0,
+                actual: actual_status & mask > 0,
+            });
+        }
+    }
+    // the 65CE02 keeps E apart from P; a cleared E extends the stack
+    if expected.extended_stack != actual.extended_stack {
+        report.flags.push(FlagDifference {
+            flag: 'E',
+            expected: !expected.extended_stack,
+            actual: !actual.extended_stack,
+        });
+    }
+
+    if expected.cycle != actual.cycle {
+        report.cycles = Some((expected.cycle, actual.cycle));
+    }
+
+    report.memory = expected
+        .memory
+        .iter()
+        .zip(actual.memory.iter())
+        .enumerate()
+        .filter(|(_, (expected, actual))| expected != actual)
+        .map(|(address, (expected, actual))| MemoryDifference {
+            address: address as Word,
+            expected: *expected,
+            actual: *actual,
+        })
+        .collect();
+
+    return report;
+}
+
+impl fmt::Display for StateDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for difference in &self.registers {
+            writeln!(
+                f,
+                "{:?}: expected ${:04X}, actual ${:04X}",
+                difference.register, difference.expected, difference.actual
+            )?;
+        }
+        for difference in &self.flags {
+            writeln!(
+                f,
+                "flag {}: expected {}, actual {}",
+                difference.flag, difference.expected as u8, difference.actual as u8
+            )?;
+        }
+        if let Some((expected, actual)) = self.cycles {
+            writeln!(f, "cycles: expected {expected}, actual {actual}")?;
+        }
+        for difference in &self.memory {
+            writeln!(
+                f,
+                "${:04X}: expected ${:02X}, actual ${:02X}",
+                difference.address, difference.expected, difference.actual
+            )?;
+        }
+
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,126 @@
+#[cfg(test)]
+mod diff {
+    use std::cell::RefCell;
+
+    use crate::cpu::{
+        state_diff::{diff, FlagDifference, MemoryDifference, Register, RegisterDifference},
+        tests::MemoryMock,
+        CPU,
+    };
+
+    #[test]
+    fn should_report_nothing_for_identical_snapshots() {
+        let memory = &RefCell::new(MemoryMock::new(&[0xE8, 0xE8]));
+        let mut cpu = CPU::new_nmos(memory);
+
+        let report = diff(&cpu.snapshot(), &cpu.snapshot());
+
+        assert!(report.is_empty());
+        assert_eq!(report.to_string(), "");
+    }
+
+    #[test]
+    fn should_report_differing_registers_flags_cycles_and_memory() {
+        let memory = &RefCell::new(MemoryMock::new(&[0xE8, 0xE8]));
+        let mut cpu = CPU::new_nmos(memory);
+        let before = cpu.snapshot();
+
+        cpu.accumulator = 0x80;
+        cpu.processor_status.change_negative_flag(true);
+        cpu.cycle = 5;
+        cpu.program_counter = 0x0001;
+        memory.borrow_mut()[0x0002] = 0x42;
+        let report = diff(&before, &cpu.snapshot());
+
+        assert_eq!(
+            report.registers,
+            vec![
+                RegisterDifference {
+                    register: Register::ProgramCounter,
+                    expected: 0x0000,
+                    actual: 0x0001,
+                },
+                RegisterDifference {
+                    register: Register::Accumulator,
+                    expected: 0x00,
+                    actual: 0x80,
+                },
+            ]
+        );
+        assert_eq!(
+            report.flags,
+            vec![FlagDifference {
+                flag: 'N',
+                expected: false,
+                actual: true,
+            }]
+        );
+        assert_eq!(report.cycles, Some((7, 5)));
+        assert_eq!(
+            report.memory,
+            vec![MemoryDifference {
+                address: 0x0002,
+                expected: 0x00,
+                actual: 0x42,
+            }]
+        );
+    }
+
+    #[test]
+    fn should_print_report_line_per_difference() {
+        let memory = &RefCell::new(MemoryMock::new(&[0xE8, 0xE8]));
+        let mut cpu = CPU::new_nmos(memory);
+        let before = cpu.snapshot();
+
+        cpu.index_register_x = 0x01;
+        cpu.processor_status.change_carry_flag(true);
+        let report = diff(&before, &cpu.snapshot());
+
+        assert_eq!(
+            report.to_string(),
+            "IndexRegisterX: expected $0000, actual $0001\nflag C: expected 0, actual 1\n"
+        );
+    }
+
+    #[test]
+    fn should_report_differing_65ce02_registers_and_stack_mode() {
+        let memory = &RefCell::new(MemoryMock::new(&[0xE8, 0xE8]));
+        let mut cpu = CPU::new_65ce02(memory);
+        let before = cpu.snapshot();
+
+        cpu.index_register_z = 0x01;
+        cpu.base_page = 0x20;
+        cpu.stack_pointer_hi = 0x02;
+        cpu.extended_stack = true;
+        let report = diff(&before, &cpu.snapshot());
+
+        assert_eq!(
+            report.registers,
+            vec![
+                RegisterDifference {
+                    register: Register::IndexRegisterZ,
+                    expected: 0x00,
+                    actual: 0x01,
+                },
+                RegisterDifference {
+                    register: Register::BasePage,
+                    expected: 0x00,
+                    actual: 0x20,
+                },
+                RegisterDifference {
+                    register: Register::StackPointerHi,
+                    expected: 0x01,
+                    actual: 0x02,
+                },
+            ]
+        );
+        assert_eq!(
+            report.flags,
+            vec![FlagDifference {
+                flag: 'E',
+                expected: true,
+                actual: false,
+            }]
+        );
+    }
+}
@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+
+use crate::consts::{Byte, Word};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct StackHighWaterMark {
+    pub stack_pointer: Byte,
+    pub program_counter: Word,
+}
+
+impl StackHighWaterMark {
+    // stack grows downwards from $01FF, so the deepest point is the lowest stack pointer
+    pub fn bytes_used(&self) -> usize {
+        return 0xFF - self.stack_pointer as usize;
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum InterruptSource {
+    Break,
+    Irq,
+    Nmi,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct InterruptStatistics {
+    pub break_count: u64,
+    pub irq_count: u64,
+    pub nmi_count: u64,
+    pub cycles_in_handlers: u64,
+    pub max_nesting: usize,
+    handler_entry_cycles: Vec<u64>,
+}
+
+impl InterruptStatistics {
+    pub fn total_count(&self) -> u64 {
+        return self.break_count + self.irq_count + self.nmi_count;
+    }
+
+    pub fn current_nesting(&self) -> usize {
+        return self.handler_entry_cycles.len();
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct BranchStatistics {
+    pub taken: u64,
+    pub not_taken: u64,
+    pub page_crossings: u64,
+}
+
+impl BranchStatistics {
+    pub fn executions(&self) -> u64 {
+        return self.taken + self.not_taken;
+    }
+
+    pub fn taken_ratio(&self) -> f64 {
+        if self.executions() == 0 {
+            return 0.0;
+        }
+
+        return self.taken as f64 / self.executions() as f64;
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Statistics {
+    stack_high_water_mark: Option<StackHighWaterMark>,
+    interrupts: InterruptStatistics,
+    branches: HashMap<Word, BranchStatistics>,
+}
+
+impl Statistics {
+    pub fn new() -> Self {
+        return Statistics::default();
+    }
+
+    pub fn get_stack_high_water_mark(&self) -> Option<StackHighWaterMark> {
+        return self.stack_high_water_mark;
+    }
+
+    pub fn get_interrupt_statistics(&self) -> &InterruptStatistics {
+        return &self.interrupts;
+    }
+
+    pub fn get_branch_statistics(&self) -> &HashMap<Word, BranchStatistics> {
+        return &self.branches;
+    }
+
+    // branch sites sorted by number of executions, hottest first
+    pub fn get_hottest_branches(&self) -> Vec<(Word, BranchStatistics)> {
+        let mut branches: Vec<(Word, BranchStatistics)> = self
+            .branches
+            .iter()
+            .map(|(addr, branch)| (*addr, *branch))
+            .collect();
+        branches.sort_by(|(addr_a, a), (addr_b, b)| {
+            b.executions().cmp(&a.executions()).then(addr_a.cmp(addr_b))
+        });
+
+        return branches;
+    }
+
+    pub fn record_branch(&mut self, site: Word, taken: bool, page_crossed: bool) {
+        let branch = self.branches.entry(site).or_default();
+        if taken {
+            branch.taken += 1;
+        } else {
+            branch.not_taken += 1;
+        }
+        if page_crossed {
+            branch.page_crossings += 1;
+        }
+    }
+
+    pub fn record_interrupt_entry(&mut self, source: InterruptSource, cycle: u64) {
+        match source {
+            InterruptSource::Break => self.interrupts.break_count += 1,
+            InterruptSource::Irq => self.interrupts.irq_count += 1,
+            InterruptSource::Nmi => self.interrupts.nmi_count += 1,
+        }
+        self.interrupts.handler_entry_cycles.push(cycle);
+        self.interrupts.max_nesting = self
+            .interrupts
+            .max_nesting
+            .max(self.interrupts.handler_entry_cycles.len());
+    }
+
+    pub fn record_interrupt_return(&mut self, cycle: u64) {
+        let entry_cycle = match self.interrupts.handler_entry_cycles.pop() {
+            Some(entry_cycle) => entry_cycle,
+            None => return,
+        };
+
+        // nested handlers are already accounted for in the time of the outermost one
+        if self.interrupts.handler_entry_cycles.is_empty() {
+            self.interrupts.cycles_in_handlers += cycle - entry_cycle;
+        }
+    }
+
+    pub fn record_stack_pointer(&mut self, stack_pointer: Byte, program_counter: Word) {
+        let deeper = match self.stack_high_water_mark {
+            Some(mark) => stack_pointer < mark.stack_pointer,
+            None => true,
+        };
+        if !deeper {
+            return;
+        }
+
+        self.stack_high_water_mark = Some(StackHighWaterMark {
+            stack_pointer,
+            program_counter,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,247 @@
+#[cfg(test)]
+mod record_stack_pointer {
+    use crate::cpu::statistics::{StackHighWaterMark, Statistics};
+
+    #[test]
+    fn should_keep_lowest_stack_pointer_with_program_counter_that_reached_it() {
+        let mut statistics = Statistics::new();
+
+        statistics.record_stack_pointer(0xFD, 0x0600);
+        statistics.record_stack_pointer(0xFB, 0x0310);
+        statistics.record_stack_pointer(0xFC, 0x0320);
+
+        assert_eq!(
+            statistics.get_stack_high_water_mark(),
+            Some(StackHighWaterMark {
+                stack_pointer: 0xFB,
+                program_counter: 0x0310,
+            })
+        );
+    }
+
+    #[test]
+    fn should_report_number_of_stack_bytes_used() {
+        let mark = StackHighWaterMark {
+            stack_pointer: 0xF0,
+            program_counter: 0x0600,
+        };
+
+        assert_eq!(mark.bytes_used(), 15);
+    }
+}
+
+#[cfg(all(test, feature = "statistics"))]
+mod cpu_integration {
+    use std::cell::RefCell;
+
+    use crate::cpu::{tests::MemoryMock, CPU};
+
+    #[test]
+    fn should_track_deepest_stack_pointer_reached_during_execution() {
+        let memory = &RefCell::new(MemoryMock::new(&[
+            0x48, 0x48, 0x68, 0x48, // PHA; PHA; PLA; PHA
+        ]));
+        let mut cpu = CPU::new_nmos(memory);
+        cpu.program_counter = 0x0000;
+        cpu.stack_pointer = 0xFF;
+        cpu.enable_statistics();
+
+        for _ in 0..4 {
+            cpu.execute_next_instruction();
+        }
+
+        let mark = cpu
+            .get_statistics()
+            .unwrap()
+            .get_stack_high_water_mark()
+            .unwrap();
+        assert_eq!(mark.stack_pointer, 0xFD);
+        assert_eq!(mark.program_counter, 0x0001);
+    }
+
+    #[test]
+    fn should_not_collect_statistics_unless_enabled() {
+        let memory = &RefCell::new(MemoryMock::new(&[0x48]));
+        let mut cpu = CPU::new_nmos(memory);
+        cpu.program_counter = 0x0000;
+
+        cpu.execute_next_instruction();
+
+        assert!(cpu.get_statistics().is_none());
+    }
+}
+
+#[cfg(test)]
+mod record_interrupt {
+    use crate::cpu::statistics::{InterruptSource, Statistics};
+
+    #[test]
+    fn should_count_interrupts_per_source() {
+        let mut statistics = Statistics::new();
+
+        statistics.record_interrupt_entry(InterruptSource::Break, 0);
+        statistics.record_interrupt_return(10);
+        statistics.record_interrupt_entry(InterruptSource::Irq, 20);
+        statistics.record_interrupt_return(30);
+        statistics.record_interrupt_entry(InterruptSource::Irq, 40);
+        statistics.record_interrupt_return(50);
+
+        let interrupts = statistics.get_interrupt_statistics();
+        assert_eq!(interrupts.break_count, 1);
+        assert_eq!(interrupts.irq_count, 2);
+        assert_eq!(interrupts.nmi_count, 0);
+        assert_eq!(interrupts.total_count(), 3);
+    }
+
+    #[test]
+    fn should_sum_cycles_between_handler_entry_and_matching_return() {
+        let mut statistics = Statistics::new();
+
+        statistics.record_interrupt_entry(InterruptSource::Irq, 100);
+        statistics.record_interrupt_return(130);
+        statistics.record_interrupt_entry(InterruptSource::Irq, 200);
+        statistics.record_interrupt_return(210);
+
+        assert_eq!(statistics.get_interrupt_statistics().cycles_in_handlers, 40);
+    }
+
+    #[test]
+    fn should_count_nested_handler_time_only_once_and_track_max_nesting() {
+        let mut statistics = Statistics::new();
+
+        statistics.record_interrupt_entry(InterruptSource::Irq, 100);
+        statistics.record_interrupt_entry(InterruptSource::Nmi, 110);
+        statistics.record_interrupt_return(120);
+        statistics.record_interrupt_return(150);
+
+        let interrupts = statistics.get_interrupt_statistics();
+        assert_eq!(interrupts.cycles_in_handlers, 50);
+        assert_eq!(interrupts.max_nesting, 2);
+        assert_eq!(interrupts.current_nesting(), 0);
+    }
+
+    #[test]
+    fn should_ignore_return_without_matching_entry() {
+        let mut statistics = Statistics::new();
+
+        statistics.record_interrupt_return(120);
+
+        assert_eq!(statistics.get_interrupt_statistics().cycles_in_handlers, 0);
+    }
+}
+
+#[cfg(all(test, feature = "statistics"))]
+mod cpu_interrupt_integration {
+    use std::cell::RefCell;
+
+    use crate::cpu::{tests::MemoryMock, CPU};
+
+    #[test]
+    fn should_record_brk_and_time_until_rti() {
+        let memory = &RefCell::new(MemoryMock::new(&[0x00, 0x00, 0xEA, 0x40])); // BRK; NOP; RTI
+        memory.borrow_mut()[0xFFFE] = 0x03;
+        memory.borrow_mut()[0xFFFF] = 0x00;
+        let mut cpu = CPU::new_nmos(memory);
+        cpu.program_counter = 0x0000;
+        cpu.stack_pointer = 0xFF;
+        cpu.enable_statistics();
+
+        cpu.execute_next_instruction();
+        cpu.execute_next_instruction();
+
+        let interrupts = cpu.get_statistics().unwrap().get_interrupt_statistics();
+        assert_eq!(interrupts.break_count, 1);
+        assert_eq!(interrupts.max_nesting, 1);
+        assert_eq!(interrupts.cycles_in_handlers, 6);
+    }
+}
+
+#[cfg(test)]
+mod record_branch {
+    use crate::cpu::statistics::{BranchStatistics, Statistics};
+
+    #[test]
+    fn should_count_taken_not_taken_and_page_crossings_per_branch_site() {
+        let mut statistics = Statistics::new();
+
+        statistics.record_branch(0x0610, true, false);
+        statistics.record_branch(0x0610, true, true);
+        statistics.record_branch(0x0610, false, false);
+        statistics.record_branch(0x0620, false, false);
+
+        assert_eq!(
+            statistics.get_branch_statistics()[&0x0610],
+            BranchStatistics {
+                taken: 2,
+                not_taken: 1,
+                page_crossings: 1,
+            }
+        );
+        assert_eq!(statistics.get_branch_statistics()[&0x0620].not_taken, 1);
+    }
+
+    #[test]
+    fn should_return_hottest_branches_first() {
+        let mut statistics = Statistics::new();
+
+        statistics.record_branch(0x0610, true, false);
+        statistics.record_branch(0x0620, true, false);
+        statistics.record_branch(0x0620, false, false);
+
+        let sites: Vec<u16> = statistics
+            .get_hottest_branches()
+            .iter()
+            .map(|(addr, _)| *addr)
+            .collect();
+        assert_eq!(sites, vec![0x0620, 0x0610]);
+    }
+
+    #[test]
+    fn should_calculate_taken_ratio() {
+        let branch = BranchStatistics {
+            taken: 3,
+            not_taken: 1,
+            page_crossings: 0,
+        };
+
+        assert_eq!(branch.taken_ratio(), 0.75);
+    }
+}
+
+#[cfg(all(test, feature = "statistics"))]
+mod cpu_branch_integration {
+    use std::cell::RefCell;
+
+    use crate::cpu::{tests::MemoryMock, CPU};
+
+    #[test]
+    fn should_record_taken_branch_crossing_page() {
+        let memory = &RefCell::new(MemoryMock::default());
+        memory.borrow_mut()[0x00F0] = 0xD0; // BNE +$20
+        memory.borrow_mut()[0x00F1] = 0x20;
+        let mut cpu = CPU::new_nmos(memory);
+        cpu.program_counter = 0x00F0;
+        cpu.enable_statistics();
+
+        cpu.execute_next_instruction();
+
+        let branch = cpu.get_statistics().unwrap().get_branch_statistics()[&0x00F0];
+        assert_eq!(branch.taken, 1);
+        assert_eq!(branch.page_crossings, 1);
+    }
+
+    #[test]
+    fn should_record_branch_not_taken() {
+        let memory = &RefCell::new(MemoryMock::new(&[0xF0, 0x20])); // BEQ +$20
+        let mut cpu = CPU::new_nmos(memory);
+        cpu.program_counter = 0x0000;
+        cpu.processor_status.change_zero_flag(false);
+        cpu.enable_statistics();
+
+        cpu.execute_next_instruction();
+
+        let branch = cpu.get_statistics().unwrap().get_branch_statistics()[&0x0000];
+        assert_eq!(branch.not_taken, 1);
+        assert_eq!(branch.taken, 0);
+    }
+}
@@ -1,8 +1,11 @@
 use crate::{
     consts::{Byte, Word},
-    memory::Memory,
+    memory::{Bus, Memory},
+};
+use std::{
+    cell::RefCell,
+    ops::{Index, IndexMut},
 };
-use std::ops::{Index, IndexMut};
 
 pub struct MemoryMock {
     data: [u8; 64 * 1024],
@@ -43,6 +46,99 @@ impl IndexMut<Word> for MemoryMock {
     }
 }
 
+// remembers every address read and written, in order
+pub struct BusLoggingMemory {
+    data: MemoryMock,
+    pub reads: RefCell<Vec<Word>>,
+    pub writes: Vec<Word>,
+}
+impl Memory for BusLoggingMemory {}
+
+impl BusLoggingMemory {
+    pub fn new(data: MemoryMock) -> Self {
+        return BusLoggingMemory {
+            data,
+            reads: RefCell::new(Vec::new()),
+            writes: Vec::new(),
+        };
+    }
+}
+
+impl Index<Word> for BusLoggingMemory {
+    type Output = Byte;
+
+    fn index(&self, index: Word) -> &Self::Output {
+        self.reads.borrow_mut().push(index);
+        return &self.data[index];
+    }
+}
+
+impl IndexMut<Word> for BusLoggingMemory {
+    fn index_mut(&mut self, index: Word) -> &mut Self::Output {
+        self.writes.push(index);
+        return &mut self.data[index];
+    }
+}
+
+pub fn apply_setup<M: Bus>(cpu: &mut super::CPU<M>, setup: impl FnOnce(&mut super::CPU<M>)) {
+    setup(cpu);
+}
+
+// places the program at $0000, clears the flags set by reset, runs a single instruction
+// through opcode dispatch and checks everything given; cycles include the opcode fetch, so
+// they match datasheet counts. The chip variant defaults to NMOS
+macro_rules! assert_instruction {
+    (
+        variant: $variant:ident,
+        program: [$($byte:expr),* $(,)?],
+        $(setup: $setup:expr,)?
+        $(cycles: $cycles:expr,)?
+        $(registers: { $($register:ident: $register_value:expr),* $(,)? },)?
+        $(memory: { $($addr:expr => $memory_value:expr),* $(,)? },)?
+        $(status: $status:expr,)?
+    ) => {{
+        let memory = &std::cell::RefCell::new($crate::cpu::tests::MemoryMock::new(&[$($byte),*]));
+        let mut cpu = $crate::cpu::CPU::new(memory, $crate::cpu::ChipVariant::$variant);
+        cpu.program_counter = 0x0000;
+        cpu.processor_status.set(0);
+        $($crate::cpu::tests::apply_setup(&mut cpu, $setup);)?
+        #[allow(unused_variables)]
+        let start_cycle = cpu.cycle;
+
+        cpu.execute_next_instruction();
+
+        $(assert_eq!(cpu.cycle - start_cycle, $cycles, "cycles");)?
+        $($(assert_eq!(cpu.$register, $register_value, stringify!($register));)*)?
+        $($(assert_eq!(memory.borrow()[$addr], $memory_value, "memory at {:#06X}", $addr);)*)?
+        $(assert_eq!(cpu.processor_status, $status, "processor status");)?
+    }};
+    (program: $($rest:tt)*) => {
+        $crate::cpu::tests::assert_instruction! {
+            variant: NMOS,
+            program: $($rest)*
+        }
+    };
+}
+
+macro_rules! assert_cycles {
+    ([$($byte:expr),* $(,)?], $cycles:expr) => {
+        $crate::cpu::tests::assert_instruction! {
+            program: [$($byte),*],
+            cycles: $cycles,
+        }
+    };
+    ([$($byte:expr),* $(,)?], $setup:expr, $cycles:expr) => {
+        $crate::cpu::tests::assert_instruction! {
+            program: [$($byte),*],
+            setup: $setup,
+            cycles: $cycles,
+        }
+    };
+}
+
+pub(crate) use assert_cycles;
+pub(crate) use assert_instruction;
+
 #[cfg(test)]
 mod new {
     use std::cell::RefCell;
@@ -51,17 +147,20 @@ mod new {
     use super::MemoryMock;
 
     #[test]
-    fn should_be_in_reset_state_after_creation() {
-        let memory = &RefCell::new(MemoryMock::default());
+    fn should_run_reset_sequence_on_creation() {
+        let mut payload = MemoryMock::default();
+        payload[0xFFFC] = 0x00;
+        payload[0xFFFD] = 0x06;
+        let memory = &RefCell::new(payload);
         let uut = CPU::new_nmos(memory);
 
         assert_eq!(uut.accumulator, 0);
-        assert_eq!(uut.cycle, 0);
+        assert_eq!(uut.cycle, 7);
         assert_eq!(uut.index_register_x, 0);
         assert_eq!(uut.index_register_y, 0);
-        assert_eq!(uut.stack_pointer, 0);
-        assert_eq!(uut.processor_status, 0);
-        assert_eq!(uut.program_counter, 0xFFFC);
+        assert_eq!(uut.stack_pointer, 0xFD);
+        assert_eq!(uut.processor_status, 0b00000100);
+        assert_eq!(uut.program_counter, 0x0600);
     }
 }
 
@@ -89,6 +188,47 @@ mod reset {
         assert_eq!(uut.program_counter, 0x00AD);
     }
 
+    #[test]
+    fn should_take_seven_cycles_and_move_stack_pointer_down_without_writing() {
+        let memory = &RefCell::new(MemoryMock::default());
+        let mut uut = CPU::new_nmos(memory);
+        uut.stack_pointer = 0x10;
+        uut.cycle = 100;
+        memory.borrow_mut()[0x0110] = 0x42;
+
+        uut.reset();
+
+        assert_eq!(uut.cycle, 107);
+        assert_eq!(uut.stack_pointer, 0x0D);
+        assert_eq!(memory.borrow()[0x0110], 0x42);
+    }
+
+    #[test]
+    fn should_keep_accumulator_and_index_registers_after_reset() {
+        let memory = &RefCell::new(MemoryMock::default());
+        let mut uut = CPU::new_nmos(memory);
+        uut.accumulator = 0x12;
+        uut.index_register_x = 0x34;
+        uut.index_register_y = 0x56;
+
+        uut.reset();
+
+        assert_eq!(uut.accumulator, 0x12);
+        assert_eq!(uut.index_register_x, 0x34);
+        assert_eq!(uut.index_register_y, 0x56);
+    }
+
+    #[test]
+    fn should_set_interrupt_disable_flag_after_reset() {
+        let memory = &RefCell::new(MemoryMock::default());
+        let mut uut = CPU::new_nmos(memory);
+        uut.processor_status.set(0);
+
+        uut.reset();
+
+        assert!(uut.processor_status.get_interrupt_disable_flag());
+    }
+
     #[test]
     fn should_set_negative_flag_in_processor_status_to_zero_after_reset() {
         let memory = &RefCell::new(MemoryMock::default());
@@ -144,6 +284,7 @@ mod fetch_address {
     fn should_increase_cycle_counter_and_a_program_counter_twice() {
         let memory = &RefCell::new(MemoryMock::new(&[0x03, 0xFF]));
         let mut uut = CPU::new_nmos(memory);
+        uut.cycle = 0;
         uut.program_counter = 0x00;
 
         assert_eq!(uut.cycle, 0);
@@ -177,6 +318,7 @@ mod fetch_zero_page_address {
     fn should_increase_cycle_counter_and_a_program_counter_once() {
         let memory = &RefCell::new(MemoryMock::new(&[0x03, 0xFF]));
         let mut uut = CPU::new_nmos(memory);
+        uut.cycle = 0;
         uut.program_counter = 0x00;
 
         assert_eq!(uut.cycle, 0);
@@ -239,6 +381,7 @@ mod fetch_zero_page_address_with_idx_register_offset {
     fn should_increase_cycle_counter_two_times() {
         let memory = &RefCell::new(MemoryMock::new(&[0x03, 0xFF]));
         let mut uut = CPU::new_nmos(memory);
+        uut.cycle = 0;
         uut.program_counter = 0x00;
 
         assert_eq!(uut.cycle, 0);
@@ -253,6 +396,7 @@ mod fetch_zero_page_address_with_idx_register_offset {
     fn should_increase_program_counter_once() {
         let memory = &RefCell::new(MemoryMock::new(&[0x03, 0xFF]));
         let mut uut = CPU::new_nmos(memory);
+        uut.cycle = 0;
         uut.program_counter = 0x00;
 
         assert_eq!(uut.cycle, 0);
@@ -286,6 +430,7 @@ mod fetch_instruction {
     fn should_increase_cycle_counter_and_a_program_counter() {
         let memory = &RefCell::new(MemoryMock::default());
         let mut uut = CPU::new_nmos(memory);
+        uut.cycle = 0;
         uut.program_counter = 0x0001;
 
         assert_eq!(uut.cycle, 0);
@@ -477,6 +622,7 @@ mod push_byte_to_stack {
     fn should_increase_cycle_counter_and_decrease_stack_pointer_by_one() {
         let memory = &RefCell::new(MemoryMock::default());
         let mut uut = CPU::new_nmos(memory);
+        uut.cycle = 0;
         uut.stack_pointer = 0xFF;
 
         assert_eq!(uut.cycle, 0);
@@ -514,6 +660,7 @@ mod push_word_to_stack {
     fn should_increase_cycle_counter_and_decrease_stack_pointer_by_two() {
         let memory = &RefCell::new(MemoryMock::default());
         let mut uut = CPU::new_nmos(memory);
+        uut.cycle = 0;
         uut.stack_pointer = 0xFF;
         assert_eq!(uut.cycle, 0);
 
@@ -549,6 +696,7 @@ mod pop_byte_from_stack {
     fn should_increment_cycle_count_and_stack_pointer_once() {
         let memory = &RefCell::new(MemoryMock::default());
         let mut uut = CPU::new_nmos(memory);
+        uut.cycle = 0;
         uut.memory.borrow_mut()[0x01FF] = 0xDF;
         uut.memory.borrow_mut()[0x01FE] = 0x48;
         uut.stack_pointer = 0xFD;
@@ -586,6 +734,7 @@ mod pop_word_from_stack {
     fn should_increment_cycle_count_and_stack_pointer_twice() {
         let memory = &RefCell::new(MemoryMock::default());
         let mut uut = CPU::new_nmos(memory);
+        uut.cycle = 0;
         uut.memory.borrow_mut()[0x01FF] = 0xDF;
         uut.memory.borrow_mut()[0x01FE] = 0x48;
         uut.stack_pointer = 0xFD;
@@ -660,6 +809,7 @@ mod sum_with_idx_register {
     fn should_increase_cycle_counter_by_one() {
         let memory = &RefCell::new(MemoryMock::default());
         let mut uut = CPU::new_nmos(memory);
+        uut.cycle = 0;
         uut.index_register_x = 0xFF;
         assert_eq!(uut.cycle, 0);
 
@@ -959,7 +1109,7 @@ mod get_address {
         }
 
         #[test]
-        fn should_take_three_cycles_when_not_crossing_page_boundary_during_offset_addition_for_a_read_operation_address(
+        fn should_take_two_cycles_when_not_crossing_page_boundary_during_offset_addition_for_a_read_operation_address(
         ) {
             let memory = &RefCell::new(MemoryMock::new(&[0x03, 0xFF, 0xCB, 0x52]));
             let mut uut = CPU::new_nmos(memory);
@@ -969,11 +1119,11 @@ mod get_address {
 
             uut.get_address(AddressingMode::AbsoluteX);
 
-            assert_eq!(uut.cycle, 3);
+            assert_eq!(uut.cycle, 2);
         }
 
         #[test]
-        fn should_take_four_cycles_when_crossing_page_boundary_during_offset_addition_for_a_read_operation_address(
+        fn should_take_three_cycles_when_crossing_page_boundary_during_offset_addition_for_a_read_operation_address(
         ) {
             let memory = &RefCell::new(MemoryMock::new(&[0x03, 0xFF, 0xCB, 0x52]));
             let mut uut = CPU::new_nmos(memory);
@@ -983,7 +1133,7 @@ mod get_address {
 
             uut.get_address(AddressingMode::AbsoluteX);
 
-            assert_eq!(uut.cycle, 4);
+            assert_eq!(uut.cycle, 3);
         }
     }
 
@@ -1020,7 +1170,7 @@ mod get_address {
         }
 
         #[test]
-        fn should_take_three_cycles_when_not_crossing_page_boundary_during_offset_addition_for_a_read_operation_address(
+        fn should_take_two_cycles_when_not_crossing_page_boundary_during_offset_addition_for_a_read_operation_address(
         ) {
             let memory = &RefCell::new(MemoryMock::new(&[0x03, 0xFF, 0xCB, 0x52]));
             let mut uut = CPU::new_nmos(memory);
@@ -1030,11 +1180,11 @@ mod get_address {
 
             uut.get_address(AddressingMode::AbsoluteY);
 
-            assert_eq!(uut.cycle, 3);
+            assert_eq!(uut.cycle, 2);
         }
 
         #[test]
-        fn should_take_four_cycles_when_crossing_page_boundary_during_offset_addition_for_a_read_operation_address(
+        fn should_take_three_cycles_when_crossing_page_boundary_during_offset_addition_for_a_read_operation_address(
         ) {
             let memory = &RefCell::new(MemoryMock::new(&[0x03, 0xFF, 0xCB, 0x52]));
             let mut uut = CPU::new_nmos(memory);
@@ -1044,7 +1194,7 @@ mod get_address {
 
             uut.get_address(AddressingMode::AbsoluteY);
 
-            assert_eq!(uut.cycle, 4);
+            assert_eq!(uut.cycle, 3);
         }
     }
 
@@ -1135,6 +1285,18 @@ mod get_address {
 
             assert_eq!(uut.cycle, 2);
         }
+
+        #[test]
+        fn should_wrap_around_within_zero_page() {
+            let memory = &RefCell::new(MemoryMock::new(&[0xF0]));
+            let mut uut = CPU::new_nmos(memory);
+            uut.program_counter = 0x00;
+            uut.index_register_x = 0x20;
+
+            let result = uut.get_address(AddressingMode::ZeroPageX);
+
+            assert_eq!(result.unwrap(), 0x0010);
+        }
     }
 
     #[cfg(test)]
@@ -1181,6 +1343,18 @@ mod get_address {
 
             assert_eq!(uut.cycle, 2);
         }
+
+        #[test]
+        fn should_wrap_around_within_zero_page() {
+            let memory = &RefCell::new(MemoryMock::new(&[0xF0]));
+            let mut uut = CPU::new_nmos(memory);
+            uut.program_counter = 0x00;
+            uut.index_register_y = 0x20;
+
+            let result = uut.get_address(AddressingMode::ZeroPageY);
+
+            assert_eq!(result.unwrap(), 0x0010);
+        }
     }
 
     #[cfg(test)]
@@ -1227,6 +1401,32 @@ mod get_address {
 
             assert_eq!(uut.cycle, 4);
         }
+
+        #[test]
+        fn should_read_pointer_high_byte_from_start_of_zero_page() {
+            let memory = &RefCell::new(MemoryMock::new(&[0x12, 0xFE]));
+            memory.borrow_mut()[0x00FF] = 0x34;
+            memory.borrow_mut()[0x0100] = 0x56;
+            let mut uut = CPU::new_nmos(memory);
+            uut.program_counter = 0x01;
+            uut.index_register_x = 0x01;
+
+            let result = uut.get_address(AddressingMode::IndexIndirectX);
+
+            assert_eq!(result.unwrap(), 0x1234);
+        }
+
+        #[test]
+        fn should_wrap_pointer_address_around_within_zero_page() {
+            let memory = &RefCell::new(MemoryMock::new(&[0xFF, 0xFF, 0x34, 0x12]));
+            let mut uut = CPU::new_nmos(memory);
+            uut.program_counter = 0x01;
+            uut.index_register_x = 0x03;
+
+            let result = uut.get_address(AddressingMode::IndexIndirectX);
+
+            assert_eq!(result.unwrap(), 0x1234);
+        }
     }
 
     #[cfg(test)]
@@ -1261,7 +1461,7 @@ mod get_address {
         }
 
         #[test]
-        fn should_take_four_cycles_when_not_crossing_page_boundary_during_offset_addition_for_a_read_operation_address(
+        fn should_take_three_cycles_when_not_crossing_page_boundary_during_offset_addition_for_a_read_operation_address(
         ) {
             let memory = RefCell::new(MemoryMock::new(&[0x02, 0xFF, 0x03, 0xDD, 0x25]));
             let mut uut = CPU::new_nmos(&memory);
@@ -1271,11 +1471,11 @@ mod get_address {
 
             uut.get_address(AddressingMode::IndirectIndexY);
 
-            assert_eq!(uut.cycle, 4);
+            assert_eq!(uut.cycle, 3);
         }
 
         #[test]
-        fn should_take_five_cycles_when_crossing_page_boundary_during_offset_addition_for_a_read_operation_address(
+        fn should_take_four_cycles_when_crossing_page_boundary_during_offset_addition_for_a_read_operation_address(
         ) {
             let memory = RefCell::new(MemoryMock::new(&[0x02, 0xFF, 0x03, 0xDD, 0x25]));
             let mut uut = CPU::new_nmos(&memory);
@@ -1285,7 +1485,21 @@ mod get_address {
 
             uut.get_address(AddressingMode::IndirectIndexY);
 
-            assert_eq!(uut.cycle, 5);
+            assert_eq!(uut.cycle, 4);
+        }
+
+        #[test]
+        fn should_read_pointer_high_byte_from_start_of_zero_page() {
+            let memory = &RefCell::new(MemoryMock::new(&[0x12, 0xFF]));
+            memory.borrow_mut()[0x00FF] = 0x34;
+            memory.borrow_mut()[0x0100] = 0x56;
+            let mut uut = CPU::new_nmos(memory);
+            uut.program_counter = 0x01;
+            uut.index_register_y = 0x01;
+
+            let result = uut.get_address(AddressingMode::IndirectIndexY);
+
+            assert_eq!(result.unwrap(), 0x1235);
         }
     }
 
@@ -1370,6 +1584,22 @@ mod get_address {
 
                 assert_eq!(result, Some(0x09A5));
             }
+
+            #[test]
+            fn should_take_four_cycles_when_indirect_address_is_falling_on_a_page_boundary() {
+                let mut program: [Byte; 512] = [0x00; 512];
+                program[0x0001] = 0xFF;
+                program[0x0002] = 0x00;
+
+                let memory = RefCell::new(MemoryMock::new(&program));
+                let mut uut = CPU::new_nmos(&memory);
+                uut.program_counter = 0x0001;
+                uut.cycle = 0;
+
+                uut.get_address(AddressingMode::Indirect);
+
+                assert_eq!(uut.cycle, 4);
+            }
         }
 
         #[cfg(test)]
@@ -1503,3 +1733,1343 @@ mod get_address {
         }
     }
 }
+
+#[cfg(test)]
+mod instruction_test_macros {
+    #[test]
+    fn should_count_cycles_including_opcode_fetch() {
+        assert_cycles!([0xEA], 2); // NOP
+        assert_cycles!([0xBD, 0xFF, 0x00], |cpu| cpu.index_register_x = 0x01, 5);
+        // LDA $00FF,X
+    }
+
+    #[test]
+    fn should_check_registers_memory_and_status_together() {
+        assert_instruction! {
+            program: [0x85, 0x10], // STA $10
+            setup: |cpu| cpu.accumulator = 0x80,
+            cycles: 3,
+            registers: { accumulator: 0x80, program_counter: 0x0002 },
+            memory: { 0x0010 => 0x80 },
+            status: 0b00000000,
+        };
+    }
+
+    #[test]
+    #[should_panic(expected = "cycles")]
+    fn should_fail_on_cycle_mismatch() {
+        assert_cycles!([0xEA], 3);
+    }
+}
+
+#[cfg(test)]
+mod skip_instruction {
+    use std::cell::RefCell;
+
+    use super::MemoryMock;
+    use crate::cpu::{ChipVariant, CPU};
+
+    #[test]
+    fn should_move_program_counter_past_instruction_operands() {
+        let memory = RefCell::new(MemoryMock::new(&[0xAD, 0x00, 0x02, 0xE8]));
+        let mut uut = CPU::new_nmos(&memory);
+        uut.cycle = 0;
+        uut.program_counter = 0x0000;
+
+        assert_eq!(uut.skip_instruction(), 0x0003);
+        assert_eq!(uut.cycle, 0);
+        assert_eq!(uut.accumulator, 0x00);
+    }
+
+    #[test]
+    fn should_skip_single_byte_of_unknown_opcode() {
+        let memory = RefCell::new(MemoryMock::new(&[0x02, 0xE8]));
+        let mut uut = CPU::new_nmos(&memory);
+        uut.program_counter = 0x0000;
+
+        assert_eq!(uut.skip_instruction(), 0x0001);
+    }
+
+    #[test]
+    fn should_skip_operand_of_cmos_nop() {
+        let memory = RefCell::new(MemoryMock::new(&[0x02, 0xE8, 0xE8])); // NOP #$E8
+        let mut uut = CPU::new_cmos(&memory);
+        uut.program_counter = 0x0000;
+
+        assert_eq!(uut.skip_instruction(), 0x0002);
+    }
+
+    #[test]
+    fn should_skip_as_many_bytes_as_variant_executes() {
+        let cases = [
+            (ChipVariant::NMOS, 0x5C),         // NOP $xxxx,X
+            (ChipVariant::CMOS, 0x5C),         // NOP $xxxx
+            (ChipVariant::CMOS, 0x0F),         // NOP
+            (ChipVariant::RockwellCMOS, 0x0F), // BBR0 $zz,+0
+            (ChipVariant::WDCCMOS, 0x87),      // SMB0 $zz
+            (ChipVariant::CSG65CE02, 0x5C),    // AUG
+            (ChipVariant::CSG65CE02, 0xF4),    // PHW #$xxxx
+            (ChipVariant::CSG65CE02, 0xCB),    // ASW $xxxx
+        ];
+        for (chip_variant, opcode) in cases {
+            let memory = RefCell::new(MemoryMock::new(&[opcode, 0x10, 0x00, 0x00]));
+            let mut skipped = CPU::new(&memory, chip_variant);
+            skipped.program_counter = 0x0000;
+            let mut executed = CPU::new(&memory, chip_variant);
+            executed.program_counter = 0x0000;
+
+            executed.execute_next_instruction();
+
+            assert_eq!(
+                skipped.skip_instruction(),
+                executed.program_counter,
+                "opcode {opcode:#04X}"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod opcode_metadata {
+    use std::cell::RefCell;
+
+    use super::MemoryMock;
+    use crate::cpu::instructions::{get_instructions, get_opcode_metadata};
+    use crate::cpu::{AddressingMode, ChipVariant};
+
+    const VARIANTS: [ChipVariant; 6] = [
+        ChipVariant::NMOS,
+        ChipVariant::Ricoh2A03,
+        ChipVariant::CMOS,
+        ChipVariant::RockwellCMOS,
+        ChipVariant::WDCCMOS,
+        ChipVariant::CSG65CE02,
+    ];
+
+    #[test]
+    fn should_describe_exactly_the_opcodes_the_variant_dispatches() {
+        for chip_variant in VARIANTS {
+            let instructions = get_instructions::<&RefCell<MemoryMock>>(chip_variant);
+            let metadata = get_opcode_metadata(chip_variant);
+            for opcode in 0..256 {
+                assert_eq!(
+                    instructions[opcode].is_some(),
+                    metadata[opcode].is_some(),
+                    "opcode {opcode:#04X}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn should_decode_opcodes_by_variant() {
+        let nmos = get_opcode_metadata(ChipVariant::NMOS)[0x80].unwrap();
+        let cmos = get_opcode_metadata(ChipVariant::CMOS)[0x80].unwrap();
+        let rockwell = get_opcode_metadata(ChipVariant::RockwellCMOS)[0x8F].unwrap();
+
+        assert_eq!(
+            (nmos.mnemonic, nmos.addressing_mode, nmos.length()),
+            ("NOP", AddressingMode::Immediate, 2)
+        );
+        assert_eq!(
+            (cmos.mnemonic, cmos.addressing_mode, cmos.length()),
+            ("BRA", AddressingMode::Relative, 2)
+        );
+        assert_eq!(
+            (
+                rockwell.mnemonic,
+                rockwell.addressing_mode,
+                rockwell.length()
+            ),
+            ("BBS0", AddressingMode::ZeroPageRelative, 3)
+        );
+    }
+}
+
+#[cfg(test)]
+mod absolute_x_read_modify_write_timing {
+    use std::cell::RefCell;
+
+    use super::MemoryMock;
+    use crate::cpu::{ChipVariant, CPU};
+
+    // INC, DEC, ASL, LSR, ROL, ROR abs,X
+    const OPCODES: [u8; 6] = [0xFE, 0xDE, 0x1E, 0x5E, 0x3E, 0x7E];
+
+    fn cycles_taken(chip_variant: ChipVariant, opcode: u8, index_register_x: u8) -> u64 {
+        let memory = &RefCell::new(MemoryMock::new(&[opcode, 0xF0, 0x10]));
+        let mut cpu = CPU::new(memory, chip_variant);
+        cpu.cycle = 0;
+        cpu.program_counter = 0x0000;
+        cpu.index_register_x = index_register_x;
+
+        cpu.execute_next_instruction();
+
+        return cpu.cycle;
+    }
+
+    #[test]
+    fn should_take_seven_cycles_on_nmos_regardless_of_page_crossing() {
+        for opcode in OPCODES {
+            assert_eq!(
+                cycles_taken(ChipVariant::NMOS, opcode, 0x01),
+                7,
+                "{opcode:#04X}"
+            );
+            assert_eq!(
+                cycles_taken(ChipVariant::NMOS, opcode, 0x20),
+                7,
+                "{opcode:#04X}"
+            );
+        }
+    }
+
+    #[test]
+    fn should_take_six_cycles_on_cmos_when_not_crossing_page() {
+        for chip_variant in [ChipVariant::RockwellCMOS, ChipVariant::WDCCMOS] {
+            for opcode in OPCODES {
+                assert_eq!(cycles_taken(chip_variant, opcode, 0x01), 6, "{opcode:#04X}");
+            }
+        }
+    }
+
+    #[test]
+    fn should_take_seven_cycles_on_cmos_when_crossing_page() {
+        for chip_variant in [ChipVariant::RockwellCMOS, ChipVariant::WDCCMOS] {
+            for opcode in OPCODES {
+                assert_eq!(cycles_taken(chip_variant, opcode, 0x20), 7, "{opcode:#04X}");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod zero_page_indirect_addressing {
+    use std::cell::RefCell;
+
+    use super::MemoryMock;
+    use crate::cpu::CPU;
+
+    // ($10) points at $0200 holding 0x81
+    fn memory_with_pointer(program: &[u8]) -> RefCell<MemoryMock> {
+        let memory = RefCell::new(MemoryMock::new(program));
+        memory.borrow_mut()[0x0010] = 0x00;
+        memory.borrow_mut()[0x0011] = 0x02;
+        memory.borrow_mut()[0x0200] = 0x81;
+
+        return memory;
+    }
+
+    fn accumulator_after(program: &[u8], accumulator: u8) -> u8 {
+        let memory = memory_with_pointer(program);
+        let mut cpu = CPU::new_cmos(&memory);
+        cpu.program_counter = 0x0000;
+        cpu.accumulator = accumulator;
+
+        cpu.execute_next_instruction();
+
+        return cpu.accumulator;
+    }
+
+    #[test]
+    fn should_load_and_combine_through_zero_page_pointer() {
+        assert_eq!(accumulator_after(&[0xB2, 0x10], 0x00), 0x81); // LDA ($10)
+        assert_eq!(accumulator_after(&[0x12, 0x10], 0x02), 0x83); // ORA ($10)
+        assert_eq!(accumulator_after(&[0x32, 0x10], 0x0F), 0x01); // AND ($10)
+        assert_eq!(accumulator_after(&[0x52, 0x10], 0x01), 0x80); // EOR ($10)
+        assert_eq!(accumulator_after(&[0x72, 0x10], 0x01), 0x82); // ADC ($10)
+    }
+
+    #[test]
+    fn should_take_five_cycles() {
+        let memory = memory_with_pointer(&[0xD2, 0x10]); // CMP ($10)
+        let mut cpu = CPU::new_cmos(&memory);
+        cpu.cycle = 0;
+        cpu.processor_status.set(0);
+        cpu.program_counter = 0x0000;
+        cpu.accumulator = 0x81;
+
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.cycle, 5);
+        assert!(cpu.processor_status.get_zero_flag());
+    }
+
+    #[test]
+    fn should_store_through_zero_page_pointer() {
+        let memory = memory_with_pointer(&[0x92, 0x10]); // STA ($10)
+        let mut cpu = CPU::new_cmos(&memory);
+        cpu.cycle = 0;
+        cpu.program_counter = 0x0000;
+        cpu.accumulator = 0x42;
+
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.cycle, 5);
+        assert_eq!(memory.borrow()[0x0200], 0x42);
+    }
+
+    #[test]
+    fn should_wrap_pointer_within_zero_page() {
+        let memory = memory_with_pointer(&[0x02, 0xB2, 0xFF]); // LDA ($FF)
+        memory.borrow_mut()[0x00FF] = 0x00;
+        let mut cpu = CPU::new_cmos(&memory);
+        cpu.program_counter = 0x0001;
+
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.accumulator, 0x81);
+    }
+}
+
+#[cfg(test)]
+mod variant_dispatch_tables {
+    use std::cell::RefCell;
+
+    use super::MemoryMock;
+    use crate::cpu::{ChipVariant, CPU};
+
+    // program counter and cycles after executing the first instruction of the program
+    fn execute(chip_variant: ChipVariant, program: &[u8]) -> (u16, u64) {
+        let memory = &RefCell::new(MemoryMock::new(program));
+        let mut cpu = CPU::new(memory, chip_variant);
+        cpu.cycle = 0;
+        cpu.program_counter = 0x0000;
+
+        cpu.execute_next_instruction();
+
+        return (cpu.program_counter, cpu.cycle);
+    }
+
+    #[test]
+    fn should_decode_every_opcode_on_cmos_variants() {
+        for chip_variant in [
+            ChipVariant::CMOS,
+            ChipVariant::RockwellCMOS,
+            ChipVariant::WDCCMOS,
+            ChipVariant::CSG65CE02,
+        ] {
+            let memory = &RefCell::new(MemoryMock::new(&[]));
+            let cpu = CPU::new(memory, chip_variant);
+
+            assert!(cpu.opcode_handlers.iter().all(|handler| handler.is_some()));
+        }
+    }
+
+    #[test]
+    fn should_treat_undefined_cmos_opcodes_as_nops_of_defined_length() {
+        // opcode, length, cycles
+        let nops = [
+            (0x02, 2, 2),
+            (0xE2, 2, 2),
+            (0x44, 2, 3),
+            (0x54, 2, 4),
+            (0xF4, 2, 4),
+            (0xDC, 3, 4),
+            (0x5C, 3, 8),
+            (0x03, 1, 1),
+            (0xFB, 1, 1),
+        ];
+        for (opcode, length, cycles) in nops {
+            assert_eq!(
+                execute(ChipVariant::CMOS, &[opcode, 0x00, 0x00]),
+                (length, cycles),
+                "{opcode:#04X}"
+            );
+        }
+    }
+
+    #[test]
+    fn should_decode_bit_instructions_only_on_rockwell_and_wdc() {
+        let program = [0x07, 0x02, 0xFF]; // RMB0 $02
+
+        assert_eq!(execute(ChipVariant::CMOS, &program), (1, 1));
+        assert_eq!(execute(ChipVariant::RockwellCMOS, &program), (2, 5));
+        assert_eq!(execute(ChipVariant::WDCCMOS, &program), (2, 5));
+    }
+
+    #[test]
+    fn should_decode_wai_and_stp_only_on_wdc() {
+        let memory = &RefCell::new(MemoryMock::new(&[0xDB])); // STP
+        let mut rockwell = CPU::new_rockwell_cmos(memory);
+        rockwell.program_counter = 0x0000;
+        rockwell.execute_next_instruction();
+        let mut wdc = CPU::new_wdc_cmos(memory);
+        wdc.program_counter = 0x0000;
+        wdc.execute_next_instruction();
+
+        assert!(!rockwell.is_stopped());
+        assert!(wdc.is_stopped());
+    }
+
+    #[test]
+    fn should_keep_undocumented_opcodes_on_ricoh_2a03() {
+        let memory = &RefCell::new(MemoryMock::new(&[0xA7, 0x02, 0x42])); // LAX $02
+        let mut cpu = CPU::new_ricoh_2a03(memory);
+        cpu.program_counter = 0x0000;
+
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.accumulator, 0x42);
+        assert_eq!(cpu.index_register_x, 0x42);
+    }
+
+    #[test]
+    fn should_ignore_decimal_mode_on_ricoh_2a03() {
+        let memory = &RefCell::new(MemoryMock::new(&[0x69, 0x01])); // ADC #$01
+        let mut cpu = CPU::new_ricoh_2a03(memory);
+        cpu.cycle = 0;
+        cpu.processor_status.set(0);
+        cpu.program_counter = 0x0000;
+        cpu.accumulator = 0x09;
+        cpu.processor_status.change_decimal_mode_flag(true);
+
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.accumulator, 0x0A);
+        assert_eq!(cpu.cycle, 2);
+        assert!(cpu.processor_status.get_decimal_mode_flag());
+    }
+}
+
+#[cfg(test)]
+mod irq_line {
+    use std::cell::RefCell;
+
+    use super::MemoryMock;
+    use crate::cpu::CPU;
+
+    // INX at $0000, handler at $0300
+    fn memory_with_irq_handler() -> RefCell<MemoryMock> {
+        let memory = RefCell::new(MemoryMock::new(&[0xE8, 0xE8]));
+        memory.borrow_mut()[0xFFFE] = 0x00;
+        memory.borrow_mut()[0xFFFF] = 0x03;
+
+        return memory;
+    }
+
+    #[test]
+    fn should_service_interrupt_between_instructions_in_seven_cycles() {
+        let memory = memory_with_irq_handler();
+        let mut cpu = CPU::new_nmos(&memory);
+        cpu.cycle = 0;
+        cpu.program_counter = 0x0000;
+        cpu.stack_pointer = 0xFF;
+        cpu.processor_status.set(0b00010001);
+        cpu.set_irq_line(true);
+
+        assert_eq!(cpu.execute_next_instruction(), None);
+
+        assert_eq!(cpu.cycle, 7);
+        assert_eq!(cpu.program_counter, 0x0300);
+        assert_eq!(cpu.stack_pointer, 0xFC);
+        assert_eq!(memory.borrow()[0x01FF], 0x00);
+        assert_eq!(memory.borrow()[0x01FE], 0x00);
+        assert_eq!(memory.borrow()[0x01FD], 0b00000001);
+        assert!(cpu.processor_status.get_interrupt_disable_flag());
+        assert_eq!(cpu.index_register_x, 0x00);
+    }
+
+    #[test]
+    fn should_ignore_interrupt_while_interrupt_disable_flag_is_set() {
+        let memory = memory_with_irq_handler();
+        let mut cpu = CPU::new_nmos(&memory);
+        cpu.program_counter = 0x0000;
+        cpu.processor_status.change_interrupt_disable_flag(true);
+        cpu.set_irq_line(true);
+
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.program_counter, 0x0001);
+        assert_eq!(cpu.index_register_x, 0x01);
+    }
+
+    #[test]
+    fn should_not_interrupt_when_line_is_released() {
+        let memory = memory_with_irq_handler();
+        let mut cpu = CPU::new_nmos(&memory);
+        cpu.program_counter = 0x0000;
+        cpu.processor_status.change_interrupt_disable_flag(false);
+        cpu.set_irq_line(true);
+        cpu.set_irq_line(false);
+
+        cpu.execute_next_instruction();
+
+        assert!(!cpu.get_irq_line());
+        assert_eq!(cpu.program_counter, 0x0001);
+    }
+
+    #[test]
+    fn should_clear_decimal_mode_on_cmos() {
+        let memory = memory_with_irq_handler();
+        let mut cpu = CPU::new_cmos(&memory);
+        cpu.program_counter = 0x0000;
+        cpu.processor_status.change_interrupt_disable_flag(false);
+        cpu.stack_pointer = 0xFF;
+        cpu.processor_status.change_decimal_mode_flag(true);
+        cpu.set_irq_line(true);
+
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.program_counter, 0x0300);
+        assert!(!cpu.processor_status.get_decimal_mode_flag());
+        assert_eq!(memory.borrow()[0x01FD], 0b00001000);
+    }
+
+    #[test]
+    fn should_wake_up_from_wai_and_service_interrupt() {
+        let memory = memory_with_irq_handler();
+        memory.borrow_mut()[0x0000] = 0xCB; // WAI
+        let mut cpu = CPU::new_wdc_cmos(&memory);
+        cpu.program_counter = 0x0000;
+        cpu.processor_status.change_interrupt_disable_flag(false);
+        cpu.stack_pointer = 0xFF;
+
+        cpu.execute_next_instruction();
+        cpu.execute_next_instruction();
+        assert!(cpu.is_waiting());
+
+        cpu.set_irq_line(true);
+        cpu.execute_next_instruction();
+
+        assert!(!cpu.is_waiting());
+        assert_eq!(cpu.program_counter, 0x0300);
+        assert_eq!(memory.borrow()[0x01FE], 0x01);
+    }
+}
+
+#[cfg(test)]
+mod nmi_line {
+    use std::cell::RefCell;
+
+    use super::MemoryMock;
+    use crate::cpu::CPU;
+
+    // BRK at $0000, IRQ/BRK handler at $0300, NMI handler at $0400
+    fn memory_with_interrupt_handlers() -> RefCell<MemoryMock> {
+        let memory = RefCell::new(MemoryMock::new(&[0x00, 0x00, 0xE8]));
+        memory.borrow_mut()[0xFFFA] = 0x00;
+        memory.borrow_mut()[0xFFFB] = 0x04;
+        memory.borrow_mut()[0xFFFE] = 0x00;
+        memory.borrow_mut()[0xFFFF] = 0x03;
+
+        return memory;
+    }
+
+    fn new_cpu(memory: &RefCell<MemoryMock>, cmos: bool) -> CPU<&RefCell<MemoryMock>> {
+        let mut cpu = if cmos {
+            CPU::new_cmos(memory)
+        } else {
+            CPU::new_nmos(memory)
+        };
+        cpu.program_counter = 0x0000;
+        cpu.stack_pointer = 0xFF;
+
+        return cpu;
+    }
+
+    #[test]
+    fn should_service_interrupt_on_asserting_edge_even_when_interrupts_are_disabled() {
+        let memory = memory_with_interrupt_handlers();
+        let mut cpu = new_cpu(&memory, false);
+        cpu.set_nmi_line(true);
+
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.program_counter, 0x0400);
+        assert_eq!(cpu.cycle, 14);
+        assert_eq!(memory.borrow()[0x01FD] & 0b00010000, 0);
+    }
+
+    #[test]
+    fn should_not_retrigger_while_line_stays_asserted() {
+        let memory = memory_with_interrupt_handlers();
+        memory.borrow_mut()[0x0400] = 0xE8; // INX
+        let mut cpu = new_cpu(&memory, false);
+        cpu.set_nmi_line(true);
+
+        cpu.execute_next_instruction();
+        cpu.set_nmi_line(true);
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.program_counter, 0x0401);
+        assert_eq!(cpu.index_register_x, 0x01);
+    }
+
+    #[test]
+    fn should_take_priority_over_irq() {
+        let memory = memory_with_interrupt_handlers();
+        let mut cpu = new_cpu(&memory, false);
+        cpu.processor_status.change_interrupt_disable_flag(false);
+        cpu.set_irq_line(true);
+        cpu.set_nmi_line(true);
+
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.program_counter, 0x0400);
+    }
+
+    #[test]
+    fn should_hijack_brk_on_nmos_when_arriving_during_its_sequence() {
+        let memory = memory_with_interrupt_handlers();
+        memory.borrow_mut()[0x0400] = 0x40; // RTI
+        let mut cpu = new_cpu(&memory, false);
+        cpu.schedule_nmi(cpu.cycle + 3);
+
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.program_counter, 0x0400);
+        assert_eq!(cpu.cycle, 14);
+        assert_eq!(memory.borrow()[0x01FD] & 0b00010000, 0b00010000);
+
+        // the BRK is never handled on its own, the NMI handler returns past it
+        cpu.execute_next_instruction();
+        assert_eq!(cpu.program_counter, 0x0002);
+    }
+
+    #[test]
+    fn should_hijack_irq_on_nmos_when_arriving_during_its_sequence() {
+        let memory = memory_with_interrupt_handlers();
+        let mut cpu = new_cpu(&memory, false);
+        cpu.processor_status.change_interrupt_disable_flag(false);
+        cpu.set_irq_line(true);
+        cpu.schedule_nmi(cpu.cycle + 2);
+
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.program_counter, 0x0400);
+        assert_eq!(memory.borrow()[0x01FD] & 0b00010000, 0);
+    }
+
+    #[test]
+    fn should_finish_brk_before_servicing_nmi_on_cmos() {
+        let memory = memory_with_interrupt_handlers();
+        let mut cpu = new_cpu(&memory, true);
+        cpu.schedule_nmi(cpu.cycle + 3);
+
+        cpu.execute_next_instruction();
+        assert_eq!(cpu.program_counter, 0x0300);
+
+        cpu.execute_next_instruction();
+        assert_eq!(cpu.program_counter, 0x0400);
+    }
+}
+
+#[cfg(test)]
+mod interrupt_polling {
+    use std::cell::RefCell;
+
+    use super::MemoryMock;
+    use crate::cpu::CPU;
+
+    // IRQ handler at $0300
+    fn new_cpu(memory: &RefCell<MemoryMock>) -> CPU<&RefCell<MemoryMock>> {
+        memory.borrow_mut()[0xFFFE] = 0x00;
+        memory.borrow_mut()[0xFFFF] = 0x03;
+        let mut cpu = CPU::new_nmos(memory);
+        cpu.program_counter = 0x0000;
+        cpu.processor_status.change_interrupt_disable_flag(false);
+
+        return cpu;
+    }
+
+    #[test]
+    fn should_service_interrupt_asserted_by_second_to_last_cycle_after_instruction() {
+        let memory = RefCell::new(MemoryMock::new(&[0xE8, 0xE8])); // INX; INX
+        let mut cpu = new_cpu(&memory);
+        cpu.schedule_irq(cpu.cycle + 1);
+
+        cpu.execute_next_instruction();
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.program_counter, 0x0300);
+        assert_eq!(cpu.index_register_x, 0x01);
+    }
+
+    #[test]
+    fn should_run_one_more_instruction_when_interrupt_is_asserted_on_last_cycle() {
+        let memory = RefCell::new(MemoryMock::new(&[0xE8, 0xE8])); // INX; INX
+        let mut cpu = new_cpu(&memory);
+        cpu.schedule_irq(cpu.cycle + 2);
+
+        cpu.execute_next_instruction();
+        cpu.execute_next_instruction();
+        assert_eq!(cpu.index_register_x, 0x02);
+
+        cpu.execute_next_instruction();
+        assert_eq!(cpu.program_counter, 0x0300);
+    }
+
+    #[test]
+    fn should_delay_interrupt_by_one_instruction_after_taken_branch_without_page_cross() {
+        let memory = RefCell::new(MemoryMock::new(&[0xD0, 0x00, 0xE8])); // BNE +0; INX
+        let mut cpu = new_cpu(&memory);
+        cpu.schedule_irq(cpu.cycle + 2);
+
+        cpu.execute_next_instruction();
+        cpu.execute_next_instruction();
+        assert_eq!(cpu.index_register_x, 0x01);
+
+        cpu.execute_next_instruction();
+        assert_eq!(cpu.program_counter, 0x0300);
+    }
+
+    #[test]
+    fn should_not_delay_interrupt_after_branch_that_is_not_taken() {
+        let memory = RefCell::new(MemoryMock::new(&[0xF0, 0x00, 0xE8])); // BEQ +0; INX
+        let mut cpu = new_cpu(&memory);
+        cpu.schedule_irq(cpu.cycle + 1);
+
+        cpu.execute_next_instruction();
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.program_counter, 0x0300);
+        assert_eq!(cpu.index_register_x, 0x00);
+    }
+}
+
+#[cfg(test)]
+mod rdy_line {
+    use std::cell::RefCell;
+
+    use super::{BusLoggingMemory, MemoryMock};
+    use crate::cpu::CPU;
+
+    #[test]
+    fn should_stall_while_rdy_is_low() {
+        let memory = RefCell::new(MemoryMock::new(&[0xE8])); // INX
+        let mut cpu = CPU::new_nmos(&memory);
+        cpu.program_counter = 0x0000;
+        cpu.cycle = 0;
+        cpu.set_rdy(false);
+
+        assert_eq!(cpu.execute_next_instruction(), None);
+        assert_eq!(cpu.execute_next_instruction(), None);
+
+        assert!(!cpu.get_rdy());
+        assert_eq!(cpu.cycle, 2);
+        assert_eq!(cpu.program_counter, 0x0000);
+        assert_eq!(cpu.index_register_x, 0x00);
+    }
+
+    #[test]
+    fn should_resume_execution_when_rdy_is_released() {
+        let memory = RefCell::new(MemoryMock::new(&[0xE8])); // INX
+        let mut cpu = CPU::new_nmos(&memory);
+        cpu.program_counter = 0x0000;
+        cpu.cycle = 0;
+        cpu.set_rdy(false);
+
+        cpu.execute_next_instruction();
+        cpu.set_rdy(true);
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.cycle, 3);
+        assert_eq!(cpu.program_counter, 0x0001);
+        assert_eq!(cpu.index_register_x, 0x01);
+    }
+
+    #[test]
+    fn should_service_interrupt_raised_during_stall_after_rdy_is_released() {
+        let memory = RefCell::new(MemoryMock::new(&[0xE8])); // INX
+        memory.borrow_mut()[0xFFFE] = 0x00;
+        memory.borrow_mut()[0xFFFF] = 0x03;
+        let mut cpu = CPU::new_nmos(&memory);
+        cpu.program_counter = 0x0000;
+        cpu.processor_status.change_interrupt_disable_flag(false);
+        cpu.set_rdy(false);
+
+        cpu.execute_next_instruction();
+        cpu.set_irq_line(true);
+        cpu.execute_next_instruction();
+        assert_eq!(cpu.program_counter, 0x0000);
+
+        cpu.set_rdy(true);
+        cpu.execute_next_instruction();
+        assert_eq!(cpu.program_counter, 0x0300);
+    }
+
+    #[test]
+    fn should_repeat_read_cycle_while_rdy_is_low_mid_instruction() {
+        let memory = RefCell::new(BusLoggingMemory::new(MemoryMock::new(&[
+            0xA5, 0x10, // LDA $10
+        ])));
+        memory.borrow_mut()[0x0010] = 0x42;
+        let mut cpu = CPU::new_nmos(&memory);
+        cpu.program_counter = 0x0000;
+        cpu.cycle = 0;
+        cpu.schedule_rdy_low(1..3);
+        memory.borrow().reads.borrow_mut().clear();
+
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.cycle, 5);
+        assert_eq!(cpu.accumulator, 0x42);
+        assert_eq!(
+            memory.borrow().reads.take(),
+            vec![0x0000, 0x0001, 0x0001, 0x0001, 0x0010]
+        );
+    }
+
+    #[test]
+    fn should_complete_write_cycle_while_rdy_is_low() {
+        let memory = RefCell::new(MemoryMock::new(&[0x85, 0x10])); // STA $10
+        let mut cpu = CPU::new_nmos(&memory);
+        cpu.program_counter = 0x0000;
+        cpu.cycle = 0;
+        cpu.accumulator = 0x42;
+        cpu.schedule_rdy_low(2..4);
+
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.cycle, 3);
+        assert_eq!(memory.borrow()[0x0010], 0x42);
+    }
+}
+
+#[cfg(test)]
+mod so_line {
+    use std::cell::RefCell;
+
+    use super::MemoryMock;
+    use crate::cpu::CPU;
+
+    #[test]
+    fn should_set_overflow_flag_on_asserting_edge() {
+        let memory = RefCell::new(MemoryMock::default());
+        let mut cpu = CPU::new_nmos(&memory);
+        cpu.processor_status.set(0);
+
+        cpu.set_so_line(true);
+
+        assert!(cpu.get_so_line());
+        assert!(cpu.processor_status.get_overflow_flag());
+    }
+
+    #[test]
+    fn should_not_set_overflow_flag_again_while_line_stays_asserted() {
+        let memory = RefCell::new(MemoryMock::new(&[0xB8])); // CLV
+        let mut cpu = CPU::new_nmos(&memory);
+        cpu.program_counter = 0x0000;
+        cpu.set_so_line(true);
+
+        cpu.execute_next_instruction();
+        cpu.set_so_line(true);
+
+        assert!(!cpu.processor_status.get_overflow_flag());
+
+        cpu.set_so_line(false);
+        cpu.set_so_line(true);
+        assert!(cpu.processor_status.get_overflow_flag());
+    }
+}
+
+#[cfg(test)]
+mod dummy_reads {
+    use std::cell::RefCell;
+
+    use super::{BusLoggingMemory, MemoryMock};
+    use crate::{consts::Word, cpu::CPU};
+
+    fn reads_of(
+        program: &[u8],
+        cmos: bool,
+        setup: impl FnOnce(&mut CPU<&RefCell<BusLoggingMemory>>),
+    ) -> Vec<Word> {
+        let memory = RefCell::new(BusLoggingMemory::new(MemoryMock::new(program)));
+        let mut cpu = if cmos {
+            CPU::new_cmos(&memory)
+        } else {
+            CPU::new_nmos(&memory)
+        };
+        cpu.program_counter = 0x0000;
+        setup(&mut cpu);
+        memory.borrow().reads.borrow_mut().clear();
+
+        cpu.execute_next_instruction();
+
+        return memory.borrow().reads.take();
+    }
+
+    #[test]
+    fn should_read_address_with_unfixed_high_byte_on_page_crossing() {
+        let reads = reads_of(&[0xBD, 0xF0, 0x20], false, |cpu| {
+            cpu.index_register_x = 0x20;
+        }); // LDA $20F0,X
+
+        assert_eq!(reads, vec![0x0000, 0x0001, 0x0002, 0x2010, 0x2110]);
+    }
+
+    #[test]
+    fn should_not_do_dummy_read_when_indexing_stays_on_page() {
+        let reads = reads_of(&[0xB9, 0x10, 0x20], false, |cpu| {
+            cpu.index_register_y = 0x20;
+        }); // LDA $2010,Y
+
+        assert_eq!(reads, vec![0x0000, 0x0001, 0x0002, 0x2030]);
+    }
+
+    #[test]
+    fn should_read_address_with_unfixed_high_byte_for_indirect_indexed() {
+        let reads = reads_of(&[0xB1, 0x10], false, |cpu| {
+            cpu.index_register_y = 0x20;
+            cpu.memory.borrow_mut()[0x0010] = 0xF0;
+            cpu.memory.borrow_mut()[0x0011] = 0x20;
+        }); // LDA ($10),Y
+
+        assert_eq!(reads, vec![0x0000, 0x0001, 0x0010, 0x0011, 0x2010, 0x2110]);
+    }
+
+    #[test]
+    fn should_reread_last_operand_byte_on_cmos() {
+        let reads = reads_of(&[0xBD, 0xF0, 0x20], true, |cpu| {
+            cpu.index_register_x = 0x20;
+        }); // LDA $20F0,X
+
+        assert_eq!(reads, vec![0x0000, 0x0001, 0x0002, 0x0002, 0x2110]);
+    }
+
+    #[test]
+    fn should_always_do_dummy_read_for_read_modify_write_on_nmos() {
+        let reads = reads_of(&[0xFE, 0x10, 0x20], false, |cpu| {
+            cpu.index_register_x = 0x20;
+        }); // INC $2010,X
+
+        assert_eq!(reads, vec![0x0000, 0x0001, 0x0002, 0x2030, 0x2030]);
+    }
+
+    #[test]
+    fn should_read_target_before_indexed_store() {
+        let reads = reads_of(&[0x9D, 0x10, 0x20], false, |cpu| {
+            cpu.index_register_x = 0x20;
+        }); // STA $2010,X
+
+        assert_eq!(reads, vec![0x0000, 0x0001, 0x0002, 0x2030]);
+    }
+}
+
+#[cfg(test)]
+mod read_modify_write {
+    use std::cell::RefCell;
+
+    use super::{BusLoggingMemory, MemoryMock};
+    use crate::cpu::CPU;
+
+    #[test]
+    fn should_write_unmodified_value_before_modified_one_on_nmos() {
+        let memory = RefCell::new(BusLoggingMemory::new(MemoryMock::new(&[
+            0xEE, 0x10, 0x20, // INC $2010
+        ])));
+        let mut cpu = CPU::new_nmos(&memory);
+        cpu.program_counter = 0x0000;
+        memory.borrow_mut()[0x2010] = 0x41;
+        memory.borrow_mut().writes.clear();
+
+        cpu.execute_next_instruction();
+
+        assert_eq!(memory.borrow().writes, vec![0x2010, 0x2010]);
+        assert_eq!(memory.borrow()[0x2010], 0x42);
+    }
+
+    #[test]
+    fn should_read_instead_of_writing_twice_on_cmos() {
+        let memory = RefCell::new(BusLoggingMemory::new(MemoryMock::new(&[
+            0xEE, 0x10, 0x20, // INC $2010
+        ])));
+        let mut cpu = CPU::new_cmos(&memory);
+        cpu.program_counter = 0x0000;
+        memory.borrow().reads.borrow_mut().clear();
+        memory.borrow_mut().writes.clear();
+
+        cpu.execute_next_instruction();
+
+        assert_eq!(memory.borrow().writes, vec![0x2010]);
+        assert_eq!(
+            memory.borrow().reads.take(),
+            vec![0x0000, 0x0001, 0x0002, 0x2010, 0x2010]
+        );
+    }
+}
+
+#[cfg(test)]
+mod stack_accesses {
+    use std::cell::RefCell;
+
+    use super::{BusLoggingMemory, MemoryMock};
+    use crate::{consts::Word, cpu::CPU};
+
+    fn reads_of(
+        program: &[u8],
+        setup: impl FnOnce(&mut CPU<&RefCell<BusLoggingMemory>>),
+    ) -> Vec<Word> {
+        let memory = RefCell::new(BusLoggingMemory::new(MemoryMock::new(program)));
+        let mut cpu = CPU::new_nmos(&memory);
+        cpu.program_counter = 0x0000;
+        setup(&mut cpu);
+        memory.borrow().reads.borrow_mut().clear();
+
+        cpu.execute_next_instruction();
+
+        return memory.borrow().reads.take();
+    }
+
+    #[test]
+    fn should_read_top_of_stack_before_pulling() {
+        let reads = reads_of(&[0x68], |cpu| cpu.stack_pointer = 0xF0); // PLA
+
+        assert_eq!(reads, vec![0x0000, 0x0001, 0x01F0, 0x01F1]);
+    }
+
+    #[test]
+    fn should_fetch_target_high_byte_after_pushing_return_address() {
+        let memory = RefCell::new(BusLoggingMemory::new(MemoryMock::new(&[
+            0x20, 0x34, 0x12, // JSR $1234
+        ])));
+        let mut cpu = CPU::new_nmos(&memory);
+        cpu.program_counter = 0x0000;
+        cpu.stack_pointer = 0xFF;
+        cpu.cycle = 0;
+        memory.borrow().reads.borrow_mut().clear();
+        memory.borrow_mut().writes.clear();
+
+        cpu.execute_next_instruction();
+
+        assert_eq!(
+            memory.borrow().reads.take(),
+            vec![0x0000, 0x0001, 0x01FF, 0x0002]
+        );
+        assert_eq!(memory.borrow().writes, vec![0x01FF, 0x01FE]);
+        assert_eq!(cpu.program_counter, 0x1234);
+        assert_eq!(cpu.cycle, 6);
+    }
+
+    #[test]
+    fn should_read_return_address_before_incrementing_it() {
+        let reads = reads_of(&[0x60], |cpu| {
+            cpu.stack_pointer = 0xFD;
+            cpu.memory.borrow_mut()[0x01FE] = 0x02;
+            cpu.memory.borrow_mut()[0x01FF] = 0x03;
+        }); // RTS
+
+        assert_eq!(reads, vec![0x0000, 0x0001, 0x01FD, 0x01FE, 0x01FF, 0x0302]);
+    }
+
+    #[test]
+    fn should_wrap_stack_pointer_around_within_stack_page() {
+        let memory = RefCell::new(MemoryMock::new(&[0x48, 0x68])); // PHA; PLA
+        let mut cpu = CPU::new_nmos(&memory);
+        cpu.program_counter = 0x0000;
+        cpu.stack_pointer = 0x00;
+        cpu.accumulator = 0x42;
+
+        cpu.execute_next_instruction();
+        assert_eq!(memory.borrow()[0x0100], 0x42);
+        assert_eq!(cpu.stack_pointer, 0xFF);
+
+        cpu.accumulator = 0x00;
+        cpu.execute_next_instruction();
+        assert_eq!(cpu.accumulator, 0x42);
+        assert_eq!(cpu.stack_pointer, 0x00);
+    }
+}
+
+#[cfg(test)]
+mod csg_65ce02 {
+    use std::cell::RefCell;
+
+    use super::MemoryMock;
+    use crate::cpu::CPU;
+
+    fn run(cpu: &mut CPU<&RefCell<MemoryMock>>, instructions: usize) {
+        for _ in 0..instructions {
+            cpu.execute_next_instruction();
+        }
+    }
+
+    #[test]
+    fn should_store_z_register_with_stz() {
+        // LDZ #$42, STZ $20
+        let memory = &RefCell::new(MemoryMock::new(&[0xA3, 0x42, 0x64, 0x20]));
+        let mut cpu = CPU::new_65ce02(memory);
+        cpu.program_counter = 0x0000;
+
+        run(&mut cpu, 2);
+
+        assert_eq!(cpu.index_register_z, 0x42);
+        assert_eq!(memory.borrow()[0x0020], 0x42);
+    }
+
+    #[test]
+    fn should_index_zero_page_indirect_with_z() {
+        // LDZ #$03, LDA ($20),Z
+        let memory = &RefCell::new(MemoryMock::new(&[0xA3, 0x03, 0xB2, 0x20]));
+        memory.borrow_mut()[0x0020] = 0x00;
+        memory.borrow_mut()[0x0021] = 0x30;
+        memory.borrow_mut()[0x3003] = 0x99;
+        let mut cpu = CPU::new_65ce02(memory);
+        cpu.program_counter = 0x0000;
+
+        run(&mut cpu, 2);
+
+        assert_eq!(cpu.accumulator, 0x99);
+    }
+
+    #[test]
+    fn should_address_zero_page_through_base_page_register() {
+        // LDA #$12, TAB, LDA $34
+        let memory = &RefCell::new(MemoryMock::new(&[0xA9, 0x12, 0x5B, 0xA5, 0x34]));
+        memory.borrow_mut()[0x1234] = 0x77;
+        let mut cpu = CPU::new_65ce02(memory);
+        cpu.program_counter = 0x0000;
+
+        run(&mut cpu, 3);
+
+        assert_eq!(cpu.base_page, 0x12);
+        assert_eq!(cpu.accumulator, 0x77);
+    }
+
+    #[test]
+    fn should_carry_into_stack_pointer_high_byte_with_extended_stack() {
+        // CLE, LDY #$20, TYS, LDX #$00, TXS, LDA #$55, PHA
+        let memory = &RefCell::new(MemoryMock::new(&[
+            0x02, 0xA0, 0x20, 0x2B, 0xA2, 0x00, 0x9A, 0xA9, 0x55, 0x48,
+        ]));
+        let mut cpu = CPU::new_65ce02(memory);
+        cpu.program_counter = 0x0000;
+
+        run(&mut cpu, 7);
+
+        assert_eq!(memory.borrow()[0x2000], 0x55);
+        assert_eq!(cpu.stack_pointer_hi, 0x1F);
+        assert_eq!(cpu.stack_pointer, 0xFF);
+    }
+
+    #[test]
+    fn should_wrap_within_stack_page_by_default() {
+        // LDY #$20, TYS, LDX #$00, TXS, PHA
+        let memory = &RefCell::new(MemoryMock::new(&[0xA0, 0x20, 0x2B, 0xA2, 0x00, 0x9A, 0x48]));
+        let mut cpu = CPU::new_65ce02(memory);
+        cpu.program_counter = 0x0000;
+
+        run(&mut cpu, 5);
+
+        assert_eq!(cpu.stack_pointer_hi, 0x20);
+        assert_eq!(cpu.stack_pointer, 0xFF);
+    }
+
+    #[test]
+    fn should_branch_with_word_offset_from_last_instruction_byte() {
+        // BRA +$1000
+        let memory = &RefCell::new(MemoryMock::new(&[0x83, 0x00, 0x10]));
+        let mut cpu = CPU::new_65ce02(memory);
+        cpu.program_counter = 0x0000;
+
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.program_counter, 0x1002);
+    }
+
+    #[test]
+    fn should_return_from_bsr_after_the_instruction() {
+        // BSR +$0010 with RTS at the target
+        let memory = &RefCell::new(MemoryMock::new(&[0x63, 0x10, 0x00]));
+        memory.borrow_mut()[0x0012] = 0x60;
+        let mut cpu = CPU::new_65ce02(memory);
+        cpu.program_counter = 0x0000;
+
+        cpu.execute_next_instruction();
+        assert_eq!(cpu.program_counter, 0x0012);
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.program_counter, 0x0003);
+    }
+
+    #[test]
+    fn should_drop_arguments_with_rts_immediate() {
+        // JSR $0010, with RTS #$02 at the target
+        let memory = &RefCell::new(MemoryMock::new(&[0x20, 0x10, 0x00]));
+        memory.borrow_mut()[0x0010] = 0x62;
+        memory.borrow_mut()[0x0011] = 0x02;
+        let mut cpu = CPU::new_65ce02(memory);
+        cpu.program_counter = 0x0000;
+        let stack_pointer = cpu.stack_pointer;
+
+        run(&mut cpu, 2);
+
+        assert_eq!(cpu.program_counter, 0x0003);
+        assert_eq!(cpu.stack_pointer, stack_pointer.wrapping_add(2));
+    }
+
+    #[test]
+    fn should_increment_zero_page_word_with_inw() {
+        // INW $20
+        let memory = &RefCell::new(MemoryMock::new(&[0xE3, 0x20]));
+        memory.borrow_mut()[0x0020] = 0xFF;
+        memory.borrow_mut()[0x0021] = 0x12;
+        let mut cpu = CPU::new_65ce02(memory);
+        cpu.program_counter = 0x0000;
+
+        cpu.execute_next_instruction();
+
+        assert_eq!(memory.borrow()[0x0020], 0x00);
+        assert_eq!(memory.borrow()[0x0021], 0x13);
+        assert!(!cpu.processor_status.get_zero_flag());
+    }
+
+    #[test]
+    fn should_shift_word_left_with_asw() {
+        // ASW $3000
+        let memory = &RefCell::new(MemoryMock::new(&[0xCB, 0x00, 0x30]));
+        memory.borrow_mut()[0x3000] = 0x80;
+        memory.borrow_mut()[0x3001] = 0x80;
+        let mut cpu = CPU::new_65ce02(memory);
+        cpu.program_counter = 0x0000;
+
+        cpu.execute_next_instruction();
+
+        assert_eq!(memory.borrow()[0x3000], 0x00);
+        assert_eq!(memory.borrow()[0x3001], 0x01);
+        assert!(cpu.processor_status.get_carry_flag());
+    }
+
+    #[test]
+    fn should_negate_accumulator_and_shift_arithmetically() {
+        // LDA #$04, NEG, ASR
+        let memory = &RefCell::new(MemoryMock::new(&[0xA9, 0x04, 0x42, 0x43]));
+        let mut cpu = CPU::new_65ce02(memory);
+        cpu.program_counter = 0x0000;
+
+        run(&mut cpu, 3);
+
+        assert_eq!(cpu.accumulator, 0xFE);
+        assert!(cpu.processor_status.get_negative_flag());
+    }
+
+    #[test]
+    fn should_load_through_stack_relative_pointer_indexed_with_y() {
+        // LDY #$01, LDA ($02,SP),Y
+        let memory = &RefCell::new(MemoryMock::new(&[0xA0, 0x01, 0xE2, 0x02]));
+        let mut cpu = CPU::new_65ce02(memory);
+        cpu.program_counter = 0x0000;
+        let pointer_address = 0x0100 + cpu.stack_pointer as usize + 2;
+        memory.borrow_mut()[pointer_address as u16] = 0x00;
+        memory.borrow_mut()[pointer_address as u16 + 1] = 0x40;
+        memory.borrow_mut()[0x4001] = 0x5A;
+
+        run(&mut cpu, 2);
+
+        assert_eq!(cpu.accumulator, 0x5A);
+    }
+}
+
+#[cfg(test)]
+mod bus_types {
+    use std::cell::RefCell;
+
+    use super::MemoryMock;
+    use crate::{cpu::CPU, memory::Bus};
+
+    // LDA #$42, STA $10
+    const PROGRAM: [u8; 4] = [0xA9, 0x42, 0x85, 0x10];
+
+    #[test]
+    fn should_run_on_memory_owned_by_cpu() {
+        let mut cpu = CPU::new_nmos(MemoryMock::new(&PROGRAM));
+        cpu.program_counter = 0x0000;
+
+        cpu.execute_next_instruction();
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.get_memory()[0x0010], 0x42);
+    }
+
+    #[test]
+    fn should_run_on_boxed_bus() {
+        let memory: Box<dyn Bus> = Box::new(MemoryMock::new(&PROGRAM));
+        let mut cpu = CPU::new_nmos(memory);
+        cpu.program_counter = 0x0000;
+
+        cpu.execute_next_instruction();
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.get_memory_mut().read(0x0010), 0x42);
+    }
+
+    #[test]
+    fn should_share_memory_behind_ref_cell() {
+        let memory = RefCell::new(MemoryMock::new(&PROGRAM));
+        let mut cpu = CPU::new_nmos(&memory);
+        cpu.program_counter = 0x0000;
+
+        cpu.execute_next_instruction();
+        cpu.execute_next_instruction();
+
+        assert_eq!(memory.borrow()[0x0010], 0x42);
+    }
+}
+
+#[cfg(test)]
+mod owned_memory {
+    use super::MemoryMock;
+    use crate::cpu::CPU;
+
+    // no lifetime ties the CPU to memory living elsewhere
+    struct Emulator {
+        cpu: CPU<MemoryMock>,
+    }
+
+    #[test]
+    fn should_keep_cpu_with_owned_memory_in_struct() {
+        // INX, INX
+        let mut emulator = Emulator {
+            cpu: CPU::with_memory(MemoryMock::new(&[0xE8, 0xE8])),
+        };
+        emulator.cpu.program_counter = 0x0000;
+
+        emulator.cpu.execute_next_instruction();
+        emulator.cpu.execute_next_instruction();
+
+        assert_eq!(emulator.cpu.get_index_register_x(), 2);
+    }
+
+    #[test]
+    fn should_hand_memory_back_when_cpu_is_consumed() {
+        // LDA #$42, STA $10
+        let mut cpu = CPU::with_memory(MemoryMock::new(&[0xA9, 0x42, 0x85, 0x10]));
+        cpu.program_counter = 0x0000;
+        cpu.execute_next_instruction();
+        cpu.execute_next_instruction();
+
+        let memory = cpu.into_memory();
+
+        assert_eq!(memory[0x0010], 0x42);
+    }
+}
+
+#[cfg(test)]
+mod state_digest {
+    use std::cell::RefCell;
+
+    use super::MemoryMock;
+    use crate::cpu::CPU;
+
+    #[test]
+    fn should_change_digest_with_65ce02_registers() {
+        let memory = &RefCell::new(MemoryMock::default());
+        let mut cpu = CPU::new_65ce02(memory);
+        let mut digests = vec![cpu.state_digest()];
+
+        cpu.index_register_z = 0x01;
+        digests.push(cpu.state_digest());
+        cpu.base_page = 0x20;
+        digests.push(cpu.state_digest());
+        cpu.stack_pointer_hi = 0x02;
+        digests.push(cpu.state_digest());
+        cpu.extended_stack = true;
+        digests.push(cpu.state_digest());
+
+        for (idx, digest) in digests.iter().enumerate() {
+            assert!(!digests[idx + 1..].contains(digest));
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use std::collections::HashSet;
+
+use crate::consts::Byte;
+
+const STABLE_OPCODES: [Byte; 85] = [
+    0x03, 0x07, 0x0F, 0x13, 0x17, 0x1B, 0x1F, // SLO
+    0x23, 0x27, 0x2F, 0x33, 0x37, 0x3B, 0x3F, // RLA
+    0x43, 0x47, 0x4F, 0x53, 0x57, 0x5B, 0x5F, // SRE
+    0x63, 0x67, 0x6F, 0x73, 0x77, 0x7B, 0x7F, // RRA
+    0x83, 0x87, 0x8F, 0x97, // SAX
+    0xA3, 0xA7, 0xAF, 0xB3, 0xB7, 0xBF, // LAX
+    0xC3, 0xC7, 0xCF, 0xD3, 0xD7, 0xDB, 0xDF, // DCP
+    0xE3, 0xE7, 0xEF, 0xF3, 0xF7, 0xFB, 0xFF, // ISC
+    0x0B, 0x2B, 0x4B, 0x6B, 0xCB, 0xEB, // ANC, ALR, ARR, SBX, USBC
+    0x1A, 0x3A, 0x5A, 0x7A, 0xDA, 0xFA, // NOP
+    0x80, 0x82, 0x89, 0xC2, 0xE2, // NOP #imm
+    0x04, 0x44, 0x64, 0x14, 0x34, 0x54, 0x74, 0xD4, 0xF4, // NOP zp, zp,X
+    0x0C, 0x1C, 0x3C, 0x5C, 0x7C, 0xDC, 0xFC, // NOP abs, abs,X
+];
+
+// results of these depend on analog effects and differ between chip revisions
+const UNSTABLE_OPCODES: [Byte; 8] = [
+    0x8B, // ANE
+    0xAB, // LXA
+    0x93, 0x9F, // SHA
+    0x9B, // TAS
+    0xBB, // LAS
+    0x9C, // SHY
+    0x9E, // SHX
+];
+
+// the value most commonly measured on NMOS chips, also assumed by common test suites
+pub const DEFAULT_MAGIC_CONSTANT: Byte = 0xEE;
+
+const JAM_OPCODES: [Byte; 12] = [
+    0x02, 0x12, 0x22, 0x32, 0x42, 0x52, 0x62, 0x72, 0x92, 0xB2, 0xD2, 0xF2,
+];
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum UndocumentedClass {
+    Stable,
+    Unstable,
+    Jam,
+}
+
+impl UndocumentedClass {
+    pub fn classify(opcode: Byte) -> Option<Self> {
+        if STABLE_OPCODES.contains(&opcode) {
+            return Some(UndocumentedClass::Stable);
+        }
+        if UNSTABLE_OPCODES.contains(&opcode) {
+            return Some(UndocumentedClass::Unstable);
+        }
+        if JAM_OPCODES.contains(&opcode) {
+            return Some(UndocumentedClass::Jam);
+        }
+
+        return None;
+    }
+
+    pub fn opcodes(&self) -> &'static [Byte] {
+        return match self {
+            UndocumentedClass::Stable => &STABLE_OPCODES,
+            UndocumentedClass::Unstable => &UNSTABLE_OPCODES,
+            UndocumentedClass::Jam => &JAM_OPCODES,
+        };
+    }
+}
+
+// documented opcodes are always enabled, undocumented ones start enabled and can be
+// switched off one by one or per class to fault on them instead of executing
+#[derive(Clone, Default, Debug)]
+pub struct UndocumentedOpcodes {
+    disabled: HashSet<Byte>,
+}
+
+impl UndocumentedOpcodes {
+    pub fn new() -> Self {
+        return UndocumentedOpcodes::default();
+    }
+
+    pub fn all_disabled() -> Self {
+        let mut matrix = UndocumentedOpcodes::new();
+        for class in [
+            UndocumentedClass::Stable,
+            UndocumentedClass::Unstable,
+            UndocumentedClass::Jam,
+        ] {
+            matrix.disable_class(class);
+        }
+
+        return matrix;
+    }
+
+    pub fn is_enabled(&self, opcode: Byte) -> bool {
+        return !self.disabled.contains(&opcode);
+    }
+
+    pub fn enable(&mut self, opcode: Byte) {
+        self.disabled.remove(&opcode);
+    }
+
+    pub fn disable(&mut self, opcode: Byte) -> bool {
+        if UndocumentedClass::classify(opcode).is_none() {
+            return false;
+        }
+
+        self.disabled.insert(opcode);
+        return true;
+    }
+
+    pub fn enable_class(&mut self, class: UndocumentedClass) {
+        for opcode in class.opcodes() {
+            self.disabled.remove(opcode);
+        }
+    }
+
+    pub fn disable_class(&mut self, class: UndocumentedClass) {
+        self.disabled.extend(class.opcodes());
+    }
+}
+
+#[cfg(test)]
+mod tests;
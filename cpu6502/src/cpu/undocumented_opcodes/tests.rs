@@ -0,0 +1,117 @@
+#[cfg(test)]
+mod classify {
+    use crate::cpu::undocumented_opcodes::UndocumentedClass;
+
+    #[test]
+    fn should_classify_undocumented_opcodes() {
+        assert_eq!(
+            UndocumentedClass::classify(0xA7),
+            Some(UndocumentedClass::Stable)
+        );
+        assert_eq!(
+            UndocumentedClass::classify(0x8B),
+            Some(UndocumentedClass::Unstable)
+        );
+        assert_eq!(
+            UndocumentedClass::classify(0x02),
+            Some(UndocumentedClass::Jam)
+        );
+    }
+
+    #[test]
+    fn should_not_classify_documented_opcodes() {
+        assert_eq!(UndocumentedClass::classify(0xA9), None);
+        assert_eq!(UndocumentedClass::classify(0xEA), None);
+    }
+
+    #[test]
+    fn should_cover_every_opcode_missing_from_documented_set() {
+        let undocumented = (0..=0xFF)
+            .filter(|opcode| UndocumentedClass::classify(*opcode).is_some())
+            .count();
+
+        assert_eq!(undocumented, 105);
+    }
+}
+
+#[cfg(test)]
+mod is_enabled {
+    use crate::cpu::undocumented_opcodes::{UndocumentedClass, UndocumentedOpcodes};
+
+    #[test]
+    fn should_enable_every_opcode_by_default() {
+        let matrix = UndocumentedOpcodes::new();
+
+        assert!((0..=0xFF).all(|opcode| matrix.is_enabled(opcode)));
+    }
+
+    #[test]
+    fn should_disable_single_opcode() {
+        let mut matrix = UndocumentedOpcodes::new();
+
+        assert!(matrix.disable(0xAB));
+
+        assert!(!matrix.is_enabled(0xAB));
+        assert!(matrix.is_enabled(0x8B));
+    }
+
+    #[test]
+    fn should_refuse_to_disable_documented_opcode() {
+        let mut matrix = UndocumentedOpcodes::new();
+
+        assert!(!matrix.disable(0xA9));
+        assert!(matrix.is_enabled(0xA9));
+    }
+
+    #[test]
+    fn should_toggle_whole_classes() {
+        let mut matrix = UndocumentedOpcodes::new();
+
+        matrix.disable_class(UndocumentedClass::Unstable);
+        assert!(!matrix.is_enabled(0x9B));
+        assert!(matrix.is_enabled(0xA7));
+
+        matrix.enable_class(UndocumentedClass::Unstable);
+        assert!(matrix.is_enabled(0x9B));
+    }
+
+    #[test]
+    fn should_allow_reenabling_single_opcode_of_disabled_class() {
+        let mut matrix = UndocumentedOpcodes::all_disabled();
+
+        matrix.enable(0xA7);
+
+        assert!(matrix.is_enabled(0xA7));
+        assert!(!matrix.is_enabled(0xAF));
+        assert!(!matrix.is_enabled(0x02));
+        assert!(matrix.is_enabled(0xA9));
+    }
+}
+
+#[cfg(test)]
+mod cpu_integration {
+    use std::cell::RefCell;
+
+    use crate::cpu::{tests::MemoryMock, CPU};
+
+    #[test]
+    fn should_execute_undocumented_opcode_on_nmos() {
+        let memory = &RefCell::new(MemoryMock::new(&[0xA7, 0x02, 0x42])); // LAX $02
+        let mut cpu = CPU::new_nmos(memory);
+        cpu.program_counter = 0x0000;
+
+        assert_eq!(cpu.execute_next_instruction(), None);
+        assert_eq!(cpu.index_register_x, 0x42);
+    }
+
+    #[test]
+    fn should_execute_undocumented_opcode_as_nop_on_cmos() {
+        let memory = &RefCell::new(MemoryMock::new(&[0xA7, 0x02, 0x42])); // LAX $02
+        let mut cpu = CPU::new_cmos(memory);
+        cpu.program_counter = 0x0000;
+
+        assert_eq!(cpu.execute_next_instruction(), None);
+        assert_eq!(cpu.accumulator, 0x00);
+        assert_eq!(cpu.program_counter, 0x0001);
+    }
+}
@@ -0,0 +1,83 @@
+use std::ops::RangeInclusive;
+
+use crate::consts::Word;
+
+const ADDRESS_SPACE_SIZE: usize = 64 * 1024;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct UninitializedRead {
+    pub address: Word,
+    pub program_counter: Word,
+    pub cycle: u64,
+}
+
+// every location is reported on its first read only; memory filled by the host (program
+// images, ROMs) has to be marked as initialized, otherwise fetching it is reported too
+pub struct UninitializedMemoryTracker {
+    written: Vec<bool>,
+    reported: Vec<bool>,
+    reads: Vec<UninitializedRead>,
+    unhandled_reads: usize,
+    stop_on_read: bool,
+}
+
+impl UninitializedMemoryTracker {
+    pub fn new(stop_on_read: bool) -> Self {
+        return UninitializedMemoryTracker {
+            written: vec![false; ADDRESS_SPACE_SIZE],
+            reported: vec![false; ADDRESS_SPACE_SIZE],
+            reads: Vec::new(),
+            unhandled_reads: 0,
+            stop_on_read,
+        };
+    }
+
+    pub fn mark_initialized(&mut self, region: RangeInclusive<Word>) {
+        for addr in region {
+            self.written[addr as usize] = true;
+        }
+    }
+
+    pub fn is_initialized(&self, addr: Word) -> bool {
+        return self.written[addr as usize];
+    }
+
+    pub fn get_reads(&self) -> &[UninitializedRead] {
+        return &self.reads;
+    }
+
+    pub fn record_write(&mut self, addr: Word) {
+        self.written[addr as usize] = true;
+    }
+
+    pub fn record_read(&mut self, addr: Word, program_counter: Word, cycle: u64) {
+        let idx = addr as usize;
+        if self.written[idx] || self.reported[idx] {
+            return;
+        }
+
+        self.reported[idx] = true;
+        self.reads.push(UninitializedRead {
+            address: addr,
+            program_counter,
+            cycle,
+        });
+        if self.stop_on_read {
+            self.unhandled_reads += 1;
+        }
+    }
+
+    // first read reported since the previous call, used to stop execution when requested
+    pub fn take_unhandled_read(&mut self) -> Option<UninitializedRead> {
+        if self.unhandled_reads == 0 {
+            return None;
+        }
+
+        let read = self.reads[self.reads.len() - self.unhandled_reads];
+        self.unhandled_reads = 0;
+        return Some(read);
+    }
+}
+
+#[cfg(test)]
+mod tests;
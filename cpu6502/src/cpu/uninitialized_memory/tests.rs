@@ -0,0 +1,91 @@
+#[cfg(test)]
+mod record_read {
+    use crate::cpu::uninitialized_memory::{UninitializedMemoryTracker, UninitializedRead};
+
+    #[test]
+    fn should_report_first_read_of_never_written_location_only() {
+        let mut tracker = UninitializedMemoryTracker::new(false);
+
+        tracker.record_read(0x0010, 0x0600, 3);
+        tracker.record_read(0x0010, 0x0604, 9);
+
+        assert_eq!(
+            tracker.get_reads(),
+            &[UninitializedRead {
+                address: 0x0010,
+                program_counter: 0x0600,
+                cycle: 3,
+            }]
+        );
+    }
+
+    #[test]
+    fn should_not_report_written_or_marked_locations() {
+        let mut tracker = UninitializedMemoryTracker::new(false);
+        tracker.record_write(0x0010);
+        tracker.mark_initialized(0x0600..=0x06FF);
+
+        tracker.record_read(0x0010, 0x0600, 3);
+        tracker.record_read(0x0650, 0x0600, 3);
+
+        assert!(tracker.get_reads().is_empty());
+    }
+
+    #[test]
+    fn should_hand_out_unhandled_read_only_when_stopping() {
+        let mut quiet = UninitializedMemoryTracker::new(false);
+        let mut stopping = UninitializedMemoryTracker::new(true);
+
+        quiet.record_read(0x0010, 0x0600, 3);
+        stopping.record_read(0x0010, 0x0600, 3);
+
+        assert_eq!(quiet.take_unhandled_read(), None);
+        assert_eq!(stopping.take_unhandled_read().unwrap().address, 0x0010);
+        assert_eq!(stopping.take_unhandled_read(), None);
+    }
+}
+
+#[cfg(all(test, feature = "uninitialized-memory"))]
+mod cpu_integration {
+    use std::cell::RefCell;
+
+    use crate::cpu::{emulation_event::EmulationEvent, tests::MemoryMock, CPU};
+
+    #[test]
+    fn should_report_load_from_never_written_location() {
+        let memory = &RefCell::new(MemoryMock::new(&[
+            0x85, 0x10, // STA $10
+            0xA5, 0x10, // LDA $10
+            0xA5, 0x11, // LDA $11
+        ]));
+        let mut cpu = CPU::new_nmos(memory);
+        cpu.program_counter = 0x0000;
+        cpu.enable_uninitialized_read_detection(true);
+        cpu.mark_memory_initialized(0x0000..=0x0005);
+
+        assert_eq!(cpu.execute_next_instruction(), None);
+        assert_eq!(cpu.execute_next_instruction(), None);
+        let event = cpu.execute_next_instruction();
+
+        match event {
+            Some(EmulationEvent::UninitializedRead(read)) => {
+                assert_eq!(read.address, 0x0011);
+                assert_eq!(read.program_counter, 0x0004);
+            }
+            _ => panic!("expected uninitialized read, got {event:?}"),
+        }
+    }
+
+    #[test]
+    fn should_only_log_reads_when_not_stopping() {
+        let memory = &RefCell::new(MemoryMock::new(&[0xA5, 0x11])); // LDA $11
+        let mut cpu = CPU::new_nmos(memory);
+        cpu.program_counter = 0x0000;
+        cpu.enable_uninitialized_read_detection(false);
+        cpu.mark_memory_initialized(0x0000..=0x0001);
+
+        assert_eq!(cpu.execute_next_instruction(), None);
+        let tracker = cpu.get_uninitialized_read_detection().unwrap();
+        assert_eq!(tracker.get_reads().len(), 1);
+    }
+}
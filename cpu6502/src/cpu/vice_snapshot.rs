@@ -0,0 +1,125 @@
+use super::CPU;
+use crate::consts::{Byte, Word};
+use crate::memory::Bus;
+
+const MODULE_NAME: &[u8] = b"MAINCPU";
+const MODULE_NAME_LENGTH: usize = 16;
+const MODULE_HEADER_LENGTH: usize = MODULE_NAME_LENGTH + 2 + 4;
+const MAJOR_VERSION: Byte = 1;
+const MINOR_VERSION: Byte = 1;
+const REGISTERS_LENGTH: usize = 4 + 4 + 2 + 1 + 4;
+// irq_clk, nmi_clk, num_last_stolen_cycles and last_stolen_cycles_clk, then nirq, nnmi and
+// global_pending_int
+const INTERRUPT_STATUS_LENGTH: usize = 7 * 4;
+const MODULE_LENGTH: usize = MODULE_HEADER_LENGTH + REGISTERS_LENGTH + INTERRUPT_STATUS_LENGTH;
+// VICE pending interrupt kinds
+const IK_NMI: u32 = 1 << 0;
+const IK_IRQ: u32 = 1 << 1;
+
+// layout of the MAINCPU module: 16-byte zero-padded name, major and minor version, module size
+// as a little-endian dword (header included), then clock (dword), A, X, Y, SP, PC (word),
+// status and last opcode info (dword), followed by the interrupt status VICE keeps for the CPU.
+// Clocks are dwords, a cycle counter past them cannot be exported.
+pub fn write_cpu_module<M: Bus>(cpu: &CPU<M>) -> Result<Vec<Byte>, String> {
+    let mut module = Vec::with_capacity(MODULE_LENGTH);
+    let mut name = [0u8; MODULE_NAME_LENGTH];
+    name[..MODULE_NAME.len()].copy_from_slice(MODULE_NAME);
+    module.extend_from_slice(&name);
+    module.push(MAJOR_VERSION);
+    module.push(MINOR_VERSION);
+    module.extend_from_slice(&(MODULE_LENGTH as u32).to_le_bytes());
+
+    module.extend_from_slice(&to_clock(cpu.cycle)?.to_le_bytes());
+    module.push(cpu.accumulator);
+    module.push(cpu.index_register_x);
+    module.push(cpu.index_register_y);
+    module.push(cpu.stack_pointer);
+    module.extend_from_slice(&cpu.program_counter.to_le_bytes());
+    module.push(cpu.processor_status.into());
+    module.extend_from_slice(&0u32.to_le_bytes());
+
+    let mut pending_interrupts = 0;
+    if cpu.nmi_edge.is_some() {
+        pending_interrupts |= IK_NMI;
+    }
+    if cpu.irq_line.is_some() {
+        pending_interrupts |= IK_IRQ;
+    }
+    let interrupt_status = [
+        to_clock(cpu.irq_line.unwrap_or(0))?,
+        to_clock(cpu.nmi_edge.unwrap_or(0))?,
+        // no cycles are stolen by DMA here
+        0,
+        0,
+        cpu.irq_line.is_some() as u32,
+        cpu.nmi_line as u32,
+        pending_interrupts,
+    ];
+    for dword in interrupt_status {
+        module.extend_from_slice(&dword.to_le_bytes());
+    }
+
+    return Ok(module);
+}
+
+fn to_clock(cycle: u64) -> Result<u32, String> {
+    return u32::try_from(cycle)
+        .map_err(|_| format!("cycle {cycle} does not fit in the 32-bit snapshot clock"));
+}
+
+fn read_dword(data: &[Byte], offset: usize) -> u32 {
+    return u32::from_le_bytes([
+        data[offset],
+        data[offset + 1],
+        data[offset + 2],
+        data[offset + 3],
+    ]);
+}
+
+pub fn read_cpu_module<M: Bus>(cpu: &mut CPU<M>, module: &[Byte]) -> Result<(), String> {
+    if module.len() < MODULE_HEADER_LENGTH + REGISTERS_LENGTH {
+        return Err(String::from("module too short"));
+    }
+
+    let name = &module[..MODULE_NAME_LENGTH];
+    let name_length = name
+        .iter()
+        .position(|byte| *byte == 0)
+        .unwrap_or(name.len());
+    if &name[..name_length] != MODULE_NAME {
+        return Err(format!(
+            "unexpected module: {}",
+            String::from_utf8_lossy(&name[..name_length])
+        ));
+    }
+    let major_version = module[MODULE_NAME_LENGTH];
+    if major_version != MAJOR_VERSION {
+        return Err(format!("unsupported module version: {major_version}"));
+    }
+
+    let data = &module[MODULE_HEADER_LENGTH..];
+    cpu.cycle = read_dword(data, 0) as u64;
+    cpu.accumulator = data[4];
+    cpu.index_register_x = data[5];
+    cpu.index_register_y = data[6];
+    cpu.stack_pointer = data[7];
+    cpu.program_counter = Word::from_le_bytes([data[8], data[9]]);
+    cpu.processor_status.set(data[10]);
+
+    // modules of older exporters end after the registers
+    if data.len() < REGISTERS_LENGTH + INTERRUPT_STATUS_LENGTH {
+        return Ok(());
+    }
+    let interrupt_status = &data[REGISTERS_LENGTH..];
+    let pending_interrupts = read_dword(interrupt_status, 24);
+    cpu.irq_line =
+        (read_dword(interrupt_status, 16) > 0).then(|| read_dword(interrupt_status, 0) as u64);
+    cpu.nmi_line = read_dword(interrupt_status, 20) > 0;
+    cpu.nmi_edge =
+        (pending_interrupts & IK_NMI > 0).then(|| read_dword(interrupt_status, 4) as u64);
+
+    return Ok(());
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,154 @@
+#[cfg(test)]
+mod write_cpu_module {
+    use std::cell::RefCell;
+
+    use crate::cpu::{tests::MemoryMock, vice_snapshot::write_cpu_module, CPU};
+
+    #[test]
+    fn should_write_header_and_registers() {
+        let memory = &RefCell::new(MemoryMock::default());
+        let mut cpu = CPU::new_nmos(memory);
+        cpu.cycle = 0x01020304;
+        cpu.accumulator = 0xAA;
+        cpu.index_register_x = 0xBB;
+        cpu.index_register_y = 0xCC;
+        cpu.stack_pointer = 0xFD;
+        cpu.program_counter = 0xC000;
+        cpu.processor_status.set(0b10000011);
+
+        let module = write_cpu_module(&cpu).unwrap();
+
+        assert_eq!(&module[..7], b"MAINCPU");
+        assert_eq!(&module[7..16], &[0; 9]);
+        assert_eq!(&module[16..22], &[1, 1, 65, 0, 0, 0]);
+        assert_eq!(
+            &module[22..37],
+            &[0x04, 0x03, 0x02, 0x01, 0xAA, 0xBB, 0xCC, 0xFD, 0x00, 0xC0, 0b10000011, 0, 0, 0, 0]
+        );
+        assert_eq!(&module[37..], &[0; 28]);
+    }
+
+    #[test]
+    fn should_write_interrupt_status() {
+        let memory = &RefCell::new(MemoryMock::default());
+        let mut cpu = CPU::new_nmos(memory);
+        cpu.schedule_irq(0x0100);
+        cpu.set_nmi_line(true);
+        cpu.schedule_nmi(0x0200);
+
+        let module = write_cpu_module(&cpu).unwrap();
+
+        assert_eq!(module.len(), 65);
+        assert_eq!(
+            &module[37..],
+            &[
+                0x00, 0x01, 0, 0, // irq_clk
+                0x00, 0x02, 0, 0, // nmi_clk
+                0, 0, 0, 0, // num_last_stolen_cycles
+                0, 0, 0, 0, // last_stolen_cycles_clk
+                1, 0, 0, 0, // nirq
+                1, 0, 0, 0, // nnmi
+                0b11, 0, 0, 0, // global_pending_int: IRQ and NMI
+            ]
+        );
+    }
+
+    #[test]
+    fn should_reject_cycle_past_32_bit_clock() {
+        let memory = &RefCell::new(MemoryMock::default());
+        let mut cpu = CPU::new_nmos(memory);
+        cpu.cycle = 0x1_0000_0000;
+
+        assert_eq!(
+            write_cpu_module(&cpu),
+            Err(String::from(
+                "cycle 4294967296 does not fit in the 32-bit snapshot clock"
+            ))
+        );
+    }
+}
+
+#[cfg(test)]
+mod read_cpu_module {
+    use std::cell::RefCell;
+
+    use crate::cpu::{
+        tests::MemoryMock,
+        vice_snapshot::{read_cpu_module, write_cpu_module},
+        CPU,
+    };
+
+    #[test]
+    fn should_restore_registers_written_by_exporter() {
+        let memory = &RefCell::new(MemoryMock::default());
+        let mut source = CPU::new_nmos(memory);
+        source.accumulator = 0x12;
+        source.stack_pointer = 0xF0;
+        source.program_counter = 0x0801;
+        source.processor_status.set(0b00000001);
+        source.cycle = 1234;
+        let module = write_cpu_module(&source).unwrap();
+        let mut target = CPU::new_nmos(memory);
+
+        assert_eq!(read_cpu_module(&mut target, &module), Ok(()));
+
+        assert_eq!(target.accumulator, 0x12);
+        assert_eq!(target.stack_pointer, 0xF0);
+        assert_eq!(target.program_counter, 0x0801);
+        assert_eq!(target.processor_status, 0b00000001);
+        assert_eq!(target.cycle, 1234);
+    }
+
+    #[test]
+    fn should_restore_interrupt_status() {
+        let memory = &RefCell::new(MemoryMock::default());
+        let mut source = CPU::new_nmos(memory);
+        source.schedule_irq(0x0100);
+        source.set_nmi_line(true);
+        source.schedule_nmi(0x0200);
+        let module = write_cpu_module(&source).unwrap();
+        let mut target = CPU::new_nmos(memory);
+
+        assert_eq!(read_cpu_module(&mut target, &module), Ok(()));
+
+        assert_eq!(target.irq_line, Some(0x0100));
+        assert!(target.nmi_line);
+        assert_eq!(target.nmi_edge, Some(0x0200));
+    }
+
+    #[test]
+    fn should_accept_module_without_interrupt_status() {
+        let memory = &RefCell::new(MemoryMock::default());
+        let mut source = CPU::new_nmos(memory);
+        source.accumulator = 0x12;
+        let module = write_cpu_module(&source).unwrap();
+        let mut target = CPU::new_nmos(memory);
+
+        assert_eq!(read_cpu_module(&mut target, &module[..37]), Ok(()));
+
+        assert_eq!(target.accumulator, 0x12);
+        assert_eq!(target.irq_line, None);
+    }
+
+    #[test]
+    fn should_reject_other_modules_and_versions() {
+        let memory = &RefCell::new(MemoryMock::default());
+        let mut cpu = CPU::new_nmos(memory);
+        let mut module = write_cpu_module(&cpu).unwrap();
+        module[16] = 2;
+
+        assert_eq!(
+            read_cpu_module(&mut cpu, &module),
+            Err(String::from("unsupported module version: 2"))
+        );
+        module[..7].copy_from_slice(b"CIA1\0\0\0");
+        assert_eq!(
+            read_cpu_module(&mut cpu, &module),
+            Err(String::from("unexpected module: CIA1"))
+        );
+        assert_eq!(
+            read_cpu_module(&mut cpu, &module[..10]),
+            Err(String::from("module too short"))
+        );
+    }
+}
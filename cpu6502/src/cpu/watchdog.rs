@@ -0,0 +1,61 @@
+use crate::consts::Word;
+
+pub enum WatchdogAction {
+    Stop,
+    // receives the program counter and the cycles spent since the watchdog was armed; returning
+    // true stops the run, false rearms the watchdog for another period
+    Callback(Box<dyn FnMut(Word, u64) -> bool + Send>),
+}
+
+// armed at the start of every run call; expires when the run spends `max_cycles` without
+// reaching any other stop condition
+pub struct Watchdog {
+    max_cycles: u64,
+    action: WatchdogAction,
+    expirations: usize,
+}
+
+impl Watchdog {
+    pub fn new(max_cycles: u64) -> Self {
+        return Watchdog {
+            max_cycles,
+            action: WatchdogAction::Stop,
+            expirations: 0,
+        };
+    }
+
+    pub fn with_callback(
+        max_cycles: u64,
+        callback: Box<dyn FnMut(Word, u64) -> bool + Send>,
+    ) -> Self {
+        return Watchdog {
+            max_cycles,
+            action: WatchdogAction::Callback(callback),
+            expirations: 0,
+        };
+    }
+
+    pub fn get_max_cycles(&self) -> u64 {
+        return self.max_cycles;
+    }
+
+    pub fn get_expirations(&self) -> usize {
+        return self.expirations;
+    }
+
+    pub fn is_expired(&self, armed_at: u64, cycle: u64) -> bool {
+        return cycle.saturating_sub(armed_at) >= self.max_cycles;
+    }
+
+    // returns whether the run should stop
+    pub fn expire(&mut self, program_counter: Word, elapsed: u64) -> bool {
+        self.expirations += 1;
+        return match &mut self.action {
+            WatchdogAction::Stop => true,
+            WatchdogAction::Callback(callback) => callback(program_counter, elapsed),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,84 @@
+#[cfg(test)]
+mod expire {
+    use crate::cpu::watchdog::Watchdog;
+
+    #[test]
+    fn should_expire_once_max_cycles_have_passed_since_arming() {
+        let watchdog = Watchdog::new(100);
+
+        assert!(!watchdog.is_expired(50, 149));
+        assert!(watchdog.is_expired(50, 150));
+    }
+
+    #[test]
+    fn should_pass_stop_decision_to_callback() {
+        let mut watchdog = Watchdog::with_callback(100, Box::new(|_, _| false));
+
+        assert!(!watchdog.expire(0x0600, 100));
+        assert_eq!(watchdog.get_expirations(), 1);
+    }
+}
+
+#[cfg(test)]
+mod cpu_integration {
+    use std::cell::RefCell;
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use crate::cpu::{emulation_event::EmulationEvent, tests::MemoryMock, watchdog::Watchdog, CPU};
+
+    const RUNAWAY_LOOP: [u8; 3] = [0x4C, 0x00, 0x00]; // JMP $0000
+
+    #[test]
+    fn should_stop_runaway_program_with_distinct_event() {
+        let memory = &RefCell::new(MemoryMock::new(&RUNAWAY_LOOP));
+        let mut cpu = CPU::new_nmos(memory);
+        cpu.program_counter = 0x0000;
+        cpu.set_watchdog(Watchdog::new(30));
+
+        let event = cpu.execute_until_break();
+
+        assert_eq!(
+            event,
+            EmulationEvent::WatchdogExpired {
+                address: 0x0000,
+                cycles: 30
+            }
+        );
+    }
+
+    #[test]
+    fn should_rearm_while_callback_lets_program_continue() {
+        let memory = &RefCell::new(MemoryMock::new(&RUNAWAY_LOOP));
+        let mut cpu = CPU::new_nmos(memory);
+        cpu.cycle = 0;
+        cpu.program_counter = 0x0000;
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        cpu.set_watchdog(Watchdog::with_callback(
+            30,
+            Box::new(move |_, _| {
+                return counter.fetch_add(1, Ordering::Relaxed) + 1 == 3;
+            }),
+        ));
+
+        let event = cpu.execute_until(0xFFFF);
+
+        assert!(matches!(event, EmulationEvent::WatchdogExpired { .. }));
+        assert_eq!(calls.load(Ordering::Relaxed), 3);
+        assert_eq!(cpu.get_cycle(), 90);
+    }
+
+    #[test]
+    fn should_not_fire_when_other_stop_condition_is_reached_first() {
+        let memory = &RefCell::new(MemoryMock::new(&[0xE8, 0xE8, 0x00])); // INX; INX; BRK
+        let mut cpu = CPU::new_nmos(memory);
+        cpu.program_counter = 0x0000;
+        cpu.set_watchdog(Watchdog::new(30));
+
+        assert_eq!(cpu.execute_until_break(), EmulationEvent::Trap(0x0002));
+        assert_eq!(cpu.get_watchdog().unwrap().get_expirations(), 0);
+    }
+}
@@ -0,0 +1,1266 @@
+use std::cell::RefCell;
+use std::ops::{Index, IndexMut};
+
+use crate::consts::{Byte, Word};
+use crate::cpu::emulation_event::EmulationEvent;
+
+mod decode;
+
+use decode::{Mode, Operation};
+
+// 65C816 core. After reset it runs in emulation mode, where it behaves like a 65C02 with an
+// 8-bit accumulator and index registers, the stack in page one and a page-wrapping direct
+// page; XCE switches it to native mode with 16-bit registers and a 24-bit address space
+
+pub type LongAddress = u32;
+
+const ADDRESS_MASK: LongAddress = 0x00FF_FFFF;
+
+// the 24-bit counterpart of `Bus`: reads take `&mut self` so devices behind it can react to
+// being read
+pub trait LongBus {
+    fn read(&mut self, addr: LongAddress) -> Byte;
+
+    fn write(&mut self, addr: LongAddress, value: Byte);
+
+    // debugger access, no cycles pass and devices must not react
+    fn peek(&mut self, addr: LongAddress) -> Byte {
+        return self.read(addr);
+    }
+
+    fn poke(&mut self, addr: LongAddress, value: Byte) {
+        self.write(addr, value);
+    }
+}
+
+// plain storage without side effects, addressable cell by cell; every long memory is a bus
+pub trait LongMemory:
+    IndexMut<LongAddress, Output = Byte> + Index<LongAddress, Output = Byte>
+{
+}
+
+impl<M: LongMemory + ?Sized> LongBus for M {
+    fn read(&mut self, addr: LongAddress) -> Byte {
+        return self[addr];
+    }
+
+    fn write(&mut self, addr: LongAddress, value: Byte) {
+        self[addr] = value;
+    }
+}
+
+impl<B: LongBus + ?Sized> LongBus for &RefCell<B> {
+    fn read(&mut self, addr: LongAddress) -> Byte {
+        return self.borrow_mut().read(addr);
+    }
+
+    fn write(&mut self, addr: LongAddress, value: Byte) {
+        self.borrow_mut().write(addr, value);
+    }
+
+    fn peek(&mut self, addr: LongAddress) -> Byte {
+        return self.borrow_mut().peek(addr);
+    }
+
+    fn poke(&mut self, addr: LongAddress, value: Byte) {
+        self.borrow_mut().poke(addr, value);
+    }
+}
+
+// covering all sixteen megabytes is rarely needed, addresses past the end mirror the start
+pub struct LongVecMemory {
+    pub data: Vec<Byte>,
+}
+
+impl LongVecMemory {
+    pub fn new(size: usize) -> Self {
+        return LongVecMemory {
+            data: vec![0; size],
+        };
+    }
+
+    pub fn insert(&mut self, addr: LongAddress, payload: &[Byte]) {
+        for (offset, value) in payload.iter().enumerate() {
+            self[addr.wrapping_add(offset as LongAddress)] = *value;
+        }
+    }
+}
+
+impl LongMemory for LongVecMemory {}
+
+impl Index<LongAddress> for LongVecMemory {
+    type Output = Byte;
+
+    fn index(&self, idx: LongAddress) -> &Self::Output {
+        let len = self.data.len();
+        return &self.data[idx as usize % len];
+    }
+}
+
+impl IndexMut<LongAddress> for LongVecMemory {
+    fn index_mut(&mut self, idx: LongAddress) -> &mut Self::Output {
+        let len = self.data.len();
+        return &mut self.data[idx as usize % len];
+    }
+}
+
+pub const CARRY_FLAG: Byte = 0b00000001;
+pub const ZERO_FLAG: Byte = 0b00000010;
+pub const INTERRUPT_DISABLE_FLAG: Byte = 0b00000100;
+pub const DECIMAL_MODE_FLAG: Byte = 0b00001000;
+// X selects 8-bit index registers; in emulation mode the bit is the break flag of a pushed P
+pub const INDEX_WIDTH_FLAG: Byte = 0b00010000;
+pub const ACCUMULATOR_WIDTH_FLAG: Byte = 0b00100000;
+pub const OVERFLOW_FLAG: Byte = 0b01000000;
+pub const NEGATIVE_FLAG: Byte = 0b10000000;
+
+const EMULATION_COP_VECTOR: Word = 0xFFF4;
+const EMULATION_NMI_VECTOR: Word = 0xFFFA;
+const RESET_VECTOR: Word = 0xFFFC;
+const EMULATION_IRQ_VECTOR: Word = 0xFFFE;
+const NATIVE_COP_VECTOR: Word = 0xFFE4;
+const NATIVE_BRK_VECTOR: Word = 0xFFE6;
+const NATIVE_NMI_VECTOR: Word = 0xFFEA;
+const NATIVE_IRQ_VECTOR: Word = 0xFFEE;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum Access {
+    Read,
+    Write,
+    Modify,
+}
+
+// effective address of an operand; direct page and stack operands wrap within bank zero
+// while data bank operands run on into the next bank
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct Target {
+    address: LongAddress,
+    bank_zero: bool,
+}
+
+impl Target {
+    fn next(&self) -> LongAddress {
+        if self.bank_zero {
+            return self.address.wrapping_add(1) & 0xFFFF;
+        }
+
+        return self.address.wrapping_add(1) & ADDRESS_MASK;
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum Interrupt {
+    Brk,
+    Cop,
+    Irq,
+    Nmi,
+}
+
+pub struct CPU65816<M: LongBus> {
+    cycle: u64,
+    accumulator: Word,
+    index_register_x: Word,
+    index_register_y: Word,
+    stack_pointer: Word,
+    direct_page: Word,
+    data_bank: Byte,
+    program_bank: Byte,
+    program_counter: Word,
+    // offset in the program bank of the opcode of the last instruction
+    instruction_address: Word,
+    processor_status: Byte,
+    emulation: bool,
+    memory: M,
+    waiting: bool,
+    stopped: bool,
+    irq_line: bool,
+    nmi_line: bool,
+    nmi_pending: bool,
+}
+
+impl<M: LongBus> CPU65816<M> {
+    pub fn new(memory: M) -> Self {
+        let mut cpu = CPU65816 {
+            cycle: 0,
+            accumulator: 0,
+            index_register_x: 0,
+            index_register_y: 0,
+            stack_pointer: 0x01FF,
+            direct_page: 0,
+            data_bank: 0,
+            program_bank: 0,
+            program_counter: 0,
+            instruction_address: 0,
+            processor_status: 0,
+            emulation: true,
+            memory,
+            waiting: false,
+            stopped: false,
+            irq_line: false,
+            nmi_line: false,
+            nmi_pending: false,
+        };
+        cpu.reset();
+
+        return cpu;
+    }
+
+    // reset always returns to emulation mode with the direct page and both banks at zero; the
+    // clock keeps running
+    pub fn reset(&mut self) {
+        self.emulation = true;
+        self.direct_page = 0;
+        self.data_bank = 0;
+        self.program_bank = 0;
+        self.stack_pointer = 0x0100 | (self.stack_pointer & 0x00FF);
+        self.processor_status = (self.processor_status
+            | INTERRUPT_DISABLE_FLAG
+            | ACCUMULATOR_WIDTH_FLAG
+            | INDEX_WIDTH_FLAG)
+            & !DECIMAL_MODE_FLAG;
+        self.index_register_x &= 0x00FF;
+        self.index_register_y &= 0x00FF;
+        self.waiting = false;
+        self.stopped = false;
+        self.nmi_pending = false;
+        for _ in 0..5 {
+            self.idle();
+        }
+        self.program_counter = self.read_vector(RESET_VECTOR);
+    }
+
+    pub fn get_cycle(&self) -> u64 {
+        return self.cycle;
+    }
+
+    pub fn get_accumulator(&self) -> Word {
+        return self.accumulator;
+    }
+
+    pub fn get_index_register_x(&self) -> Word {
+        return self.index_register_x;
+    }
+
+    pub fn get_index_register_y(&self) -> Word {
+        return self.index_register_y;
+    }
+
+    pub fn get_stack_pointer(&self) -> Word {
+        return self.stack_pointer;
+    }
+
+    pub fn get_direct_page(&self) -> Word {
+        return self.direct_page;
+    }
+
+    pub fn get_data_bank(&self) -> Byte {
+        return self.data_bank;
+    }
+
+    pub fn get_program_bank(&self) -> Byte {
+        return self.program_bank;
+    }
+
+    pub fn get_program_counter(&self) -> Word {
+        return self.program_counter;
+    }
+
+    // accesses made through this reference bypass the CPU, no cycles pass
+    pub fn get_memory_mut(&mut self) -> &mut M {
+        return &mut self.memory;
+    }
+
+    // program bank and program counter as one 24-bit address
+    pub fn get_program_address(&self) -> LongAddress {
+        return self.program_address();
+    }
+
+    pub fn set_program_address(&mut self, address: LongAddress) {
+        self.program_bank = (address >> 16) as Byte;
+        self.program_counter = address as Word;
+    }
+
+    pub fn get_processor_status(&self) -> Byte {
+        return self.processor_status;
+    }
+
+    pub fn is_emulation_mode(&self) -> bool {
+        return self.emulation;
+    }
+
+    pub fn is_waiting(&self) -> bool {
+        return self.waiting;
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        return self.stopped;
+    }
+
+    pub fn set_irq_line(&mut self, asserted: bool) {
+        self.irq_line = asserted;
+    }
+
+    pub fn get_irq_line(&self) -> bool {
+        return self.irq_line;
+    }
+
+    pub fn set_nmi_line(&mut self, asserted: bool) {
+        if asserted && !self.nmi_line {
+            self.nmi_pending = true;
+        }
+        self.nmi_line = asserted;
+    }
+
+    pub fn get_nmi_line(&self) -> bool {
+        return self.nmi_line;
+    }
+
+    // reports BRK, WAI and STP, the instructions after which a monitor usually takes over
+    pub fn execute_next_instruction(&mut self) -> Option<EmulationEvent> {
+        // a stopped CPU does nothing until reset, it only keeps reporting the stop
+        if self.stopped {
+            return Some(EmulationEvent::Stop(self.instruction_address));
+        }
+        if self.waiting {
+            if !self.irq_line && !self.nmi_pending {
+                self.idle();
+                return None;
+            }
+            self.waiting = false;
+        }
+        if self.nmi_pending {
+            self.nmi_pending = false;
+            self.service_interrupt(Interrupt::Nmi);
+            return None;
+        }
+        if self.irq_line && !self.is_flag_set(INTERRUPT_DISABLE_FLAG) {
+            self.service_interrupt(Interrupt::Irq);
+            return None;
+        }
+
+        self.instruction_address = self.program_counter;
+        let opcode = self.fetch();
+        let (operation, mode) = decode::decode(opcode);
+        self.execute(operation, mode);
+
+        return match operation {
+            Operation::Brk => Some(EmulationEvent::Trap(self.instruction_address)),
+            Operation::Wai => Some(EmulationEvent::Wait(self.instruction_address)),
+            Operation::Stp => Some(EmulationEvent::Stop(self.instruction_address)),
+            _ => None,
+        };
+    }
+
+    fn is_flag_set(&self, flag: Byte) -> bool {
+        return self.processor_status & flag > 0;
+    }
+
+    fn change_flag(&mut self, flag: Byte, set: bool) {
+        if set {
+            self.processor_status |= flag;
+        } else {
+            self.processor_status &= !flag;
+        }
+    }
+
+    // emulation mode pins M and X, and 8-bit index registers lose their high bytes
+    fn set_processor_status(&mut self, value: Byte) {
+        self.processor_status = value;
+        if self.emulation {
+            self.processor_status |= ACCUMULATOR_WIDTH_FLAG | INDEX_WIDTH_FLAG;
+        }
+        if self.is_flag_set(INDEX_WIDTH_FLAG) {
+            self.index_register_x &= 0x00FF;
+            self.index_register_y &= 0x00FF;
+        }
+    }
+
+    fn is_accumulator_wide(&self) -> bool {
+        return !self.emulation && !self.is_flag_set(ACCUMULATOR_WIDTH_FLAG);
+    }
+
+    fn is_index_wide(&self) -> bool {
+        return !self.emulation && !self.is_flag_set(INDEX_WIDTH_FLAG);
+    }
+
+    fn get_accumulator_value(&self, wide: bool) -> Word {
+        if wide {
+            return self.accumulator;
+        }
+
+        return self.accumulator & 0x00FF;
+    }
+
+    // in 8-bit mode the high byte (B) keeps its value
+    fn set_accumulator_value(&mut self, value: Word, wide: bool) {
+        if wide {
+            self.accumulator = value;
+        } else {
+            self.accumulator = (self.accumulator & 0xFF00) | (value & 0x00FF);
+        }
+    }
+
+    fn index_value(&self, value: Word) -> Word {
+        if self.is_index_wide() {
+            return value;
+        }
+
+        return value & 0x00FF;
+    }
+
+    fn set_nz(&mut self, value: Word, wide: bool) {
+        let (value, sign) = if wide {
+            (value, 0x8000)
+        } else {
+            (value & 0x00FF, 0x0080)
+        };
+        self.change_flag(ZERO_FLAG, value == 0);
+        self.change_flag(NEGATIVE_FLAG, value & sign > 0);
+    }
+
+    fn program_address(&self) -> LongAddress {
+        return (self.program_bank as LongAddress) << 16 | self.program_counter as LongAddress;
+    }
+
+    fn data_address(&self, offset: Word) -> LongAddress {
+        return (self.data_bank as LongAddress) << 16 | offset as LongAddress;
+    }
+
+    fn idle(&mut self) {
+        self.cycle += 1;
+    }
+
+    fn read(&mut self, address: LongAddress) -> Byte {
+        self.cycle += 1;
+        return self.memory.read(address & ADDRESS_MASK);
+    }
+
+    fn write(&mut self, address: LongAddress, value: Byte) {
+        self.cycle += 1;
+        self.memory.write(address & ADDRESS_MASK, value);
+    }
+
+    fn read_target(&mut self, target: Target, wide: bool) -> Word {
+        let lo = self.read(target.address);
+        if !wide {
+            return lo as Word;
+        }
+        let hi = self.read(target.next());
+
+        return Word::from_le_bytes([lo, hi]);
+    }
+
+    fn write_target(&mut self, target: Target, value: Word, wide: bool) {
+        let [lo, hi] = value.to_le_bytes();
+        self.write(target.address, lo);
+        if wide {
+            self.write(target.next(), hi);
+        }
+    }
+
+    fn read_vector(&mut self, vector: Word) -> Word {
+        let lo = self.read(vector as LongAddress);
+        let hi = self.read(vector.wrapping_add(1) as LongAddress);
+
+        return Word::from_le_bytes([lo, hi]);
+    }
+
+    // the program counter wraps within its bank
+    fn fetch(&mut self) -> Byte {
+        let value = self.read(self.program_address());
+        self.program_counter = self.program_counter.wrapping_add(1);
+
+        return value;
+    }
+
+    fn fetch_word(&mut self) -> Word {
+        let lo = self.fetch();
+        let hi = self.fetch();
+
+        return Word::from_le_bytes([lo, hi]);
+    }
+
+    fn fetch_long(&mut self) -> LongAddress {
+        let offset = self.fetch_word();
+        let bank = self.fetch();
+
+        return (bank as LongAddress) << 16 | offset as LongAddress;
+    }
+
+    // emulation mode keeps the stack in page one
+    fn push(&mut self, value: Byte) {
+        self.write(self.stack_pointer as LongAddress, value);
+        self.stack_pointer = self.stack_pointer.wrapping_sub(1);
+        if self.emulation {
+            self.stack_pointer = 0x0100 | (self.stack_pointer & 0x00FF);
+        }
+    }
+
+    fn push_word(&mut self, value: Word) {
+        let [lo, hi] = value.to_le_bytes();
+        self.push(hi);
+        self.push(lo);
+    }
+
+    fn pull(&mut self) -> Byte {
+        self.stack_pointer = self.stack_pointer.wrapping_add(1);
+        if self.emulation {
+            self.stack_pointer = 0x0100 | (self.stack_pointer & 0x00FF);
+        }
+
+        return self.read(self.stack_pointer as LongAddress);
+    }
+
+    fn pull_word(&mut self) -> Word {
+        let lo = self.pull();
+        let hi = self.pull();
+
+        return Word::from_le_bytes([lo, hi]);
+    }
+
+    // a direct page that is not page aligned costs a cycle; in emulation mode a page aligned
+    // direct page wraps within its page like zero page on the 6502
+    fn direct_address(&mut self, offset: Word) -> Target {
+        let address = if self.emulation && self.direct_page & 0x00FF == 0 {
+            self.direct_page | (offset & 0x00FF)
+        } else {
+            self.direct_page.wrapping_add(offset)
+        };
+
+        return Target {
+            address: address as LongAddress,
+            bank_zero: true,
+        };
+    }
+
+    fn fetch_direct_operand(&mut self) -> Word {
+        let operand = self.fetch() as Word;
+        if self.direct_page & 0x00FF != 0 {
+            self.idle();
+        }
+
+        return operand;
+    }
+
+    fn read_direct_pointer(&mut self, offset: Word) -> Word {
+        let lo_target = self.direct_address(offset);
+        let hi_target = self.direct_address(offset.wrapping_add(1));
+        let lo = self.read(lo_target.address);
+        let hi = self.read(hi_target.address);
+
+        return Word::from_le_bytes([lo, hi]);
+    }
+
+    fn read_direct_long_pointer(&mut self, offset: Word) -> LongAddress {
+        let pointer = self.read_direct_pointer(offset);
+        let bank_target = self.direct_address(offset.wrapping_add(2));
+        let bank = self.read(bank_target.address);
+
+        return (bank as LongAddress) << 16 | pointer as LongAddress;
+    }
+
+    // indexing across a page, or with 16-bit index registers, costs a cycle on reads; writes
+    // and read-modify-write always spend it
+    fn indexed(&mut self, base: LongAddress, index: Word, access: Access) -> Target {
+        let address = base.wrapping_add(index as LongAddress) & ADDRESS_MASK;
+        if access != Access::Read || self.is_index_wide() || (base ^ address) & 0xFF00 != 0 {
+            self.idle();
+        }
+
+        return Target {
+            address,
+            bank_zero: false,
+        };
+    }
+
+    fn linear(address: LongAddress) -> Target {
+        return Target {
+            address: address & ADDRESS_MASK,
+            bank_zero: false,
+        };
+    }
+
+    fn resolve(&mut self, mode: Mode, access: Access) -> Target {
+        let x = self.index_value(self.index_register_x);
+        let y = self.index_value(self.index_register_y);
+
+        return match mode {
+            Mode::Direct => {
+                let offset = self.fetch_direct_operand();
+                self.direct_address(offset)
+            }
+            Mode::DirectX | Mode::DirectY => {
+                let offset = self.fetch_direct_operand();
+                self.idle();
+                let index = if mode == Mode::DirectX { x } else { y };
+                self.direct_address(offset.wrapping_add(index))
+            }
+            Mode::DirectIndirect => {
+                let offset = self.fetch_direct_operand();
+                let pointer = self.read_direct_pointer(offset);
+                Self::linear(self.data_address(pointer))
+            }
+            Mode::DirectIndirectLong => {
+                let offset = self.fetch_direct_operand();
+                Self::linear(self.read_direct_long_pointer(offset))
+            }
+            Mode::DirectXIndirect => {
+                let offset = self.fetch_direct_operand();
+                self.idle();
+                let pointer = self.read_direct_pointer(offset.wrapping_add(x));
+                Self::linear(self.data_address(pointer))
+            }
+            Mode::DirectIndirectY => {
+                let offset = self.fetch_direct_operand();
+                let pointer = self.read_direct_pointer(offset);
+                self.indexed(self.data_address(pointer), y, access)
+            }
+            Mode::DirectIndirectLongY => {
+                let offset = self.fetch_direct_operand();
+                let base = self.read_direct_long_pointer(offset);
+                Self::linear(base.wrapping_add(y as LongAddress))
+            }
+            Mode::Absolute => {
+                let offset = self.fetch_word();
+                Self::linear(self.data_address(offset))
+            }
+            Mode::AbsoluteX | Mode::AbsoluteY => {
+                let offset = self.fetch_word();
+                let index = if mode == Mode::AbsoluteX { x } else { y };
+                self.indexed(self.data_address(offset), index, access)
+            }
+            Mode::AbsoluteLong => {
+                let address = self.fetch_long();
+                Self::linear(address)
+            }
+            Mode::AbsoluteLongX => {
+                let address = self.fetch_long();
+                Self::linear(address.wrapping_add(x as LongAddress))
+            }
+            Mode::StackRelative => {
+                let offset = self.fetch() as Word;
+                self.idle();
+                Target {
+                    address: self.stack_pointer.wrapping_add(offset) as LongAddress,
+                    bank_zero: true,
+                }
+            }
+            Mode::StackRelativeIndirectY => {
+                let offset = self.fetch() as Word;
+                self.idle();
+                let pointer_target = Target {
+                    address: self.stack_pointer.wrapping_add(offset) as LongAddress,
+                    bank_zero: true,
+                };
+                let pointer = self.read_target(pointer_target, true);
+                self.idle();
+                Self::linear(self.data_address(pointer).wrapping_add(y as LongAddress))
+            }
+            _ => panic!("{mode:?} does not address memory operands"),
+        };
+    }
+
+    fn read_operand(&mut self, mode: Mode, wide: bool) -> Word {
+        if mode == Mode::Immediate {
+            if wide {
+                return self.fetch_word();
+            }
+            return self.fetch() as Word;
+        }
+
+        let target = self.resolve(mode, Access::Read);
+        return self.read_target(target, wide);
+    }
+
+    fn write_operand(&mut self, mode: Mode, value: Word, wide: bool) {
+        let target = self.resolve(mode, Access::Write);
+        self.write_target(target, value, wide);
+    }
+
+    fn modify_operand(&mut self, mode: Mode, modify: fn(&mut CPU65816<M>, Word, bool) -> Word) {
+        let wide = self.is_accumulator_wide();
+        if mode == Mode::Accumulator {
+            self.idle();
+            let value = modify(self, self.get_accumulator_value(wide), wide);
+            self.set_accumulator_value(value, wide);
+            return;
+        }
+
+        let target = self.resolve(mode, Access::Modify);
+        let value = self.read_target(target, wide);
+        self.idle();
+        let value = modify(self, value, wide);
+        // the high byte is written first
+        if wide {
+            let [lo, hi] = value.to_le_bytes();
+            self.write(target.next(), hi);
+            self.write(target.address, lo);
+        } else {
+            self.write(target.address, value as Byte);
+        }
+    }
+
+    fn sign_bit(wide: bool) -> Word {
+        return if wide { 0x8000 } else { 0x0080 };
+    }
+
+    fn width_mask(wide: bool) -> u32 {
+        return if wide { 0xFFFF } else { 0x00FF };
+    }
+
+    // binary and BCD addition; subtraction adds the ones' complement and corrects digits
+    // downwards. Unlike the 6502 every flag is valid in decimal mode
+    fn add_with_carry(&mut self, value: Word, subtract: bool) {
+        let wide = self.is_accumulator_wide();
+        let mask = Self::width_mask(wide);
+        let accumulator = self.get_accumulator_value(wide) as i32;
+        let operand = if subtract {
+            (!value as u32 & mask) as i32
+        } else {
+            (value as u32 & mask) as i32
+        };
+        let carry = self.is_flag_set(CARRY_FLAG) as i32;
+        let sign = Self::sign_bit(wide) as i32;
+        let digits = if wide { 4 } else { 2 };
+
+        let mut result;
+        let overflow;
+        if !self.is_flag_set(DECIMAL_MODE_FLAG) {
+            result = accumulator + operand + carry;
+            overflow = !(accumulator ^ operand) & (accumulator ^ result) & sign != 0;
+        } else {
+            // digit by digit, each one sees the carry out of the corrected digit below it
+            result = 0;
+            let mut digit_carry = carry;
+            let mut digit_overflow = false;
+            for digit in 0..digits {
+                let shift = digit * 4;
+                let digit_mask = 0xF << shift;
+                let lower_digits = (1 << shift) - 1;
+                result = (accumulator & digit_mask)
+                    + (operand & digit_mask)
+                    + (digit_carry << shift)
+                    + (result & lower_digits);
+                if digit == digits - 1 {
+                    digit_overflow = !(accumulator ^ operand) & (accumulator ^ result) & sign != 0;
+                }
+                if subtract {
+                    if result < 0x10 << shift {
+                        result -= 0x6 << shift;
+                    }
+                } else if result >= 0xA << shift {
+                    result += 0x6 << shift;
+                }
+                digit_carry = (result >= 0x10 << shift) as i32;
+            }
+            overflow = digit_overflow;
+        }
+
+        self.change_flag(CARRY_FLAG, result > mask as i32);
+        self.change_flag(OVERFLOW_FLAG, overflow);
+        let result = (result as u32 & mask) as Word;
+        self.set_accumulator_value(result, wide);
+        self.set_nz(result, wide);
+    }
+
+    fn compare(&mut self, register: Word, value: Word, wide: bool) {
+        let mask = Self::width_mask(wide) as Word;
+        let register = register & mask;
+        let value = value & mask;
+        self.change_flag(CARRY_FLAG, register >= value);
+        self.set_nz(register.wrapping_sub(value), wide);
+    }
+
+    fn branch(&mut self, condition: bool) {
+        let offset = self.fetch() as i8;
+        if !condition {
+            return;
+        }
+
+        self.idle();
+        let target = self.program_counter.wrapping_add(offset as Word);
+        if self.emulation && (target ^ self.program_counter) & 0xFF00 != 0 {
+            self.idle();
+        }
+        self.program_counter = target;
+    }
+
+    // emulation mode pushes B in place of X and ignores the program bank, like the 6502
+    fn service_interrupt(&mut self, interrupt: Interrupt) {
+        if interrupt == Interrupt::Brk || interrupt == Interrupt::Cop {
+            self.fetch();
+        } else {
+            self.idle();
+            self.idle();
+        }
+
+        if !self.emulation {
+            self.push(self.program_bank);
+        }
+        self.push_word(self.program_counter);
+        let mut status = self.processor_status;
+        if self.emulation {
+            if interrupt == Interrupt::Brk {
+                status |= INDEX_WIDTH_FLAG;
+            } else {
+                status &= !INDEX_WIDTH_FLAG;
+            }
+        }
+        self.push(status);
+        self.change_flag(INTERRUPT_DISABLE_FLAG, true);
+        self.change_flag(DECIMAL_MODE_FLAG, false);
+        self.program_bank = 0;
+
+        let vector = match (interrupt, self.emulation) {
+            (Interrupt::Brk | Interrupt::Irq, true) => EMULATION_IRQ_VECTOR,
+            (Interrupt::Cop, true) => EMULATION_COP_VECTOR,
+            (Interrupt::Nmi, true) => EMULATION_NMI_VECTOR,
+            (Interrupt::Brk, false) => NATIVE_BRK_VECTOR,
+            (Interrupt::Cop, false) => NATIVE_COP_VECTOR,
+            (Interrupt::Irq, false) => NATIVE_IRQ_VECTOR,
+            (Interrupt::Nmi, false) => NATIVE_NMI_VECTOR,
+        };
+        self.program_counter = self.read_vector(vector);
+    }
+
+    // one byte per execution; the instruction rewinds onto itself until the count in C runs
+    // out, so interrupts can be taken between bytes
+    fn block_move(&mut self, step: Word) {
+        let destination_bank = self.fetch();
+        let source_bank = self.fetch();
+        self.data_bank = destination_bank;
+
+        let source = (source_bank as LongAddress) << 16 | self.index_register_x as LongAddress;
+        let destination =
+            (destination_bank as LongAddress) << 16 | self.index_register_y as LongAddress;
+        let value = self.read(source);
+        self.write(destination, value);
+        self.idle();
+        self.idle();
+
+        self.index_register_x = self.index_value(self.index_register_x.wrapping_add(step));
+        self.index_register_y = self.index_value(self.index_register_y.wrapping_add(step));
+        self.accumulator = self.accumulator.wrapping_sub(1);
+        if self.accumulator != 0xFFFF {
+            self.program_counter = self.program_counter.wrapping_sub(3);
+        }
+    }
+
+    fn transfer_to_accumulator(&mut self, value: Word) {
+        self.idle();
+        let wide = self.is_accumulator_wide();
+        self.set_accumulator_value(value, wide);
+        self.set_nz(value, wide);
+    }
+
+    fn transfer_to_index(&mut self, value: Word) -> Word {
+        self.idle();
+        let value = self.index_value(value);
+        self.set_nz(value, self.is_index_wide());
+
+        return value;
+    }
+
+    fn push_sized(&mut self, value: Word, wide: bool) {
+        self.idle();
+        if wide {
+            self.push_word(value);
+        } else {
+            self.push(value as Byte);
+        }
+    }
+
+    fn pull_sized(&mut self, wide: bool) -> Word {
+        self.idle();
+        self.idle();
+        let value = if wide {
+            self.pull_word()
+        } else {
+            self.pull() as Word
+        };
+        self.set_nz(value, wide);
+
+        return value;
+    }
+
+    fn execute(&mut self, operation: Operation, mode: Mode) {
+        let accumulator_wide = self.is_accumulator_wide();
+        let index_wide = self.is_index_wide();
+
+        match operation {
+            Operation::Lda => {
+                let value = self.read_operand(mode, accumulator_wide);
+                self.set_accumulator_value(value, accumulator_wide);
+                self.set_nz(value, accumulator_wide);
+            }
+            Operation::Ldx => {
+                let value = self.read_operand(mode, index_wide);
+                self.index_register_x = value;
+                self.set_nz(value, index_wide);
+            }
+            Operation::Ldy => {
+                let value = self.read_operand(mode, index_wide);
+                self.index_register_y = value;
+                self.set_nz(value, index_wide);
+            }
+            Operation::Sta => {
+                let value = self.get_accumulator_value(accumulator_wide);
+                self.write_operand(mode, value, accumulator_wide);
+            }
+            Operation::Stx => self.write_operand(mode, self.index_register_x, index_wide),
+            Operation::Sty => self.write_operand(mode, self.index_register_y, index_wide),
+            Operation::Stz => self.write_operand(mode, 0, accumulator_wide),
+            Operation::Adc | Operation::Sbc => {
+                let value = self.read_operand(mode, accumulator_wide);
+                self.add_with_carry(value, operation == Operation::Sbc);
+            }
+            Operation::And | Operation::Ora | Operation::Eor => {
+                let value = self.read_operand(mode, accumulator_wide);
+                let accumulator = self.get_accumulator_value(accumulator_wide);
+                let result = match operation {
+                    Operation::And => accumulator & value,
+                    Operation::Ora => accumulator | value,
+                    _ => accumulator ^ value,
+                };
+                self.set_accumulator_value(result, accumulator_wide);
+                self.set_nz(result, accumulator_wide);
+            }
+            Operation::Cmp => {
+                let value = self.read_operand(mode, accumulator_wide);
+                self.compare(self.accumulator, value, accumulator_wide);
+            }
+            Operation::Cpx => {
+                let value = self.read_operand(mode, index_wide);
+                self.compare(self.index_register_x, value, index_wide);
+            }
+            Operation::Cpy => {
+                let value = self.read_operand(mode, index_wide);
+                self.compare(self.index_register_y, value, index_wide);
+            }
+            // immediate BIT only affects Z
+            Operation::Bit => {
+                let value = self.read_operand(mode, accumulator_wide);
+                let accumulator = self.get_accumulator_value(accumulator_wide);
+                self.change_flag(ZERO_FLAG, accumulator & value == 0);
+                if mode != Mode::Immediate {
+                    let sign = Self::sign_bit(accumulator_wide);
+                    self.change_flag(NEGATIVE_FLAG, value & sign > 0);
+                    self.change_flag(OVERFLOW_FLAG, value & (sign >> 1) > 0);
+                }
+            }
+            Operation::Asl => self.modify_operand(mode, |cpu, value, wide| {
+                cpu.change_flag(CARRY_FLAG, value & Self::sign_bit(wide) > 0);
+                let result = value << 1;
+                cpu.set_nz(result, wide);
+                return result;
+            }),
+            Operation::Lsr => self.modify_operand(mode, |cpu, value, wide| {
+                cpu.change_flag(CARRY_FLAG, value & 0x0001 > 0);
+                let result = value >> 1;
+                cpu.set_nz(result, wide);
+                return result;
+            }),
+            Operation::Rol => self.modify_operand(mode, |cpu, value, wide| {
+                let carry = cpu.is_flag_set(CARRY_FLAG) as Word;
+                cpu.change_flag(CARRY_FLAG, value & Self::sign_bit(wide) > 0);
+                let result = value << 1 | carry;
+                cpu.set_nz(result, wide);
+                return result;
+            }),
+            Operation::Ror => self.modify_operand(mode, |cpu, value, wide| {
+                let carry = cpu.is_flag_set(CARRY_FLAG);
+                cpu.change_flag(CARRY_FLAG, value & 0x0001 > 0);
+                let mut result = value >> 1;
+                if carry {
+                    result |= Self::sign_bit(wide);
+                }
+                cpu.set_nz(result, wide);
+                return result;
+            }),
+            Operation::Inc => self.modify_operand(mode, |cpu, value, wide| {
+                let result = value.wrapping_add(1);
+                cpu.set_nz(result, wide);
+                return result;
+            }),
+            Operation::Dec => self.modify_operand(mode, |cpu, value, wide| {
+                let result = value.wrapping_sub(1);
+                cpu.set_nz(result, wide);
+                return result;
+            }),
+            Operation::Tsb => self.modify_operand(mode, |cpu, value, wide| {
+                let accumulator = cpu.get_accumulator_value(wide);
+                cpu.change_flag(ZERO_FLAG, accumulator & value == 0);
+                return value | accumulator;
+            }),
+            Operation::Trb => self.modify_operand(mode, |cpu, value, wide| {
+                let accumulator = cpu.get_accumulator_value(wide);
+                cpu.change_flag(ZERO_FLAG, accumulator & value == 0);
+                return value & !accumulator;
+            }),
+            Operation::Inx => {
+                self.idle();
+                self.index_register_x = self.index_value(self.index_register_x.wrapping_add(1));
+                self.set_nz(self.index_register_x, index_wide);
+            }
+            Operation::Iny => {
+                self.idle();
+                self.index_register_y = self.index_value(self.index_register_y.wrapping_add(1));
+                self.set_nz(self.index_register_y, index_wide);
+            }
+            Operation::Dex => {
+                self.idle();
+                self.index_register_x = self.index_value(self.index_register_x.wrapping_sub(1));
+                self.set_nz(self.index_register_x, index_wide);
+            }
+            Operation::Dey => {
+                self.idle();
+                self.index_register_y = self.index_value(self.index_register_y.wrapping_sub(1));
+                self.set_nz(self.index_register_y, index_wide);
+            }
+            Operation::Bcc => self.branch(!self.is_flag_set(CARRY_FLAG)),
+            Operation::Bcs => self.branch(self.is_flag_set(CARRY_FLAG)),
+            Operation::Beq => self.branch(self.is_flag_set(ZERO_FLAG)),
+            Operation::Bne => self.branch(!self.is_flag_set(ZERO_FLAG)),
+            Operation::Bmi => self.branch(self.is_flag_set(NEGATIVE_FLAG)),
+            Operation::Bpl => self.branch(!self.is_flag_set(NEGATIVE_FLAG)),
+            Operation::Bvc => self.branch(!self.is_flag_set(OVERFLOW_FLAG)),
+            Operation::Bvs => self.branch(self.is_flag_set(OVERFLOW_FLAG)),
+            Operation::Bra => self.branch(true),
+            Operation::Brl => {
+                let offset = self.fetch_word();
+                self.idle();
+                self.program_counter = self.program_counter.wrapping_add(offset);
+            }
+            Operation::Jmp => match mode {
+                Mode::Absolute => self.program_counter = self.fetch_word(),
+                Mode::AbsoluteIndirect => {
+                    let pointer = self.fetch_word();
+                    self.program_counter = self.read_target(
+                        Target {
+                            address: pointer as LongAddress,
+                            bank_zero: true,
+                        },
+                        true,
+                    );
+                }
+                _ => {
+                    let pointer = self.fetch_word();
+                    self.idle();
+                    let index = self.index_value(self.index_register_x);
+                    self.program_counter = self.read_program_bank_pointer(pointer, index);
+                }
+            },
+            Operation::Jml => {
+                let address = if mode == Mode::AbsoluteLong {
+                    self.fetch_long()
+                } else {
+                    let pointer = self.fetch_word() as LongAddress;
+                    let offset = self.read_target(
+                        Target {
+                            address: pointer,
+                            bank_zero: true,
+                        },
+                        true,
+                    );
+                    let bank = self.read(pointer.wrapping_add(2) & 0xFFFF);
+                    (bank as LongAddress) << 16 | offset as LongAddress
+                };
+                self.set_program_address(address);
+            }
+            Operation::Jsr => {
+                if mode == Mode::Absolute {
+                    let target = self.fetch_word();
+                    self.idle();
+                    self.push_word(self.program_counter.wrapping_sub(1));
+                    self.program_counter = target;
+                } else {
+                    let pointer_lo = self.fetch();
+                    self.push_word(self.program_counter);
+                    let pointer_hi = self.fetch();
+                    self.idle();
+                    let pointer = Word::from_le_bytes([pointer_lo, pointer_hi]);
+                    let index = self.index_value(self.index_register_x);
+                    self.program_counter = self.read_program_bank_pointer(pointer, index);
+                }
+            }
+            Operation::Jsl => {
+                let offset = self.fetch_word();
+                self.push(self.program_bank);
+                self.idle();
+                let bank = self.fetch();
+                self.push_word(self.program_counter.wrapping_sub(1));
+                self.program_bank = bank;
+                self.program_counter = offset;
+            }
+            Operation::Rts => {
+                self.idle();
+                self.idle();
+                self.program_counter = self.pull_word().wrapping_add(1);
+                self.idle();
+            }
+            Operation::Rtl => {
+                self.idle();
+                self.idle();
+                self.program_counter = self.pull_word().wrapping_add(1);
+                self.program_bank = self.pull();
+            }
+            Operation::Rti => {
+                self.idle();
+                self.idle();
+                let status = self.pull();
+                self.set_processor_status(status);
+                self.program_counter = self.pull_word();
+                if !self.emulation {
+                    self.program_bank = self.pull();
+                }
+            }
+            Operation::Brk => self.service_interrupt(Interrupt::Brk),
+            Operation::Cop => self.service_interrupt(Interrupt::Cop),
+            Operation::Pha => self.push_sized(self.accumulator, accumulator_wide),
+            Operation::Phx => self.push_sized(self.index_register_x, index_wide),
+            Operation::Phy => self.push_sized(self.index_register_y, index_wide),
+            Operation::Php => self.push_sized(self.processor_status as Word, false),
+            Operation::Phb => self.push_sized(self.data_bank as Word, false),
+            Operation::Phk => self.push_sized(self.program_bank as Word, false),
+            Operation::Phd => self.push_sized(self.direct_page, true),
+            Operation::Pla => {
+                let value = self.pull_sized(accumulator_wide);
+                self.set_accumulator_value(value, accumulator_wide);
+            }
+            Operation::Plx => self.index_register_x = self.pull_sized(index_wide),
+            Operation::Ply => self.index_register_y = self.pull_sized(index_wide),
+            Operation::Plb => self.data_bank = self.pull_sized(false) as Byte,
+            Operation::Pld => self.direct_page = self.pull_sized(true),
+            Operation::Plp => {
+                self.idle();
+                self.idle();
+                let status = self.pull();
+                self.set_processor_status(status);
+            }
+            Operation::Pea => {
+                let value = self.fetch_word();
+                self.push_word(value);
+            }
+            Operation::Pei => {
+                let offset = self.fetch_direct_operand();
+                let value = self.read_direct_pointer(offset);
+                self.push_word(value);
+            }
+            Operation::Per => {
+                let offset = self.fetch_word();
+                self.idle();
+                self.push_word(self.program_counter.wrapping_add(offset));
+            }
+            Operation::Mvn => self.block_move(1),
+            Operation::Mvp => self.block_move(0xFFFF),
+            Operation::Rep | Operation::Sep => {
+                let mask = self.fetch();
+                self.idle();
+                let status = if operation == Operation::Rep {
+                    self.processor_status & !mask
+                } else {
+                    self.processor_status | mask
+                };
+                self.set_processor_status(status);
+            }
+            Operation::Xce => {
+                self.idle();
+                let carry = self.is_flag_set(CARRY_FLAG);
+                self.change_flag(CARRY_FLAG, self.emulation);
+                self.emulation = carry;
+                if self.emulation {
+                    self.stack_pointer = 0x0100 | (self.stack_pointer & 0x00FF);
+                }
+                self.set_processor_status(self.processor_status);
+            }
+            Operation::Xba => {
+                self.idle();
+                self.idle();
+                self.accumulator = self.accumulator.rotate_left(8);
+                self.set_nz(self.accumulator, false);
+            }
+            Operation::Tax => self.index_register_x = self.transfer_to_index(self.accumulator),
+            Operation::Tay => self.index_register_y = self.transfer_to_index(self.accumulator),
+            Operation::Tsx => self.index_register_x = self.transfer_to_index(self.stack_pointer),
+            Operation::Txy => {
+                self.index_register_y = self.transfer_to_index(self.index_register_x);
+            }
+            Operation::Tyx => {
+                self.index_register_x = self.transfer_to_index(self.index_register_y);
+            }
+            Operation::Txa => self.transfer_to_accumulator(self.index_register_x),
+            Operation::Tya => self.transfer_to_accumulator(self.index_register_y),
+            Operation::Txs => {
+                self.idle();
+                self.stack_pointer = if self.emulation {
+                    0x0100 | (self.index_register_x & 0x00FF)
+                } else {
+                    self.index_register_x
+                };
+            }
+            Operation::Tcs => {
+                self.idle();
+                self.stack_pointer = if self.emulation {
+                    0x0100 | (self.accumulator & 0x00FF)
+                } else {
+                    self.accumulator
+                };
+            }
+            Operation::Tsc => {
+                self.idle();
+                self.accumulator = self.stack_pointer;
+                self.set_nz(self.accumulator, true);
+            }
+            Operation::Tcd => {
+                self.idle();
+                self.direct_page = self.accumulator;
+                self.set_nz(self.direct_page, true);
+            }
+            Operation::Tdc => {
+                self.idle();
+                self.accumulator = self.direct_page;
+                self.set_nz(self.accumulator, true);
+            }
+            Operation::Clc => self.set_flag_implied(CARRY_FLAG, false),
+            Operation::Sec => self.set_flag_implied(CARRY_FLAG, true),
+            Operation::Cli => self.set_flag_implied(INTERRUPT_DISABLE_FLAG, false),
+            Operation::Sei => self.set_flag_implied(INTERRUPT_DISABLE_FLAG, true),
+            Operation::Cld => self.set_flag_implied(DECIMAL_MODE_FLAG, false),
+            Operation::Sed => self.set_flag_implied(DECIMAL_MODE_FLAG, true),
+            Operation::Clv => self.set_flag_implied(OVERFLOW_FLAG, false),
+            Operation::Wai => {
+                self.idle();
+                self.idle();
+                self.waiting = true;
+            }
+            Operation::Stp => {
+                self.idle();
+                self.idle();
+                self.stopped = true;
+            }
+            // WDM is reserved for future expansion and skips its signature byte
+            Operation::Wdm => {
+                self.fetch();
+            }
+            Operation::Nop => self.idle(),
+        }
+    }
+
+    fn set_flag_implied(&mut self, flag: Byte, set: bool) {
+        self.idle();
+        self.change_flag(flag, set);
+    }
+
+    // (abs,X) pointers are read from the program bank
+    fn read_program_bank_pointer(&mut self, pointer: Word, index: Word) -> Word {
+        let address = pointer.wrapping_add(index);
+        let bank = (self.program_bank as LongAddress) << 16;
+        let lo = self.read(bank | address as LongAddress);
+        let hi = self.read(bank | address.wrapping_add(1) as LongAddress);
+
+        return Word::from_le_bytes([lo, hi]);
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,314 @@
+use crate::consts::Byte;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Mode {
+    Implied,
+    Accumulator,
+    Immediate,
+    Direct,
+    DirectX,
+    DirectY,
+    DirectIndirect,
+    DirectIndirectLong,
+    DirectXIndirect,
+    DirectIndirectY,
+    DirectIndirectLongY,
+    Absolute,
+    AbsoluteX,
+    AbsoluteY,
+    AbsoluteLong,
+    AbsoluteLongX,
+    StackRelative,
+    StackRelativeIndirectY,
+    AbsoluteIndirect,
+    AbsoluteIndirectLong,
+    AbsoluteXIndirect,
+    Relative,
+    RelativeLong,
+    BlockMove,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Operation {
+    Adc,
+    And,
+    Asl,
+    Bcc,
+    Bcs,
+    Beq,
+    Bit,
+    Bmi,
+    Bne,
+    Bpl,
+    Bra,
+    Brk,
+    Brl,
+    Bvc,
+    Bvs,
+    Clc,
+    Cld,
+    Cli,
+    Clv,
+    Cmp,
+    Cop,
+    Cpx,
+    Cpy,
+    Dec,
+    Dex,
+    Dey,
+    Eor,
+    Inc,
+    Inx,
+    Iny,
+    Jml,
+    Jmp,
+    Jsl,
+    Jsr,
+    Lda,
+    Ldx,
+    Ldy,
+    Lsr,
+    Mvn,
+    Mvp,
+    Nop,
+    Ora,
+    Pea,
+    Pei,
+    Per,
+    Pha,
+    Phb,
+    Phd,
+    Phk,
+    Php,
+    Phx,
+    Phy,
+    Pla,
+    Plb,
+    Pld,
+    Plp,
+    Plx,
+    Ply,
+    Rep,
+    Rol,
+    Ror,
+    Rti,
+    Rtl,
+    Rts,
+    Sbc,
+    Sec,
+    Sed,
+    Sei,
+    Sep,
+    Sta,
+    Stp,
+    Stx,
+    Sty,
+    Stz,
+    Tax,
+    Tay,
+    Tcd,
+    Tcs,
+    Tdc,
+    Trb,
+    Tsb,
+    Tsc,
+    Tsx,
+    Txa,
+    Txs,
+    Txy,
+    Tya,
+    Tyx,
+    Wai,
+    Wdm,
+    Xba,
+    Xce,
+}
+
+// the accumulator group (ORA, AND, EOR, ADC, STA, LDA, CMP, SBC) repeats every $20 opcodes
+// with the same fifteen addressing modes
+const ACCUMULATOR_GROUP: [Operation; 8] = [
+    Operation::Ora,
+    Operation::And,
+    Operation::Eor,
+    Operation::Adc,
+    Operation::Sta,
+    Operation::Lda,
+    Operation::Cmp,
+    Operation::Sbc,
+];
+
+fn accumulator_group_mode(column: Byte) -> Option<Mode> {
+    return match column {
+        0x01 => Some(Mode::DirectXIndirect),
+        0x03 => Some(Mode::StackRelative),
+        0x05 => Some(Mode::Direct),
+        0x07 => Some(Mode::DirectIndirectLong),
+        0x09 => Some(Mode::Immediate),
+        0x0D => Some(Mode::Absolute),
+        0x0F => Some(Mode::AbsoluteLong),
+        0x11 => Some(Mode::DirectIndirectY),
+        0x12 => Some(Mode::DirectIndirect),
+        0x13 => Some(Mode::StackRelativeIndirectY),
+        0x15 => Some(Mode::DirectX),
+        0x17 => Some(Mode::DirectIndirectLongY),
+        0x19 => Some(Mode::AbsoluteY),
+        0x1D => Some(Mode::AbsoluteX),
+        0x1F => Some(Mode::AbsoluteLongX),
+        _ => None,
+    };
+}
+
+// every one of the 256 opcodes is assigned on the 65C816
+pub fn decode(opcode: Byte) -> (Operation, Mode) {
+    use Mode::*;
+    use Operation::*;
+
+    let decoded = match opcode {
+        0x00 => (Brk, Implied),
+        0x02 => (Cop, Implied),
+        0x04 => (Tsb, Direct),
+        0x06 => (Asl, Direct),
+        0x08 => (Php, Implied),
+        0x0A => (Asl, Accumulator),
+        0x0B => (Phd, Implied),
+        0x0C => (Tsb, Absolute),
+        0x0E => (Asl, Absolute),
+        0x10 => (Bpl, Relative),
+        0x14 => (Trb, Direct),
+        0x16 => (Asl, DirectX),
+        0x18 => (Clc, Implied),
+        0x1A => (Inc, Accumulator),
+        0x1B => (Tcs, Implied),
+        0x1C => (Trb, Absolute),
+        0x1E => (Asl, AbsoluteX),
+        0x20 => (Jsr, Absolute),
+        0x22 => (Jsl, AbsoluteLong),
+        0x24 => (Bit, Direct),
+        0x26 => (Rol, Direct),
+        0x28 => (Plp, Implied),
+        0x2A => (Rol, Accumulator),
+        0x2B => (Pld, Implied),
+        0x2C => (Bit, Absolute),
+        0x2E => (Rol, Absolute),
+        0x30 => (Bmi, Relative),
+        0x34 => (Bit, DirectX),
+        0x36 => (Rol, DirectX),
+        0x38 => (Sec, Implied),
+        0x3A => (Dec, Accumulator),
+        0x3B => (Tsc, Implied),
+        0x3C => (Bit, AbsoluteX),
+        0x3E => (Rol, AbsoluteX),
+        0x40 => (Rti, Implied),
+        0x42 => (Wdm, Immediate),
+        0x44 => (Mvp, BlockMove),
+        0x46 => (Lsr, Direct),
+        0x48 => (Pha, Implied),
+        0x4A => (Lsr, Accumulator),
+        0x4B => (Phk, Implied),
+        0x4C => (Jmp, Absolute),
+        0x4E => (Lsr, Absolute),
+        0x50 => (Bvc, Relative),
+        0x54 => (Mvn, BlockMove),
+        0x56 => (Lsr, DirectX),
+        0x58 => (Cli, Implied),
+        0x5A => (Phy, Implied),
+        0x5B => (Tcd, Implied),
+        0x5C => (Jml, AbsoluteLong),
+        0x5E => (Lsr, AbsoluteX),
+        0x60 => (Rts, Implied),
+        0x62 => (Per, RelativeLong),
+        0x64 => (Stz, Direct),
+        0x66 => (Ror, Direct),
+        0x68 => (Pla, Implied),
+        0x6A => (Ror, Accumulator),
+        0x6B => (Rtl, Implied),
+        0x6C => (Jmp, AbsoluteIndirect),
+        0x6E => (Ror, Absolute),
+        0x70 => (Bvs, Relative),
+        0x74 => (Stz, DirectX),
+        0x76 => (Ror, DirectX),
+        0x78 => (Sei, Implied),
+        0x7A => (Ply, Implied),
+        0x7B => (Tdc, Implied),
+        0x7C => (Jmp, AbsoluteXIndirect),
+        0x7E => (Ror, AbsoluteX),
+        0x80 => (Bra, Relative),
+        0x82 => (Brl, RelativeLong),
+        0x84 => (Sty, Direct),
+        0x86 => (Stx, Direct),
+        0x88 => (Dey, Implied),
+        0x89 => (Bit, Immediate),
+        0x8A => (Txa, Implied),
+        0x8B => (Phb, Implied),
+        0x8C => (Sty, Absolute),
+        0x8E => (Stx, Absolute),
+        0x90 => (Bcc, Relative),
+        0x94 => (Sty, DirectX),
+        0x96 => (Stx, DirectY),
+        0x98 => (Tya, Implied),
+        0x9A => (Txs, Implied),
+        0x9B => (Txy, Implied),
+        0x9C => (Stz, Absolute),
+        0x9E => (Stz, AbsoluteX),
+        0xA0 => (Ldy, Immediate),
+        0xA2 => (Ldx, Immediate),
+        0xA4 => (Ldy, Direct),
+        0xA6 => (Ldx, Direct),
+        0xA8 => (Tay, Implied),
+        0xAA => (Tax, Implied),
+        0xAB => (Plb, Implied),
+        0xAC => (Ldy, Absolute),
+        0xAE => (Ldx, Absolute),
+        0xB0 => (Bcs, Relative),
+        0xB4 => (Ldy, DirectX),
+        0xB6 => (Ldx, DirectY),
+        0xB8 => (Clv, Implied),
+        0xBA => (Tsx, Implied),
+        0xBB => (Tyx, Implied),
+        0xBC => (Ldy, AbsoluteX),
+        0xBE => (Ldx, AbsoluteY),
+        0xC0 => (Cpy, Immediate),
+        0xC2 => (Rep, Immediate),
+        0xC4 => (Cpy, Direct),
+        0xC6 => (Dec, Direct),
+        0xC8 => (Iny, Implied),
+        0xCA => (Dex, Implied),
+        0xCB => (Wai, Implied),
+        0xCC => (Cpy, Absolute),
+        0xCE => (Dec, Absolute),
+        0xD0 => (Bne, Relative),
+        0xD4 => (Pei, Direct),
+        0xD6 => (Dec, DirectX),
+        0xD8 => (Cld, Implied),
+        0xDA => (Phx, Implied),
+        0xDB => (Stp, Implied),
+        0xDC => (Jml, AbsoluteIndirectLong),
+        0xDE => (Dec, AbsoluteX),
+        0xE0 => (Cpx, Immediate),
+        0xE2 => (Sep, Immediate),
+        0xE4 => (Cpx, Direct),
+        0xE6 => (Inc, Direct),
+        0xE8 => (Inx, Implied),
+        0xEA => (Nop, Implied),
+        0xEB => (Xba, Implied),
+        0xEC => (Cpx, Absolute),
+        0xEE => (Inc, Absolute),
+        0xF0 => (Beq, Relative),
+        0xF4 => (Pea, Absolute),
+        0xF6 => (Inc, DirectX),
+        0xF8 => (Sed, Implied),
+        0xFA => (Plx, Implied),
+        0xFB => (Xce, Implied),
+        0xFC => (Jsr, AbsoluteXIndirect),
+        0xFE => (Inc, AbsoluteX),
+        _ => {
+            let operation = ACCUMULATOR_GROUP[(opcode >> 5) as usize];
+            match accumulator_group_mode(opcode & 0x1F) {
+                Some(mode) => (operation, mode),
+                None => unreachable!("opcode ${opcode:02X} is not in the accumulator group"),
+            }
+        }
+    };
+
+    return decoded;
+}
@@ -0,0 +1,671 @@
+use std::cell::RefCell;
+
+use crate::cpu65816::LongVecMemory;
+
+// vectors: reset $8000, emulation IRQ/BRK $9000, native BRK $A000, native NMI $B000
+pub fn memory_with_program(program: &[u8]) -> RefCell<LongVecMemory> {
+    let mut memory = LongVecMemory::new(0x20000);
+    memory.insert(0xFFFC, &[0x00, 0x80]);
+    memory.insert(0xFFFE, &[0x00, 0x90]);
+    memory.insert(0xFFE6, &[0x00, 0xA0]);
+    memory.insert(0xFFEA, &[0x00, 0xB0]);
+    memory.insert(0x8000, program);
+
+    return RefCell::new(memory);
+}
+
+#[cfg(test)]
+mod reset {
+    use std::cell::RefCell;
+
+    use crate::cpu65816::{
+        tests::memory_with_program, LongVecMemory, ACCUMULATOR_WIDTH_FLAG, CPU65816,
+        INDEX_WIDTH_FLAG,
+    };
+
+    #[test]
+    fn should_start_in_emulation_mode_from_reset_vector() {
+        let mut memory = LongVecMemory::new(0x20000);
+        memory.insert(0xFFFC, &[0x00, 0x80]);
+        let memory = RefCell::new(memory);
+
+        let cpu = CPU65816::new(&memory);
+
+        assert!(cpu.is_emulation_mode());
+        assert_eq!(cpu.get_program_address(), 0x008000);
+        assert_eq!(cpu.get_stack_pointer() & 0xFF00, 0x0100);
+        assert_eq!(
+            cpu.get_processor_status() & (ACCUMULATOR_WIDTH_FLAG | INDEX_WIDTH_FLAG),
+            ACCUMULATOR_WIDTH_FLAG | INDEX_WIDTH_FLAG
+        );
+        assert_eq!(cpu.get_cycle(), 7);
+    }
+
+    #[test]
+    fn should_keep_cycle_counter_running_across_reset() {
+        let memory = memory_with_program(&[0xEA]);
+        let mut cpu = CPU65816::new(&memory);
+        cpu.execute_next_instruction();
+
+        cpu.reset();
+
+        assert_eq!(cpu.get_cycle(), 7 + 2 + 7);
+    }
+}
+
+#[cfg(test)]
+mod decode {
+    use crate::cpu65816::decode::decode;
+
+    #[test]
+    fn should_decode_every_opcode() {
+        for opcode in 0..=0xFF {
+            decode(opcode);
+        }
+    }
+}
+
+#[cfg(test)]
+mod register_widths {
+    use crate::cpu65816::{tests::memory_with_program, CPU65816};
+
+    #[test]
+    fn should_load_8_bit_immediate_in_emulation_mode() {
+        let memory = memory_with_program(&[0xA9, 0x34, 0x12]);
+        let mut cpu = CPU65816::new(&memory);
+        let start = cpu.get_cycle();
+
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.get_accumulator(), 0x0034);
+        assert_eq!(cpu.get_program_counter(), 0x8002);
+        assert_eq!(cpu.get_cycle() - start, 2);
+    }
+
+    #[test]
+    fn should_load_16_bit_immediate_after_clearing_m_in_native_mode() {
+        // CLC, XCE, REP #$20, LDA #$1234
+        let memory = memory_with_program(&[0x18, 0xFB, 0xC2, 0x20, 0xA9, 0x34, 0x12]);
+        let mut cpu = CPU65816::new(&memory);
+        for _ in 0..3 {
+            cpu.execute_next_instruction();
+        }
+        let start = cpu.get_cycle();
+
+        cpu.execute_next_instruction();
+
+        assert!(!cpu.is_emulation_mode());
+        assert_eq!(cpu.get_accumulator(), 0x1234);
+        assert_eq!(cpu.get_program_counter(), 0x8007);
+        assert_eq!(cpu.get_cycle() - start, 3);
+    }
+
+    #[test]
+    fn should_keep_high_byte_of_accumulator_with_8_bit_accumulator() {
+        // LDA #$CD, XBA, LDA #$EF
+        let memory = memory_with_program(&[0xA9, 0xCD, 0xEB, 0xA9, 0xEF]);
+        let mut cpu = CPU65816::new(&memory);
+
+        for _ in 0..3 {
+            cpu.execute_next_instruction();
+        }
+
+        assert_eq!(cpu.get_accumulator(), 0xCDEF);
+    }
+
+    #[test]
+    fn should_force_8_bit_registers_and_stack_page_when_returning_to_emulation() {
+        // CLC, XCE, REP #$30, LDX #$1234, TXS, SEC, XCE
+        let memory =
+            memory_with_program(&[0x18, 0xFB, 0xC2, 0x30, 0xA2, 0x34, 0x12, 0x9A, 0x38, 0xFB]);
+        let mut cpu = CPU65816::new(&memory);
+
+        for _ in 0..5 {
+            cpu.execute_next_instruction();
+        }
+        assert_eq!(cpu.get_stack_pointer(), 0x1234);
+        cpu.execute_next_instruction();
+        cpu.execute_next_instruction();
+
+        assert!(cpu.is_emulation_mode());
+        assert_eq!(cpu.get_index_register_x(), 0x0034);
+        assert_eq!(cpu.get_stack_pointer(), 0x0134);
+    }
+}
+
+#[cfg(test)]
+mod long_bus {
+    use crate::cpu65816::{LongAddress, LongBus, LongVecMemory, CPU65816};
+
+    const COUNTER: LongAddress = 0x01_2000;
+
+    // a register in bank one counting how often it was read, next to plain memory
+    struct CountingBus {
+        memory: LongVecMemory,
+        reads: u8,
+    }
+
+    impl LongBus for CountingBus {
+        fn read(&mut self, addr: LongAddress) -> u8 {
+            if addr == COUNTER {
+                self.reads += 1;
+                return self.reads;
+            }
+
+            return self.memory.read(addr);
+        }
+
+        fn write(&mut self, addr: LongAddress, value: u8) {
+            self.memory.write(addr, value);
+        }
+    }
+
+    #[test]
+    fn should_let_devices_react_to_reads_through_owned_bus() {
+        let mut memory = LongVecMemory::new(0x20000);
+        memory.insert(0xFFFC, &[0x00, 0x80]);
+        // LDA $012000; LDA $012000; STA $0010
+        memory.insert(
+            0x8000,
+            &[0xAF, 0x00, 0x20, 0x01, 0xAF, 0x00, 0x20, 0x01, 0x85, 0x10],
+        );
+        let mut cpu = CPU65816::new(CountingBus { memory, reads: 0 });
+
+        cpu.execute_next_instruction();
+        cpu.execute_next_instruction();
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.get_accumulator(), 0x0002);
+        assert_eq!(cpu.get_memory_mut().memory[0x0010], 0x02);
+    }
+}
+
+#[cfg(test)]
+mod long_addressing {
+    use crate::cpu65816::{tests::memory_with_program, CPU65816};
+
+    #[test]
+    fn should_load_from_another_bank() {
+        let memory = memory_with_program(&[0xAF, 0x00, 0x20, 0x01]);
+        memory.borrow_mut()[0x012000] = 0x42;
+        let mut cpu = CPU65816::new(&memory);
+        let start = cpu.get_cycle();
+
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.get_accumulator(), 0x0042);
+        assert_eq!(cpu.get_cycle() - start, 5);
+    }
+
+    #[test]
+    fn should_call_and_return_across_banks() {
+        // JSL $01:9000 and RTL there
+        let memory = memory_with_program(&[0x22, 0x00, 0x90, 0x01, 0xEA]);
+        memory.borrow_mut()[0x019000] = 0x6B;
+        let mut cpu = CPU65816::new(&memory);
+        let start = cpu.get_cycle();
+
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.get_program_address(), 0x019000);
+        assert_eq!(cpu.get_cycle() - start, 8);
+
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.get_program_address(), 0x008004);
+        assert_eq!(cpu.get_cycle() - start, 14);
+    }
+
+    #[test]
+    fn should_move_block_until_count_runs_out() {
+        // CLC, XCE, REP #$30, LDA #$0002, LDX #$1000, LDY #$2000, MVN $01,$00
+        let memory = memory_with_program(&[
+            0x18, 0xFB, 0xC2, 0x30, 0xA9, 0x02, 0x00, 0xA2, 0x00, 0x10, 0xA0, 0x00, 0x20, 0x54,
+            0x01, 0x00,
+        ]);
+        memory.borrow_mut().insert(0x1000, &[0x11, 0x22, 0x33]);
+        let mut cpu = CPU65816::new(&memory);
+        for _ in 0..6 {
+            cpu.execute_next_instruction();
+        }
+        let start = cpu.get_cycle();
+
+        for _ in 0..3 {
+            cpu.execute_next_instruction();
+        }
+
+        assert_eq!(cpu.get_cycle() - start, 21);
+        assert_eq!(cpu.get_accumulator(), 0xFFFF);
+        assert_eq!(cpu.get_data_bank(), 0x01);
+        assert_eq!(cpu.get_program_counter(), 0x8010);
+        assert_eq!(memory.borrow()[0x012000], 0x11);
+        assert_eq!(memory.borrow()[0x012001], 0x22);
+        assert_eq!(memory.borrow()[0x012002], 0x33);
+    }
+}
+
+#[cfg(test)]
+mod decimal_mode {
+    use crate::cpu65816::{tests::memory_with_program, CARRY_FLAG, CPU65816};
+
+    #[test]
+    fn should_add_four_digits_with_16_bit_accumulator() {
+        // CLC, XCE, REP #$20, SED, LDA #$1999, CLC, ADC #$0001
+        let memory = memory_with_program(&[
+            0x18, 0xFB, 0xC2, 0x20, 0xF8, 0xA9, 0x99, 0x19, 0x18, 0x69, 0x01, 0x00,
+        ]);
+        let mut cpu = CPU65816::new(&memory);
+
+        for _ in 0..7 {
+            cpu.execute_next_instruction();
+        }
+
+        assert_eq!(cpu.get_accumulator(), 0x2000);
+        assert_eq!(cpu.get_processor_status() & CARRY_FLAG, 0);
+    }
+
+    #[test]
+    fn should_borrow_across_digits_with_8_bit_accumulator() {
+        // SED, SEC, LDA #$00, SBC #$01
+        let memory = memory_with_program(&[0xF8, 0x38, 0xA9, 0x00, 0xE9, 0x01]);
+        let mut cpu = CPU65816::new(&memory);
+
+        for _ in 0..4 {
+            cpu.execute_next_instruction();
+        }
+
+        assert_eq!(cpu.get_accumulator() & 0x00FF, 0x99);
+        assert_eq!(cpu.get_processor_status() & CARRY_FLAG, 0);
+    }
+}
+
+#[cfg(test)]
+mod interrupts {
+    use crate::cpu65816::{tests::memory_with_program, CPU65816, INDEX_WIDTH_FLAG};
+
+    #[test]
+    fn should_push_break_flag_and_use_shared_vector_for_brk_in_emulation_mode() {
+        let memory = memory_with_program(&[0x00, 0x00]);
+        let mut cpu = CPU65816::new(&memory);
+        let stack_pointer = cpu.get_stack_pointer();
+        let start = cpu.get_cycle();
+
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.get_program_counter(), 0x9000);
+        assert_eq!(cpu.get_cycle() - start, 7);
+        assert_eq!(cpu.get_stack_pointer(), stack_pointer - 3);
+        let pushed_status = memory.borrow()[stack_pointer as u32 - 2];
+        assert_eq!(pushed_status & INDEX_WIDTH_FLAG, INDEX_WIDTH_FLAG);
+    }
+
+    #[test]
+    fn should_push_program_bank_for_brk_in_native_mode() {
+        // CLC, XCE, BRK
+        let memory = memory_with_program(&[0x18, 0xFB, 0x00, 0x00]);
+        let mut cpu = CPU65816::new(&memory);
+        cpu.execute_next_instruction();
+        cpu.execute_next_instruction();
+        let stack_pointer = cpu.get_stack_pointer();
+        let start = cpu.get_cycle();
+
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.get_program_counter(), 0xA000);
+        assert_eq!(cpu.get_cycle() - start, 8);
+        assert_eq!(cpu.get_stack_pointer(), stack_pointer - 4);
+    }
+
+    #[test]
+    fn should_service_nmi_through_native_vector_and_return_with_rti() {
+        // CLC, XCE, NOP; RTI at the handler
+        let memory = memory_with_program(&[0x18, 0xFB, 0xEA]);
+        memory.borrow_mut()[0xB000] = 0x40;
+        let mut cpu = CPU65816::new(&memory);
+        cpu.execute_next_instruction();
+        cpu.execute_next_instruction();
+
+        cpu.set_nmi_line(true);
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.get_program_counter(), 0xB000);
+
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.get_program_address(), 0x008002);
+        assert!(!cpu.is_emulation_mode());
+    }
+}
+
+#[cfg(test)]
+mod emulation_events {
+    use crate::{
+        cpu::emulation_event::EmulationEvent,
+        cpu65816::{tests::memory_with_program, CPU65816},
+    };
+
+    #[test]
+    fn should_return_no_event_for_regular_instruction() {
+        let memory = memory_with_program(&[0xEA]);
+        let mut cpu = CPU65816::new(&memory);
+
+        assert_eq!(cpu.execute_next_instruction(), None);
+    }
+
+    #[test]
+    fn should_report_brk_at_its_address() {
+        // NOP, BRK
+        let memory = memory_with_program(&[0xEA, 0x00, 0x00]);
+        let mut cpu = CPU65816::new(&memory);
+        cpu.execute_next_instruction();
+
+        assert_eq!(
+            cpu.execute_next_instruction(),
+            Some(EmulationEvent::Trap(0x8001))
+        );
+        assert_eq!(cpu.get_program_counter(), 0x9000);
+    }
+
+    #[test]
+    fn should_report_wai_once_and_idle_until_interrupt() {
+        let memory = memory_with_program(&[0xCB, 0xEA]);
+        let mut cpu = CPU65816::new(&memory);
+
+        assert_eq!(
+            cpu.execute_next_instruction(),
+            Some(EmulationEvent::Wait(0x8000))
+        );
+        let start = cpu.get_cycle();
+        assert_eq!(cpu.execute_next_instruction(), None);
+        assert!(cpu.is_waiting());
+        assert_eq!(cpu.get_cycle() - start, 1);
+    }
+
+    #[test]
+    fn should_keep_reporting_stp_without_running_the_clock() {
+        let memory = memory_with_program(&[0xDB, 0xEA]);
+        let mut cpu = CPU65816::new(&memory);
+
+        assert_eq!(
+            cpu.execute_next_instruction(),
+            Some(EmulationEvent::Stop(0x8000))
+        );
+        let start = cpu.get_cycle();
+        assert_eq!(
+            cpu.execute_next_instruction(),
+            Some(EmulationEvent::Stop(0x8000))
+        );
+        assert!(cpu.is_stopped());
+        assert_eq!(cpu.get_cycle(), start);
+    }
+}
+
+#[cfg(test)]
+mod cycle_counts {
+    use crate::cpu65816::{tests::memory_with_program, CPU65816};
+
+    // cycles taken by the last of the given number of instructions
+    fn cycles_of_last(program: &[u8], instructions: usize) -> u64 {
+        let memory = memory_with_program(program);
+        let mut cpu = CPU65816::new(&memory);
+        for _ in 1..instructions {
+            cpu.execute_next_instruction();
+        }
+        let start = cpu.get_cycle();
+        cpu.execute_next_instruction();
+
+        return cpu.get_cycle() - start;
+    }
+
+    #[test]
+    fn should_take_8_bit_cycle_counts_in_emulation_mode() {
+        assert_eq!(cycles_of_last(&[0xA5, 0x10], 1), 3); // LDA $10
+        assert_eq!(cycles_of_last(&[0xAD, 0x00, 0x20], 1), 4); // LDA $2000
+        assert_eq!(cycles_of_last(&[0x8D, 0x00, 0x20], 1), 4); // STA $2000
+        assert_eq!(cycles_of_last(&[0xEE, 0x00, 0x20], 1), 6); // INC $2000
+        assert_eq!(cycles_of_last(&[0x48], 1), 3); // PHA
+        assert_eq!(cycles_of_last(&[0x68], 1), 4); // PLA
+        assert_eq!(cycles_of_last(&[0x20, 0x00, 0x90], 1), 6); // JSR $9000
+    }
+
+    #[test]
+    fn should_add_a_cycle_for_every_extra_byte_with_16_bit_accumulator() {
+        // CLC, XCE, REP #$20, then the instruction under test
+        let native = |instruction: &[u8]| {
+            let mut program = vec![0x18, 0xFB, 0xC2, 0x20];
+            program.extend_from_slice(instruction);
+
+            return cycles_of_last(&program, 4);
+        };
+
+        assert_eq!(native(&[0xA5, 0x10]), 4); // LDA $10
+        assert_eq!(native(&[0xAD, 0x00, 0x20]), 5); // LDA $2000
+        assert_eq!(native(&[0xEE, 0x00, 0x20]), 8); // INC $2000
+        assert_eq!(native(&[0x48]), 4); // PHA
+        assert_eq!(native(&[0x68]), 5); // PLA
+    }
+
+    #[test]
+    fn should_add_a_cycle_for_direct_page_off_page_boundary() {
+        // LDA #$00, XBA, LDA #$01, TCD, LDA $10
+        let program = [0xA9, 0x00, 0xEB, 0xA9, 0x01, 0x5B, 0xA5, 0x10];
+
+        assert_eq!(cycles_of_last(&program, 5), 4);
+    }
+
+    #[test]
+    fn should_add_a_cycle_for_taken_branch_and_another_for_page_cross_in_emulation_mode() {
+        assert_eq!(cycles_of_last(&[0x80, 0x00], 1), 3); // BRA +0
+        assert_eq!(cycles_of_last(&[0x80, 0x80], 1), 4); // BRA -128
+        assert_eq!(cycles_of_last(&[0xF0, 0x00], 1), 2); // BEQ +0, Z is clear after reset
+    }
+}
+
+#[cfg(test)]
+mod addressing_modes {
+    use crate::cpu65816::{tests::memory_with_program, CPU65816};
+
+    #[test]
+    fn should_read_through_relocated_direct_page() {
+        // CLC, XCE, REP #$20, LDA #$0300, TCD, SEP #$20, LDA $10
+        let memory = memory_with_program(&[
+            0x18, 0xFB, 0xC2, 0x20, 0xA9, 0x00, 0x03, 0x5B, 0xE2, 0x20, 0xA5, 0x10,
+        ]);
+        memory.borrow_mut()[0x000310] = 0x42;
+        let mut cpu = CPU65816::new(&memory);
+
+        for _ in 0..7 {
+            cpu.execute_next_instruction();
+        }
+
+        assert_eq!(cpu.get_direct_page(), 0x0300);
+        assert_eq!(cpu.get_accumulator() & 0x00FF, 0x42);
+    }
+
+    #[test]
+    fn should_read_through_direct_page_indirect_long_pointer() {
+        // LDA [$10]
+        let memory = memory_with_program(&[0xA7, 0x10]);
+        memory.borrow_mut().insert(0x0010, &[0x00, 0x20, 0x01]);
+        memory.borrow_mut()[0x012000] = 0x42;
+        let mut cpu = CPU65816::new(&memory);
+        let start = cpu.get_cycle();
+
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.get_accumulator(), 0x0042);
+        assert_eq!(cpu.get_cycle() - start, 6);
+    }
+
+    #[test]
+    fn should_read_through_direct_page_indirect_pointer_indexed_by_y() {
+        // LDY #$05, LDA ($10),Y
+        let memory = memory_with_program(&[0xA0, 0x05, 0xB1, 0x10]);
+        memory.borrow_mut().insert(0x0010, &[0x00, 0x20]);
+        memory.borrow_mut()[0x002005] = 0x42;
+        let mut cpu = CPU65816::new(&memory);
+
+        cpu.execute_next_instruction();
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.get_accumulator(), 0x0042);
+    }
+
+    #[test]
+    fn should_read_through_pre_indexed_direct_page_pointer() {
+        // LDX #$04, LDA ($10,X)
+        let memory = memory_with_program(&[0xA2, 0x04, 0xA1, 0x10]);
+        memory.borrow_mut().insert(0x0014, &[0x00, 0x20]);
+        memory.borrow_mut()[0x002000] = 0x42;
+        let mut cpu = CPU65816::new(&memory);
+
+        cpu.execute_next_instruction();
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.get_accumulator(), 0x0042);
+    }
+
+    #[test]
+    fn should_read_relative_to_stack_pointer() {
+        // LDA #$42, PHA, LDA #$00, LDA $01,S
+        let memory = memory_with_program(&[0xA9, 0x42, 0x48, 0xA9, 0x00, 0xA3, 0x01]);
+        let mut cpu = CPU65816::new(&memory);
+
+        for _ in 0..4 {
+            cpu.execute_next_instruction();
+        }
+
+        assert_eq!(cpu.get_accumulator(), 0x0042);
+    }
+
+    #[test]
+    fn should_read_long_address_indexed_by_x() {
+        // LDX #$02, LDA $012000,X
+        let memory = memory_with_program(&[0xA2, 0x02, 0xBF, 0x00, 0x20, 0x01]);
+        memory.borrow_mut()[0x012002] = 0x42;
+        let mut cpu = CPU65816::new(&memory);
+
+        cpu.execute_next_instruction();
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.get_accumulator(), 0x0042);
+    }
+
+    #[test]
+    fn should_read_absolute_address_in_data_bank() {
+        // LDA #$01, PHA, PLB, LDA $2000
+        let memory = memory_with_program(&[0xA9, 0x01, 0x48, 0xAB, 0xAD, 0x00, 0x20]);
+        memory.borrow_mut()[0x012000] = 0x42;
+        let mut cpu = CPU65816::new(&memory);
+
+        for _ in 0..4 {
+            cpu.execute_next_instruction();
+        }
+
+        assert_eq!(cpu.get_data_bank(), 0x01);
+        assert_eq!(cpu.get_accumulator(), 0x0042);
+    }
+}
+
+#[cfg(test)]
+mod width_switching {
+    use crate::cpu65816::{
+        tests::memory_with_program, ACCUMULATOR_WIDTH_FLAG, CPU65816, INDEX_WIDTH_FLAG,
+    };
+
+    #[test]
+    fn should_return_to_8_bit_accumulator_after_setting_m() {
+        // CLC, XCE, REP #$20, LDA #$1234, SEP #$20, LDA #$FF
+        let memory = memory_with_program(&[
+            0x18, 0xFB, 0xC2, 0x20, 0xA9, 0x34, 0x12, 0xE2, 0x20, 0xA9, 0xFF,
+        ]);
+        let mut cpu = CPU65816::new(&memory);
+
+        for _ in 0..6 {
+            cpu.execute_next_instruction();
+        }
+
+        assert_eq!(cpu.get_accumulator(), 0x12FF);
+        assert_eq!(cpu.get_program_counter(), 0x800B);
+    }
+
+    #[test]
+    fn should_clear_index_high_bytes_after_setting_x() {
+        // CLC, XCE, REP #$10, LDX #$1234, LDY #$5678, SEP #$10
+        let memory = memory_with_program(&[
+            0x18, 0xFB, 0xC2, 0x10, 0xA2, 0x34, 0x12, 0xA0, 0x78, 0x56, 0xE2, 0x10,
+        ]);
+        let mut cpu = CPU65816::new(&memory);
+
+        for _ in 0..5 {
+            cpu.execute_next_instruction();
+        }
+        assert_eq!(cpu.get_index_register_x(), 0x1234);
+        assert_eq!(cpu.get_index_register_y(), 0x5678);
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.get_index_register_x(), 0x0034);
+        assert_eq!(cpu.get_index_register_y(), 0x0078);
+    }
+
+    #[test]
+    fn should_switch_index_and_accumulator_widths_independently() {
+        // CLC, XCE, REP #$10, LDX #$1234, LDA #$56
+        let memory = memory_with_program(&[0x18, 0xFB, 0xC2, 0x10, 0xA2, 0x34, 0x12, 0xA9, 0x56]);
+        let mut cpu = CPU65816::new(&memory);
+
+        for _ in 0..5 {
+            cpu.execute_next_instruction();
+        }
+
+        assert_eq!(
+            cpu.get_processor_status() & (ACCUMULATOR_WIDTH_FLAG | INDEX_WIDTH_FLAG),
+            ACCUMULATOR_WIDTH_FLAG
+        );
+        assert_eq!(cpu.get_index_register_x(), 0x1234);
+        assert_eq!(cpu.get_accumulator(), 0x0056);
+        assert_eq!(cpu.get_program_counter(), 0x8009);
+    }
+
+    #[test]
+    fn should_keep_8_bit_registers_when_clearing_m_and_x_in_emulation_mode() {
+        // REP #$30, LDA #$34, LDX #$56
+        let memory = memory_with_program(&[0xC2, 0x30, 0xA9, 0x34, 0xA2, 0x56]);
+        let mut cpu = CPU65816::new(&memory);
+
+        for _ in 0..3 {
+            cpu.execute_next_instruction();
+        }
+
+        assert_eq!(
+            cpu.get_processor_status() & (ACCUMULATOR_WIDTH_FLAG | INDEX_WIDTH_FLAG),
+            ACCUMULATOR_WIDTH_FLAG | INDEX_WIDTH_FLAG
+        );
+        assert_eq!(cpu.get_accumulator(), 0x0034);
+        assert_eq!(cpu.get_index_register_x(), 0x0056);
+        assert_eq!(cpu.get_program_counter(), 0x8006);
+    }
+
+    #[test]
+    fn should_push_and_pull_two_bytes_with_16_bit_accumulator() {
+        // CLC, XCE, REP #$20, LDA #$1234, PHA, LDA #$0000, PLA
+        let memory = memory_with_program(&[
+            0x18, 0xFB, 0xC2, 0x20, 0xA9, 0x34, 0x12, 0x48, 0xA9, 0x00, 0x00, 0x68,
+        ]);
+        let mut cpu = CPU65816::new(&memory);
+        for _ in 0..4 {
+            cpu.execute_next_instruction();
+        }
+        let stack_pointer = cpu.get_stack_pointer();
+
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.get_stack_pointer(), stack_pointer - 2);
+
+        cpu.execute_next_instruction();
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.get_accumulator(), 0x1234);
+        assert_eq!(cpu.get_stack_pointer(), stack_pointer);
+    }
+}
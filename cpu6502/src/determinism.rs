@@ -0,0 +1,91 @@
+use crate::{cpu::CPU, memory::Bus};
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Divergence {
+    pub step: usize,
+    pub expected_digest: u64,
+    pub actual_digest: u64,
+}
+
+// prepares a CPU built for a run, e.g. points it at the program
+pub type CpuSetup<M> = Box<dyn Fn(&mut CPU<M>)>;
+
+// runs the same program twice from freshly built memory and compares state digests after
+// every instruction; any difference means something outside of the emulated state (host
+// time, unseeded randomness, nondeterministic backends) leaked into the run. `new_cpu` picks
+// the chip, e.g. `CPU::new_cmos`.
+pub struct DeterminismAudit<M: Bus + 'static, F: Fn() -> M> {
+    make_memory: F,
+    new_cpu: fn(M) -> CPU<M>,
+    setup: CpuSetup<M>,
+    steps: usize,
+}
+
+impl<M: Bus + 'static, F: Fn() -> M> DeterminismAudit<M, F> {
+    pub fn new(make_memory: F, new_cpu: fn(M) -> CPU<M>, setup: CpuSetup<M>, steps: usize) -> Self {
+        return DeterminismAudit {
+            make_memory,
+            new_cpu,
+            setup,
+            steps,
+        };
+    }
+
+    pub fn record(&self) -> Vec<u64> {
+        let mut cpu = (self.new_cpu)((self.make_memory)());
+        (self.setup)(&mut cpu);
+
+        let mut digests = Vec::with_capacity(self.steps);
+        for _ in 0..self.steps {
+            let event = cpu.execute_next_instruction();
+            digests.push(cpu.state_digest());
+            if event.is_some() {
+                break;
+            }
+        }
+
+        return digests;
+    }
+
+    // returns the final digest so that it can be stored and verified against later
+    pub fn run(&self) -> Result<u64, Divergence> {
+        let expected = self.record();
+        self.verify_trace(&expected)?;
+
+        return Ok(expected.last().copied().unwrap_or_default());
+    }
+
+    pub fn verify_trace(&self, expected: &[u64]) -> Result<(), Divergence> {
+        let actual = self.record();
+        for step in 0..expected.len().max(actual.len()) {
+            let expected_digest = expected.get(step).copied().unwrap_or_default();
+            let actual_digest = actual.get(step).copied().unwrap_or_default();
+            if expected_digest != actual_digest {
+                return Err(Divergence {
+                    step,
+                    expected_digest,
+                    actual_digest,
+                });
+            }
+        }
+
+        return Ok(());
+    }
+
+    pub fn verify_digest(&self, recorded_digest: u64) -> Result<(), Divergence> {
+        let actual = self.record();
+        let actual_digest = actual.last().copied().unwrap_or_default();
+        if actual_digest == recorded_digest {
+            return Ok(());
+        }
+
+        return Err(Divergence {
+            step: actual.len().saturating_sub(1),
+            expected_digest: recorded_digest,
+            actual_digest,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,71 @@
+#[cfg(test)]
+mod run {
+    use std::cell::Cell;
+
+    use crate::{
+        cpu::CPU,
+        determinism::{CpuSetup, DeterminismAudit},
+        memory::VecMemory,
+    };
+
+    fn program() -> VecMemory {
+        let mut memory = VecMemory::new();
+        memory.insert(0x0200, &[0xE6, 0x10, 0xE6, 0x10, 0x00]); // INC $10; INC $10; BRK
+
+        return memory;
+    }
+
+    fn setup() -> CpuSetup<VecMemory> {
+        return Box::new(|cpu: &mut CPU<VecMemory>| cpu.set_program_counter(0x0200));
+    }
+
+    #[test]
+    fn should_pass_for_deterministic_program() {
+        let audit = DeterminismAudit::new(program, CPU::new_nmos, setup(), 10);
+
+        assert!(audit.run().is_ok());
+    }
+
+    #[test]
+    fn should_flag_first_step_that_diverges() {
+        // stands in for a device seeded from host state: every run sees a different value
+        let runs = Cell::new(0u8);
+        let make_memory = || {
+            runs.set(runs.get() + 1);
+            let mut memory = program();
+            memory.insert(0x0202, &[0xA9, runs.get()]); // LDA #<run>
+
+            return memory;
+        };
+        let audit = DeterminismAudit::new(make_memory, CPU::new_nmos, setup(), 10);
+
+        let divergence = audit.run().unwrap_err();
+
+        assert_eq!(divergence.step, 0);
+        assert_ne!(divergence.expected_digest, divergence.actual_digest);
+    }
+
+    #[test]
+    fn should_verify_against_recorded_digest() {
+        let audit = DeterminismAudit::new(program, CPU::new_nmos, setup(), 10);
+        let digest = audit.run().unwrap();
+
+        assert_eq!(audit.verify_digest(digest), Ok(()));
+        assert_eq!(audit.verify_digest(digest ^ 1).unwrap_err().step, 2);
+    }
+
+    #[test]
+    fn should_run_on_chosen_chip_variant() {
+        let make_memory = || {
+            let mut memory = VecMemory::new();
+            memory.insert(0x0200, &[0x1A, 0x00]); // INC A on CMOS, NOP on NMOS; BRK
+
+            return memory;
+        };
+        let nmos = DeterminismAudit::new(make_memory, CPU::new_nmos, setup(), 10);
+        let cmos = DeterminismAudit::new(make_memory, CPU::new_cmos, setup(), 10);
+
+        assert_ne!(nmos.record()[0], cmos.record()[0]);
+        assert!(cmos.run().is_ok());
+    }
+}
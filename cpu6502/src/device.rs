@@ -0,0 +1,90 @@
+use std::cell::RefCell;
+
+use crate::{
+    consts::Word,
+    memory::{Bus, MaskedMemory},
+};
+
+pub mod acia;
+pub mod beeper;
+pub mod character_output;
+pub mod dma;
+pub mod keyboard;
+pub mod paravirt;
+pub mod pia;
+mod port;
+pub mod random;
+pub mod riot;
+pub mod text_screen;
+pub mod timer;
+pub mod via;
+
+// one byte a DMA device moves over the bus while it holds RDY low
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct DmaTransfer {
+    pub source: Word,
+    pub destination: Word,
+}
+
+// a peripheral with a clock of its own: its registers are read and written through the bus at
+// offsets relative to where it is mapped, and time passes for it in CPU cycles
+pub trait Device: Bus {
+    // advances the device by `cycles` CPU cycles
+    fn tick(&mut self, cycles: u64);
+
+    // level of the device's IRQ output; the line is shared, so any device can hold it low
+    fn irq_asserted(&self) -> bool {
+        return false;
+    }
+
+    // set once the device asks to end the emulation, with the exit code of the program
+    fn exit_code(&self) -> Option<i32> {
+        return None;
+    }
+
+    // the next byte to move while the device takes the bus from the CPU, None releases it
+    fn next_dma_transfer(&mut self) -> Option<DmaTransfer> {
+        return None;
+    }
+}
+
+// a device also reachable from outside the map, e.g. a terminal feeding keystrokes to a UART
+impl<D: Device + ?Sized> Device for &RefCell<D> {
+    fn tick(&mut self, cycles: u64) {
+        self.borrow_mut().tick(cycles);
+    }
+
+    fn irq_asserted(&self) -> bool {
+        return self.borrow().irq_asserted();
+    }
+
+    fn exit_code(&self) -> Option<i32> {
+        return self.borrow().exit_code();
+    }
+
+    fn next_dma_transfer(&mut self) -> Option<DmaTransfer> {
+        return self.borrow_mut().next_dma_transfer();
+    }
+}
+
+// a device behind partial address decoding, e.g. on the 13-bit bus of the 6507
+impl<D: Device> Device for MaskedMemory<D> {
+    fn tick(&mut self, cycles: u64) {
+        self.get_inner_mut().tick(cycles);
+    }
+
+    fn irq_asserted(&self) -> bool {
+        return self.get_inner().irq_asserted();
+    }
+
+    fn exit_code(&self) -> Option<i32> {
+        return self.get_inner().exit_code();
+    }
+
+    fn next_dma_transfer(&mut self) -> Option<DmaTransfer> {
+        return self.get_inner_mut().next_dma_transfer();
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,284 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::io::{self, BufReader, Read, Write};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+use super::Device;
+use crate::{
+    consts::{Byte, Word},
+    memory::Bus,
+};
+
+const DATA: Word = 0;
+const STATUS: Word = 1;
+const COMMAND: Word = 2;
+
+const STATUS_OVERRUN: Byte = 0x04;
+const STATUS_RECEIVER_FULL: Byte = 0x08;
+const STATUS_TRANSMITTER_EMPTY: Byte = 0x10;
+const STATUS_IRQ: Byte = 0x80;
+
+const COMMAND_DTR: Byte = 0x01;
+const COMMAND_RECEIVER_IRQ_DISABLED: Byte = 0x02;
+const COMMAND_ECHO: Byte = 0x10;
+// bits a programmed reset clears, parity settings survive it
+const COMMAND_RESET_MASK: Byte = 0xE0;
+
+// start bit, 8 data bits and a stop bit
+const BITS_PER_CHARACTER: u64 = 10;
+// baud rates selected by the low nibble of the control register; 0 selects the external
+// receiver clock, taken to run at 115200 baud
+const BAUD_RATES: [u64; 16] = [
+    115200, 50, 75, 110, 135, 150, 300, 600, 1200, 1800, 2400, 3600, 4800, 7200, 9600, 19200,
+];
+
+// the host side of the serial line
+pub trait SerialBackend {
+    fn transmit(&mut self, value: Byte);
+
+    // the next byte typed on the host, without waiting for one
+    fn receive(&mut self) -> Option<Byte>;
+}
+
+// a backend also reachable from outside the device, e.g. to feed input while the machine runs
+impl<B: SerialBackend + ?Sized> SerialBackend for &RefCell<B> {
+    fn transmit(&mut self, value: Byte) {
+        self.borrow_mut().transmit(value);
+    }
+
+    fn receive(&mut self) -> Option<Byte> {
+        return self.borrow_mut().receive();
+    }
+}
+
+// keeps both directions in memory, for tests and for driving firmware from code
+#[derive(Default)]
+pub struct BufferBackend {
+    input: VecDeque<Byte>,
+    output: Vec<Byte>,
+}
+
+impl BufferBackend {
+    pub fn new() -> Self {
+        return BufferBackend::default();
+    }
+
+    pub fn push_input(&mut self, input: &[Byte]) {
+        self.input.extend(input);
+    }
+
+    pub fn get_output(&self) -> &[Byte] {
+        return &self.output;
+    }
+
+    pub fn take_output(&mut self) -> Vec<Byte> {
+        return std::mem::take(&mut self.output);
+    }
+}
+
+impl SerialBackend for BufferBackend {
+    fn transmit(&mut self, value: Byte) {
+        self.output.push(value);
+    }
+
+    fn receive(&mut self) -> Option<Byte> {
+        return self.input.pop_front();
+    }
+}
+
+// bridges the line to a host stream, e.g. stdin and stdout or the slave side of a
+// pseudo-terminal opened as a file. The reader is drained on a thread of its own, so a
+// program polling the ACIA never blocks the emulator.
+pub struct StreamBackend<W: Write> {
+    received: Receiver<Byte>,
+    writer: W,
+}
+
+impl<W: Write> StreamBackend<W> {
+    pub fn new(reader: impl Read + Send + 'static, writer: W) -> Self {
+        let (sender, received) = mpsc::channel();
+        thread::spawn(move || {
+            for byte in BufReader::new(reader).bytes() {
+                let sent = match byte {
+                    Ok(byte) => sender.send(byte),
+                    Err(_) => return,
+                };
+                if sent.is_err() {
+                    return;
+                }
+            }
+        });
+
+        return StreamBackend { received, writer };
+    }
+
+    pub fn get_writer(&self) -> &W {
+        return &self.writer;
+    }
+}
+
+impl StreamBackend<io::Stdout> {
+    pub fn stdio() -> Self {
+        return StreamBackend::new(io::stdin(), io::stdout());
+    }
+}
+
+impl<W: Write> SerialBackend for StreamBackend<W> {
+    // the emulated program cannot handle a host error, the byte is lost like on a broken line
+    fn transmit(&mut self, value: Byte) {
+        let _ = self
+            .writer
+            .write_all(&[value])
+            .and_then(|_| self.writer.flush());
+    }
+
+    fn receive(&mut self) -> Option<Byte> {
+        return self.received.try_recv().ok();
+    }
+}
+
+// MOS 6551 asynchronous communications interface adapter with registers at offsets 0-3.
+// Received bytes arrive no faster than the programmed baud rate allows, with an IRQ when the
+// receiver interrupt is enabled. Transmission is immediate and the transmitter always reads
+// as empty, which is also what the WDC 65C51 does.
+pub struct Acia<B: SerialBackend> {
+    backend: B,
+    // CPU clock the baud rate is measured against
+    clock_hz: u64,
+    receive_data: Byte,
+    status: Byte,
+    command: Byte,
+    control: Byte,
+    cycles_since_character: u64,
+}
+
+impl<B: SerialBackend> Acia<B> {
+    pub fn new(backend: B, clock_hz: u64) -> Self {
+        return Acia {
+            backend,
+            clock_hz,
+            receive_data: 0,
+            status: STATUS_TRANSMITTER_EMPTY,
+            command: 0,
+            control: 0,
+            cycles_since_character: 0,
+        };
+    }
+
+    pub fn get_backend(&self) -> &B {
+        return &self.backend;
+    }
+
+    pub fn get_backend_mut(&mut self) -> &mut B {
+        return &mut self.backend;
+    }
+
+    pub fn get_command(&self) -> Byte {
+        return self.command;
+    }
+
+    pub fn get_control(&self) -> Byte {
+        return self.control;
+    }
+
+    fn cycles_per_character(&self) -> u64 {
+        let baud = BAUD_RATES[(self.control & 0x0F) as usize];
+
+        return (self.clock_hz * BITS_PER_CHARACTER / baud).max(1);
+    }
+
+    fn receiver_irq_enabled(&self) -> bool {
+        return self.command & COMMAND_DTR != 0
+            && self.command & COMMAND_RECEIVER_IRQ_DISABLED == 0;
+    }
+
+    fn receive(&mut self) {
+        let value = match self.backend.receive() {
+            Some(value) => value,
+            None => return,
+        };
+        self.receive_data = value;
+        self.status |= STATUS_RECEIVER_FULL;
+        if self.receiver_irq_enabled() {
+            self.status |= STATUS_IRQ;
+        }
+        if self.command & COMMAND_ECHO != 0 {
+            self.backend.transmit(value);
+        }
+    }
+}
+
+impl<B: SerialBackend> Bus for Acia<B> {
+    fn read(&mut self, addr: Word) -> Byte {
+        return match addr & 0x03 {
+            DATA => {
+                self.status &= !(STATUS_RECEIVER_FULL | STATUS_OVERRUN);
+                self.receive_data
+            }
+            STATUS => {
+                let status = self.status;
+                self.status &= !STATUS_IRQ;
+                status
+            }
+            COMMAND => self.command,
+            _ => self.control,
+        };
+    }
+
+    fn write(&mut self, addr: Word, value: Byte) {
+        match addr & 0x03 {
+            DATA => self.backend.transmit(value),
+            // programmed reset
+            STATUS => {
+                self.command &= COMMAND_RESET_MASK;
+                self.status &= !STATUS_OVERRUN;
+            }
+            COMMAND => self.command = value,
+            _ => self.control = value,
+        };
+    }
+
+    // reading data or status acknowledges what was received, peeking leaves it pending
+    fn peek(&mut self, addr: Word) -> Byte {
+        return match addr & 0x03 {
+            DATA => self.receive_data,
+            STATUS => self.status,
+            COMMAND => self.command,
+            _ => self.control,
+        };
+    }
+
+    // registers are set directly, nothing is transmitted and no reset is triggered
+    fn poke(&mut self, addr: Word, value: Byte) {
+        match addr & 0x03 {
+            DATA => self.receive_data = value,
+            STATUS => self.status = value,
+            COMMAND => self.command = value,
+            _ => self.control = value,
+        };
+    }
+}
+
+impl<B: SerialBackend> Device for Acia<B> {
+    fn tick(&mut self, cycles: u64) {
+        self.cycles_since_character += cycles;
+        let cycles_per_character = self.cycles_per_character();
+        if self.cycles_since_character < cycles_per_character {
+            return;
+        }
+        self.cycles_since_character %= cycles_per_character;
+
+        // the host keeps what the program has not picked up yet, so nothing is overrun
+        if self.status & STATUS_RECEIVER_FULL == 0 {
+            self.receive();
+        }
+    }
+
+    fn irq_asserted(&self) -> bool {
+        return self.status & STATUS_IRQ != 0;
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,174 @@
+#[cfg(test)]
+mod registers {
+    use crate::{
+        device::{
+            acia::{Acia, BufferBackend},
+            Device,
+        },
+        memory::Bus,
+    };
+
+    const CLOCK_HZ: u64 = 1_000_000;
+
+    // 19200 baud, a character every 520 cycles at 1 MHz
+    fn acia() -> Acia<BufferBackend> {
+        let mut acia = Acia::new(BufferBackend::new(), CLOCK_HZ);
+        acia.write(0x0003, 0x1F);
+
+        return acia;
+    }
+
+    #[test]
+    fn should_transmit_written_bytes_and_report_transmitter_empty() {
+        let mut acia = acia();
+
+        acia.write(0x0000, b'H');
+        acia.write(0x0000, b'i');
+
+        assert_eq!(acia.get_backend().get_output(), b"Hi");
+        assert_eq!(acia.read(0x0001) & 0x10, 0x10);
+    }
+
+    #[test]
+    fn should_receive_at_programmed_baud_rate() {
+        let mut acia = acia();
+        acia.get_backend_mut().push_input(b"AB");
+
+        acia.tick(519);
+        assert_eq!(acia.read(0x0001) & 0x08, 0x00);
+        acia.tick(1);
+        assert_eq!(acia.read(0x0001) & 0x08, 0x08);
+
+        assert_eq!(acia.read(0x0000), b'A');
+        assert_eq!(acia.read(0x0001) & 0x08, 0x00);
+    }
+
+    #[test]
+    fn should_hold_unread_byte_until_program_picks_it_up() {
+        let mut acia = acia();
+        acia.get_backend_mut().push_input(b"AB");
+
+        acia.tick(520 * 3);
+
+        assert_eq!(acia.read(0x0000), b'A');
+        acia.tick(520);
+        assert_eq!(acia.read(0x0000), b'B');
+    }
+
+    #[test]
+    fn should_raise_irq_on_receive_until_status_is_read() {
+        let mut acia = acia();
+        acia.write(0x0002, 0x09); // DTR, receiver IRQ enabled
+        acia.get_backend_mut().push_input(b"A");
+
+        acia.tick(520);
+
+        assert!(acia.irq_asserted());
+        assert_eq!(acia.peek(0x0001), 0x98);
+        assert!(acia.irq_asserted());
+        assert_eq!(acia.read(0x0001), 0x98);
+        assert!(!acia.irq_asserted());
+    }
+
+    #[test]
+    fn should_not_raise_irq_when_receiver_irq_is_disabled() {
+        let mut acia = acia();
+        acia.write(0x0002, 0x0B); // DTR, receiver IRQ disabled
+        acia.get_backend_mut().push_input(b"A");
+
+        acia.tick(520);
+
+        assert!(!acia.irq_asserted());
+        assert_eq!(acia.read(0x0001), 0x18);
+    }
+
+    #[test]
+    fn should_echo_received_bytes_in_echo_mode() {
+        let mut acia = acia();
+        acia.write(0x0002, 0x13);
+        acia.get_backend_mut().push_input(b"A");
+
+        acia.tick(520);
+
+        assert_eq!(acia.get_backend().get_output(), b"A");
+    }
+
+    #[test]
+    fn should_clear_command_bits_on_programmed_reset() {
+        let mut acia = acia();
+        acia.write(0x0002, 0xEB);
+
+        acia.write(0x0001, 0x00);
+
+        assert_eq!(acia.get_command(), 0xE0);
+        assert_eq!(acia.get_control(), 0x1F);
+    }
+}
+
+#[cfg(test)]
+mod stream_backend {
+    use std::{
+        io::Cursor,
+        thread,
+        time::{Duration, Instant},
+    };
+
+    use crate::device::acia::{SerialBackend, StreamBackend};
+
+    #[test]
+    fn should_forward_host_stream_in_both_directions() {
+        let mut backend = StreamBackend::new(Cursor::new(b"ok".to_vec()), Vec::new());
+
+        backend.transmit(b'!');
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let mut received = Vec::new();
+        while received.len() < 2 && Instant::now() < deadline {
+            match backend.receive() {
+                Some(value) => received.push(value),
+                None => thread::sleep(Duration::from_millis(1)),
+            };
+        }
+
+        assert_eq!(received, b"ok");
+        assert_eq!(backend.get_writer(), b"!");
+    }
+}
+
+#[cfg(test)]
+mod system_integration {
+    use std::cell::RefCell;
+
+    use crate::{
+        cpu::CPU,
+        device::acia::{Acia, BufferBackend},
+        memory::{memory_map::MemoryMap, Bus, Ram64k},
+        system::System,
+    };
+
+    #[test]
+    fn should_echo_input_through_polling_loop() {
+        // loop: LDA $8801; AND #$08; BEQ loop; LDA $8800; STA $8800; JMP loop
+        let mut ram = Ram64k::new();
+        ram.load(
+            &[
+                0xAD, 0x01, 0x88, 0x29, 0x08, 0xF0, 0xF9, 0xAD, 0x00, 0x88, 0x8D, 0x00, 0x88, 0x4C,
+                0x00, 0x02,
+            ],
+            0x0200,
+        );
+        let mut backend = BufferBackend::new();
+        backend.push_input(b"READY");
+        let acia = RefCell::new(Acia::new(backend, 1_000_000));
+        let mut map = MemoryMap::new();
+        map.map(0x0000..=0xFFFF, ram);
+        map.attach(0x8800..=0x8803, &acia);
+        let mut cpu = CPU::with_memory(map);
+        cpu.set_program_counter(0x0200);
+        let mut system = System::new(cpu);
+        system.get_memory_map_mut().write(0x8803, 0x1F);
+
+        system.execute_cycles(520 * 6);
+
+        assert_eq!(acia.borrow().get_backend().get_output(), b"READY");
+    }
+}
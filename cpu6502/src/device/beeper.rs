@@ -0,0 +1,112 @@
+use std::io::{self, Write};
+
+use super::Device;
+use crate::{
+    consts::{Byte, Word},
+    memory::Bus,
+};
+
+// level of the speaker in the exported 16 bit samples
+const AMPLITUDE: i16 = 0x2000;
+const BITS_PER_SAMPLE: u16 = 16;
+
+// 1-bit speaker like the Apple II one: every write at any offset flips the speaker between
+// its two positions. Flips are recorded against the cycles the device has been clocked for,
+// the speaker starts out low. Reads return 0.
+#[derive(Default)]
+pub struct Beeper {
+    cycle: u64,
+    transitions: Vec<u64>,
+}
+
+impl Beeper {
+    pub fn new() -> Self {
+        return Beeper::default();
+    }
+
+    pub fn get_cycle(&self) -> u64 {
+        return self.cycle;
+    }
+
+    // cycles at which the speaker flipped, in order
+    pub fn get_transitions(&self) -> &[u64] {
+        return &self.transitions;
+    }
+
+    pub fn is_high(&self) -> bool {
+        return self.transitions.len() % 2 == 1;
+    }
+
+    // forgets the recording, the speaker keeps its position
+    pub fn clear(&mut self) {
+        let high = self.is_high();
+        self.transitions.clear();
+        if high {
+            self.transitions.push(self.cycle);
+        }
+    }
+
+    // the recording up to the current cycle as a mono 16 bit PCM WAV file, with `clock_hz`
+    // the CPU clock the cycles are counted in
+    pub fn write_wav(
+        &self,
+        writer: &mut impl Write,
+        clock_hz: u64,
+        sample_rate: u32,
+    ) -> io::Result<()> {
+        let sample_count = self.cycle * sample_rate as u64 / clock_hz;
+        let block_align = BITS_PER_SAMPLE / 8;
+        let data_size = (sample_count * block_align as u64) as u32;
+
+        writer.write_all(b"RIFF")?;
+        writer.write_all(&(36 + data_size).to_le_bytes())?;
+        writer.write_all(b"WAVEfmt ")?;
+        writer.write_all(&16u32.to_le_bytes())?;
+        // PCM, one channel
+        writer.write_all(&1u16.to_le_bytes())?;
+        writer.write_all(&1u16.to_le_bytes())?;
+        writer.write_all(&sample_rate.to_le_bytes())?;
+        writer.write_all(&(sample_rate * block_align as u32).to_le_bytes())?;
+        writer.write_all(&block_align.to_le_bytes())?;
+        writer.write_all(&BITS_PER_SAMPLE.to_le_bytes())?;
+        writer.write_all(b"data")?;
+        writer.write_all(&data_size.to_le_bytes())?;
+
+        let mut flips = 0;
+        for sample in 0..sample_count {
+            let cycle = sample * clock_hz / sample_rate as u64;
+            while flips < self.transitions.len() && self.transitions[flips] <= cycle {
+                flips += 1;
+            }
+            let level = match flips % 2 {
+                0 => -AMPLITUDE,
+                _ => AMPLITUDE,
+            };
+            writer.write_all(&level.to_le_bytes())?;
+        }
+
+        return writer.flush();
+    }
+}
+
+impl Bus for Beeper {
+    fn read(&mut self, _addr: Word) -> Byte {
+        return 0;
+    }
+
+    fn write(&mut self, _addr: Word, _value: Byte) {
+        self.transitions.push(self.cycle);
+    }
+
+    // a monitor poking the port does not click the speaker
+    fn poke(&mut self, _addr: Word, _value: Byte) {}
+}
+
+impl Device for Beeper {
+    fn tick(&mut self, cycles: u64) {
+        self.cycle += cycles;
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,142 @@
+#[cfg(test)]
+mod recording {
+    use crate::{
+        device::{beeper::Beeper, Device},
+        memory::Bus,
+    };
+
+    #[test]
+    fn should_record_flips_against_clocked_cycles() {
+        let mut beeper = Beeper::new();
+
+        beeper.tick(10);
+        beeper.write(0x0000, 0x00);
+        beeper.tick(5);
+        beeper.write(0x0000, 0xFF);
+
+        assert_eq!(beeper.get_transitions(), &[10, 15]);
+        assert!(!beeper.is_high());
+        assert_eq!(beeper.get_cycle(), 15);
+    }
+
+    #[test]
+    fn should_not_flip_on_reads_or_pokes() {
+        let mut beeper = Beeper::new();
+
+        beeper.read(0x0000);
+        beeper.poke(0x0000, 0x00);
+
+        assert!(beeper.get_transitions().is_empty());
+    }
+
+    #[test]
+    fn should_keep_speaker_position_when_cleared() {
+        let mut beeper = Beeper::new();
+        beeper.write(0x0000, 0x00);
+        beeper.tick(100);
+
+        beeper.clear();
+
+        assert!(beeper.is_high());
+        assert_eq!(beeper.get_transitions(), &[100]);
+    }
+}
+
+#[cfg(test)]
+mod wav_export {
+    use crate::{
+        device::{beeper::Beeper, Device},
+        memory::Bus,
+    };
+
+    fn samples(wav: &[u8]) -> Vec<i16> {
+        return wav[44..]
+            .chunks(2)
+            .map(|sample| i16::from_le_bytes([sample[0], sample[1]]))
+            .collect();
+    }
+
+    #[test]
+    fn should_write_pcm_header() {
+        let mut beeper = Beeper::new();
+        beeper.tick(1000);
+        let mut wav = Vec::new();
+
+        beeper.write_wav(&mut wav, 1000, 100).unwrap();
+
+        assert_eq!(&wav[0..4], b"RIFF");
+        assert_eq!(u32::from_le_bytes([wav[4], wav[5], wav[6], wav[7]]), 236);
+        assert_eq!(&wav[8..16], b"WAVEfmt ");
+        assert_eq!(u16::from_le_bytes([wav[22], wav[23]]), 1);
+        assert_eq!(
+            u32::from_le_bytes([wav[24], wav[25], wav[26], wav[27]]),
+            100
+        );
+        assert_eq!(u16::from_le_bytes([wav[34], wav[35]]), 16);
+        assert_eq!(&wav[36..40], b"data");
+        assert_eq!(
+            u32::from_le_bytes([wav[40], wav[41], wav[42], wav[43]]),
+            200
+        );
+        assert_eq!(wav.len(), 244);
+    }
+
+    #[test]
+    fn should_sample_square_wave_from_flips() {
+        let mut beeper = Beeper::new();
+        (0..4).for_each(|_| {
+            beeper.tick(20);
+            beeper.write(0x0000, 0x00);
+        });
+        beeper.tick(20);
+        let mut wav = Vec::new();
+
+        // one sample every 10 cycles
+        beeper.write_wav(&mut wav, 1000, 100).unwrap();
+
+        let low = -0x2000;
+        let high = 0x2000;
+        assert_eq!(
+            samples(&wav),
+            vec![low, low, high, high, low, low, high, high, low, low]
+        );
+    }
+}
+
+#[cfg(test)]
+mod system_integration {
+    use std::cell::RefCell;
+
+    use crate::{
+        cpu::CPU,
+        device::beeper::Beeper,
+        memory::{memory_map::MemoryMap, Ram64k},
+        system::System,
+    };
+
+    // loop: STA $C030; LDX #4; delay: DEX; BNE delay; JMP loop
+    #[test]
+    fn should_record_tone_at_loop_period() {
+        let beeper = RefCell::new(Beeper::new());
+        let mut ram = Ram64k::new();
+        ram.load(
+            &[
+                0x8D, 0x30, 0xC0, 0xA2, 0x04, 0xCA, 0xD0, 0xFD, 0x4C, 0x00, 0x02,
+            ],
+            0x0200,
+        );
+        let mut map = MemoryMap::new();
+        map.map(0x0000..=0xFFFF, ram);
+        map.attach(0xC030..=0xC030, &beeper);
+        let mut cpu = CPU::with_memory(map);
+        cpu.set_program_counter(0x0200);
+        let mut system = System::new(cpu);
+
+        system.execute_cycles(200);
+
+        // STA 4 + LDX 2 + 4 * DEX 2 + 3 * taken BNE 3 + BNE 2 + JMP 3
+        let transitions = beeper.borrow().get_transitions().to_vec();
+        assert!(transitions.len() > 3);
+        assert!(transitions.windows(2).all(|pair| pair[1] - pair[0] == 28));
+    }
+}
@@ -0,0 +1,81 @@
+use std::io::{self, Write};
+
+use super::Device;
+use crate::{
+    consts::{Byte, Word},
+    memory::Bus,
+};
+
+// the "putchar" port test ROMs and tutorials expect: every byte written anywhere in the range
+// it is mapped at goes to the host writer. Reads return 0.
+pub struct CharacterOutput<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> CharacterOutput<W> {
+    pub fn new(writer: W) -> Self {
+        return CharacterOutput { writer };
+    }
+
+    pub fn get_writer(&self) -> &W {
+        return &self.writer;
+    }
+
+    pub fn get_writer_mut(&mut self) -> &mut W {
+        return &mut self.writer;
+    }
+
+    pub fn into_writer(self) -> W {
+        return self.writer;
+    }
+}
+
+impl CharacterOutput<io::Stdout> {
+    pub fn stdout() -> Self {
+        return CharacterOutput::new(io::stdout());
+    }
+}
+
+// collects the output in memory, e.g. to compare it against the expected text in a test
+impl CharacterOutput<Vec<Byte>> {
+    pub fn buffered() -> Self {
+        return CharacterOutput::new(Vec::new());
+    }
+
+    pub fn get_output(&self) -> &[Byte] {
+        return &self.writer;
+    }
+
+    // bytes that are not valid UTF-8 show up as replacement characters
+    pub fn get_text(&self) -> String {
+        return String::from_utf8_lossy(&self.writer).into_owned();
+    }
+
+    pub fn take_output(&mut self) -> Vec<Byte> {
+        return std::mem::take(&mut self.writer);
+    }
+}
+
+impl<W: Write> Bus for CharacterOutput<W> {
+    fn read(&mut self, _addr: Word) -> Byte {
+        return 0;
+    }
+
+    // the emulated program cannot handle a host error, the byte is dropped
+    fn write(&mut self, _addr: Word, value: Byte) {
+        let _ = self
+            .writer
+            .write_all(&[value])
+            .and_then(|_| self.writer.flush());
+    }
+
+    // nothing is printed when a monitor pokes the port
+    fn poke(&mut self, _addr: Word, _value: Byte) {}
+}
+
+impl<W: Write> Device for CharacterOutput<W> {
+    fn tick(&mut self, _cycles: u64) {}
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod output {
+    use crate::{device::character_output::CharacterOutput, memory::Bus};
+
+    #[test]
+    fn should_collect_written_bytes_as_text() {
+        let mut output = CharacterOutput::buffered();
+
+        b"Hello".iter().for_each(|c| output.write(0x0000, *c));
+
+        assert_eq!(output.get_output(), b"Hello");
+        assert_eq!(output.get_text(), "Hello");
+        assert_eq!(output.read(0x0000), 0);
+    }
+
+    #[test]
+    fn should_not_print_poked_bytes() {
+        let mut output = CharacterOutput::buffered();
+
+        output.poke(0x0000, b'X');
+
+        assert_eq!(output.take_output(), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn should_write_to_any_host_writer() {
+        let mut output = CharacterOutput::new(std::io::Cursor::new(Vec::new()));
+
+        output.write(0x0000, b'A');
+
+        assert_eq!(output.into_writer().into_inner(), b"A");
+    }
+}
+
+#[cfg(test)]
+mod system_integration {
+    use crate::{
+        cpu::CPU,
+        device::character_output::CharacterOutput,
+        memory::{memory_map::MemoryMap, Ram64k},
+    };
+
+    // loop: LDA $0300,X; BEQ done; STA $F001; INX; BNE loop; done: BRK
+    #[test]
+    fn should_print_string_written_to_chosen_address() {
+        let output = std::cell::RefCell::new(CharacterOutput::buffered());
+        let mut ram = Ram64k::new();
+        ram.load(
+            &[
+                0xBD, 0x00, 0x03, 0xF0, 0x06, 0x8D, 0x01, 0xF0, 0xE8, 0xD0, 0xF5, 0x00,
+            ],
+            0x0200,
+        );
+        ram.load(b"hello, world\n\0", 0x0300);
+        let mut map = MemoryMap::new();
+        map.map(0x0000..=0xFFFF, ram);
+        map.map(0xF001..=0xF001, &output);
+        let mut cpu = CPU::with_memory(map);
+        cpu.set_program_counter(0x0200);
+
+        cpu.execute_until_break();
+
+        assert_eq!(output.borrow().get_text(), "hello, world\n");
+    }
+}
@@ -0,0 +1,152 @@
+use super::{Device, DmaTransfer};
+use crate::{
+    consts::{Byte, Word},
+    memory::Bus,
+};
+
+const SOURCE_LOW: Word = 0;
+const SOURCE_HIGH: Word = 1;
+const DESTINATION_LOW: Word = 2;
+const DESTINATION_HIGH: Word = 3;
+const LENGTH_LOW: Word = 4;
+const LENGTH_HIGH: Word = 5;
+const CONTROL: Word = 6;
+const STATUS: Word = 7;
+
+const CONTROL_START: Byte = 0x01;
+// keeps the address on one location, e.g. the data register of a disk controller
+const CONTROL_FIXED_SOURCE: Byte = 0x02;
+const CONTROL_FIXED_DESTINATION: Byte = 0x04;
+const CONTROL_IRQ_ENABLED: Byte = 0x08;
+
+const STATUS_BUSY: Byte = 0x80;
+const STATUS_DONE: Byte = 0x40;
+
+// block copy engine stealing cycles from the CPU, with registers at offsets 0-7:
+//  - 0-1 source, 2-3 destination and 4-5 length, little endian
+//  - 6: control, bit 0 starts the transfer, bits 1 and 2 keep the source or destination on one
+//    address, bit 3 raises an IRQ when the transfer is done
+//  - 7: status, bit 7 set while busy, bit 6 once done; reading it acknowledges completion
+// While busy the controller holds RDY low and moves a byte at a time; in a System each byte
+// halts the CPU for a read and a write cycle. Addresses wrap around the 64K space and a zero
+// length moves nothing.
+#[derive(Default)]
+pub struct DmaController {
+    source: Word,
+    destination: Word,
+    length: Word,
+    control: Byte,
+    status: Byte,
+    // bytes left of the running transfer
+    remaining: Word,
+}
+
+impl DmaController {
+    pub fn new() -> Self {
+        return DmaController::default();
+    }
+
+    pub fn is_busy(&self) -> bool {
+        return self.status & STATUS_BUSY != 0;
+    }
+
+    pub fn get_remaining(&self) -> Word {
+        return self.remaining;
+    }
+
+    fn write_control(&mut self, value: Byte) {
+        self.control = value & !CONTROL_START;
+        if value & CONTROL_START == 0 || self.length == 0 {
+            return;
+        }
+
+        self.remaining = self.length;
+        self.status = STATUS_BUSY;
+    }
+
+    fn register(&self, addr: Word) -> Byte {
+        return match addr & 0x07 {
+            SOURCE_LOW => self.source as Byte,
+            SOURCE_HIGH => (self.source >> 8) as Byte,
+            DESTINATION_LOW => self.destination as Byte,
+            DESTINATION_HIGH => (self.destination >> 8) as Byte,
+            LENGTH_LOW => self.length as Byte,
+            LENGTH_HIGH => (self.length >> 8) as Byte,
+            CONTROL => self.control,
+            _ => self.status,
+        };
+    }
+}
+
+fn set_low(word: Word, value: Byte) -> Word {
+    return (word & 0xFF00) | value as Word;
+}
+
+fn set_high(word: Word, value: Byte) -> Word {
+    return (word & 0x00FF) | ((value as Word) << 8);
+}
+
+impl Bus for DmaController {
+    fn read(&mut self, addr: Word) -> Byte {
+        let value = self.register(addr);
+        if addr & 0x07 == STATUS {
+            self.status &= !STATUS_DONE;
+        }
+
+        return value;
+    }
+
+    // source and destination registers advance as the transfer runs, the length keeps its
+    // value so the same block can be started again
+    fn write(&mut self, addr: Word, value: Byte) {
+        match addr & 0x07 {
+            SOURCE_LOW => self.source = set_low(self.source, value),
+            SOURCE_HIGH => self.source = set_high(self.source, value),
+            DESTINATION_LOW => self.destination = set_low(self.destination, value),
+            DESTINATION_HIGH => self.destination = set_high(self.destination, value),
+            LENGTH_LOW => self.length = set_low(self.length, value),
+            LENGTH_HIGH => self.length = set_high(self.length, value),
+            CONTROL => self.write_control(value),
+            _ => (),
+        };
+    }
+
+    // reading the status acknowledges completion, peeking does not
+    fn peek(&mut self, addr: Word) -> Byte {
+        return self.register(addr);
+    }
+}
+
+impl Device for DmaController {
+    fn tick(&mut self, _cycles: u64) {}
+
+    fn irq_asserted(&self) -> bool {
+        return self.status & STATUS_DONE != 0 && self.control & CONTROL_IRQ_ENABLED != 0;
+    }
+
+    fn next_dma_transfer(&mut self) -> Option<DmaTransfer> {
+        if !self.is_busy() {
+            return None;
+        }
+
+        let transfer = DmaTransfer {
+            source: self.source,
+            destination: self.destination,
+        };
+        if self.control & CONTROL_FIXED_SOURCE == 0 {
+            self.source = self.source.wrapping_add(1);
+        }
+        if self.control & CONTROL_FIXED_DESTINATION == 0 {
+            self.destination = self.destination.wrapping_add(1);
+        }
+        self.remaining -= 1;
+        if self.remaining == 0 {
+            self.status = STATUS_DONE;
+        }
+
+        return Some(transfer);
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,146 @@
+#[cfg(test)]
+mod transfers {
+    use crate::{
+        device::{dma::DmaController, Device, DmaTransfer},
+        memory::Bus,
+    };
+
+    // source $1234, destination $5678, length 3
+    fn controller() -> DmaController {
+        let mut dma = DmaController::new();
+        [0x34, 0x12, 0x78, 0x56, 0x03, 0x00]
+            .iter()
+            .enumerate()
+            .for_each(|(idx, value)| dma.write(idx as u16, *value));
+
+        return dma;
+    }
+
+    fn transfer(source: u16, destination: u16) -> Option<DmaTransfer> {
+        return Some(DmaTransfer {
+            source,
+            destination,
+        });
+    }
+
+    #[test]
+    fn should_hold_bus_only_after_start() {
+        let mut dma = controller();
+        assert_eq!(dma.next_dma_transfer(), None);
+
+        dma.write(0x0006, 0x01);
+
+        assert!(dma.is_busy());
+        assert_eq!(dma.read(0x0007), 0x80);
+    }
+
+    #[test]
+    fn should_move_length_bytes_advancing_both_addresses() {
+        let mut dma = controller();
+        dma.write(0x0006, 0x01);
+
+        assert_eq!(dma.next_dma_transfer(), transfer(0x1234, 0x5678));
+        assert_eq!(dma.next_dma_transfer(), transfer(0x1235, 0x5679));
+        assert_eq!(dma.next_dma_transfer(), transfer(0x1236, 0x567A));
+
+        assert_eq!(dma.next_dma_transfer(), None);
+        assert_eq!(dma.get_remaining(), 0);
+        assert_eq!(dma.read(0x0007), 0x40);
+        assert_eq!(dma.read(0x0007), 0x00);
+        assert_eq!(dma.read(0x0004), 0x03);
+    }
+
+    #[test]
+    fn should_keep_fixed_source_on_one_address() {
+        let mut dma = controller();
+        dma.write(0x0006, 0x03);
+
+        dma.next_dma_transfer();
+
+        assert_eq!(dma.next_dma_transfer(), transfer(0x1234, 0x5679));
+    }
+
+    #[test]
+    fn should_not_start_with_zero_length() {
+        let mut dma = controller();
+        dma.write(0x0004, 0x00);
+
+        dma.write(0x0006, 0x01);
+
+        assert!(!dma.is_busy());
+    }
+
+    #[test]
+    fn should_raise_irq_when_done_until_status_is_read() {
+        let mut dma = controller();
+        dma.write(0x0006, 0x09);
+
+        (0..3).for_each(|_| {
+            dma.next_dma_transfer();
+        });
+        assert!(dma.irq_asserted());
+        assert_eq!(dma.peek(0x0007), 0x40);
+        dma.read(0x0007);
+
+        assert!(!dma.irq_asserted());
+    }
+}
+
+#[cfg(test)]
+mod system_integration {
+    use crate::{
+        cpu::CPU,
+        device::dma::DmaController,
+        memory::{memory_map::MemoryMap, Bus, Ram64k},
+        system::System,
+    };
+
+    // copies 16 bytes from $0300 to $0400: six LDA #imm / STA $D00n pairs, then BRK
+    fn system(control: u8) -> System<'static> {
+        let mut ram = Ram64k::new();
+        let registers = [(0x00, 0x00), (0x03, 0x01), (0x00, 0x02), (0x04, 0x03)];
+        let setup: Vec<u8> = registers
+            .iter()
+            .chain(&[(0x10, 0x04), (control, 0x06)])
+            .flat_map(|(value, register)| [0xA9, *value, 0x8D, *register, 0xD0])
+            .chain([0x00])
+            .collect();
+        ram.load(&setup, 0x0200);
+        ram.load(&(0x01..=0x10).collect::<Vec<u8>>(), 0x0300);
+        let mut map = MemoryMap::new();
+        map.map(0x0000..=0xFFFF, ram);
+        map.attach(0xD000..=0xD007, DmaController::new());
+        let mut cpu = CPU::with_memory(map);
+        cpu.set_program_counter(0x0200);
+
+        return System::new(cpu);
+    }
+
+    #[test]
+    fn should_copy_block_while_cpu_is_halted() {
+        let mut system = system(0x01);
+
+        system.execute_until_break();
+
+        let map = system.get_memory_map_mut();
+        let copied: Vec<u8> = (0x0400..0x0410).map(|addr| map.read(addr)).collect();
+        assert_eq!(copied, (0x01..=0x10).collect::<Vec<u8>>());
+        assert!(system.get_cpu().get_rdy());
+    }
+
+    #[test]
+    fn should_account_two_stolen_cycles_per_byte() {
+        let mut idle = system(0x00);
+        let mut copying = system(0x01);
+
+        idle.execute_until_break();
+        copying.execute_until_break();
+
+        assert_eq!(idle.get_stolen_cycles(), 0);
+        assert_eq!(copying.get_stolen_cycles(), 32);
+        assert_eq!(
+            copying.get_cpu().get_cycle() - idle.get_cpu().get_cycle(),
+            32
+        );
+    }
+}
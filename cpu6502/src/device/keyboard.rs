@@ -0,0 +1,89 @@
+use std::collections::VecDeque;
+
+use super::Device;
+use crate::{
+    consts::{Byte, Word},
+    memory::Bus,
+};
+
+const DATA: Word = 0;
+
+const STATUS_KEY_AVAILABLE: Byte = 0x80;
+const CONTROL_IRQ_ENABLED: Byte = 0x01;
+
+// keys pushed from the host, queued until the program picks them up. Offset 0 reads the next
+// key and removes it from the queue, or the last key again when none is waiting. Offset 1
+// reads the status, bit 7 set while a key is waiting, with the control bits written to it
+// below: bit 0 raises an IRQ for as long as keys are waiting.
+#[derive(Default)]
+pub struct Keyboard {
+    keys: VecDeque<Byte>,
+    last_key: Byte,
+    control: Byte,
+}
+
+impl Keyboard {
+    pub fn new() -> Self {
+        return Keyboard::default();
+    }
+
+    pub fn push_key(&mut self, key: Byte) {
+        self.keys.push_back(key);
+    }
+
+    pub fn push_keys(&mut self, keys: &[Byte]) {
+        self.keys.extend(keys);
+    }
+
+    pub fn get_pending_count(&self) -> usize {
+        return self.keys.len();
+    }
+
+    fn status(&self) -> Byte {
+        let available = match self.keys.is_empty() {
+            true => 0,
+            false => STATUS_KEY_AVAILABLE,
+        };
+
+        return available | self.control;
+    }
+}
+
+impl Bus for Keyboard {
+    fn read(&mut self, addr: Word) -> Byte {
+        if addr & 0x01 != DATA {
+            return self.status();
+        }
+
+        if let Some(key) = self.keys.pop_front() {
+            self.last_key = key;
+        }
+        return self.last_key;
+    }
+
+    fn write(&mut self, addr: Word, value: Byte) {
+        if addr & 0x01 != DATA {
+            self.control = value & CONTROL_IRQ_ENABLED;
+        }
+    }
+
+    // reading the data register consumes the key, peeking leaves it waiting
+    fn peek(&mut self, addr: Word) -> Byte {
+        if addr & 0x01 != DATA {
+            return self.status();
+        }
+
+        return *self.keys.front().unwrap_or(&self.last_key);
+    }
+}
+
+impl Device for Keyboard {
+    fn tick(&mut self, _cycles: u64) {}
+
+    fn irq_asserted(&self) -> bool {
+        return !self.keys.is_empty() && self.control & CONTROL_IRQ_ENABLED != 0;
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,90 @@
+#[cfg(test)]
+mod registers {
+    use crate::{
+        device::{keyboard::Keyboard, Device},
+        memory::Bus,
+    };
+
+    #[test]
+    fn should_report_waiting_key_in_status() {
+        let mut keyboard = Keyboard::new();
+        assert_eq!(keyboard.read(0x0001), 0x00);
+
+        keyboard.push_key(b'A');
+
+        assert_eq!(keyboard.read(0x0001), 0x80);
+    }
+
+    #[test]
+    fn should_read_keys_in_order_and_repeat_last_one() {
+        let mut keyboard = Keyboard::new();
+        keyboard.push_keys(b"AB");
+
+        assert_eq!(keyboard.read(0x0000), b'A');
+        assert_eq!(keyboard.read(0x0000), b'B');
+        assert_eq!(keyboard.read(0x0000), b'B');
+        assert_eq!(keyboard.read(0x0001), 0x00);
+    }
+
+    #[test]
+    fn should_leave_key_waiting_when_peeked() {
+        let mut keyboard = Keyboard::new();
+        keyboard.push_key(b'A');
+
+        assert_eq!(keyboard.peek(0x0000), b'A');
+
+        assert_eq!(keyboard.get_pending_count(), 1);
+    }
+
+    #[test]
+    fn should_raise_irq_while_keys_wait_when_enabled() {
+        let mut keyboard = Keyboard::new();
+        keyboard.push_key(b'A');
+        assert!(!keyboard.irq_asserted());
+
+        keyboard.write(0x0001, 0x01);
+        assert!(keyboard.irq_asserted());
+        assert_eq!(keyboard.read(0x0001), 0x81);
+        keyboard.read(0x0000);
+
+        assert!(!keyboard.irq_asserted());
+    }
+}
+
+#[cfg(test)]
+mod system_integration {
+    use std::cell::RefCell;
+
+    use crate::{
+        cpu::CPU,
+        device::{character_output::CharacterOutput, keyboard::Keyboard},
+        memory::{memory_map::MemoryMap, Ram64k},
+    };
+
+    // echoes keys until Return: poll: LDA $F011; BPL poll; LDA $F010; STA $F000; CMP #$0D;
+    // BNE poll; BRK
+    #[test]
+    fn should_echo_pushed_keys_to_character_output() {
+        let keyboard = RefCell::new(Keyboard::new());
+        let output = RefCell::new(CharacterOutput::buffered());
+        let mut ram = Ram64k::new();
+        ram.load(
+            &[
+                0xAD, 0x11, 0xF0, 0x10, 0xFB, 0xAD, 0x10, 0xF0, 0x8D, 0x00, 0xF0, 0xC9, 0x0D, 0xD0,
+                0xF1, 0x00,
+            ],
+            0x0200,
+        );
+        let mut map = MemoryMap::new();
+        map.map(0x0000..=0xFFFF, ram);
+        map.map(0xF000..=0xF000, &output);
+        map.attach(0xF010..=0xF011, &keyboard);
+        let mut cpu = CPU::with_memory(map);
+        cpu.set_program_counter(0x0200);
+        keyboard.borrow_mut().push_keys(b"hi\r");
+
+        cpu.execute_until_break();
+
+        assert_eq!(output.borrow().get_text(), "hi\r");
+    }
+}
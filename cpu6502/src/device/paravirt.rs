@@ -0,0 +1,295 @@
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::path::PathBuf;
+
+use super::Device;
+use crate::{
+    consts::{Byte, Word},
+    memory::Bus,
+};
+
+const COMMAND: Word = 0x00;
+const RESULT: Word = 0x01;
+const HANDLE: Word = 0x02;
+const LENGTH: Word = 0x03;
+const ARGUMENT: Word = 0x04;
+const MODE: Word = 0x05;
+const BUFFER_START: Word = 0x100;
+const BUFFER_SIZE: usize = 0x100;
+
+const COMMAND_OPEN: Byte = 0x01;
+const COMMAND_CLOSE: Byte = 0x02;
+const COMMAND_READ: Byte = 0x03;
+const COMMAND_WRITE: Byte = 0x04;
+const COMMAND_ARGC: Byte = 0x05;
+const COMMAND_ARGV: Byte = 0x06;
+const COMMAND_EXIT: Byte = 0x07;
+
+const RESULT_OK: Byte = 0x00;
+const RESULT_BAD_HANDLE: Byte = 0x01;
+const RESULT_IO_ERROR: Byte = 0x02;
+const RESULT_BAD_COMMAND: Byte = 0x03;
+const RESULT_NO_ARGUMENT: Byte = 0x04;
+const RESULT_NO_FREE_HANDLE: Byte = 0x05;
+
+const MODE_READ: Byte = 0x00;
+const MODE_WRITE: Byte = 0x01;
+const MODE_APPEND: Byte = 0x02;
+
+// handles 0-2 are stdin, stdout and stderr, opened files get the lowest free handle above them
+const MAX_HANDLES: usize = 256;
+
+enum HostStream {
+    Input(Box<dyn Read>),
+    Output(Box<dyn Write>),
+    File(File),
+}
+
+impl HostStream {
+    fn read(&mut self, buffer: &mut [Byte]) -> io::Result<usize> {
+        return match self {
+            HostStream::Input(reader) => reader.read(buffer),
+            HostStream::File(file) => file.read(buffer),
+            HostStream::Output(_) => Err(io::ErrorKind::Unsupported.into()),
+        };
+    }
+
+    fn write(&mut self, buffer: &[Byte]) -> io::Result<()> {
+        return match self {
+            HostStream::Output(writer) => writer.write_all(buffer).and_then(|_| writer.flush()),
+            HostStream::File(file) => file.write_all(buffer),
+            HostStream::Input(_) => Err(io::ErrorKind::Unsupported.into()),
+        };
+    }
+}
+
+// system calls for the emulated program, in the spirit of cc65's sim65, so compiled test
+// programs can run headless against host files. The program fills the registers and the
+// transfer buffer at $100-$1FF, then writes a command to offset 0:
+//  - $01 open: NUL terminated path in the buffer, mode at offset 5 (0 read, 1 write, 2 append);
+//    the handle comes back at offset 2
+//  - $02 close: handle at offset 2
+//  - $03 read: handle at offset 2, up to length at offset 3 bytes into the buffer; the length
+//    comes back as the count read, 0 at the end of the file
+//  - $04 write: handle at offset 2, length at offset 3 bytes from the buffer
+//  - $05 argc: the argument count comes back at offset 4
+//  - $06 argv: the argument indexed by offset 4 comes back NUL terminated in the buffer, its
+//    length at offset 3
+//  - $07 exit: ends the emulation with the exit code at offset 4
+// Offset 1 holds the result of the last command: 0 ok, 1 bad handle, 2 host I/O error, 3 unknown
+// command, 4 no such argument, 5 no free handle.
+// Paths are resolved against the root directory, which is not a sandbox: absolute paths and
+// ".." reach the rest of the host file system.
+pub struct Paravirt {
+    root: PathBuf,
+    args: Vec<String>,
+    streams: Vec<Option<HostStream>>,
+    command: Byte,
+    result: Byte,
+    handle: Byte,
+    length: Byte,
+    argument: Byte,
+    mode: Byte,
+    buffer: [Byte; BUFFER_SIZE],
+    exit_code: Option<i32>,
+}
+
+impl Paravirt {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        return Paravirt {
+            root: root.into(),
+            args: Vec::new(),
+            streams: vec![
+                Some(HostStream::Input(Box::new(io::stdin()))),
+                Some(HostStream::Output(Box::new(io::stdout()))),
+                Some(HostStream::Output(Box::new(io::stderr()))),
+            ],
+            command: 0,
+            result: RESULT_OK,
+            handle: 0,
+            length: 0,
+            argument: 0,
+            mode: MODE_READ,
+            buffer: [0; BUFFER_SIZE],
+            exit_code: None,
+        };
+    }
+
+    // argv as the program sees it, conventionally starting with the program name
+    pub fn set_args(&mut self, args: Vec<String>) {
+        self.args = args;
+    }
+
+    pub fn set_stdin(&mut self, stdin: Box<dyn Read>) {
+        self.streams[0] = Some(HostStream::Input(stdin));
+    }
+
+    pub fn set_stdout(&mut self, stdout: Box<dyn Write>) {
+        self.streams[1] = Some(HostStream::Output(stdout));
+    }
+
+    pub fn set_stderr(&mut self, stderr: Box<dyn Write>) {
+        self.streams[2] = Some(HostStream::Output(stderr));
+    }
+
+    pub fn get_exit_code(&self) -> Option<i32> {
+        return self.exit_code;
+    }
+
+    fn execute(&mut self, command: Byte) {
+        self.command = command;
+        self.result = match command {
+            COMMAND_OPEN => self.open(),
+            COMMAND_CLOSE => self.close(),
+            COMMAND_READ => self.read_stream(),
+            COMMAND_WRITE => self.write_stream(),
+            COMMAND_ARGC => {
+                self.argument = self.args.len().min(Byte::MAX as usize) as Byte;
+                RESULT_OK
+            }
+            COMMAND_ARGV => self.argv(),
+            COMMAND_EXIT => {
+                self.exit_code = Some(self.argument as i32);
+                RESULT_OK
+            }
+            _ => RESULT_BAD_COMMAND,
+        };
+    }
+
+    fn open(&mut self) -> Byte {
+        let path_length = self
+            .buffer
+            .iter()
+            .position(|c| *c == 0)
+            .unwrap_or(BUFFER_SIZE);
+        let path = String::from_utf8_lossy(&self.buffer[..path_length]).into_owned();
+        let mut options = OpenOptions::new();
+        match self.mode {
+            MODE_READ => options.read(true),
+            MODE_WRITE => options.write(true).create(true).truncate(true),
+            MODE_APPEND => options.append(true).create(true),
+            _ => return RESULT_BAD_COMMAND,
+        };
+
+        let file = match options.open(self.root.join(path)) {
+            Ok(file) => file,
+            Err(_) => return RESULT_IO_ERROR,
+        };
+        let free = self.streams.iter().position(|stream| stream.is_none());
+        let handle = match free {
+            Some(handle) => handle,
+            None if self.streams.len() < MAX_HANDLES => {
+                self.streams.push(None);
+                self.streams.len() - 1
+            }
+            None => return RESULT_NO_FREE_HANDLE,
+        };
+        self.streams[handle] = Some(HostStream::File(file));
+        self.handle = handle as Byte;
+
+        return RESULT_OK;
+    }
+
+    fn close(&mut self) -> Byte {
+        return match self.streams.get_mut(self.handle as usize) {
+            Some(stream @ Some(_)) => {
+                *stream = None;
+                RESULT_OK
+            }
+            _ => RESULT_BAD_HANDLE,
+        };
+    }
+
+    fn read_stream(&mut self) -> Byte {
+        let stream = match self.streams.get_mut(self.handle as usize) {
+            Some(Some(stream)) => stream,
+            _ => return RESULT_BAD_HANDLE,
+        };
+
+        return match stream.read(&mut self.buffer[..self.length as usize]) {
+            Ok(count) => {
+                self.length = count as Byte;
+                RESULT_OK
+            }
+            Err(_) => RESULT_IO_ERROR,
+        };
+    }
+
+    fn write_stream(&mut self) -> Byte {
+        let stream = match self.streams.get_mut(self.handle as usize) {
+            Some(Some(stream)) => stream,
+            _ => return RESULT_BAD_HANDLE,
+        };
+
+        return match stream.write(&self.buffer[..self.length as usize]) {
+            Ok(()) => RESULT_OK,
+            Err(_) => RESULT_IO_ERROR,
+        };
+    }
+
+    fn argv(&mut self) -> Byte {
+        let arg = match self.args.get(self.argument as usize) {
+            Some(arg) => arg.as_bytes(),
+            None => return RESULT_NO_ARGUMENT,
+        };
+        // the NUL terminator needs the last byte of the buffer
+        let length = arg.len().min(BUFFER_SIZE - 1);
+        self.buffer[..length].copy_from_slice(&arg[..length]);
+        self.buffer[length] = 0;
+        self.length = length as Byte;
+
+        return RESULT_OK;
+    }
+
+    fn register_mut(&mut self, addr: Word) -> Option<&mut Byte> {
+        if addr >= BUFFER_START {
+            return self.buffer.get_mut((addr - BUFFER_START) as usize);
+        }
+
+        return match addr {
+            COMMAND => Some(&mut self.command),
+            RESULT => Some(&mut self.result),
+            HANDLE => Some(&mut self.handle),
+            LENGTH => Some(&mut self.length),
+            ARGUMENT => Some(&mut self.argument),
+            MODE => Some(&mut self.mode),
+            _ => None,
+        };
+    }
+}
+
+// unused offsets below the buffer read 0 and ignore writes
+impl Bus for Paravirt {
+    fn read(&mut self, addr: Word) -> Byte {
+        return self.register_mut(addr).map_or(0, |register| *register);
+    }
+
+    fn write(&mut self, addr: Word, value: Byte) {
+        if addr == COMMAND {
+            self.execute(value);
+            return;
+        }
+
+        if let Some(register) = self.register_mut(addr) {
+            *register = value;
+        }
+    }
+
+    // a poked command is stored, not executed
+    fn poke(&mut self, addr: Word, value: Byte) {
+        if let Some(register) = self.register_mut(addr) {
+            *register = value;
+        }
+    }
+}
+
+impl Device for Paravirt {
+    fn tick(&mut self, _cycles: u64) {}
+
+    fn exit_code(&self) -> Option<i32> {
+        return self.exit_code;
+    }
+}
+
+#[cfg(test)]
+mod tests;
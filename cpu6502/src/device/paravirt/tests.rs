@@ -0,0 +1,187 @@
+#[cfg(test)]
+mod host_files {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use crate::{device::paravirt::Paravirt, memory::Bus};
+
+    fn root(name: &str) -> PathBuf {
+        let root =
+            std::env::temp_dir().join(format!("emu65-paravirt-{}-{name}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+
+        return root;
+    }
+
+    fn fill_buffer(paravirt: &mut Paravirt, bytes: &[u8]) {
+        bytes
+            .iter()
+            .enumerate()
+            .for_each(|(idx, c)| paravirt.write(0x0100 + idx as u16, *c));
+    }
+
+    fn open(paravirt: &mut Paravirt, path: &str, mode: u8) -> u8 {
+        fill_buffer(paravirt, format!("{path}\0").as_bytes());
+        paravirt.write(0x0005, mode);
+        paravirt.write(0x0000, 0x01);
+        assert_eq!(paravirt.read(0x0001), 0x00);
+
+        return paravirt.read(0x0002);
+    }
+
+    #[test]
+    fn should_write_and_read_back_host_file() {
+        let root = root("write-read");
+        let mut paravirt = Paravirt::new(&root);
+
+        let handle = open(&mut paravirt, "out.txt", 0x01);
+        fill_buffer(&mut paravirt, b"hello");
+        paravirt.write(0x0003, 5);
+        paravirt.write(0x0000, 0x04);
+        paravirt.write(0x0000, 0x02);
+        assert_eq!(handle, 3);
+        assert_eq!(fs::read(root.join("out.txt")).unwrap(), b"hello");
+
+        let handle = open(&mut paravirt, "out.txt", 0x00);
+        paravirt.write(0x0003, 0xFF);
+        paravirt.write(0x0000, 0x03);
+        assert_eq!(paravirt.read(0x0003), 5);
+        assert_eq!(paravirt.read(0x0100), b'h');
+        paravirt.write(0x0000, 0x03);
+        assert_eq!(paravirt.read(0x0003), 0);
+        paravirt.write(0x0002, handle);
+        paravirt.write(0x0000, 0x02);
+
+        assert_eq!(paravirt.read(0x0001), 0x00);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn should_report_missing_file_as_io_error() {
+        let root = root("missing");
+        let mut paravirt = Paravirt::new(&root);
+        fill_buffer(&mut paravirt, b"missing.txt\0");
+
+        paravirt.write(0x0000, 0x01);
+
+        assert_eq!(paravirt.read(0x0001), 0x02);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn should_report_bad_handle_and_unknown_command() {
+        let mut paravirt = Paravirt::new(".");
+
+        paravirt.write(0x0002, 9);
+        paravirt.write(0x0000, 0x03);
+        assert_eq!(paravirt.read(0x0001), 0x01);
+        paravirt.write(0x0000, 0x7F);
+
+        assert_eq!(paravirt.read(0x0001), 0x03);
+    }
+
+    #[test]
+    fn should_send_stdout_writes_to_configured_writer() {
+        let root = root("stdout");
+        let mut paravirt = Paravirt::new(&root);
+        paravirt.set_stdout(Box::new(fs::File::create(root.join("stdout")).unwrap()));
+
+        fill_buffer(&mut paravirt, b"ok\n");
+        paravirt.write(0x0002, 1);
+        paravirt.write(0x0003, 3);
+        paravirt.write(0x0000, 0x04);
+
+        assert_eq!(fs::read(root.join("stdout")).unwrap(), b"ok\n");
+        fs::remove_dir_all(root).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod arguments_and_exit {
+    use crate::{
+        device::{paravirt::Paravirt, Device},
+        memory::Bus,
+    };
+
+    #[test]
+    fn should_expose_argument_count_and_values() {
+        let mut paravirt = Paravirt::new(".");
+        paravirt.set_args(vec![String::from("prog"), String::from("-v")]);
+
+        paravirt.write(0x0000, 0x05);
+        assert_eq!(paravirt.read(0x0004), 2);
+        paravirt.write(0x0004, 1);
+        paravirt.write(0x0000, 0x06);
+
+        assert_eq!(paravirt.read(0x0003), 2);
+        assert_eq!(paravirt.read(0x0100), b'-');
+        assert_eq!(paravirt.read(0x0101), b'v');
+        assert_eq!(paravirt.read(0x0102), 0);
+    }
+
+    #[test]
+    fn should_report_missing_argument() {
+        let mut paravirt = Paravirt::new(".");
+
+        paravirt.write(0x0004, 0);
+        paravirt.write(0x0000, 0x06);
+
+        assert_eq!(paravirt.read(0x0001), 0x04);
+    }
+
+    #[test]
+    fn should_request_exit_with_code() {
+        let mut paravirt = Paravirt::new(".");
+        assert_eq!(paravirt.exit_code(), None);
+
+        paravirt.write(0x0004, 3);
+        paravirt.write(0x0000, 0x07);
+
+        assert_eq!(paravirt.get_exit_code(), Some(3));
+        assert_eq!(paravirt.exit_code(), Some(3));
+    }
+
+    #[test]
+    fn should_store_poked_command_without_executing_it() {
+        let mut paravirt = Paravirt::new(".");
+
+        paravirt.poke(0x0000, 0x07);
+
+        assert_eq!(paravirt.exit_code(), None);
+        assert_eq!(paravirt.peek(0x0000), 0x07);
+    }
+}
+
+#[cfg(test)]
+mod system_integration {
+    use crate::{
+        cpu::{emulation_event::EmulationEvent, CPU},
+        device::paravirt::Paravirt,
+        memory::{memory_map::MemoryMap, Ram64k},
+        system::System,
+    };
+
+    // LDA #42; STA $FE04; LDA #$07; STA $FE00; BRK
+    #[test]
+    fn should_end_system_run_with_exit_code() {
+        let mut ram = Ram64k::new();
+        ram.load(
+            &[
+                0xA9, 42, 0x8D, 0x04, 0xFE, 0xA9, 0x07, 0x8D, 0x00, 0xFE, 0x00,
+            ],
+            0x0200,
+        );
+        let mut map = MemoryMap::new();
+        map.map(0x0000..=0xFFFF, ram);
+        map.attach(0xFE00..=0xFFFF, Paravirt::new("."));
+        let mut cpu = CPU::with_memory(map);
+        cpu.set_program_counter(0x0200);
+        let mut system = System::new(cpu);
+
+        let event = system.execute_until_break();
+
+        assert_eq!(event, EmulationEvent::Exit(42));
+        assert_eq!(event.to_string(), "exited with code 42");
+        assert_eq!(system.get_cpu().get_program_counter(), 0x020A);
+    }
+}
@@ -0,0 +1,245 @@
+use super::{port::Port, Device};
+use crate::{
+    consts::{Byte, Word},
+    memory::Bus,
+};
+
+const CONTROL_C1_IRQ_ENABLED: Byte = 0x01;
+const CONTROL_C1_RISING_EDGE: Byte = 0x02;
+const CONTROL_PERIPHERAL_ACCESS: Byte = 0x04;
+// with C2 as an input: IRQ enable; as a handshake output: pulse instead of handshake; as a
+// manual output: the level of C2
+const CONTROL_C2_BIT3: Byte = 0x08;
+// with C2 as an input: active edge; as an output: manual instead of handshake
+const CONTROL_C2_BIT4: Byte = 0x10;
+const CONTROL_C2_OUTPUT: Byte = 0x20;
+const CONTROL_IRQ2: Byte = 0x40;
+const CONTROL_IRQ1: Byte = 0x80;
+// the flags are read only, writes only reach the lower six bits
+const CONTROL_WRITABLE: Byte = 0x3F;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum PiaPort {
+    A,
+    B,
+}
+
+// one side of the PIA: a port with its control register
+struct Side {
+    port: Port,
+    control: Byte,
+}
+
+impl Side {
+    fn new() -> Self {
+        return Side {
+            port: Port::new(),
+            control: 0,
+        };
+    }
+
+    fn read_register(&mut self) -> Byte {
+        if self.control & CONTROL_PERIPHERAL_ACCESS == 0 {
+            return self.port.data_direction;
+        }
+
+        self.control &= !(CONTROL_IRQ1 | CONTROL_IRQ2);
+        return self.port.pins();
+    }
+
+    fn peek_register(&self) -> Byte {
+        if self.control & CONTROL_PERIPHERAL_ACCESS == 0 {
+            return self.port.data_direction;
+        }
+
+        return self.port.pins();
+    }
+
+    fn write_register(&mut self, value: Byte) {
+        if self.control & CONTROL_PERIPHERAL_ACCESS == 0 {
+            self.port.data_direction = value;
+        } else {
+            self.port.output = value;
+        }
+    }
+
+    fn write_control(&mut self, value: Byte) {
+        self.control = (self.control & !CONTROL_WRITABLE) | (value & CONTROL_WRITABLE);
+        if self.is_c2_output() && self.control & CONTROL_C2_BIT4 != 0 {
+            self.port.c2_output = self.control & CONTROL_C2_BIT3 != 0;
+        }
+    }
+
+    fn is_c2_output(&self) -> bool {
+        return self.control & CONTROL_C2_OUTPUT != 0;
+    }
+
+    // C2 handshake and pulse outputs go low on an access to the data register, the
+    // read of port A or the write of port B
+    fn start_handshake(&mut self) {
+        if !self.is_c2_output() || self.control & CONTROL_C2_BIT4 != 0 {
+            return;
+        }
+        self.port
+            .start_handshake(self.control & CONTROL_C2_BIT3 != 0);
+    }
+
+    fn set_c1(&mut self, level: bool) {
+        let rising = self.control & CONTROL_C1_RISING_EDGE != 0;
+        if self.port.set_c1(level, rising) {
+            self.control |= CONTROL_IRQ1;
+            // the active C1 transition completes a handshake
+            if self.is_c2_output() && self.control & (CONTROL_C2_BIT4 | CONTROL_C2_BIT3) == 0 {
+                self.port.complete_handshake();
+            }
+        }
+    }
+
+    fn set_c2(&mut self, level: bool) {
+        let rising = self.control & CONTROL_C2_BIT4 != 0;
+        if self.port.set_c2(level, rising, !self.is_c2_output()) {
+            self.control |= CONTROL_IRQ2;
+        }
+    }
+
+    fn irq_asserted(&self) -> bool {
+        let irq1 = self.control & CONTROL_IRQ1 != 0 && self.control & CONTROL_C1_IRQ_ENABLED != 0;
+        let irq2 = self.control & CONTROL_IRQ2 != 0
+            && !self.is_c2_output()
+            && self.control & CONTROL_C2_BIT3 != 0;
+
+        return irq1 || irq2;
+    }
+}
+
+// MOS 6520 / 6521 peripheral interface adapter with port A, control A, port B and control B
+// at offsets 0-3. Bit 2 of a control register selects whether the port offset reaches the
+// data direction or the peripheral register. Both IRQ outputs drive the one line the device
+// reports, as on most boards.
+pub struct Pia {
+    port_a: Side,
+    port_b: Side,
+}
+
+impl Pia {
+    pub fn new() -> Self {
+        return Pia {
+            port_a: Side::new(),
+            port_b: Side::new(),
+        };
+    }
+
+    // levels driven onto the port by the outside world, seen on bits configured as inputs
+    pub fn set_input(&mut self, port: PiaPort, value: Byte) {
+        self.port_mut(port).port.input = value;
+    }
+
+    // the port as seen from outside, input bits read back what drives them
+    pub fn get_pins(&self, port: PiaPort) -> Byte {
+        return self.port(port).port.pins();
+    }
+
+    pub fn get_data_direction(&self, port: PiaPort) -> Byte {
+        return self.port(port).port.data_direction;
+    }
+
+    // CA1 / CB1 interrupt inputs
+    pub fn set_c1(&mut self, port: PiaPort, level: bool) {
+        self.port_mut(port).set_c1(level);
+    }
+
+    // CA2 / CB2, interrupt inputs unless programmed as outputs
+    pub fn set_c2(&mut self, port: PiaPort, level: bool) {
+        self.port_mut(port).set_c2(level);
+    }
+
+    // the level of CA2 / CB2 when programmed as an output
+    pub fn get_c2_output(&self, port: PiaPort) -> Option<bool> {
+        let side = self.port(port);
+
+        return side.is_c2_output().then_some(side.port.c2_output);
+    }
+
+    pub fn is_irq_asserted(&self, port: PiaPort) -> bool {
+        return self.port(port).irq_asserted();
+    }
+
+    fn port(&self, port: PiaPort) -> &Side {
+        return match port {
+            PiaPort::A => &self.port_a,
+            PiaPort::B => &self.port_b,
+        };
+    }
+
+    fn port_mut(&mut self, port: PiaPort) -> &mut Side {
+        return match port {
+            PiaPort::A => &mut self.port_a,
+            PiaPort::B => &mut self.port_b,
+        };
+    }
+}
+
+impl Default for Pia {
+    fn default() -> Self {
+        return Pia::new();
+    }
+}
+
+impl Bus for Pia {
+    fn read(&mut self, addr: Word) -> Byte {
+        return match addr & 0x03 {
+            0 => {
+                let is_peripheral = self.port_a.control & CONTROL_PERIPHERAL_ACCESS != 0;
+                if is_peripheral {
+                    self.port_a.start_handshake();
+                }
+                self.port_a.read_register()
+            }
+            1 => self.port_a.control,
+            2 => self.port_b.read_register(),
+            _ => self.port_b.control,
+        };
+    }
+
+    fn write(&mut self, addr: Word, value: Byte) {
+        match addr & 0x03 {
+            0 => self.port_a.write_register(value),
+            1 => self.port_a.write_control(value),
+            2 => {
+                let is_peripheral = self.port_b.control & CONTROL_PERIPHERAL_ACCESS != 0;
+                self.port_b.write_register(value);
+                if is_peripheral {
+                    self.port_b.start_handshake();
+                }
+            }
+            _ => self.port_b.write_control(value),
+        };
+    }
+
+    // reading a peripheral register clears the interrupt flags and starts a CA2 handshake,
+    // peeking does neither
+    fn peek(&mut self, addr: Word) -> Byte {
+        return match addr & 0x03 {
+            0 => self.port_a.peek_register(),
+            1 => self.port_a.control,
+            2 => self.port_b.peek_register(),
+            _ => self.port_b.control,
+        };
+    }
+}
+
+impl Device for Pia {
+    fn tick(&mut self, cycles: u64) {
+        if cycles > 0 {
+            self.port_a.port.tick();
+            self.port_b.port.tick();
+        }
+    }
+
+    fn irq_asserted(&self) -> bool {
+        return self.port_a.irq_asserted() || self.port_b.irq_asserted();
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,148 @@
+#[cfg(test)]
+mod ports {
+    use crate::{
+        device::pia::{Pia, PiaPort},
+        memory::Bus,
+    };
+
+    // port A all inputs, port B all outputs, both peripheral registers selected
+    fn pia() -> Pia {
+        let mut pia = Pia::new();
+        pia.write(0x0000, 0x00);
+        pia.write(0x0001, 0x04);
+        pia.write(0x0002, 0xFF);
+        pia.write(0x0003, 0x04);
+
+        return pia;
+    }
+
+    #[test]
+    fn should_reach_data_direction_register_until_peripheral_access_is_selected() {
+        let mut pia = Pia::new();
+
+        pia.write(0x0002, 0x0F);
+        pia.write(0x0003, 0x04);
+        pia.write(0x0002, 0xA5);
+
+        assert_eq!(pia.get_data_direction(PiaPort::B), 0x0F);
+        assert_eq!(pia.get_pins(PiaPort::B), 0xF5);
+        assert_eq!(pia.read(0x0002), 0xF5);
+    }
+
+    #[test]
+    fn should_read_external_levels_on_input_bits() {
+        let mut pia = pia();
+
+        pia.set_input(PiaPort::A, 0x3C);
+
+        assert_eq!(pia.read(0x0000), 0x3C);
+    }
+
+    #[test]
+    fn should_keep_flags_read_only() {
+        let mut pia = pia();
+
+        pia.write(0x0001, 0xFF);
+
+        assert_eq!(pia.read(0x0001), 0x3F);
+    }
+}
+
+#[cfg(test)]
+mod interrupts {
+    use crate::{
+        device::{
+            pia::{Pia, PiaPort},
+            Device,
+        },
+        memory::Bus,
+    };
+
+    #[test]
+    fn should_flag_active_c1_edge_and_raise_irq_when_enabled() {
+        let mut pia = Pia::new();
+        pia.write(0x0001, 0x05); // CA1 IRQ on falling edge, peripheral register
+
+        pia.set_c1(PiaPort::A, true);
+        assert!(!pia.irq_asserted());
+        pia.set_c1(PiaPort::A, false);
+
+        assert!(pia.irq_asserted());
+        assert_eq!(pia.peek(0x0001), 0x85);
+        pia.read(0x0000);
+        assert!(!pia.irq_asserted());
+        assert_eq!(pia.read(0x0001), 0x05);
+    }
+
+    #[test]
+    fn should_flag_c1_edge_without_irq_when_disabled() {
+        let mut pia = Pia::new();
+        pia.write(0x0003, 0x06); // CB1 rising edge, IRQ disabled
+
+        pia.set_c1(PiaPort::B, false);
+        pia.set_c1(PiaPort::B, true);
+
+        assert_eq!(pia.read(0x0003), 0x86);
+        assert!(!pia.irq_asserted());
+    }
+
+    #[test]
+    fn should_raise_irq_on_c2_input_edge() {
+        let mut pia = Pia::new();
+        pia.write(0x0003, 0x1C); // CB2 input, IRQ on rising edge
+
+        pia.set_c2(PiaPort::B, false);
+        pia.set_c2(PiaPort::B, true);
+
+        assert!(pia.is_irq_asserted(PiaPort::B));
+        assert!(!pia.is_irq_asserted(PiaPort::A));
+        assert_eq!(pia.read(0x0003), 0x5C);
+    }
+}
+
+#[cfg(test)]
+mod c2_output {
+    use crate::{
+        device::{
+            pia::{Pia, PiaPort},
+            Device,
+        },
+        memory::Bus,
+    };
+
+    #[test]
+    fn should_drive_c2_manually() {
+        let mut pia = Pia::new();
+
+        pia.write(0x0001, 0x34);
+        assert_eq!(pia.get_c2_output(PiaPort::A), Some(false));
+        pia.write(0x0001, 0x3C);
+        assert_eq!(pia.get_c2_output(PiaPort::A), Some(true));
+        pia.write(0x0001, 0x04);
+        assert_eq!(pia.get_c2_output(PiaPort::A), None);
+    }
+
+    #[test]
+    fn should_complete_read_handshake_on_c1_edge() {
+        let mut pia = Pia::new();
+        pia.write(0x0001, 0x24); // CA2 read handshake, CA1 falling edge
+
+        pia.read(0x0000);
+        assert_eq!(pia.get_c2_output(PiaPort::A), Some(false));
+        pia.set_c1(PiaPort::A, false);
+
+        assert_eq!(pia.get_c2_output(PiaPort::A), Some(true));
+    }
+
+    #[test]
+    fn should_pulse_cb2_for_one_cycle_after_write() {
+        let mut pia = Pia::new();
+        pia.write(0x0003, 0x2C); // CB2 write pulse
+
+        pia.write(0x0002, 0x42);
+        assert_eq!(pia.get_c2_output(PiaPort::B), Some(false));
+        pia.tick(1);
+
+        assert_eq!(pia.get_c2_output(PiaPort::B), Some(true));
+    }
+}
@@ -0,0 +1,71 @@
+use crate::consts::Byte;
+
+// one port of a 6520 / 6522: the output and data direction registers, the lines driven from
+// outside and the C1 / C2 control lines. What the control lines do is up to the chip, the
+// port only tracks their levels and reports active transitions.
+pub struct Port {
+    pub output: Byte,
+    pub data_direction: Byte,
+    pub input: Byte,
+    c1: bool,
+    c2: bool,
+    // level of a handshake, pulse or manual output on C2
+    pub c2_output: bool,
+    // a C2 pulse lasts until the next cycle
+    c2_pulse: bool,
+}
+
+impl Port {
+    pub fn new() -> Self {
+        return Port {
+            output: 0,
+            data_direction: 0,
+            input: 0xFF,
+            c1: true,
+            c2: true,
+            c2_output: true,
+            c2_pulse: false,
+        };
+    }
+
+    // output bits show the output register, input bits the lines driven from outside
+    pub fn pins(&self) -> Byte {
+        return (self.output & self.data_direction) | (self.input & !self.data_direction);
+    }
+
+    // true on the transition to the active level, `rising` or falling
+    pub fn set_c1(&mut self, level: bool, rising: bool) -> bool {
+        let edge = level != self.c1 && level == rising;
+        self.c1 = level;
+
+        return edge;
+    }
+
+    // as C1, though a C2 programmed as an output sees no edges
+    pub fn set_c2(&mut self, level: bool, rising: bool, is_input: bool) -> bool {
+        let edge = is_input && level != self.c2 && level == rising;
+        self.c2 = level;
+
+        return edge;
+    }
+
+    // a handshake output stays low until the active C1 transition, a pulse for one cycle
+    pub fn start_handshake(&mut self, pulse: bool) {
+        self.c2_output = false;
+        self.c2_pulse = pulse;
+    }
+
+    pub fn complete_handshake(&mut self) {
+        self.c2_output = true;
+    }
+
+    pub fn tick(&mut self) {
+        if self.c2_pulse {
+            self.c2_output = true;
+            self.c2_pulse = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
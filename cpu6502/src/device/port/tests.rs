@@ -0,0 +1,49 @@
+#[cfg(test)]
+mod port {
+    use crate::device::port::Port;
+
+    #[test]
+    fn should_show_output_register_on_output_bits_and_input_lines_on_the_rest() {
+        let mut port = Port::new();
+        port.output = 0xA5;
+        port.data_direction = 0xF0;
+        port.input = 0x3C;
+
+        assert_eq!(port.pins(), 0xAC);
+    }
+
+    #[test]
+    fn should_report_only_transitions_to_active_level() {
+        let mut port = Port::new();
+
+        assert!(!port.set_c1(true, false));
+        assert!(port.set_c1(false, false));
+        assert!(!port.set_c1(false, false));
+        assert!(port.set_c1(true, true));
+    }
+
+    #[test]
+    fn should_ignore_c2_edges_while_it_is_an_output() {
+        let mut port = Port::new();
+
+        assert!(!port.set_c2(false, false, false));
+        assert!(!port.set_c2(true, true, false));
+        assert!(port.set_c2(false, false, true));
+    }
+
+    #[test]
+    fn should_end_pulse_after_one_cycle_and_hold_handshake_until_completed() {
+        let mut port = Port::new();
+
+        port.start_handshake(true);
+        assert!(!port.c2_output);
+        port.tick();
+        assert!(port.c2_output);
+
+        port.start_handshake(false);
+        port.tick();
+        assert!(!port.c2_output);
+        port.complete_handshake();
+        assert!(port.c2_output);
+    }
+}
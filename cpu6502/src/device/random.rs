@@ -0,0 +1,55 @@
+use super::Device;
+use crate::{
+    consts::{Byte, Word},
+    memory::Bus,
+};
+
+// random byte register, e.g. the one easy6502 programs such as snake read at $FE. Every read at
+// any offset returns the next byte of a seeded xorshift64 sequence, so runs repeat exactly
+// for the same seed; peeking shows the last byte read. Writes are ignored.
+pub struct RandomGenerator {
+    state: u64,
+    last_value: Byte,
+}
+
+impl RandomGenerator {
+    pub fn new(seed: u64) -> Self {
+        return RandomGenerator {
+            // xorshift never leaves zero
+            state: seed.max(1),
+            last_value: 0,
+        };
+    }
+
+    pub fn set_seed(&mut self, seed: u64) {
+        self.state = seed.max(1);
+    }
+
+    fn next_value(&mut self) -> Byte {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+
+        return (self.state >> 32) as Byte;
+    }
+}
+
+impl Bus for RandomGenerator {
+    fn read(&mut self, _addr: Word) -> Byte {
+        self.last_value = self.next_value();
+        return self.last_value;
+    }
+
+    fn write(&mut self, _addr: Word, _value: Byte) {}
+
+    fn peek(&mut self, _addr: Word) -> Byte {
+        return self.last_value;
+    }
+}
+
+impl Device for RandomGenerator {
+    fn tick(&mut self, _cycles: u64) {}
+}
+
+#[cfg(test)]
+mod tests;
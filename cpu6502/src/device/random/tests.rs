@@ -0,0 +1,88 @@
+#[cfg(test)]
+mod sequence {
+    use crate::{device::random::RandomGenerator, memory::Bus};
+
+    fn values(generator: &mut RandomGenerator, count: usize) -> Vec<u8> {
+        return (0..count).map(|_| generator.read(0x0000)).collect();
+    }
+
+    #[test]
+    fn should_repeat_sequence_for_same_seed() {
+        let mut first = RandomGenerator::new(7);
+        let mut second = RandomGenerator::new(7);
+
+        assert_eq!(values(&mut first, 16), values(&mut second, 16));
+    }
+
+    #[test]
+    fn should_differ_between_seeds_and_reads() {
+        let mut first = RandomGenerator::new(7);
+        let mut second = RandomGenerator::new(8);
+
+        let sequence = values(&mut first, 16);
+
+        assert_ne!(sequence, values(&mut second, 16));
+        assert!(sequence.windows(2).any(|pair| pair[0] != pair[1]));
+    }
+
+    #[test]
+    fn should_restart_sequence_when_reseeded() {
+        let mut generator = RandomGenerator::new(7);
+        let sequence = values(&mut generator, 4);
+
+        generator.set_seed(7);
+
+        assert_eq!(values(&mut generator, 4), sequence);
+    }
+
+    #[test]
+    fn should_peek_last_value_without_advancing() {
+        let mut generator = RandomGenerator::new(7);
+        let value = generator.read(0x0000);
+
+        assert_eq!(generator.peek(0x0000), value);
+        assert_eq!(generator.peek(0x0000), value);
+        assert_ne!(generator.read(0x0000), value);
+    }
+
+    #[test]
+    fn should_ignore_writes() {
+        let mut generator = RandomGenerator::new(7);
+        let mut reference = RandomGenerator::new(7);
+
+        generator.write(0x0000, 0x00);
+
+        assert_eq!(generator.read(0x0000), reference.read(0x0000));
+    }
+}
+
+#[cfg(test)]
+mod system_integration {
+    use crate::{
+        cpu::CPU,
+        device::random::RandomGenerator,
+        memory::{memory_map::MemoryMap, Bus, Ram64k},
+    };
+
+    // LDA $FE; STA $10; LDA $FE; STA $11; BRK
+    #[test]
+    fn should_serve_random_bytes_at_zero_page_address() {
+        let mut ram = Ram64k::new();
+        ram.load(
+            &[0xA5, 0xFE, 0x85, 0x10, 0xA5, 0xFE, 0x85, 0x11, 0x00],
+            0x0600,
+        );
+        let mut map = MemoryMap::new();
+        map.map(0x0000..=0xFFFF, ram);
+        map.attach(0x00FE..=0x00FE, RandomGenerator::new(1234));
+        let mut cpu = CPU::with_memory(map);
+        cpu.set_program_counter(0x0600);
+        let mut reference = RandomGenerator::new(1234);
+
+        cpu.execute_until_break();
+
+        let map = cpu.get_memory_mut();
+        assert_eq!(map.read(0x0010), reference.read(0x0000));
+        assert_eq!(map.read(0x0011), reference.read(0x0000));
+    }
+}
@@ -0,0 +1,210 @@
+use super::Device;
+use crate::{
+    consts::{Byte, Word},
+    memory::Bus,
+};
+
+const RAM_SIZE: usize = 128;
+// offsets with this bit set reach the I/O registers, as if it were wired to RS
+const IO_SELECT: Word = 0x80;
+
+const FLAG_TIMER: Byte = 0x80;
+const FLAG_PA7: Byte = 0x40;
+
+const TIMER_INTERVALS: [u16; 4] = [1, 8, 64, 1024];
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum RiotPort {
+    A,
+    B,
+}
+
+// MOS 6532 RAM-I/O-timer. Offsets $00-$7F reach the 128 bytes of RAM and $80-$FF the I/O
+// registers, decoded by the low five address bits as on the chip:
+//  - x0-x3: port A, its data direction, port B, its data direction
+//  - write x4-x7: PA7 edge detection, bit 0 selects the rising edge and bit 1 the IRQ
+//  - write 1x4-1x7: start the timer at 1, 8, 64 or 1024 cycles per count, bit 3 enables its IRQ
+//  - read x4/xC: the timer, bit 3 enables its IRQ; read x5/x7: interrupt flags
+pub struct Riot {
+    ram: [Byte; RAM_SIZE],
+    output: [Byte; 2],
+    data_direction: [Byte; 2],
+    input: [Byte; 2],
+    timer: Byte,
+    // cycles per count, 1 once the timer expired
+    interval: u16,
+    // cycles until the next count
+    prescaler: u16,
+    timer_irq_enabled: bool,
+    pa7_rising_edge: bool,
+    pa7_irq_enabled: bool,
+    flags: Byte,
+}
+
+impl Riot {
+    pub fn new() -> Self {
+        return Riot {
+            ram: [0; RAM_SIZE],
+            output: [0; 2],
+            data_direction: [0; 2],
+            input: [0xFF; 2],
+            timer: 0,
+            interval: TIMER_INTERVALS[3],
+            prescaler: TIMER_INTERVALS[3],
+            timer_irq_enabled: false,
+            pa7_rising_edge: false,
+            pa7_irq_enabled: false,
+            flags: 0,
+        };
+    }
+
+    // levels driven onto the port from outside, seen on bits configured as inputs; a change
+    // of PA7 in the selected direction sets its interrupt flag
+    pub fn set_input(&mut self, port: RiotPort, value: Byte) {
+        let previous_pa7 = self.get_pins(RiotPort::A) & 0x80 != 0;
+        self.input[port as usize] = value;
+        let pa7 = self.get_pins(RiotPort::A) & 0x80 != 0;
+        if pa7 != previous_pa7 && pa7 == self.pa7_rising_edge {
+            self.flags |= FLAG_PA7;
+        }
+    }
+
+    pub fn get_pins(&self, port: RiotPort) -> Byte {
+        let idx = port as usize;
+
+        return (self.output[idx] & self.data_direction[idx])
+            | (self.input[idx] & !self.data_direction[idx]);
+    }
+
+    pub fn get_data_direction(&self, port: RiotPort) -> Byte {
+        return self.data_direction[port as usize];
+    }
+
+    pub fn get_ram(&self) -> &[Byte] {
+        return &self.ram;
+    }
+
+    pub fn get_timer(&self) -> Byte {
+        return self.timer;
+    }
+
+    fn start_timer(&mut self, addr: Word, value: Byte) {
+        self.timer = value;
+        self.interval = TIMER_INTERVALS[(addr & 0x03) as usize];
+        // the first count comes on the cycle after the write
+        self.prescaler = 1;
+        self.timer_irq_enabled = addr & 0x08 != 0;
+        self.flags &= !FLAG_TIMER;
+    }
+
+    fn count(&mut self) {
+        self.prescaler -= 1;
+        if self.prescaler > 0 {
+            return;
+        }
+
+        if self.timer == 0 {
+            // once expired the timer keeps counting down on every cycle
+            self.flags |= FLAG_TIMER;
+            self.interval = 1;
+        }
+        self.timer = self.timer.wrapping_sub(1);
+        self.prescaler = self.interval;
+    }
+
+    fn read_io(&mut self, addr: Word) -> Byte {
+        if addr & 0x04 == 0 {
+            return self.read_port(addr);
+        }
+        if addr & 0x01 == 0 {
+            self.timer_irq_enabled = addr & 0x08 != 0;
+            self.flags &= !FLAG_TIMER;
+            return self.timer;
+        }
+
+        let flags = self.flags;
+        self.flags &= !FLAG_PA7;
+        return flags;
+    }
+
+    fn read_port(&self, addr: Word) -> Byte {
+        return match addr & 0x03 {
+            0 => self.get_pins(RiotPort::A),
+            1 => self.data_direction[0],
+            2 => self.get_pins(RiotPort::B),
+            _ => self.data_direction[1],
+        };
+    }
+
+    fn write_io(&mut self, addr: Word, value: Byte) {
+        if addr & 0x04 == 0 {
+            match addr & 0x03 {
+                0 => self.output[0] = value,
+                1 => self.data_direction[0] = value,
+                2 => self.output[1] = value,
+                _ => self.data_direction[1] = value,
+            };
+        } else if addr & 0x10 != 0 {
+            self.start_timer(addr, value);
+        } else {
+            self.pa7_rising_edge = addr & 0x01 != 0;
+            self.pa7_irq_enabled = addr & 0x02 != 0;
+        }
+    }
+}
+
+impl Default for Riot {
+    fn default() -> Self {
+        return Riot::new();
+    }
+}
+
+impl Bus for Riot {
+    fn read(&mut self, addr: Word) -> Byte {
+        if addr & IO_SELECT == 0 {
+            return self.ram[addr as usize % RAM_SIZE];
+        }
+
+        return self.read_io(addr);
+    }
+
+    fn write(&mut self, addr: Word, value: Byte) {
+        if addr & IO_SELECT == 0 {
+            self.ram[addr as usize % RAM_SIZE] = value;
+            return;
+        }
+
+        self.write_io(addr, value);
+    }
+
+    // reading the timer or the flags acknowledges interrupts, peeking does not
+    fn peek(&mut self, addr: Word) -> Byte {
+        if addr & IO_SELECT == 0 {
+            return self.ram[addr as usize % RAM_SIZE];
+        }
+        if addr & 0x04 == 0 {
+            return self.read_port(addr);
+        }
+
+        return match addr & 0x01 {
+            0 => self.timer,
+            _ => self.flags,
+        };
+    }
+}
+
+impl Device for Riot {
+    fn tick(&mut self, cycles: u64) {
+        for _ in 0..cycles {
+            self.count();
+        }
+    }
+
+    fn irq_asserted(&self) -> bool {
+        return (self.flags & FLAG_TIMER != 0 && self.timer_irq_enabled)
+            || (self.flags & FLAG_PA7 != 0 && self.pa7_irq_enabled);
+    }
+}
+
+#[cfg(test)]
+mod tests;
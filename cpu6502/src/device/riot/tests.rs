@@ -0,0 +1,124 @@
+#[cfg(test)]
+mod ram_and_ports {
+    use crate::{
+        device::riot::{Riot, RiotPort},
+        memory::Bus,
+    };
+
+    #[test]
+    fn should_store_128_bytes_of_ram_below_io_registers() {
+        let mut riot = Riot::new();
+
+        riot.write(0x007F, 0x42);
+        riot.write(0x0080, 0xFF);
+
+        assert_eq!(riot.read(0x007F), 0x42);
+        assert_eq!(riot.get_ram()[0x7F], 0x42);
+        assert_eq!(riot.get_data_direction(RiotPort::A), 0x00);
+        assert_eq!(riot.get_pins(RiotPort::A), 0xFF);
+    }
+
+    #[test]
+    fn should_mix_outputs_and_inputs_on_port() {
+        let mut riot = Riot::new();
+        riot.write(0x0083, 0xF0);
+        riot.write(0x0082, 0xA5);
+
+        riot.set_input(RiotPort::B, 0x3C);
+
+        assert_eq!(riot.read(0x0082), 0xAC);
+    }
+}
+
+#[cfg(test)]
+mod timer {
+    use crate::{
+        device::{riot::Riot, Device},
+        memory::Bus,
+    };
+
+    #[test]
+    fn should_count_down_once_per_interval() {
+        let mut riot = Riot::new();
+        riot.write(0x0095, 10); // 8 cycles per count
+
+        riot.tick(1);
+        assert_eq!(riot.read(0x0084), 9);
+        riot.tick(8 * 3);
+
+        assert_eq!(riot.read(0x0084), 6);
+    }
+
+    #[test]
+    fn should_flag_expiry_and_count_every_cycle_afterwards() {
+        let mut riot = Riot::new();
+        riot.write(0x0096, 1); // 64 cycles per count
+
+        riot.tick(1 + 63);
+        assert_eq!(riot.get_timer(), 0x00);
+        assert_eq!(riot.peek(0x0085) & 0x80, 0x00);
+        riot.tick(1);
+
+        assert_eq!(riot.get_timer(), 0xFF);
+        assert_eq!(riot.peek(0x0085) & 0x80, 0x80);
+        riot.tick(3);
+        assert_eq!(riot.get_timer(), 0xFC);
+    }
+
+    #[test]
+    fn should_raise_irq_on_expiry_when_enabled_until_timer_is_read() {
+        let mut riot = Riot::new();
+        riot.write(0x009C, 2); // 1 cycle per count, IRQ enabled
+
+        riot.tick(3);
+        assert!(riot.irq_asserted());
+        riot.read(0x008C);
+
+        assert!(!riot.irq_asserted());
+    }
+
+    #[test]
+    fn should_not_raise_irq_when_disabled() {
+        let mut riot = Riot::new();
+        riot.write(0x0094, 0);
+
+        riot.tick(2);
+
+        assert!(!riot.irq_asserted());
+        assert_eq!(riot.read(0x0085) & 0x80, 0x80);
+    }
+}
+
+#[cfg(test)]
+mod pa7_edge {
+    use crate::{
+        device::{
+            riot::{Riot, RiotPort},
+            Device,
+        },
+        memory::Bus,
+    };
+
+    #[test]
+    fn should_flag_selected_pa7_edge_and_clear_it_on_flag_read() {
+        let mut riot = Riot::new();
+        riot.write(0x0087, 0); // rising edge, IRQ enabled
+        riot.set_input(RiotPort::A, 0x00);
+
+        riot.set_input(RiotPort::A, 0x80);
+
+        assert!(riot.irq_asserted());
+        assert_eq!(riot.read(0x0085), 0x40);
+        assert!(!riot.irq_asserted());
+    }
+
+    #[test]
+    fn should_ignore_opposite_pa7_edge() {
+        let mut riot = Riot::new();
+        riot.write(0x0085, 0); // rising edge, IRQ disabled
+
+        riot.set_input(RiotPort::A, 0x7F);
+
+        assert_eq!(riot.read(0x0085), 0x00);
+    }
+}
@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod shared {
+    use std::cell::RefCell;
+
+    use crate::{
+        consts::{Byte, Word},
+        device::Device,
+        memory::Bus,
+    };
+
+    // counts cycles, register 0 holds the low byte of the count
+    #[derive(Default)]
+    struct CycleCounter {
+        cycles: u64,
+    }
+
+    impl Bus for CycleCounter {
+        fn read(&mut self, _addr: Word) -> Byte {
+            return self.cycles as Byte;
+        }
+
+        fn write(&mut self, _addr: Word, _value: Byte) {
+            self.cycles = 0;
+        }
+    }
+
+    impl Device for CycleCounter {
+        fn tick(&mut self, cycles: u64) {
+            self.cycles += cycles;
+        }
+    }
+
+    #[test]
+    fn should_tick_device_behind_shared_reference() {
+        let counter = RefCell::new(CycleCounter::default());
+        let mut handle = &counter;
+
+        handle.tick(3);
+        handle.tick(4);
+
+        assert_eq!(handle.read(0x0000), 7);
+        assert_eq!(counter.borrow().cycles, 7);
+        assert!(!handle.irq_asserted());
+    }
+}
+
+#[cfg(test)]
+mod masked {
+    use crate::{
+        device::{timer::Timer, Device},
+        memory::{Bus, MaskedMemory},
+    };
+
+    #[test]
+    fn should_tick_device_behind_partial_decoding() {
+        let mut timer = MaskedMemory::new(Timer::new(), 13);
+
+        timer.write(0x2000, 2);
+        timer.write(0xE002, 0x05);
+        timer.tick(2);
+
+        assert!(timer.irq_asserted());
+        assert_eq!(timer.read(0x6003), 0x80);
+    }
+}
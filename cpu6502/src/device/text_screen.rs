@@ -0,0 +1,124 @@
+use std::fmt;
+use std::io::{self, Write};
+
+use super::Device;
+use crate::{
+    consts::{Byte, Word},
+    memory::Bus,
+};
+
+const DEFAULT_COLUMNS: usize = 40;
+const DEFAULT_ROWS: usize = 25;
+
+// moves the cursor to the top left corner of the terminal
+const ANSI_CURSOR_HOME: &str = "\x1b[H";
+
+// screen memory of a text mode display, one byte per character cell row by row from offset 0.
+// Cells hold ASCII; anything unprintable shows as a space. As a string the screen has one
+// line per row with trailing spaces trimmed, so tests can compare it against plain text.
+pub struct TextScreen {
+    columns: usize,
+    rows: usize,
+    cells: Vec<Byte>,
+}
+
+impl TextScreen {
+    pub fn new(columns: usize, rows: usize) -> Self {
+        return TextScreen {
+            columns,
+            rows,
+            cells: vec![b' '; columns * rows],
+        };
+    }
+
+    pub fn get_columns(&self) -> usize {
+        return self.columns;
+    }
+
+    pub fn get_rows(&self) -> usize {
+        return self.rows;
+    }
+
+    pub fn get_cells(&self) -> &[Byte] {
+        return &self.cells;
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.fill(b' ');
+    }
+
+    // the row as printable text, trailing spaces trimmed
+    pub fn get_row(&self, row: usize) -> String {
+        let start = row * self.columns;
+        let text: String = self.cells[start..start + self.columns]
+            .iter()
+            .map(|cell| printable(*cell))
+            .collect();
+
+        return text.trim_end().to_string();
+    }
+
+    // redraws the whole screen over what the terminal showed before, every row at full width
+    pub fn render_ansi(&self, writer: &mut impl Write) -> io::Result<()> {
+        write!(writer, "{ANSI_CURSOR_HOME}")?;
+        for row in self.cells.chunks(self.columns) {
+            let text: String = row.iter().map(|cell| printable(*cell)).collect();
+            write!(writer, "{text}\r\n")?;
+        }
+
+        return writer.flush();
+    }
+
+    fn cell_index(&self, addr: Word) -> Option<usize> {
+        let idx = addr as usize;
+
+        return (idx < self.cells.len()).then_some(idx);
+    }
+}
+
+impl Default for TextScreen {
+    fn default() -> Self {
+        return TextScreen::new(DEFAULT_COLUMNS, DEFAULT_ROWS);
+    }
+}
+
+fn printable(cell: Byte) -> char {
+    if (0x20..0x7F).contains(&cell) {
+        return cell as char;
+    }
+
+    return ' ';
+}
+
+impl fmt::Display for TextScreen {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in 0..self.rows {
+            writeln!(f, "{}", self.get_row(row))?;
+        }
+
+        return Ok(());
+    }
+}
+
+// offsets past the last cell are open, they read 0 and ignore writes
+impl Bus for TextScreen {
+    fn read(&mut self, addr: Word) -> Byte {
+        return match self.cell_index(addr) {
+            Some(idx) => self.cells[idx],
+            None => 0,
+        };
+    }
+
+    fn write(&mut self, addr: Word, value: Byte) {
+        if let Some(idx) = self.cell_index(addr) {
+            self.cells[idx] = value;
+        }
+    }
+}
+
+impl Device for TextScreen {
+    fn tick(&mut self, _cycles: u64) {}
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,93 @@
+#[cfg(test)]
+mod rendering {
+    use crate::{device::text_screen::TextScreen, memory::Bus};
+
+    #[test]
+    fn should_start_blank_with_40_by_25_cells() {
+        let screen = TextScreen::default();
+
+        assert_eq!(screen.get_columns(), 40);
+        assert_eq!(screen.get_rows(), 25);
+        assert_eq!(screen.to_string(), "\n".repeat(25));
+    }
+
+    #[test]
+    fn should_render_rows_with_trailing_spaces_trimmed() {
+        let mut screen = TextScreen::new(8, 2);
+
+        b"HI"
+            .iter()
+            .enumerate()
+            .for_each(|(idx, c)| screen.write(idx as u16 + 9, *c));
+
+        assert_eq!(screen.get_row(1), " HI");
+        assert_eq!(screen.to_string(), "\n HI\n");
+    }
+
+    #[test]
+    fn should_show_unprintable_cells_as_spaces() {
+        let mut screen = TextScreen::new(4, 1);
+
+        screen.write(0x0000, 0x07);
+        screen.write(0x0001, b'A');
+
+        assert_eq!(screen.get_row(0), " A");
+        assert_eq!(screen.read(0x0000), 0x07);
+    }
+
+    #[test]
+    fn should_ignore_writes_past_last_cell() {
+        let mut screen = TextScreen::new(4, 1);
+
+        screen.write(0x0004, b'A');
+
+        assert_eq!(screen.read(0x0004), 0);
+        assert_eq!(screen.get_cells(), b"    ");
+    }
+
+    #[test]
+    fn should_render_full_rows_after_cursor_home_for_terminal() {
+        let mut screen = TextScreen::new(3, 2);
+        screen.write(0x0000, b'A');
+        let mut terminal = Vec::new();
+
+        screen.render_ansi(&mut terminal).unwrap();
+
+        assert_eq!(terminal, b"\x1b[HA  \r\n   \r\n");
+    }
+}
+
+#[cfg(test)]
+mod system_integration {
+    use std::cell::RefCell;
+
+    use crate::{
+        cpu::CPU,
+        device::text_screen::TextScreen,
+        memory::{memory_map::MemoryMap, Ram64k},
+    };
+
+    // loop: LDA $0300,X; BEQ done; STA $0428,X; INX; BNE loop; done: BRK
+    #[test]
+    fn should_show_hello_world_written_to_screen_memory() {
+        let screen = RefCell::new(TextScreen::default());
+        let mut ram = Ram64k::new();
+        ram.load(
+            &[
+                0xBD, 0x00, 0x03, 0xF0, 0x06, 0x9D, 0x28, 0x04, 0xE8, 0xD0, 0xF5, 0x00,
+            ],
+            0x0200,
+        );
+        ram.load(b"HELLO WORLD\0", 0x0300);
+        let mut map = MemoryMap::new();
+        map.map(0x0000..=0xFFFF, ram);
+        map.attach(0x0400..=0x07E7, &screen);
+        let mut cpu = CPU::with_memory(map);
+        cpu.set_program_counter(0x0200);
+
+        cpu.execute_until_break();
+
+        assert_eq!(screen.borrow().get_row(0), "");
+        assert_eq!(screen.borrow().get_row(1), "HELLO WORLD");
+    }
+}
@@ -0,0 +1,133 @@
+use super::Device;
+use crate::{
+    consts::{Byte, Word},
+    memory::Bus,
+};
+
+const PERIOD_LOW: Word = 0;
+const PERIOD_HIGH: Word = 1;
+const CONTROL: Word = 2;
+const STATUS: Word = 3;
+const COUNTER_LOW: Word = 4;
+const COUNTER_HIGH: Word = 5;
+
+const CONTROL_ENABLED: Byte = 0x01;
+const CONTROL_PERIODIC: Byte = 0x02;
+const CONTROL_IRQ_ENABLED: Byte = 0x04;
+
+const STATUS_EXPIRED: Byte = 0x80;
+
+// cycle-counting timer for custom machines, with registers at offsets 0-7:
+//  - 0-1: period in cycles, little endian
+//  - 2: control, bit 0 starts the timer, bit 1 reloads it on expiry, bit 2 enables its IRQ
+//  - 3: status, bit 7 set on expiry; reading or writing it acknowledges the expiry
+//  - 4-5: the counter, read only
+// Setting the enable bit loads the counter with the period. A one-shot timer clears the
+// enable bit when it expires, a zero period keeps the timer from counting.
+#[derive(Default)]
+pub struct Timer {
+    period: u16,
+    counter: u16,
+    control: Byte,
+    status: Byte,
+}
+
+impl Timer {
+    pub fn new() -> Self {
+        return Timer::default();
+    }
+
+    pub fn get_period(&self) -> u16 {
+        return self.period;
+    }
+
+    pub fn get_counter(&self) -> u16 {
+        return self.counter;
+    }
+
+    pub fn is_running(&self) -> bool {
+        return self.control & CONTROL_ENABLED != 0 && self.period > 0;
+    }
+
+    pub fn has_expired(&self) -> bool {
+        return self.status & STATUS_EXPIRED != 0;
+    }
+
+    fn write_control(&mut self, value: Byte) {
+        let starting = value & CONTROL_ENABLED != 0 && self.control & CONTROL_ENABLED == 0;
+        self.control = value;
+        if starting {
+            self.counter = self.period;
+        }
+    }
+
+    fn count(&mut self, cycles: u64) {
+        let mut cycles = cycles;
+        while cycles > 0 && self.is_running() {
+            let elapsed = cycles.min(self.counter as u64);
+            self.counter -= elapsed as u16;
+            cycles -= elapsed;
+            if self.counter > 0 {
+                return;
+            }
+
+            self.status |= STATUS_EXPIRED;
+            if self.control & CONTROL_PERIODIC != 0 {
+                self.counter = self.period;
+            } else {
+                self.control &= !CONTROL_ENABLED;
+            }
+        }
+    }
+
+    fn register(&self, addr: Word) -> Byte {
+        return match addr & 0x07 {
+            PERIOD_LOW => self.period as Byte,
+            PERIOD_HIGH => (self.period >> 8) as Byte,
+            CONTROL => self.control,
+            STATUS => self.status,
+            COUNTER_LOW => self.counter as Byte,
+            COUNTER_HIGH => (self.counter >> 8) as Byte,
+            _ => 0,
+        };
+    }
+}
+
+impl Bus for Timer {
+    fn read(&mut self, addr: Word) -> Byte {
+        let value = self.register(addr);
+        if addr & 0x07 == STATUS {
+            self.status &= !STATUS_EXPIRED;
+        }
+
+        return value;
+    }
+
+    fn write(&mut self, addr: Word, value: Byte) {
+        match addr & 0x07 {
+            PERIOD_LOW => self.period = (self.period & 0xFF00) | value as u16,
+            PERIOD_HIGH => self.period = (self.period & 0x00FF) | ((value as u16) << 8),
+            CONTROL => self.write_control(value),
+            STATUS => self.status &= !STATUS_EXPIRED,
+            _ => (),
+        };
+    }
+
+    // reading the status acknowledges the expiry, peeking does not
+    fn peek(&mut self, addr: Word) -> Byte {
+        return self.register(addr);
+    }
+}
+
+impl Device for Timer {
+    fn tick(&mut self, cycles: u64) {
+        self.count(cycles);
+    }
+
+    fn irq_asserted(&self) -> bool {
+        return self.has_expired() && self.control & CONTROL_IRQ_ENABLED != 0;
+    }
+}
+
+#[cfg(test)]
+mod tests;
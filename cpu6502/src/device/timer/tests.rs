@@ -0,0 +1,155 @@
+#[cfg(test)]
+mod counting {
+    use crate::{
+        device::{timer::Timer, Device},
+        memory::Bus,
+    };
+
+    fn timer(period: u16, control: u8) -> Timer {
+        let mut timer = Timer::new();
+        timer.write(0x0000, period as u8);
+        timer.write(0x0001, (period >> 8) as u8);
+        timer.write(0x0002, control);
+
+        return timer;
+    }
+
+    #[test]
+    fn should_load_counter_with_period_when_started() {
+        let mut timer = timer(0x1234, 0x01);
+
+        timer.tick(0x34);
+
+        assert_eq!(timer.get_counter(), 0x1200);
+        assert_eq!(timer.read(0x0004), 0x00);
+        assert_eq!(timer.read(0x0005), 0x12);
+    }
+
+    #[test]
+    fn should_stop_one_shot_timer_on_expiry() {
+        let mut timer = timer(10, 0x01);
+
+        timer.tick(9);
+        assert!(!timer.has_expired());
+        timer.tick(5);
+
+        assert!(timer.has_expired());
+        assert!(!timer.is_running());
+        assert_eq!(timer.get_counter(), 0);
+        assert_eq!(timer.read(0x0002), 0x00);
+    }
+
+    #[test]
+    fn should_reload_periodic_timer_on_expiry() {
+        let mut timer = timer(10, 0x03);
+
+        timer.tick(25);
+
+        assert!(timer.has_expired());
+        assert!(timer.is_running());
+        assert_eq!(timer.get_counter(), 5);
+    }
+
+    #[test]
+    fn should_not_count_with_zero_period() {
+        let mut timer = timer(0, 0x03);
+
+        timer.tick(100);
+
+        assert!(!timer.is_running());
+        assert!(!timer.has_expired());
+    }
+
+    #[test]
+    fn should_keep_counting_when_enabled_again() {
+        let mut timer = timer(10, 0x01);
+        timer.tick(4);
+
+        timer.write(0x0002, 0x01);
+        timer.tick(1);
+
+        assert_eq!(timer.get_counter(), 5);
+    }
+}
+
+#[cfg(test)]
+mod interrupts {
+    use crate::{
+        device::{timer::Timer, Device},
+        memory::Bus,
+    };
+
+    #[test]
+    fn should_hold_irq_until_status_is_read() {
+        let mut timer = Timer::new();
+        timer.write(0x0000, 3);
+        timer.write(0x0002, 0x05);
+
+        timer.tick(3);
+        assert!(timer.irq_asserted());
+        assert_eq!(timer.peek(0x0003), 0x80);
+        assert!(timer.irq_asserted());
+
+        assert_eq!(timer.read(0x0003), 0x80);
+        assert!(!timer.irq_asserted());
+    }
+
+    #[test]
+    fn should_acknowledge_expiry_on_status_write() {
+        let mut timer = Timer::new();
+        timer.write(0x0000, 3);
+        timer.write(0x0002, 0x07);
+        timer.tick(3);
+
+        timer.write(0x0003, 0x00);
+
+        assert!(!timer.irq_asserted());
+    }
+
+    #[test]
+    fn should_flag_expiry_without_irq_when_disabled() {
+        let mut timer = Timer::new();
+        timer.write(0x0000, 3);
+        timer.write(0x0002, 0x01);
+
+        timer.tick(3);
+
+        assert!(!timer.irq_asserted());
+        assert!(timer.has_expired());
+    }
+}
+
+#[cfg(test)]
+mod system_integration {
+    use crate::{
+        cpu::CPU,
+        device::timer::Timer,
+        memory::{memory_map::MemoryMap, Ram64k},
+        system::System,
+    };
+
+    // main: period 50, periodic with IRQ; CLI; loop: JMP loop
+    // handler at $0300: LDA $D003; INX; RTI
+    #[test]
+    fn should_interrupt_cpu_once_per_period() {
+        let mut ram = Ram64k::new();
+        ram.load(
+            &[
+                0xA9, 50, 0x8D, 0x00, 0xD0, 0xA9, 0x07, 0x8D, 0x02, 0xD0, 0x58, 0x4C, 0x0B, 0x02,
+            ],
+            0x0200,
+        );
+        ram.load(&[0xAD, 0x03, 0xD0, 0xE8, 0x40], 0x0300);
+        ram.load(&[0x00, 0x03], 0xFFFE);
+        let mut map = MemoryMap::new();
+        map.map(0x0000..=0xFFFF, ram);
+        map.attach(0xD000..=0xD007, Timer::new());
+        let mut cpu = CPU::with_memory(map);
+        cpu.set_program_counter(0x0200);
+        let mut system = System::new(cpu);
+
+        system.execute_cycles(540);
+
+        assert_eq!(system.get_cpu().get_index_register_x(), 10);
+    }
+}
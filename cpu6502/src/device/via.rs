@@ -0,0 +1,356 @@
+use super::{port::Port, Device};
+use crate::{
+    consts::{Byte, Word},
+    memory::Bus,
+};
+
+const ORB: Word = 0x0;
+const ORA: Word = 0x1;
+const DDRB: Word = 0x2;
+const DDRA: Word = 0x3;
+const T1C_L: Word = 0x4;
+const T1C_H: Word = 0x5;
+const T1L_L: Word = 0x6;
+const T1L_H: Word = 0x7;
+const T2C_L: Word = 0x8;
+const T2C_H: Word = 0x9;
+const SR: Word = 0xA;
+const ACR: Word = 0xB;
+const PCR: Word = 0xC;
+const IFR: Word = 0xD;
+const IER: Word = 0xE;
+// port A without the handshake
+const ORA_NH: Word = 0xF;
+
+const IFR_CA2: Byte = 0x01;
+const IFR_CA1: Byte = 0x02;
+const IFR_CB2: Byte = 0x08;
+const IFR_CB1: Byte = 0x10;
+const IFR_T2: Byte = 0x20;
+const IFR_T1: Byte = 0x40;
+const IFR_IRQ: Byte = 0x80;
+// bit 7 of IER selects whether the written bits are set or cleared
+const IER_SET: Byte = 0x80;
+
+const ACR_T2_PULSE_COUNTING: Byte = 0x20;
+const ACR_T1_FREE_RUN: Byte = 0x40;
+
+// within the four PCR bits of a port
+const PCR_C1_RISING_EDGE: Byte = 0x01;
+const PCR_C2_OUTPUT: Byte = 0x08;
+const PCR_C2_RISING_EDGE: Byte = 0x04;
+// an input C2 whose flag port accesses leave alone
+const PCR_C2_INDEPENDENT: Byte = 0x02;
+const PCR_C2_MODE: Byte = 0x0E;
+const PCR_C2_HANDSHAKE: Byte = 0x08;
+const PCR_C2_PULSE: Byte = 0x0A;
+const PCR_C2_LOW: Byte = 0x0C;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum ViaPort {
+    A,
+    B,
+}
+
+// MOS 6522 versatile interface adapter with its sixteen registers at offsets 0-F: two ports
+// with CA1/CA2 and CB1/CB2 control lines, the two interval timers and the interrupt flag and
+// enable registers. Timer 1 runs one-shot or free running with a period of the latch plus two
+// cycles, timer 2 one-shot. Not modelled: the shift register, which only stores what is
+// written, input latching, timer 1 output on PB7 and timer 2 counting PB6 pulses, which
+// leaves it holding its count.
+pub struct Via {
+    port_a: Port,
+    port_b: Port,
+    t1_counter: u16,
+    t1_latch: u16,
+    // timer 1 flags an underflow once per start unless free running
+    t1_armed: bool,
+    // a free running timer 1 reloads on the cycle after it underflowed
+    t1_reload: bool,
+    t2_counter: u16,
+    t2_latch_low: Byte,
+    t2_armed: bool,
+    shift: Byte,
+    acr: Byte,
+    pcr: Byte,
+    ifr: Byte,
+    ier: Byte,
+}
+
+impl Via {
+    pub fn new() -> Self {
+        return Via {
+            port_a: Port::new(),
+            port_b: Port::new(),
+            t1_counter: 0,
+            t1_latch: 0,
+            t1_armed: false,
+            t1_reload: false,
+            t2_counter: 0,
+            t2_latch_low: 0,
+            t2_armed: false,
+            shift: 0,
+            acr: 0,
+            pcr: 0,
+            ifr: 0,
+            ier: 0,
+        };
+    }
+
+    // levels driven onto the port by the outside world, seen on bits configured as inputs
+    pub fn set_input(&mut self, port: ViaPort, value: Byte) {
+        self.port_mut(port).input = value;
+    }
+
+    pub fn get_pins(&self, port: ViaPort) -> Byte {
+        return self.port(port).pins();
+    }
+
+    pub fn get_data_direction(&self, port: ViaPort) -> Byte {
+        return self.port(port).data_direction;
+    }
+
+    pub fn get_timer1(&self) -> u16 {
+        return self.t1_counter;
+    }
+
+    pub fn get_timer2(&self) -> u16 {
+        return self.t2_counter;
+    }
+
+    // CA1 / CB1 interrupt inputs
+    pub fn set_c1(&mut self, port: ViaPort, level: bool) {
+        let control = self.port_control(port);
+        let (c1_flag, _) = flags(port);
+        let port = self.port_mut(port);
+        if !port.set_c1(level, control & PCR_C1_RISING_EDGE != 0) {
+            return;
+        }
+
+        // the active C1 transition completes a handshake
+        if control & PCR_C2_MODE == PCR_C2_HANDSHAKE {
+            port.complete_handshake();
+        }
+        self.ifr |= c1_flag;
+    }
+
+    // CA2 / CB2, interrupt inputs unless programmed as outputs
+    pub fn set_c2(&mut self, port: ViaPort, level: bool) {
+        let control = self.port_control(port);
+        let (_, c2_flag) = flags(port);
+        let rising = control & PCR_C2_RISING_EDGE != 0;
+        let is_input = control & PCR_C2_OUTPUT == 0;
+        if self.port_mut(port).set_c2(level, rising, is_input) {
+            self.ifr |= c2_flag;
+        }
+    }
+
+    // the level of CA2 / CB2 when programmed as an output
+    pub fn get_c2_output(&self, port: ViaPort) -> Option<bool> {
+        let control = self.port_control(port);
+        if control & PCR_C2_OUTPUT == 0 {
+            return None;
+        }
+
+        return match control & PCR_C2_MODE {
+            PCR_C2_HANDSHAKE | PCR_C2_PULSE => Some(self.port(port).c2_output),
+            PCR_C2_LOW => Some(false),
+            _ => Some(true),
+        };
+    }
+
+    fn port(&self, port: ViaPort) -> &Port {
+        return match port {
+            ViaPort::A => &self.port_a,
+            ViaPort::B => &self.port_b,
+        };
+    }
+
+    fn port_mut(&mut self, port: ViaPort) -> &mut Port {
+        return match port {
+            ViaPort::A => &mut self.port_a,
+            ViaPort::B => &mut self.port_b,
+        };
+    }
+
+    fn port_control(&self, port: ViaPort) -> Byte {
+        return match port {
+            ViaPort::A => self.pcr & 0x0F,
+            ViaPort::B => self.pcr >> 4,
+        };
+    }
+
+    // a read or write of the port register acknowledges both control line interrupts, except
+    // an independent C2 input
+    fn clear_port_flags(&mut self, port: ViaPort) {
+        let control = self.port_control(port);
+        let (c1_flag, c2_flag) = flags(port);
+        self.ifr &= !c1_flag;
+        if control & (PCR_C2_OUTPUT | PCR_C2_INDEPENDENT) != PCR_C2_INDEPENDENT {
+            self.ifr &= !c2_flag;
+        }
+    }
+
+    // C2 handshake and pulse outputs go low on the read or write of port A and the write of
+    // port B
+    fn start_handshake(&mut self, port: ViaPort) {
+        let mode = self.port_control(port) & PCR_C2_MODE;
+        if mode != PCR_C2_HANDSHAKE && mode != PCR_C2_PULSE {
+            return;
+        }
+        self.port_mut(port).start_handshake(mode == PCR_C2_PULSE);
+    }
+
+    fn ifr_with_irq(&self) -> Byte {
+        let irq = match self.ifr & self.ier & !IFR_IRQ {
+            0 => 0,
+            _ => IFR_IRQ,
+        };
+
+        return self.ifr | irq;
+    }
+
+    fn register(&self, addr: Word) -> Byte {
+        return match addr & 0x0F {
+            ORB => self.port_b.pins(),
+            DDRB => self.port_b.data_direction,
+            DDRA => self.port_a.data_direction,
+            T1C_L => self.t1_counter as Byte,
+            T1C_H => (self.t1_counter >> 8) as Byte,
+            T1L_L => self.t1_latch as Byte,
+            T1L_H => (self.t1_latch >> 8) as Byte,
+            T2C_L => self.t2_counter as Byte,
+            T2C_H => (self.t2_counter >> 8) as Byte,
+            SR => self.shift,
+            ACR => self.acr,
+            PCR => self.pcr,
+            IFR => self.ifr_with_irq(),
+            IER => self.ier | IER_SET,
+            // ORA and ORA_NH
+            _ => self.port_a.pins(),
+        };
+    }
+
+    fn count(&mut self) {
+        if self.t1_reload {
+            self.t1_counter = self.t1_latch;
+            self.t1_reload = false;
+        } else {
+            self.t1_counter = self.t1_counter.wrapping_sub(1);
+            if self.t1_counter == 0xFFFF {
+                let free_run = self.acr & ACR_T1_FREE_RUN != 0;
+                if self.t1_armed {
+                    self.ifr |= IFR_T1;
+                    self.t1_armed = free_run;
+                }
+                self.t1_reload = free_run;
+            }
+        }
+
+        if self.acr & ACR_T2_PULSE_COUNTING == 0 {
+            self.t2_counter = self.t2_counter.wrapping_sub(1);
+            if self.t2_counter == 0xFFFF && self.t2_armed {
+                self.ifr |= IFR_T2;
+                self.t2_armed = false;
+            }
+        }
+    }
+}
+
+fn flags(port: ViaPort) -> (Byte, Byte) {
+    return match port {
+        ViaPort::A => (IFR_CA1, IFR_CA2),
+        ViaPort::B => (IFR_CB1, IFR_CB2),
+    };
+}
+
+impl Default for Via {
+    fn default() -> Self {
+        return Via::new();
+    }
+}
+
+impl Bus for Via {
+    fn read(&mut self, addr: Word) -> Byte {
+        let value = self.register(addr);
+        match addr & 0x0F {
+            ORB => self.clear_port_flags(ViaPort::B),
+            ORA => {
+                self.clear_port_flags(ViaPort::A);
+                self.start_handshake(ViaPort::A);
+            }
+            T1C_L => self.ifr &= !IFR_T1,
+            T2C_L => self.ifr &= !IFR_T2,
+            _ => (),
+        };
+
+        return value;
+    }
+
+    fn write(&mut self, addr: Word, value: Byte) {
+        match addr & 0x0F {
+            ORB => {
+                self.port_b.output = value;
+                self.clear_port_flags(ViaPort::B);
+                self.start_handshake(ViaPort::B);
+            }
+            ORA => {
+                self.port_a.output = value;
+                self.clear_port_flags(ViaPort::A);
+                self.start_handshake(ViaPort::A);
+            }
+            ORA_NH => self.port_a.output = value,
+            DDRB => self.port_b.data_direction = value,
+            DDRA => self.port_a.data_direction = value,
+            T1C_L | T1L_L => self.t1_latch = (self.t1_latch & 0xFF00) | value as u16,
+            T1C_H => {
+                self.t1_latch = (self.t1_latch & 0x00FF) | ((value as u16) << 8);
+                self.t1_counter = self.t1_latch;
+                self.t1_armed = true;
+                self.t1_reload = false;
+                self.ifr &= !IFR_T1;
+            }
+            T1L_H => {
+                self.t1_latch = (self.t1_latch & 0x00FF) | ((value as u16) << 8);
+                self.ifr &= !IFR_T1;
+            }
+            T2C_L => self.t2_latch_low = value,
+            T2C_H => {
+                self.t2_counter = self.t2_latch_low as u16 | ((value as u16) << 8);
+                self.t2_armed = true;
+                self.ifr &= !IFR_T2;
+            }
+            SR => self.shift = value,
+            ACR => self.acr = value,
+            PCR => self.pcr = value,
+            IFR => self.ifr &= !value,
+            IER => match value & IER_SET {
+                0 => self.ier &= !value,
+                _ => self.ier |= value & !IER_SET,
+            },
+            _ => (),
+        };
+    }
+
+    // reading acknowledges interrupts and starts a CA2 handshake, peeking does neither
+    fn peek(&mut self, addr: Word) -> Byte {
+        return self.register(addr);
+    }
+}
+
+impl Device for Via {
+    fn tick(&mut self, cycles: u64) {
+        for _ in 0..cycles {
+            self.count();
+            self.port_a.tick();
+            self.port_b.tick();
+        }
+    }
+
+    fn irq_asserted(&self) -> bool {
+        return self.ifr & self.ier & !IFR_IRQ != 0;
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,171 @@
+#[cfg(test)]
+mod ports {
+    use crate::{
+        device::via::{Via, ViaPort},
+        memory::Bus,
+    };
+
+    #[test]
+    fn should_mix_outputs_and_inputs_on_port() {
+        let mut via = Via::new();
+        via.write(0x0002, 0xF0);
+        via.write(0x0000, 0xA5);
+
+        via.set_input(ViaPort::B, 0x3C);
+
+        assert_eq!(via.get_data_direction(ViaPort::B), 0xF0);
+        assert_eq!(via.read(0x0000), 0xAC);
+        assert_eq!(via.get_pins(ViaPort::B), 0xAC);
+    }
+
+    #[test]
+    fn should_reach_port_a_without_handshake_at_offset_f() {
+        let mut via = Via::new();
+        via.write(0x0003, 0xFF);
+
+        via.write(0x000F, 0x42);
+
+        assert_eq!(via.read(0x0001), 0x42);
+    }
+
+    #[test]
+    fn should_read_ier_with_bit7_set() {
+        let mut via = Via::new();
+
+        via.write(0x000E, 0xC0);
+        via.write(0x000E, 0x20);
+
+        assert_eq!(via.read(0x000E), 0xC0);
+    }
+}
+
+#[cfg(test)]
+mod timers {
+    use crate::{
+        device::{via::Via, Device},
+        memory::Bus,
+    };
+
+    #[test]
+    fn should_flag_timer1_one_shot_underflow_once() {
+        let mut via = Via::new();
+        via.write(0x0004, 0x03);
+        via.write(0x0005, 0x00);
+
+        via.tick(3);
+        assert_eq!(via.get_timer1(), 0);
+        assert_eq!(via.peek(0x000D) & 0x40, 0x00);
+        via.tick(1);
+        assert_eq!(via.peek(0x000D) & 0x40, 0x40);
+        via.read(0x0004);
+        via.tick(0x10000);
+
+        assert_eq!(via.peek(0x000D) & 0x40, 0x00);
+    }
+
+    #[test]
+    fn should_run_timer1_free_with_latch_plus_two_period() {
+        let mut via = Via::new();
+        via.write(0x000B, 0x40);
+        via.write(0x0004, 0x03);
+        via.write(0x0005, 0x00);
+        via.tick(4);
+        via.read(0x0004);
+
+        via.tick(4);
+        assert_eq!(via.get_timer1(), 0);
+        assert_eq!(via.peek(0x000D) & 0x40, 0x00);
+        via.tick(1);
+
+        assert_eq!(via.peek(0x000D) & 0x40, 0x40);
+    }
+
+    #[test]
+    fn should_raise_irq_for_enabled_timer_until_acknowledged() {
+        let mut via = Via::new();
+        via.write(0x000E, 0xA0);
+        via.write(0x0008, 0x02);
+        via.write(0x0009, 0x00);
+
+        via.tick(3);
+        assert!(via.irq_asserted());
+        assert_eq!(via.read(0x000D), 0xA0);
+        via.write(0x000D, 0x20);
+
+        assert!(!via.irq_asserted());
+    }
+
+    #[test]
+    fn should_hold_timer2_while_counting_pulses() {
+        let mut via = Via::new();
+        via.write(0x000B, 0x20);
+        via.write(0x0008, 0x10);
+        via.write(0x0009, 0x00);
+
+        via.tick(5);
+
+        assert_eq!(via.get_timer2(), 0x10);
+    }
+}
+
+#[cfg(test)]
+mod control_lines {
+    use crate::{
+        device::{
+            via::{Via, ViaPort},
+            Device,
+        },
+        memory::Bus,
+    };
+
+    #[test]
+    fn should_flag_active_c1_edge_until_port_is_read() {
+        let mut via = Via::new();
+        via.write(0x000C, 0x01); // CA1 rising edge
+        via.write(0x000E, 0x82);
+
+        via.set_c1(ViaPort::A, false);
+        assert!(!via.irq_asserted());
+        via.set_c1(ViaPort::A, true);
+        assert!(via.irq_asserted());
+        via.read(0x0001);
+
+        assert!(!via.irq_asserted());
+    }
+
+    #[test]
+    fn should_keep_independent_c2_flag_on_port_access() {
+        let mut via = Via::new();
+        via.write(0x000C, 0x20); // CB2 independent interrupt on falling edge
+
+        via.set_c2(ViaPort::B, false);
+        via.read(0x0000);
+
+        assert_eq!(via.read(0x000D), 0x08);
+    }
+
+    #[test]
+    fn should_pulse_ca2_after_port_a_read() {
+        let mut via = Via::new();
+        via.write(0x000C, 0x0A);
+        assert_eq!(via.get_c2_output(ViaPort::A), Some(true));
+
+        via.read(0x0001);
+        assert_eq!(via.get_c2_output(ViaPort::A), Some(false));
+        via.tick(1);
+
+        assert_eq!(via.get_c2_output(ViaPort::A), Some(true));
+    }
+
+    #[test]
+    fn should_drive_manual_c2_levels() {
+        let mut via = Via::new();
+
+        via.write(0x000C, 0xCE);
+
+        assert_eq!(via.get_c2_output(ViaPort::A), Some(true));
+        assert_eq!(via.get_c2_output(ViaPort::B), Some(false));
+        via.write(0x000C, 0x00);
+        assert_eq!(via.get_c2_output(ViaPort::A), None);
+    }
+}
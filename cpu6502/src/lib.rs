@@ -1,4 +1,12 @@
 pub mod consts;
 pub mod cpu;
+pub mod cpu65816;
+pub mod determinism;
+pub mod device;
 pub mod machine;
+pub mod machines;
 pub mod memory;
+pub mod multiprocessor;
+pub mod symbols;
+pub mod system;
+pub mod variant_timing;
@@ -2,11 +2,11 @@ use std::cell::RefCell;
 
 use crate::memory::VecMemory;
 
-use super::cpu::CPU;
+use super::cpu::{emulation_event::EmulationEvent, CPU};
 
 pub struct Machine<'a> {
     memory: &'a RefCell<VecMemory>,
-    cpu: CPU<'a>,
+    cpu: CPU<&'a RefCell<VecMemory>>,
 }
 
 impl<'a> Machine<'a> {
@@ -17,9 +17,9 @@ impl<'a> Machine<'a> {
         };
     }
 
-    pub fn execute_until_break(&mut self, program: &[(u16, u8)]) {
+    pub fn execute_until_break(&mut self, program: &[(u16, u8)]) -> EmulationEvent {
         self.memory.borrow_mut().store(program);
         self.cpu.reset();
-        self.cpu.execute_until_break();
+        return self.cpu.execute_until_break();
     }
 }
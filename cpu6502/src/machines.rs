@@ -0,0 +1,60 @@
+use crate::{
+    consts::{Byte, Word},
+    memory::Bus,
+};
+
+pub mod apple1;
+pub mod atari2600;
+pub mod ben_eater;
+pub mod c64;
+pub mod kim1;
+pub mod nes;
+pub mod vic20;
+
+// registers that keep what is written, standing in for a chip that is not emulated
+struct Stub {
+    registers: Vec<Byte>,
+}
+
+impl Stub {
+    fn new(size: usize) -> Self {
+        return Stub {
+            registers: vec![0; size],
+        };
+    }
+}
+
+impl Bus for Stub {
+    fn read(&mut self, addr: Word) -> Byte {
+        return self.registers[addr as usize % self.registers.len()];
+    }
+
+    fn write(&mut self, addr: Word, value: Byte) {
+        let idx = addr as usize % self.registers.len();
+        self.registers[idx] = value;
+    }
+}
+
+// 4-bit color RAM of the Commodore machines; the upper nybble reads 0 instead of floating
+struct ColorRam {
+    nybbles: Vec<Byte>,
+}
+
+impl ColorRam {
+    fn new(size: usize) -> Self {
+        return ColorRam {
+            nybbles: vec![0; size],
+        };
+    }
+}
+
+impl Bus for ColorRam {
+    fn read(&mut self, addr: Word) -> Byte {
+        return self.nybbles[addr as usize % self.nybbles.len()];
+    }
+
+    fn write(&mut self, addr: Word, value: Byte) {
+        let idx = addr as usize % self.nybbles.len();
+        self.nybbles[idx] = value & 0x0F;
+    }
+}
@@ -0,0 +1,139 @@
+use crate::{
+    consts::{Byte, Word},
+    cpu::CPU,
+    device::{
+        acia::SerialBackend,
+        pia::{Pia, PiaPort},
+        Device,
+    },
+    memory::{memory_map::MemoryMap, rom::Rom, Bus, Ram64k},
+    system::System,
+};
+
+pub const RAM_END: Word = 0x7FFF;
+pub const PIA_START: Word = 0xD010;
+pub const PIA_END: Word = 0xD013;
+pub const BASIC_START: Word = 0xE000;
+pub const BASIC_END: Word = 0xEFFF;
+// the monitor ROM ends at $FFFF, Wozmon alone takes the last page
+pub const MONITOR_MAX_SIZE: usize = 0x1000;
+
+// offsets of the PIA registers the terminal watches
+const KBDCR: Word = 1;
+const DSP: Word = 2;
+const DSPCR: Word = 3;
+
+const CONTROL_PERIPHERAL_ACCESS: Byte = 0x04;
+const CONTROL_IRQ1: Byte = 0x80;
+// the keyboard drives bit 7 of its data high
+const KEY_BIT7: Byte = 0x80;
+const CARRIAGE_RETURN: Byte = 0x0D;
+
+// the terminal section of the Apple 1 around its PIA: keys typed on the host show up on port
+// A with a strobe on CA1, and characters written to port B go to the host. The display is
+// always ready, PB7 reads low. Host newlines become carriage returns and keys are upper-cased
+// as on the original keyboard; the carriage returns of the Apple 1 come out as newlines.
+struct Terminal<B: SerialBackend> {
+    pia: Pia,
+    backend: B,
+}
+
+impl<B: SerialBackend> Terminal<B> {
+    fn new(backend: B) -> Self {
+        let mut pia = Pia::new();
+        pia.set_input(PiaPort::B, 0x00);
+
+        return Terminal { pia, backend };
+    }
+
+    fn poll_keyboard(&mut self) {
+        // the previous key waits until the program reads it
+        if self.pia.peek(KBDCR) & CONTROL_IRQ1 != 0 {
+            return;
+        }
+        let key = match self.backend.receive() {
+            Some(b'\n') => CARRIAGE_RETURN,
+            Some(key) => key.to_ascii_uppercase(),
+            None => return,
+        };
+        self.pia.set_input(PiaPort::A, key | KEY_BIT7);
+        // strobe pulse, the line idles low
+        self.pia.set_c1(PiaPort::A, false);
+        self.pia.set_c1(PiaPort::A, true);
+        self.pia.set_c1(PiaPort::A, false);
+    }
+
+    fn display(&mut self, value: Byte) {
+        match value & !KEY_BIT7 {
+            CARRIAGE_RETURN => self.backend.transmit(b'\n'),
+            character @ 0x20..=0x7E => self.backend.transmit(character),
+            _ => (),
+        };
+    }
+}
+
+impl<B: SerialBackend> Bus for Terminal<B> {
+    fn read(&mut self, addr: Word) -> Byte {
+        return self.pia.read(addr);
+    }
+
+    fn write(&mut self, addr: Word, value: Byte) {
+        let is_display =
+            addr & 0x03 == DSP && self.pia.peek(DSPCR) & CONTROL_PERIPHERAL_ACCESS != 0;
+        self.pia.write(addr, value);
+        if is_display {
+            self.display(value);
+        }
+    }
+
+    fn peek(&mut self, addr: Word) -> Byte {
+        return self.pia.peek(addr);
+    }
+}
+
+// the PIA IRQ outputs are not wired on the Apple 1
+impl<B: SerialBackend> Device for Terminal<B> {
+    fn tick(&mut self, cycles: u64) {
+        self.pia.tick(cycles);
+        self.poll_keyboard();
+    }
+}
+
+// an Apple 1 as most replica boards build it: 32K of RAM from $0000, the terminal PIA at
+// $D010-$D013, an optional BASIC image at $E000-$EFFF and the monitor ROM ending at $FFFF,
+// which is where the CPU takes its reset vector from. Run it interactively with
+// `StreamBackend::stdio()` as the terminal, or headless with a `BufferBackend`.
+pub fn build<'a>(
+    monitor: Vec<Byte>,
+    basic: Option<Vec<Byte>>,
+    terminal: impl SerialBackend + 'a,
+) -> Result<System<'a>, String> {
+    if monitor.is_empty() {
+        return Err("monitor ROM image is empty".to_string());
+    }
+    if monitor.len() > MONITOR_MAX_SIZE {
+        return Err("monitor ROM image is larger than 4K".to_string());
+    }
+    if let Some(basic) = &basic {
+        if basic.is_empty() {
+            return Err("BASIC ROM image is empty".to_string());
+        }
+        if basic.len() > (BASIC_END - BASIC_START) as usize + 1 {
+            return Err("BASIC ROM image is larger than 4K".to_string());
+        }
+    }
+
+    let mut map = MemoryMap::new();
+    map.map(0x0000..=RAM_END, Ram64k::new());
+    map.attach(PIA_START..=PIA_END, Terminal::new(terminal));
+    if let Some(basic) = basic {
+        map.map(BASIC_START..=BASIC_END, Rom::new(basic));
+    }
+    let monitor_start = (0x10000 - monitor.len()) as Word;
+    map.map(monitor_start..=0xFFFF, Rom::new(monitor));
+
+    return Ok(System::new(CPU::with_memory(map)));
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,98 @@
+#[cfg(test)]
+mod apple1 {
+    use std::cell::RefCell;
+
+    use crate::{
+        cpu::emulation_event::EmulationEvent, device::acia::BufferBackend, machines::apple1,
+        memory::Bus,
+    };
+
+    // a stand-in for Wozmon driving the PIA the same way, echoing keys until Return:
+    // $FF00: LDY #$7F; STY DSP; LDA #$A7; STA KBDCR; STA DSPCR
+    // $FF0D: LDA KBDCR; BPL $FF0D; LDA KBD
+    // $FF15: BIT DSP; BMI $FF15; STA DSP; CMP #$8D; BNE $FF0D; BRK
+    fn monitor() -> Vec<u8> {
+        let mut rom = vec![0xEA; 0x100];
+        let program = [
+            0xA0, 0x7F, 0x8C, 0x12, 0xD0, 0xA9, 0xA7, 0x8D, 0x11, 0xD0, 0x8D, 0x13, 0xD0, 0xAD,
+            0x11, 0xD0, 0x10, 0xFB, 0xAD, 0x10, 0xD0, 0x2C, 0x12, 0xD0, 0x30, 0xFB, 0x8D, 0x12,
+            0xD0, 0xC9, 0x8D, 0xD0, 0xEC, 0x00,
+        ];
+        rom[..program.len()].copy_from_slice(&program);
+        rom[0xFC] = 0x00;
+        rom[0xFD] = 0xFF;
+
+        return rom;
+    }
+
+    #[test]
+    fn should_start_monitor_from_reset_vector() {
+        let terminal = RefCell::new(BufferBackend::new());
+
+        let system = apple1::build(monitor(), None, &terminal).unwrap();
+
+        assert_eq!(system.get_cpu().get_program_counter(), 0xFF00);
+    }
+
+    #[test]
+    fn should_echo_typed_keys_through_terminal() {
+        let terminal = RefCell::new(BufferBackend::new());
+        let mut system = apple1::build(monitor(), None, &terminal).unwrap();
+        terminal.borrow_mut().push_input(b"hello\n");
+
+        let event = system.execute_until_break();
+
+        assert!(matches!(event, EmulationEvent::Trap(_)));
+        assert_eq!(terminal.borrow().get_output(), b"HELLO\n");
+    }
+
+    #[test]
+    fn should_map_ram_and_basic_image() {
+        let terminal = RefCell::new(BufferBackend::new());
+        let mut system = apple1::build(monitor(), Some(vec![0x4C; 0x1000]), &terminal).unwrap();
+        let map = system.get_memory_map_mut();
+
+        map.write(0x7FFF, 0x42);
+
+        assert_eq!(map.read(0x7FFF), 0x42);
+        assert_eq!(map.read(0xE000), 0x4C);
+        assert_eq!(map.read(0xEFFF), 0x4C);
+    }
+
+    #[test]
+    fn should_reject_oversized_monitor() {
+        let terminal = RefCell::new(BufferBackend::new());
+
+        let error = apple1::build(vec![0; 0x1001], None, &terminal)
+            .err()
+            .unwrap();
+
+        assert_eq!(error, "monitor ROM image is larger than 4K");
+    }
+
+    #[test]
+    fn should_reject_empty_images() {
+        let terminal = RefCell::new(BufferBackend::new());
+
+        let error = apple1::build(vec![], None, &terminal).err().unwrap();
+
+        assert_eq!(error, "monitor ROM image is empty");
+
+        let error = apple1::build(monitor(), Some(vec![]), &terminal)
+            .err()
+            .unwrap();
+
+        assert_eq!(error, "BASIC ROM image is empty");
+    }
+
+    #[test]
+    fn should_reject_oversized_basic() {
+        let terminal = RefCell::new(BufferBackend::new());
+
+        let error = apple1::build(monitor(), Some(vec![0; 0x1001]), &terminal)
+            .err()
+            .unwrap();
+
+        assert_eq!(error, "BASIC ROM image is larger than 4K");
+    }
+}
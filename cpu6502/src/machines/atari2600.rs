@@ -0,0 +1,136 @@
+use crate::{
+    consts::{Byte, Word},
+    cpu::CPU,
+    device::{riot::Riot, Device},
+    memory::{memory_map::MemoryMap, rom::Rom, Bus, MaskedMemory},
+    system::System,
+};
+
+pub const CLOCK_HZ: u64 = 1_193_182;
+pub const ADDRESS_LINES: u8 = 13;
+pub const ROM_START: Word = 0x1000;
+
+// chip selects of the 13-bit bus: A12 selects the cartridge, otherwise A7 the RIOT over the
+// TIA and A9 the RIOT I/O over its RAM
+const CARTRIDGE_SELECT: Word = 0x1000;
+const RIOT_SELECT: Word = 0x0080;
+const RIOT_IO_SELECT: Word = 0x0200;
+// the 6532 offsets its I/O registers by $80
+const RIOT_IO: Word = 0x80;
+
+const TIA_WRITE_REGISTERS: usize = 0x40;
+const TIA_READ_MASK: Word = 0x0F;
+// INPT4 and INPT5, the fire buttons, read high while released
+const INPT4: Word = 0x0C;
+const INPT5: Word = 0x0D;
+const BUTTON_RELEASED: Byte = 0x80;
+
+// the TIA without video and sound: writes are kept for inspection, the collision latches read
+// clear and the paddles and fire buttons idle. WSYNC does not halt the CPU.
+struct Tia {
+    registers: [Byte; TIA_WRITE_REGISTERS],
+}
+
+impl Tia {
+    fn new() -> Self {
+        return Tia {
+            registers: [0; TIA_WRITE_REGISTERS],
+        };
+    }
+}
+
+impl Bus for Tia {
+    fn read(&mut self, addr: Word) -> Byte {
+        return match addr & TIA_READ_MASK {
+            INPT4 | INPT5 => BUTTON_RELEASED,
+            _ => 0,
+        };
+    }
+
+    fn write(&mut self, addr: Word, value: Byte) {
+        self.registers[addr as usize % TIA_WRITE_REGISTERS] = value;
+    }
+}
+
+// the 13-bit address space of the console, decoded by the chip selects alone so every chip
+// repeats through it like on the board
+struct Console {
+    tia: Tia,
+    riot: Riot,
+    cartridge: Rom,
+}
+
+enum Chip {
+    Tia,
+    Riot(Word),
+    Cartridge,
+}
+
+fn select(addr: Word) -> Chip {
+    if addr & CARTRIDGE_SELECT != 0 {
+        return Chip::Cartridge;
+    }
+    if addr & RIOT_SELECT == 0 {
+        return Chip::Tia;
+    }
+
+    return match addr & RIOT_IO_SELECT {
+        0 => Chip::Riot(addr & 0x7F),
+        _ => Chip::Riot(RIOT_IO | (addr & 0x7F)),
+    };
+}
+
+impl Bus for Console {
+    fn read(&mut self, addr: Word) -> Byte {
+        return match select(addr) {
+            Chip::Tia => self.tia.read(addr),
+            Chip::Riot(offset) => self.riot.read(offset),
+            Chip::Cartridge => self.cartridge.read(addr - ROM_START),
+        };
+    }
+
+    fn write(&mut self, addr: Word, value: Byte) {
+        match select(addr) {
+            Chip::Tia => self.tia.write(addr, value),
+            Chip::Riot(offset) => self.riot.write(offset, value),
+            Chip::Cartridge => self.cartridge.write(addr - ROM_START, value),
+        };
+    }
+
+    fn peek(&mut self, addr: Word) -> Byte {
+        return match select(addr) {
+            Chip::Riot(offset) => self.riot.peek(offset),
+            _ => self.read(addr),
+        };
+    }
+}
+
+// the 6507 has no IRQ pin, the RIOT interrupt output goes nowhere
+impl Device for Console {
+    fn tick(&mut self, cycles: u64) {
+        self.riot.tick(cycles);
+    }
+}
+
+// an Atari 2600 for timing kernels: the 6507, an NMOS 6502 with 13 address lines, running a
+// 2K or 4K cartridge from $1000-$1FFF, 2K images appearing twice. The RIOT has the 128 bytes
+// of RAM at $80 and its timer and ports at $280; the TIA is a register stub at $00. The CPU
+// takes its reset vector from the top of the cartridge.
+pub fn build<'a>(rom: Vec<Byte>) -> Result<System<'a>, String> {
+    if rom.len() != 0x800 && rom.len() != 0x1000 {
+        return Err("ROM image is not 2K or 4K".to_string());
+    }
+
+    let console = Console {
+        tia: Tia::new(),
+        riot: Riot::new(),
+        cartridge: Rom::new(rom),
+    };
+    let mut map = MemoryMap::new();
+    map.attach(0x0000..=0xFFFF, MaskedMemory::new(console, ADDRESS_LINES));
+
+    return Ok(System::new(CPU::with_memory(map)));
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,90 @@
+#[cfg(test)]
+mod memory_map {
+    use crate::{machines::atari2600, memory::Bus};
+
+    fn rom(size: usize) -> Vec<u8> {
+        let mut rom = vec![0xEA; size];
+        rom[size - 4] = 0x00;
+        rom[size - 3] = 0xF0;
+
+        return rom;
+    }
+
+    #[test]
+    fn should_start_from_reset_vector_of_cartridge() {
+        let system = atari2600::build(rom(0x1000)).unwrap();
+
+        assert_eq!(system.get_cpu().get_program_counter(), 0xF000);
+    }
+
+    #[test]
+    fn should_repeat_2k_cartridge_and_mirror_it_through_13_bit_bus() {
+        let mut rom = rom(0x800);
+        rom[0] = 0x42;
+        let mut system = atari2600::build(rom).unwrap();
+        let map = system.get_memory_map_mut();
+
+        assert_eq!(map.read(0x1000), 0x42);
+        assert_eq!(map.read(0x1800), 0x42);
+        assert_eq!(map.read(0xF800), 0x42);
+        assert_eq!(map.read(0xFFFD), 0xF0);
+    }
+
+    #[test]
+    fn should_mirror_riot_ram_into_stack_page() {
+        let mut system = atari2600::build(rom(0x1000)).unwrap();
+        let map = system.get_memory_map_mut();
+
+        map.write(0x00FF, 0x42);
+
+        assert_eq!(map.read(0x01FF), 0x42);
+        assert_eq!(map.read(0x20FF), 0x42);
+    }
+
+    #[test]
+    fn should_read_released_fire_buttons_and_clear_collisions_from_tia() {
+        let mut system = atari2600::build(rom(0x1000)).unwrap();
+        let map = system.get_memory_map_mut();
+
+        map.write(0x0000, 0xFF);
+
+        assert_eq!(map.read(0x0000), 0x00);
+        assert_eq!(map.read(0x000C), 0x80);
+        assert_eq!(map.read(0x003D), 0x80);
+    }
+
+    #[test]
+    fn should_reject_other_rom_sizes() {
+        for size in [0, 0x2000] {
+            let error = atari2600::build(vec![0; size]).err().unwrap();
+
+            assert_eq!(error, "ROM image is not 2K or 4K");
+        }
+    }
+}
+
+#[cfg(test)]
+mod system_integration {
+    use crate::{cpu::emulation_event::EmulationEvent, machines::atari2600};
+
+    // a kernel waiting out vertical blank on the RIOT timer like most games do:
+    // LDA #2; STA TIM64T; loop: LDA INTIM; BNE loop; BRK
+    #[test]
+    fn should_wait_for_riot_timer() {
+        let mut rom = vec![0xEA; 0x1000];
+        let program = [
+            0xA9, 0x02, 0x8D, 0x96, 0x02, 0xAD, 0x84, 0x02, 0xD0, 0xFB, 0x00,
+        ];
+        rom[..program.len()].copy_from_slice(&program);
+        rom[0xFFC] = 0x00;
+        rom[0xFFD] = 0xF0;
+        let mut system = atari2600::build(rom).unwrap();
+
+        let event = system.execute_until_break();
+
+        assert!(matches!(event, EmulationEvent::Trap(_)));
+        // INTIM reaches 0 one interval after the count following the write
+        let cycle = system.get_cpu().get_cycle();
+        assert!((65..65 + 64).contains(&cycle), "{cycle}");
+    }
+}
@@ -0,0 +1,57 @@
+use crate::{
+    consts::{Byte, Word},
+    cpu::CPU,
+    device::{
+        acia::{Acia, SerialBackend},
+        via::Via,
+    },
+    memory::{memory_map::MemoryMap, rom::Rom, Ram64k},
+    system::System,
+};
+
+pub const CLOCK_HZ: u64 = 1_000_000;
+pub const RAM_END: Word = 0x3FFF;
+pub const ACIA_START: Word = 0x5000;
+pub const ACIA_END: Word = 0x5003;
+// the VIA is selected through $6000-$7FFF, its registers repeating every 16 bytes
+pub const VIA_START: Word = 0x6000;
+pub const VIA_END: Word = 0x7FFF;
+pub const ROM_START: Word = 0x8000;
+pub const ROM_SIZE: usize = 0x8000;
+
+// Ben Eater's breadboard computer: a W65C02 at 1 MHz with 16K of RAM from $0000, the 6522 at
+// $6000 and the 32K EEPROM image at $8000-$FFFF, where the CPU takes its reset vector from.
+// Smaller images repeat through the ROM area like on the EEPROM socket.
+pub fn build<'a>(rom: Vec<Byte>) -> Result<System<'a>, String> {
+    return Ok(System::new(CPU::new_wdc_cmos(memory_map(rom)?)));
+}
+
+// the build from the serial interface videos, with the 6551 at $5000 talking to `terminal`
+pub fn build_with_acia<'a>(
+    rom: Vec<Byte>,
+    terminal: impl SerialBackend + 'a,
+) -> Result<System<'a>, String> {
+    let mut map = memory_map(rom)?;
+    map.attach(ACIA_START..=ACIA_END, Acia::new(terminal, CLOCK_HZ));
+
+    return Ok(System::new(CPU::new_wdc_cmos(map)));
+}
+
+fn memory_map<'a>(rom: Vec<Byte>) -> Result<MemoryMap<'a>, String> {
+    if rom.is_empty() {
+        return Err("ROM image is empty".to_string());
+    }
+    if rom.len() > ROM_SIZE {
+        return Err("ROM image is larger than 32K".to_string());
+    }
+
+    let mut map = MemoryMap::new();
+    map.map(0x0000..=RAM_END, Ram64k::new());
+    map.attach(VIA_START..=VIA_END, Via::new());
+    map.map(ROM_START..=0xFFFF, Rom::new(rom));
+
+    return Ok(map);
+}
+
+#[cfg(test)]
+mod tests;
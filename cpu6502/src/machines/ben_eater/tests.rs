@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod ben_eater {
+    use std::cell::RefCell;
+
+    use crate::{
+        cpu::emulation_event::EmulationEvent, device::acia::BufferBackend, machines::ben_eater,
+        memory::Bus,
+    };
+
+    // 32K image with `program` at $8000 and the reset vector pointing at it
+    fn rom(program: &[u8]) -> Vec<u8> {
+        let mut rom = vec![0xEA; 0x8000];
+        rom[..program.len()].copy_from_slice(program);
+        rom[0x7FFC] = 0x00;
+        rom[0x7FFD] = 0x80;
+
+        return rom;
+    }
+
+    #[test]
+    fn should_drive_via_port_b_from_rom_program() {
+        // LDA #$FF; STA $6002; LDA #$55; STA $6000; BRK
+        let program = [
+            0xA9, 0xFF, 0x8D, 0x02, 0x60, 0xA9, 0x55, 0x8D, 0x00, 0x60, 0x00,
+        ];
+        let mut system = ben_eater::build(rom(&program)).unwrap();
+
+        let event = system.execute_until_break();
+
+        assert!(matches!(event, EmulationEvent::Trap(_)));
+        let map = system.get_memory_map_mut();
+        assert_eq!(map.peek(0x6000), 0x55);
+        assert_eq!(map.peek(0x7FF0), 0x55);
+    }
+
+    #[test]
+    fn should_map_ram_below_via_and_leave_acia_out() {
+        let mut system = ben_eater::build(rom(&[0x00])).unwrap();
+        let map = system.get_memory_map_mut();
+
+        map.write(0x3FFF, 0x42);
+        map.write(0x4000, 0x42);
+
+        assert_eq!(map.read(0x3FFF), 0x42);
+        assert_eq!(map.read(0x4000), 0x00);
+        assert_eq!(map.read(0x5001), 0x00);
+        assert_eq!(map.read(0xFFFD), 0x80);
+    }
+
+    #[test]
+    fn should_transmit_through_acia() {
+        // LDA #'A'; STA $5000; BRK
+        let terminal = RefCell::new(BufferBackend::new());
+        let mut system =
+            ben_eater::build_with_acia(rom(&[0xA9, b'A', 0x8D, 0x00, 0x50, 0x00]), &terminal)
+                .unwrap();
+
+        system.execute_until_break();
+
+        assert_eq!(terminal.borrow().get_output(), b"A");
+    }
+
+    #[test]
+    fn should_reject_oversized_rom() {
+        let error = ben_eater::build(vec![0; 0x8001]).err().unwrap();
+
+        assert_eq!(error, "ROM image is larger than 32K");
+    }
+
+    #[test]
+    fn should_reject_empty_rom() {
+        let terminal = RefCell::new(BufferBackend::new());
+
+        let error = ben_eater::build_with_acia(vec![], &terminal).err().unwrap();
+
+        assert_eq!(error, "ROM image is empty");
+    }
+}
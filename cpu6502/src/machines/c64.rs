@@ -0,0 +1,228 @@
+use std::ops::RangeInclusive;
+
+use super::{ColorRam, Stub};
+use crate::{
+    consts::{Byte, Word},
+    cpu::CPU,
+    device::Device,
+    memory::{memory_map::MemoryMap, rom::Rom, Bus, Ram64k},
+    system::System,
+};
+
+pub const BASIC_START: Word = 0xA000;
+pub const BASIC_SIZE: usize = 0x2000;
+pub const IO_START: Word = 0xD000;
+pub const CHARACTERS_SIZE: usize = 0x1000;
+pub const KERNAL_START: Word = 0xE000;
+pub const KERNAL_SIZE: usize = 0x2000;
+
+const VIC_START: Word = 0xD000;
+const VIC_END: Word = 0xD3FF;
+const VIC_REGISTERS: usize = 0x40;
+const SID_START: Word = 0xD400;
+const SID_END: Word = 0xD7FF;
+const SID_REGISTERS: usize = 0x20;
+const COLOR_RAM_START: Word = 0xD800;
+const COLOR_RAM_END: Word = 0xDBFF;
+const CIA1_START: Word = 0xDC00;
+const CIA1_END: Word = 0xDCFF;
+const CIA2_START: Word = 0xDD00;
+const CIA2_END: Word = 0xDDFF;
+const CIA_REGISTERS: usize = 0x10;
+
+const PORT_DIRECTION: Word = 0x0000;
+const PORT_DATA: Word = 0x0001;
+const PORT_LORAM: Byte = 0x01;
+const PORT_HIRAM: Byte = 0x02;
+const PORT_CHAREN: Byte = 0x04;
+// levels of the port lines programmed as inputs: the banking lines are pulled up and the
+// cassette sense line reports no button pressed
+const PORT_PULL_UPS: Byte = 0x17;
+
+// the ROM images of the machine, supplied by the user
+pub struct C64Roms {
+    pub basic: Vec<Byte>,
+    pub kernal: Vec<Byte>,
+    pub characters: Vec<Byte>,
+}
+
+// the C64 address space as the 6510 sees it: 64K of RAM with BASIC, KERNAL, the character ROM
+// and the I/O area banked in by the processor port at $0000/$0001. Writes always reach the
+// RAM underneath the ROMs; the I/O area takes them while it is visible. VIC-II, SID and both
+// CIAs are register stubs that keep what is written, with color RAM at $D800; devices
+// attached with `attach_io` take the place of the stubs.
+pub struct C64Memory<'a> {
+    ram: Ram64k,
+    basic: Rom,
+    kernal: Rom,
+    characters: Rom,
+    io: MemoryMap<'a>,
+    port_direction: Byte,
+    port_data: Byte,
+}
+
+enum Area {
+    Ram,
+    Basic,
+    Kernal,
+    Characters,
+    Io,
+}
+
+impl<'a> C64Memory<'a> {
+    pub fn new(roms: C64Roms) -> Result<Self, String> {
+        if roms.basic.len() != BASIC_SIZE {
+            return Err("BASIC ROM image is not 8K".to_string());
+        }
+        if roms.kernal.len() != KERNAL_SIZE {
+            return Err("KERNAL ROM image is not 8K".to_string());
+        }
+        if roms.characters.len() != CHARACTERS_SIZE {
+            return Err("character ROM image is not 4K".to_string());
+        }
+
+        let mut io = MemoryMap::new();
+        io.map_mirrored(VIC_START..=VIC_END, VIC_REGISTERS, Stub::new(VIC_REGISTERS));
+        io.map_mirrored(SID_START..=SID_END, SID_REGISTERS, Stub::new(SID_REGISTERS));
+        io.map(COLOR_RAM_START..=COLOR_RAM_END, ColorRam::new(0x400));
+        io.map_mirrored(
+            CIA1_START..=CIA1_END,
+            CIA_REGISTERS,
+            Stub::new(CIA_REGISTERS),
+        );
+        io.map_mirrored(
+            CIA2_START..=CIA2_END,
+            CIA_REGISTERS,
+            Stub::new(CIA_REGISTERS),
+        );
+
+        return Ok(C64Memory {
+            ram: Ram64k::new(),
+            basic: Rom::new(roms.basic),
+            kernal: Rom::new(roms.kernal),
+            characters: Rom::new(roms.characters),
+            io,
+            // every port line is an input at power-on, so all ROMs and I/O are visible
+            port_direction: 0,
+            port_data: 0,
+        });
+    }
+
+    // `device` answers at `range` within $D000-$DFFF wherever it overlaps a stub, ticked and
+    // driving IRQ like any attached device, e.g. a CIA at $DC00-$DCFF
+    pub fn attach_io(&mut self, range: RangeInclusive<Word>, device: impl Device + 'a) -> usize {
+        return self.io.attach(range, device);
+    }
+
+    pub fn get_io_mut(&mut self) -> &mut MemoryMap<'a> {
+        return &mut self.io;
+    }
+
+    pub fn get_ram(&self) -> &Ram64k {
+        return &self.ram;
+    }
+
+    // the levels on the processor port lines
+    pub fn get_port(&self) -> Byte {
+        return (self.port_data & self.port_direction) | (PORT_PULL_UPS & !self.port_direction);
+    }
+
+    fn area(&self, addr: Word) -> Area {
+        let port = self.get_port();
+        let loram = port & PORT_LORAM != 0;
+        let hiram = port & PORT_HIRAM != 0;
+
+        return match addr {
+            0xA000..=0xBFFF if loram && hiram => Area::Basic,
+            0xD000..=0xDFFF if loram || hiram => match port & PORT_CHAREN {
+                0 => Area::Characters,
+                _ => Area::Io,
+            },
+            0xE000..=0xFFFF if hiram => Area::Kernal,
+            _ => Area::Ram,
+        };
+    }
+
+    fn read_port(&self, addr: Word) -> Option<Byte> {
+        return match addr {
+            PORT_DIRECTION => Some(self.port_direction),
+            PORT_DATA => Some(self.get_port()),
+            _ => None,
+        };
+    }
+
+    // the 6510 keeps the port registers, the RAM underneath still takes the write
+    fn write_port(&mut self, addr: Word, value: Byte) {
+        match addr {
+            PORT_DIRECTION => self.port_direction = value,
+            PORT_DATA => self.port_data = value,
+            _ => (),
+        };
+    }
+}
+
+impl Bus for C64Memory<'_> {
+    fn read(&mut self, addr: Word) -> Byte {
+        if let Some(value) = self.read_port(addr) {
+            return value;
+        }
+
+        return match self.area(addr) {
+            Area::Ram => self.ram.read(addr),
+            Area::Basic => self.basic.read(addr - BASIC_START),
+            Area::Kernal => self.kernal.read(addr - KERNAL_START),
+            Area::Characters => self.characters.read(addr - IO_START),
+            Area::Io => self.io.read(addr),
+        };
+    }
+
+    fn write(&mut self, addr: Word, value: Byte) {
+        self.write_port(addr, value);
+        match self.area(addr) {
+            Area::Io => self.io.write(addr, value),
+            _ => self.ram.write(addr, value),
+        };
+    }
+
+    fn peek(&mut self, addr: Word) -> Byte {
+        if let Some(value) = self.read_port(addr) {
+            return value;
+        }
+
+        return match self.area(addr) {
+            Area::Io => self.io.peek(addr),
+            _ => self.read(addr),
+        };
+    }
+
+    fn poke(&mut self, addr: Word, value: Byte) {
+        self.write_port(addr, value);
+        match self.area(addr) {
+            Area::Io => self.io.poke(addr, value),
+            _ => self.ram.write(addr, value),
+        };
+    }
+}
+
+impl Device for C64Memory<'_> {
+    fn tick(&mut self, cycles: u64) {
+        self.io.tick(cycles);
+    }
+
+    fn irq_asserted(&self) -> bool {
+        return self.io.irq_asserted();
+    }
+}
+
+// a C64 without video and sound: the 6510 running from the KERNAL reset vector with the banked
+// memory above. KERNAL code can be traced and debugged, though anything waiting on a chip
+// that is only a stub will not see it change.
+pub fn build<'a>(memory: C64Memory<'a>) -> System<'a> {
+    let mut map = MemoryMap::new();
+    map.attach(0x0000..=0xFFFF, memory);
+
+    return System::new(CPU::with_memory(map));
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,186 @@
+#[cfg(test)]
+mod banking {
+    use crate::{
+        machines::c64::{C64Memory, C64Roms},
+        memory::Bus,
+    };
+
+    fn roms() -> C64Roms {
+        return C64Roms {
+            basic: vec![0xBA; 0x2000],
+            kernal: vec![0xEE; 0x2000],
+            characters: vec![0xCC; 0x1000],
+        };
+    }
+
+    fn memory(port: u8) -> C64Memory<'static> {
+        let mut memory = C64Memory::new(roms()).unwrap();
+        memory.write(0x0000, 0x2F);
+        memory.write(0x0001, port);
+
+        return memory;
+    }
+
+    #[test]
+    fn should_show_roms_and_io_at_power_on() {
+        let mut memory = C64Memory::new(roms()).unwrap();
+
+        assert_eq!(memory.get_port() & 0x07, 0x07);
+        assert_eq!(memory.read(0xA000), 0xBA);
+        assert_eq!(memory.read(0xE000), 0xEE);
+        assert_eq!(memory.read(0xD000), 0x00);
+    }
+
+    #[test]
+    fn should_write_ram_underneath_roms() {
+        let mut memory = memory(0x37);
+
+        memory.write(0xA000, 0x42);
+        memory.write(0xE000, 0x43);
+        assert_eq!(memory.read(0xA000), 0xBA);
+        assert_eq!(memory.read(0xE000), 0xEE);
+        memory.write(0x0001, 0x35);
+
+        assert_eq!(memory.read(0xA000), 0x42);
+        assert_eq!(memory.read(0xE000), 0x43);
+    }
+
+    #[test]
+    fn should_bank_out_basic_with_loram_cleared() {
+        let mut memory = memory(0x36);
+
+        assert_eq!(memory.read(0xA000), 0x00);
+        assert_eq!(memory.read(0xE000), 0xEE);
+    }
+
+    #[test]
+    fn should_show_character_rom_with_charen_cleared() {
+        let mut memory = memory(0x33);
+
+        memory.write(0xD000, 0x42);
+
+        assert_eq!(memory.read(0xD000), 0xCC);
+        assert_eq!(memory.get_ram().as_slice()[0xD000], 0x42);
+    }
+
+    #[test]
+    fn should_show_ram_everywhere_with_both_rom_lines_cleared() {
+        let mut memory = memory(0x30);
+        memory.write(0xD020, 0x01);
+
+        assert_eq!(memory.read(0xA000), 0x00);
+        assert_eq!(memory.read(0xD020), 0x01);
+        assert_eq!(memory.read(0xE000), 0x00);
+    }
+
+    #[test]
+    fn should_read_input_port_lines_as_pulled_up() {
+        let mut memory = memory(0x30);
+
+        memory.write(0x0000, 0x28);
+
+        assert_eq!(memory.read(0x0001), 0x37);
+        assert_eq!(memory.read(0x0000), 0x28);
+    }
+
+    #[test]
+    fn should_reject_wrong_rom_size() {
+        let mut roms = roms();
+        roms.kernal.truncate(0x1000);
+
+        let error = C64Memory::new(roms).err().unwrap();
+
+        assert_eq!(error, "KERNAL ROM image is not 8K");
+    }
+}
+
+#[cfg(test)]
+mod io {
+    use crate::{
+        device::{timer::Timer, Device},
+        machines::c64::{C64Memory, C64Roms},
+        memory::Bus,
+    };
+
+    fn memory() -> C64Memory<'static> {
+        return C64Memory::new(C64Roms {
+            basic: vec![0; 0x2000],
+            kernal: vec![0; 0x2000],
+            characters: vec![0; 0x1000],
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn should_keep_stub_registers_and_mirror_them() {
+        let mut memory = memory();
+
+        memory.write(0xD020, 0x0E);
+        memory.write(0xDC0D, 0x7F);
+
+        assert_eq!(memory.read(0xD060), 0x0E);
+        assert_eq!(memory.read(0xDC1D), 0x7F);
+        assert_eq!(memory.get_ram().as_slice()[0xD020], 0x00);
+    }
+
+    #[test]
+    fn should_keep_only_low_nybble_in_color_ram() {
+        let mut memory = memory();
+
+        memory.write(0xD800, 0xF3);
+
+        assert_eq!(memory.read(0xD800), 0x03);
+    }
+
+    #[test]
+    fn should_tick_attached_io_device_and_report_its_irq() {
+        let mut memory = memory();
+        memory.attach_io(0xDC00..=0xDCFF, Timer::new());
+
+        memory.write(0xDC00, 2);
+        memory.write(0xDC02, 0x05);
+        memory.tick(2);
+
+        assert!(memory.irq_asserted());
+        assert_eq!(memory.read(0xDC03), 0x80);
+    }
+}
+
+#[cfg(test)]
+mod system_integration {
+    use crate::{
+        cpu::emulation_event::EmulationEvent,
+        machines::c64::{self, C64Memory, C64Roms},
+        memory::Bus,
+    };
+
+    // KERNAL at $E000, setting the port data before its direction like the real one does:
+    // LDA #$36; STA $01; LDA #$2F; STA $00; LDA #$01; STA $A000; STA $D020;
+    // BRK, with the reset vector pointing at it
+    #[test]
+    fn should_boot_from_kernal_reset_vector() {
+        let mut kernal = vec![0xEA; 0x2000];
+        let program = [
+            0xA9, 0x36, 0x85, 0x01, 0xA9, 0x2F, 0x85, 0x00, 0xA9, 0x01, 0x8D, 0x00, 0xA0, 0x8D,
+            0x20, 0xD0, 0x00,
+        ];
+        kernal[..program.len()].copy_from_slice(&program);
+        kernal[0x1FFC] = 0x00;
+        kernal[0x1FFD] = 0xE0;
+        let memory = C64Memory::new(C64Roms {
+            basic: vec![0xBA; 0x2000],
+            kernal,
+            characters: vec![0; 0x1000],
+        })
+        .unwrap();
+        let mut system = c64::build(memory);
+
+        let event = system.execute_until_break();
+
+        assert!(matches!(event, EmulationEvent::Trap(_)));
+        let map = system.get_memory_map_mut();
+        assert_eq!(map.read(0xA000), 0x01);
+        assert_eq!(map.read(0xD020), 0x01);
+        assert_eq!(map.read(0x0001) & 0x07, 0x06);
+    }
+}
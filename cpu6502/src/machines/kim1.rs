@@ -0,0 +1,205 @@
+use crate::{
+    consts::{Byte, Word},
+    cpu::CPU,
+    device::{
+        riot::{Riot, RiotPort},
+        Device,
+    },
+    memory::{memory_map::MemoryMap, rom::Rom, Bus, Ram64k},
+    system::System,
+};
+
+pub const CLOCK_HZ: u64 = 1_000_000;
+pub const RAM_END: Word = 0x03FF;
+pub const RIOT_START: Word = 0x1700;
+pub const RIOT_END: Word = 0x17FF;
+pub const MONITOR_START: Word = 0x1800;
+pub const MONITOR_SIZE: usize = 0x800;
+
+// within $1700-$17FF bit 7 selects the RAM of a 6530 and bit 6 the 6530-002
+const RAM_SELECT: Word = 0x80;
+const RIOT_002_SELECT: Word = 0x40;
+const RIOT_RAM_SIZE: Word = 0x40;
+// the 6532 offsets the I/O registers by $80 and starts its timer through 1x4-1x7
+const RIOT_IO: Word = 0x80;
+const RIOT_TIMER_START: Word = 0x10;
+
+const SAD: Word = 0x00;
+const SBD: Word = 0x02;
+// the decoder outputs lighting the six digits, left to right
+const FIRST_DIGIT: Byte = 4;
+const DIGITS: Byte = 6;
+const KEYPAD_ROWS: Byte = 3;
+const KEYS_PER_ROW: Byte = 7;
+
+// the keys of the keypad; ST and RS are wired to NMI and RESET, which devices cannot drive
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Key {
+    Hex(Byte),
+    Address,
+    Data,
+    Plus,
+    Go,
+    Pc,
+}
+
+impl Key {
+    // the code the monitor's GETKEY returns for the key
+    fn code(&self) -> Byte {
+        return match self {
+            Key::Hex(value) => value & 0x0F,
+            Key::Address => 0x10,
+            Key::Data => 0x11,
+            Key::Plus => 0x12,
+            Key::Go => 0x13,
+            Key::Pc => 0x14,
+        };
+    }
+}
+
+// called with the digit, 0 being the leftmost, and its segments, bit 0 for segment a through
+// bit 6 for segment g, whenever the program selects a digit
+pub type DisplayCallback = Box<dyn FnMut(usize, Byte) + Send>;
+// asked for the key held down whenever the program reads a keypad row
+pub type KeypadCallback = Box<dyn FnMut() -> Option<Key> + Send>;
+
+// the $1700-$17FF page of the KIM-1 with its two 6530s, each a 6532 here since the timer and
+// ports behave alike. The 6530-003 has its I/O at $1700 and RAM at $1780, the 6530-002 its I/O
+// at $1740 and RAM at $17C0. Port B bits 1-4 of the 6530-002 feed the 74145 decoder that
+// selects a display digit or a keypad row, port A drives the segments or reads the row with a
+// pressed key pulling its column low. The interrupt outputs are only wired by jumpers, so
+// none are reported.
+struct Io {
+    riot_002: Riot,
+    riot_003: Riot,
+    display: DisplayCallback,
+    keypad: KeypadCallback,
+}
+
+impl Io {
+    fn new(display: DisplayCallback, keypad: KeypadCallback) -> Self {
+        return Io {
+            riot_002: Riot::new(),
+            riot_003: Riot::new(),
+            display,
+            keypad,
+        };
+    }
+
+    fn riot(&mut self, addr: Word) -> &mut Riot {
+        return match addr & RIOT_002_SELECT {
+            0 => &mut self.riot_003,
+            _ => &mut self.riot_002,
+        };
+    }
+
+    fn decoder(&self) -> Byte {
+        return (self.riot_002.get_pins(RiotPort::B) >> 1) & 0x0F;
+    }
+
+    fn is_keypad_port(&self, addr: Word) -> bool {
+        return addr & (RAM_SELECT | RIOT_002_SELECT) == RIOT_002_SELECT && addr & 0x07 == SAD;
+    }
+
+    fn scan_keypad(&mut self) {
+        let row = self.decoder();
+        let mut columns = 0xFF;
+        if row < KEYPAD_ROWS {
+            if let Some(key) = (self.keypad)() {
+                let code = key.code();
+                if code / KEYS_PER_ROW == row {
+                    // the monitor counts columns from bit 6 down
+                    columns &= !(0x40 >> (code % KEYS_PER_ROW));
+                }
+            }
+        }
+        self.riot_002.set_input(RiotPort::A, columns);
+    }
+
+    fn strobe_display(&mut self) {
+        let decoder = self.decoder();
+        if (FIRST_DIGIT..FIRST_DIGIT + DIGITS).contains(&decoder) {
+            let segments = self.riot_002.get_pins(RiotPort::A) & 0x7F;
+            (self.display)((decoder - FIRST_DIGIT) as usize, segments);
+        }
+    }
+}
+
+// the 6530 starts its timer through x4-x7 rather than 1x4-1x7
+fn io_offset(addr: Word, is_write: bool) -> Word {
+    let offset = addr & 0x0F;
+    if is_write && offset & 0x04 != 0 {
+        return RIOT_IO | RIOT_TIMER_START | offset;
+    }
+
+    return RIOT_IO | offset;
+}
+
+impl Bus for Io {
+    fn read(&mut self, addr: Word) -> Byte {
+        if addr & RAM_SELECT != 0 {
+            return self.riot(addr).read(addr % RIOT_RAM_SIZE);
+        }
+        if self.is_keypad_port(addr) {
+            self.scan_keypad();
+        }
+
+        return self.riot(addr).read(io_offset(addr, false));
+    }
+
+    fn write(&mut self, addr: Word, value: Byte) {
+        if addr & RAM_SELECT != 0 {
+            self.riot(addr).write(addr % RIOT_RAM_SIZE, value);
+            return;
+        }
+
+        self.riot(addr).write(io_offset(addr, true), value);
+        if addr & (RIOT_002_SELECT | 0x07) == RIOT_002_SELECT | SBD {
+            self.strobe_display();
+        }
+    }
+
+    fn peek(&mut self, addr: Word) -> Byte {
+        if addr & RAM_SELECT != 0 {
+            return self.riot(addr).peek(addr % RIOT_RAM_SIZE);
+        }
+
+        return self.riot(addr).peek(io_offset(addr, false));
+    }
+}
+
+impl Device for Io {
+    fn tick(&mut self, cycles: u64) {
+        self.riot_002.tick(cycles);
+        self.riot_003.tick(cycles);
+    }
+}
+
+// a stock KIM-1: 1K of RAM from $0000, the two 6530s in $1700-$17FF and the 2K monitor ROM at
+// $1800-$1FFF, the keypad and display part being the upper half. Only 13 address lines are
+// decoded, so the vectors at $FFFA-$FFFF come from the top of the monitor, which is mirrored at
+// $FC00-$FFFF for it.
+pub fn build<'a>(
+    monitor: Vec<Byte>,
+    display: DisplayCallback,
+    keypad: KeypadCallback,
+) -> Result<System<'a>, String> {
+    if monitor.len() != MONITOR_SIZE {
+        return Err("monitor ROM image is not 2K".to_string());
+    }
+
+    let mut map = MemoryMap::new();
+    map.map(0x0000..=RAM_END, Ram64k::new());
+    map.attach(RIOT_START..=RIOT_END, Io::new(display, keypad));
+    let vectors = monitor[MONITOR_SIZE / 2..].to_vec();
+    map.map(
+        MONITOR_START..=MONITOR_START + (MONITOR_SIZE - 1) as Word,
+        Rom::new(monitor),
+    );
+    map.map(0xFC00..=0xFFFF, Rom::new(vectors));
+
+    return Ok(System::new(CPU::with_memory(map)));
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,172 @@
+#[cfg(test)]
+mod memory_map {
+    use crate::{machines::kim1, memory::Bus};
+
+    fn monitor() -> Vec<u8> {
+        let mut rom = vec![0x03; 0x800];
+        rom[..0x400].fill(0x02);
+        rom[0x7FC] = 0x22;
+        rom[0x7FD] = 0x1C;
+
+        return rom;
+    }
+
+    #[test]
+    fn should_start_from_reset_vector_of_mirrored_monitor() {
+        let system = kim1::build(monitor(), Box::new(|_, _| ()), Box::new(|| None)).unwrap();
+
+        assert_eq!(system.get_cpu().get_program_counter(), 0x1C22);
+    }
+
+    #[test]
+    fn should_map_ram_riot_ram_and_monitor() {
+        let mut system = kim1::build(monitor(), Box::new(|_, _| ()), Box::new(|| None)).unwrap();
+        let map = system.get_memory_map_mut();
+
+        map.write(0x03FF, 0x42);
+        map.write(0x1780, 0x43);
+        map.write(0x17C0, 0x44);
+
+        assert_eq!(map.read(0x03FF), 0x42);
+        assert_eq!(map.read(0x1780), 0x43);
+        assert_eq!(map.read(0x17C0), 0x44);
+        assert_eq!(map.read(0x1800), 0x02);
+        assert_eq!(map.read(0x1C00), 0x03);
+        assert_eq!(map.read(0xFFFD), 0x1C);
+    }
+
+    #[test]
+    fn should_reject_wrong_monitor_size() {
+        let error = kim1::build(vec![0; 0x400], Box::new(|_, _| ()), Box::new(|| None))
+            .err()
+            .unwrap();
+
+        assert_eq!(error, "monitor ROM image is not 2K");
+    }
+}
+
+#[cfg(test)]
+mod display_and_keypad {
+    use std::sync::{Arc, Mutex};
+
+    use crate::{
+        machines::kim1::{self, Key},
+        memory::Bus,
+    };
+
+    #[test]
+    fn should_report_segments_of_selected_digit() {
+        let digits = Arc::new(Mutex::new(vec![]));
+        let recorded = digits.clone();
+        let mut system = kim1::build(
+            vec![0; 0x800],
+            Box::new(move |digit, segments| recorded.lock().unwrap().push((digit, segments))),
+            Box::new(|| None),
+        )
+        .unwrap();
+        let map = system.get_memory_map_mut();
+
+        map.write(0x1741, 0x7F);
+        map.write(0x1743, 0x1E);
+        map.write(0x1740, 0x06);
+        map.write(0x1742, 0x09);
+        map.write(0x1740, 0xED);
+        map.write(0x1742, 0x13);
+        // a keypad row lights nothing
+        map.write(0x1742, 0x01);
+
+        assert_eq!(*digits.lock().unwrap(), vec![(0, 0x06), (5, 0x6D)]);
+    }
+
+    #[test]
+    fn should_pull_column_of_pressed_key_low_in_its_row() {
+        let mut system = kim1::build(
+            vec![0; 0x800],
+            Box::new(|_, _| ()),
+            Box::new(|| Some(Key::Go)),
+        )
+        .unwrap();
+        let map = system.get_memory_map_mut();
+        map.write(0x1743, 0x1E);
+
+        let rows: Vec<u8> = [0x01, 0x03, 0x05, 0x07]
+            .iter()
+            .map(|&row| {
+                map.write(0x1742, row);
+                return map.read(0x1740);
+            })
+            .collect();
+
+        assert_eq!(rows, vec![0xFF, 0xFF, 0xFD, 0xFF]);
+    }
+
+    #[test]
+    fn should_place_hex_keys_from_bit_6_down() {
+        let mut system = kim1::build(
+            vec![0; 0x800],
+            Box::new(|_, _| ()),
+            Box::new(|| Some(Key::Hex(0x07))),
+        )
+        .unwrap();
+        let map = system.get_memory_map_mut();
+        map.write(0x1743, 0x1E);
+        map.write(0x1742, 0x03);
+
+        assert_eq!(map.read(0x1740), 0xBF);
+    }
+}
+
+#[cfg(test)]
+mod timers {
+    use crate::{machines::kim1, memory::Bus};
+
+    #[test]
+    fn should_start_6530_timer_through_x4_to_x7() {
+        let mut system =
+            kim1::build(vec![0; 0x800], Box::new(|_, _| ()), Box::new(|| None)).unwrap();
+        let map = system.get_memory_map_mut();
+
+        map.write(0x1704, 5);
+        map.tick(3);
+        assert_eq!(map.read(0x1706), 2);
+        map.tick(3);
+
+        assert_eq!(map.read(0x1707) & 0x80, 0x80);
+        assert!(!map.irq_asserted());
+    }
+}
+
+#[cfg(test)]
+mod system_integration {
+    use std::sync::{Arc, Mutex};
+
+    use crate::{cpu::emulation_event::EmulationEvent, machines::kim1};
+
+    // the monitor's way of lighting a digit: set port A and the decoder lines as outputs, put
+    // the segments on port A and select the digit through port B, LDA #$7F; STA PADD;
+    // LDA #$1E; STA PBDD; LDA #$5B; STA SAD; LDA #$09; STA SBD; BRK
+    #[test]
+    fn should_light_digit_from_monitor_code() {
+        let mut monitor = vec![0xEA; 0x800];
+        let program = [
+            0xA9, 0x7F, 0x8D, 0x41, 0x17, 0xA9, 0x1E, 0x8D, 0x43, 0x17, 0xA9, 0x5B, 0x8D, 0x40,
+            0x17, 0xA9, 0x09, 0x8D, 0x42, 0x17, 0x00,
+        ];
+        monitor[0x400..0x400 + program.len()].copy_from_slice(&program);
+        monitor[0x7FC] = 0x00;
+        monitor[0x7FD] = 0x1C;
+        let digits = Arc::new(Mutex::new(vec![]));
+        let recorded = digits.clone();
+        let mut system = kim1::build(
+            monitor,
+            Box::new(move |digit, segments| recorded.lock().unwrap().push((digit, segments))),
+            Box::new(|| None),
+        )
+        .unwrap();
+
+        let event = system.execute_until_break();
+
+        assert!(matches!(event, EmulationEvent::Trap(_)));
+        assert_eq!(*digits.lock().unwrap(), vec![(0, 0x5B)]);
+    }
+}
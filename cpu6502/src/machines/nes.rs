@@ -0,0 +1,226 @@
+use crate::{
+    consts::{Byte, Word},
+    cpu::CPU,
+    device::{Device, DmaTransfer},
+    memory::{memory_map::MemoryMap, rom::Rom, Bus, Ram64k},
+    system::System,
+};
+
+pub const CLOCK_HZ: u64 = 1_789_773;
+pub const RAM_END: Word = 0x1FFF;
+pub const RAM_SIZE: usize = 0x800;
+pub const PPU_START: Word = 0x2000;
+pub const PPU_END: Word = 0x3FFF;
+pub const APU_START: Word = 0x4000;
+pub const APU_END: Word = 0x4017;
+pub const PRG_RAM_START: Word = 0x6000;
+pub const PRG_RAM_END: Word = 0x7FFF;
+pub const PRG_ROM_START: Word = 0x8000;
+
+const INES_MAGIC: &[Byte] = b"NES\x1A";
+const INES_HEADER_SIZE: usize = 16;
+const INES_TRAINER_SIZE: usize = 512;
+const INES_FLAG_TRAINER: Byte = 0x04;
+const PRG_BANK_SIZE: usize = 0x4000;
+const CHR_BANK_SIZE: usize = 0x2000;
+
+const PPU_REGISTERS: usize = 8;
+const PPUSTATUS: Word = 2;
+const PPUSTATUS_VBLANK: Byte = 0x80;
+// PPUSTATUS drives its top three bits, the rest come from the PPU's own bus latch
+const PPUSTATUS_BITS: Byte = 0xE0;
+// NTSC: 262 scanlines of 341 dots, three dots to a CPU cycle, vertical blank starting on the
+// second dot of line 241 and ending on the second dot of the pre-render line
+const PPU_DOTS_PER_CYCLE: u64 = 3;
+const PPU_DOTS_PER_LINE: u32 = 341;
+const PPU_DOTS_PER_FRAME: u32 = 262 * PPU_DOTS_PER_LINE;
+const VBLANK_START: u32 = 241 * PPU_DOTS_PER_LINE + 1;
+const VBLANK_END: u32 = 261 * PPU_DOTS_PER_LINE + 1;
+
+const APU_STATUS: Word = 0x15;
+const OAM_DMA: Word = 0x14;
+const OAM_DATA: Word = 0x2004;
+const OAM_SIZE: Word = 0x100;
+
+// the parts of an iNES file the CPU side needs
+pub struct Cartridge {
+    pub mapper: Byte,
+    pub prg_rom: Vec<Byte>,
+    pub chr_rom: Vec<Byte>,
+}
+
+impl Cartridge {
+    pub fn parse(image: &[Byte]) -> Result<Self, String> {
+        if image.len() < INES_HEADER_SIZE || &image[..INES_MAGIC.len()] != INES_MAGIC {
+            return Err("not an iNES image".to_string());
+        }
+
+        let prg_size = image[4] as usize * PRG_BANK_SIZE;
+        let chr_size = image[5] as usize * CHR_BANK_SIZE;
+        let mapper = (image[7] & 0xF0) | (image[6] >> 4);
+        let mut prg_start = INES_HEADER_SIZE;
+        if image[6] & INES_FLAG_TRAINER != 0 {
+            prg_start += INES_TRAINER_SIZE;
+        }
+        let chr_start = prg_start + prg_size;
+        if image.len() < chr_start + chr_size {
+            return Err(format!(
+                "iNES image is truncated: {} bytes of {} expected",
+                image.len(),
+                chr_start + chr_size
+            ));
+        }
+
+        return Ok(Cartridge {
+            mapper,
+            prg_rom: image[prg_start..chr_start].to_vec(),
+            chr_rom: image[chr_start..chr_start + chr_size].to_vec(),
+        });
+    }
+}
+
+// the CPU side of the PPU without rendering: the eight registers repeat through $2000-$3FFF,
+// write-only ones read back the last value written to any of them and PPUSTATUS reports
+// vertical blank with NTSC frame timing, cleared by reading it. The NMI at vertical blank is
+// not raised, programs have to poll PPUSTATUS.
+struct Ppu {
+    latch: Byte,
+    status: Byte,
+    dot: u32,
+}
+
+impl Ppu {
+    fn new() -> Self {
+        return Ppu {
+            latch: 0,
+            status: 0,
+            dot: 0,
+        };
+    }
+
+    fn advance(&mut self) {
+        self.dot = (self.dot + 1) % PPU_DOTS_PER_FRAME;
+        match self.dot {
+            VBLANK_START => self.status |= PPUSTATUS_VBLANK,
+            VBLANK_END => self.status = 0,
+            _ => (),
+        };
+    }
+}
+
+impl Bus for Ppu {
+    fn read(&mut self, addr: Word) -> Byte {
+        if addr as usize % PPU_REGISTERS != PPUSTATUS as usize {
+            return self.latch;
+        }
+
+        let value = self.peek(addr);
+        self.latch = value;
+        self.status &= !PPUSTATUS_VBLANK;
+        return value;
+    }
+
+    fn write(&mut self, _addr: Word, value: Byte) {
+        self.latch = value;
+    }
+
+    fn peek(&mut self, addr: Word) -> Byte {
+        if addr as usize % PPU_REGISTERS != PPUSTATUS as usize {
+            return self.latch;
+        }
+
+        return (self.status & PPUSTATUS_BITS) | (self.latch & !PPUSTATUS_BITS);
+    }
+}
+
+impl Device for Ppu {
+    fn tick(&mut self, cycles: u64) {
+        for _ in 0..cycles * PPU_DOTS_PER_CYCLE {
+            self.advance();
+        }
+    }
+}
+
+// the APU and I/O registers of the 2A03 without sound or controllers. Only the status register
+// drives the data bus, reading no channel active; the rest read the CPU's open bus, so the
+// controllers report no button pressed. Writing a page to OAMDMA copies it to OAMDATA, the CPU
+// halted meanwhile.
+struct Apu {
+    dma_page: Word,
+    dma_offset: Word,
+}
+
+impl Apu {
+    fn new() -> Self {
+        return Apu {
+            dma_page: 0,
+            dma_offset: OAM_SIZE,
+        };
+    }
+}
+
+impl Bus for Apu {
+    fn read(&mut self, _addr: Word) -> Byte {
+        return 0;
+    }
+
+    fn write(&mut self, addr: Word, value: Byte) {
+        if addr == OAM_DMA {
+            self.dma_page = (value as Word) << 8;
+            self.dma_offset = 0;
+        }
+    }
+
+    fn is_mapped(&self, addr: Word) -> bool {
+        return addr == APU_STATUS;
+    }
+}
+
+impl Device for Apu {
+    fn tick(&mut self, _cycles: u64) {}
+
+    fn next_dma_transfer(&mut self) -> Option<DmaTransfer> {
+        if self.dma_offset == OAM_SIZE {
+            return None;
+        }
+
+        let transfer = DmaTransfer {
+            source: self.dma_page | self.dma_offset,
+            destination: OAM_DATA,
+        };
+        self.dma_offset += 1;
+        return Some(transfer);
+    }
+}
+
+// an NES for CPU tests such as nestest: the 2A03 with its 2K of RAM repeating through
+// $0000-$1FFF, the PPU and APU stubs above, 8K of PRG RAM at $6000 where test ROMs report
+// their results, and an NROM (mapper 0) cartridge at $8000, a 16K PRG ROM appearing twice.
+// The CPU takes its reset vector from the cartridge.
+pub fn build<'a>(cartridge: Cartridge) -> Result<System<'a>, String> {
+    if cartridge.mapper != 0 {
+        return Err(format!(
+            "mapper {} is not supported, only NROM (mapper 0)",
+            cartridge.mapper
+        ));
+    }
+    if cartridge.prg_rom.len() != PRG_BANK_SIZE && cartridge.prg_rom.len() != 2 * PRG_BANK_SIZE {
+        return Err(format!(
+            "NROM takes 16K or 32K of PRG ROM, not {} bytes",
+            cartridge.prg_rom.len()
+        ));
+    }
+
+    let mut map = MemoryMap::new();
+    map.map_mirrored(0x0000..=RAM_END, RAM_SIZE, Ram64k::new());
+    let ppu = map.attach(PPU_START..=PPU_END, Ppu::new());
+    map.set_mirror_size(ppu, PPU_REGISTERS);
+    map.attach(APU_START..=APU_END, Apu::new());
+    map.map(PRG_RAM_START..=PRG_RAM_END, Ram64k::new());
+    map.map(PRG_ROM_START..=0xFFFF, Rom::new(cartridge.prg_rom));
+
+    return Ok(System::new(CPU::new_ricoh_2a03(map)));
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,201 @@
+#[cfg(test)]
+mod ines {
+    use crate::machines::nes::Cartridge;
+
+    fn header(prg_banks: u8, chr_banks: u8, flags6: u8, flags7: u8) -> Vec<u8> {
+        let mut image = b"NES\x1A".to_vec();
+        image.extend([prg_banks, chr_banks, flags6, flags7]);
+        image.resize(16, 0);
+
+        return image;
+    }
+
+    #[test]
+    fn should_split_prg_and_chr_rom() {
+        let mut image = header(1, 1, 0x00, 0x00);
+        image.extend(vec![0x11; 0x4000]);
+        image.extend(vec![0x22; 0x2000]);
+
+        let cartridge = Cartridge::parse(&image).unwrap();
+
+        assert_eq!(cartridge.mapper, 0);
+        assert_eq!(cartridge.prg_rom, vec![0x11; 0x4000]);
+        assert_eq!(cartridge.chr_rom, vec![0x22; 0x2000]);
+    }
+
+    #[test]
+    fn should_skip_trainer_and_combine_mapper_nybbles() {
+        let mut image = header(1, 0, 0x14, 0x20);
+        image.extend(vec![0xFF; 512]);
+        image.extend(vec![0x11; 0x4000]);
+
+        let cartridge = Cartridge::parse(&image).unwrap();
+
+        assert_eq!(cartridge.mapper, 0x21);
+        assert_eq!(cartridge.prg_rom[0], 0x11);
+        assert!(cartridge.chr_rom.is_empty());
+    }
+
+    #[test]
+    fn should_reject_missing_magic() {
+        let error = Cartridge::parse(&[0; 32]).err().unwrap();
+
+        assert_eq!(error, "not an iNES image");
+    }
+
+    #[test]
+    fn should_reject_truncated_image() {
+        let mut image = header(2, 0, 0x00, 0x00);
+        image.extend(vec![0; 0x4000]);
+
+        let error = Cartridge::parse(&image).err().unwrap();
+
+        assert_eq!(
+            error,
+            "iNES image is truncated: 16400 bytes of 32784 expected"
+        );
+    }
+}
+
+#[cfg(test)]
+mod memory_map {
+    use crate::{
+        machines::nes::{self, Cartridge},
+        memory::Bus,
+    };
+
+    fn cartridge(prg_size: usize) -> Cartridge {
+        let mut prg_rom = vec![0xEA; prg_size];
+        prg_rom[prg_size - 4] = 0x00;
+        prg_rom[prg_size - 3] = 0xC0;
+
+        return Cartridge {
+            mapper: 0,
+            prg_rom,
+            chr_rom: vec![],
+        };
+    }
+
+    #[test]
+    fn should_mirror_16k_prg_rom_and_start_from_its_reset_vector() {
+        let mut system = nes::build(cartridge(0x4000)).unwrap();
+
+        assert_eq!(system.get_cpu().get_program_counter(), 0xC000);
+        assert_eq!(system.get_memory_map_mut().read(0xBFFD), 0xC0);
+    }
+
+    #[test]
+    fn should_mirror_internal_ram_and_map_prg_ram() {
+        let mut system = nes::build(cartridge(0x8000)).unwrap();
+        let map = system.get_memory_map_mut();
+
+        map.write(0x0001, 0x42);
+        map.write(0x6000, 0x43);
+
+        assert_eq!(map.read(0x1801), 0x42);
+        assert_eq!(map.read(0x6000), 0x43);
+    }
+
+    #[test]
+    fn should_read_last_written_value_from_write_only_ppu_registers() {
+        let mut system = nes::build(cartridge(0x4000)).unwrap();
+        let map = system.get_memory_map_mut();
+
+        map.write(0x3FF8, 0x9C);
+
+        assert_eq!(map.read(0x2005), 0x9C);
+        assert_eq!(map.read(0x2002), 0x1C);
+    }
+
+    #[test]
+    fn should_report_vertical_blank_until_status_read() {
+        let mut system = nes::build(cartridge(0x4000)).unwrap();
+        let map = system.get_memory_map_mut();
+
+        // vertical blank starts on dot 82182 of the frame, three dots to a cycle
+        map.tick(82_181 / 3);
+        assert_eq!(map.read(0x2002) & 0x80, 0x00);
+        map.tick(1);
+
+        assert_eq!(map.read(0x2002) & 0x80, 0x80);
+        assert_eq!(map.read(0x2002) & 0x80, 0x00);
+    }
+
+    #[test]
+    fn should_reject_other_mappers() {
+        let mut cartridge = cartridge(0x4000);
+        cartridge.mapper = 1;
+
+        let error = nes::build(cartridge).err().unwrap();
+
+        assert_eq!(error, "mapper 1 is not supported, only NROM (mapper 0)");
+    }
+
+    #[test]
+    fn should_reject_odd_prg_size() {
+        let error = nes::build(cartridge(0x2000)).err().unwrap();
+
+        assert_eq!(error, "NROM takes 16K or 32K of PRG ROM, not 8192 bytes");
+    }
+}
+
+#[cfg(test)]
+mod system_integration {
+    use crate::{
+        cpu::emulation_event::EmulationEvent,
+        machines::nes::{self, Cartridge},
+        memory::Bus,
+        system::System,
+    };
+
+    fn system<'a>(program: &[u8]) -> System<'a> {
+        let mut prg_rom = vec![0xEA; 0x4000];
+        prg_rom[..program.len()].copy_from_slice(program);
+        prg_rom[0x3FFC] = 0x00;
+        prg_rom[0x3FFD] = 0xC0;
+
+        return nes::build(Cartridge {
+            mapper: 0,
+            prg_rom,
+            chr_rom: vec![],
+        })
+        .unwrap();
+    }
+
+    // SED; CLC; LDA #$09; ADC #$01; STA $00; BRK
+    #[test]
+    fn should_add_in_binary_with_decimal_flag_set() {
+        let mut system = system(&[0xF8, 0x18, 0xA9, 0x09, 0x69, 0x01, 0x85, 0x00, 0x00]);
+
+        system.execute_until_break();
+
+        assert_eq!(system.get_memory_map_mut().read(0x0000), 0x0A);
+    }
+
+    // LDA $4016; STA $00; LDA #$FF; LDA $4015; STA $01; BRK
+    #[test]
+    fn should_read_open_bus_from_controller_ports() {
+        let mut system = system(&[
+            0xAD, 0x16, 0x40, 0x85, 0x00, 0xA9, 0xFF, 0xAD, 0x15, 0x40, 0x85, 0x01, 0x00,
+        ]);
+
+        let event = system.execute_until_break();
+
+        assert!(matches!(event, EmulationEvent::Trap(_)));
+        let map = system.get_memory_map_mut();
+        assert_eq!(map.read(0x0000), 0x40);
+        assert_eq!(map.read(0x0001), 0x00);
+    }
+
+    // LDA #$02; STA $4014; BRK, copying $0200-$02FF to OAMDATA
+    #[test]
+    fn should_halt_cpu_for_oam_dma() {
+        let mut system = system(&[0xA9, 0x02, 0x8D, 0x14, 0x40, 0x00]);
+        system.get_memory_map_mut().write(0x02FF, 0x5A);
+
+        system.execute_until_break();
+
+        assert_eq!(system.get_stolen_cycles(), 512);
+        assert_eq!(system.get_memory_map_mut().read(0x2004), 0x5A);
+    }
+}
@@ -0,0 +1,149 @@
+use std::ops::RangeInclusive;
+
+use super::{ColorRam, Stub};
+use crate::{
+    consts::{Byte, Word},
+    cpu::CPU,
+    device::{via::Via, Device},
+    memory::{memory_map::MemoryMap, rom::Rom, Bus, PartiallyMappedMemory, Ram64k},
+    system::System,
+};
+
+pub const CHARACTERS_START: Word = 0x8000;
+pub const CHARACTERS_SIZE: usize = 0x1000;
+pub const VIA1_START: Word = 0x9110;
+pub const VIA2_START: Word = 0x9120;
+pub const CARTRIDGE_START: Word = 0xA000;
+pub const CARTRIDGE_SIZE: usize = 0x2000;
+pub const BASIC_START: Word = 0xC000;
+pub const BASIC_SIZE: usize = 0x2000;
+pub const KERNAL_START: Word = 0xE000;
+pub const KERNAL_SIZE: usize = 0x2000;
+
+const RAM_END: Word = 0x7FFF;
+const LOW_RAM: RangeInclusive<Word> = 0x0000..=0x03FF;
+const MAIN_RAM: RangeInclusive<Word> = 0x1000..=0x1FFF;
+const BLOCK_0: RangeInclusive<Word> = 0x0400..=0x0FFF;
+const BLOCK_1: RangeInclusive<Word> = 0x2000..=0x3FFF;
+const BLOCK_2: RangeInclusive<Word> = 0x4000..=0x5FFF;
+const BLOCK_3: RangeInclusive<Word> = 0x6000..=0x7FFF;
+const VIC_START: Word = 0x9000;
+const VIC_END: Word = 0x90FF;
+const VIC_REGISTERS: usize = 0x10;
+const VIA_SIZE: Word = 0x10;
+const COLOR_RAM_START: Word = 0x9400;
+const COLOR_RAM_END: Word = 0x97FF;
+
+// RAM expansion cartridges, each filling some of the blocks the unexpanded machine leaves open
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub enum RamExpansion {
+    #[default]
+    None,
+    // block 0 at $0400-$0FFF
+    Expanded3K,
+    // block 1 at $2000-$3FFF
+    Expanded8K,
+    // blocks 1 and 2, $2000-$5FFF
+    Expanded16K,
+    // blocks 1 to 3, $2000-$7FFF
+    Expanded24K,
+    // every block, 35K of RAM in total
+    Full,
+}
+
+impl RamExpansion {
+    fn ranges(&self) -> Vec<RangeInclusive<Word>> {
+        let blocks = match self {
+            RamExpansion::None => vec![],
+            RamExpansion::Expanded3K => vec![BLOCK_0],
+            RamExpansion::Expanded8K => vec![BLOCK_1],
+            RamExpansion::Expanded16K => vec![BLOCK_1, BLOCK_2],
+            RamExpansion::Expanded24K => vec![BLOCK_1, BLOCK_2, BLOCK_3],
+            RamExpansion::Full => vec![BLOCK_0, BLOCK_1, BLOCK_2, BLOCK_3],
+        };
+
+        return [LOW_RAM, MAIN_RAM].into_iter().chain(blocks).collect();
+    }
+}
+
+// the ROM images and RAM configuration of the machine, the images supplied by the user
+pub struct Vic20Config {
+    pub basic: Vec<Byte>,
+    pub kernal: Vec<Byte>,
+    pub characters: Vec<Byte>,
+    pub expansion: RamExpansion,
+    // an image for block 5 at $A000-$BFFF, which the KERNAL starts when it carries the
+    // autostart signature
+    pub cartridge: Option<Vec<Byte>>,
+}
+
+// the interrupt output of VIA1 drives NMI on the VIC-20, which devices cannot raise, so the
+// line is left unconnected
+struct NmiVia(Via);
+
+impl Bus for NmiVia {
+    fn read(&mut self, addr: Word) -> Byte {
+        return self.0.read(addr);
+    }
+
+    fn write(&mut self, addr: Word, value: Byte) {
+        self.0.write(addr, value);
+    }
+
+    fn peek(&mut self, addr: Word) -> Byte {
+        return self.0.peek(addr);
+    }
+}
+
+impl Device for NmiVia {
+    fn tick(&mut self, cycles: u64) {
+        self.0.tick(cycles);
+    }
+}
+
+// a VIC-20 without video and sound: RAM as the expansion provides it with the blocks left
+// open reading the open bus, the character ROM at $8000, a VIC register stub at $9000, VIA1 at
+// $9110 and VIA2 at $9120 raising IRQ, color RAM at $9400 and BASIC and KERNAL at $C000 and
+// $E000. The CPU starts from the KERNAL reset vector.
+pub fn build<'a>(config: Vic20Config) -> Result<System<'a>, String> {
+    if config.basic.len() != BASIC_SIZE {
+        return Err("BASIC ROM image is not 8K".to_string());
+    }
+    if config.kernal.len() != KERNAL_SIZE {
+        return Err("KERNAL ROM image is not 8K".to_string());
+    }
+    if config.characters.len() != CHARACTERS_SIZE {
+        return Err("character ROM image is not 4K".to_string());
+    }
+
+    let mut map = MemoryMap::new();
+    let ram = PartiallyMappedMemory::new(Ram64k::new(), config.expansion.ranges());
+    map.map(0x0000..=RAM_END, ram);
+    map.map(
+        CHARACTERS_START..=CHARACTERS_START + (CHARACTERS_SIZE - 1) as Word,
+        Rom::new(config.characters),
+    );
+    map.map_mirrored(VIC_START..=VIC_END, VIC_REGISTERS, Stub::new(VIC_REGISTERS));
+    map.attach(VIA1_START..=VIA1_START + VIA_SIZE - 1, NmiVia(Via::new()));
+    map.attach(VIA2_START..=VIA2_START + VIA_SIZE - 1, Via::new());
+    map.map(COLOR_RAM_START..=COLOR_RAM_END, ColorRam::new(0x400));
+    if let Some(cartridge) = config.cartridge {
+        if cartridge.is_empty() {
+            return Err("cartridge image is empty".to_string());
+        }
+        if cartridge.len() > CARTRIDGE_SIZE {
+            return Err("cartridge image is larger than 8K".to_string());
+        }
+        map.map(
+            CARTRIDGE_START..=CARTRIDGE_START + (CARTRIDGE_SIZE - 1) as Word,
+            Rom::new(cartridge),
+        );
+    }
+    map.map(BASIC_START..=KERNAL_START - 1, Rom::new(config.basic));
+    map.map(KERNAL_START..=0xFFFF, Rom::new(config.kernal));
+
+    return Ok(System::new(CPU::with_memory(map)));
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,131 @@
+#[cfg(test)]
+mod memory_map {
+    use crate::{
+        machines::vic20::{self, RamExpansion, Vic20Config},
+        memory::Bus,
+    };
+
+    fn config(expansion: RamExpansion) -> Vic20Config {
+        let mut kernal = vec![0xEE; 0x2000];
+        kernal[0x1FFC] = 0x00;
+        kernal[0x1FFD] = 0xE0;
+
+        return Vic20Config {
+            basic: vec![0xBA; 0x2000],
+            kernal,
+            characters: vec![0xCC; 0x1000],
+            expansion,
+            cartridge: None,
+        };
+    }
+
+    // whether a byte written at `addr` reads back
+    fn is_ram(config: Vic20Config, addr: u16) -> bool {
+        let mut system = vic20::build(config).unwrap();
+        let map = system.get_memory_map_mut();
+        map.write(addr, 0x5A);
+
+        return map.read(addr) == 0x5A;
+    }
+
+    #[test]
+    fn should_leave_expansion_blocks_open_when_unexpanded() {
+        assert!(is_ram(config(RamExpansion::None), 0x0000));
+        assert!(is_ram(config(RamExpansion::None), 0x1FFF));
+        assert!(!is_ram(config(RamExpansion::None), 0x0400));
+        assert!(!is_ram(config(RamExpansion::None), 0x2000));
+    }
+
+    #[test]
+    fn should_fill_blocks_of_selected_expansion() {
+        assert!(is_ram(config(RamExpansion::Expanded3K), 0x0FFF));
+        assert!(!is_ram(config(RamExpansion::Expanded3K), 0x2000));
+        assert!(is_ram(config(RamExpansion::Expanded16K), 0x5FFF));
+        assert!(!is_ram(config(RamExpansion::Expanded16K), 0x6000));
+        assert!(is_ram(config(RamExpansion::Full), 0x0400));
+        assert!(is_ram(config(RamExpansion::Full), 0x7FFF));
+    }
+
+    #[test]
+    fn should_map_roms_and_io() {
+        let mut system = vic20::build(config(RamExpansion::None)).unwrap();
+        let map = system.get_memory_map_mut();
+
+        map.write(0x900F, 0x1B);
+        map.write(0x9400, 0xF6);
+        map.write(0x9123, 0xFF);
+
+        assert_eq!(map.read(0x8000), 0xCC);
+        assert_eq!(map.read(0x901F), 0x1B);
+        assert_eq!(map.read(0x9400), 0x06);
+        assert_eq!(map.read(0x9123), 0xFF);
+        assert_eq!(map.read(0xC000), 0xBA);
+        assert_eq!(map.read(0xE000), 0xEE);
+        assert_eq!(system.get_cpu().get_program_counter(), 0xE000);
+    }
+
+    #[test]
+    fn should_map_cartridge_in_block_5() {
+        let mut config = config(RamExpansion::None);
+        config.cartridge = Some(vec![0xCA; 0x2000]);
+        let mut system = vic20::build(config).unwrap();
+
+        assert_eq!(system.get_memory_map_mut().read(0xBFFF), 0xCA);
+    }
+
+    #[test]
+    fn should_reject_wrong_rom_size() {
+        let mut config = config(RamExpansion::None);
+        config.basic.truncate(0x1000);
+
+        let error = vic20::build(config).err().unwrap();
+
+        assert_eq!(error, "BASIC ROM image is not 8K");
+    }
+
+    #[test]
+    fn should_reject_empty_cartridge() {
+        let mut config = config(RamExpansion::None);
+        config.cartridge = Some(vec![]);
+
+        let error = vic20::build(config).err().unwrap();
+
+        assert_eq!(error, "cartridge image is empty");
+    }
+}
+
+#[cfg(test)]
+mod interrupts {
+    use crate::{
+        machines::vic20::{self, RamExpansion, Vic20Config},
+        memory::Bus,
+    };
+
+    fn config() -> Vic20Config {
+        return Vic20Config {
+            basic: vec![0; 0x2000],
+            kernal: vec![0; 0x2000],
+            characters: vec![0; 0x1000],
+            expansion: RamExpansion::None,
+            cartridge: None,
+        };
+    }
+
+    // enables timer 1 of the VIA at `base` with IRQ and lets it expire
+    fn expire_timer(base: u16) -> bool {
+        let mut system = vic20::build(config()).unwrap();
+        let map = system.get_memory_map_mut();
+        map.write(base + 0x0E, 0xC0);
+        map.write(base + 0x04, 0x01);
+        map.write(base + 0x05, 0x00);
+        map.tick(3);
+
+        return map.irq_asserted();
+    }
+
+    #[test]
+    fn should_raise_irq_from_via2_only() {
+        assert!(expire_timer(0x9120));
+        assert!(!expire_timer(0x9110));
+    }
+}
@@ -1,12 +1,144 @@
 use crate::consts::Word;
 
 use super::consts::Byte;
-use std::ops::{Index, IndexMut, Range};
+use std::cell::RefCell;
+use std::ops::{Index, IndexMut, Range, RangeInclusive};
+
+pub mod bank_register;
+pub mod controller;
+pub mod memory_map;
+pub mod rom;
+pub mod sync_bus;
+pub mod watchpoints;
 
 const MAX_MEMORY_KB: usize = 64 * 1024;
 
+// everything the CPU reads and writes goes through a bus; reads take `&mut self` so devices
+// behind it can react to being read (acknowledge an interrupt, advance a FIFO)
+pub trait Bus {
+    fn read(&mut self, addr: Word) -> Byte;
+
+    fn write(&mut self, addr: Word, value: Byte);
+
+    // whether any device answers at the address; the CPU does not read unmapped addresses and
+    // sees the open bus instead
+    fn is_mapped(&self, _addr: Word) -> bool {
+        return true;
+    }
+
+    // debugger access: no cycles pass and devices must not react, so inspecting an I/O
+    // register neither acknowledges an interrupt nor advances a FIFO. Buses whose reads and
+    // writes have side effects override these.
+    fn peek(&mut self, addr: Word) -> Byte {
+        return self.read(addr);
+    }
+
+    fn poke(&mut self, addr: Word, value: Byte) {
+        self.write(addr, value);
+    }
+}
+
+// plain storage without side effects, addressable cell by cell; every memory is a bus
 pub trait Memory: IndexMut<Word, Output = Byte> + Index<Word, Output = Byte> {}
 
+impl<M: Memory + ?Sized> Bus for M {
+    fn read(&mut self, addr: Word) -> Byte {
+        return self[addr];
+    }
+
+    fn write(&mut self, addr: Word, value: Byte) {
+        self[addr] = value;
+    }
+}
+
+// memory shared between several owners, e.g. a CPU and the devices or tooling inspecting it
+impl<B: Bus + ?Sized> Bus for &RefCell<B> {
+    fn read(&mut self, addr: Word) -> Byte {
+        return self.borrow_mut().read(addr);
+    }
+
+    fn write(&mut self, addr: Word, value: Byte) {
+        self.borrow_mut().write(addr, value);
+    }
+
+    fn is_mapped(&self, addr: Word) -> bool {
+        return self.borrow().is_mapped(addr);
+    }
+
+    fn peek(&mut self, addr: Word) -> Byte {
+        return self.borrow_mut().peek(addr);
+    }
+
+    fn poke(&mut self, addr: Word, value: Byte) {
+        self.borrow_mut().poke(addr, value);
+    }
+}
+
+// lets the bus be picked at runtime at the cost of dynamic dispatch
+impl Bus for Box<dyn Bus + '_> {
+    fn read(&mut self, addr: Word) -> Byte {
+        return (**self).read(addr);
+    }
+
+    fn write(&mut self, addr: Word, value: Byte) {
+        (**self).write(addr, value);
+    }
+
+    fn is_mapped(&self, addr: Word) -> bool {
+        return (**self).is_mapped(addr);
+    }
+
+    fn peek(&mut self, addr: Word) -> Byte {
+        return (**self).peek(addr);
+    }
+
+    fn poke(&mut self, addr: Word, value: Byte) {
+        (**self).poke(addr, value);
+    }
+}
+
+// what RAM holds at power-on; real DRAM comes up neither cleared nor random, often in blocks
+// of $00 and $FF, and programs reading memory they never wrote only misbehave with those
+#[derive(Default, Clone, Copy, PartialEq, Debug)]
+pub enum PowerOnPattern {
+    #[default]
+    Zero,
+    Ones,
+    // `block_size` bytes of $00 followed by `block_size` bytes of $FF, repeated
+    Alternating {
+        block_size: usize,
+    },
+    // the same contents for the same seed, so failures found with it can be replayed
+    Random {
+        seed: u64,
+    },
+}
+
+impl PowerOnPattern {
+    pub fn fill(&self, data: &mut [Byte]) {
+        match *self {
+            PowerOnPattern::Zero => data.fill(0x00),
+            PowerOnPattern::Ones => data.fill(0xFF),
+            PowerOnPattern::Alternating { block_size } => {
+                assert!(block_size > 0, "alternating blocks cannot be empty");
+                for (idx, chunk) in data.chunks_mut(block_size).enumerate() {
+                    chunk.fill(if idx % 2 == 0 { 0x00 } else { 0xFF });
+                }
+            }
+            PowerOnPattern::Random { seed } => {
+                // xorshift64, the same generator the program generator uses
+                let mut state = seed.max(1);
+                for value in data.iter_mut() {
+                    state ^= state << 13;
+                    state ^= state >> 7;
+                    state ^= state << 17;
+                    *value = (state >> 32) as Byte;
+                }
+            }
+        };
+    }
+}
+
 pub struct VecMemory {
     pub data: Vec<Byte>,
 }
@@ -18,6 +150,13 @@ impl VecMemory {
         };
     }
 
+    pub fn with_pattern(pattern: PowerOnPattern) -> Self {
+        let mut memory = VecMemory::new();
+        pattern.fill(&mut memory.data);
+
+        return memory;
+    }
+
     pub fn store(&mut self, payload: &[(Word, Byte)]) {
         for (address, value) in payload {
             let idx: usize = (*address).into();
@@ -70,3 +209,167 @@ impl From<&[(Word, Byte)]> for VecMemory {
         return res;
     }
 }
+
+// the whole 64 KiB address space backed by RAM, cleared at construction unless a power-on
+// pattern is given
+pub struct Ram64k {
+    data: Box<[Byte; MAX_MEMORY_KB]>,
+}
+
+impl Ram64k {
+    pub fn new() -> Self {
+        return Ram64k {
+            data: Box::new([0; MAX_MEMORY_KB]),
+        };
+    }
+
+    pub fn with_pattern(pattern: PowerOnPattern) -> Self {
+        let mut memory = Ram64k::new();
+        pattern.fill(memory.as_mut_slice());
+
+        return memory;
+    }
+
+    // copies `payload` starting at `at`, wrapping around to $0000 past $FFFF
+    pub fn load(&mut self, payload: &[Byte], at: Word) {
+        for (offset, value) in payload.iter().enumerate() {
+            self.data[at.wrapping_add(offset as Word) as usize] = *value;
+        }
+    }
+
+    pub fn fill(&mut self, range: RangeInclusive<Word>, value: Byte) {
+        self.data[*range.start() as usize..=*range.end() as usize].fill(value);
+    }
+
+    pub fn as_slice(&self) -> &[Byte] {
+        return &self.data[..];
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [Byte] {
+        return &mut self.data[..];
+    }
+}
+
+impl Default for Ram64k {
+    fn default() -> Self {
+        return Ram64k::new();
+    }
+}
+
+impl Memory for Ram64k {}
+
+impl Index<Word> for Ram64k {
+    type Output = Byte;
+
+    fn index(&self, idx: Word) -> &Self::Output {
+        return &self.data[idx as usize];
+    }
+}
+
+impl IndexMut<Word> for Ram64k {
+    fn index_mut(&mut self, idx: Word) -> &mut Self::Output {
+        return &mut self.data[idx as usize];
+    }
+}
+
+// partial address decoding: only the lowest `address_lines` bits reach the wrapped memory,
+// so on a 13-bit bus $2000 aliases $0000
+pub struct MaskedMemory<M: Bus> {
+    inner: M,
+    mask: Word,
+}
+
+impl<M: Bus> MaskedMemory<M> {
+    pub fn new(inner: M, address_lines: u8) -> Self {
+        let mask = match address_lines {
+            0..=15 => (1 << address_lines) - 1,
+            _ => Word::MAX,
+        };
+
+        return MaskedMemory { inner, mask };
+    }
+
+    pub fn get_mask(&self) -> Word {
+        return self.mask;
+    }
+
+    pub fn get_inner(&self) -> &M {
+        return &self.inner;
+    }
+
+    pub fn get_inner_mut(&mut self) -> &mut M {
+        return &mut self.inner;
+    }
+}
+
+impl<M: Bus> Bus for MaskedMemory<M> {
+    fn read(&mut self, addr: Word) -> Byte {
+        return self.inner.read(addr & self.mask);
+    }
+
+    fn write(&mut self, addr: Word, value: Byte) {
+        self.inner.write(addr & self.mask, value);
+    }
+
+    fn is_mapped(&self, addr: Word) -> bool {
+        return self.inner.is_mapped(addr & self.mask);
+    }
+
+    fn peek(&mut self, addr: Word) -> Byte {
+        return self.inner.peek(addr & self.mask);
+    }
+
+    fn poke(&mut self, addr: Word, value: Byte) {
+        self.inner.poke(addr & self.mask, value);
+    }
+}
+
+// only addresses inside the given ranges are backed by the wrapped memory, reads of the rest
+// see the open bus and writes to them are dropped
+pub struct PartiallyMappedMemory<M: Bus> {
+    inner: M,
+    mapped: Vec<RangeInclusive<Word>>,
+}
+
+impl<M: Bus> PartiallyMappedMemory<M> {
+    pub fn new(inner: M, mapped: Vec<RangeInclusive<Word>>) -> Self {
+        return PartiallyMappedMemory { inner, mapped };
+    }
+
+    pub fn get_inner(&self) -> &M {
+        return &self.inner;
+    }
+
+    pub fn get_inner_mut(&mut self) -> &mut M {
+        return &mut self.inner;
+    }
+}
+
+impl<M: Bus> Bus for PartiallyMappedMemory<M> {
+    fn read(&mut self, addr: Word) -> Byte {
+        return self.inner.read(addr);
+    }
+
+    fn write(&mut self, addr: Word, value: Byte) {
+        if self.is_mapped(addr) {
+            self.inner.write(addr, value);
+        }
+    }
+
+    fn is_mapped(&self, addr: Word) -> bool {
+        return self.mapped.iter().any(|range| range.contains(&addr)) && self.inner.is_mapped(addr);
+    }
+
+    fn peek(&mut self, addr: Word) -> Byte {
+        return self.inner.peek(addr);
+    }
+
+    fn poke(&mut self, addr: Word, value: Byte) {
+        if self.is_mapped(addr) {
+            self.inner.poke(addr, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,141 @@
+use std::ops::RangeInclusive;
+
+use super::Bus;
+use crate::consts::{Byte, Word};
+
+pub enum BankMapping {
+    // every window shows the bank selected by the latched value
+    Latched,
+    // receives the latched value and a window index, returns the bank shown in that window
+    Callback(Box<dyn Fn(Byte, usize) -> usize + Send>),
+}
+
+// a latch at `register_address` selects which bank of the backing store appears in each window;
+// banks are as large as the window they are shown in and wrap around the backing store.
+// Every other address is passed to the wrapped memory.
+pub struct BankRegister<M: Bus> {
+    inner: M,
+    register_address: Word,
+    latch: Byte,
+    windows: Vec<RangeInclusive<Word>>,
+    banks: Vec<Byte>,
+    mapping: BankMapping,
+}
+
+impl<M: Bus> BankRegister<M> {
+    pub fn new(inner: M, register_address: Word, banks: Vec<Byte>, mapping: BankMapping) -> Self {
+        return BankRegister {
+            inner,
+            register_address,
+            latch: 0,
+            windows: Vec::new(),
+            banks,
+            mapping,
+        };
+    }
+
+    pub fn add_window(&mut self, window: RangeInclusive<Word>) -> usize {
+        self.windows.push(window);
+        return self.windows.len() - 1;
+    }
+
+    pub fn get_latch(&self) -> Byte {
+        return self.latch;
+    }
+
+    pub fn set_latch(&mut self, value: Byte) {
+        self.latch = value;
+    }
+
+    pub fn get_inner(&self) -> &M {
+        return &self.inner;
+    }
+
+    pub fn get_banks(&self) -> &[Byte] {
+        return &self.banks;
+    }
+
+    pub fn selected_bank(&self, window_idx: usize) -> usize {
+        return match &self.mapping {
+            BankMapping::Latched => self.latch as usize,
+            BankMapping::Callback(cb) => cb(self.latch, window_idx),
+        };
+    }
+
+    fn backing_offset(&self, addr: Word) -> Option<usize> {
+        let (window_idx, window) = self
+            .windows
+            .iter()
+            .enumerate()
+            .find(|(_, window)| window.contains(&addr))?;
+        if self.banks.is_empty() {
+            return None;
+        }
+
+        let window_len = (*window.end() - *window.start()) as usize + 1;
+        let bank_start = self.selected_bank(window_idx) * window_len;
+        let offset = (addr - *window.start()) as usize;
+
+        return Some((bank_start + offset) % self.banks.len());
+    }
+}
+
+// writing the latch takes effect on the next access, so remapping is immediate for the CPU
+impl<M: Bus> Bus for BankRegister<M> {
+    fn read(&mut self, addr: Word) -> Byte {
+        if addr == self.register_address {
+            return self.latch;
+        }
+
+        return match self.backing_offset(addr) {
+            Some(offset) => self.banks[offset],
+            None => self.inner.read(addr),
+        };
+    }
+
+    fn write(&mut self, addr: Word, value: Byte) {
+        if addr == self.register_address {
+            self.latch = value;
+            return;
+        }
+
+        match self.backing_offset(addr) {
+            Some(offset) => self.banks[offset] = value,
+            None => self.inner.write(addr, value),
+        };
+    }
+
+    fn is_mapped(&self, addr: Word) -> bool {
+        if addr == self.register_address || self.backing_offset(addr).is_some() {
+            return true;
+        }
+
+        return self.inner.is_mapped(addr);
+    }
+
+    fn peek(&mut self, addr: Word) -> Byte {
+        if addr == self.register_address {
+            return self.latch;
+        }
+
+        return match self.backing_offset(addr) {
+            Some(offset) => self.banks[offset],
+            None => self.inner.peek(addr),
+        };
+    }
+
+    fn poke(&mut self, addr: Word, value: Byte) {
+        if addr == self.register_address {
+            self.latch = value;
+            return;
+        }
+
+        match self.backing_offset(addr) {
+            Some(offset) => self.banks[offset] = value,
+            None => self.inner.poke(addr, value),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,104 @@
+#[cfg(test)]
+mod index {
+    use crate::memory::{
+        bank_register::{BankMapping, BankRegister},
+        Bus, VecMemory,
+    };
+
+    fn banks() -> Vec<u8> {
+        return (0..4).flat_map(|bank| vec![bank; 0x1000]).collect();
+    }
+
+    #[test]
+    fn should_show_bank_selected_by_latch_in_window() {
+        let mut memory = BankRegister::new(VecMemory::new(), 0xDE00, banks(), BankMapping::Latched);
+        memory.add_window(0x8000..=0x8FFF);
+
+        assert_eq!(memory.read(0x8000), 0);
+        memory.write(0xDE00, 2);
+
+        assert_eq!(memory.read(0x8000), 2);
+        assert_eq!(memory.read(0x8FFF), 2);
+        assert_eq!(memory.get_latch(), 2);
+    }
+
+    #[test]
+    fn should_pass_addresses_outside_of_windows_to_wrapped_memory() {
+        let mut memory = BankRegister::new(VecMemory::new(), 0xDE00, banks(), BankMapping::Latched);
+        memory.add_window(0x8000..=0x8FFF);
+
+        memory.write(0x9000, 0x42);
+
+        assert_eq!(memory.get_inner().data[0x9000], 0x42);
+    }
+
+    #[test]
+    fn should_write_into_selected_bank_of_backing_store() {
+        let mut memory = BankRegister::new(VecMemory::new(), 0xDE00, banks(), BankMapping::Latched);
+        memory.add_window(0x8000..=0x8FFF);
+        memory.write(0xDE00, 1);
+
+        memory.write(0x8010, 0xAA);
+
+        assert_eq!(memory.get_banks()[0x1010], 0xAA);
+    }
+
+    #[test]
+    fn should_wrap_bank_numbers_around_backing_store() {
+        let mut memory = BankRegister::new(VecMemory::new(), 0xDE00, banks(), BankMapping::Latched);
+        memory.add_window(0x8000..=0x8FFF);
+
+        memory.write(0xDE00, 5);
+
+        assert_eq!(memory.read(0x8000), 1);
+    }
+
+    #[test]
+    fn should_map_windows_using_callback() {
+        // the second window always shows the bank following the first one
+        let mapping = BankMapping::Callback(Box::new(|latch, window| latch as usize + window));
+        let mut memory = BankRegister::new(VecMemory::new(), 0xDE00, banks(), mapping);
+        memory.add_window(0x8000..=0x8FFF);
+        memory.add_window(0xA000..=0xAFFF);
+
+        memory.write(0xDE00, 2);
+
+        assert_eq!(memory.read(0x8000), 2);
+        assert_eq!(memory.read(0xA000), 3);
+    }
+}
+
+#[cfg(test)]
+mod cpu_integration {
+    use std::cell::RefCell;
+
+    use crate::{
+        cpu::CPU,
+        memory::{
+            bank_register::{BankMapping, BankRegister},
+            Bus, VecMemory,
+        },
+    };
+
+    #[test]
+    fn should_remap_window_when_program_writes_bank_register() {
+        let mut inner = VecMemory::new();
+        inner.insert(0x0200, &[0xA9, 0x01, 0x8D, 0x00, 0xDE, 0xAD, 0x00, 0x80]); // LDA #1; STA $DE00; LDA $8000
+        let banks: Vec<u8> = (0..2).flat_map(|bank| vec![0x10 + bank; 0x1000]).collect();
+        let memory = RefCell::new(BankRegister::new(
+            inner,
+            0xDE00,
+            banks,
+            BankMapping::Latched,
+        ));
+        memory.borrow_mut().add_window(0x8000..=0x8FFF);
+        let mut cpu = CPU::new_nmos(&memory);
+        cpu.set_program_counter(0x0200);
+
+        cpu.execute_cycles(10);
+
+        assert_eq!(memory.borrow().get_latch(), 1);
+        assert_eq!(cpu.get_program_counter(), 0x0208);
+        assert_eq!(memory.borrow_mut().read(0x8000), 0x11);
+    }
+}
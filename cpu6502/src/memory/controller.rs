@@ -0,0 +1,221 @@
+use std::collections::HashSet;
+
+use super::Bus;
+use crate::consts::{Byte, Word};
+
+// value of the data line once all buttons have been shifted out, as on standard NES pads
+const SHIFT_EXHAUSTED: Byte = 0x01;
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Button {
+    Up,
+    Down,
+    Left,
+    Right,
+    Fire,
+    A,
+    B,
+    Select,
+    Start,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Polarity {
+    // pressed button reads as 1
+    ActiveHigh,
+    // pressed button reads as 0, as on Atari style joystick ports
+    ActiveLow,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum ControllerMode {
+    // every button has its own bit of the register
+    Parallel,
+    // writing the register latches the buttons, every read shifts the next one out on bit 0
+    StrobeShift,
+}
+
+// `layout` lists buttons by bit position in parallel mode and by shift order in strobe/shift
+// mode. Reading the register has side effects in strobe/shift mode; debugger peeks do not
+// advance the shift register.
+pub struct Controller<M: Bus> {
+    inner: M,
+    register_address: Word,
+    layout: Vec<Button>,
+    polarity: Polarity,
+    mode: ControllerMode,
+    pressed: HashSet<Button>,
+    strobe: Byte,
+    parallel: Byte,
+    latched: Vec<Byte>,
+    shift: usize,
+}
+
+impl<M: Bus> Controller<M> {
+    pub fn new(
+        inner: M,
+        register_address: Word,
+        layout: Vec<Button>,
+        polarity: Polarity,
+        mode: ControllerMode,
+    ) -> Self {
+        let mut controller = Controller {
+            inner,
+            register_address,
+            layout,
+            polarity,
+            mode,
+            pressed: HashSet::new(),
+            strobe: 0,
+            parallel: 0,
+            latched: Vec::new(),
+            shift: 0,
+        };
+        controller.update_parallel();
+        controller.latch_buttons();
+
+        return controller;
+    }
+
+    // standard NES pad: A, B, Select, Start, Up, Down, Left, Right shifted out after a strobe
+    pub fn new_nes(inner: M, register_address: Word) -> Self {
+        return Controller::new(
+            inner,
+            register_address,
+            vec![
+                Button::A,
+                Button::B,
+                Button::Select,
+                Button::Start,
+                Button::Up,
+                Button::Down,
+                Button::Left,
+                Button::Right,
+            ],
+            Polarity::ActiveHigh,
+            ControllerMode::StrobeShift,
+        );
+    }
+
+    pub fn set_button(&mut self, button: Button, pressed: bool) {
+        if pressed {
+            self.pressed.insert(button);
+        } else {
+            self.pressed.remove(&button);
+        }
+        self.update_parallel();
+        // while strobe is held the shift register keeps reloading
+        if self.strobe & 0x01 > 0 {
+            self.latch_buttons();
+        }
+    }
+
+    pub fn set_buttons(&mut self, pressed: &[Button]) {
+        self.pressed = pressed.iter().copied().collect();
+        self.update_parallel();
+        if self.strobe & 0x01 > 0 {
+            self.latch_buttons();
+        }
+    }
+
+    pub fn is_pressed(&self, button: Button) -> bool {
+        return self.pressed.contains(&button);
+    }
+
+    pub fn get_inner(&self) -> &M {
+        return &self.inner;
+    }
+
+    pub fn get_inner_mut(&mut self) -> &mut M {
+        return &mut self.inner;
+    }
+
+    fn button_bit(&self, button: Button) -> Byte {
+        let pressed = self.pressed.contains(&button);
+        return match (self.polarity, pressed) {
+            (Polarity::ActiveHigh, true) | (Polarity::ActiveLow, false) => 1,
+            _ => 0,
+        };
+    }
+
+    fn update_parallel(&mut self) {
+        self.parallel = self
+            .layout
+            .iter()
+            .take(8)
+            .enumerate()
+            .fold(0, |acc, (bit, button)| {
+                acc | self.button_bit(*button) << bit
+            });
+    }
+
+    fn latch_buttons(&mut self) {
+        self.latched = self
+            .layout
+            .iter()
+            .map(|button| self.button_bit(*button))
+            .collect();
+        self.latched.push(SHIFT_EXHAUSTED);
+        self.shift = 0;
+    }
+}
+
+impl<M: Bus> Bus for Controller<M> {
+    fn read(&mut self, addr: Word) -> Byte {
+        if addr != self.register_address {
+            return self.inner.read(addr);
+        }
+
+        return match self.mode {
+            ControllerMode::Parallel => self.parallel,
+            ControllerMode::StrobeShift if self.strobe & 0x01 > 0 => self.latched[0],
+            ControllerMode::StrobeShift => {
+                let position = self.shift;
+                self.shift = (position + 1).min(self.latched.len() - 1);
+                self.latched[position]
+            }
+        };
+    }
+
+    // any write to the register reloads the shift register; writing 1 then 0 leaves it
+    // loaded with the buttons at the last write
+    fn write(&mut self, addr: Word, value: Byte) {
+        if addr != self.register_address {
+            self.inner.write(addr, value);
+            return;
+        }
+
+        self.latch_buttons();
+        self.strobe = value;
+    }
+
+    fn is_mapped(&self, addr: Word) -> bool {
+        return addr == self.register_address || self.inner.is_mapped(addr);
+    }
+
+    // shows the bit the next read returns without shifting
+    fn peek(&mut self, addr: Word) -> Byte {
+        if addr != self.register_address {
+            return self.inner.peek(addr);
+        }
+
+        return match self.mode {
+            ControllerMode::Parallel => self.parallel,
+            ControllerMode::StrobeShift if self.strobe & 0x01 > 0 => self.latched[0],
+            ControllerMode::StrobeShift => self.latched[self.shift],
+        };
+    }
+
+    // sets the strobe latch without reloading the shift register
+    fn poke(&mut self, addr: Word, value: Byte) {
+        if addr != self.register_address {
+            self.inner.poke(addr, value);
+            return;
+        }
+
+        self.strobe = value;
+    }
+}
+
+#[cfg(test)]
+mod tests;
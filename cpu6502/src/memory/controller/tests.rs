@@ -0,0 +1,111 @@
+#[cfg(test)]
+mod parallel {
+    use crate::memory::{
+        controller::{Button, Controller, ControllerMode, Polarity},
+        Bus, VecMemory,
+    };
+
+    fn joystick(polarity: Polarity) -> Controller<VecMemory> {
+        return Controller::new(
+            VecMemory::new(),
+            0xDC00,
+            vec![
+                Button::Up,
+                Button::Down,
+                Button::Left,
+                Button::Right,
+                Button::Fire,
+            ],
+            polarity,
+            ControllerMode::Parallel,
+        );
+    }
+
+    #[test]
+    fn should_report_pressed_buttons_on_their_bits() {
+        let mut memory = joystick(Polarity::ActiveHigh);
+
+        memory.set_button(Button::Left, true);
+        memory.set_button(Button::Fire, true);
+
+        assert_eq!(memory.read(0xDC00), 0b00010100);
+    }
+
+    #[test]
+    fn should_clear_bits_of_pressed_buttons_when_active_low() {
+        let mut memory = joystick(Polarity::ActiveLow);
+
+        assert_eq!(memory.read(0xDC00), 0b00011111);
+        memory.set_buttons(&[Button::Up, Button::Fire]);
+
+        assert_eq!(memory.read(0xDC00), 0b00001110);
+    }
+
+    #[test]
+    fn should_pass_other_addresses_to_wrapped_memory() {
+        let mut memory = joystick(Polarity::ActiveHigh);
+
+        memory.write(0x0200, 0x42);
+
+        assert_eq!(memory.read(0x0200), 0x42);
+        assert_eq!(memory.get_inner()[0x0200], 0x42);
+    }
+}
+
+#[cfg(test)]
+mod strobe_shift {
+    use crate::memory::{
+        controller::{Button, Controller},
+        Bus, VecMemory,
+    };
+
+    #[test]
+    fn should_shift_out_buttons_latched_by_strobe() {
+        let mut memory = Controller::new_nes(VecMemory::new(), 0x4016);
+        memory.set_buttons(&[Button::A, Button::Start, Button::Right]);
+
+        memory.write(0x4016, 1);
+        memory.write(0x4016, 0);
+        let bits: Vec<u8> = (0..10).map(|_| memory.read(0x4016)).collect();
+
+        assert_eq!(bits, vec![1, 0, 0, 1, 0, 0, 0, 1, 1, 1]);
+    }
+
+    #[test]
+    fn should_keep_returning_first_button_while_strobe_is_held() {
+        let mut memory = Controller::new_nes(VecMemory::new(), 0x4016);
+        memory.write(0x4016, 1);
+
+        assert_eq!(memory.read(0x4016), 0);
+        memory.set_button(Button::A, true);
+
+        assert_eq!(memory.read(0x4016), 1);
+        assert_eq!(memory.read(0x4016), 1);
+    }
+
+    #[test]
+    fn should_ignore_button_changes_after_latching() {
+        let mut memory = Controller::new_nes(VecMemory::new(), 0x4016);
+        memory.write(0x4016, 1);
+        memory.write(0x4016, 0);
+
+        memory.set_button(Button::A, true);
+
+        assert_eq!(memory.read(0x4016), 0);
+        assert!(memory.is_pressed(Button::A));
+    }
+
+    #[test]
+    fn should_peek_next_bit_without_shifting() {
+        let mut memory = Controller::new_nes(VecMemory::new(), 0x4016);
+        memory.set_buttons(&[Button::B]);
+        memory.write(0x4016, 1);
+        memory.write(0x4016, 0);
+        memory.read(0x4016);
+
+        assert_eq!(memory.peek(0x4016), 1);
+        assert_eq!(memory.peek(0x4016), 1);
+        assert_eq!(memory.read(0x4016), 1);
+        assert_eq!(memory.read(0x4016), 0);
+    }
+}
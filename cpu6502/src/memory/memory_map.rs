@@ -0,0 +1,375 @@
+use std::ops::RangeInclusive;
+
+use super::Bus;
+use crate::{
+    consts::{Byte, Word},
+    device::{Device, DmaTransfer},
+};
+
+const PAGE_COUNT: usize = 256;
+
+// what accesses to addresses no region covers do
+#[derive(Default, Clone, Copy, PartialEq, Debug)]
+pub enum UnmappedPolicy {
+    // reported as unmapped, the CPU reads its open bus; writes are dropped
+    #[default]
+    OpenBus,
+    // reads return a fixed value, e.g. $FF from pull-ups; writes are dropped
+    Value(Byte),
+    // any access is a bug in the machine description
+    Panic,
+}
+
+// memory and other buses without a clock of their own, stored next to devices
+struct Unclocked<B: Bus>(B);
+
+impl<B: Bus> Bus for Unclocked<B> {
+    fn read(&mut self, addr: Word) -> Byte {
+        return self.0.read(addr);
+    }
+
+    fn write(&mut self, addr: Word, value: Byte) {
+        self.0.write(addr, value);
+    }
+
+    fn is_mapped(&self, addr: Word) -> bool {
+        return self.0.is_mapped(addr);
+    }
+
+    fn peek(&mut self, addr: Word) -> Byte {
+        return self.0.peek(addr);
+    }
+
+    fn poke(&mut self, addr: Word, value: Byte) {
+        self.0.poke(addr, value);
+    }
+}
+
+impl<B: Bus> Device for Unclocked<B> {
+    fn tick(&mut self, _cycles: u64) {}
+}
+
+struct Region<'a> {
+    range: RangeInclusive<Word>,
+    // backing stores the region can show, only the selected one is visible
+    banks: Vec<Box<dyn Device + 'a>>,
+    selected_bank: usize,
+    // offsets repeat every this many bytes through the region
+    mirror_size: Option<usize>,
+    // where writes land instead of the selected bank, e.g. RAM underneath a ROM
+    write_target: Option<Box<dyn Bus + 'a>>,
+}
+
+impl<'a> Region<'a> {
+    fn bus(&mut self) -> &mut (dyn Bus + 'a) {
+        return &mut *self.banks[self.selected_bank];
+    }
+
+    fn write_bus(&mut self) -> &mut (dyn Bus + 'a) {
+        return match self.write_target.as_mut() {
+            Some(bus) => &mut **bus,
+            None => &mut *self.banks[self.selected_bank],
+        };
+    }
+}
+
+// writes to `range` select the bank of `region` instead of reaching memory; the written value
+// wraps around the number of banks
+struct BankLatch {
+    range: RangeInclusive<Word>,
+    region: usize,
+}
+
+// composes ROM, RAM and devices into one bus, optionally bank switched. Each region sees
+// addresses relative to its own start, so a 16-byte device answers $00-$0F wherever it is
+// mapped. Regions mapped later take precedence where they overlap earlier ones.
+pub struct MemoryMap<'a> {
+    regions: Vec<Region<'a>>,
+    // regions holding attached devices, the ones `tick` advances
+    devices: Vec<usize>,
+    // for every page, the regions touching it with the most recently mapped first
+    pages: Vec<Vec<usize>>,
+    bank_latches: Vec<BankLatch>,
+    unmapped_policy: UnmappedPolicy,
+}
+
+impl<'a> MemoryMap<'a> {
+    pub fn new() -> Self {
+        return MemoryMap {
+            regions: Vec::new(),
+            devices: Vec::new(),
+            pages: vec![Vec::new(); PAGE_COUNT],
+            bank_latches: Vec::new(),
+            unmapped_policy: UnmappedPolicy::default(),
+        };
+    }
+
+    pub fn map(&mut self, range: RangeInclusive<Word>, bus: impl Bus + 'a) -> usize {
+        return self.add_region(range, vec![Box::new(Unclocked(bus))]);
+    }
+
+    // maps a device whose clock `tick` advances and whose IRQ output `irq_asserted` reports
+    pub fn attach(&mut self, range: RangeInclusive<Word>, device: impl Device + 'a) -> usize {
+        let id = self.add_region(range, vec![Box::new(device)]);
+        self.devices.push(id);
+
+        return id;
+    }
+
+    // advances every attached device, bringing them up to the CPU after it ran `cycles`
+    pub fn tick(&mut self, cycles: u64) {
+        for id in &self.devices {
+            self.regions[*id].banks[0].tick(cycles);
+        }
+    }
+
+    pub fn irq_asserted(&self) -> bool {
+        return self
+            .devices
+            .iter()
+            .any(|id| self.regions[*id].banks[0].irq_asserted());
+    }
+
+    // exit code of the first attached device that asked to end the emulation
+    pub fn exit_code(&self) -> Option<i32> {
+        return self
+            .devices
+            .iter()
+            .find_map(|id| self.regions[*id].banks[0].exit_code());
+    }
+
+    // the byte the first attached device holding the bus wants moved next
+    pub fn next_dma_transfer(&mut self) -> Option<DmaTransfer> {
+        let regions = &mut self.regions;
+
+        return self
+            .devices
+            .iter()
+            .find_map(|id| regions[*id].banks[0].next_dma_transfer());
+    }
+
+    pub fn get_device_mut(&mut self, id: usize) -> Option<&mut (dyn Device + 'a)> {
+        if !self.devices.contains(&id) {
+            return None;
+        }
+
+        return Some(&mut *self.regions[id].banks[0]);
+    }
+
+    // a region switching between several backing stores, showing the first one until
+    // `set_bank` or a bank latch selects another
+    pub fn map_banked(
+        &mut self,
+        range: RangeInclusive<Word>,
+        banks: Vec<Box<dyn Bus + 'a>>,
+    ) -> usize {
+        assert!(!banks.is_empty(), "a banked region needs at least one bank");
+        let banks = banks
+            .into_iter()
+            .map(|bank| Box::new(Unclocked(bank)) as Box<dyn Device + 'a>)
+            .collect();
+
+        return self.add_region(range, banks);
+    }
+
+    // `bus` answers the first `size` bytes of the region and repeats through the rest of it,
+    // e.g. 2 KiB of RAM through $0000-$1FFF or 16 I/O registers through a whole page
+    pub fn map_mirrored(
+        &mut self,
+        range: RangeInclusive<Word>,
+        size: usize,
+        bus: impl Bus + 'a,
+    ) -> usize {
+        let id = self.map(range, bus);
+        self.set_mirror_size(id, size);
+
+        return id;
+    }
+
+    // reads come from `read` while writes go to `write`, like the C64 where the CPU sees BASIC
+    // and KERNAL ROM but stores into the RAM underneath them. Both see the same offsets.
+    pub fn map_overlay(
+        &mut self,
+        range: RangeInclusive<Word>,
+        read: impl Bus + 'a,
+        write: impl Bus + 'a,
+    ) -> usize {
+        let id = self.map(range, read);
+        self.set_write_target(id, write);
+
+        return id;
+    }
+
+    // writes to the region land in `bus` whichever bank is selected; sharing it with one of
+    // the banks lets a program read back what it wrote once that bank is switched in
+    pub fn set_write_target(&mut self, region: usize, bus: impl Bus + 'a) {
+        if let Some(region) = self.regions.get_mut(region) {
+            region.write_target = Some(Box::new(bus));
+        }
+    }
+
+    pub fn set_mirror_size(&mut self, region: usize, size: usize) {
+        assert!(size > 0, "mirrored block cannot be empty");
+        if let Some(region) = self.regions.get_mut(region) {
+            region.mirror_size = Some(size);
+        }
+    }
+
+    pub fn add_bank_latch(&mut self, range: RangeInclusive<Word>, region: usize) {
+        self.bank_latches.push(BankLatch { range, region });
+    }
+
+    // selecting a bank past the last one wraps around
+    pub fn set_bank(&mut self, region: usize, bank: usize) {
+        if let Some(region) = self.regions.get_mut(region) {
+            region.selected_bank = bank % region.banks.len();
+        }
+    }
+
+    pub fn get_bank(&self, region: usize) -> Option<usize> {
+        return self.regions.get(region).map(|region| region.selected_bank);
+    }
+
+    pub fn get_bank_count(&self, region: usize) -> Option<usize> {
+        return self.regions.get(region).map(|region| region.banks.len());
+    }
+
+    pub fn get_range(&self, id: usize) -> Option<RangeInclusive<Word>> {
+        return self.regions.get(id).map(|region| region.range.clone());
+    }
+
+    // the bank currently selected in the region
+    pub fn get_region_mut(&mut self, id: usize) -> Option<&mut (dyn Bus + 'a)> {
+        return self.regions.get_mut(id).map(|region| region.bus());
+    }
+
+    pub fn get_bank_mut(&mut self, id: usize, bank: usize) -> Option<&mut (dyn Bus + 'a)> {
+        let region = self.regions.get_mut(id)?;
+
+        return region
+            .banks
+            .get_mut(bank)
+            .map(|bus| &mut **bus as &mut (dyn Bus + 'a));
+    }
+
+    pub fn set_unmapped_policy(&mut self, policy: UnmappedPolicy) {
+        self.unmapped_policy = policy;
+    }
+
+    pub fn get_unmapped_policy(&self) -> UnmappedPolicy {
+        return self.unmapped_policy;
+    }
+
+    // the region answering at `addr`, if any
+    pub fn region_at(&self, addr: Word) -> Option<usize> {
+        return self.pages[(addr >> 8) as usize]
+            .iter()
+            .copied()
+            .find(|id| self.regions[*id].range.contains(&addr));
+    }
+
+    fn add_region(
+        &mut self,
+        range: RangeInclusive<Word>,
+        banks: Vec<Box<dyn Device + 'a>>,
+    ) -> usize {
+        let id = self.regions.len();
+        let first_page = (*range.start() >> 8) as usize;
+        let last_page = (*range.end() >> 8) as usize;
+        for page in first_page..=last_page {
+            self.pages[page].insert(0, id);
+        }
+        self.regions.push(Region {
+            range,
+            banks,
+            selected_bank: 0,
+            mirror_size: None,
+            write_target: None,
+        });
+
+        return id;
+    }
+
+    fn resolve(&self, addr: Word) -> Option<(usize, Word)> {
+        let id = self.region_at(addr)?;
+        let region = &self.regions[id];
+        let offset = addr - *region.range.start();
+
+        return match region.mirror_size {
+            Some(size) => Some((id, (offset as usize % size) as Word)),
+            None => Some((id, offset)),
+        };
+    }
+
+    fn unmapped_access(&self, addr: Word) -> Byte {
+        return match self.unmapped_policy {
+            UnmappedPolicy::OpenBus => 0,
+            UnmappedPolicy::Value(value) => value,
+            UnmappedPolicy::Panic => panic!("access to unmapped address ${addr:04X}"),
+        };
+    }
+}
+
+impl Default for MemoryMap<'_> {
+    fn default() -> Self {
+        return MemoryMap::new();
+    }
+}
+
+impl Bus for MemoryMap<'_> {
+    fn read(&mut self, addr: Word) -> Byte {
+        return match self.resolve(addr) {
+            Some((id, offset)) => self.regions[id].bus().read(offset),
+            None => self.unmapped_access(addr),
+        };
+    }
+
+    fn write(&mut self, addr: Word, value: Byte) {
+        if let Some(latch) = self
+            .bank_latches
+            .iter()
+            .find(|latch| latch.range.contains(&addr))
+        {
+            self.set_bank(latch.region, value as usize);
+            return;
+        }
+
+        match self.resolve(addr) {
+            Some((id, offset)) => self.regions[id].write_bus().write(offset, value),
+            None => {
+                self.unmapped_access(addr);
+            }
+        };
+    }
+
+    fn is_mapped(&self, addr: Word) -> bool {
+        return match self.resolve(addr) {
+            Some((id, offset)) => {
+                let region = &self.regions[id];
+                region.banks[region.selected_bank].is_mapped(offset)
+            }
+            None => self.unmapped_policy != UnmappedPolicy::OpenBus,
+        };
+    }
+
+    fn peek(&mut self, addr: Word) -> Byte {
+        return match self.resolve(addr) {
+            Some((id, offset)) => self.regions[id].bus().peek(offset),
+            None => match self.unmapped_policy {
+                UnmappedPolicy::Value(value) => value,
+                _ => 0,
+            },
+        };
+    }
+
+    // bank latches only react to the CPU, a poke reaches the memory mapped underneath; like a
+    // write, it lands in the write target of an overlay
+    fn poke(&mut self, addr: Word, value: Byte) {
+        if let Some((id, offset)) = self.resolve(addr) {
+            self.regions[id].write_bus().poke(offset, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
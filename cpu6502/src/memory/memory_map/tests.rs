@@ -0,0 +1,488 @@
+#[cfg(test)]
+mod dispatch {
+    use std::cell::RefCell;
+
+    use crate::memory::{memory_map::MemoryMap, Bus, Ram64k, VecMemory};
+
+    #[test]
+    fn should_pass_addresses_relative_to_region_start() {
+        let device = RefCell::new(VecMemory::new());
+        let mut map = MemoryMap::new();
+        map.map(0x6000..=0x600F, &device);
+
+        map.write(0x6003, 0x42);
+
+        assert_eq!(device.borrow()[0x0003], 0x42);
+        assert_eq!(map.read(0x6003), 0x42);
+    }
+
+    #[test]
+    fn should_prefer_most_recently_mapped_region_where_regions_overlap() {
+        let mut map = MemoryMap::new();
+        let ram = map.map(0x0000..=0xFFFF, Ram64k::new());
+        let io = map.map(0xD000..=0xD0FF, Ram64k::new());
+
+        map.write(0xD010, 0x42);
+
+        assert_eq!(map.region_at(0xCFFF), Some(ram));
+        assert_eq!(map.region_at(0xD010), Some(io));
+        assert_eq!(map.region_at(0xD100), Some(ram));
+        assert_eq!(map.get_region_mut(io).unwrap().read(0x0010), 0x42);
+        assert_eq!(map.get_region_mut(ram).unwrap().read(0xD010), 0x00);
+    }
+
+    #[test]
+    fn should_resolve_regions_sharing_a_page() {
+        let mut map = MemoryMap::new();
+        let first = map.map(0x6000..=0x600F, Ram64k::new());
+        let second = map.map(0x6010..=0x601F, Ram64k::new());
+
+        assert_eq!(map.region_at(0x600F), Some(first));
+        assert_eq!(map.region_at(0x6010), Some(second));
+        assert_eq!(map.region_at(0x6020), None);
+    }
+}
+
+#[cfg(test)]
+mod unmapped {
+    use crate::memory::{
+        memory_map::{MemoryMap, UnmappedPolicy},
+        Bus, Ram64k,
+    };
+
+    fn map() -> MemoryMap<'static> {
+        let mut map = MemoryMap::new();
+        map.map(0x0000..=0x07FF, Ram64k::new());
+        return map;
+    }
+
+    #[test]
+    fn should_report_gaps_as_unmapped_by_default() {
+        let map = map();
+
+        assert!(map.is_mapped(0x07FF));
+        assert!(!map.is_mapped(0x0800));
+    }
+
+    #[test]
+    fn should_read_fixed_value_from_gaps() {
+        let mut map = map();
+        map.set_unmapped_policy(UnmappedPolicy::Value(0xFF));
+
+        map.write(0x4000, 0x00);
+
+        assert!(map.is_mapped(0x4000));
+        assert_eq!(map.read(0x4000), 0xFF);
+    }
+
+    #[test]
+    #[should_panic(expected = "access to unmapped address $4000")]
+    fn should_panic_on_access_to_gap() {
+        let mut map = map();
+        map.set_unmapped_policy(UnmappedPolicy::Panic);
+
+        map.read(0x4000);
+    }
+}
+
+#[cfg(test)]
+mod cpu_integration {
+    use crate::{
+        cpu::{open_bus::OpenBus, CPU},
+        memory::{memory_map::MemoryMap, Ram64k},
+    };
+
+    #[test]
+    fn should_run_from_rom_region_and_store_into_ram_region() {
+        // 32 KiB of ROM at $8000: LDA $4000 (unmapped); STA $10; vectors at the end
+        let mut rom = Ram64k::new();
+        rom.load(&[0xAD, 0x00, 0x40, 0x85, 0x10], 0x0000);
+        rom.load(&[0x00, 0x80], 0x7FFC);
+        let mut map = MemoryMap::new();
+        let ram = map.map(0x0000..=0x07FF, Ram64k::new());
+        map.map(0x8000..=0xFFFF, rom);
+        let mut cpu = CPU::with_memory(map);
+        cpu.set_open_bus(OpenBus::Constant(0x5A));
+
+        cpu.execute_next_instruction();
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.get_accumulator(), 0x5A);
+        let ram = cpu.get_memory_mut().get_region_mut(ram).unwrap();
+        assert_eq!(ram.read(0x0010), 0x5A);
+    }
+}
+
+#[cfg(test)]
+mod banks {
+    use crate::{
+        cpu::CPU,
+        memory::{memory_map::MemoryMap, Bus, Ram64k},
+    };
+
+    fn bank(fill: u8) -> Box<dyn Bus> {
+        let mut memory = Ram64k::new();
+        memory.fill(0x0000..=0x1FFF, fill);
+        return Box::new(memory);
+    }
+
+    #[test]
+    fn should_show_selected_bank_and_wrap_selection() {
+        let mut map = MemoryMap::new();
+        let cartridge = map.map_banked(0x8000..=0x9FFF, vec![bank(0x10), bank(0x11), bank(0x12)]);
+
+        assert_eq!(map.read(0x8000), 0x10);
+        map.set_bank(cartridge, 2);
+        assert_eq!(map.read(0x9FFF), 0x12);
+        map.set_bank(cartridge, 4);
+        assert_eq!(map.get_bank(cartridge), Some(1));
+        assert_eq!(map.read(0x8000), 0x11);
+    }
+
+    #[test]
+    fn should_keep_contents_of_banks_switched_out() {
+        let mut map = MemoryMap::new();
+        let region = map.map_banked(0x4000..=0x5FFF, vec![bank(0x00), bank(0x00)]);
+
+        map.write(0x4000, 0x42);
+        map.set_bank(region, 1);
+        map.write(0x4000, 0x24);
+        map.set_bank(region, 0);
+
+        assert_eq!(map.read(0x4000), 0x42);
+        assert_eq!(map.get_bank_mut(region, 1).unwrap().read(0x0000), 0x24);
+    }
+
+    #[test]
+    fn should_switch_bank_when_program_writes_latch() {
+        // LDA #1; STA $DE00; LDA $8000
+        let mut ram = Ram64k::new();
+        ram.load(&[0xA9, 0x01, 0x8D, 0x00, 0xDE, 0xAD, 0x00, 0x80], 0x0200);
+        let mut map = MemoryMap::new();
+        map.map(0x0000..=0xFFFF, ram);
+        let cartridge = map.map_banked(0x8000..=0x9FFF, vec![bank(0x10), bank(0x11)]);
+        map.add_bank_latch(0xDE00..=0xDE00, cartridge);
+        let mut cpu = CPU::with_memory(map);
+        cpu.set_program_counter(0x0200);
+
+        cpu.execute_next_instruction();
+        cpu.execute_next_instruction();
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.get_memory().get_bank(cartridge), Some(1));
+        assert_eq!(cpu.get_accumulator(), 0x11);
+    }
+}
+
+#[cfg(test)]
+mod overlay {
+    use std::cell::RefCell;
+
+    use crate::{
+        cpu::CPU,
+        memory::{memory_map::MemoryMap, rom::Rom, Bus, Ram64k, VecMemory},
+    };
+
+    fn basic_rom() -> Rom {
+        return Rom::new(vec![0x94; 0x2000]);
+    }
+
+    #[test]
+    fn should_read_rom_and_write_ram_underneath() {
+        let ram = RefCell::new(VecMemory::new());
+        let mut map = MemoryMap::new();
+        map.map_overlay(0xA000..=0xBFFF, basic_rom(), &ram);
+
+        map.write(0xA010, 0x42);
+
+        assert_eq!(map.read(0xA010), 0x94);
+        assert_eq!(ram.borrow()[0x0010], 0x42);
+    }
+
+    #[test]
+    fn should_poke_into_write_target() {
+        let ram = RefCell::new(VecMemory::new());
+        let mut map = MemoryMap::new();
+        map.map_overlay(0xA000..=0xBFFF, basic_rom(), &ram);
+
+        map.poke(0xA000, 0x42);
+
+        assert_eq!(map.peek(0xA000), 0x94);
+        assert_eq!(ram.borrow()[0x0000], 0x42);
+    }
+
+    #[test]
+    fn should_read_back_ram_once_rom_is_banked_out() {
+        // LDA #$42; STA $A000; LDA #1; STA $0001; LDA $A000
+        let mut program = Ram64k::new();
+        program.load(
+            &[
+                0xA9, 0x42, 0x8D, 0x00, 0xA0, 0xA9, 0x01, 0x8D, 0x01, 0x00, 0xAD, 0x00, 0xA0,
+            ],
+            0x0200,
+        );
+        let ram = RefCell::new(VecMemory::new());
+        let mut map = MemoryMap::new();
+        map.map(0x0000..=0xFFFF, program);
+        let basic = map.map_banked(0xA000..=0xBFFF, vec![Box::new(basic_rom()), Box::new(&ram)]);
+        map.set_write_target(basic, &ram);
+        map.add_bank_latch(0x0001..=0x0001, basic);
+        let mut cpu = CPU::with_memory(map);
+        cpu.set_program_counter(0x0200);
+
+        (0..5).for_each(|_| {
+            cpu.execute_next_instruction();
+        });
+
+        assert_eq!(cpu.get_memory().get_bank(basic), Some(1));
+        assert_eq!(cpu.get_accumulator(), 0x42);
+    }
+}
+
+#[cfg(test)]
+mod devices {
+    use std::cell::RefCell;
+
+    use crate::{
+        consts::{Byte, Word},
+        cpu::CPU,
+        device::Device,
+        memory::{memory_map::MemoryMap, Bus, Ram64k},
+    };
+
+    // counts down once per cycle from the value written to register 0 and holds IRQ while
+    // expired, until register 1 is read
+    #[derive(Default)]
+    struct Timer {
+        counter: u64,
+        expired: bool,
+    }
+
+    impl Bus for Timer {
+        fn read(&mut self, addr: Word) -> Byte {
+            if addr == 1 {
+                let status = self.expired as Byte;
+                self.expired = false;
+                return status;
+            }
+
+            return self.counter as Byte;
+        }
+
+        fn write(&mut self, addr: Word, value: Byte) {
+            if addr == 0 {
+                self.counter = value as u64;
+            }
+        }
+    }
+
+    impl Device for Timer {
+        fn tick(&mut self, cycles: u64) {
+            if self.counter == 0 {
+                return;
+            }
+            self.counter = self.counter.saturating_sub(cycles);
+            self.expired = self.counter == 0;
+        }
+
+        fn irq_asserted(&self) -> bool {
+            return self.expired;
+        }
+    }
+
+    #[test]
+    fn should_reach_device_registers_at_region_offsets() {
+        let mut map = MemoryMap::new();
+        map.map(0x0000..=0xFFFF, Ram64k::new());
+        map.attach(0xDC00..=0xDC0F, Timer::default());
+
+        map.write(0xDC00, 10);
+        map.tick(4);
+
+        assert_eq!(map.read(0xDC00), 6);
+        assert_eq!(map.read(0xDC10), 0x00);
+    }
+
+    #[test]
+    fn should_report_irq_of_any_attached_device() {
+        let timer = RefCell::new(Timer::default());
+        let mut map = MemoryMap::new();
+        map.attach(0xDC00..=0xDC0F, Timer::default());
+        map.attach(0xDD00..=0xDD0F, &timer);
+        map.write(0xDD00, 2);
+
+        map.tick(1);
+        assert!(!map.irq_asserted());
+        map.tick(1);
+        assert!(map.irq_asserted());
+        assert!(timer.borrow().expired);
+
+        map.read(0xDD01);
+        assert!(!map.irq_asserted());
+    }
+
+    #[test]
+    fn should_not_tick_regions_mapped_without_clock() {
+        let mut map = MemoryMap::new();
+        let ram = map.map(0x0000..=0x07FF, Ram64k::new());
+        let timer = map.attach(0xDC00..=0xDC0F, Timer::default());
+
+        assert!(map.get_device_mut(ram).is_none());
+        map.get_device_mut(timer).unwrap().write(0x0000, 5);
+        assert_eq!(map.read(0xDC00), 5);
+    }
+
+    #[test]
+    fn should_advance_device_by_cycles_cpu_executed() {
+        // LDA #10; STA $DC00; NOP; NOP; LDA $DC00 - the timer is brought up to date after every
+        // instruction, STA and both NOPs take 8 cycles
+        let mut ram = Ram64k::new();
+        ram.load(
+            &[0xA9, 0x0A, 0x8D, 0x00, 0xDC, 0xEA, 0xEA, 0xAD, 0x00, 0xDC],
+            0x0200,
+        );
+        let mut map = MemoryMap::new();
+        map.map(0x0000..=0xFFFF, ram);
+        map.attach(0xDC00..=0xDC0F, Timer::default());
+        let mut cpu = CPU::with_memory(map);
+        cpu.set_program_counter(0x0200);
+
+        let mut last_cycle = cpu.get_cycle();
+        for _ in 0..4 {
+            cpu.execute_next_instruction();
+            let cycle = cpu.get_cycle();
+            cpu.get_memory_mut().tick(cycle - last_cycle);
+            last_cycle = cycle;
+        }
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.get_accumulator(), 2);
+    }
+}
+
+#[cfg(test)]
+mod mirroring {
+    use crate::{
+        cpu::CPU,
+        memory::{memory_map::MemoryMap, Bus, Ram64k},
+    };
+
+    #[test]
+    fn should_mirror_ram_through_whole_region() {
+        let mut map = MemoryMap::new();
+        map.map_mirrored(0x0000..=0x1FFF, 0x0800, Ram64k::new());
+
+        map.write(0x0010, 0x42);
+
+        assert_eq!(map.read(0x0810), 0x42);
+        assert_eq!(map.read(0x1810), 0x42);
+        map.write(0x1FFF, 0x24);
+        assert_eq!(map.read(0x07FF), 0x24);
+    }
+
+    #[test]
+    fn should_repeat_registers_at_region_offsets() {
+        let mut map = MemoryMap::new();
+        let via = map.map_mirrored(0x6000..=0x60FF, 0x10, Ram64k::new());
+
+        map.write(0x6023, 0x42);
+
+        assert_eq!(map.read(0x6003), 0x42);
+        assert_eq!(map.get_region_mut(via).unwrap().read(0x0003), 0x42);
+    }
+
+    #[test]
+    fn should_mirror_every_bank_of_banked_region() {
+        let mut map = MemoryMap::new();
+        let region = map.map_banked(
+            0x8000..=0xFFFF,
+            vec![Box::new(Ram64k::new()), Box::new(Ram64k::new())],
+        );
+        map.set_mirror_size(region, 0x4000);
+        map.set_bank(region, 1);
+
+        map.write(0xC000, 0x42);
+
+        assert_eq!(map.read(0x8000), 0x42);
+        assert_eq!(map.get_bank_mut(region, 1).unwrap().read(0x0000), 0x42);
+    }
+
+    #[test]
+    fn should_reach_zero_page_through_mirror() {
+        // LDA #$42; STA $0810; LDX $10
+        let mut ram = Ram64k::new();
+        ram.load(&[0xA9, 0x42, 0x8D, 0x10, 0x08, 0xA6, 0x10], 0x0200);
+        let mut map = MemoryMap::new();
+        map.map_mirrored(0x0000..=0x1FFF, 0x0800, ram);
+        let mut cpu = CPU::with_memory(map);
+        cpu.set_program_counter(0x0200);
+
+        for _ in 0..3 {
+            cpu.execute_next_instruction();
+        }
+
+        assert_eq!(cpu.get_index_register_x(), 0x42);
+    }
+}
+
+#[cfg(test)]
+mod peek_and_poke {
+    use crate::{
+        cpu::{monitor::Monitor, CPU},
+        memory::{
+            controller::{Button, Controller},
+            memory_map::MemoryMap,
+            rom::Rom,
+            Bus, Ram64k,
+        },
+    };
+
+    #[test]
+    fn should_poke_under_bank_latch_without_switching_banks() {
+        let mut map = MemoryMap::new();
+        let ram = map.map(0x0000..=0xFFFF, Ram64k::new());
+        let cartridge = map.map_banked(
+            0x8000..=0x9FFF,
+            vec![Box::new(Ram64k::new()), Box::new(Ram64k::new())],
+        );
+        map.add_bank_latch(0xDE00..=0xDE00, cartridge);
+
+        map.poke(0xDE00, 0x01);
+
+        assert_eq!(map.get_bank(cartridge), Some(0));
+        assert_eq!(map.get_region_mut(ram).unwrap().read(0xDE00), 0x01);
+    }
+
+    #[test]
+    fn should_patch_rom_image_with_poke() {
+        let mut map = MemoryMap::new();
+        map.map(0x8000..=0xFFFF, Rom::new(vec![0xEA; 0x8000]));
+
+        map.write(0x8000, 0x00);
+        assert_eq!(map.peek(0x8000), 0xEA);
+        map.poke(0x8000, 0x00);
+
+        assert_eq!(map.read(0x8000), 0x00);
+    }
+
+    #[test]
+    fn should_dump_controller_register_from_monitor_without_shifting() {
+        let mut pad = Controller::new_nes(Ram64k::new(), 0x0000);
+        pad.set_buttons(&[Button::B]);
+        pad.write(0x0000, 1);
+        pad.write(0x0000, 0);
+        let mut map = MemoryMap::new();
+        map.map(0x0000..=0xFFFF, Ram64k::new());
+        map.map(0x4016..=0x4016, pad);
+        let mut cpu = CPU::with_memory(map);
+        let mut monitor = Monitor::new(cpu.get_chip_variant());
+
+        let dumps: Vec<_> = (0..2)
+            .map(|_| monitor.execute(&mut cpu, "m $4016 1"))
+            .collect();
+
+        assert_eq!(dumps, vec![Ok(String::from("$4016: 00")); 2]);
+        assert_eq!(cpu.get_memory_mut().read(0x4016), 0x00);
+        assert_eq!(cpu.get_memory_mut().read(0x4016), 0x01);
+    }
+}
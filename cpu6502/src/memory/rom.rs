@@ -0,0 +1,103 @@
+use super::Bus;
+use crate::consts::{Byte, Word};
+
+// what a write into ROM does; the ROM contents never change
+#[derive(Default, Clone, Copy, PartialEq, Debug)]
+pub enum RomWritePolicy {
+    // dropped like on a real ROM chip
+    #[default]
+    Ignore,
+    // dropped, but recorded for inspection after the run
+    Log,
+    // lands in RAM underneath the ROM that reads never see
+    UnderlyingRam,
+    // a program writing its own ROM is a bug worth stopping on
+    Panic,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct RomWrite {
+    pub addr: Word,
+    pub value: Byte,
+}
+
+// read-only memory answering at offsets relative to its start; images smaller than the
+// mapped region repeat through it
+pub struct Rom {
+    data: Vec<Byte>,
+    write_policy: RomWritePolicy,
+    write_log: Vec<RomWrite>,
+    underlying_ram: Vec<Byte>,
+}
+
+impl Rom {
+    pub fn new(data: Vec<Byte>) -> Self {
+        assert!(!data.is_empty(), "ROM image is empty");
+
+        return Rom {
+            data,
+            write_policy: RomWritePolicy::default(),
+            write_log: Vec::new(),
+            underlying_ram: Vec::new(),
+        };
+    }
+
+    pub fn set_write_policy(&mut self, policy: RomWritePolicy) {
+        if policy == RomWritePolicy::UnderlyingRam && self.underlying_ram.is_empty() {
+            self.underlying_ram = vec![0; self.data.len()];
+        }
+        self.write_policy = policy;
+    }
+
+    pub fn get_write_policy(&self) -> RomWritePolicy {
+        return self.write_policy;
+    }
+
+    pub fn get_data(&self) -> &[Byte] {
+        return &self.data;
+    }
+
+    pub fn get_write_log(&self) -> &[RomWrite] {
+        return &self.write_log;
+    }
+
+    pub fn clear_write_log(&mut self) {
+        self.write_log.clear();
+    }
+
+    // empty until writes are routed to the underlying RAM
+    pub fn get_underlying_ram(&self) -> &[Byte] {
+        return &self.underlying_ram;
+    }
+
+    fn offset(&self, addr: Word) -> usize {
+        return addr as usize % self.data.len();
+    }
+}
+
+impl Bus for Rom {
+    fn read(&mut self, addr: Word) -> Byte {
+        return self.data[self.offset(addr)];
+    }
+
+    fn write(&mut self, addr: Word, value: Byte) {
+        match self.write_policy {
+            RomWritePolicy::Ignore => (),
+            RomWritePolicy::Log => self.write_log.push(RomWrite { addr, value }),
+            RomWritePolicy::UnderlyingRam => {
+                let offset = self.offset(addr);
+                self.underlying_ram[offset] = value;
+            }
+            RomWritePolicy::Panic => panic!("write of ${value:02X} to ROM at ${addr:04X}"),
+        };
+    }
+
+    // patches the image itself, e.g. to plant a breakpoint from a debugger
+    fn poke(&mut self, addr: Word, value: Byte) {
+        let offset = self.offset(addr);
+        self.data[offset] = value;
+    }
+}
+
+#[cfg(test)]
+mod tests;
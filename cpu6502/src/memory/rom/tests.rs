@@ -0,0 +1,97 @@
+#[cfg(test)]
+mod write_policy {
+    use crate::memory::{
+        rom::{Rom, RomWrite, RomWritePolicy},
+        Bus,
+    };
+
+    fn rom(policy: RomWritePolicy) -> Rom {
+        let mut rom = Rom::new(vec![0xEA; 0x1000]);
+        rom.set_write_policy(policy);
+        return rom;
+    }
+
+    #[test]
+    fn should_ignore_writes_by_default() {
+        let mut rom = Rom::new(vec![0xEA; 0x1000]);
+
+        rom.write(0x0010, 0x42);
+
+        assert_eq!(rom.read(0x0010), 0xEA);
+        assert!(rom.get_write_log().is_empty());
+    }
+
+    #[test]
+    fn should_log_writes_without_changing_contents() {
+        let mut rom = rom(RomWritePolicy::Log);
+
+        rom.write(0x0010, 0x42);
+
+        assert_eq!(rom.read(0x0010), 0xEA);
+        assert_eq!(
+            rom.get_write_log(),
+            &[RomWrite {
+                addr: 0x0010,
+                value: 0x42
+            }]
+        );
+    }
+
+    #[test]
+    fn should_route_writes_to_underlying_ram() {
+        let mut rom = rom(RomWritePolicy::UnderlyingRam);
+
+        rom.write(0x0010, 0x42);
+
+        assert_eq!(rom.read(0x0010), 0xEA);
+        assert_eq!(rom.get_underlying_ram()[0x0010], 0x42);
+    }
+
+    #[test]
+    #[should_panic(expected = "write of $42 to ROM at $0010")]
+    fn should_panic_on_write_when_treated_as_error() {
+        let mut rom = rom(RomWritePolicy::Panic);
+
+        rom.write(0x0010, 0x42);
+    }
+
+    #[test]
+    fn should_repeat_image_smaller_than_address_range() {
+        let mut rom = Rom::new(vec![0x01, 0x02]);
+
+        assert_eq!(rom.read(0x0000), 0x01);
+        assert_eq!(rom.read(0x0003), 0x02);
+    }
+}
+
+#[cfg(test)]
+mod cpu_integration {
+    use crate::{
+        cpu::CPU,
+        memory::{
+            memory_map::MemoryMap,
+            rom::{Rom, RomWritePolicy},
+            Ram64k,
+        },
+    };
+
+    #[test]
+    fn should_keep_program_intact_when_it_writes_into_its_own_rom() {
+        // LDA #$42; STA $8004 - overwriting its own operand
+        let mut image = vec![0xFF; 0x8000];
+        image[..5].copy_from_slice(&[0xA9, 0x42, 0x8D, 0x04, 0x80]);
+        image[0x7FFC..].copy_from_slice(&[0x00, 0x80, 0x00, 0x80]);
+        let mut rom = Rom::new(image);
+        rom.set_write_policy(RomWritePolicy::Log);
+        let mut map = MemoryMap::new();
+        map.map(0x0000..=0x7FFF, Ram64k::new());
+        let rom = map.map(0x8000..=0xFFFF, rom);
+        let mut cpu = CPU::with_memory(map);
+
+        cpu.execute_next_instruction();
+        cpu.execute_next_instruction();
+
+        let rom = cpu.get_memory_mut().get_region_mut(rom).unwrap();
+        assert_eq!(rom.read(0x0004), 0x80);
+    }
+}
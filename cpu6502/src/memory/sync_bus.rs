@@ -0,0 +1,59 @@
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use super::Bus;
+use crate::consts::{Byte, Word};
+
+// a bus shared between threads, e.g. a CPU running in the background and a UI or debugger
+// inspecting its memory. Clones are handles to the same bus and every access takes the lock,
+// so the other side sees writes as soon as the instruction making them performs them.
+pub struct SyncBus<B: Bus> {
+    bus: Arc<Mutex<B>>,
+}
+
+impl<B: Bus> SyncBus<B> {
+    pub fn new(bus: B) -> Self {
+        return SyncBus {
+            bus: Arc::new(Mutex::new(bus)),
+        };
+    }
+
+    // holding the guard stalls a CPU using the bus on its next access, which makes a
+    // consistent view of several addresses possible; a thread that panicked while holding it
+    // cannot leave memory half written, so the poisoning is ignored
+    pub fn lock(&self) -> MutexGuard<'_, B> {
+        return self.bus.lock().unwrap_or_else(PoisonError::into_inner);
+    }
+}
+
+impl<B: Bus> Clone for SyncBus<B> {
+    fn clone(&self) -> Self {
+        return SyncBus {
+            bus: Arc::clone(&self.bus),
+        };
+    }
+}
+
+impl<B: Bus> Bus for SyncBus<B> {
+    fn read(&mut self, addr: Word) -> Byte {
+        return self.lock().read(addr);
+    }
+
+    fn write(&mut self, addr: Word, value: Byte) {
+        self.lock().write(addr, value);
+    }
+
+    fn is_mapped(&self, addr: Word) -> bool {
+        return self.lock().is_mapped(addr);
+    }
+
+    fn peek(&mut self, addr: Word) -> Byte {
+        return self.lock().peek(addr);
+    }
+
+    fn poke(&mut self, addr: Word, value: Byte) {
+        self.lock().poke(addr, value);
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,72 @@
+#[cfg(test)]
+mod handles {
+    use crate::memory::{sync_bus::SyncBus, Bus, VecMemory};
+
+    #[test]
+    fn should_see_writes_through_every_clone() {
+        let mut bus = SyncBus::new(VecMemory::new());
+        let mut other = bus.clone();
+
+        bus.write(0x0200, 0x42);
+
+        assert_eq!(other.read(0x0200), 0x42);
+        assert_eq!(other.lock().data[0x0200], 0x42);
+    }
+}
+
+#[cfg(test)]
+mod threads {
+    use std::thread;
+
+    use crate::{
+        cpu::{emulation_event::EmulationEvent, watchdog::Watchdog, CPU},
+        memory::{sync_bus::SyncBus, Bus, VecMemory},
+        multiprocessor::{ArbitrationPolicy, MultiprocessorSystem},
+    };
+
+    // LDX #$00; loop: TXA; STA $0300,X; INX; CPX #$10; BNE loop; BRK
+    const FILL_PAGE: [u8; 12] = [
+        0xA2, 0x00, 0x8A, 0x9D, 0x00, 0x03, 0xE8, 0xE0, 0x10, 0xD0, 0xF7, 0x00,
+    ];
+
+    fn memory_with_program() -> SyncBus<VecMemory> {
+        let mut memory = VecMemory::new();
+        memory.insert(0x0600, &FILL_PAGE);
+
+        return SyncBus::new(memory);
+    }
+
+    #[test]
+    fn should_run_cpu_on_background_thread_over_shared_memory() {
+        let memory = memory_with_program();
+        let mut cpu = CPU::new_nmos(memory.clone());
+        cpu.set_program_counter(0x0600);
+        cpu.set_watchdog(Watchdog::with_callback(10_000, Box::new(|_, _| true)));
+
+        let event = thread::spawn(move || cpu.execute_until_break())
+            .join()
+            .unwrap();
+
+        assert_eq!(event, EmulationEvent::Trap(0x060B));
+        assert_eq!(
+            memory.lock().data[0x0300..0x0310],
+            (0..0x10).collect::<Vec<u8>>()
+        );
+    }
+
+    #[test]
+    fn should_move_multiprocessor_system_to_background_thread() {
+        let mut memory = memory_with_program();
+        let mut system = MultiprocessorSystem::new(ArbitrationPolicy::RoundRobin);
+        let mut cpu = CPU::new_nmos(memory.clone());
+        cpu.set_program_counter(0x0600);
+        system.add_cpu(cpu);
+
+        let event = thread::spawn(move || system.run_until_event())
+            .join()
+            .unwrap();
+
+        assert_eq!(event, Some((0, EmulationEvent::Trap(0x060B))));
+        assert_eq!(memory.read(0x030F), 0x0F);
+    }
+}
@@ -0,0 +1,161 @@
+#[cfg(test)]
+mod masked_memory {
+    use std::cell::RefCell;
+
+    use crate::{
+        cpu::CPU,
+        memory::{Bus, MaskedMemory, VecMemory},
+    };
+
+    #[test]
+    fn should_alias_addresses_above_decoded_lines() {
+        let mut memory = MaskedMemory::new(VecMemory::new(), 13);
+
+        memory.write(0x2005, 0x42);
+
+        assert_eq!(memory.read(0x0005), 0x42);
+        assert_eq!(memory.read(0xE005), 0x42);
+        assert_eq!(memory.get_inner().data[0x0005], 0x42);
+        assert_eq!(memory.get_inner().data[0x2005], 0x00);
+    }
+
+    #[test]
+    fn should_decode_whole_address_space_with_sixteen_lines() {
+        let memory = MaskedMemory::new(VecMemory::new(), 16);
+
+        assert_eq!(memory.get_mask(), 0xFFFF);
+    }
+
+    #[test]
+    fn should_fetch_reset_vector_through_mirror() {
+        let mut inner = VecMemory::new();
+        inner.insert(0x1FFC, &[0x00, 0x10]);
+        let memory = RefCell::new(MaskedMemory::new(inner, 13));
+        let mut cpu = CPU::new_nmos(&memory);
+
+        cpu.reset();
+
+        assert_eq!(cpu.get_program_counter(), 0x1000);
+    }
+}
+
+#[cfg(test)]
+mod partially_mapped_memory {
+    use crate::memory::{Bus, MaskedMemory, PartiallyMappedMemory, VecMemory};
+
+    #[test]
+    fn should_map_only_given_ranges() {
+        let memory =
+            PartiallyMappedMemory::new(VecMemory::new(), vec![0x0000..=0x07FF, 0x8000..=0xFFFF]);
+
+        assert!(memory.is_mapped(0x0000));
+        assert!(memory.is_mapped(0x07FF));
+        assert!(!memory.is_mapped(0x0800));
+        assert!(!memory.is_mapped(0x7FFF));
+        assert!(memory.is_mapped(0xFFFF));
+    }
+
+    #[test]
+    fn should_drop_writes_to_unmapped_addresses() {
+        let mut memory = PartiallyMappedMemory::new(VecMemory::new(), vec![0x0000..=0x00FF]);
+
+        memory.write(0x0010, 0x42);
+        memory.write(0x4000, 0x42);
+
+        assert_eq!(memory.get_inner().data[0x0010], 0x42);
+        assert_eq!(memory.get_inner().data[0x4000], 0x00);
+    }
+
+    #[test]
+    fn should_report_mirrors_of_mapped_addresses_as_mapped() {
+        let inner = PartiallyMappedMemory::new(VecMemory::new(), vec![0x0000..=0x00FF]);
+        let memory = MaskedMemory::new(inner, 13);
+
+        assert!(memory.is_mapped(0x2010));
+        assert!(!memory.is_mapped(0x2110));
+    }
+}
+
+#[cfg(test)]
+mod ram_64k {
+    use crate::{
+        cpu::CPU,
+        memory::{Bus, Ram64k},
+    };
+
+    #[test]
+    fn should_load_payload_wrapping_past_end_of_address_space() {
+        let mut memory = Ram64k::new();
+
+        memory.load(&[0x01, 0x02, 0x03], 0xFFFF);
+
+        assert_eq!(memory[0xFFFF], 0x01);
+        assert_eq!(memory[0x0000], 0x02);
+        assert_eq!(memory[0x0001], 0x03);
+    }
+
+    #[test]
+    fn should_fill_inclusive_range() {
+        let mut memory = Ram64k::new();
+
+        memory.fill(0x0200..=0x02FF, 0xEA);
+
+        assert_eq!(memory.read(0x01FF), 0x00);
+        assert_eq!(memory.read(0x0200), 0xEA);
+        assert_eq!(memory.read(0x02FF), 0xEA);
+        assert_eq!(memory.read(0x0300), 0x00);
+    }
+
+    #[test]
+    fn should_run_program_loaded_with_reset_vector() {
+        let mut memory = Ram64k::new();
+        memory.load(&[0xA9, 0x42, 0x85, 0x10], 0x0400); // LDA #$42; STA $10
+        memory.load(&[0x00, 0x04], 0xFFFC);
+        let mut cpu = CPU::with_memory(memory);
+
+        cpu.execute_next_instruction();
+        cpu.execute_next_instruction();
+
+        assert_eq!(cpu.get_memory()[0x0010], 0x42);
+    }
+}
+
+#[cfg(test)]
+mod power_on_pattern {
+    use crate::memory::{PowerOnPattern, Ram64k, VecMemory};
+
+    #[test]
+    fn should_fill_ram_with_ones() {
+        let memory = Ram64k::with_pattern(PowerOnPattern::Ones);
+
+        assert!(memory.as_slice().iter().all(|value| *value == 0xFF));
+    }
+
+    #[test]
+    fn should_alternate_blocks_of_zeros_and_ones() {
+        let memory = VecMemory::with_pattern(PowerOnPattern::Alternating { block_size: 4 });
+
+        assert_eq!(
+            memory.data[0..12],
+            [0x00, 0x00, 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0x00, 0x00, 0x00, 0x00]
+        );
+    }
+
+    #[test]
+    fn should_repeat_random_contents_for_same_seed() {
+        let first = Ram64k::with_pattern(PowerOnPattern::Random { seed: 42 });
+        let second = Ram64k::with_pattern(PowerOnPattern::Random { seed: 42 });
+        let other = Ram64k::with_pattern(PowerOnPattern::Random { seed: 43 });
+
+        assert_eq!(first.as_slice(), second.as_slice());
+        assert_ne!(first.as_slice(), other.as_slice());
+        assert!(first.as_slice().iter().any(|value| *value != first[0x0000]));
+    }
+
+    #[test]
+    fn should_clear_ram_by_default() {
+        let memory = Ram64k::with_pattern(PowerOnPattern::default());
+
+        assert!(memory.as_slice().iter().all(|value| *value == 0x00));
+    }
+}
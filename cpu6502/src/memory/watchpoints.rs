@@ -0,0 +1,117 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use crate::consts::{Byte, Word};
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Access {
+    Read,
+    Write,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct WatchpointHit {
+    pub id: usize,
+    pub access: Access,
+    pub address: Word,
+    pub value: Byte,
+    // first byte of the instruction whose bus cycle touched the address
+    pub instruction_address: Word,
+    pub cycle: u64,
+}
+
+impl fmt::Display for WatchpointHit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self.access {
+            Access::Read => write!(
+                f,
+                "read of ${:02X} from ${:04X} by ${:04X}",
+                self.value, self.address, self.instruction_address
+            ),
+            Access::Write => write!(
+                f,
+                "write of ${:02X} to ${:04X} by ${:04X}",
+                self.value, self.address, self.instruction_address
+            ),
+        };
+    }
+}
+
+struct Watchpoint {
+    range: RangeInclusive<Word>,
+    access: Access,
+}
+
+// address ranges whose reads or writes pause execution. Every bus cycle counts, so dummy
+// reads and opcode fetches hit read watchpoints just like loads do. Hits are collected while
+// an instruction runs and the CPU stops once it completes. To stop on what memory holds
+// rather than on who touches it use data breakpoints; to record writes without stopping use
+// memory watches.
+#[derive(Default)]
+pub struct Watchpoints {
+    // ordered by id so simultaneous hits are reported in the order the watchpoints were added
+    watchpoints: BTreeMap<usize, Watchpoint>,
+    hits: Vec<WatchpointHit>,
+    next_id: usize,
+}
+
+impl Watchpoints {
+    pub fn is_empty(&self) -> bool {
+        return self.watchpoints.is_empty();
+    }
+
+    pub fn watch_read(&mut self, range: RangeInclusive<Word>) -> usize {
+        return self.add(range, Access::Read);
+    }
+
+    pub fn watch_write(&mut self, range: RangeInclusive<Word>) -> usize {
+        return self.add(range, Access::Write);
+    }
+
+    pub fn remove(&mut self, id: usize) -> bool {
+        return self.watchpoints.remove(&id).is_some();
+    }
+
+    pub fn notify(
+        &mut self,
+        access: Access,
+        address: Word,
+        value: Byte,
+        instruction_address: Word,
+        cycle: u64,
+    ) {
+        for (id, watchpoint) in &self.watchpoints {
+            if watchpoint.access != access || !watchpoint.range.contains(&address) {
+                continue;
+            }
+            self.hits.push(WatchpointHit {
+                id: *id,
+                access,
+                address,
+                value,
+                instruction_address,
+                cycle,
+            });
+        }
+    }
+
+    pub fn get_hits(&self) -> &[WatchpointHit] {
+        return &self.hits;
+    }
+
+    pub fn take_hits(&mut self) -> Vec<WatchpointHit> {
+        return std::mem::take(&mut self.hits);
+    }
+
+    fn add(&mut self, range: RangeInclusive<Word>, access: Access) -> usize {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.watchpoints.insert(id, Watchpoint { range, access });
+
+        return id;
+    }
+}
+
+#[cfg(test)]
+mod tests;
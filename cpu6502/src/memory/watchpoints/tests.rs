@@ -0,0 +1,154 @@
+#[cfg(test)]
+mod notify {
+    use crate::memory::watchpoints::{Access, WatchpointHit, Watchpoints};
+
+    #[test]
+    fn should_record_hit_of_matching_access_inside_range() {
+        let mut watchpoints = Watchpoints::default();
+        let id = watchpoints.watch_write(0xD000..=0xD02E);
+
+        watchpoints.notify(Access::Read, 0xD020, 0x0E, 0x0600, 10);
+        watchpoints.notify(Access::Write, 0xD02F, 0x01, 0x0603, 14);
+        watchpoints.notify(Access::Write, 0xD020, 0x01, 0x0606, 18);
+
+        assert_eq!(
+            watchpoints.get_hits(),
+            &[WatchpointHit {
+                id,
+                access: Access::Write,
+                address: 0xD020,
+                value: 0x01,
+                instruction_address: 0x0606,
+                cycle: 18,
+            }]
+        );
+    }
+
+    #[test]
+    fn should_report_every_watchpoint_covering_address_in_order_of_adding() {
+        let mut watchpoints = Watchpoints::default();
+        let page = watchpoints.watch_read(0x0200..=0x02FF);
+        let cell = watchpoints.watch_read(0x0210..=0x0210);
+
+        watchpoints.notify(Access::Read, 0x0210, 0x00, 0x0600, 3);
+
+        let ids: Vec<usize> = watchpoints.take_hits().iter().map(|hit| hit.id).collect();
+        assert_eq!(ids, vec![page, cell]);
+        assert!(watchpoints.get_hits().is_empty());
+    }
+
+    #[test]
+    fn should_not_record_hits_after_removal() {
+        let mut watchpoints = Watchpoints::default();
+        let id = watchpoints.watch_write(0x0000..=0x00FF);
+
+        assert!(watchpoints.remove(id));
+        watchpoints.notify(Access::Write, 0x0010, 0x00, 0x0600, 3);
+
+        assert!(watchpoints.get_hits().is_empty());
+        assert!(!watchpoints.remove(id));
+    }
+}
+
+#[cfg(test)]
+mod display {
+    use crate::memory::watchpoints::{Access, WatchpointHit};
+
+    #[test]
+    fn should_describe_access_and_instruction() {
+        let hit = WatchpointHit {
+            id: 0,
+            access: Access::Write,
+            address: 0xD020,
+            value: 0x01,
+            instruction_address: 0x0606,
+            cycle: 18,
+        };
+
+        assert_eq!(hit.to_string(), "write of $01 to $D020 by $0606");
+    }
+}
+
+#[cfg(all(test, feature = "watchpoints"))]
+mod cpu_integration {
+    use crate::{
+        cpu::{emulation_event::EmulationEvent, CPU},
+        memory::{watchpoints::Access, VecMemory},
+    };
+
+    #[test]
+    fn should_stop_after_instruction_that_wrote_watched_address() {
+        let mut memory = VecMemory::new();
+        memory.insert(
+            0x0000,
+            &[
+                0xA9, 0x05, // LDA #$05
+                0x85, 0x40, // STA $40
+                0x85, 0x80, // STA $80
+                0x00, // BRK
+            ],
+        );
+        let mut cpu = CPU::new_nmos(memory);
+        cpu.set_program_counter(0x0000);
+        let id = cpu.watch_write(0x0080..=0x00FF);
+
+        let event = cpu.execute_until_break();
+
+        let hits = match event {
+            EmulationEvent::Watchpoint(hits) => hits,
+            event => panic!("unexpected event: {event}"),
+        };
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].id, id);
+        assert_eq!(hits[0].access, Access::Write);
+        assert_eq!(hits[0].address, 0x0080);
+        assert_eq!(hits[0].value, 0x05);
+        assert_eq!(hits[0].instruction_address, 0x0004);
+        assert_eq!(cpu.get_program_counter(), 0x0006);
+    }
+
+    #[test]
+    fn should_stop_on_read_and_resume_to_next_hit() {
+        let mut memory = VecMemory::new();
+        memory.insert(
+            0x0000,
+            &[
+                0xA5, 0x40, // LDA $40
+                0xA6, 0x41, // LDX $41
+                0xA4, 0x40, // LDY $40
+                0x00, // BRK
+            ],
+        );
+        memory[0x0040] = 0x42;
+        let mut cpu = CPU::new_nmos(memory);
+        cpu.set_program_counter(0x0000);
+        cpu.watch_read(0x0040..=0x0040);
+
+        let first = cpu.execute_until_break();
+        let second = cpu.execute_until_break();
+
+        assert_eq!(first.to_string(), "watched read of $42 from $0040 by $0000");
+        assert_eq!(
+            second.to_string(),
+            "watched read of $42 from $0040 by $0004"
+        );
+        assert_eq!(cpu.execute_until_break(), EmulationEvent::Trap(0x0006));
+    }
+
+    #[test]
+    fn should_ignore_writes_to_read_watchpoint() {
+        let mut memory = VecMemory::new();
+        memory.insert(
+            0x0000,
+            &[
+                0x85, 0x40, // STA $40
+                0x00, // BRK
+            ],
+        );
+        let mut cpu = CPU::new_nmos(memory);
+        cpu.set_program_counter(0x0000);
+        cpu.watch_read(0x0040..=0x0040);
+
+        assert_eq!(cpu.execute_until_break(), EmulationEvent::Trap(0x0002));
+    }
+}
@@ -0,0 +1,94 @@
+use crate::{
+    cpu::{emulation_event::EmulationEvent, CPU},
+    memory::Bus,
+};
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum ArbitrationPolicy {
+    // every ready core executes one instruction in turn
+    RoundRobin,
+    // the ready core that is furthest behind in cycles runs next, keeping cores in step
+    CycleInterleaved,
+}
+
+struct Core<M: Bus> {
+    cpu: CPU<M>,
+    ready: bool,
+}
+
+// cores are constructed against the same memory and take turns at instruction granularity;
+// deasserting a core's RDY line holds it off the bus until it is asserted again
+pub struct MultiprocessorSystem<M: Bus> {
+    cores: Vec<Core<M>>,
+    policy: ArbitrationPolicy,
+    next_core: usize,
+}
+
+impl<M: Bus> MultiprocessorSystem<M> {
+    pub fn new(policy: ArbitrationPolicy) -> Self {
+        return MultiprocessorSystem {
+            cores: Vec::new(),
+            policy,
+            next_core: 0,
+        };
+    }
+
+    pub fn add_cpu(&mut self, cpu: CPU<M>) -> usize {
+        self.cores.push(Core { cpu, ready: true });
+        return self.cores.len() - 1;
+    }
+
+    pub fn get_cpu(&self, idx: usize) -> Option<&CPU<M>> {
+        return self.cores.get(idx).map(|core| &core.cpu);
+    }
+
+    pub fn get_cpu_mut(&mut self, idx: usize) -> Option<&mut CPU<M>> {
+        return self.cores.get_mut(idx).map(|core| &mut core.cpu);
+    }
+
+    pub fn set_ready(&mut self, idx: usize, ready: bool) {
+        if let Some(core) = self.cores.get_mut(idx) {
+            core.ready = ready;
+        }
+    }
+
+    pub fn is_ready(&self, idx: usize) -> bool {
+        return self.cores.get(idx).is_some_and(|core| core.ready);
+    }
+
+    // returns the index of the core that executed an instruction together with its event,
+    // or None when every core is held off by RDY
+    pub fn step(&mut self) -> Option<(usize, Option<EmulationEvent>)> {
+        let idx = self.select_core()?;
+        let event = self.cores[idx].cpu.execute_next_instruction();
+        self.next_core = (idx + 1) % self.cores.len();
+
+        return Some((idx, event));
+    }
+
+    pub fn run_until_event(&mut self) -> Option<(usize, EmulationEvent)> {
+        loop {
+            match self.step()? {
+                (idx, Some(event)) => return Some((idx, event)),
+                (_, None) => continue,
+            }
+        }
+    }
+
+    fn select_core(&self) -> Option<usize> {
+        let core_count = self.cores.len();
+        let mut ready_cores = (0..core_count)
+            .map(|offset| (self.next_core + offset) % core_count)
+            .filter(|idx| self.cores[*idx].ready);
+
+        return match self.policy {
+            ArbitrationPolicy::RoundRobin => ready_cores.next(),
+            ArbitrationPolicy::CycleInterleaved => {
+                ready_cores.min_by_key(|idx| self.cores[*idx].cpu.get_cycle())
+            }
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,108 @@
+#[cfg(test)]
+mod step {
+    use std::cell::RefCell;
+
+    use crate::{
+        cpu::CPU,
+        memory::VecMemory,
+        multiprocessor::{ArbitrationPolicy, MultiprocessorSystem},
+    };
+
+    fn memory() -> RefCell<VecMemory> {
+        let mut memory = VecMemory::new();
+        memory.insert(0x0200, &[0xE6, 0x10, 0xE6, 0x10, 0xE6, 0x10]); // INC $10 (x3)
+        memory.insert(0x0300, &[0xE8, 0xE8, 0xE8, 0xE8, 0xE8, 0xE8]); // INX (x6)
+
+        return RefCell::new(memory);
+    }
+
+    fn system(
+        memory: &RefCell<VecMemory>,
+        policy: ArbitrationPolicy,
+    ) -> MultiprocessorSystem<&RefCell<VecMemory>> {
+        let mut system = MultiprocessorSystem::new(policy);
+        let mut first = CPU::new_nmos(memory);
+        first.set_program_counter(0x0200);
+        let mut second = CPU::new_nmos(memory);
+        second.set_program_counter(0x0300);
+        system.add_cpu(first);
+        system.add_cpu(second);
+
+        return system;
+    }
+
+    #[test]
+    fn should_alternate_instructions_between_cores() {
+        let memory = memory();
+        let mut system = system(&memory, ArbitrationPolicy::RoundRobin);
+
+        let order: Vec<usize> = (0..4).map(|_| system.step().unwrap().0).collect();
+
+        assert_eq!(order, vec![0, 1, 0, 1]);
+        assert_eq!(memory.borrow()[0x0010], 2);
+    }
+
+    #[test]
+    fn should_run_core_that_is_behind_in_cycles() {
+        let memory = memory();
+        let mut system = system(&memory, ArbitrationPolicy::CycleInterleaved);
+
+        let order: Vec<usize> = (0..5).map(|_| system.step().unwrap().0).collect();
+
+        assert_eq!(order, vec![0, 1, 1, 1, 0]);
+    }
+
+    #[test]
+    fn should_hold_off_core_with_rdy_deasserted() {
+        let memory = memory();
+        let mut system = system(&memory, ArbitrationPolicy::RoundRobin);
+        system.set_ready(0, false);
+
+        system.step();
+        system.step();
+
+        assert_eq!(system.get_cpu(0).unwrap().get_program_counter(), 0x0200);
+        assert_eq!(system.get_cpu(1).unwrap().get_program_counter(), 0x0302);
+    }
+
+    #[test]
+    fn should_not_step_when_no_core_is_ready() {
+        let memory = memory();
+        let mut system = system(&memory, ArbitrationPolicy::RoundRobin);
+        system.set_ready(0, false);
+        system.set_ready(1, false);
+
+        assert_eq!(system.step(), None);
+    }
+}
+
+#[cfg(test)]
+mod run_until_event {
+    use std::cell::RefCell;
+
+    use crate::{
+        cpu::{emulation_event::EmulationEvent, CPU},
+        memory::VecMemory,
+        multiprocessor::{ArbitrationPolicy, MultiprocessorSystem},
+    };
+
+    #[test]
+    fn should_stop_on_first_event_of_any_core_sharing_memory() {
+        let mut memory = VecMemory::new();
+        memory.insert(0x0200, &[0xA9, 0x42, 0x85, 0x10, 0xE8, 0xE8]); // LDA #$42; STA $10; INX; INX
+        memory.insert(0x0300, &[0xE8, 0xE8, 0xA5, 0x10, 0x00]); // INX; INX; LDA $10; BRK
+        let memory = RefCell::new(memory);
+        let mut system = MultiprocessorSystem::new(ArbitrationPolicy::RoundRobin);
+        let mut first = CPU::new_nmos(&memory);
+        first.set_program_counter(0x0200);
+        let mut second = CPU::new_nmos(&memory);
+        second.set_program_counter(0x0300);
+        system.add_cpu(first);
+        system.add_cpu(second);
+
+        let result = system.run_until_event();
+
+        assert_eq!(result, Some((1, EmulationEvent::Trap(0x0304))));
+        assert_eq!(memory.borrow()[0x0010], 0x42);
+    }
+}
@@ -0,0 +1,69 @@
+use std::collections::HashMap;
+
+use crate::consts::Word;
+
+#[derive(Debug, Clone, Default)]
+pub struct SymbolTable {
+    symbols: HashMap<Word, String>,
+}
+
+impl SymbolTable {
+    pub fn new() -> Self {
+        return SymbolTable::default();
+    }
+
+    pub fn insert(&mut self, addr: Word, name: &str) {
+        self.symbols.insert(addr, name.to_owned());
+    }
+
+    pub fn get(&self, addr: Word) -> Option<&str> {
+        return self.symbols.get(&addr).map(|name| name.as_str());
+    }
+
+    pub fn resolve(&self, addr: Word) -> String {
+        return match self.get(addr) {
+            Some(name) => name.to_owned(),
+            None => format!("${addr:04X}"),
+        };
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.symbols.is_empty();
+    }
+
+    // accepts VICE label files ("al C000 .start") and plain assignments ("start = $C000")
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let mut table = SymbolTable::new();
+        for (line_idx, line) in source.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with(';') {
+                continue;
+            }
+
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+            let (addr, name) = match tokens.as_slice() {
+                ["al", addr, name] => (*addr, name.trim_start_matches('.')),
+                [name, "=", addr] => (*addr, *name),
+                _ => return Err(format!("line {}: unrecognized symbol entry", line_idx + 1)),
+            };
+
+            let addr = addr.trim_start_matches('$').trim_start_matches("0x");
+            let addr = match u32::from_str_radix(addr, 16) {
+                Ok(addr) if addr <= Word::MAX as u32 => addr as Word,
+                _ => return Err(format!("line {}: incorrect address", line_idx + 1)),
+            };
+            table.insert(addr, name);
+        }
+
+        return Ok(table);
+    }
+}
+
+impl From<HashMap<Word, String>> for SymbolTable {
+    fn from(symbols: HashMap<Word, String>) -> Self {
+        return SymbolTable { symbols };
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,54 @@
+#[cfg(test)]
+mod parse {
+    use crate::symbols::SymbolTable;
+
+    #[test]
+    fn should_parse_vice_label_entries() {
+        let table = SymbolTable::parse("al 00C000 .start\nal 0300 .print").unwrap();
+
+        assert_eq!(table.get(0xC000), Some("start"));
+        assert_eq!(table.get(0x0300), Some("print"));
+    }
+
+    #[test]
+    fn should_parse_assignment_entries() {
+        let table = SymbolTable::parse("; comment\nmain = $1234\n\nloop = 0x1240").unwrap();
+
+        assert_eq!(table.get(0x1234), Some("main"));
+        assert_eq!(table.get(0x1240), Some("loop"));
+    }
+
+    #[test]
+    fn should_report_line_of_incorrect_entry() {
+        let result = SymbolTable::parse("main = $1234\nmain $1234");
+
+        assert_eq!(result.unwrap_err(), "line 2: unrecognized symbol entry");
+    }
+
+    #[test]
+    fn should_reject_addresses_outside_of_address_space() {
+        let result = SymbolTable::parse("main = $12345");
+
+        assert_eq!(result.unwrap_err(), "line 1: incorrect address");
+    }
+}
+
+#[cfg(test)]
+mod resolve {
+    use crate::symbols::SymbolTable;
+
+    #[test]
+    fn should_return_symbol_name_when_address_is_known() {
+        let mut table = SymbolTable::new();
+        table.insert(0x0300, "print");
+
+        assert_eq!(table.resolve(0x0300), "print");
+    }
+
+    #[test]
+    fn should_return_hex_address_when_address_is_unknown() {
+        let table = SymbolTable::new();
+
+        assert_eq!(table.resolve(0x0300), "$0300");
+    }
+}
@@ -0,0 +1,170 @@
+use std::time::Duration;
+
+use crate::{
+    cpu::{emulation_event::EmulationEvent, CPU},
+    device::DmaTransfer,
+    memory::{memory_map::MemoryMap, Bus},
+};
+
+pub mod builder;
+
+// a DMA byte takes a read and a write cycle
+const DMA_CYCLES_PER_BYTE: u64 = 2;
+
+// a CPU together with the memory map holding its devices, clocked as one machine. After every
+// instruction the devices are brought up to the CPU one cycle at a time and the IRQ line
+// follows them on the exact cycle they change it, so interrupt timing does not depend on
+// instruction boundaries. Register accesses see a device as it was when the instruction
+// started, which is at most a few cycles behind.
+// A device taking the bus for DMA pulls RDY low between instructions and keeps it low until it
+// has no more bytes to move, with the CPU halted for the cycles each byte takes.
+pub struct System<'a> {
+    cpu: CPU<MemoryMap<'a>>,
+    // last IRQ level the devices drove onto the CPU line
+    irq_asserted: bool,
+    // set while a device holds RDY low
+    dma_active: bool,
+    stolen_cycles: u64,
+    // the frequency the machine runs at, when it is known
+    clock_hz: Option<u64>,
+}
+
+impl<'a> System<'a> {
+    pub fn new(cpu: CPU<MemoryMap<'a>>) -> Self {
+        let mut system = System {
+            cpu,
+            irq_asserted: false,
+            dma_active: false,
+            stolen_cycles: 0,
+            clock_hz: None,
+        };
+        let cycle = system.cpu.get_cycle();
+        system.update_irq_line(cycle);
+
+        return system;
+    }
+
+    pub fn get_cpu(&self) -> &CPU<MemoryMap<'a>> {
+        return &self.cpu;
+    }
+
+    pub fn get_cpu_mut(&mut self) -> &mut CPU<MemoryMap<'a>> {
+        return &mut self.cpu;
+    }
+
+    pub fn get_memory_map_mut(&mut self) -> &mut MemoryMap<'a> {
+        return self.cpu.get_memory_mut();
+    }
+
+    // cycles the CPU spent halted by DMA
+    pub fn get_stolen_cycles(&self) -> u64 {
+        return self.stolen_cycles;
+    }
+
+    pub fn set_clock_hz(&mut self, clock_hz: u64) {
+        assert!(clock_hz > 0, "clock frequency cannot be 0");
+        self.clock_hz = Some(clock_hz);
+    }
+
+    pub fn get_clock_hz(&self) -> Option<u64> {
+        return self.clock_hz;
+    }
+
+    // emulated time since power-on, known once the clock frequency is set
+    pub fn get_elapsed(&self) -> Option<Duration> {
+        let clock_hz = self.clock_hz?;
+        let cycles = self.cpu.get_cycle() as u128;
+
+        return Some(Duration::from_nanos(
+            (cycles * 1_000_000_000 / clock_hz as u128) as u64,
+        ));
+    }
+
+    pub fn into_cpu(self) -> CPU<MemoryMap<'a>> {
+        return self.cpu;
+    }
+
+    // executes one instruction, enters an interrupt handler or moves a byte for a device
+    // holding the bus, and clocks the devices for the cycles it took. A device asking to end
+    // the emulation takes precedence over whatever else the instruction raised.
+    pub fn step(&mut self) -> Option<EmulationEvent> {
+        if let Some(transfer) = self.cpu.get_memory_mut().next_dma_transfer() {
+            self.transfer(transfer);
+            return None;
+        }
+        if self.dma_active {
+            self.dma_active = false;
+            self.cpu.set_rdy(true);
+        }
+
+        return self.run_cpu();
+    }
+
+    pub fn execute_until_break(&mut self) -> EmulationEvent {
+        loop {
+            if let Some(event) = self.step() {
+                return event;
+            }
+        }
+    }
+
+    pub fn execute_cycles(&mut self, budget: u64) -> EmulationEvent {
+        let last_cycle = self.cpu.get_cycle().saturating_add(budget);
+        while self.cpu.get_cycle() < last_cycle {
+            if let Some(event) = self.step() {
+                return event;
+            }
+        }
+
+        return EmulationEvent::CycleBudgetExhausted;
+    }
+
+    // with RDY low this only clocks the halted CPU for a cycle
+    fn run_cpu(&mut self) -> Option<EmulationEvent> {
+        let start = self.cpu.get_cycle();
+        let event = self.cpu.execute_next_instruction();
+        let end = self.cpu.get_cycle();
+        for cycle in start..end {
+            self.cpu.get_memory_mut().tick(1);
+            self.update_irq_line(cycle + 1);
+        }
+
+        if let Some(code) = self.cpu.get_memory().exit_code() {
+            return Some(EmulationEvent::Exit(code));
+        }
+        return event;
+    }
+
+    fn transfer(&mut self, transfer: DmaTransfer) {
+        if !self.dma_active {
+            self.dma_active = true;
+            self.cpu.set_rdy(false);
+        }
+        let map = self.cpu.get_memory_mut();
+        let value = map.read(transfer.source);
+        map.write(transfer.destination, value);
+
+        let start = self.cpu.get_cycle();
+        for _ in 0..DMA_CYCLES_PER_BYTE {
+            self.run_cpu();
+        }
+        self.stolen_cycles += self.cpu.get_cycle() - start;
+    }
+
+    fn update_irq_line(&mut self, cycle: u64) {
+        let asserted = self.cpu.get_memory().irq_asserted();
+        if asserted == self.irq_asserted {
+            return;
+        }
+
+        if asserted {
+            self.cpu.schedule_irq(cycle);
+        } else {
+            self.cpu.set_irq_line(false);
+        }
+        self.irq_asserted = asserted;
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,133 @@
+use std::{fs, ops::RangeInclusive, path::Path};
+
+use super::System;
+use crate::{
+    consts::{Byte, Word},
+    cpu::CPU,
+    device::Device,
+    memory::{memory_map::MemoryMap, rom::Rom, Ram64k},
+};
+
+// the CPU the machine is built around
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub enum CpuModel {
+    #[default]
+    Nmos,
+    Ricoh2A03,
+    Cmos,
+    RockwellCmos,
+    WdcCmos,
+    Csg65Ce02,
+}
+
+// composes a single-board machine piece by piece, e.g.
+// `SystemBuilder::new().ram(0x0000..=0x3FFF).rom("fw.bin", 0x8000).device(via, 0x6000..=0x600F)`.
+// Pieces are mapped in the order they are added, later ones taking precedence where they
+// overlap. A ROM image that cannot be loaded or a zero clock frequency is reported by `build`.
+pub struct SystemBuilder<'a> {
+    map: MemoryMap<'a>,
+    cpu: CpuModel,
+    clock_hz: Option<u64>,
+    error: Option<String>,
+}
+
+impl<'a> SystemBuilder<'a> {
+    pub fn new() -> Self {
+        return SystemBuilder {
+            map: MemoryMap::new(),
+            cpu: CpuModel::default(),
+            clock_hz: None,
+            error: None,
+        };
+    }
+
+    pub fn cpu(mut self, cpu: CpuModel) -> Self {
+        self.cpu = cpu;
+        return self;
+    }
+
+    pub fn clock_hz(mut self, clock_hz: u64) -> Self {
+        if clock_hz == 0 {
+            return self.fail("clock frequency cannot be 0".to_string());
+        }
+        self.clock_hz = Some(clock_hz);
+        return self;
+    }
+
+    pub fn ram(mut self, range: RangeInclusive<Word>) -> Self {
+        self.map.map(range, Ram64k::new());
+        return self;
+    }
+
+    // the image file at `path` from `start` on
+    pub fn rom(self, path: impl AsRef<Path>, start: Word) -> Self {
+        let path = path.as_ref();
+        return match fs::read(path) {
+            Ok(image) => self.rom_image(image, start),
+            Err(error) => self.fail(format!("cannot read ROM image {}: {error}", path.display())),
+        };
+    }
+
+    pub fn rom_image(mut self, image: Vec<Byte>, start: Word) -> Self {
+        if image.is_empty() {
+            return self.fail("ROM image is empty".to_string());
+        }
+        let end = start as usize + image.len() - 1;
+        if end > Word::MAX as usize {
+            return self.fail(format!(
+                "ROM image of {} bytes does not fit at ${start:04X}",
+                image.len()
+            ));
+        }
+
+        self.map.map(start..=end as Word, Rom::new(image));
+        return self;
+    }
+
+    pub fn device(mut self, device: impl Device + 'a, range: RangeInclusive<Word>) -> Self {
+        self.map.attach(range, device);
+        return self;
+    }
+
+    // the map as composed so far, for what the builder has no shorthand for, e.g. banking
+    pub fn get_memory_map_mut(&mut self) -> &mut MemoryMap<'a> {
+        return &mut self.map;
+    }
+
+    // the CPU comes out of reset with the vector from the composed map
+    pub fn build(self) -> Result<System<'a>, String> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+
+        let cpu = match self.cpu {
+            CpuModel::Nmos => CPU::new_nmos(self.map),
+            CpuModel::Ricoh2A03 => CPU::new_ricoh_2a03(self.map),
+            CpuModel::Cmos => CPU::new_cmos(self.map),
+            CpuModel::RockwellCmos => CPU::new_rockwell_cmos(self.map),
+            CpuModel::WdcCmos => CPU::new_wdc_cmos(self.map),
+            CpuModel::Csg65Ce02 => CPU::new_65ce02(self.map),
+        };
+        let mut system = System::new(cpu);
+        if let Some(clock_hz) = self.clock_hz {
+            system.set_clock_hz(clock_hz);
+        }
+
+        return Ok(system);
+    }
+
+    // keeps the first error, later pieces are still mapped but never built
+    fn fail(mut self, error: String) -> Self {
+        self.error.get_or_insert(error);
+        return self;
+    }
+}
+
+impl Default for SystemBuilder<'_> {
+    fn default() -> Self {
+        return SystemBuilder::new();
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,157 @@
+#[cfg(test)]
+mod composition {
+    use std::fs;
+
+    use crate::{
+        cpu::emulation_event::EmulationEvent,
+        device::via::Via,
+        memory::Bus,
+        system::builder::{CpuModel, SystemBuilder},
+    };
+
+    // at $8000: LDA #$FF; STA $6002; LDA #$55; STA $6000; STA $0200; BRK, with the reset
+    // vector pointing at it
+    fn firmware() -> Vec<u8> {
+        let mut rom = vec![0xEA; 0x8000];
+        let program = [
+            0xA9, 0xFF, 0x8D, 0x02, 0x60, 0xA9, 0x55, 0x8D, 0x00, 0x60, 0x8D, 0x00, 0x02, 0x00,
+        ];
+        rom[..program.len()].copy_from_slice(&program);
+        rom[0x7FFC] = 0x00;
+        rom[0x7FFD] = 0x80;
+
+        return rom;
+    }
+
+    #[test]
+    fn should_run_firmware_on_composed_machine() {
+        let mut system = SystemBuilder::new()
+            .ram(0x0000..=0x3FFF)
+            .rom_image(firmware(), 0x8000)
+            .device(Via::new(), 0x6000..=0x600F)
+            .build()
+            .unwrap();
+
+        let event = system.execute_until_break();
+
+        assert!(matches!(event, EmulationEvent::Trap(_)));
+        let map = system.get_memory_map_mut();
+        assert_eq!(map.read(0x0200), 0x55);
+        assert_eq!(map.read(0x6000), 0x55);
+    }
+
+    #[test]
+    fn should_load_rom_from_file() {
+        let path = std::env::temp_dir().join(format!("emu65-builder-{}.bin", std::process::id()));
+        fs::write(&path, firmware()).unwrap();
+
+        let system = SystemBuilder::new()
+            .ram(0x0000..=0x3FFF)
+            .rom(&path, 0x8000)
+            .build()
+            .unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(system.get_cpu().get_program_counter(), 0x8000);
+    }
+
+    // STZ $0200 only exists on the CMOS parts
+    #[test]
+    fn should_build_selected_cpu() {
+        let mut rom = vec![0xEA; 0x100];
+        rom[..4].copy_from_slice(&[0x9C, 0x00, 0x02, 0x00]);
+        rom[0xFC] = 0x00;
+        rom[0xFD] = 0xFF;
+        let mut system = SystemBuilder::new()
+            .cpu(CpuModel::WdcCmos)
+            .ram(0x0000..=0x3FFF)
+            .rom_image(rom, 0xFF00)
+            .build()
+            .unwrap();
+        system.get_memory_map_mut().write(0x0200, 0x42);
+
+        system.execute_until_break();
+
+        assert_eq!(system.get_memory_map_mut().read(0x0200), 0x00);
+    }
+
+    #[test]
+    fn should_give_later_pieces_precedence() {
+        let mut system = SystemBuilder::new()
+            .ram(0x0000..=0xFFFF)
+            .rom_image(vec![0x42; 0x100], 0xFF00)
+            .build()
+            .unwrap();
+        let map = system.get_memory_map_mut();
+
+        map.write(0xFEFF, 0x43);
+        map.write(0xFF00, 0x43);
+
+        assert_eq!(map.read(0xFEFF), 0x43);
+        assert_eq!(map.read(0xFF00), 0x42);
+    }
+
+    #[test]
+    fn should_set_clock_of_system() {
+        let system = SystemBuilder::new()
+            .rom_image(firmware(), 0x8000)
+            .clock_hz(1_000_000)
+            .build()
+            .unwrap();
+
+        assert_eq!(system.get_clock_hz(), Some(1_000_000));
+    }
+}
+
+#[cfg(test)]
+mod errors {
+    use crate::system::builder::SystemBuilder;
+
+    #[test]
+    fn should_report_unreadable_rom_file() {
+        let path = std::env::temp_dir().join("emu65-builder-missing.bin");
+
+        let error = SystemBuilder::new()
+            .rom(&path, 0x8000)
+            .build()
+            .err()
+            .unwrap();
+
+        assert!(error.starts_with("cannot read ROM image"), "{error}");
+    }
+
+    #[test]
+    fn should_report_rom_past_end_of_address_space() {
+        let error = SystemBuilder::new()
+            .rom_image(vec![0; 0x200], 0xFF00)
+            .build()
+            .err()
+            .unwrap();
+
+        assert_eq!(error, "ROM image of 512 bytes does not fit at $FF00");
+    }
+
+    #[test]
+    fn should_keep_first_error() {
+        let error = SystemBuilder::new()
+            .rom_image(vec![], 0x8000)
+            .rom_image(vec![0; 0x200], 0xFF00)
+            .build()
+            .err()
+            .unwrap();
+
+        assert_eq!(error, "ROM image is empty");
+    }
+
+    #[test]
+    fn should_report_zero_clock_frequency() {
+        let error = SystemBuilder::new()
+            .ram(0x0000..=0xFFFF)
+            .clock_hz(0)
+            .build()
+            .err()
+            .unwrap();
+
+        assert_eq!(error, "clock frequency cannot be 0");
+    }
+}
@@ -0,0 +1,160 @@
+#[cfg(test)]
+mod step {
+    use crate::{
+        consts::{Byte, Word},
+        cpu::CPU,
+        device::Device,
+        memory::{memory_map::MemoryMap, Bus, Ram64k},
+        system::System,
+    };
+
+    // counts down once per cycle from the value written to register 0 and holds IRQ once
+    // expired, until register 1 is read
+    #[derive(Default)]
+    struct Timer {
+        counter: u64,
+        expired: bool,
+    }
+
+    impl Bus for Timer {
+        fn read(&mut self, addr: Word) -> Byte {
+            if addr == 1 {
+                let status = self.expired as Byte;
+                self.expired = false;
+                return status;
+            }
+
+            return self.counter as Byte;
+        }
+
+        fn write(&mut self, addr: Word, value: Byte) {
+            if addr == 0 {
+                self.counter = value as u64;
+            }
+        }
+    }
+
+    impl Device for Timer {
+        fn tick(&mut self, cycles: u64) {
+            if self.counter == 0 {
+                return;
+            }
+            self.counter = self.counter.saturating_sub(cycles);
+            self.expired = self.counter == 0;
+        }
+
+        fn irq_asserted(&self) -> bool {
+            return self.expired;
+        }
+    }
+
+    // main: LDA #timeout; STA $DC00; CLI; loop: JMP loop
+    // handler at $0300: LDA $DC01; INX; RTI
+    fn system(timeout: Byte) -> System<'static> {
+        let mut ram = Ram64k::new();
+        ram.load(
+            &[0xA9, timeout, 0x8D, 0x00, 0xDC, 0x58, 0x4C, 0x06, 0x02],
+            0x0200,
+        );
+        ram.load(&[0xAD, 0x01, 0xDC, 0xE8, 0x40], 0x0300);
+        ram.load(&[0x00, 0x03], 0xFFFE);
+        let mut map = MemoryMap::new();
+        map.map(0x0000..=0xFFFF, ram);
+        map.attach(0xDC00..=0xDC0F, Timer::default());
+        let mut cpu = CPU::with_memory(map);
+        cpu.set_program_counter(0x0200);
+
+        return System::new(cpu);
+    }
+
+    #[test]
+    fn should_clock_devices_for_every_cycle_cpu_ran() {
+        let mut system = system(100);
+        system.step();
+        system.step();
+        let start = system.get_cpu().get_cycle();
+
+        (0..5).for_each(|_| {
+            system.step();
+        });
+
+        let elapsed = system.get_cpu().get_cycle() - start;
+        // the 4 cycles of the STA starting the timer are clocked once it has been written
+        assert_eq!(
+            system.get_memory_map_mut().read(0xDC00) as u64,
+            100 - 4 - elapsed
+        );
+    }
+
+    #[test]
+    fn should_enter_handler_when_device_raises_irq() {
+        let mut system = system(20);
+
+        system.execute_cycles(40);
+
+        assert_eq!(system.get_cpu().get_index_register_x(), 1);
+        assert!(!system.get_cpu().get_irq_line());
+    }
+
+    // CLI; loop: JMP loop, with the timer started right before CLI
+    fn cycles_until_handler(timeout: Byte) -> u64 {
+        let mut ram = Ram64k::new();
+        ram.load(&[0x58, 0x4C, 0x01, 0x02], 0x0200);
+        ram.load(&[0x00, 0x03], 0xFFFE);
+        let mut map = MemoryMap::new();
+        map.map(0x0000..=0xFFFF, ram);
+        map.attach(0xDC00..=0xDC0F, Timer::default());
+        map.write(0xDC00, timeout);
+        let mut cpu = CPU::with_memory(map);
+        cpu.set_program_counter(0x0200);
+        let mut system = System::new(cpu);
+        let start = system.get_cpu().get_cycle();
+
+        while system.get_cpu().get_program_counter() != 0x0300 {
+            system.step();
+        }
+
+        return system.get_cpu().get_cycle() - start;
+    }
+
+    #[test]
+    fn should_take_interrupt_raised_before_last_cycle_of_instruction() {
+        // CLI takes 2 cycles and every JMP 3, the timer expires on the second-to-last cycle of
+        // the second JMP, followed by the 7 cycles of the interrupt sequence
+        assert_eq!(cycles_until_handler(7), 8 + 7);
+    }
+
+    #[test]
+    fn should_delay_interrupt_raised_on_last_cycle_of_instruction() {
+        // the lines were already polled when the second JMP ends, the third one runs first
+        assert_eq!(cycles_until_handler(8), 11 + 7);
+    }
+}
+
+#[cfg(test)]
+mod clock {
+    use std::time::Duration;
+
+    use crate::{
+        cpu::CPU,
+        memory::{memory_map::MemoryMap, Ram64k},
+        system::System,
+    };
+
+    #[test]
+    fn should_convert_cycles_to_emulated_time_once_clock_is_known() {
+        let mut map = MemoryMap::new();
+        map.map(0x0000..=0xFFFF, Ram64k::new());
+        let mut system = System::new(CPU::with_memory(map));
+        assert_eq!(system.get_elapsed(), None);
+
+        system.set_clock_hz(2_000_000);
+        system.execute_cycles(100);
+
+        let cycles = system.get_cpu().get_cycle();
+        assert_eq!(
+            system.get_elapsed(),
+            Some(Duration::from_nanos(cycles * 500))
+        );
+    }
+}
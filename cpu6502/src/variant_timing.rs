@@ -0,0 +1,155 @@
+use std::fmt;
+
+use crate::{
+    consts::{Byte, Word},
+    cpu::{
+        program_generator::TraceEntry,
+        state_diff::{diff, StateDiff},
+        CPU,
+    },
+    determinism::CpuSetup,
+    memory::Bus,
+};
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct InstructionDifference {
+    pub step: usize,
+    pub address: Word,
+    pub opcode: Byte,
+    pub nmos_cycles: u64,
+    pub cmos_cycles: u64,
+    pub nmos: TraceEntry,
+    pub cmos: TraceEntry,
+}
+
+impl InstructionDifference {
+    pub fn cycles_differ(&self) -> bool {
+        return self.nmos_cycles != self.cmos_cycles;
+    }
+
+    pub fn results_differ(&self) -> bool {
+        let nmos = TraceEntry {
+            cycle: 0,
+            ..self.nmos
+        };
+        let cmos = TraceEntry {
+            cycle: 0,
+            ..self.cmos
+        };
+        return nmos != cmos;
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct VariantReport {
+    pub steps: usize,
+    pub nmos_cycles: u64,
+    pub cmos_cycles: u64,
+    pub differences: Vec<InstructionDifference>,
+    // step after which both variants continued at different addresses; comparison stops there
+    pub diverged_at: Option<usize>,
+    // registers and memory of both variants when the comparison ended
+    pub final_state: StateDiff,
+}
+
+// runs the same program on an NMOS 6502 and a WDC 65C02, each with memory built by
+// `make_memory` and registers prepared by `setup`, stepping both in lockstep and recording
+// every instruction whose cycle count or resulting registers differ
+pub struct VariantComparison<M: Bus + 'static, F: Fn() -> M> {
+    make_memory: F,
+    setup: CpuSetup<M>,
+    steps: usize,
+}
+
+impl<M: Bus + 'static, F: Fn() -> M> VariantComparison<M, F> {
+    pub fn new(make_memory: F, setup: CpuSetup<M>, steps: usize) -> Self {
+        return VariantComparison {
+            make_memory,
+            setup,
+            steps,
+        };
+    }
+
+    pub fn run(&self) -> VariantReport {
+        let mut nmos = CPU::new_nmos((self.make_memory)());
+        let mut cmos = CPU::new_wdc_cmos((self.make_memory)());
+        (self.setup)(&mut nmos);
+        (self.setup)(&mut cmos);
+        let nmos_start = nmos.get_cycle();
+        let cmos_start = cmos.get_cycle();
+
+        let mut differences = Vec::new();
+        let mut diverged_at = None;
+        let mut steps = 0;
+        while steps < self.steps {
+            let address = nmos.get_program_counter();
+            let opcode = nmos.get_memory_mut().peek(address);
+            let (nmos_before, cmos_before) = (nmos.get_cycle(), cmos.get_cycle());
+            let nmos_event = nmos.execute_next_instruction();
+            let cmos_event = cmos.execute_next_instruction();
+            steps += 1;
+
+            let difference = InstructionDifference {
+                step: steps - 1,
+                address,
+                opcode,
+                nmos_cycles: nmos.get_cycle() - nmos_before,
+                cmos_cycles: cmos.get_cycle() - cmos_before,
+                nmos: TraceEntry::capture(&nmos),
+                cmos: TraceEntry::capture(&cmos),
+            };
+            if difference.cycles_differ() || difference.results_differ() {
+                differences.push(difference);
+            }
+            if nmos.get_program_counter() != cmos.get_program_counter() {
+                diverged_at = Some(steps - 1);
+                break;
+            }
+            if nmos_event.is_some() || cmos_event.is_some() {
+                break;
+            }
+        }
+
+        return VariantReport {
+            steps,
+            nmos_cycles: nmos.get_cycle() - nmos_start,
+            cmos_cycles: cmos.get_cycle() - cmos_start,
+            differences,
+            diverged_at,
+            final_state: diff(&nmos.snapshot(), &cmos.snapshot()),
+        };
+    }
+}
+
+impl fmt::Display for VariantReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} instructions: NMOS {} cycles, CMOS {} cycles",
+            self.steps, self.nmos_cycles, self.cmos_cycles
+        )?;
+        for difference in &self.differences {
+            write!(
+                f,
+                "#{} ${:04X} ${:02X}: NMOS {} cycles, CMOS {} cycles",
+                difference.step,
+                difference.address,
+                difference.opcode,
+                difference.nmos_cycles,
+                difference.cmos_cycles
+            )?;
+            if difference.results_differ() {
+                write!(f, ", results differ")?;
+            }
+            writeln!(f)?;
+        }
+        if let Some(step) = self.diverged_at {
+            writeln!(f, "execution diverged after #{step}")?;
+        }
+
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,58 @@
+#[cfg(test)]
+mod run {
+    use crate::{
+        cpu::CPU, determinism::CpuSetup, memory::VecMemory, variant_timing::VariantComparison,
+    };
+
+    fn setup() -> CpuSetup<VecMemory> {
+        return Box::new(|cpu: &mut CPU<VecMemory>| cpu.set_program_counter(0x0200));
+    }
+
+    #[test]
+    fn should_report_no_differences_for_portable_code() {
+        let make_memory = || {
+            let mut memory = VecMemory::new();
+            // LDA #$01; STA $10; INC $10; BRK
+            memory.insert(0x0200, &[0xA9, 0x01, 0x85, 0x10, 0xE6, 0x10, 0x00]);
+            return memory;
+        };
+        let comparison = VariantComparison::new(make_memory, setup(), 10);
+
+        let report = comparison.run();
+
+        assert_eq!(report.steps, 4);
+        assert!(report.diverged_at.is_none());
+        assert!(report
+            .differences
+            .iter()
+            .all(|difference| difference.opcode == 0x00)); // BRK clears D on CMOS only
+    }
+
+    #[test]
+    fn should_report_timing_and_result_of_indirect_jump_across_page() {
+        let make_memory = || {
+            let mut memory = VecMemory::new();
+            memory.insert(0x0200, &[0x6C, 0xFF, 0x10]); // JMP ($10FF)
+            memory.insert(0x10FF, &[0x00, 0x20]);
+            memory.insert(0x1000, &[0x30]);
+            return memory;
+        };
+        let comparison = VariantComparison::new(make_memory, setup(), 10);
+
+        let report = comparison.run();
+
+        assert_eq!(report.differences.len(), 1);
+        let difference = report.differences[0];
+        assert_eq!(difference.address, 0x0200);
+        assert_eq!((difference.nmos_cycles, difference.cmos_cycles), (5, 6));
+        assert_eq!(difference.nmos.program_counter, 0x3000);
+        assert_eq!(difference.cmos.program_counter, 0x2000);
+        assert_eq!(report.diverged_at, Some(0));
+        assert_eq!(
+            report.to_string(),
+            "1 instructions: NMOS 5 cycles, CMOS 6 cycles\n\
+             #0 $0200 $6C: NMOS 5 cycles, CMOS 6 cycles, results differ\n\
+             execution diverged after #0\n"
+        );
+    }
+}